@@ -3677,6 +3677,7 @@ impl AcpThreadView {
                 offset: point(px(1.), px(-1.)),
                 blur_radius: px(3.),
                 spread_radius: px(0.),
+                inset: false,
             }])
             .when(!plan.is_empty(), |this| {
                 this.child(self.render_plan_summary(plan, window, cx))
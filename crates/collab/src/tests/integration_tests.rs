@@ -19,8 +19,8 @@ use git::{
     status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode},
 };
 use gpui::{
-    App, BackgroundExecutor, Entity, Modifiers, MouseButton, MouseDownEvent, TestAppContext,
-    UpdateGlobal, px, size,
+    App, BackgroundExecutor, Entity, Modifiers, MouseButton, MouseDownEvent, PointerKind,
+    TestAppContext, UpdateGlobal, px, size,
 };
 use language::{
     Diagnostic, DiagnosticEntry, DiagnosticSourceKind, FakeLspAdapter, Language, LanguageConfig,
@@ -6521,6 +6521,7 @@ async fn test_right_click_menu_behind_collab_panel(cx: &mut TestAppContext) {
         modifiers: Modifiers::default(),
         click_count: 1,
         first_mouse: false,
+        pointer_kind: PointerKind::Mouse,
     });
 
     // regression test that the right click menu for tabs does not open.
@@ -6533,6 +6534,7 @@ async fn test_right_click_menu_behind_collab_panel(cx: &mut TestAppContext) {
         modifiers: Modifiers::default(),
         click_count: 1,
         first_mouse: false,
+        pointer_kind: PointerKind::Mouse,
     });
     assert!(cx.debug_bounds("MENU_ITEM-Close").is_some());
 }
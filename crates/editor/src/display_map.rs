@@ -712,6 +712,7 @@ impl<'a> HighlightedChunk<'a> {
                             color: Some(editor_style.status.hint),
                             thickness: px(1.),
                             wavy: false,
+                            ..Default::default()
                         }),
                         ..Default::default()
                     };
@@ -734,6 +735,7 @@ impl<'a> HighlightedChunk<'a> {
                             color: Some(editor_style.status.hint),
                             thickness: px(1.),
                             wavy: false,
+                            ..Default::default()
                         }),
                         ..Default::default()
                     };
@@ -1043,6 +1045,7 @@ impl DisplaySnapshot {
                                 color: Some(diagnostic_color),
                                 thickness: 1.0.into(),
                                 wavy: true,
+                                ..Default::default()
                             }
                         }),
                     ..Default::default()
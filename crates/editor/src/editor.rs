@@ -9163,6 +9163,7 @@ impl Editor {
                         offset: point(px(1.), px(1.)),
                         blur_radius: px(2.),
                         spread_radius: px(0.),
+                        inset: false,
                     }])
                     .bg(Editor::edit_prediction_line_popover_bg_color(cx))
                     .border(BORDER_WIDTH)
@@ -22288,6 +22289,7 @@ impl Editor {
                         thickness: px(1.),
                         color: None,
                         wavy: false,
+                        ..Default::default()
                     }),
                     ..Default::default()
                 },
@@ -24633,6 +24635,7 @@ impl EntityInputHandler for Editor {
                             thickness: px(1.),
                             color: None,
                             wavy: false,
+                            ..Default::default()
                         }),
                         ..Default::default()
                     },
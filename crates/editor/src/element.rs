@@ -5925,7 +5925,7 @@ impl EditorElement {
                     );
                     let mut quad = fill(Bounds { origin, size }, highlight.background);
                     if let Some(border_color) = highlight.border {
-                        quad.border_color = border_color;
+                        quad.border_colors = Edges::all(border_color);
                         quad.border_widths = edges
                     }
                     window.paint_quad(quad);
@@ -7554,6 +7554,14 @@ impl EditorElement {
                                     point(lines.x * max_glyph_advance, lines.y * line_height);
                                 (pixels, None)
                             }
+
+                            gpui::ScrollDelta::Page(_) => (
+                                delta.pixel_delta_in_page(
+                                    line_height,
+                                    position_map.text_hitbox.size,
+                                ),
+                                None,
+                            ),
                         };
 
                         let current_scroll_position = position_map.snapshot.scroll_position();
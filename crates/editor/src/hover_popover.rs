@@ -639,6 +639,7 @@ pub fn hover_markdown_style(window: &Window, cx: &App) -> MarkdownStyle {
                 thickness: px(1.),
                 color: Some(cx.theme().colors().editor_foreground),
                 wavy: false,
+                ..Default::default()
             }),
             ..Default::default()
         },
@@ -688,6 +689,7 @@ pub fn diagnostics_markdown_style(window: &Window, cx: &App) -> MarkdownStyle {
                 thickness: px(1.),
                 color: Some(cx.theme().colors().editor_foreground),
                 wavy: false,
+                ..Default::default()
             }),
             ..Default::default()
         },
@@ -128,17 +128,25 @@ mod macos {
             "Uniforms".into(),
             "AtlasTile".into(),
             "PathRasterizationInputIndex".into(),
+            "ClipRegionInputIndex".into(),
+            "ClipRegion".into(),
+            "ClipRegionKind".into(),
             "PathVertex_ScaledPixels".into(),
             "PathRasterizationVertex".into(),
             "ShadowInputIndex".into(),
             "Shadow".into(),
+            "BackdropInputIndex".into(),
+            "Backdrop".into(),
+            "OverlayInputIndex".into(),
             "QuadInputIndex".into(),
             "Underline".into(),
             "UnderlineInputIndex".into(),
             "Quad".into(),
             "BorderStyle".into(),
+            "BlendMode".into(),
             "SpriteInputIndex".into(),
             "MonochromeSprite".into(),
+            "GradientSprite".into(),
             "PolychromeSprite".into(),
             "PathSprite".into(),
             "SurfaceInputIndex".into(),
@@ -291,10 +299,14 @@ mod windows {
         let modules = [
             "quad",
             "shadow",
+            "backdrop_horizontal_blur",
+            "backdrop_vertical_blur",
+            "overlay",
             "path_rasterization",
             "path_sprite",
             "underline",
             "monochrome_sprite",
+            "gradient_sprite",
             "polychrome_sprite",
         ];
 
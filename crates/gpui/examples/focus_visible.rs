@@ -20,7 +20,7 @@ impl Example {
             ),
             (
                 cx.focus_handle().tab_index(2).tab_stop(true),
-                "Button with .focus_visible() - only shows border with keyboard",
+                "Button with .focus_visible() - only shows outline with keyboard",
             ),
             (
                 cx.focus_handle().tab_index(3).tab_stop(true),
@@ -147,11 +147,13 @@ impl Render for Example {
                                 button_base("button2", self.items[1].1)
                                     .track_focus(&self.items[1].0)
                                     .focus_visible(|style| {
-                                        style.border_4().border_color(gpui::rgb(0x10b981))
+                                        style
+                                            .outline(px(2.), gpui::rgb(0x10b981))
+                                            .outline_offset(px(2.))
                                     })
                                     .on_click(cx.listener(|this, _, _, cx| {
                                         this.message =
-                                            "Clicked button 2 - no border! Try Tab instead.".into();
+                                            "Clicked button 2 - no outline! Try Tab instead.".into();
                                         cx.notify();
                                     })),
                             ),
@@ -177,11 +179,13 @@ impl Render for Example {
                                         style.border_4().border_color(gpui::rgb(0xfbbf24))
                                     })
                                     .focus_visible(|style| {
-                                        style.border_4().border_color(gpui::rgb(0x10b981))
+                                        style
+                                            .outline(px(2.), gpui::rgb(0x10b981))
+                                            .outline_offset(px(2.))
                                     })
                                     .on_click(cx.listener(|this, _, _, cx| {
                                         this.message =
-                                            "Clicked button 3 - yellow border. Tab shows green!"
+                                            "Clicked button 3 - yellow border. Tab shows green outline!"
                                                 .into();
                                         cx.notify();
                                     })),
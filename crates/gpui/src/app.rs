@@ -14,7 +14,7 @@ use anyhow::{Context as _, Result, anyhow};
 use derive_more::{Deref, DerefMut};
 use futures::{
     Future, FutureExt,
-    channel::oneshot,
+    channel::{mpsc, oneshot},
     future::{LocalBoxFuture, Shared},
 };
 use itertools::Itertools;
@@ -35,13 +35,15 @@ use util::{ResultExt, debug_panic};
 use crate::InspectorElementRegistry;
 use crate::{
     Action, ActionBuildError, ActionRegistry, Any, AnyView, AnyWindowHandle, AppContext, Asset,
-    AssetSource, BackgroundExecutor, Bounds, ClipboardItem, CursorStyle, DispatchPhase, DisplayId,
-    EventEmitter, FocusHandle, FocusMap, ForegroundExecutor, Global, KeyBinding, KeyContext,
-    Keymap, Keystroke, LayoutId, Menu, MenuItem, OwnedMenu, PathPromptOptions, Pixels, Platform,
-    PlatformDisplay, PlatformKeyboardLayout, PlatformKeyboardMapper, Point, PromptBuilder,
+    AssetSource, BackgroundExecutor, Bounds, CachedAsset, ClipboardItem, CursorStyle, DevicePixels,
+    DispatchPhase, DisplayId, EventEmitter, FocusHandle, FocusMap, ForegroundExecutor, Global,
+    Hsla, KeyBinding, KeyContext, Keymap, Keystroke, LayoutId, MAX_UNUSED_ASSET_LOAD_FRAMES, Menu,
+    MenuItem, OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay,
+    PlatformKeyboardLayout, PlatformKeyboardMapper, Point, ProgressSink, PromptBuilder,
     PromptButton, PromptHandle, PromptLevel, Render, RenderImage, RenderablePromptHandle,
-    Reservation, ScreenCaptureSource, SharedString, SubscriberSet, Subscription, SvgRenderer, Task,
-    TextSystem, Window, WindowAppearance, WindowHandle, WindowId, WindowInvalidator,
+    Reservation, ScreenCaptureSource, SharedString, Size, SubscriberSet, Subscription, SvgRenderer,
+    Task, TextSystem, Window, WindowAppearance, WindowHandle, WindowId, WindowInvalidator,
+    cached_asset_loaded, cached_asset_size,
     colors::{Colors, GlobalColors},
     current_platform, hash, init_app_menus,
 };
@@ -153,11 +155,7 @@ impl Application {
 
     /// Assign
     pub fn with_assets(self, asset_source: impl AssetSource) -> Self {
-        let mut context_lock = self.0.borrow_mut();
-        let asset_source = Arc::new(asset_source);
-        context_lock.asset_source = asset_source.clone();
-        context_lock.svg_renderer = SvgRenderer::new(asset_source);
-        drop(context_lock);
+        self.0.borrow_mut().set_asset_source(asset_source);
         self
     }
 
@@ -564,8 +562,12 @@ pub struct App {
     pub(crate) active_drag: Option<AnyDrag>,
     pub(crate) background_executor: BackgroundExecutor,
     pub(crate) foreground_executor: ForegroundExecutor,
-    pub(crate) loading_assets: FxHashMap<(TypeId, u64), Box<dyn Any>>,
+    pub(crate) loading_assets: FxHashMap<(TypeId, u64), CachedAsset>,
+    asset_budgets: FxHashMap<TypeId, usize>,
+    asset_fetch_count: u64,
+    asset_frame_count: u64,
     asset_source: Arc<dyn AssetSource>,
+    named_asset_sources: FxHashMap<SharedString, Arc<dyn AssetSource>>,
     pub(crate) svg_renderer: SvgRenderer,
     http_client: Arc<dyn HttpClient>,
     pub(crate) globals_by_type: FxHashMap<TypeId, Box<dyn Any>>,
@@ -643,7 +645,11 @@ impl App {
                 foreground_executor,
                 svg_renderer: SvgRenderer::new(asset_source.clone()),
                 loading_assets: Default::default(),
+                asset_budgets: FxHashMap::default(),
+                asset_fetch_count: 0,
+                asset_frame_count: 0,
                 asset_source,
+                named_asset_sources: FxHashMap::default(),
                 http_client,
                 globals_by_type: FxHashMap::default(),
                 entities,
@@ -1204,6 +1210,20 @@ impl App {
         self.svg_renderer.clone()
     }
 
+    /// Rasterizes an SVG on the background executor, without a window, into an RGBA image with
+    /// straight alpha. See [`SvgRenderer::rasterize`].
+    pub fn rasterize_svg(
+        &self,
+        bytes: impl Into<Arc<[u8]>>,
+        size: Size<DevicePixels>,
+        color: Option<Hsla>,
+    ) -> Task<Result<Arc<RenderImage>>> {
+        let svg_renderer = self.svg_renderer();
+        let bytes = bytes.into();
+        self.background_executor
+            .spawn(async move { svg_renderer.rasterize(&bytes, size, color) })
+    }
+
     pub(crate) fn push_effect(&mut self, effect: Effect) {
         match &effect {
             Effect::Notify { emitter } => {
@@ -1479,6 +1499,33 @@ impl App {
         &self.asset_source
     }
 
+    /// Replace the application's asset source, for example with a [`crate::CompositeAssetSource`]
+    /// that consults a directory of user overrides before the embedded assets. Assets that were
+    /// already loaded from the previous source aren't reloaded.
+    pub fn set_asset_source(&mut self, asset_source: impl AssetSource) {
+        let asset_source: Arc<dyn AssetSource> = Arc::new(asset_source);
+        self.svg_renderer = SvgRenderer::new(asset_source.clone());
+        self.asset_source = asset_source;
+    }
+
+    /// Register a secondary asset source under the given name, such as a directory of user themes,
+    /// replacing any source previously registered with that name. Assets can be loaded from it
+    /// with [`crate::Svg::path_in`]. Assets that were already loaded from a replaced source aren't
+    /// reloaded.
+    pub fn register_asset_source(
+        &mut self,
+        name: impl Into<SharedString>,
+        asset_source: impl AssetSource,
+    ) {
+        self.named_asset_sources
+            .insert(name.into(), Arc::new(asset_source));
+    }
+
+    /// The asset source registered with [`App::register_asset_source`] under the given name.
+    pub fn named_asset_source(&self, name: &str) -> Option<&Arc<dyn AssetSource>> {
+        self.named_asset_sources.get(name)
+    }
+
     /// Accessor for the text system.
     pub fn text_system(&self) -> &Arc<TextSystem> {
         &self.text_system
@@ -2031,33 +2078,189 @@ impl App {
         self.prompt_builder = Some(PromptBuilder::Default);
     }
 
-    /// Remove an asset from GPUI's cache
+    /// Remove an asset from GPUI's cache. If the asset is still loading, the load is canceled
+    /// unless something other than GPUI is holding its task. The next call to
+    /// [`Window::use_asset`] for the source loads the asset again.
     pub fn remove_asset<A: Asset>(&mut self, source: &A::Source) {
         let asset_id = (TypeId::of::<A>(), hash(source));
         self.loading_assets.remove(&asset_id);
     }
 
+    /// Remove every asset of the given type from GPUI's cache, like [`App::remove_asset`].
+    pub fn clear_assets<A: Asset>(&mut self) {
+        let asset_type = TypeId::of::<A>();
+        self.loading_assets
+            .retain(|(cached_type, _), _| *cached_type != asset_type);
+    }
+
+    /// Limit the memory held by the cached assets of the given type, as measured by
+    /// [`Asset::size_in_bytes`]. Once a window has drawn a frame, the least recently used assets
+    /// of the type are evicted until the rest fit in the budget, except for those used by the
+    /// last frame of any window.
+    pub fn set_asset_budget<A: Asset>(&mut self, budget_in_bytes: usize) {
+        self.asset_budgets
+            .insert(TypeId::of::<A>(), budget_in_bytes);
+    }
+
+    /// Called once a window has drawn a frame using the given assets. Cancels the loads of
+    /// cancellable assets that no frame has used for a while, and evicts the least recently used
+    /// assets of each type with a budget until the type fits in it. The assets used by the given
+    /// frame or by the last frame of any other window are kept.
+    pub(crate) fn finish_asset_frame(&mut self, drawn_assets: &[(TypeId, u64)]) {
+        self.asset_frame_count += 1;
+        if self.asset_budgets.is_empty()
+            && !self.loading_assets.values().any(|asset| asset.cancellable)
+        {
+            return;
+        }
+
+        let pinned_assets = drawn_assets
+            .iter()
+            .chain(
+                self.windows
+                    .values()
+                    .flatten()
+                    .flat_map(|window| &window.rendered_frame.used_assets),
+            )
+            .copied()
+            .collect::<FxHashSet<_>>();
+        self.cancel_unused_asset_loads(&pinned_assets);
+        self.evict_assets(&pinned_assets);
+    }
+
+    fn cancel_unused_asset_loads(&mut self, pinned_assets: &FxHashSet<(TypeId, u64)>) {
+        let frame_count = self.asset_frame_count;
+        self.loading_assets.retain(|asset_id, asset| {
+            // The frame count was already advanced past the frame that fetched the asset.
+            let unused_frames = frame_count.saturating_sub(asset.last_fetched_frame + 1);
+            !asset.cancellable
+                || pinned_assets.contains(asset_id)
+                || unused_frames <= MAX_UNUSED_ASSET_LOAD_FRAMES
+                || (asset.size_in_bytes)(asset.task.as_ref()).is_some()
+        });
+    }
+
+    fn evict_assets(&mut self, pinned_assets: &FxHashSet<(TypeId, u64)>) {
+        for (asset_type, budget) in &self.asset_budgets {
+            let mut total_size = 0;
+            let mut evictable_assets = Vec::new();
+            for (asset_id, asset) in &self.loading_assets {
+                if asset_id.0 != *asset_type {
+                    continue;
+                }
+                let Some(size) = (asset.size_in_bytes)(asset.task.as_ref()) else {
+                    continue;
+                };
+                total_size += size;
+                if size > 0 && !pinned_assets.contains(asset_id) {
+                    evictable_assets.push((asset.last_fetched, *asset_id, size));
+                }
+            }
+            if total_size <= *budget {
+                continue;
+            }
+
+            evictable_assets.sort_unstable_by_key(|(last_fetched, _, _)| *last_fetched);
+            for (_, asset_id, size) in evictable_assets {
+                if total_size <= *budget {
+                    break;
+                }
+                self.loading_assets.remove(&asset_id);
+                total_size -= size;
+            }
+        }
+    }
+
+    /// A receiver that's canceled once the asset is removed from the cache, or right away if
+    /// it isn't cached.
+    pub(crate) fn asset_removal<A: Asset>(&mut self, source: &A::Source) -> oneshot::Receiver<()> {
+        let asset_id = (TypeId::of::<A>(), hash(source));
+        let (sender, receiver) = oneshot::channel();
+        if let Some(asset) = self.loading_assets.get_mut(&asset_id) {
+            asset.removal_signals.push(sender);
+        }
+        receiver
+    }
+
+    /// Load an asset and block the main thread until it has finished loading. The asset is cached
+    /// like with [`Self::fetch_asset`]. In tests, this runs the test executor's tasks
+    /// deterministically until the load completes.
+    pub fn block_on_asset<A: Asset>(&mut self, source: &A::Source) -> A::Output {
+        let (task, _) = self.fetch_asset::<A>(source);
+        self.background_executor.block(task)
+    }
+
+    /// Waits until every asset that's currently loading has finished loading.
+    pub(crate) fn pending_asset_loads(&self) -> Vec<LocalBoxFuture<'static, ()>> {
+        self.loading_assets
+            .values()
+            .filter(|asset| (asset.size_in_bytes)(asset.task.as_ref()).is_none())
+            .map(|asset| (asset.loaded)(asset.task.as_ref()))
+            .collect()
+    }
+
+    /// The fraction of the asset that's loaded, as last reported by its load, allowing the load to
+    /// wake up the view polling it again.
+    pub(crate) fn asset_progress<A: Asset>(&self, source: &A::Source) -> Option<f32> {
+        let asset_id = (TypeId::of::<A>(), hash(source));
+        self.loading_assets.get(&asset_id)?.progress.read()
+    }
+
+    /// Takes the stream that wakes up when the asset's load reports progress, if no view took it
+    /// yet.
+    pub(crate) fn take_asset_progress_updates<A: Asset>(
+        &mut self,
+        source: &A::Source,
+    ) -> Option<mpsc::UnboundedReceiver<()>> {
+        let asset_id = (TypeId::of::<A>(), hash(source));
+        self.loading_assets
+            .get_mut(&asset_id)?
+            .progress_updates
+            .take()
+    }
+
     /// Asynchronously load an asset, if the asset hasn't finished loading this will return None.
     ///
     /// Note that the multiple calls to this method will only result in one `Asset::load` call at a
     /// time, and the results of this call will be cached
     pub fn fetch_asset<A: Asset>(&mut self, source: &A::Source) -> (Shared<Task<A::Output>>, bool) {
         let asset_id = (TypeId::of::<A>(), hash(source));
-        let mut is_first = false;
-        let task = self
-            .loading_assets
-            .remove(&asset_id)
-            .map(|boxed_task| *boxed_task.downcast::<Shared<Task<A::Output>>>().unwrap())
-            .unwrap_or_else(|| {
-                is_first = true;
-                let future = A::load(source.clone(), self);
-
-                self.background_executor().spawn(future).shared()
+        self.asset_fetch_count += 1;
+        if let Some(asset) = self.loading_assets.get_mut(&asset_id)
+            && let Some(task) = asset.task.downcast_ref::<Shared<Task<A::Output>>>()
+        {
+            let task = task.clone();
+            let expired = task.peek().is_some_and(|output| {
+                let now = self.background_executor.now();
+                let loaded_at = *asset.loaded_at.get_or_insert(now);
+                A::retry_after(output).is_some_and(|delay| now >= loaded_at + delay)
             });
+            if !expired {
+                asset.last_fetched = self.asset_fetch_count;
+                asset.last_fetched_frame = self.asset_frame_count;
+                return (task, false);
+            }
+        }
 
-        self.loading_assets.insert(asset_id, Box::new(task.clone()));
-
-        (task, is_first)
+        let (progress, progress_updates) = ProgressSink::new();
+        let future = A::load_with_progress(source.clone(), progress.clone(), self);
+        let task = self.background_executor().spawn(future).shared();
+        self.loading_assets.insert(
+            asset_id,
+            CachedAsset {
+                task: Box::new(task.clone()),
+                removal_signals: Vec::new(),
+                last_fetched: self.asset_fetch_count,
+                last_fetched_frame: self.asset_frame_count,
+                cancellable: A::cancellable(),
+                loaded_at: None,
+                progress,
+                progress_updates: Some(progress_updates),
+                size_in_bytes: cached_asset_size::<A>,
+                loaded: cached_asset_loaded::<A>,
+            },
+        );
+        (task, true)
     }
 
     /// Obtain a new [`FocusHandle`], which allows you to track and manipulate the keyboard focus
@@ -2456,9 +2659,21 @@ impl<'a, T> Drop for GpuiBorrow<'a, T> {
 
 #[cfg(test)]
 mod test {
-    use std::{cell::RefCell, rc::Rc};
-
-    use crate::{AppContext, TestAppContext};
+    use std::{
+        any::TypeId,
+        cell::RefCell,
+        rc::Rc,
+        sync::atomic::{AtomicUsize, Ordering::SeqCst},
+        time::Duration,
+    };
+
+    use futures::{Future, FutureExt, channel::oneshot};
+    use parking_lot::Mutex;
+
+    use crate::{
+        self as gpui, App, AppContext, Asset, AssetWithContext, AsyncApp, Context, Global,
+        IntoElement, ProgressSink, Render, SharedString, TestAppContext, Window, div, hash,
+    };
 
     #[test]
     fn test_gpui_borrow() {
@@ -2490,4 +2705,370 @@ mod test {
 
         assert_eq!(*observation_count.borrow(), 2);
     }
+
+    #[gpui::test]
+    fn test_remove_asset_cancels_load(cx: &mut TestAppContext) {
+        static LOADS: AtomicUsize = AtomicUsize::new(0);
+        static CANCELED_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+        struct CancelGuard;
+
+        impl Drop for CancelGuard {
+            fn drop(&mut self) {
+                CANCELED_LOADS.fetch_add(1, SeqCst);
+            }
+        }
+
+        enum PendingAsset {}
+
+        impl Asset for PendingAsset {
+            type Source = ();
+            type Output = ();
+
+            fn load(_: (), _: &mut App) -> impl Future<Output = ()> + Send + 'static {
+                LOADS.fetch_add(1, SeqCst);
+                async move {
+                    let _guard = CancelGuard;
+                    futures::future::pending::<()>().await
+                }
+            }
+        }
+
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+                window.use_asset::<PendingAsset>(&(), cx);
+                div()
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.run_until_parked();
+        assert_eq!(LOADS.load(SeqCst), 1);
+        assert_eq!(CANCELED_LOADS.load(SeqCst), 0);
+
+        cx.update(|_, cx| cx.remove_asset::<PendingAsset>(&()));
+        cx.run_until_parked();
+        assert_eq!(CANCELED_LOADS.load(SeqCst), 1);
+        assert_eq!(
+            LOADS.load(SeqCst),
+            2,
+            "the view should load the asset again"
+        );
+
+        cx.update(|_, cx| cx.clear_assets::<PendingAsset>());
+        cx.run_until_parked();
+        assert_eq!(CANCELED_LOADS.load(SeqCst), 2);
+    }
+
+    #[gpui::test]
+    fn test_unused_cancellable_asset_load_is_canceled(cx: &mut TestAppContext) {
+        static CANCELED_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+        struct CancelGuard;
+
+        impl Drop for CancelGuard {
+            fn drop(&mut self) {
+                CANCELED_LOADS.fetch_add(1, SeqCst);
+            }
+        }
+
+        enum PendingAsset {}
+
+        impl Asset for PendingAsset {
+            type Source = ();
+            type Output = ();
+
+            fn load(_: (), _: &mut App) -> impl Future<Output = ()> + Send + 'static {
+                async move {
+                    let _guard = CancelGuard;
+                    futures::future::pending::<()>().await
+                }
+            }
+
+            fn cancellable() -> bool {
+                true
+            }
+        }
+
+        struct TestView {
+            uses_asset: bool,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+                if self.uses_asset {
+                    window.use_asset::<PendingAsset>(&(), cx);
+                }
+                div()
+            }
+        }
+
+        let (view, cx) = cx.add_window_view(|_, _| TestView { uses_asset: true });
+        cx.run_until_parked();
+        for _ in 0..=MAX_UNUSED_ASSET_LOAD_FRAMES {
+            view.update(cx, |_, cx| cx.notify());
+            cx.run_until_parked();
+        }
+        assert_eq!(
+            CANCELED_LOADS.load(SeqCst),
+            0,
+            "loads used by every frame shouldn't be canceled"
+        );
+
+        view.update(cx, |view, cx| {
+            view.uses_asset = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        for _ in 1..MAX_UNUSED_ASSET_LOAD_FRAMES {
+            view.update(cx, |_, cx| cx.notify());
+            cx.run_until_parked();
+        }
+        assert_eq!(CANCELED_LOADS.load(SeqCst), 0);
+
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert_eq!(CANCELED_LOADS.load(SeqCst), 1);
+    }
+
+    #[gpui::test]
+    fn test_asset_budget_evicts_least_recently_used(cx: &mut TestAppContext) {
+        enum SizedAsset {}
+
+        impl Asset for SizedAsset {
+            type Source = u32;
+            type Output = u32;
+
+            fn load(source: u32, _: &mut App) -> impl Future<Output = u32> + Send + 'static {
+                async move { source }
+            }
+
+            fn size_in_bytes(_: &u32) -> usize {
+                10
+            }
+        }
+
+        struct TestView {
+            sources: Vec<u32>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+                for source in &self.sources {
+                    window.use_asset::<SizedAsset>(source, cx);
+                }
+                div()
+            }
+        }
+
+        fn is_cached(source: u32, cx: &App) -> bool {
+            cx.loading_assets
+                .contains_key(&(TypeId::of::<SizedAsset>(), hash(&source)))
+        }
+
+        cx.update(|cx| cx.set_asset_budget::<SizedAsset>(25));
+        let (view, cx) = cx.add_window_view(|_, _| TestView {
+            sources: vec![1, 2, 3],
+        });
+        cx.run_until_parked();
+        cx.update(|_, cx| {
+            assert!(
+                (1..=3).all(|source| is_cached(source, cx)),
+                "assets used by the last frame shouldn't be evicted"
+            );
+        });
+
+        view.update(cx, |view, cx| {
+            view.sources = vec![3];
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.update(|_, cx| {
+            assert!(!is_cached(1, cx));
+            assert!(is_cached(2, cx));
+            assert!(is_cached(3, cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_use_asset_with_progress(cx: &mut TestAppContext) {
+        static LOAD: Mutex<Option<(ProgressSink, oneshot::Sender<()>)>> = Mutex::new(None);
+
+        enum ProgressAsset {}
+
+        impl Asset for ProgressAsset {
+            type Source = ();
+            type Output = u32;
+
+            fn load(_: (), _: &mut App) -> impl Future<Output = u32> + Send + 'static {
+                async move { unreachable!("the asset should be loaded with progress") }
+            }
+
+            fn load_with_progress(
+                _: (),
+                progress: ProgressSink,
+                _: &mut App,
+            ) -> impl Future<Output = u32> + Send + 'static {
+                let (sender, receiver) = oneshot::channel();
+                *LOAD.lock() = Some((progress, sender));
+                async move {
+                    receiver.await.ok();
+                    7
+                }
+            }
+        }
+
+        struct TestView {
+            renders: Vec<(Option<u32>, Option<f32>)>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+                self.renders
+                    .push(window.use_asset_with_progress::<ProgressAsset>(&(), cx));
+                div()
+            }
+        }
+
+        let (view, cx) = cx.add_window_view(|_, _| TestView {
+            renders: Vec::new(),
+        });
+        cx.run_until_parked();
+        let Some((progress, loaded)) = LOAD.lock().take() else {
+            panic!("the asset should be loading");
+        };
+        progress.set(0.25);
+        progress.set(0.5);
+        cx.run_until_parked();
+        loaded.send(()).ok();
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            assert_eq!(
+                view.renders,
+                [(None, None), (None, Some(0.5)), (Some(7), Some(0.5))],
+                "progress updates should be redrawn once per frame"
+            );
+        });
+    }
+
+    enum DoublingAsset {}
+
+    impl Asset for DoublingAsset {
+        type Source = u32;
+        type Output = u32;
+
+        fn load(source: u32, cx: &mut App) -> impl Future<Output = u32> + Send + 'static {
+            let timer = cx.background_executor().timer(Duration::from_millis(10));
+            async move {
+                timer.await;
+                source * 2
+            }
+        }
+    }
+
+    #[gpui::test]
+    fn test_block_on_asset(cx: &mut TestAppContext) {
+        let output = cx.update(|cx| cx.block_on_asset::<DoublingAsset>(&21));
+        assert_eq!(output, 42);
+        cx.update(|cx| {
+            let (task, _) = cx.fetch_asset::<DoublingAsset>(&21);
+            assert_eq!(task.now_or_never(), Some(42), "the asset should be cached");
+        });
+    }
+
+    #[gpui::test]
+    fn test_flush_asset_loads(cx: &mut TestAppContext) {
+        struct TestView {
+            output: Option<u32>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+                self.output = window
+                    .use_asset::<DoublingAsset>(&21, cx)
+                    .and_then(|output| window.use_asset::<DoublingAsset>(&output, cx));
+                div()
+            }
+        }
+
+        let (view, cx) = cx.add_window_view(|_, _| TestView { output: None });
+        cx.flush_asset_loads();
+        view.read_with(cx, |view, _| assert_eq!(view.output, Some(84)));
+    }
+
+    #[gpui::test]
+    fn test_asset_with_context_reads_app_while_loading(cx: &mut TestAppContext) {
+        struct AuthToken(SharedString);
+
+        impl Global for AuthToken {}
+
+        enum AuthenticatedAsset {}
+
+        impl AssetWithContext for AuthenticatedAsset {
+            type Source = ();
+            type Output = Option<SharedString>;
+
+            fn load(_: (), cx: AsyncApp) -> impl Future<Output = Self::Output> + 'static {
+                async move { cx.read_global(|token: &AuthToken, _| token.0.clone()).ok() }
+            }
+        }
+
+        cx.update(|cx| cx.set_global(AuthToken("initial".into())));
+        let (task, _) = cx.update(|cx| cx.fetch_asset::<AuthenticatedAsset>(&()));
+        cx.update(|cx| cx.set_global(AuthToken("rotated".into())));
+        cx.run_until_parked();
+        assert_eq!(
+            task.now_or_never(),
+            Some(Some("rotated".into())),
+            "the load should read the app once it runs"
+        );
+    }
+
+    #[gpui::test]
+    fn test_failed_asset_is_retried(cx: &mut TestAppContext) {
+        static LOADS: AtomicUsize = AtomicUsize::new(0);
+
+        enum FlakyAsset {}
+
+        impl Asset for FlakyAsset {
+            type Source = ();
+            type Output = Result<u32, SharedString>;
+
+            fn load(_: (), _: &mut App) -> impl Future<Output = Self::Output> + Send + 'static {
+                let load_count = LOADS.fetch_add(1, SeqCst) + 1;
+                async move {
+                    if load_count == 1 {
+                        Err("network error".into())
+                    } else {
+                        Ok(7)
+                    }
+                }
+            }
+
+            fn retry_after(output: &Self::Output) -> Option<Duration> {
+                output.is_err().then_some(Duration::from_secs(1))
+            }
+        }
+
+        let fetch = |cx: &mut TestAppContext| {
+            let (task, _) = cx.update(|cx| cx.fetch_asset::<FlakyAsset>(&()));
+            cx.run_until_parked();
+            task.now_or_never()
+        };
+        assert_eq!(fetch(cx), Some(Err("network error".into())));
+        assert_eq!(fetch(cx), Some(Err("network error".into())));
+        assert_eq!(LOADS.load(SeqCst), 1);
+
+        cx.executor().advance_clock(Duration::from_secs(1));
+        assert_eq!(fetch(cx), Some(Ok(7)));
+        cx.executor().advance_clock(Duration::from_secs(10));
+        assert_eq!(fetch(cx), Some(Ok(7)));
+        assert_eq!(
+            LOADS.load(SeqCst),
+            2,
+            "successful loads shouldn't be retried"
+        );
+    }
 }
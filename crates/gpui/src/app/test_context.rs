@@ -1,9 +1,9 @@
 use crate::{
     Action, AnyView, AnyWindowHandle, App, AppCell, AppContext, AsyncApp, AvailableSpace,
-    BackgroundExecutor, BorrowAppContext, Bounds, Capslock, ClipboardItem, DrawPhase, Drawable,
-    Element, Empty, EventEmitter, ForegroundExecutor, Global, InputEvent, Keystroke, Modifiers,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels,
-    Platform, Point, Render, Result, Size, Task, TestDispatcher, TestPlatform,
+    BackgroundExecutor, BorrowAppContext, Bounds, Capslock, ClipboardItem, DevicePixels, DrawPhase,
+    Drawable, Element, Empty, EventEmitter, ForegroundExecutor, Global, InputEvent, Keystroke,
+    Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    Pixels, Platform, Point, PointerKind, Render, Result, Size, Task, TestDispatcher, TestPlatform,
     TestScreenCaptureSource, TestWindow, TextSystem, VisualContext, Window, WindowBounds,
     WindowHandle, WindowOptions,
 };
@@ -407,6 +407,21 @@ impl TestAppContext {
         self.background_executor.run_until_parked()
     }
 
+    /// Wait until all pending asset loads have finished, including the loads started by views
+    /// redrawn with the loaded assets, so that elements such as `img` and `svg` render with
+    /// their assets. Panics if a load can never finish.
+    pub fn flush_asset_loads(&mut self) {
+        loop {
+            self.run_until_parked();
+            let pending_loads = self.app.borrow().pending_asset_loads();
+            if pending_loads.is_empty() {
+                break;
+            }
+            self.background_executor
+                .block(futures::future::join_all(pending_loads));
+        }
+    }
+
     /// Simulate dispatching an action to the currently focused node in the window.
     pub fn dispatch_action<A>(&mut self, window: AnyWindowHandle, action: A)
     where
@@ -719,6 +734,29 @@ impl VisualTestContext {
         self.cx.test_window(self.window).0.lock().title.clone()
     }
 
+    /// Read the files most recently dragged out of the window (set by [`Window::start_drag_out`])
+    pub fn dragged_out_paths(&mut self) -> Option<Vec<PathBuf>> {
+        self.cx
+            .test_window(self.window)
+            .0
+            .lock()
+            .dragged_out_paths
+            .clone()
+    }
+
+    /// Read the size of the drag image most recently shown while dragging files out of the
+    /// window, and where the pointer was within it.
+    pub fn dragged_out_image_size(&mut self) -> Option<(Size<DevicePixels>, Point<DevicePixels>)> {
+        let window = self.cx.test_window(self.window);
+        let state = window.0.lock();
+        let drag_image = state.dragged_out_image.as_ref()?;
+        let size = Size {
+            width: DevicePixels(drag_image.image.width() as i32),
+            height: DevicePixels(drag_image.image.height() as i32),
+        };
+        Some((size, drag_image.cursor_offset))
+    }
+
     /// Simulate a sequence of keystrokes `cx.simulate_keystrokes("cmd-p escape")`
     /// Automatically runs until parked.
     pub fn simulate_keystrokes(&mut self, keystrokes: &str) {
@@ -758,6 +796,7 @@ impl VisualTestContext {
             button,
             click_count: 1,
             first_mouse: false,
+            pointer_kind: PointerKind::Mouse,
         })
     }
 
@@ -784,6 +823,7 @@ impl VisualTestContext {
             button: MouseButton::Left,
             click_count: 1,
             first_mouse: false,
+            pointer_kind: PointerKind::Mouse,
         });
         self.simulate_event(MouseUpEvent {
             position,
@@ -793,6 +833,32 @@ impl VisualTestContext {
         });
     }
 
+    /// Simulate a run of primary mouse clicks in quick succession at the given point, reported
+    /// with increasing click counts the way a platform reports double and triple clicks.
+    pub fn simulate_clicks(
+        &mut self,
+        position: Point<Pixels>,
+        click_count: usize,
+        modifiers: Modifiers,
+    ) {
+        for click_count in 1..=click_count {
+            self.simulate_event(MouseDownEvent {
+                position,
+                modifiers,
+                button: MouseButton::Left,
+                click_count,
+                first_mouse: false,
+                pointer_kind: PointerKind::Mouse,
+            });
+            self.simulate_event(MouseUpEvent {
+                position,
+                modifiers,
+                button: MouseButton::Left,
+                click_count,
+            });
+        }
+    }
+
     /// Simulate a modifiers changed event
     pub fn simulate_modifiers_change(&mut self, modifiers: Modifiers) {
         self.simulate_event(ModifiersChangedEvent {
@@ -814,6 +880,14 @@ impl VisualTestContext {
         self.simulate_window_resize(self.window, size)
     }
 
+    /// Simulates the platform asking the window for its next frame, which runs the callbacks
+    /// registered with [`Window::on_next_frame`], such as by [`Window::request_animation_frame`],
+    /// and redraws the window if it's dirty.
+    pub fn simulate_animation_frame(&mut self) {
+        self.test_window(self.window).simulate_request_frame();
+        self.background_executor.run_until_parked();
+    }
+
     /// debug_bounds returns the bounds of the element with the given selector.
     pub fn debug_bounds(&mut self, selector: &'static str) -> Option<Bounds<Pixels>> {
         self.update(|window, _| window.rendered_frame.debug_bounds.get(selector).copied())
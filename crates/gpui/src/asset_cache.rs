@@ -1,11 +1,18 @@
-use crate::{App, SharedString, SharedUri};
-use futures::{Future, TryFutureExt};
+use crate::{App, AsyncApp, SharedString, SharedUri, Task};
+use futures::{
+    Future, FutureExt, TryFutureExt,
+    channel::{mpsc, oneshot},
+    future::{LocalBoxFuture, Shared},
+};
 
+use std::any::Any;
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering::SeqCst};
+use std::time::{Duration, Instant};
 
 /// An enum representing
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -49,6 +56,186 @@ pub trait Asset: 'static {
         source: Self::Source,
         cx: &mut App,
     ) -> impl Future<Output = Self::Output> + Send + 'static;
+
+    /// Load the asset asynchronously, reporting the fraction of it that's loaded to the given
+    /// sink, which [`Window::use_asset_with_progress`](crate::Window::use_asset_with_progress)
+    /// returns. Calls [`Asset::load`] by default, which reports no progress.
+    fn load_with_progress(
+        source: Self::Source,
+        progress: ProgressSink,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        drop(progress);
+        Self::load(source, cx)
+    }
+
+    /// The number of bytes the loaded asset holds in memory, which counts towards the budget set
+    /// with [`App::set_asset_budget`]. Assets of size 0 are never evicted.
+    fn size_in_bytes(_output: &Self::Output) -> usize {
+        0
+    }
+
+    /// Whether GPUI may cancel loading the asset once no frame has used it for a few frames, such
+    /// as for images scrolled out of view. The asset is loaded again if it's used later. Loads
+    /// with side effects that must run to completion shouldn't be cancellable, which is the
+    /// default.
+    fn cancellable() -> bool {
+        false
+    }
+
+    /// How long the given loaded asset stays cached before it's loaded again the next time it's
+    /// used, for example to retry failed loads. By default, loaded assets stay cached until
+    /// they're removed or evicted.
+    fn retry_after(_output: &Self::Output) -> Option<Duration> {
+        None
+    }
+}
+
+/// A trait for assets whose asynchronous loading needs access to the app, for example to read
+/// entities, globals or settings that may change while the asset is loading. Every
+/// `AssetWithContext` is an [`Asset`], whose load runs on the main thread.
+///
+/// Since the load needs the main thread, these assets can't be loaded with
+/// [`App::block_on_asset`].
+pub trait AssetWithContext: 'static {
+    /// The source of the asset.
+    type Source: Clone + Hash + Send;
+
+    /// The loaded asset
+    type Output: Clone + Send;
+
+    /// Load the asset asynchronously, using the given handle to access the app.
+    fn load(source: Self::Source, cx: AsyncApp) -> impl Future<Output = Self::Output> + 'static;
+
+    /// See [`Asset::size_in_bytes`].
+    fn size_in_bytes(_output: &Self::Output) -> usize {
+        0
+    }
+
+    /// See [`Asset::cancellable`].
+    fn cancellable() -> bool {
+        false
+    }
+
+    /// See [`Asset::retry_after`].
+    fn retry_after(_output: &Self::Output) -> Option<Duration> {
+        None
+    }
+}
+
+impl<T: AssetWithContext> Asset for T {
+    type Source = T::Source;
+
+    type Output = T::Output;
+
+    fn load(
+        source: Self::Source,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        cx.spawn(async move |cx| T::load(source, cx.clone()).await)
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        T::size_in_bytes(output)
+    }
+
+    fn cancellable() -> bool {
+        T::cancellable()
+    }
+
+    fn retry_after(output: &Self::Output) -> Option<Duration> {
+        T::retry_after(output)
+    }
+}
+
+/// Receives the fraction of an asset that's loaded, see [`Asset::load_with_progress`].
+#[derive(Clone)]
+pub struct ProgressSink(Arc<ProgressState>);
+
+struct ProgressState {
+    /// The bits of the last reported fraction, or `u32::MAX` if none was reported.
+    fraction: AtomicU32,
+    /// Whether a progress update was sent since the last frame read the progress, so that the
+    /// view using the asset is only redrawn once per frame.
+    update_pending: AtomicBool,
+    updates: mpsc::UnboundedSender<()>,
+}
+
+impl ProgressSink {
+    pub(crate) fn new() -> (Self, mpsc::UnboundedReceiver<()>) {
+        let (updates, receiver) = mpsc::unbounded();
+        let state = ProgressState {
+            fraction: AtomicU32::new(u32::MAX),
+            update_pending: AtomicBool::new(false),
+            updates,
+        };
+        (Self(Arc::new(state)), receiver)
+    }
+
+    /// Report the fraction of the asset that's loaded, from 0 to 1.
+    pub fn set(&self, fraction: f32) {
+        let fraction = if fraction.is_nan() {
+            0.
+        } else {
+            fraction.clamp(0., 1.)
+        };
+        self.0.fraction.store(fraction.to_bits(), SeqCst);
+        if !self.0.update_pending.swap(true, SeqCst) {
+            self.0.updates.unbounded_send(()).ok();
+        }
+    }
+
+    /// The last reported fraction, allowing another update to be sent.
+    pub(crate) fn read(&self) -> Option<f32> {
+        self.0.update_pending.store(false, SeqCst);
+        let bits = self.0.fraction.load(SeqCst);
+        (bits != u32::MAX).then(|| f32::from_bits(bits))
+    }
+}
+
+/// How many frames a cancellable asset keeps loading once no frame uses it.
+pub(crate) const MAX_UNUSED_ASSET_LOAD_FRAMES: u64 = 3;
+
+/// An entry in GPUI's asset cache, whose task may still be loading.
+pub(crate) struct CachedAsset {
+    pub(crate) task: Box<dyn Any>,
+    /// Dropped along with the entry when the asset is removed, which signals the tasks waiting on
+    /// the load to let go of it, so the load is canceled.
+    pub(crate) removal_signals: Vec<oneshot::Sender<()>>,
+    /// When the asset was last fetched, in fetches since the app started.
+    pub(crate) last_fetched: u64,
+    /// When the asset was last fetched, in frames drawn since the app started.
+    pub(crate) last_fetched_frame: u64,
+    /// Whether the asset's load can be canceled, see [`Asset::cancellable`].
+    pub(crate) cancellable: bool,
+    /// When a fetch first found the asset loaded, from which [`Asset::retry_after`] counts.
+    pub(crate) loaded_at: Option<Instant>,
+    /// Receives the load's progress, see [`Asset::load_with_progress`].
+    pub(crate) progress: ProgressSink,
+    /// Wakes up when the load reports progress, until it's taken by the view polling it.
+    pub(crate) progress_updates: Option<mpsc::UnboundedReceiver<()>>,
+    /// Measures the loaded asset, or returns `None` while it's still loading.
+    pub(crate) size_in_bytes: fn(&dyn Any) -> Option<usize>,
+    /// Waits until the asset has finished loading.
+    pub(crate) loaded: fn(&dyn Any) -> LocalBoxFuture<'static, ()>,
+}
+
+/// Measures the loaded asset in a [`CachedAsset`] of the given type.
+pub(crate) fn cached_asset_size<A: Asset>(task: &dyn Any) -> Option<usize> {
+    task.downcast_ref::<Shared<Task<A::Output>>>()?
+        .peek()
+        .map(A::size_in_bytes)
+}
+
+/// Waits until the asset in a [`CachedAsset`] of the given type has finished loading.
+pub(crate) fn cached_asset_loaded<A: Asset>(task: &dyn Any) -> LocalBoxFuture<'static, ()> {
+    let task = task.downcast_ref::<Shared<Task<A::Output>>>().cloned();
+    async move {
+        if let Some(task) = task {
+            task.await;
+        }
+    }
+    .boxed_local()
 }
 
 /// An asset Loader which logs the [`Err`] variant of a [`Result`] during loading
@@ -74,6 +261,27 @@ where
         let load = T::load(source, cx);
         load.inspect_err(|e| log::error!("Failed to load asset: {}", e))
     }
+
+    fn load_with_progress(
+        source: Self::Source,
+        progress: ProgressSink,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        let load = T::load_with_progress(source, progress, cx);
+        load.inspect_err(|e| log::error!("Failed to load asset: {}", e))
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        T::size_in_bytes(output)
+    }
+
+    fn cancellable() -> bool {
+        T::cancellable()
+    }
+
+    fn retry_after(output: &Self::Output) -> Option<Duration> {
+        T::retry_after(output)
+    }
 }
 
 /// Use a quick, non-cryptographically secure hash function to get an identifier from data
@@ -1,4 +1,5 @@
 use crate::{DevicePixels, Pixels, Result, SharedString, Size, size};
+use collections::FxHashSet;
 use smallvec::SmallVec;
 
 use image::{Delay, Frame};
@@ -6,7 +7,10 @@ use std::{
     borrow::Cow,
     fmt,
     hash::Hash,
-    sync::atomic::{AtomicUsize, Ordering::SeqCst},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering::SeqCst},
+    },
 };
 
 /// A source of assets for this app to use.
@@ -28,6 +32,66 @@ impl AssetSource for () {
     }
 }
 
+/// An asset source that consults several sources in order, such as a directory of user overrides,
+/// then the assets provided by plugins, then the assets embedded in the app.
+pub struct CompositeAssetSource {
+    sources: Vec<Arc<dyn AssetSource>>,
+}
+
+impl CompositeAssetSource {
+    /// Create an asset source that loads each asset from the first of the given sources that has
+    /// it.
+    pub fn new(sources: Vec<Arc<dyn AssetSource>>) -> Self {
+        Self { sources }
+    }
+}
+
+impl AssetSource for CompositeAssetSource {
+    /// Loads the asset from the first source that has it. If none has it, returns the first error
+    /// a source returned, if any.
+    fn load(&self, path: &str) -> Result<Option<Cow<'static, [u8]>>> {
+        let mut first_error = None;
+        for source in &self.sources {
+            match source.load(path) {
+                Ok(Some(data)) => return Ok(Some(data)),
+                Ok(None) => {}
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        first_error.map_or(Ok(None), Err)
+    }
+
+    /// Lists the assets of every source, without duplicates. Returns the first error a source
+    /// returned only if no source could list its assets.
+    fn list(&self, path: &str) -> Result<Vec<SharedString>> {
+        let mut first_error = None;
+        let mut listed_any = false;
+        let mut seen = FxHashSet::default();
+        let mut assets = Vec::new();
+        for source in &self.sources {
+            match source.list(path) {
+                Ok(source_assets) => {
+                    listed_any = true;
+                    assets.extend(
+                        source_assets
+                            .into_iter()
+                            .filter(|asset| seen.insert(asset.clone())),
+                    );
+                }
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        match first_error {
+            Some(error) if !listed_any => Err(error),
+            _ => Ok(assets),
+        }
+    }
+}
+
 /// A unique identifier for the image cache
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ImageId(pub usize);
@@ -95,6 +159,14 @@ impl RenderImage {
     pub fn frame_count(&self) -> usize {
         self.data.len()
     }
+
+    /// Get the number of bytes held by all the frames of this image.
+    pub fn size_in_bytes(&self) -> usize {
+        self.data
+            .iter()
+            .map(|frame| frame.buffer().as_raw().len())
+            .sum()
+    }
 }
 
 impl fmt::Debug for RenderImage {
@@ -105,3 +177,76 @@ impl fmt::Debug for RenderImage {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    struct TestAssets(&'static [(&'static str, &'static str)]);
+
+    impl AssetSource for TestAssets {
+        fn load(&self, path: &str) -> Result<Option<Cow<'static, [u8]>>> {
+            Ok(self
+                .0
+                .iter()
+                .find(|(asset_path, _)| *asset_path == path)
+                .map(|(_, data)| Cow::Borrowed(data.as_bytes())))
+        }
+
+        fn list(&self, path: &str) -> Result<Vec<SharedString>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|(asset_path, _)| asset_path.starts_with(path))
+                .map(|(asset_path, _)| (*asset_path).into())
+                .collect())
+        }
+    }
+
+    struct FailingAssets;
+
+    impl AssetSource for FailingAssets {
+        fn load(&self, _path: &str) -> Result<Option<Cow<'static, [u8]>>> {
+            Err(anyhow!("failed to load"))
+        }
+
+        fn list(&self, _path: &str) -> Result<Vec<SharedString>> {
+            Err(anyhow!("failed to list"))
+        }
+    }
+
+    #[test]
+    fn test_composite_asset_source() {
+        let source = CompositeAssetSource::new(vec![
+            Arc::new(TestAssets(&[("icons/file.svg", "override")])),
+            Arc::new(FailingAssets),
+            Arc::new(TestAssets(&[
+                ("icons/file.svg", "default"),
+                ("icons/folder.svg", "default"),
+            ])),
+        ]);
+
+        let load = |path| {
+            source
+                .load(path)
+                .map(|data| data.map(|data| String::from_utf8_lossy(&data).into_owned()))
+        };
+        assert_eq!(load("icons/file.svg").ok(), Some(Some("override".into())));
+        assert_eq!(load("icons/folder.svg").ok(), Some(Some("default".into())));
+        assert!(
+            load("icons/missing.svg").is_err(),
+            "the error should be returned when no source has the asset"
+        );
+
+        assert_eq!(
+            source.list("icons/").ok(),
+            Some(vec!["icons/file.svg".into(), "icons/folder.svg".into()])
+        );
+        assert!(
+            CompositeAssetSource::new(vec![Arc::new(FailingAssets)])
+                .list("icons/")
+                .is_err()
+        );
+    }
+}
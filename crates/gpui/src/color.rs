@@ -10,6 +10,8 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+use crate::Point;
+
 /// Convert an RGB hex color code number to a color type
 pub fn rgb(hex: u32) -> Rgba {
     let [_, r, g, b] = hex.to_be_bytes().map(|b| (b as f32) / 255.0);
@@ -658,6 +660,8 @@ pub(crate) enum BackgroundTag {
     Solid = 0,
     LinearGradient = 1,
     PatternSlash = 2,
+    RadialGradient = 3,
+    ConicGradient = 4,
 }
 
 /// A color space for color interpolation.
@@ -684,7 +688,7 @@ impl Display for ColorSpace {
     }
 }
 
-/// A background color, which can be either a solid color or a linear gradient.
+/// A background color, which can be either a solid color or a linear, radial or conic gradient.
 #[derive(Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[repr(C)]
 pub struct Background {
@@ -693,8 +697,12 @@ pub struct Background {
     pub(crate) solid: Hsla,
     pub(crate) gradient_angle_or_pattern_height: f32,
     pub(crate) colors: [LinearColorStop; 2],
-    /// Padding for alignment for repr(C) layout.
-    pad: u32,
+    /// The center of radial and conic gradients, relative to the size of the painted bounds.
+    pub(crate) gradient_center_x: f32,
+    pub(crate) gradient_center_y: f32,
+    /// The radius of radial gradients, relative to the distance from their center to the
+    /// farthest corner of the painted bounds.
+    pub(crate) gradient_radius: f32,
 }
 
 impl std::fmt::Debug for Background {
@@ -715,6 +723,28 @@ impl std::fmt::Debug for Background {
                     self.solid, self.gradient_angle_or_pattern_height
                 )
             }
+            BackgroundTag::RadialGradient => {
+                write!(
+                    f,
+                    "RadialGradient(({}, {}), {}, {:?}, {:?})",
+                    self.gradient_center_x,
+                    self.gradient_center_y,
+                    self.gradient_radius,
+                    self.colors[0],
+                    self.colors[1]
+                )
+            }
+            BackgroundTag::ConicGradient => {
+                write!(
+                    f,
+                    "ConicGradient(({}, {}), {}, {:?}, {:?})",
+                    self.gradient_center_x,
+                    self.gradient_center_y,
+                    self.gradient_angle_or_pattern_height,
+                    self.colors[0],
+                    self.colors[1]
+                )
+            }
         }
     }
 }
@@ -728,7 +758,9 @@ impl Default for Background {
             color_space: ColorSpace::default(),
             gradient_angle_or_pattern_height: 0.0,
             colors: [LinearColorStop::default(), LinearColorStop::default()],
-            pad: 0,
+            gradient_center_x: 0.5,
+            gradient_center_y: 0.5,
+            gradient_radius: 1.0,
         }
     }
 }
@@ -775,6 +807,51 @@ pub fn linear_gradient(
     }
 }
 
+/// Creates a RadialGradient background color.
+///
+/// The `center` is relative to the painted bounds, so `point(0.5, 0.5)` is their middle. The
+/// `radius` is relative to the distance from the center to the farthest corner of the bounds,
+/// so `1.0` reaches every corner.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/gradient/radial-gradient>
+pub fn radial_gradient(
+    center: Point<f32>,
+    radius: f32,
+    from: impl Into<LinearColorStop>,
+    to: impl Into<LinearColorStop>,
+) -> Background {
+    Background {
+        tag: BackgroundTag::RadialGradient,
+        gradient_center_x: center.x,
+        gradient_center_y: center.y,
+        gradient_radius: radius,
+        colors: [from.into(), to.into()],
+        ..Default::default()
+    }
+}
+
+/// Creates a ConicGradient background color, which sweeps clockwise around a center.
+///
+/// The `center` is relative to the painted bounds, so `point(0.5, 0.5)` is their middle. The
+/// `start_angle` is in degrees, where `0.` starts the sweep at the top.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/gradient/conic-gradient>
+pub fn conic_gradient(
+    center: Point<f32>,
+    start_angle: f32,
+    from: impl Into<LinearColorStop>,
+    to: impl Into<LinearColorStop>,
+) -> Background {
+    Background {
+        tag: BackgroundTag::ConicGradient,
+        gradient_angle_or_pattern_height: start_angle,
+        gradient_center_x: center.x,
+        gradient_center_y: center.y,
+        colors: [from.into(), to.into()],
+        ..Default::default()
+    }
+}
+
 /// A color stop in a linear gradient.
 ///
 /// <https://developer.mozilla.org/en-US/docs/Web/CSS/gradient/linear-gradient#linear-color-stop>
@@ -831,7 +908,9 @@ impl Background {
     pub fn is_transparent(&self) -> bool {
         match self.tag {
             BackgroundTag::Solid => self.solid.is_transparent(),
-            BackgroundTag::LinearGradient => self.colors.iter().all(|c| c.color.is_transparent()),
+            BackgroundTag::LinearGradient
+            | BackgroundTag::RadialGradient
+            | BackgroundTag::ConicGradient => self.colors.iter().all(|c| c.color.is_transparent()),
             BackgroundTag::PatternSlash => self.solid.is_transparent(),
         }
     }
@@ -931,4 +1010,27 @@ mod tests {
         assert!(!background.is_transparent());
         assert!(background.opacity(0.0).is_transparent());
     }
+
+    #[test]
+    fn test_background_radial_and_conic_gradients() {
+        let from = linear_color_stop(rgba(0xff0099ff), 0.0);
+        let to = linear_color_stop(rgba(0x00ff99ff), 1.0);
+        let center = crate::point(0.25, 0.75);
+
+        let background = radial_gradient(center, 0.5, from, to);
+        assert_eq!(background.tag, BackgroundTag::RadialGradient);
+        assert_eq!(background.gradient_center_x, 0.25);
+        assert_eq!(background.gradient_center_y, 0.75);
+        assert_eq!(background.gradient_radius, 0.5);
+        assert_eq!(background.colors, [from, to]);
+        assert!(!background.is_transparent());
+        assert!(background.opacity(0.0).is_transparent());
+
+        let background = conic_gradient(center, 45.0, from, to);
+        assert_eq!(background.tag, BackgroundTag::ConicGradient);
+        assert_eq!(background.gradient_angle_or_pattern_height, 45.0);
+        assert_eq!(background.colors, [from, to]);
+        assert!(!background.is_transparent());
+        assert!(background.opacity(0.0).is_transparent());
+    }
 }
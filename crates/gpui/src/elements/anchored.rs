@@ -20,6 +20,8 @@ pub struct Anchored {
     anchor_position: Option<Point<Pixels>>,
     position_mode: AnchoredPositionMode,
     offset: Option<Point<Pixels>>,
+    margin: Edges<Pixels>,
+    placement_listener: Option<Box<dyn Fn(&AnchoredPlacement, &mut Window, &mut App)>>,
 }
 
 /// anchored gives you an element that will avoid overflowing the window bounds.
@@ -32,6 +34,8 @@ pub fn anchored() -> Anchored {
         anchor_position: None,
         position_mode: AnchoredPositionMode::Window,
         offset: None,
+        margin: Edges::default(),
+        placement_listener: None,
     }
 }
 
@@ -64,6 +68,30 @@ impl Anchored {
         self
     }
 
+    /// Sets which algorithm to use when fitting the anchored element inside the window.
+    pub fn fit_mode(mut self, fit_mode: AnchoredFitMode) -> Self {
+        self.fit_mode = fit_mode;
+        self
+    }
+
+    /// Keep the anchored element at least this far from the window edges, both when deciding
+    /// whether to switch the anchor corner and when snapping to the window edge.
+    pub fn window_margin(mut self, margin: impl Into<Edges<Pixels>>) -> Self {
+        self.margin = margin.into();
+        self
+    }
+
+    /// Called during prepaint with the placement that was chosen for the anchored element,
+    /// before its children are prepainted. Useful for drawing an arrow on the side that faces
+    /// the anchor.
+    pub fn on_placement(
+        mut self,
+        listener: impl Fn(&AnchoredPlacement, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.placement_listener = Some(Box::new(listener));
+        self
+    }
+
     /// Snap to window edge instead of switching anchor corner when an overflow would occur.
     pub fn snap_to_window(mut self) -> Self {
         self.fit_mode = AnchoredFitMode::SnapToWindow;
@@ -153,62 +181,84 @@ impl Element for Anchored {
             origin: Point::default(),
             size: window.viewport_size(),
         };
-
-        if self.fit_mode == AnchoredFitMode::SwitchAnchor {
-            let mut anchor_corner = self.anchor_corner;
-
-            if desired.left() < limits.left() || desired.right() > limits.right() {
+        let fit_limits = limits.extend(self.margin.map(|edge| -*edge));
+
+        let mut anchor_corner = self.anchor_corner;
+        if matches!(
+            self.fit_mode,
+            AnchoredFitMode::SwitchAnchor | AnchoredFitMode::SwitchAnchorWithoutSnapping
+        ) {
+            if desired.left() < fit_limits.left() || desired.right() > fit_limits.right() {
                 let switched = Bounds::from_corner_and_size(
                     anchor_corner.other_side_corner_along(Axis::Horizontal),
                     origin,
                     size,
                 );
-                if !(switched.left() < limits.left() || switched.right() > limits.right()) {
+                if !(switched.left() < fit_limits.left() || switched.right() > fit_limits.right()) {
                     anchor_corner = anchor_corner.other_side_corner_along(Axis::Horizontal);
                     desired = switched
                 }
             }
 
-            if desired.top() < limits.top() || desired.bottom() > limits.bottom() {
+            if desired.top() < fit_limits.top() || desired.bottom() > fit_limits.bottom() {
                 let switched = Bounds::from_corner_and_size(
                     anchor_corner.other_side_corner_along(Axis::Vertical),
                     origin,
                     size,
                 );
-                if !(switched.top() < limits.top() || switched.bottom() > limits.bottom()) {
+                if !(switched.top() < fit_limits.top() || switched.bottom() > fit_limits.bottom()) {
+                    anchor_corner = anchor_corner.other_side_corner_along(Axis::Vertical);
                     desired = switched;
                 }
             }
         }
 
-        let client_inset = window.client_inset.unwrap_or(px(0.));
-        let edges = match self.fit_mode {
-            AnchoredFitMode::SnapToWindowWithMargin(edges) => edges,
-            _ => Edges::default(),
-        }
-        .map(|edge| *edge + client_inset);
-
-        // Snap the horizontal edges of the anchored element to the horizontal edges of the window if
-        // its horizontal bounds overflow, aligning to the left if it is wider than the limits.
-        if desired.right() > limits.right() {
-            desired.origin.x -= desired.right() - limits.right() + edges.right;
-        }
-        if desired.left() < limits.left() {
-            desired.origin.x = limits.origin.x + edges.left;
-        }
+        if self.fit_mode != AnchoredFitMode::SwitchAnchorWithoutSnapping {
+            let client_inset = window.client_inset.unwrap_or(px(0.));
+            let edges = match self.fit_mode {
+                AnchoredFitMode::SnapToWindowWithMargin(edges) => edges,
+                _ => Edges::default(),
+            };
+            let edges = Edges {
+                top: edges.top + self.margin.top + client_inset,
+                right: edges.right + self.margin.right + client_inset,
+                bottom: edges.bottom + self.margin.bottom + client_inset,
+                left: edges.left + self.margin.left + client_inset,
+            };
+
+            // Snap the horizontal edges of the anchored element to the horizontal edges of the window if
+            // its horizontal bounds overflow, aligning to the left if it is wider than the limits.
+            if desired.right() > limits.right() - edges.right {
+                desired.origin.x -= desired.right() - limits.right() + edges.right;
+            }
+            if desired.left() < limits.left() + edges.left {
+                desired.origin.x = limits.origin.x + edges.left;
+            }
 
-        // Snap the vertical edges of the anchored element to the vertical edges of the window if
-        // its vertical bounds overflow, aligning to the top if it is taller than the limits.
-        if desired.bottom() > limits.bottom() {
-            desired.origin.y -= desired.bottom() - limits.bottom() + edges.bottom;
-        }
-        if desired.top() < limits.top() {
-            desired.origin.y = limits.origin.y + edges.top;
+            // Snap the vertical edges of the anchored element to the vertical edges of the window if
+            // its vertical bounds overflow, aligning to the top if it is taller than the limits.
+            if desired.bottom() > limits.bottom() - edges.bottom {
+                desired.origin.y -= desired.bottom() - limits.bottom() + edges.bottom;
+            }
+            if desired.top() < limits.top() + edges.top {
+                desired.origin.y = limits.origin.y + edges.top;
+            }
         }
 
         let offset = desired.origin - bounds.origin;
         let offset = point(offset.x.round(), offset.y.round());
 
+        if let Some(listener) = self.placement_listener.as_ref() {
+            let placement = AnchoredPlacement {
+                anchor: anchor_corner,
+                bounds: Bounds {
+                    origin: bounds.origin + offset,
+                    size,
+                },
+            };
+            listener(&placement, window, cx);
+        }
+
         window.with_element_offset(offset, |window| {
             for child in &mut self.children {
                 child.prepaint(window, cx);
@@ -247,8 +297,22 @@ pub enum AnchoredFitMode {
     SnapToWindow,
     /// Snap to window edge and leave some margins.
     SnapToWindowWithMargin(Edges<Pixels>),
-    /// Switch which corner anchor this anchored element is attached to.
+    /// Switch which corner anchor this anchored element is attached to, then snap to the window
+    /// edge if it still overflows.
     SwitchAnchor,
+    /// Switch which corner anchor this anchored element is attached to, without snapping to the
+    /// window edge if it still overflows.
+    SwitchAnchorWithoutSnapping,
+}
+
+/// Where an anchored element ended up being placed, see [`Anchored::on_placement`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnchoredPlacement {
+    /// The corner of the anchored element that is attached to the anchor position. This differs
+    /// from the corner passed to [`Anchored::anchor`] if the anchor was switched to fit.
+    pub anchor: Corner,
+    /// The bounds of the anchored element in window coordinates.
+    pub bounds: Bounds<Pixels>,
 }
 
 /// Which algorithm to use when positioning the anchored element.
@@ -290,3 +354,55 @@ impl AnchoredPositionMode {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        self as gpui, AnchoredFitMode, AnchoredPlacement, Corner, ParentElement, Styled,
+        TestAppContext, anchored, div, point, px, size,
+    };
+
+    #[gpui::test]
+    fn test_anchored_fit_modes(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let viewport_size = cx.update(|window, _| window.viewport_size());
+        let anchor_position = point(
+            viewport_size.width - px(10.),
+            viewport_size.height - px(10.),
+        );
+
+        let mut placement_for = |fit_mode| {
+            let placement = Rc::new(RefCell::new(None));
+            cx.draw(point(px(0.), px(0.)), viewport_size, |_, _| {
+                let placement = placement.clone();
+                anchored()
+                    .position(anchor_position)
+                    .fit_mode(fit_mode)
+                    .window_margin(px(8.))
+                    .on_placement(move |chosen: &AnchoredPlacement, _, _| {
+                        *placement.borrow_mut() = Some(chosen.clone())
+                    })
+                    .child(div().size(px(50.)))
+            });
+            placement.take().expect("placement should be reported")
+        };
+
+        let switched = placement_for(AnchoredFitMode::SwitchAnchor);
+        assert_eq!(switched.anchor, Corner::BottomRight);
+        assert_eq!(
+            switched.bounds.origin,
+            anchor_position - point(px(50.), px(50.))
+        );
+
+        let snapped = placement_for(AnchoredFitMode::SnapToWindow);
+        assert_eq!(snapped.anchor, Corner::TopLeft);
+        assert_eq!(
+            snapped.bounds.origin,
+            point(viewport_size.width, viewport_size.height) - point(px(58.), px(58.)),
+            "snapping should respect the window margin"
+        );
+        assert_eq!(snapped.bounds.size, size(px(50.), px(50.)));
+    }
+}
@@ -1,11 +1,15 @@
 use std::{
+    ops::Range,
     rc::Rc,
     time::{Duration, Instant},
 };
 
 use crate::{
-    AnyElement, App, Element, ElementId, GlobalElementId, InspectorElementId, IntoElement, Window,
+    AbsoluteLength, AnyElement, App, CornersRefinement, EdgesRefinement, Element, ElementId,
+    GlobalElementId, Hsla, InspectorElementId, Interpolate, IntoElement, Length, Pixels,
+    SizeRefinement, StyleRefinement, Styled, Window,
 };
+use refineable::Refineable;
 
 pub use easing::*;
 use smallvec::SmallVec;
@@ -85,6 +89,30 @@ pub trait AnimationExt {
             animations: animations.into(),
         }
     }
+
+    /// Move this element through the given keyframes, each iteration of which takes the given duration
+    fn animate(
+        self,
+        id: impl Into<ElementId>,
+        keyframes: Keyframes,
+        duration: Duration,
+        fill: AnimationFill,
+        iterations: IterationCount,
+    ) -> KeyframeAnimationElement<Self>
+    where
+        Self: Styled + Sized,
+    {
+        KeyframeAnimationElement {
+            id: id.into(),
+            element: Some(self),
+            keyframes,
+            duration,
+            delay: Duration::ZERO,
+            fill,
+            iterations,
+            on_iteration: None,
+        }
+    }
 }
 
 impl<E: IntoElement + 'static> AnimationExt for E {}
@@ -206,6 +234,365 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
     }
 }
 
+/// A sequence of styles that an element moves through over the course of an animation, see
+/// [`AnimationExt::animate`].
+#[derive(Clone, Default)]
+pub struct Keyframes {
+    keyframes: Vec<Keyframe>,
+    last_added: Option<usize>,
+}
+
+#[derive(Clone)]
+struct Keyframe {
+    offset: f32,
+    style: StyleRefinement,
+    easing: Rc<dyn Fn(f32) -> f32>,
+}
+
+/// Creates an empty sequence of keyframes.
+pub fn keyframes() -> Keyframes {
+    Keyframes::default()
+}
+
+impl Keyframes {
+    /// Adds a keyframe at the given offset into the animation, between 0 and 1, with the styles
+    /// set by the given function. The opacity, colors, corner radii, inset and size are blended
+    /// between the keyframes that set them, and hold the nearest keyframe's value before the first
+    /// one and after the last one. Styles have no transforms, so to scale an element animate its
+    /// size.
+    pub fn at(
+        mut self,
+        offset: f32,
+        style: impl FnOnce(StyleRefinement) -> StyleRefinement,
+    ) -> Self {
+        let offset = offset.clamp(0., 1.);
+        let index = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.offset <= offset);
+        self.keyframes.insert(
+            index,
+            Keyframe {
+                offset,
+                style: style(StyleRefinement::default()),
+                easing: Rc::new(linear),
+            },
+        );
+        self.last_added = Some(index);
+        self
+    }
+
+    /// Set the easing function used between the most recently added keyframe and the next one.
+    /// The easing function will take a time delta between 0 and 1 and return a new delta
+    /// between 0 and 1
+    pub fn with_easing(mut self, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        if let Some(keyframe) = self
+            .last_added
+            .and_then(|index| self.keyframes.get_mut(index))
+        {
+            keyframe.easing = Rc::new(easing);
+        }
+        self
+    }
+
+    /// Returns the styles at the given progress through the animation, between 0 and 1.
+    fn sample(&self, progress: f32, rem_size: Pixels) -> StyleRefinement {
+        let sample = |property: fn(&StyleRefinement) -> Option<AbsoluteLength>| {
+            self.sample_property(progress, rem_size, property)
+        };
+        let sample_length = |property: fn(&StyleRefinement) -> Option<Length>| {
+            self.sample_property(progress, rem_size, property)
+        };
+        StyleRefinement {
+            opacity: self.sample_property(progress, rem_size, |style| style.opacity),
+            background: self
+                .sample_property(progress, rem_size, |style| {
+                    style.background.clone().map(Some)
+                })
+                .flatten(),
+            border_color: self
+                .sample_property(progress, rem_size, |style| style.border_color.map(Some))
+                .flatten(),
+            corner_radii: CornersRefinement {
+                top_left: sample(|style| style.corner_radii.top_left),
+                top_right: sample(|style| style.corner_radii.top_right),
+                bottom_right: sample(|style| style.corner_radii.bottom_right),
+                bottom_left: sample(|style| style.corner_radii.bottom_left),
+            },
+            inset: EdgesRefinement {
+                top: sample_length(|style| style.inset.top),
+                right: sample_length(|style| style.inset.right),
+                bottom: sample_length(|style| style.inset.bottom),
+                left: sample_length(|style| style.inset.left),
+            },
+            size: SizeRefinement {
+                width: sample_length(|style| style.size.width),
+                height: sample_length(|style| style.size.height),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Returns the text color at the given progress through the animation, which is kept apart
+    /// from [`Self::sample`] so it doesn't replace the rest of the element's text style.
+    fn sample_text_color(&self, progress: f32, rem_size: Pixels) -> Option<Hsla> {
+        self.sample_property(progress, rem_size, |style| {
+            style.text.as_ref().and_then(|text| text.color).map(Some)
+        })
+        .flatten()
+    }
+
+    /// Blends the values of a property between the keyframes on either side of the given
+    /// progress, skipping the keyframes that don't set it.
+    fn sample_property<T: Interpolate>(
+        &self,
+        progress: f32,
+        rem_size: Pixels,
+        property: impl Fn(&StyleRefinement) -> Option<T>,
+    ) -> Option<T> {
+        let mut previous: Option<(&Keyframe, T)> = None;
+        for keyframe in &self.keyframes {
+            let Some(value) = property(&keyframe.style) else {
+                continue;
+            };
+            if keyframe.offset <= progress {
+                previous = Some((keyframe, value));
+                continue;
+            }
+
+            let Some((previous, previous_value)) = previous else {
+                return Some(value);
+            };
+            let delta = (previous.easing)(
+                (progress - previous.offset) / (keyframe.offset - previous.offset),
+            );
+            return previous_value
+                .interpolate(&value, delta, rem_size)
+                .or(Some(if delta < 0.5 { previous_value } else { value }));
+        }
+        previous.map(|(_, value)| value)
+    }
+}
+
+/// Whether an element has the styles of the animation before it starts and after it finishes.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub enum AnimationFill {
+    /// The element only has the animation's styles while it runs.
+    #[default]
+    None,
+    /// The element keeps the styles of the end of the animation once it finishes.
+    Forwards,
+    /// The element has the styles of the start of the animation during its
+    /// [delay](KeyframeAnimationElement::with_delay).
+    Backwards,
+    /// Both [`Forwards`](Self::Forwards) and [`Backwards`](Self::Backwards).
+    Both,
+}
+
+impl AnimationFill {
+    fn fills_forwards(self) -> bool {
+        matches!(self, Self::Forwards | Self::Both)
+    }
+
+    fn fills_backwards(self) -> bool {
+        matches!(self, Self::Backwards | Self::Both)
+    }
+}
+
+/// How many times an animation runs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IterationCount {
+    /// The animation runs the given number of times.
+    Finite(usize),
+    /// The animation repeats until the element is removed.
+    Infinite,
+}
+
+impl Default for IterationCount {
+    fn default() -> Self {
+        Self::Finite(1)
+    }
+}
+
+/// A GPUI element that moves another element through a sequence of [`Keyframes`]
+pub struct KeyframeAnimationElement<E> {
+    id: ElementId,
+    element: Option<E>,
+    keyframes: Keyframes,
+    duration: Duration,
+    delay: Duration,
+    fill: AnimationFill,
+    iterations: IterationCount,
+    on_iteration: Option<Rc<dyn Fn(usize, &mut Window, &mut App)>>,
+}
+
+impl<E> KeyframeAnimationElement<E> {
+    /// Waits for the given time after the element is first rendered before starting the
+    /// animation.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Calls the given function each time an iteration of the animation completes, with the
+    /// index of that iteration.
+    pub fn on_iteration(
+        mut self,
+        listener: impl Fn(usize, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_iteration = Some(Rc::new(listener));
+        self
+    }
+}
+
+impl<E: Styled + IntoElement + 'static> IntoElement for KeyframeAnimationElement<E> {
+    type Element = KeyframeAnimationElement<E>;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+struct KeyframeAnimationState {
+    start: Instant,
+    completed_iterations: usize,
+}
+
+/// Where a keyframe animation is at a point in time.
+#[derive(Debug, PartialEq)]
+struct KeyframeAnimationStep {
+    /// The progress through the keyframes to style the element with, if it has the animation's
+    /// styles at all.
+    progress: Option<f32>,
+    /// The iterations that completed since the previous step.
+    completed_iterations: Range<usize>,
+    done: bool,
+}
+
+impl KeyframeAnimationState {
+    fn advance(
+        &mut self,
+        now: Instant,
+        duration: Duration,
+        delay: Duration,
+        iterations: IterationCount,
+        fill: AnimationFill,
+    ) -> KeyframeAnimationStep {
+        let Some(active) = now.saturating_duration_since(self.start).checked_sub(delay) else {
+            return KeyframeAnimationStep {
+                progress: fill.fills_backwards().then_some(0.),
+                completed_iterations: self.completed_iterations..self.completed_iterations,
+                done: false,
+            };
+        };
+
+        let elapsed = if duration.is_zero() {
+            f32::INFINITY
+        } else {
+            active.div_duration_f32(duration)
+        };
+        let (completed_iterations, done) = match iterations {
+            IterationCount::Finite(count) => {
+                ((elapsed as usize).min(count), elapsed >= count as f32)
+            }
+            IterationCount::Infinite if elapsed.is_finite() => (elapsed as usize, false),
+            IterationCount::Infinite => (self.completed_iterations, true),
+        };
+        let newly_completed = self.completed_iterations..completed_iterations;
+        self.completed_iterations = completed_iterations;
+
+        KeyframeAnimationStep {
+            progress: if done {
+                fill.fills_forwards().then_some(1.)
+            } else {
+                Some(elapsed.fract())
+            },
+            completed_iterations: newly_completed,
+            done,
+        }
+    }
+}
+
+impl<E: Styled + IntoElement + 'static> Element for KeyframeAnimationElement<E> {
+    type RequestLayoutState = AnyElement;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (crate::LayoutId, Self::RequestLayoutState) {
+        window.with_optional_element_state(global_id, |state, window| {
+            let now = cx.background_executor().now();
+            let mut state = state.flatten().unwrap_or(KeyframeAnimationState {
+                start: now,
+                completed_iterations: 0,
+            });
+            let step = state.advance(now, self.duration, self.delay, self.iterations, self.fill);
+
+            if let Some(on_iteration) = self.on_iteration.as_ref() {
+                for iteration in step.completed_iterations {
+                    let on_iteration = on_iteration.clone();
+                    window.defer(cx, move |window, cx| on_iteration(iteration, window, cx));
+                }
+            }
+
+            let mut element = self.element.take().expect("should only be called once");
+            if let Some(progress) = step.progress {
+                let style = self.keyframes.sample(progress, window.rem_size());
+                element.style().refine(&style);
+                if let Some(color) = self
+                    .keyframes
+                    .sample_text_color(progress, window.rem_size())
+                {
+                    element.text_style().get_or_insert_default().color = Some(color);
+                }
+            }
+            let mut element = element.into_any_element();
+
+            if !step.done {
+                window.request_animation_frame();
+            }
+
+            ((element.request_layout(window, cx), element), Some(state))
+        })
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: crate::Bounds<crate::Pixels>,
+        element: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        element.prepaint(window, cx);
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: crate::Bounds<crate::Pixels>,
+        element: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        element.paint(window, cx);
+    }
+}
+
 mod easing {
     use std::f32::consts::PI;
 
@@ -261,3 +648,164 @@ mod easing {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::px;
+
+    fn pulse() -> Keyframes {
+        keyframes()
+            .at(1., |style| style.opacity(0.5).w(px(30.)))
+            .at(0., |style| style.opacity(0.).w(px(10.)))
+            .at(0.5, |style| style.opacity(1.))
+            .with_easing(quadratic)
+    }
+
+    #[test]
+    fn test_keyframes_sample() {
+        let keyframes = pulse();
+        let rem_size = px(16.);
+        let opacity = |progress| keyframes.sample(progress, rem_size).opacity;
+
+        // At each keyframe, in any order they were added.
+        assert_eq!(opacity(0.), Some(0.));
+        assert_eq!(opacity(0.5), Some(1.));
+        assert_eq!(opacity(1.), Some(0.5));
+
+        // Between keyframes, eased by the easing of the keyframe before.
+        assert_eq!(opacity(0.25), Some(0.5));
+        assert_eq!(opacity(0.75), Some(0.875));
+
+        // Keyframes that don't set a property are skipped when blending it.
+        let width = |progress| keyframes.sample(progress, rem_size).size.width;
+        assert_eq!(width(0.5), Some(px(20.).into()));
+        assert_eq!(width(1.), Some(px(30.).into()));
+        assert_eq!(keyframes.sample(0.5, rem_size).size.height, None);
+    }
+
+    #[test]
+    fn test_keyframes_sample_property_outside_keyframes() {
+        let keyframes = keyframes()
+            .at(0.25, |style| style.opacity(0.25))
+            .at(0.75, |style| style.opacity(0.75));
+        let opacity =
+            |progress| keyframes.sample_property(progress, px(16.), |style| style.opacity);
+
+        assert_eq!(opacity(0.), Some(0.25));
+        assert_eq!(opacity(0.5), Some(0.5));
+        assert_eq!(opacity(1.), Some(0.75));
+        assert_eq!(
+            keyframes.sample_property(0.5, px(16.), |style| style.size.width),
+            None
+        );
+    }
+
+    fn step(
+        progress: Option<f32>,
+        completed_iterations: Range<usize>,
+        done: bool,
+    ) -> KeyframeAnimationStep {
+        KeyframeAnimationStep {
+            progress,
+            completed_iterations,
+            done,
+        }
+    }
+
+    #[test]
+    fn test_keyframe_animation_fill() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let duration = Duration::from_secs(1);
+        let delay = Duration::from_millis(500);
+        let new_state = || KeyframeAnimationState {
+            start,
+            completed_iterations: 0,
+        };
+        let once = IterationCount::Finite(1);
+
+        let mut state = new_state();
+        assert_eq!(
+            state.advance(at(200), duration, delay, once, AnimationFill::None),
+            step(None, 0..0, false)
+        );
+        assert_eq!(
+            state.advance(at(1000), duration, delay, once, AnimationFill::None),
+            step(Some(0.5), 0..0, false)
+        );
+        assert_eq!(
+            state.advance(at(2000), duration, delay, once, AnimationFill::None),
+            step(None, 0..1, true)
+        );
+
+        let mut state = new_state();
+        assert_eq!(
+            state.advance(at(200), duration, delay, once, AnimationFill::Forwards),
+            step(None, 0..0, false)
+        );
+        assert_eq!(
+            state.advance(at(2000), duration, delay, once, AnimationFill::Forwards),
+            step(Some(1.), 0..1, true)
+        );
+
+        let mut state = new_state();
+        assert_eq!(
+            state.advance(at(200), duration, delay, once, AnimationFill::Backwards),
+            step(Some(0.), 0..0, false)
+        );
+        assert_eq!(
+            state.advance(at(2000), duration, delay, once, AnimationFill::Backwards),
+            step(None, 0..1, true)
+        );
+
+        let mut state = new_state();
+        assert_eq!(
+            state.advance(at(200), duration, delay, once, AnimationFill::Both),
+            step(Some(0.), 0..0, false)
+        );
+        assert_eq!(
+            state.advance(at(2000), duration, delay, once, AnimationFill::Both),
+            step(Some(1.), 0..1, true)
+        );
+    }
+
+    #[test]
+    fn test_keyframe_animation_iterations() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let duration = Duration::from_secs(1);
+        let fill = AnimationFill::None;
+
+        let twice = IterationCount::Finite(2);
+        let mut state = KeyframeAnimationState {
+            start,
+            completed_iterations: 0,
+        };
+        let mut advance = |ms| state.advance(at(ms), duration, Duration::ZERO, twice, fill);
+        assert_eq!(advance(0), step(Some(0.), 0..0, false));
+        assert_eq!(advance(1500), step(Some(0.5), 0..1, false));
+        // Each iteration is only reported once, however often the animation is sampled.
+        assert_eq!(advance(1500), step(Some(0.5), 1..1, false));
+        // Frames can be far apart, but iterations past the count are never reported.
+        assert_eq!(advance(10000), step(None, 1..2, true));
+        assert_eq!(advance(20000), step(None, 2..2, true));
+
+        let mut state = KeyframeAnimationState {
+            start,
+            completed_iterations: 0,
+        };
+        let mut advance = |ms| {
+            state.advance(
+                at(ms),
+                duration,
+                Duration::ZERO,
+                IterationCount::Infinite,
+                fill,
+            )
+        };
+        assert_eq!(advance(3500), step(Some(0.5), 0..3, false));
+        assert_eq!(advance(3750), step(Some(0.75), 3..3, false));
+        assert_eq!(advance(4500), step(Some(0.5), 3..4, false));
+    }
+}
@@ -1,8 +1,12 @@
+use std::rc::Rc;
+
 use refineable::Refineable as _;
 
 use crate::{
-    App, Bounds, Element, ElementId, GlobalElementId, InspectorElementId, IntoElement, Pixels,
-    Style, StyleRefinement, Styled, Window,
+    App, Bounds, DispatchPhase, Element, ElementId, FocusHandle, GlobalElementId, Hitbox,
+    HitboxBehavior, InspectorElementId, IntoElement, KeyDownEvent, KeyUpEvent, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, Pixels, Point, ScrollWheelEvent, Style, StyleRefinement, Styled,
+    Window,
 };
 
 /// Construct a canvas element with the given paint callback.
@@ -15,6 +19,10 @@ pub fn canvas<T>(
         prepaint: Some(Box::new(prepaint)),
         paint: Some(Box::new(paint)),
         style: StyleRefinement::default(),
+        mouse_listeners: Vec::new(),
+        scroll_wheel_listeners: Vec::new(),
+        key_listeners: Vec::new(),
+        focus_handle: None,
     }
 }
 
@@ -24,6 +32,103 @@ pub struct Canvas<T> {
     prepaint: Option<Box<dyn FnOnce(Bounds<Pixels>, &mut Window, &mut App) -> T>>,
     paint: Option<Box<dyn FnOnce(Bounds<Pixels>, T, &mut Window, &mut App)>>,
     style: StyleRefinement,
+    mouse_listeners: Vec<Rc<dyn Fn(&CanvasMouseEvent, &mut Window, &mut App)>>,
+    scroll_wheel_listeners: Vec<Rc<dyn Fn(&CanvasScrollWheelEvent, &mut Window, &mut App)>>,
+    key_listeners: Vec<Rc<dyn Fn(&CanvasKeyEvent, &mut Window, &mut App)>>,
+    focus_handle: Option<FocusHandle>,
+}
+
+/// A mouse button event or mouse move received by a [`Canvas`].
+#[derive(Clone, Debug)]
+pub enum CanvasMouseInput {
+    /// A mouse button was pressed.
+    Down(MouseDownEvent),
+    /// A mouse button was released.
+    Up(MouseUpEvent),
+    /// The mouse was moved.
+    Move(MouseMoveEvent),
+}
+
+/// A mouse event over a [`Canvas`], with its position in both window and canvas coordinates.
+#[derive(Clone, Debug)]
+pub struct CanvasMouseEvent {
+    /// The event the canvas received.
+    pub input: CanvasMouseInput,
+    /// The position of the mouse in window coordinates.
+    pub position: Point<Pixels>,
+    /// The position of the mouse relative to the canvas's origin.
+    pub local_position: Point<Pixels>,
+    /// The phase of the event dispatch.
+    pub phase: DispatchPhase,
+}
+
+/// A scroll wheel event over a [`Canvas`], with its position in both window and canvas coordinates.
+#[derive(Clone, Debug)]
+pub struct CanvasScrollWheelEvent {
+    /// The event the canvas received.
+    pub event: ScrollWheelEvent,
+    /// The position of the mouse in window coordinates.
+    pub position: Point<Pixels>,
+    /// The position of the mouse relative to the canvas's origin.
+    pub local_position: Point<Pixels>,
+    /// The phase of the event dispatch.
+    pub phase: DispatchPhase,
+}
+
+/// A key event received by a focused [`Canvas`].
+#[derive(Clone, Debug)]
+pub enum CanvasKeyInput {
+    /// A key was pressed.
+    Down(KeyDownEvent),
+    /// A key was released.
+    Up(KeyUpEvent),
+}
+
+/// A key event received by a focused [`Canvas`], see [`Canvas::track_focus`].
+#[derive(Clone, Debug)]
+pub struct CanvasKeyEvent {
+    /// The event the canvas received.
+    pub input: CanvasKeyInput,
+    /// The phase of the event dispatch.
+    pub phase: DispatchPhase,
+}
+
+impl<T> Canvas<T> {
+    /// Call `listener` with mouse button events and mouse moves that happen while the canvas
+    /// is hovered, in both dispatch phases.
+    pub fn on_mouse_event(
+        mut self,
+        listener: impl Fn(&CanvasMouseEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.mouse_listeners.push(Rc::new(listener));
+        self
+    }
+
+    /// Call `listener` with scroll wheel events that happen while the canvas is hovered, in
+    /// both dispatch phases.
+    pub fn on_scroll_wheel(
+        mut self,
+        listener: impl Fn(&CanvasScrollWheelEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.scroll_wheel_listeners.push(Rc::new(listener));
+        self
+    }
+
+    /// Call `listener` with key events while the canvas is focused, in both dispatch phases.
+    pub fn on_key_event(
+        mut self,
+        listener: impl Fn(&CanvasKeyEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.key_listeners.push(Rc::new(listener));
+        self
+    }
+
+    /// Track the focus state of the canvas with the given handle, so that it can receive key
+    /// events when focused.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
 }
 
 impl<T: 'static> IntoElement for Canvas<T> {
@@ -36,7 +141,7 @@ impl<T: 'static> IntoElement for Canvas<T> {
 
 impl<T: 'static> Element for Canvas<T> {
     type RequestLayoutState = Style;
-    type PrepaintState = Option<T>;
+    type PrepaintState = (Option<T>, Option<Hitbox>);
 
     fn id(&self) -> Option<ElementId> {
         None
@@ -67,8 +172,16 @@ impl<T: 'static> Element for Canvas<T> {
         _request_layout: &mut Style,
         window: &mut Window,
         cx: &mut App,
-    ) -> Option<T> {
-        Some(self.prepaint.take().unwrap()(bounds, window, cx))
+    ) -> Self::PrepaintState {
+        if let Some(focus_handle) = self.focus_handle.as_ref() {
+            window.set_focus_handle(focus_handle, cx);
+        }
+        let hitbox = (!self.mouse_listeners.is_empty() || !self.scroll_wheel_listeners.is_empty())
+            .then(|| window.insert_hitbox(bounds, HitboxBehavior::Normal));
+        (
+            Some(self.prepaint.take().unwrap()(bounds, window, cx)),
+            hitbox,
+        )
     }
 
     fn paint(
@@ -81,6 +194,28 @@ impl<T: 'static> Element for Canvas<T> {
         window: &mut Window,
         cx: &mut App,
     ) {
+        let (prepaint, hitbox) = prepaint;
+        if let Some(hitbox) = hitbox.as_ref() {
+            self.paint_mouse_listeners(hitbox, window);
+        }
+        for listener in self.key_listeners.drain(..) {
+            let key_down_listener = listener.clone();
+            window.on_key_event(move |event: &KeyDownEvent, phase, window, cx| {
+                let event = CanvasKeyEvent {
+                    input: CanvasKeyInput::Down(event.clone()),
+                    phase,
+                };
+                key_down_listener(&event, window, cx);
+            });
+            window.on_key_event(move |event: &KeyUpEvent, phase, window, cx| {
+                let event = CanvasKeyEvent {
+                    input: CanvasKeyInput::Up(event.clone()),
+                    phase,
+                };
+                listener(&event, window, cx);
+            });
+        }
+
         let prepaint = prepaint.take().unwrap();
         style.paint(bounds, window, cx, |window, cx| {
             (self.paint.take().unwrap())(bounds, prepaint, window, cx)
@@ -88,8 +223,213 @@ impl<T: 'static> Element for Canvas<T> {
     }
 }
 
+impl<T> Canvas<T> {
+    fn paint_mouse_listeners(&mut self, hitbox: &Hitbox, window: &mut Window) {
+        let origin = hitbox.bounds.origin;
+        for listener in self.mouse_listeners.drain(..) {
+            let mouse_down_listener = listener.clone();
+            let mouse_up_listener = listener.clone();
+            let hitbox = hitbox.clone();
+            let mouse_down_hitbox = hitbox.clone();
+            let mouse_up_hitbox = hitbox.clone();
+            window.on_mouse_event(move |event: &MouseDownEvent, phase, window, cx| {
+                if mouse_down_hitbox.is_hovered(window) {
+                    let event = CanvasMouseEvent {
+                        input: CanvasMouseInput::Down(event.clone()),
+                        position: event.position,
+                        local_position: event.position - origin,
+                        phase,
+                    };
+                    mouse_down_listener(&event, window, cx);
+                }
+            });
+            window.on_mouse_event(move |event: &MouseUpEvent, phase, window, cx| {
+                if mouse_up_hitbox.is_hovered(window) {
+                    let event = CanvasMouseEvent {
+                        input: CanvasMouseInput::Up(event.clone()),
+                        position: event.position,
+                        local_position: event.position - origin,
+                        phase,
+                    };
+                    mouse_up_listener(&event, window, cx);
+                }
+            });
+            window.on_mouse_event(move |event: &MouseMoveEvent, phase, window, cx| {
+                if hitbox.is_hovered(window) {
+                    let event = CanvasMouseEvent {
+                        input: CanvasMouseInput::Move(event.clone()),
+                        position: event.position,
+                        local_position: event.position - origin,
+                        phase,
+                    };
+                    listener(&event, window, cx);
+                }
+            });
+        }
+
+        for listener in self.scroll_wheel_listeners.drain(..) {
+            let hitbox = hitbox.clone();
+            window.on_mouse_event(move |event: &ScrollWheelEvent, phase, window, cx| {
+                if hitbox.should_handle_scroll(window) {
+                    let event = CanvasScrollWheelEvent {
+                        event: event.clone(),
+                        position: event.position,
+                        local_position: event.position - origin,
+                        phase,
+                    };
+                    listener(&event, window, cx);
+                }
+            });
+        }
+    }
+}
+
 impl<T> Styled for Canvas<T> {
     fn style(&mut self) -> &mut crate::StyleRefinement {
         &mut self.style
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        self as gpui, CanvasKeyInput, CanvasMouseInput, ClipRegionKind, ContentMask, Context,
+        DispatchPhase, FocusHandle, IntoElement, Modifiers, ParentElement, Render, ScrollDelta,
+        ScrollWheelEvent, Styled, TestAppContext, Window, bounds, canvas, div, fill, point, px,
+        red, size,
+    };
+
+    struct MaskedCanvas;
+
+    impl Render for MaskedCanvas {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            canvas(
+                |_, _, _| {},
+                |canvas_bounds, _, window, _| {
+                    window.with_content_mask(
+                        Some(ContentMask {
+                            bounds: canvas_bounds,
+                        }),
+                        |window| {
+                            window.with_rounded_content_mask(
+                                bounds(point(px(50.), px(50.)), size(px(100.), px(100.))),
+                                px(10.).into(),
+                                |window| {
+                                    window.paint_quad(fill(
+                                        bounds(point(px(0.), px(0.)), size(px(200.), px(200.))),
+                                        red(),
+                                    ));
+                                },
+                            );
+                        },
+                    );
+                },
+            )
+            .size(px(80.))
+        }
+    }
+
+    #[gpui::test]
+    fn test_rounded_content_mask(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_, _| MaskedCanvas);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            let scale_factor = window.scale_factor();
+            assert_eq!(scene.quads.len(), 1);
+            let quad = &scene.quads[0];
+            assert_eq!(
+                quad.content_mask.bounds,
+                bounds(point(px(50.), px(50.)), size(px(30.), px(30.))).scale(scale_factor),
+                "the mask should intersect with the enclosing one"
+            );
+
+            let region = &scene.clip_regions[quad.clip_region as usize - 1];
+            assert_eq!(region.kind, ClipRegionKind::RoundedRect);
+            assert_eq!(region.corner_radii.top_left, px(10.).scale(scale_factor));
+            assert_eq!(
+                region.bounds,
+                bounds(point(px(50.), px(50.)), size(px(100.), px(100.))).scale(scale_factor)
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_canvas_input_events(cx: &mut TestAppContext) {
+        struct TestView {
+            focus_handle: FocusHandle,
+            events: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let mouse_events = self.events.clone();
+                let scroll_events = self.events.clone();
+                let key_events = self.events.clone();
+                div().pl(px(20.)).pt(px(10.)).child(
+                    canvas(|_, _, _| {}, |_, _, _, _| {})
+                        .size(px(50.))
+                        .track_focus(&self.focus_handle)
+                        .on_mouse_event(move |event, _, _| {
+                            if event.phase == DispatchPhase::Bubble
+                                && let CanvasMouseInput::Down(_) = event.input
+                            {
+                                mouse_events.borrow_mut().push(format!(
+                                    "down {:?} {:?}",
+                                    event.position, event.local_position
+                                ));
+                            }
+                        })
+                        .on_scroll_wheel(move |event, _, _| {
+                            if event.phase == DispatchPhase::Bubble {
+                                scroll_events
+                                    .borrow_mut()
+                                    .push(format!("scroll {:?}", event.local_position));
+                            }
+                        })
+                        .on_key_event(move |event, _, _| {
+                            if event.phase == DispatchPhase::Bubble
+                                && let CanvasKeyInput::Down(key_down) = &event.input
+                            {
+                                key_events
+                                    .borrow_mut()
+                                    .push(format!("key {}", key_down.keystroke.key));
+                            }
+                        }),
+                )
+            }
+        }
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let (view, cx) = cx.add_window_view(|_, cx| TestView {
+            focus_handle: cx.focus_handle(),
+            events: events.clone(),
+        });
+
+        cx.simulate_click(point(px(25.), px(15.)), Modifiers::none());
+        // Outside of the canvas.
+        cx.simulate_click(point(px(100.), px(15.)), Modifiers::none());
+        cx.simulate_event(ScrollWheelEvent {
+            position: point(px(30.), px(40.)),
+            delta: ScrollDelta::Pixels(point(px(0.), px(-10.))),
+            ..Default::default()
+        });
+        cx.simulate_keystrokes("a");
+        cx.update(|window, cx| window.focus(&view.read(cx).focus_handle));
+        cx.simulate_keystrokes("b");
+
+        assert_eq!(
+            *events.borrow(),
+            [
+                format!(
+                    "down {:?} {:?}",
+                    point(px(25.), px(15.)),
+                    point(px(5.), px(5.))
+                ),
+                format!("scroll {:?}", point(px(10.), px(30.))),
+                "key b".to_string(),
+            ]
+        );
+    }
+}
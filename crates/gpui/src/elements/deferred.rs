@@ -19,9 +19,22 @@ pub struct Deferred {
 }
 
 impl Deferred {
+    /// The priority at which the drag preview of an active drag started with
+    /// [`StatefulInteractiveElement::on_drag`](crate::StatefulInteractiveElement::on_drag) is
+    /// painted. Deferred elements with a higher priority are painted on top of it.
+    pub const DRAG_PREVIEW_PRIORITY: usize = 1000;
+
+    /// The priority at which tooltips set with
+    /// [`StatefulInteractiveElement::tooltip`](crate::StatefulInteractiveElement::tooltip) are
+    /// painted. Deferred elements with a higher priority are painted on top of them.
+    pub const TOOLTIP_PRIORITY: usize = 2000;
+
     /// Sets the `priority` value of the `deferred` element, which
     /// determines the drawing order relative to other deferred elements,
-    /// with higher values being drawn on top.
+    /// with higher values being drawn on top and hit tested first. Deferred
+    /// elements with the same priority are drawn in the order they were
+    /// prepainted. See [`Self::DRAG_PREVIEW_PRIORITY`] and
+    /// [`Self::TOOLTIP_PRIORITY`] for the priorities of gpui's own layers.
     pub fn with_priority(mut self, priority: usize) -> Self {
         self.priority = priority;
         self
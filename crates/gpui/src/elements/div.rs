@@ -16,14 +16,16 @@
 //! constructed by combining these two systems into an all-in-one element.
 
 use crate::{
-    AbsoluteLength, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, App, Bounds, ClickEvent,
-    DispatchPhase, Display, Element, ElementId, Entity, FocusHandle, Global, GlobalElementId,
-    Hitbox, HitboxBehavior, HitboxId, InspectorElementId, IntoElement, IsZero, KeyContext,
-    KeyDownEvent, KeyUpEvent, KeyboardButton, KeyboardClickEvent, LayoutId, ModifiersChangedEvent,
-    MouseButton, MouseClickEvent, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Overflow,
-    ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
-    StyleRefinement, Styled, Task, TooltipId, Visibility, Window, WindowControlArea, point, px,
-    size,
+    AbsoluteLength, Action, Along, AnyDrag, AnyElement, AnyTooltip, AnyView, App, Axis, Bounds,
+    ClickEvent, Corners, CursorStyle, DispatchPhase, Display, Element, ElementId, Entity, EntityId,
+    FocusHandle, GlobalElementId, Hitbox, HitboxBehavior, HitboxId, HitboxShape,
+    InspectorElementId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, KeyboardButton,
+    KeyboardClickEvent, LayoutId, LongPressEvent, ModifiersChangedEvent, MouseButton,
+    MouseClickEvent, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Overflow, OverscrollBehavior,
+    ParentElement, PinchEvent, Pixels, Point, Render, RotateGestureEvent, ScrollSnap,
+    ScrollWheelEvent, SharedString, Size, SnapAlign, SnapAxis, Style, StyleRefinement, Styled,
+    Task, TooltipId, TouchPhase, TransitionState, Visibility, Window, WindowControlArea,
+    WindowInvalidator, ease_out_quint, point, px, size,
 };
 use collections::HashMap;
 use refineable::Refineable;
@@ -31,22 +33,27 @@ use smallvec::SmallVec;
 use stacksafe::{StackSafe, stacksafe};
 use std::{
     any::{Any, TypeId},
-    cell::RefCell,
+    cell::{Cell, RefCell},
     cmp::Ordering,
     fmt::Debug,
     marker::PhantomData,
     mem,
+    path::PathBuf,
     rc::Rc,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use util::ResultExt;
 
 use super::ImageCacheProvider;
 
 const DRAG_THRESHOLD: f64 = 2.;
+const DEFAULT_LONG_PRESS_SLOP: Pixels = px(8.);
 const TOOLTIP_SHOW_DELAY: Duration = Duration::from_millis(500);
 const HOVERABLE_TOOLTIP_HIDE_DELAY: Duration = Duration::from_millis(500);
+/// How long after the last scroll event without momentum a snapping container settles.
+const SNAP_SETTLE_DELAY: Duration = Duration::from_millis(150);
+const SNAP_SETTLE_DURATION: Duration = Duration::from_millis(200);
 
 /// The styling information for a given group.
 pub struct GroupStyle {
@@ -134,6 +141,30 @@ impl Interactivity {
             }));
     }
 
+    /// Bind the given callback to the mouse down event for the given button, during the capture phase.
+    /// The imperative API equivalent to [`InteractiveElement::on_mouse_down_capture`].
+    ///
+    /// Capture listeners run from the outermost element inwards, before any bubble phase listener.
+    /// Multiple capture listeners on the same element run in the order they were registered.
+    /// Calling [`App::stop_propagation`] from this callback prevents the bubble phase entirely.
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_mouse_down_capture(
+        &mut self,
+        button: MouseButton,
+        listener: impl Fn(&MouseDownEvent, &mut Window, &mut App) + 'static,
+    ) {
+        self.mouse_down_listeners
+            .push(Box::new(move |event, phase, hitbox, window, cx| {
+                if phase == DispatchPhase::Capture
+                    && event.button == button
+                    && hitbox.is_hovered(window)
+                {
+                    (listener)(event, window, cx)
+                }
+            }));
+    }
+
     /// Bind the given callback to the mouse down event for any button, during the capture phase.
     /// The imperative API equivalent of [`InteractiveElement::capture_any_mouse_down`].
     ///
@@ -186,6 +217,30 @@ impl Interactivity {
             }));
     }
 
+    /// Bind the given callback to the mouse up event for the given button, during the capture phase.
+    /// The imperative API equivalent to [`InteractiveElement::on_mouse_up_capture`].
+    ///
+    /// Capture listeners run from the outermost element inwards, before any bubble phase listener.
+    /// Multiple capture listeners on the same element run in the order they were registered.
+    /// Calling [`App::stop_propagation`] from this callback prevents the bubble phase entirely.
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_mouse_up_capture(
+        &mut self,
+        button: MouseButton,
+        listener: impl Fn(&MouseUpEvent, &mut Window, &mut App) + 'static,
+    ) {
+        self.mouse_up_listeners
+            .push(Box::new(move |event, phase, hitbox, window, cx| {
+                if phase == DispatchPhase::Capture
+                    && event.button == button
+                    && hitbox.is_hovered(window)
+                {
+                    (listener)(event, window, cx)
+                }
+            }));
+    }
+
     /// Bind the given callback to the mouse up event for any button, during the capture phase.
     /// The imperative API equivalent to [`InteractiveElement::capture_any_mouse_up`].
     ///
@@ -321,6 +376,37 @@ impl Interactivity {
             }));
     }
 
+    /// Bind the given callback to pinch gestures during the bubble phase, e.g. to zoom content.
+    /// Platforms that don't report pinch gestures never dispatch them.
+    /// The imperative API equivalent to [`InteractiveElement::on_pinch`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_pinch(&mut self, listener: impl Fn(&PinchEvent, &mut Window, &mut App) + 'static) {
+        self.pinch_listeners
+            .push(Box::new(move |event, phase, hitbox, window, cx| {
+                if phase == DispatchPhase::Bubble && hitbox.should_handle_scroll(window) {
+                    (listener)(event, window, cx);
+                }
+            }));
+    }
+
+    /// Bind the given callback to rotation gestures during the bubble phase.
+    /// Platforms that don't report rotation gestures never dispatch them.
+    /// The imperative API equivalent to [`InteractiveElement::on_rotate_gesture`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_rotate_gesture(
+        &mut self,
+        listener: impl Fn(&RotateGestureEvent, &mut Window, &mut App) + 'static,
+    ) {
+        self.rotate_gesture_listeners
+            .push(Box::new(move |event, phase, hitbox, window, cx| {
+                if phase == DispatchPhase::Bubble && hitbox.should_handle_scroll(window) {
+                    (listener)(event, window, cx);
+                }
+            }));
+    }
+
     /// Bind the given callback to an action dispatch during the capture phase.
     /// The imperative API equivalent to [`InteractiveElement::capture_action`].
     ///
@@ -490,6 +576,97 @@ impl Interactivity {
         }));
     }
 
+    /// Bind the given callback to double clicks of this element, which are detected with the
+    /// platform's double-click interval and distance. [`Self::on_click`] listeners still fire for
+    /// each click, and [`ClickEvent::click_count`] reports how many clicks have chained.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_double_click`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_double_click(
+        &mut self,
+        listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) {
+        self.on_click_with_count(2, listener);
+    }
+
+    /// Bind the given callback to triple clicks of this element, which are detected with the
+    /// platform's double-click interval and distance.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_triple_click`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_triple_click(
+        &mut self,
+        listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) {
+        self.on_click_with_count(3, listener);
+    }
+
+    /// Bind the given callback to secondary clicks of this element, which are a press and release
+    /// of the right mouse button over it. Like clicks, a press that moves past the drag threshold
+    /// or is released outside of the element is canceled.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_secondary_click`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_secondary_click(
+        &mut self,
+        listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) {
+        self.secondary_click_listeners.push(Rc::new(listener));
+    }
+
+    /// Bind the given callback to middle clicks of this element, which are a press and release of
+    /// the middle mouse button over it, with the same semantics as [`Self::on_secondary_click`].
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_middle_click`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_middle_click(
+        &mut self,
+        listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) {
+        self.middle_click_listeners.push(Rc::new(listener));
+    }
+
+    /// Report left clicks made while holding control as secondary clicks on macOS, where
+    /// control-click is the conventional way to open a context menu without a second button.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::ctrl_click_as_secondary`].
+    pub fn ctrl_click_as_secondary(&mut self, enabled: bool) {
+        self.ctrl_click_as_secondary = enabled;
+    }
+
+    fn on_click_with_count(
+        &mut self,
+        click_count: usize,
+        listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) {
+        self.click_listeners.push(Rc::new(move |event, window, cx| {
+            if !event.is_keyboard() && event.click_count() == click_count {
+                listener(event, window, cx)
+            }
+        }));
+    }
+
+    /// Bind the given callback to long presses of this element, which are reported once the left
+    /// mouse button or a touch has been held down for the given delay. The press is canceled if
+    /// it's released, moves further than the slop set with [`Self::long_press_slop`], or scrolls.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_long_press`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_long_press(
+        &mut self,
+        delay: Duration,
+        listener: impl Fn(&LongPressEvent, &mut Window, &mut App) + 'static,
+    ) where
+        Self: Sized,
+    {
+        self.long_press_listener = Some((delay, Rc::new(listener)));
+    }
+
+    /// Set how far a long press can move before it's canceled, which defaults to 8 pixels.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::long_press_slop`].
+    pub fn long_press_slop(&mut self, slop: Pixels) {
+        self.long_press_slop = Some(slop);
+    }
+
     /// On drag initiation, this callback will be used to create a new view to render the dragged value for a
     /// drag and drop operation. This API should also be used as the equivalent of 'on drag start' with
     /// the [`Self::on_drag_move`] API.
@@ -517,6 +694,15 @@ impl Interactivity {
         ));
     }
 
+    /// Drag the given files out of the window, e.g. into Finder or Explorer, once the pointer is
+    /// pressed on this element and moves past the drag threshold. The element as it was last
+    /// rendered is used as the drag image. Nothing happens on platforms that don't support
+    /// dragging files out.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_drag_out`].
+    pub fn on_drag_out(&mut self, paths: Vec<PathBuf>) {
+        self.drag_out_paths = Some(paths);
+    }
+
     /// Bind the given callback on the hover start and end events of this element. Note that the boolean
     /// passed to the callback is true when the hover starts and false when it ends.
     /// The imperative API equivalent to [`StatefulInteractiveElement::on_hover`].
@@ -533,6 +719,24 @@ impl Interactivity {
         self.hover_listener = Some(Box::new(listener));
     }
 
+    /// Bind the given callback on the hover start and end events of this element, like
+    /// [`Self::on_hover`], but only call it once the mouse has stayed over the element for the
+    /// given delay. Hovers that end before the delay has passed don't call the callback, and the
+    /// end of a hover that started is reported immediately.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_hover_with_delay`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_hover_with_delay(
+        &mut self,
+        delay: Duration,
+        listener: impl Fn(&bool, &mut Window, &mut App) + 'static,
+    ) where
+        Self: Sized,
+    {
+        self.on_hover(listener);
+        self.hover_delay = Some(delay);
+    }
+
     /// Use the given callback to construct a new tooltip view when the mouse hovers over this element.
     /// The imperative API equivalent to [`StatefulInteractiveElement::tooltip`].
     pub fn tooltip(&mut self, build_tooltip: impl Fn(&mut Window, &mut App) -> AnyView + 'static)
@@ -589,6 +793,21 @@ impl Interactivity {
     pub fn block_mouse_except_scroll(&mut self) {
         self.hitbox_behavior = HitboxBehavior::BlockMouseExceptScroll;
     }
+
+    /// Apply hover styles, hover listeners and tooltips to this element whenever it contains the
+    /// mouse, even when an overlapping element in front of it is hovered. Useful for elements like
+    /// drop targets that stay highlighted under what's being dragged over them.
+    /// The imperative API equivalent to [`InteractiveElement::hover_even_when_occluded`].
+    pub fn hover_even_when_occluded(&mut self) {
+        self.hover_when_occluded = true;
+    }
+
+    /// Set the shape of this element's hitbox within its bounds, which defaults to the bounds
+    /// rounded by the element's corner radii.
+    /// The imperative API equivalent to [`InteractiveElement::hitbox_shape`].
+    pub fn hitbox_shape(&mut self, shape: HitboxShape) {
+        self.hitbox_shape = Some(shape);
+    }
 }
 
 /// A trait for elements that want to use the standard GPUI event handlers that don't
@@ -630,10 +849,18 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Set whether pressing enter or space while this element is focused dispatches its click
+    /// listeners, with a [`ClickEvent::Keyboard`] event. This is enabled by default.
+    fn activate_on_key(mut self, activate: bool) -> Self {
+        self.interactivity().activate_on_key = Some(activate);
+        self
+    }
+
     /// Set index of the tab stop order, and set this node as a tab stop.
     /// This will default the element to being a tab stop. See [`Self::tab_stop`] for more information.
     /// This should only be used in conjunction with `tab_group`
     /// in order to not interfere with the tab index of other elements.
+    /// A negative index keeps the element focusable but leaves it out of the tab order.
     fn tab_index(mut self, index: isize) -> Self {
         self.interactivity().focusable = true;
         self.interactivity().tab_index = Some(index);
@@ -653,6 +880,14 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Designate this div as a tab group that scopes traversal to its children: while focus is
+    /// within it, tabbing cycles through its tab stops and wraps at the edges instead of leaving it.
+    /// This is useful for modal dialogs.
+    fn tab_scope(mut self) -> Self {
+        self.interactivity().tab_scope = true;
+        self.tab_group()
+    }
+
     /// Set the keymap context for this element. This will be used to determine
     /// which action to dispatch from the keymap.
     fn key_context<C, E>(mut self, key_context: C) -> Self
@@ -666,6 +901,22 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Set whether this element is disabled. Disabled elements don't call their click, drag and
+    /// key listeners, are skipped during tab traversal, show [`CursorStyle::OperationNotAllowed`]
+    /// (or the cursor set with [`StatefulInteractiveElement::cursor_when_disabled`]) and apply
+    /// the style set with [`Self::when_disabled`]. Hover styles and tooltips still apply, so a
+    /// tooltip can explain why the element is disabled.
+    fn disabled(mut self, disabled: bool) -> Self {
+        self.interactivity().disabled = disabled;
+        self
+    }
+
+    /// Apply the given style to this element while it's disabled, see [`Self::disabled`].
+    fn when_disabled(mut self, f: impl FnOnce(StyleRefinement) -> StyleRefinement) -> Self {
+        self.interactivity().disabled_style = Some(Box::new(f(StyleRefinement::default())));
+        self
+    }
+
     /// Apply the given style to this element when the mouse hovers over it
     fn hover(mut self, f: impl FnOnce(StyleRefinement) -> StyleRefinement) -> Self {
         debug_assert!(
@@ -720,6 +971,23 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Bind the given callback to the mouse down event for the given button, during the capture phase.
+    /// The fluent API equivalent to [`Interactivity::on_mouse_down_capture`].
+    ///
+    /// Capture listeners run from the outermost element inwards, before any bubble phase listener.
+    /// Multiple capture listeners on the same element run in the order they were registered.
+    /// Calling [`App::stop_propagation`] from this callback prevents the bubble phase entirely.
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_mouse_down_capture(
+        mut self,
+        button: MouseButton,
+        listener: impl Fn(&MouseDownEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.interactivity().on_mouse_down_capture(button, listener);
+        self
+    }
+
     /// Bind the given callback to the mouse down event for any button, during the capture phase.
     /// The fluent API equivalent to [`Interactivity::capture_any_mouse_down`].
     ///
@@ -757,6 +1025,23 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Bind the given callback to the mouse up event for the given button, during the capture phase.
+    /// The fluent API equivalent to [`Interactivity::on_mouse_up_capture`].
+    ///
+    /// Capture listeners run from the outermost element inwards, before any bubble phase listener.
+    /// Multiple capture listeners on the same element run in the order they were registered.
+    /// Calling [`App::stop_propagation`] from this callback prevents the bubble phase entirely.
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_mouse_up_capture(
+        mut self,
+        button: MouseButton,
+        listener: impl Fn(&MouseUpEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.interactivity().on_mouse_up_capture(button, listener);
+        self
+    }
+
     /// Bind the given callback to the mouse up event for any button, during the capture phase.
     /// The fluent API equivalent to [`Interactivity::capture_any_mouse_up`].
     ///
@@ -835,6 +1120,27 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Bind the given callback to pinch gestures during the bubble phase, e.g. to zoom content.
+    /// The fluent API equivalent to [`Interactivity::on_pinch`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_pinch(mut self, listener: impl Fn(&PinchEvent, &mut Window, &mut App) + 'static) -> Self {
+        self.interactivity().on_pinch(listener);
+        self
+    }
+
+    /// Bind the given callback to rotation gestures during the bubble phase.
+    /// The fluent API equivalent to [`Interactivity::on_rotate_gesture`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_rotate_gesture(
+        mut self,
+        listener: impl Fn(&RotateGestureEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.interactivity().on_rotate_gesture(listener);
+        self
+    }
+
     /// Capture the given action, before normal action dispatch can fire.
     /// The fluent API equivalent to [`Interactivity::capture_action`].
     ///
@@ -1015,6 +1321,24 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Apply hover styles, hover listeners and tooltips to this element whenever it contains the
+    /// mouse, even when an overlapping element in front of it is hovered. By default, only the
+    /// frontmost hovered element and its ancestors are treated as hovered.
+    /// The fluent API equivalent to [`Interactivity::hover_even_when_occluded`].
+    fn hover_even_when_occluded(mut self) -> Self {
+        self.interactivity().hover_even_when_occluded();
+        self
+    }
+
+    /// Set the shape of this element's hitbox within its bounds, so that the mouse only hits the
+    /// element where it's drawn. Defaults to the bounds rounded by the element's corner radii, so
+    /// that `.rounded_full()` elements are hit as circles.
+    /// The fluent API equivalent to [`Interactivity::hitbox_shape`].
+    fn hitbox_shape(mut self, shape: HitboxShape) -> Self {
+        self.interactivity().hitbox_shape(shape);
+        self
+    }
+
     /// Set the given styles to be applied when this element, specifically, is focused.
     /// Requires that the element is focusable. Elements can be made focusable using [`InteractiveElement::track_focus`].
     fn focus(mut self, f: impl FnOnce(StyleRefinement) -> StyleRefinement) -> Self
@@ -1076,6 +1400,25 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
+    /// Settle the scroll offset along the given axes on the nearest of the children marked with
+    /// [`Styled::snap_point`], aligned with this element by `align`, once scrolling by hand
+    /// stops. Programmatic scrolls through a tracked [`ScrollHandle`] with
+    /// [`ScrollHandle::scroll_to_animated`] or [`ScrollHandle::scroll_to_item`] also land on
+    /// the nearest snap point, while [`ScrollHandle::set_offset`] is left exact.
+    fn scroll_snap(mut self, axis: SnapAxis, align: SnapAlign) -> Self {
+        self.interactivity().base_style.scroll_snap = Some(ScrollSnap { axis, align });
+        self
+    }
+
+    /// Set whether scrolling past the edge of this element scrolls the scroll container it's in,
+    /// which it does by default. Use [`OverscrollBehavior::Contain`] to keep scrolling over a
+    /// modal list from scrolling the page behind it.
+    /// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/overscroll-behavior)
+    fn overscroll_behavior(mut self, behavior: OverscrollBehavior) -> Self {
+        self.interactivity().base_style.overscroll_behavior = Some(behavior);
+        self
+    }
+
     /// Set the space to be reserved for rendering the scrollbar.
     ///
     /// This will only affect the layout of the element when overflow for this element is set to
@@ -1122,6 +1465,20 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
+    /// Set the cursor style to use in place of the one set with `cursor` while this element is
+    /// pressed, and while it's being dragged.
+    fn cursor_when_active(mut self, cursor: CursorStyle) -> Self {
+        self.interactivity().active_cursor = Some(cursor);
+        self
+    }
+
+    /// Set the cursor style to use in place of the one set with `cursor` while this element is
+    /// disabled, see [`InteractiveElement::disabled`].
+    fn cursor_when_disabled(mut self, cursor: CursorStyle) -> Self {
+        self.interactivity().disabled_cursor = Some(cursor);
+        self
+    }
+
     /// Bind the given callback to click events of this element.
     /// The fluent API equivalent to [`Interactivity::on_click`].
     ///
@@ -1134,6 +1491,101 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
+    /// Bind the given callback to double clicks of this element. [`Self::on_click`] listeners
+    /// still fire for each click.
+    /// The fluent API equivalent to [`Interactivity::on_double_click`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_double_click(
+        mut self,
+        listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().on_double_click(listener);
+        self
+    }
+
+    /// Bind the given callback to triple clicks of this element.
+    /// The fluent API equivalent to [`Interactivity::on_triple_click`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_triple_click(
+        mut self,
+        listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().on_triple_click(listener);
+        self
+    }
+
+    /// Bind the given callback to secondary clicks of this element, which are a press and release
+    /// of the right mouse button over it.
+    /// The fluent API equivalent to [`Interactivity::on_secondary_click`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_secondary_click(
+        mut self,
+        listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().on_secondary_click(listener);
+        self
+    }
+
+    /// Bind the given callback to middle clicks of this element.
+    /// The fluent API equivalent to [`Interactivity::on_middle_click`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_middle_click(
+        mut self,
+        listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().on_middle_click(listener);
+        self
+    }
+
+    /// Report left clicks made while holding control as secondary clicks on macOS.
+    /// The fluent API equivalent to [`Interactivity::ctrl_click_as_secondary`].
+    fn ctrl_click_as_secondary(mut self, enabled: bool) -> Self {
+        self.interactivity().ctrl_click_as_secondary(enabled);
+        self
+    }
+
+    /// Bind the given callback to long presses of this element, which are reported once the left
+    /// mouse button or a touch has been held down for the given delay, for example to open a
+    /// context menu on touch devices. The press is canceled if it's released, moves further than
+    /// the slop set with [`Self::long_press_slop`], or scrolls.
+    /// The fluent API equivalent to [`Interactivity::on_long_press`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_long_press(
+        mut self,
+        delay: Duration,
+        listener: impl Fn(&LongPressEvent, &mut Window, &mut App) + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().on_long_press(delay, listener);
+        self
+    }
+
+    /// Set how far a long press can move before it's canceled, which defaults to 8 pixels.
+    /// The fluent API equivalent to [`Interactivity::long_press_slop`].
+    fn long_press_slop(mut self, slop: Pixels) -> Self {
+        self.interactivity().long_press_slop(slop);
+        self
+    }
+
     /// On drag initiation, this callback will be used to create a new view to render the dragged value for a
     /// drag and drop operation. This API should also be used as the equivalent of 'on drag start' with
     /// the [`InteractiveElement::on_drag_move`] API.
@@ -1155,6 +1607,14 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
+    /// Drag the given files out of the window, e.g. into Finder or Explorer, once the pointer is
+    /// pressed on this element and moves past the drag threshold.
+    /// The fluent API equivalent to [`Interactivity::on_drag_out`].
+    fn on_drag_out(mut self, paths: Vec<PathBuf>) -> Self {
+        self.interactivity().on_drag_out(paths);
+        self
+    }
+
     /// Bind the given callback on the hover start and end events of this element. Note that the boolean
     /// passed to the callback is true when the hover starts and false when it ends.
     /// The fluent API equivalent to [`Interactivity::on_hover`].
@@ -1168,6 +1628,26 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
+    /// Bind the given callback on the hover start and end events of this element, like
+    /// [`Self::on_hover`], but only call it once the mouse has stayed over the element for the
+    /// given delay, so that passing over the element doesn't flicker. Hovers that end before the
+    /// delay has passed don't call the callback, and the end of a hover that started is reported
+    /// immediately.
+    /// The fluent API equivalent to [`Interactivity::on_hover_with_delay`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_hover_with_delay(
+        mut self,
+        delay: Duration,
+        listener: impl Fn(&bool, &mut Window, &mut App) + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().on_hover_with_delay(delay, listener);
+        self
+    }
+
     /// Use the given callback to construct a new tooltip view when the mouse hovers over this element.
     /// The fluent API equivalent to [`Interactivity::tooltip`].
     fn tooltip(mut self, build_tooltip: impl Fn(&mut Window, &mut App) -> AnyView + 'static) -> Self
@@ -1204,8 +1684,16 @@ pub(crate) type MouseMoveListener =
 pub(crate) type ScrollWheelListener =
     Box<dyn Fn(&ScrollWheelEvent, DispatchPhase, &Hitbox, &mut Window, &mut App) + 'static>;
 
+pub(crate) type PinchListener =
+    Box<dyn Fn(&PinchEvent, DispatchPhase, &Hitbox, &mut Window, &mut App) + 'static>;
+
+pub(crate) type RotateGestureListener =
+    Box<dyn Fn(&RotateGestureEvent, DispatchPhase, &Hitbox, &mut Window, &mut App) + 'static>;
+
 pub(crate) type ClickListener = Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
 
+pub(crate) type LongPressListener = Rc<dyn Fn(&LongPressEvent, &mut Window, &mut App) + 'static>;
+
 pub(crate) type DragListener =
     Box<dyn Fn(&dyn Any, Point<Pixels>, &mut Window, &mut App) -> AnyView + 'static>;
 
@@ -1503,6 +1991,9 @@ pub struct Interactivity {
     pub(crate) tracked_scroll_handle: Option<ScrollHandle>,
     pub(crate) scroll_anchor: Option<ScrollAnchor>,
     pub(crate) scroll_offset: Option<Rc<RefCell<Point<Pixels>>>>,
+    pub(crate) scroll_snap_state: Option<Rc<RefCell<ScrollSnapState>>>,
+    /// The maximum scroll offset, from the last prepaint.
+    pub(crate) scroll_max: Size<Pixels>,
     pub(crate) group: Option<SharedString>,
     /// The base style of the element, before any modifications are applied
     /// by focus, active, etc.
@@ -1514,6 +2005,7 @@ pub struct Interactivity {
     pub(crate) group_hover_style: Option<GroupStyle>,
     pub(crate) active_style: Option<Box<StyleRefinement>>,
     pub(crate) group_active_style: Option<GroupStyle>,
+    pub(crate) disabled_style: Option<Box<StyleRefinement>>,
     pub(crate) drag_over_styles: Vec<(
         TypeId,
         Box<dyn Fn(&dyn Any, &mut Window, &mut App) -> StyleRefinement>,
@@ -1523,6 +2015,8 @@ pub struct Interactivity {
     pub(crate) mouse_up_listeners: Vec<MouseUpListener>,
     pub(crate) mouse_move_listeners: Vec<MouseMoveListener>,
     pub(crate) scroll_wheel_listeners: Vec<ScrollWheelListener>,
+    pub(crate) pinch_listeners: Vec<PinchListener>,
+    pub(crate) rotate_gesture_listeners: Vec<RotateGestureListener>,
     pub(crate) key_down_listeners: Vec<KeyDownListener>,
     pub(crate) key_up_listeners: Vec<KeyUpListener>,
     pub(crate) modifiers_changed_listeners: Vec<ModifiersChangedListener>,
@@ -1530,14 +2024,28 @@ pub struct Interactivity {
     pub(crate) drop_listeners: Vec<(TypeId, DropListener)>,
     pub(crate) can_drop_predicate: Option<CanDropPredicate>,
     pub(crate) click_listeners: Vec<ClickListener>,
+    pub(crate) secondary_click_listeners: Vec<ClickListener>,
+    pub(crate) middle_click_listeners: Vec<ClickListener>,
+    pub(crate) ctrl_click_as_secondary: bool,
+    pub(crate) long_press_listener: Option<(Duration, LongPressListener)>,
+    pub(crate) long_press_slop: Option<Pixels>,
     pub(crate) drag_listener: Option<(Arc<dyn Any>, DragListener)>,
+    pub(crate) drag_out_paths: Option<Vec<PathBuf>>,
     pub(crate) hover_listener: Option<Box<dyn Fn(&bool, &mut Window, &mut App)>>,
+    pub(crate) hover_delay: Option<Duration>,
     pub(crate) tooltip_builder: Option<TooltipBuilder>,
     pub(crate) window_control: Option<WindowControlArea>,
     pub(crate) hitbox_behavior: HitboxBehavior,
+    pub(crate) hitbox_shape: Option<HitboxShape>,
+    pub(crate) hover_when_occluded: bool,
     pub(crate) tab_index: Option<isize>,
     pub(crate) tab_group: bool,
+    pub(crate) tab_scope: bool,
     pub(crate) tab_stop: bool,
+    pub(crate) activate_on_key: Option<bool>,
+    pub(crate) active_cursor: Option<CursorStyle>,
+    pub(crate) disabled_cursor: Option<CursorStyle>,
+    pub(crate) disabled: bool,
 
     #[cfg(any(feature = "inspector", debug_assertions))]
     pub(crate) source_location: Option<&'static core::panic::Location<'static>>,
@@ -1682,54 +2190,164 @@ impl Interactivity {
                     }
                 }
 
-                window.with_text_style(style.text_style().cloned(), |window| {
-                    window.with_content_mask(
-                        style.overflow_mask(bounds, window.rem_size()),
-                        |window| {
-                            let hitbox = if self.should_insert_hitbox(&style, window, cx) {
-                                Some(window.insert_hitbox(bounds, self.hitbox_behavior))
-                            } else {
-                                None
-                            };
+                self.scroll_snap_state = match (style.scroll_snap, element_state.as_mut()) {
+                    (Some(snap), Some(element_state)) if self.scroll_offset.is_some() => {
+                        let snap_state = element_state
+                            .scroll_snap
+                            .get_or_insert_with(|| {
+                                Rc::new(RefCell::new(ScrollSnapState::new(snap)))
+                            })
+                            .clone();
+                        snap_state.borrow_mut().snap = snap;
+                        Some(snap_state)
+                    }
+                    _ => None,
+                };
+                if style.snap_point {
+                    window.insert_snap_point(bounds);
+                }
 
-                            let scroll_offset =
-                                self.clamp_scroll_position(bounds, &style, window, cx);
-                            let result = f(&style, scroll_offset, hitbox, window, cx);
-                            (result, element_state)
-                        },
-                    )
-                })
-            },
-        )
-    }
+                style.with_clip_path(bounds, window, |window| {
+                    window.with_text_style(style.text_style().cloned(), |window| {
+                        window.with_content_mask(
+                            style.overflow_mask(bounds, window.rem_size()),
+                            |window| {
+                                let hitbox = if self.should_insert_hitbox(&style, window, cx) {
+                                    let shape = self.resolved_hitbox_shape(bounds, &style, window);
+                                    Some(window.insert_shaped_hitbox(
+                                        bounds,
+                                        self.hitbox_behavior,
+                                        shape,
+                                    ))
+                                } else {
+                                    None
+                                };
+
+                                let scroll_offset =
+                                    self.clamp_scroll_position(bounds, &style, window, cx);
+                                let hitbox_id = hitbox.as_ref().map(|hitbox| hitbox.id);
+                                let prepaint_contents = |window: &mut Window| {
+                                    window.with_hitbox_parent(hitbox_id, |window| {
+                                        f(&style, scroll_offset, hitbox, window, cx)
+                                    })
+                                };
+                                // Snap points belong to the innermost scroll container.
+                                let result = if self.scroll_offset.is_some() {
+                                    let (result, snap_points) = window.with_snap_points(
+                                        self.scroll_snap_state.is_some(),
+                                        prepaint_contents,
+                                    );
+                                    if let Some(snap_state) = self.scroll_snap_state.as_ref() {
+                                        snap_state.borrow_mut().set_snap_points(
+                                            bounds,
+                                            scroll_offset,
+                                            &snap_points,
+                                        );
+                                    }
+                                    result
+                                } else {
+                                    prepaint_contents(window)
+                                };
+                                (result, element_state)
+                            },
+                        )
+                    })
+                })
+            },
+        )
+    }
+
+    /// The shape set with [`Interactivity::hitbox_shape`], or the bounds rounded by the element's
+    /// corner radii.
+    fn resolved_hitbox_shape(
+        &self,
+        bounds: Bounds<Pixels>,
+        style: &Style,
+        window: &Window,
+    ) -> HitboxShape {
+        if let Some(shape) = &self.hitbox_shape {
+            return shape.clone();
+        }
+        let corner_radii = style
+            .corner_radii
+            .to_pixels(window.rem_size())
+            .clamp_radii_for_quad_size(bounds.size);
+        if corner_radii == Corners::default() {
+            HitboxShape::Rect
+        } else {
+            HitboxShape::RoundedRect(corner_radii)
+        }
+    }
+
+    /// The cursor for the element's current state: the disabled cursor (defaulting to
+    /// [`CursorStyle::OperationNotAllowed`]) while disabled, the active cursor while pressed, and
+    /// otherwise the cursor of the style.
+    fn mouse_cursor(
+        &self,
+        style: &Style,
+        element_state: Option<&InteractiveElementState>,
+    ) -> Option<CursorStyle> {
+        if self.disabled {
+            return Some(
+                self.disabled_cursor
+                    .unwrap_or(CursorStyle::OperationNotAllowed),
+            );
+        }
+        let is_pressed = element_state.is_some_and(|element_state| {
+            element_state
+                .clicked_state
+                .as_ref()
+                .is_some_and(|clicked_state| clicked_state.borrow().element)
+                || element_state
+                    .pending_mouse_down
+                    .as_ref()
+                    .is_some_and(|pending_mouse_down| pending_mouse_down.borrow().is_some())
+        });
+        if is_pressed {
+            self.active_cursor.or(style.mouse_cursor)
+        } else {
+            style.mouse_cursor
+        }
+    }
 
     fn should_insert_hitbox(&self, style: &Style, window: &Window, cx: &App) -> bool {
         self.hitbox_behavior != HitboxBehavior::Normal
             || self.window_control.is_some()
             || style.mouse_cursor.is_some()
+            || self.active_cursor.is_some()
+            || self.disabled_cursor.is_some()
+            || self.disabled
             || self.group.is_some()
             || self.scroll_offset.is_some()
             || self.tracked_focus_handle.is_some()
             || self.hover_style.is_some()
             || self.group_hover_style.is_some()
+            || self.active_style.is_some()
+            || self.group_active_style.is_some()
             || self.hover_listener.is_some()
             || !self.mouse_up_listeners.is_empty()
             || !self.mouse_down_listeners.is_empty()
             || !self.mouse_move_listeners.is_empty()
             || !self.click_listeners.is_empty()
+            || !self.secondary_click_listeners.is_empty()
+            || !self.middle_click_listeners.is_empty()
+            || self.long_press_listener.is_some()
             || !self.scroll_wheel_listeners.is_empty()
+            || !self.pinch_listeners.is_empty()
+            || !self.rotate_gesture_listeners.is_empty()
             || self.drag_listener.is_some()
+            || self.drag_out_paths.is_some()
             || !self.drop_listeners.is_empty()
             || self.tooltip_builder.is_some()
             || window.is_inspector_picking(cx)
     }
 
     fn clamp_scroll_position(
-        &self,
+        &mut self,
         bounds: Bounds<Pixels>,
         style: &Style,
         window: &mut Window,
-        _cx: &mut App,
+        cx: &mut App,
     ) -> Point<Pixels> {
         fn round_to_two_decimals(pixels: Pixels) -> Pixels {
             const ROUNDING_FACTOR: f32 = 100.0;
@@ -1744,7 +2362,40 @@ impl Interactivity {
                 .map(|handle| handle.0.borrow_mut());
             if let Some(mut scroll_handle_state) = tracked_scroll_handle.as_deref_mut() {
                 scroll_handle_state.overflow = style.overflow;
+                scroll_handle_state.view =
+                    window
+                        .rendered_entity_stack
+                        .last()
+                        .map(|&view_id| ScrollHandleView {
+                            invalidator: window.invalidator.clone(),
+                            view_id,
+                        });
                 scroll_to_bottom = mem::take(&mut scroll_handle_state.scroll_to_bottom);
+                let snap_pending = mem::take(&mut scroll_handle_state.snap_pending);
+                // Programmatic scrolls land on the nearest snap point.
+                if let Some(snap_state) = self.scroll_snap_state.as_ref() {
+                    let mut snap_state = snap_state.borrow_mut();
+                    if let Some(animation) = scroll_handle_state.animation.as_mut() {
+                        if !animation.target_snapped {
+                            animation.to = snap_state.nearest(animation.to);
+                            animation.target_snapped = true;
+                        }
+                        snap_state.settle = None;
+                    }
+                    if snap_pending {
+                        let mut offset = scroll_offset.borrow_mut();
+                        *offset = snap_state.nearest(*offset);
+                    }
+                }
+                if let Some(animation) = scroll_handle_state.animation.as_mut() {
+                    let (offset, finished) = animation.offset_at(cx.background_executor().now());
+                    *scroll_offset.borrow_mut() = offset;
+                    if finished {
+                        scroll_handle_state.animation = None;
+                    } else {
+                        window.request_animation_frame();
+                    }
+                }
             }
 
             let rem_size = window.rem_size();
@@ -1760,6 +2411,31 @@ impl Interactivity {
             let scroll_max = (padded_content_size - bounds.size)
                 .map(round_to_two_decimals)
                 .max(&Default::default());
+            if let Some(snap_state) = self.scroll_snap_state.as_ref() {
+                let mut snap_state = snap_state.borrow_mut();
+                snap_state.max_offset = scroll_max;
+                if mem::take(&mut snap_state.settle_requested) {
+                    let offset = *scroll_offset.borrow();
+                    let target = snap_state.nearest(offset);
+                    if target != offset {
+                        snap_state.settle = Some(ScrollAnimation::new(
+                            offset,
+                            target,
+                            ScrollTiming::new(SNAP_SETTLE_DURATION, ease_out_quint()),
+                        ));
+                    }
+                }
+                if let Some(settle) = snap_state.settle.as_mut() {
+                    let (offset, finished) = settle.offset_at(cx.background_executor().now());
+                    *scroll_offset.borrow_mut() = offset;
+                    if finished {
+                        snap_state.settle = None;
+                    } else {
+                        window.request_animation_frame();
+                    }
+                }
+            }
+
             // Clamp scroll offset in case scroll max is smaller now (e.g., if children
             // were removed or the bounds became larger).
             let mut scroll_offset = scroll_offset.borrow_mut();
@@ -1775,6 +2451,7 @@ impl Interactivity {
                 scroll_handle_state.max_offset = scroll_max;
                 scroll_handle_state.bounds = bounds;
             }
+            self.scroll_max = scroll_max;
 
             *scroll_offset
         } else {
@@ -1800,7 +2477,9 @@ impl Interactivity {
         cx: &mut App,
         f: impl FnOnce(&Style, &mut Window, &mut App),
     ) {
-        self.hovered = hitbox.map(|hitbox| hitbox.is_hovered(window));
+        let hover_when_occluded = self.hover_when_occluded;
+        self.hovered =
+            hitbox.map(|hitbox| is_hovered_for_hover(hitbox, hover_when_occluded, window));
         window.with_optional_element_state::<InteractiveElementState, _>(
             global_id,
             |element_state, window| {
@@ -1808,6 +2487,7 @@ impl Interactivity {
                     element_state.map(|element_state| element_state.unwrap_or_default());
 
                 let style = self.compute_style_internal(hitbox, element_state.as_mut(), window, cx);
+                let mouse_cursor = self.mouse_cursor(&style, element_state.as_ref());
 
                 #[cfg(any(feature = "test-support", test))]
                 if let Some(debug_selector) = &self.debug_selector {
@@ -1817,7 +2497,7 @@ impl Interactivity {
                         .insert(debug_selector.clone(), bounds);
                 }
 
-                self.paint_hover_group_handler(window, cx);
+                self.paint_hover_group_handler(window);
 
                 if style.visibility == Visibility::Hidden {
                     return ((), element_state);
@@ -1827,71 +2507,100 @@ impl Interactivity {
                 if self.tab_group {
                     tab_group = self.tab_index;
                 }
-                if let Some(focus_handle) = &self.tracked_focus_handle {
+                if let Some(focus_handle) = &self.tracked_focus_handle
+                    && !self.disabled
+                {
                     window.next_frame.tab_stops.insert(focus_handle);
                 }
 
-                window.with_element_opacity(style.opacity, |window| {
-                    style.paint(bounds, window, cx, |window: &mut Window, cx: &mut App| {
-                        window.with_text_style(style.text_style().cloned(), |window| {
-                            window.with_content_mask(
-                                style.overflow_mask(bounds, window.rem_size()),
-                                |window| {
-                                    window.with_tab_group(tab_group, |window| {
-                                        if let Some(hitbox) = hitbox {
-                                            #[cfg(debug_assertions)]
-                                            self.paint_debug_info(
-                                                global_id, hitbox, &style, window, cx,
-                                            );
-
-                                            if let Some(drag) = cx.active_drag.as_ref() {
-                                                if let Some(mouse_cursor) = drag.cursor_style {
-                                                    window.set_window_cursor_style(mouse_cursor);
-                                                }
-                                            } else {
-                                                if let Some(mouse_cursor) = style.mouse_cursor {
-                                                    window.set_cursor_style(mouse_cursor, hitbox);
-                                                }
-                                            }
-
-                                            if let Some(group) = self.group.clone() {
-                                                GroupHitboxes::push(group, hitbox.id, cx);
-                                            }
-
-                                            if let Some(area) = self.window_control {
-                                                window.insert_window_control_hitbox(
-                                                    area,
-                                                    hitbox.clone(),
-                                                );
-                                            }
-
-                                            self.paint_mouse_listeners(
-                                                hitbox,
-                                                element_state.as_mut(),
-                                                window,
-                                                cx,
-                                            );
-                                            self.paint_scroll_listener(hitbox, &style, window, cx);
-                                        }
-
-                                        self.paint_keyboard_listeners(window, cx);
-                                        f(&style, window, cx);
-
-                                        if let Some(_hitbox) = hitbox {
-                                            #[cfg(any(feature = "inspector", debug_assertions))]
-                                            window.insert_inspector_hitbox(
-                                                _hitbox.id,
-                                                _inspector_id,
-                                                cx,
-                                            );
-
-                                            if let Some(group) = self.group.as_ref() {
-                                                GroupHitboxes::pop(group, cx);
-                                            }
-                                        }
-                                    })
-                                },
-                            );
+                window.with_element_blend_mode(style.blend_mode, |window| {
+                    window.with_element_opacity(style.opacity, |window| {
+                        style.with_clip_path(bounds, window, |window| {
+                            style.paint(bounds, window, cx, |window: &mut Window, cx: &mut App| {
+                                window.with_text_style(style.text_style().cloned(), |window| {
+                                    window.with_content_mask(
+                                        style.overflow_mask(bounds, window.rem_size()),
+                                        |window| {
+                                            window.with_optionally_scoped_tab_group(
+                                                tab_group,
+                                                self.tab_scope,
+                                                |window| {
+                                                    if let Some(hitbox) = hitbox {
+                                                        #[cfg(debug_assertions)]
+                                                        self.paint_debug_info(
+                                                            global_id, hitbox, &style, window, cx,
+                                                        );
+
+                                                        if let Some(drag) = cx.active_drag.as_ref()
+                                                        {
+                                                            if let Some(mouse_cursor) =
+                                                                drag.cursor_style
+                                                            {
+                                                                window.set_window_cursor_style(
+                                                                    mouse_cursor,
+                                                                );
+                                                            }
+                                                        } else if let Some(mouse_cursor) =
+                                                            mouse_cursor
+                                                        {
+                                                            window.set_cursor_style(
+                                                                mouse_cursor,
+                                                                hitbox,
+                                                            );
+                                                        }
+
+                                                        if let Some(group) = self.group.clone() {
+                                                            window
+                                                                .next_frame
+                                                                .group_hitboxes
+                                                                .push(group, hitbox.id);
+                                                        }
+
+                                                        if let Some(area) = self.window_control {
+                                                            window.insert_window_control_hitbox(
+                                                                area,
+                                                                hitbox.clone(),
+                                                            );
+                                                        }
+
+                                                        self.paint_mouse_listeners(
+                                                            hitbox,
+                                                            element_state.as_mut(),
+                                                            window,
+                                                            cx,
+                                                        );
+                                                        self.paint_scroll_listener(
+                                                            hitbox, &style, window, cx,
+                                                        );
+                                                    }
+
+                                                    self.paint_keyboard_listeners(window, cx);
+                                                    f(&style, window, cx);
+
+                                                    if let Some(_hitbox) = hitbox {
+                                                        #[cfg(any(
+                                                            feature = "inspector",
+                                                            debug_assertions
+                                                        ))]
+                                                        window.insert_inspector_hitbox(
+                                                            _hitbox.id,
+                                                            _inspector_id,
+                                                            cx,
+                                                        );
+
+                                                        if let Some(group) = self.group.as_ref() {
+                                                            window
+                                                                .next_frame
+                                                                .group_hitboxes
+                                                                .pop(group);
+                                                        }
+                                                    }
+                                                },
+                                            )
+                                        },
+                                    );
+                                });
+                            });
                         });
                     });
                 });
@@ -1910,7 +2619,7 @@ impl Interactivity {
         window: &mut Window,
         cx: &mut App,
     ) {
-        use crate::{BorderStyle, TextAlign};
+        use crate::{BorderStyle, TextAlign, TextDirection};
 
         if global_id.is_some()
             && (style.debug || style.debug_below || cx.has_global::<crate::DebugBelow>())
@@ -1927,7 +2636,9 @@ impl Interactivity {
                         element_id.into(),
                         FONT_SIZE,
                         &[window.text_style().to_run(str_len)],
+                        TextDirection::Ltr,
                         None,
+                        Default::default(),
                         None,
                     )
                     .ok()
@@ -2078,25 +2789,55 @@ impl Interactivity {
             })
         }
 
+        for listener in self.pinch_listeners.drain(..) {
+            let hitbox = hitbox.clone();
+            window.on_mouse_event(move |event: &PinchEvent, phase, window, cx| {
+                listener(event, phase, &hitbox, window, cx);
+            })
+        }
+
+        for listener in self.rotate_gesture_listeners.drain(..) {
+            let hitbox = hitbox.clone();
+            window.on_mouse_event(move |event: &RotateGestureEvent, phase, window, cx| {
+                listener(event, phase, &hitbox, window, cx);
+            })
+        }
+
         if self.hover_style.is_some()
             || self.base_style.mouse_cursor.is_some()
+            || self.active_cursor.is_some()
+            || self.disabled_cursor.is_some()
             || cx.active_drag.is_some() && !self.drag_over_styles.is_empty()
         {
             let hitbox = hitbox.clone();
-            let was_hovered = hitbox.is_hovered(window);
+            let hover_when_occluded = self.hover_when_occluded;
+            let was_hovered = is_hovered_for_hover(&hitbox, hover_when_occluded, window);
             let current_view = window.current_view();
             window.on_mouse_event(move |_: &MouseMoveEvent, phase, window, cx| {
-                let hovered = hitbox.is_hovered(window);
+                let hovered = is_hovered_for_hover(&hitbox, hover_when_occluded, window);
                 if phase == DispatchPhase::Capture && hovered != was_hovered {
                     cx.notify(current_view);
                 }
             });
         }
-        let drag_cursor_style = self.base_style.as_ref().mouse_cursor;
+        let drag_cursor_style = self.active_cursor.or(self.base_style.as_ref().mouse_cursor);
+
+        if self.disabled {
+            self.click_listeners.clear();
+            self.secondary_click_listeners.clear();
+            self.middle_click_listeners.clear();
+            self.long_press_listener = None;
+            self.drag_listener = None;
+            self.drag_out_paths = None;
+        }
 
         let mut drag_listener = mem::take(&mut self.drag_listener);
+        let drag_out_paths = mem::take(&mut self.drag_out_paths);
         let drop_listeners = mem::take(&mut self.drop_listeners);
-        let click_listeners = mem::take(&mut self.click_listeners);
+        let mut click_listeners = mem::take(&mut self.click_listeners);
+        let mut secondary_click_listeners = mem::take(&mut self.secondary_click_listeners);
+        let mut middle_click_listeners = mem::take(&mut self.middle_click_listeners);
+        let ctrl_click_as_secondary = self.ctrl_click_as_secondary && cfg!(target_os = "macos");
         let can_drop_predicate = mem::take(&mut self.can_drop_predicate);
 
         if !drop_listeners.is_empty() {
@@ -2133,7 +2874,12 @@ impl Interactivity {
         }
 
         if let Some(element_state) = element_state {
-            if !click_listeners.is_empty() || drag_listener.is_some() {
+            if !click_listeners.is_empty()
+                || !secondary_click_listeners.is_empty()
+                || !middle_click_listeners.is_empty()
+                || drag_listener.is_some()
+                || drag_out_paths.is_some()
+            {
                 let pending_mouse_down = element_state
                     .pending_mouse_down
                     .get_or_insert_with(Default::default)
@@ -2144,12 +2890,20 @@ impl Interactivity {
                     .get_or_insert_with(Default::default)
                     .clone();
 
+                let presses_secondary = !secondary_click_listeners.is_empty();
+                let presses_middle = !middle_click_listeners.is_empty();
                 window.on_mouse_event({
                     let pending_mouse_down = pending_mouse_down.clone();
                     let hitbox = hitbox.clone();
                     move |event: &MouseDownEvent, phase, window, _cx| {
+                        let is_pressable_button = match event.button {
+                            MouseButton::Left => true,
+                            MouseButton::Right => presses_secondary,
+                            MouseButton::Middle => presses_middle,
+                            MouseButton::Navigate(_) => false,
+                        };
                         if phase == DispatchPhase::Bubble
-                            && event.button == MouseButton::Left
+                            && is_pressable_button
                             && hitbox.is_hovered(window)
                         {
                             *pending_mouse_down.borrow_mut() = Some(event.clone());
@@ -2168,6 +2922,14 @@ impl Interactivity {
 
                         let mut pending_mouse_down = pending_mouse_down.borrow_mut();
                         if let Some(mouse_down) = pending_mouse_down.clone()
+                            && mouse_down.button != MouseButton::Left
+                            && (event.position - mouse_down.position).magnitude() > DRAG_THRESHOLD
+                        {
+                            // Only the left button drags, but moving far enough still cancels the
+                            // click of the other buttons.
+                            pending_mouse_down.take();
+                            window.refresh();
+                        } else if let Some(mouse_down) = pending_mouse_down.clone()
                             && !cx.has_active_drag()
                             && (event.position - mouse_down.position).magnitude() > DRAG_THRESHOLD
                             && let Some((drag_value, drag_listener)) = drag_listener.take()
@@ -2185,11 +2947,46 @@ impl Interactivity {
                             pending_mouse_down.take();
                             window.refresh();
                             cx.stop_propagation();
+                        } else if let Some(mouse_down) = pending_mouse_down.clone()
+                            && !cx.has_active_drag()
+                            && (event.position - mouse_down.position).magnitude() > DRAG_THRESHOLD
+                            && let Some(paths) = drag_out_paths.as_ref()
+                        {
+                            // The platform takes over the drag and swallows the mouse up, so
+                            // forget the press rather than reporting a click later.
+                            *clicked_state.borrow_mut() = ElementClickedState::default();
+                            pending_mouse_down.take();
+                            if window.start_drag_out(paths, hitbox.bounds) {
+                                cx.stop_propagation();
+                            }
+                            window.refresh();
                         }
                     }
                 });
 
-                if is_focused {
+                let pending_space_press = element_state
+                    .pending_space_press
+                    .get_or_insert_with(Default::default)
+                    .clone();
+                if !is_focused || !self.activate_on_key.unwrap_or(true) {
+                    pending_space_press.set(false);
+                } else {
+                    // Like native buttons, space only activates once it's released after being
+                    // pressed on this element, and its key repeats are ignored.
+                    window.on_key_event({
+                        let pending_space_press = pending_space_press.clone();
+                        move |event: &KeyDownEvent, phase, window, _cx| {
+                            if phase.bubble()
+                                && !window.default_prevented()
+                                && !event.is_held
+                                && event.keystroke.key.eq("space")
+                                && !event.keystroke.modifiers.modified()
+                            {
+                                pending_space_press.set(true);
+                            }
+                        }
+                    });
+
                     // Press enter, space to trigger click, when the element is focused.
                     window.on_key_event({
                         let click_listeners = click_listeners.clone();
@@ -2199,7 +2996,7 @@ impl Interactivity {
                                 let stroke = &event.keystroke;
                                 let keyboard_button = if stroke.key.eq("enter") {
                                     Some(KeyboardButton::Enter)
-                                } else if stroke.key.eq("space") {
+                                } else if stroke.key.eq("space") && pending_space_press.take() {
                                     Some(KeyboardButton::Space)
                                 } else {
                                     None
@@ -2231,10 +3028,14 @@ impl Interactivity {
                         // propagation.
                         DispatchPhase::Capture => {
                             let mut pending_mouse_down = pending_mouse_down.borrow_mut();
-                            if pending_mouse_down.is_some() && hitbox.is_hovered(window) {
+                            // Releasing another button doesn't end the press.
+                            let is_pressed_button = pending_mouse_down
+                                .as_ref()
+                                .is_some_and(|mouse_down| mouse_down.button == event.button);
+                            if is_pressed_button && hitbox.is_hovered(window) {
                                 captured_mouse_down = pending_mouse_down.take();
                                 window.refresh();
-                            } else if pending_mouse_down.is_some() {
+                            } else if is_pressed_button {
                                 // Clear the pending mouse down event (without firing click handlers)
                                 // if the hitbox is not being hovered.
                                 // This avoids dragging elements that changed their position
@@ -2247,11 +3048,23 @@ impl Interactivity {
                         // Fire click handlers during the bubble phase.
                         DispatchPhase::Bubble => {
                             if let Some(mouse_down) = captured_mouse_down.take() {
+                                let listeners = match mouse_down.button {
+                                    MouseButton::Left
+                                        if ctrl_click_as_secondary
+                                            && mouse_down.modifiers.control =>
+                                    {
+                                        &secondary_click_listeners
+                                    }
+                                    MouseButton::Left => &click_listeners,
+                                    MouseButton::Right => &secondary_click_listeners,
+                                    MouseButton::Middle => &middle_click_listeners,
+                                    MouseButton::Navigate(_) => return,
+                                };
                                 let mouse_click = ClickEvent::Mouse(MouseClickEvent {
                                     down: mouse_down,
                                     up: event.clone(),
                                 });
-                                for listener in &click_listeners {
+                                for listener in listeners {
                                     listener(&mouse_click, window, cx);
                                 }
                             }
@@ -2261,6 +3074,10 @@ impl Interactivity {
             }
 
             if let Some(hover_listener) = self.hover_listener.take() {
+                let hover_listener: Rc<dyn Fn(&bool, &mut Window, &mut App)> =
+                    Rc::from(hover_listener);
+                let hover_delay = self.hover_delay;
+                let hover_when_occluded = self.hover_when_occluded;
                 let hitbox = hitbox.clone();
                 let was_hovered = element_state
                     .hover_state
@@ -2270,6 +3087,10 @@ impl Interactivity {
                     .pending_mouse_down
                     .get_or_insert_with(Default::default)
                     .clone();
+                let delayed_hover = element_state
+                    .delayed_hover
+                    .get_or_insert_with(Default::default)
+                    .clone();
 
                 window.on_mouse_event(move |_: &MouseMoveEvent, phase, window, cx| {
                     if phase != DispatchPhase::Bubble {
@@ -2277,14 +3098,111 @@ impl Interactivity {
                     }
                     let is_hovered = has_mouse_down.borrow().is_none()
                         && !cx.has_active_drag()
-                        && hitbox.is_hovered(window);
+                        && is_hovered_for_hover(&hitbox, hover_when_occluded, window);
                     let mut was_hovered = was_hovered.borrow_mut();
 
                     if is_hovered != *was_hovered {
                         *was_hovered = is_hovered;
                         drop(was_hovered);
 
-                        hover_listener(&is_hovered, window, cx);
+                        let Some(hover_delay) = hover_delay else {
+                            hover_listener(&is_hovered, window, cx);
+                            return;
+                        };
+                        if is_hovered {
+                            let task = window.spawn(cx, {
+                                let hover_listener = hover_listener.clone();
+                                let delayed_hover = delayed_hover.clone();
+                                async move |cx| {
+                                    cx.background_executor().timer(hover_delay).await;
+                                    cx.update(|window, cx| {
+                                        delayed_hover.borrow_mut().started = true;
+                                        hover_listener(&true, window, cx);
+                                    })
+                                    .ok();
+                                }
+                            });
+                            *delayed_hover.borrow_mut() = DelayedHoverState {
+                                pending_start: Some(task),
+                                started: false,
+                            };
+                        } else {
+                            // Dropping the pending start cancels it if the delay hasn't passed.
+                            let started = mem::take(&mut *delayed_hover.borrow_mut()).started;
+                            if started {
+                                hover_listener(&false, window, cx);
+                            }
+                        }
+                    }
+                });
+            }
+
+            if let Some((delay, long_press_listener)) = self.long_press_listener.take() {
+                let slop = self.long_press_slop.unwrap_or(DEFAULT_LONG_PRESS_SLOP);
+                let pending_long_press = element_state
+                    .pending_long_press
+                    .get_or_insert_with(Default::default)
+                    .clone();
+
+                window.on_mouse_event({
+                    let pending_long_press = pending_long_press.clone();
+                    let hitbox = hitbox.clone();
+                    move |event: &MouseDownEvent, phase, window, cx| {
+                        if phase != DispatchPhase::Bubble
+                            || event.button != MouseButton::Left
+                            || !hitbox.is_hovered(window)
+                        {
+                            return;
+                        }
+                        let long_press = LongPressEvent {
+                            position: event.position,
+                            modifiers: event.modifiers,
+                            pointer_kind: event.pointer_kind,
+                        };
+                        let task = window.spawn(cx, {
+                            let long_press_listener = long_press_listener.clone();
+                            let pending_long_press = pending_long_press.clone();
+                            async move |cx| {
+                                cx.background_executor().timer(delay).await;
+                                cx.update(|window, cx| {
+                                    pending_long_press.borrow_mut().take();
+                                    long_press_listener(&long_press, window, cx);
+                                })
+                                .ok();
+                            }
+                        });
+                        *pending_long_press.borrow_mut() = Some(PendingLongPress {
+                            position: event.position,
+                            _task: task,
+                        });
+                    }
+                });
+                window.on_mouse_event({
+                    let pending_long_press = pending_long_press.clone();
+                    move |event: &MouseMoveEvent, phase, _, _| {
+                        if phase != DispatchPhase::Capture {
+                            return;
+                        }
+                        let moved_too_far =
+                            pending_long_press.borrow().as_ref().is_some_and(|pending| {
+                                (event.position - pending.position).magnitude() > slop.0 as f64
+                            });
+                        if moved_too_far {
+                            pending_long_press.borrow_mut().take();
+                        }
+                    }
+                });
+                window.on_mouse_event({
+                    let pending_long_press = pending_long_press.clone();
+                    move |_: &MouseUpEvent, phase, _, _| {
+                        if phase == DispatchPhase::Capture {
+                            pending_long_press.borrow_mut().take();
+                        }
+                    }
+                });
+                window.on_mouse_event(move |_: &ScrollWheelEvent, phase, _, _| {
+                    if phase == DispatchPhase::Capture {
+                        pending_long_press.borrow_mut().take();
                     }
                 });
             }
@@ -2314,8 +3232,10 @@ impl Interactivity {
                 });
                 let check_is_hovered = Rc::new({
                     let hitbox = hitbox.clone();
+                    let hover_when_occluded = self.hover_when_occluded;
                     move |window: &Window| {
-                        pending_mouse_down.borrow().is_none() && hitbox.is_hovered(window)
+                        pending_mouse_down.borrow().is_none()
+                            && is_hovered_for_hover(&hitbox, hover_when_occluded, window)
                     }
                 });
                 register_tooltip_mouse_handlers(
@@ -2340,10 +3260,10 @@ impl Interactivity {
                     }
                 });
             } else {
-                let active_group_hitbox = self
-                    .group_active_style
-                    .as_ref()
-                    .and_then(|group_active| GroupHitboxes::get(&group_active.group, cx));
+                let active_group_hitbox =
+                    self.group_active_style.as_ref().and_then(|group_active| {
+                        window.next_frame.group_hitboxes.get(&group_active.group)
+                    });
                 let hitbox = hitbox.clone();
                 window.on_mouse_event(move |_: &MouseDownEvent, phase, window, _cx| {
                     if phase == DispatchPhase::Bubble && !window.default_prevented() {
@@ -2364,8 +3284,12 @@ impl Interactivity {
     }
 
     fn paint_keyboard_listeners(&mut self, window: &mut Window, _cx: &mut App) {
-        let key_down_listeners = mem::take(&mut self.key_down_listeners);
-        let key_up_listeners = mem::take(&mut self.key_up_listeners);
+        let mut key_down_listeners = mem::take(&mut self.key_down_listeners);
+        let mut key_up_listeners = mem::take(&mut self.key_up_listeners);
+        if self.disabled {
+            key_down_listeners.clear();
+            key_up_listeners.clear();
+        }
         let modifiers_changed_listeners = mem::take(&mut self.modifiers_changed_listeners);
         let action_listeners = mem::take(&mut self.action_listeners);
         if let Some(context) = self.key_context.clone() {
@@ -2395,17 +3319,17 @@ impl Interactivity {
         }
     }
 
-    fn paint_hover_group_handler(&self, window: &mut Window, cx: &mut App) {
+    fn paint_hover_group_handler(&self, window: &mut Window) {
         let group_hitbox = self
             .group_hover_style
             .as_ref()
-            .and_then(|group_hover| GroupHitboxes::get(&group_hover.group, cx));
+            .and_then(|group_hover| window.next_frame.group_hitboxes.get(&group_hover.group));
 
         if let Some(group_hitbox) = group_hitbox {
-            let was_hovered = group_hitbox.is_hovered(window);
+            let was_hovered = group_hitbox.is_hovered_unoccluded(window);
             let current_view = window.current_view();
             window.on_mouse_event(move |_: &MouseMoveEvent, phase, window, cx| {
-                let hovered = group_hitbox.is_hovered(window);
+                let hovered = group_hitbox.is_hovered_unoccluded(window);
                 if phase == DispatchPhase::Capture && hovered != was_hovered {
                     cx.notify(current_view);
                 }
@@ -2424,29 +3348,39 @@ impl Interactivity {
             let overflow = style.overflow;
             let allow_concurrent_scroll = style.allow_concurrent_scroll;
             let restrict_scroll_to_axis = style.restrict_scroll_to_axis;
+            let overscroll_behavior = style.overscroll_behavior;
+            let scroll_max = self.scroll_max;
             let line_height = window.line_height();
             let hitbox = hitbox.clone();
+            let tracked_scroll_handle = self.tracked_scroll_handle.clone();
+            let scroll_snap_state = self.scroll_snap_state.clone();
             let current_view = window.current_view();
             window.on_mouse_event(move |event: &ScrollWheelEvent, phase, window, cx| {
                 if phase == DispatchPhase::Bubble && hitbox.should_handle_scroll(window) {
-                    let mut scroll_offset = scroll_offset.borrow_mut();
-                    let old_scroll_offset = *scroll_offset;
-                    let delta = event.delta.pixel_delta(line_height);
+                    // The scroll containers under the mouse handle the event from the innermost
+                    // out, each scrolling by what it can of the delta that's left along each axis.
+                    let delta = window.unconsumed_scroll_delta.unwrap_or_else(|| {
+                        event.delta.pixel_delta_in_page(line_height, hitbox.size)
+                    });
 
                     let mut delta_x = Pixels::ZERO;
+                    let mut delta_x_axis = Axis::Horizontal;
                     if overflow.x == Overflow::Scroll {
                         if !delta.x.is_zero() {
                             delta_x = delta.x;
                         } else if !restrict_scroll_to_axis && overflow.y != Overflow::Scroll {
                             delta_x = delta.y;
+                            delta_x_axis = Axis::Vertical;
                         }
                     }
                     let mut delta_y = Pixels::ZERO;
+                    let mut delta_y_axis = Axis::Vertical;
                     if overflow.y == Overflow::Scroll {
                         if !delta.y.is_zero() {
                             delta_y = delta.y;
                         } else if !restrict_scroll_to_axis && overflow.x != Overflow::Scroll {
                             delta_y = delta.x;
+                            delta_y_axis = Axis::Horizontal;
                         }
                     }
                     if !allow_concurrent_scroll && !delta_x.is_zero() && !delta_y.is_zero() {
@@ -2456,11 +3390,47 @@ impl Interactivity {
                             delta_x = Pixels::ZERO;
                         }
                     }
-                    scroll_offset.y += delta_y;
-                    scroll_offset.x += delta_x;
+
+                    // Events that only report a phase, like the end of momentum scrolling, reach
+                    // every container.
+                    let phase_only = event.delta_in_pixels(line_height) == Point::default();
+                    if delta_x.is_zero() && delta_y.is_zero() && !phase_only {
+                        if overscroll_behavior == OverscrollBehavior::Contain {
+                            window.unconsumed_scroll_delta = Some(Point::default());
+                        }
+                        return;
+                    }
+
+                    // Scrolling by hand takes over from any animated scroll in flight.
+                    if let Some(scroll_handle) = tracked_scroll_handle.as_ref() {
+                        scroll_handle.cancel_animation();
+                    }
+                    let mut scroll_offset = scroll_offset.borrow_mut();
+                    let old_scroll_offset = *scroll_offset;
+                    scroll_offset.x = (scroll_offset.x + delta_x).clamp(-scroll_max.width, px(0.));
+                    scroll_offset.y = (scroll_offset.y + delta_y).clamp(-scroll_max.height, px(0.));
+                    let consumed = *scroll_offset - old_scroll_offset;
                     if *scroll_offset != old_scroll_offset {
                         cx.notify(current_view);
                     }
+                    drop(scroll_offset);
+
+                    window.unconsumed_scroll_delta = Some(match overscroll_behavior {
+                        OverscrollBehavior::Auto => delta
+                            .apply_along(delta_x_axis, |delta| delta - consumed.x)
+                            .apply_along(delta_y_axis, |delta| delta - consumed.y),
+                        OverscrollBehavior::Contain => Point::default(),
+                    });
+
+                    if let Some(snap_state) = scroll_snap_state.as_ref() {
+                        ScrollSnapState::handle_scroll_wheel(
+                            snap_state,
+                            event,
+                            current_view,
+                            window,
+                            cx,
+                        );
+                    }
                 }
             });
         }
@@ -2486,7 +3456,7 @@ impl Interactivity {
     fn compute_style_internal(
         &self,
         hitbox: Option<&Hitbox>,
-        element_state: Option<&mut InteractiveElementState>,
+        mut element_state: Option<&mut InteractiveElementState>,
         window: &mut Window,
         cx: &mut App,
     ) -> Style {
@@ -2517,8 +3487,9 @@ impl Interactivity {
         if let Some(hitbox) = hitbox {
             if !cx.has_active_drag() {
                 if let Some(group_hover) = self.group_hover_style.as_ref()
-                    && let Some(group_hitbox_id) = GroupHitboxes::get(&group_hover.group, cx)
-                    && group_hitbox_id.is_hovered(window)
+                    && let Some(group_hitbox_id) =
+                        window.next_frame.group_hitboxes.get(&group_hover.group)
+                    && group_hitbox_id.is_hovered_unoccluded(window)
                 {
                     style.refine(&group_hover.style);
                 }
@@ -2538,8 +3509,10 @@ impl Interactivity {
 
                 if can_drop {
                     for (state_type, group_drag_style) in &self.group_drag_over_styles {
-                        if let Some(group_hitbox_id) =
-                            GroupHitboxes::get(&group_drag_style.group, cx)
+                        if let Some(group_hitbox_id) = window
+                            .next_frame
+                            .group_hitboxes
+                            .get(&group_drag_style.group)
                             && *state_type == drag.value.as_ref().type_id()
                             && group_hitbox_id.is_hovered(window)
                         {
@@ -2560,7 +3533,7 @@ impl Interactivity {
             }
         }
 
-        if let Some(element_state) = element_state {
+        if let Some(element_state) = element_state.as_deref_mut() {
             let clicked_state = element_state
                 .clicked_state
                 .get_or_insert_with(Default::default)
@@ -2578,6 +3551,24 @@ impl Interactivity {
             }
         }
 
+        if let Some(disabled_style) = self.disabled_style.as_ref()
+            && self.disabled
+        {
+            style.refine(disabled_style);
+        }
+
+        if let Some(element_state) = element_state {
+            if style.transitions.is_empty() {
+                element_state.transitions = None;
+            } else if element_state.transitions.get_or_insert_default().apply(
+                &mut style,
+                cx.background_executor().now(),
+                window.rem_size(),
+            ) {
+                window.request_animation_frame();
+            }
+        }
+
         style
     }
 }
@@ -2589,9 +3580,134 @@ pub struct InteractiveElementState {
     pub(crate) focus_handle: Option<FocusHandle>,
     pub(crate) clicked_state: Option<Rc<RefCell<ElementClickedState>>>,
     pub(crate) hover_state: Option<Rc<RefCell<bool>>>,
+    pub(crate) delayed_hover: Option<Rc<RefCell<DelayedHoverState>>>,
+    pub(crate) pending_long_press: Option<Rc<RefCell<Option<PendingLongPress>>>>,
     pub(crate) pending_mouse_down: Option<Rc<RefCell<Option<MouseDownEvent>>>>,
+    pub(crate) pending_space_press: Option<Rc<Cell<bool>>>,
     pub(crate) scroll_offset: Option<Rc<RefCell<Point<Pixels>>>>,
+    pub(crate) scroll_snap: Option<Rc<RefCell<ScrollSnapState>>>,
     pub(crate) active_tooltip: Option<Rc<RefCell<Option<ActiveTooltip>>>>,
+    pub(crate) transitions: Option<TransitionState>,
+}
+
+/// The state of a hover listener registered with [`Interactivity::on_hover_with_delay`].
+#[derive(Default)]
+pub(crate) struct DelayedHoverState {
+    /// Reports the start of the hover once the delay has passed.
+    pending_start: Option<Task<()>>,
+    /// Whether the start of the hover was reported.
+    started: bool,
+}
+
+/// The state of a scroll container with [`StatefulInteractiveElement::scroll_snap`] set.
+pub(crate) struct ScrollSnapState {
+    snap: ScrollSnap,
+    /// The scroll offsets at which each snap point is aligned, from the last prepaint.
+    snap_offsets: Vec<Point<Pixels>>,
+    max_offset: Size<Pixels>,
+    /// Whether scrolling has stopped, so the offset should settle on the nearest snap point.
+    settle_requested: bool,
+    /// The scroll onto the nearest snap point.
+    settle: Option<ScrollAnimation>,
+    /// Requests a settle once no more scroll events arrive.
+    pending_settle: Option<Task<()>>,
+}
+
+impl ScrollSnapState {
+    fn new(snap: ScrollSnap) -> Self {
+        Self {
+            snap,
+            snap_offsets: Vec::new(),
+            max_offset: Size::default(),
+            settle_requested: false,
+            settle: None,
+            pending_settle: None,
+        }
+    }
+
+    /// Records the snap points prepainted in the container with the given bounds, which was
+    /// scrolled by `scroll_offset` at the time.
+    fn set_snap_points(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        scroll_offset: Point<Pixels>,
+        snap_points: &[Bounds<Pixels>],
+    ) {
+        self.snap_offsets.clear();
+        self.snap_offsets
+            .extend(snap_points.iter().map(|snap_point_bounds| {
+                let snap_point_bounds = Bounds {
+                    origin: snap_point_bounds.origin - scroll_offset,
+                    size: snap_point_bounds.size,
+                };
+                match self.snap.align {
+                    SnapAlign::Start => bounds.origin - snap_point_bounds.origin,
+                    SnapAlign::Center => bounds.center() - snap_point_bounds.center(),
+                    SnapAlign::End => bounds.bottom_right() - snap_point_bounds.bottom_right(),
+                }
+            }));
+    }
+
+    /// The given offset moved onto the nearest snap point along each axis that snaps.
+    fn nearest(&self, offset: Point<Pixels>) -> Point<Pixels> {
+        let mut nearest = offset;
+        for axis in [Axis::Horizontal, Axis::Vertical] {
+            if !self.snap.axis.contains(axis) {
+                continue;
+            }
+            let max_offset = self.max_offset.along(axis);
+            let current = offset.along(axis);
+            if let Some(snapped) = self
+                .snap_offsets
+                .iter()
+                .map(|snap_offset| snap_offset.along(axis).clamp(-max_offset, px(0.)))
+                .min_by_key(|snapped| (*snapped - current).abs())
+            {
+                nearest = nearest.apply_along(axis, |_| snapped);
+            }
+        }
+        nearest
+    }
+
+    /// Settles once scrolling stops: right away when momentum scrolling ends, or after a delay
+    /// for wheel ticks and scrolls that end without momentum. Any scroll cancels a settle that's
+    /// in flight.
+    fn handle_scroll_wheel(
+        this: &Rc<RefCell<Self>>,
+        event: &ScrollWheelEvent,
+        current_view: EntityId,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let mut state = this.borrow_mut();
+        state.settle = None;
+        state.settle_requested = false;
+        state.pending_settle = None;
+        match event.momentum_phase {
+            Some(TouchPhase::Ended) => {
+                state.settle_requested = true;
+                cx.notify(current_view);
+            }
+            // Momentum scrolling is still in flight, and reports when it ends.
+            Some(_) => {}
+            None => {
+                let this = this.clone();
+                state.pending_settle = Some(window.spawn(cx, async move |cx| {
+                    cx.background_executor().timer(SNAP_SETTLE_DELAY).await;
+                    this.borrow_mut().settle_requested = true;
+                    cx.update(|_, cx| cx.notify(current_view)).ok();
+                }));
+            }
+        }
+    }
+}
+
+/// A long press that's waiting for its delay to pass, see [`Interactivity::on_long_press`].
+pub(crate) struct PendingLongPress {
+    /// Where the press started.
+    position: Point<Pixels>,
+    /// Reports the long press once the delay has passed.
+    _task: Task<()>,
 }
 
 /// Whether or not the element or a group that contains it is clicked by the mouse.
@@ -2719,6 +3835,16 @@ pub(crate) fn register_tooltip_mouse_handlers(
 /// `check_is_hovered_during_prepaint` is used which bases the check off of the absolute bounds of
 /// the element.
 ///
+/// Whether the hitbox should get hover styles, hover listeners and tooltips, which by default
+/// excludes hitboxes occluded by an overlapping hitbox in front of them.
+fn is_hovered_for_hover(hitbox: &Hitbox, hover_when_occluded: bool, window: &Window) -> bool {
+    if hover_when_occluded {
+        hitbox.is_hovered(window)
+    } else {
+        hitbox.is_hovered_unoccluded(window)
+    }
+}
+
 /// TODO: There's a minor bug due to the use of absolute bounds while checking during prepaint - it
 /// does not know if the hitbox is occluded. In the case where a tooltip gets displayed and then
 /// gets occluded after display, it will stick around until the mouse exits the hover bounds.
@@ -2892,30 +4018,31 @@ fn handle_tooltip_check_visible_and_update(
     active_tooltip.borrow().is_some()
 }
 
+/// The hitboxes of the groups being painted, keyed by group name. A name refers to the innermost
+/// group with that name, so that each row of a list can be its own group.
 #[derive(Default)]
 pub(crate) struct GroupHitboxes(HashMap<SharedString, SmallVec<[HitboxId; 1]>>);
 
-impl Global for GroupHitboxes {}
-
 impl GroupHitboxes {
-    pub fn get(name: &SharedString, cx: &mut App) -> Option<HitboxId> {
-        cx.default_global::<Self>()
-            .0
+    pub fn get(&self, name: &SharedString) -> Option<HitboxId> {
+        self.0
             .get(name)
             .and_then(|bounds_stack| bounds_stack.last())
             .cloned()
     }
 
-    pub fn push(name: SharedString, hitbox_id: HitboxId, cx: &mut App) {
-        cx.default_global::<Self>()
-            .0
-            .entry(name)
-            .or_default()
-            .push(hitbox_id);
+    pub fn push(&mut self, name: SharedString, hitbox_id: HitboxId) {
+        self.0.entry(name).or_default().push(hitbox_id);
+    }
+
+    pub fn pop(&mut self, name: &SharedString) {
+        if let Some(bounds_stack) = self.0.get_mut(name) {
+            bounds_stack.pop();
+        }
     }
 
-    pub fn pop(name: &SharedString, cx: &mut App) {
-        cx.default_global::<Self>().0.get_mut(name).unwrap().pop();
+    pub fn clear(&mut self) {
+        self.0.clear();
     }
 }
 
@@ -3066,6 +4193,94 @@ struct ScrollHandleState {
     scroll_to_bottom: bool,
     overflow: Point<Overflow>,
     active_item: Option<ScrollActiveItem>,
+    animation: Option<ScrollAnimation>,
+    /// Whether the offset was just scrolled to an item, and should be moved onto the nearest
+    /// snap point if the element snaps.
+    snap_pending: bool,
+    /// The view the tracking element was last drawn in.
+    view: Option<ScrollHandleView>,
+}
+
+/// The view a scroll handle's element is drawn in, which the handle redraws when it starts
+/// scrolling on its own.
+#[derive(Clone)]
+struct ScrollHandleView {
+    invalidator: WindowInvalidator,
+    view_id: EntityId,
+}
+
+impl Debug for ScrollHandleView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScrollHandleView")
+            .field("view_id", &self.view_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The duration and easing of an animated scroll.
+#[derive(Clone)]
+pub(crate) struct ScrollTiming {
+    pub(crate) duration: Duration,
+    pub(crate) easing: Rc<dyn Fn(f32) -> f32>,
+}
+
+impl ScrollTiming {
+    pub(crate) fn new(duration: Duration, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        Self {
+            duration,
+            easing: Rc::new(easing),
+        }
+    }
+}
+
+impl Debug for ScrollTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScrollTiming")
+            .field("duration", &self.duration)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A scroll in flight from one offset to another, advanced each frame as the scrolling element
+/// is prepainted.
+#[derive(Debug)]
+pub(crate) struct ScrollAnimation {
+    from: Point<Pixels>,
+    to: Point<Pixels>,
+    /// When the animation was first advanced.
+    start: Option<Instant>,
+    timing: ScrollTiming,
+    /// Whether `to` has been moved onto the nearest snap point of a snapping container.
+    target_snapped: bool,
+}
+
+impl ScrollAnimation {
+    fn new(from: Point<Pixels>, to: Point<Pixels>, timing: ScrollTiming) -> Self {
+        Self {
+            from,
+            to,
+            start: None,
+            timing,
+            target_snapped: false,
+        }
+    }
+
+    /// The offset at the given time, and whether the animation has reached its target. The
+    /// animation starts the first time this is called.
+    fn offset_at(&mut self, now: Instant) -> (Point<Pixels>, bool) {
+        let start = *self.start.get_or_insert(now);
+        let elapsed = now.saturating_duration_since(start);
+        if elapsed >= self.timing.duration {
+            return (self.to, true);
+        }
+        let delta =
+            (self.timing.easing)(elapsed.as_secs_f32() / self.timing.duration.as_secs_f32());
+        let offset = point(
+            self.from.x + (self.to.x - self.from.x) * delta,
+            self.from.y + (self.to.y - self.from.y) * delta,
+        );
+        (offset, false)
+    }
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -3216,9 +4431,49 @@ impl ScrollHandle {
             }
             None => Some(active_item),
         };
+        state.snap_pending |= active_item.is_none();
         state.active_item = active_item;
     }
 
+    /// Scroll smoothly from the current offset to the given one over `duration`, with the
+    /// progress of the scroll shaped by `easing`, such as [`ease_in_out`](crate::ease_in_out).
+    /// The animation is cancelled if the user scrolls, or the offset is set, before it ends.
+    /// The tracking element is redrawn every frame until the scroll ends.
+    pub fn scroll_to_animated(
+        &self,
+        offset: Point<Pixels>,
+        duration: Duration,
+        easing: impl Fn(f32) -> f32 + 'static,
+    ) {
+        self.animate_to(offset, ScrollTiming::new(duration, easing));
+        self.redraw();
+    }
+
+    /// Redraws the view the tracking element was last drawn in, for scrolls that start outside
+    /// of an update.
+    pub(crate) fn redraw(&self) {
+        if let Some(view) = self.0.borrow().view.as_ref() {
+            view.invalidator.invalidate_view_silently(view.view_id);
+        }
+    }
+
+    pub(crate) fn animate_to(&self, offset: Point<Pixels>, timing: ScrollTiming) {
+        let mut state = self.0.borrow_mut();
+        let from = *state.offset.borrow();
+        state.animation = Some(ScrollAnimation::new(from, offset, timing));
+    }
+
+    /// Whether an animated scroll started with [`Self::scroll_to_animated`] is in flight, so
+    /// that autoscrolling can avoid fighting it.
+    pub fn is_animating(&self) -> bool {
+        self.0.borrow().animation.is_some()
+    }
+
+    /// Stop any animated scroll in flight, leaving the offset where it is.
+    pub fn cancel_animation(&self) {
+        self.0.borrow_mut().animation = None;
+    }
+
     /// Scrolls to the bottom.
     pub fn scroll_to_bottom(&self) {
         let mut state = self.0.borrow_mut();
@@ -3228,8 +4483,10 @@ impl ScrollHandle {
     /// Set the offset explicitly. The offset is the distance from the top left of the
     /// parent container to the top left of the first child.
     /// As you scroll further down the offset becomes more negative.
+    /// This cancels any animated scroll in flight.
     pub fn set_offset(&self, mut position: Point<Pixels>) {
-        let state = self.0.borrow();
+        let mut state = self.0.borrow_mut();
+        state.animation = None;
         *state.offset.borrow_mut() = position;
     }
 
@@ -3268,3 +4525,241 @@ impl ScrollHandle {
         self.0.borrow().child_bounds.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        self as gpui, Context, InteractiveElement, IntoElement, ListAlignment, ListOffset,
+        ListState, OverscrollBehavior, ParentElement, Pixels, Point, Render, ScrollDelta,
+        ScrollHandle, ScrollWheelEvent, SnapAlign, SnapAxis, StatefulInteractiveElement, Styled,
+        TestAppContext, TouchPhase, VisualTestContext, Window, div, linear, point, px,
+    };
+
+    #[gpui::test]
+    fn test_scroll_snap(cx: &mut TestAppContext) {
+        struct CarouselView {
+            scroll_handle: ScrollHandle,
+        }
+
+        impl Render for CarouselView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .id("carousel")
+                    .flex()
+                    .w(px(100.))
+                    .h(px(50.))
+                    .overflow_x_scroll()
+                    .scroll_snap(SnapAxis::X, SnapAlign::Start)
+                    .track_scroll(&self.scroll_handle)
+                    .children((0..5).map(|_| div().flex_none().w(px(80.)).h(px(50.)).snap_point()))
+            }
+        }
+
+        let scroll_handle = ScrollHandle::new();
+        let (_, cx) = cx.add_window_view(|_, _| CarouselView {
+            scroll_handle: scroll_handle.clone(),
+        });
+        let scroll = |cx: &mut VisualTestContext, delta_x: f32, momentum_phase| {
+            cx.simulate_event(ScrollWheelEvent {
+                position: point(px(10.), px(10.)),
+                delta: ScrollDelta::Pixels(point(px(delta_x), px(0.))),
+                momentum_phase,
+                ..Default::default()
+            });
+        };
+        let advance = |cx: &mut VisualTestContext, millis| {
+            cx.executor().advance_clock(Duration::from_millis(millis));
+            cx.run_until_parked();
+            cx.simulate_animation_frame();
+        };
+
+        // A wheel tick settles on the nearest card once no more scroll events arrive.
+        scroll(cx, -100., None);
+        assert_eq!(scroll_handle.offset().x, px(-100.));
+        advance(cx, 100);
+        assert_eq!(scroll_handle.offset().x, px(-100.));
+        advance(cx, 100);
+        advance(cx, 250);
+        assert_eq!(scroll_handle.offset().x, px(-80.));
+
+        // Momentum scrolling settles when it ends, and not before.
+        scroll(cx, -50., Some(TouchPhase::Started));
+        advance(cx, 500);
+        assert_eq!(scroll_handle.offset().x, px(-130.));
+        scroll(cx, -5., Some(TouchPhase::Ended));
+        advance(cx, 250);
+        assert_eq!(scroll_handle.offset().x, px(-160.));
+
+        // Programmatic scrolls land on a snap point, which is clamped to the scrollable range.
+        scroll_handle.scroll_to_animated(point(px(-250.), px(0.)), Duration::ZERO, linear);
+        advance(cx, 0);
+        assert_eq!(scroll_handle.offset().x, px(-240.));
+        scroll_handle.scroll_to_animated(point(px(-310.), px(0.)), Duration::ZERO, linear);
+        advance(cx, 0);
+        assert_eq!(scroll_handle.offset().x, px(-300.));
+    }
+
+    #[gpui::test]
+    fn test_scroll_chaining(cx: &mut TestAppContext) {
+        struct NestedView {
+            outer_scrolls_horizontally: bool,
+            overscroll_behavior: OverscrollBehavior,
+            outer: ScrollHandle,
+            inner: ScrollHandle,
+        }
+
+        impl Render for NestedView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let inner = div()
+                    .id("inner")
+                    .flex_none()
+                    .w(px(100.))
+                    .h(px(50.))
+                    .overflow_y_scroll()
+                    .overscroll_behavior(self.overscroll_behavior)
+                    .track_scroll(&self.inner)
+                    .child(div().w(px(100.)).h(px(100.)));
+                let outer = div()
+                    .id("outer")
+                    .flex()
+                    .size(px(100.))
+                    .track_scroll(&self.outer)
+                    .child(inner);
+                if self.outer_scrolls_horizontally {
+                    outer
+                        .overflow_x_scroll()
+                        .child(div().flex_none().w(px(200.)).h(px(50.)))
+                } else {
+                    outer
+                        .flex_col()
+                        .overflow_y_scroll()
+                        .child(div().flex_none().w(px(100.)).h(px(200.)))
+                }
+            }
+        }
+
+        fn nested_view(
+            cx: &mut TestAppContext,
+            outer_scrolls_horizontally: bool,
+            overscroll_behavior: OverscrollBehavior,
+        ) -> (ScrollHandle, ScrollHandle, &mut VisualTestContext) {
+            let outer = ScrollHandle::new();
+            let inner = ScrollHandle::new();
+            let (_, cx) = cx.add_window_view(|_, _| NestedView {
+                outer_scrolls_horizontally,
+                overscroll_behavior,
+                outer: outer.clone(),
+                inner: inner.clone(),
+            });
+            (outer, inner, cx)
+        }
+
+        let scroll = |cx: &mut VisualTestContext, delta: Point<Pixels>| {
+            cx.simulate_event(ScrollWheelEvent {
+                position: point(px(10.), px(5.)),
+                delta: ScrollDelta::Pixels(delta),
+                ..Default::default()
+            });
+        };
+
+        // The inner container scrolls to its end, and the rest scrolls the outer one.
+        let (outer, inner, window_cx) = nested_view(cx, false, OverscrollBehavior::Auto);
+        scroll(window_cx, point(px(0.), px(-80.)));
+        assert_eq!(inner.offset(), point(px(0.), px(-50.)));
+        assert_eq!(outer.offset(), point(px(0.), px(-30.)));
+        scroll(window_cx, point(px(0.), px(-10.)));
+        assert_eq!(inner.offset(), point(px(0.), px(-50.)));
+        assert_eq!(outer.offset(), point(px(0.), px(-40.)));
+        scroll(window_cx, point(px(0.), px(20.)));
+        assert_eq!(inner.offset(), point(px(0.), px(-30.)));
+        assert_eq!(outer.offset(), point(px(0.), px(-40.)));
+
+        // Diagonal scrolls are split between the containers by axis.
+        let (outer, inner, window_cx) = nested_view(cx, true, OverscrollBehavior::Auto);
+        scroll(window_cx, point(px(-20.), px(-30.)));
+        assert_eq!(inner.offset(), point(px(0.), px(-30.)));
+        assert_eq!(outer.offset(), point(px(-20.), px(0.)));
+
+        // Contained scrolls never reach the outer container.
+        let (outer, inner, window_cx) = nested_view(cx, false, OverscrollBehavior::Contain);
+        scroll(window_cx, point(px(0.), px(-80.)));
+        assert_eq!(inner.offset(), point(px(0.), px(-50.)));
+        assert_eq!(outer.offset(), point(px(0.), px(0.)));
+
+        struct NestedListView {
+            overscroll_behavior: OverscrollBehavior,
+            outer: ScrollHandle,
+            list: ListState,
+        }
+
+        impl Render for NestedListView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .id("outer")
+                    .flex()
+                    .flex_col()
+                    .size(px(100.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.outer)
+                    .child(
+                        gpui::list(self.list.clone(), |_, _, _| {
+                            div().w(px(100.)).h(px(20.)).into_any_element()
+                        })
+                        .flex_none()
+                        .w(px(100.))
+                        .h(px(50.))
+                        .overscroll_behavior(self.overscroll_behavior),
+                    )
+                    .child(div().flex_none().w(px(100.)).h(px(200.)))
+            }
+        }
+
+        fn nested_list_view(
+            cx: &mut TestAppContext,
+            overscroll_behavior: OverscrollBehavior,
+        ) -> (ScrollHandle, ListState, &mut VisualTestContext) {
+            let outer = ScrollHandle::new();
+            let list = ListState::new(5, ListAlignment::Top, px(10.));
+            let (_, cx) = cx.add_window_view(|_, _| NestedListView {
+                overscroll_behavior,
+                outer: outer.clone(),
+                list: list.clone(),
+            });
+            (outer, list, cx)
+        }
+
+        // Lists pass on what they can't scroll too.
+        let (outer, list, window_cx) = nested_list_view(cx, OverscrollBehavior::Auto);
+        scroll(window_cx, point(px(0.), px(-80.)));
+        assert_eq!(
+            list.logical_scroll_top(),
+            ListOffset {
+                item_ix: 2,
+                offset_in_item: px(10.),
+            }
+        );
+        assert_eq!(outer.offset(), point(px(0.), px(-30.)));
+        scroll(window_cx, point(px(0.), px(20.)));
+        assert_eq!(
+            list.logical_scroll_top(),
+            ListOffset {
+                item_ix: 1,
+                offset_in_item: px(10.),
+            }
+        );
+        assert_eq!(outer.offset(), point(px(0.), px(-30.)));
+
+        let (outer, list, window_cx) = nested_list_view(cx, OverscrollBehavior::Contain);
+        scroll(window_cx, point(px(0.), px(-80.)));
+        assert_eq!(
+            list.logical_scroll_top(),
+            ListOffset {
+                item_ix: 2,
+                offset_in_item: px(10.),
+            }
+        );
+        assert_eq!(outer.offset(), point(px(0.), px(0.)));
+    }
+}
@@ -1,22 +1,29 @@
 use crate::{
-    AnyElement, AnyImageCache, App, Asset, AssetLogger, Bounds, DefiniteLength, Element, ElementId,
-    Entity, GlobalElementId, Hitbox, Image, ImageCache, InspectorElementId, InteractiveElement,
-    Interactivity, IntoElement, LayoutId, Length, ObjectFit, Pixels, RenderImage, Resource,
-    SharedString, SharedUri, StyleRefinement, Styled, Task, Window, px,
+    AnyElement, AnyImageCache, App, Asset, AssetLogger, Bounds, ContentMask, Corners,
+    DefiniteLength, DevicePixels, Edges, Element, ElementId, Entity, EntityId, GlobalElementId,
+    Hitbox, Image, ImageCache, InspectorElementId, InteractiveElement, Interactivity, IntoElement,
+    LayoutId, Length, ObjectFit, Pixels, Point, RenderImage, Resource, SharedString, SharedUri,
+    Size, StyleRefinement, Styled, SvgParseOptions, SvgRenderer, Task, Window, decode_data_uri,
+    point, px, relative, size,
 };
 use anyhow::{Context as _, Result};
 
 use futures::{AsyncReadExt, Future};
 use image::{
-    AnimationDecoder, DynamicImage, Frame, ImageError, ImageFormat, Rgba,
+    AnimationDecoder, DynamicImage, Frame, ImageBuffer, ImageDecoder as _, ImageError, ImageFormat,
+    ImageReader, ImageResult, Rgba, RgbaImage,
     codecs::{gif::GifDecoder, webp::WebPDecoder},
+    imageops::{self, FilterType},
 };
 use smallvec::SmallVec;
 use std::{
+    cell::{RefCell, RefMut},
     fs,
+    hash::{Hash, Hasher},
     io::{self, Cursor},
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
+    rc::Rc,
     str::FromStr,
     sync::Arc,
     time::{Duration, Instant},
@@ -29,6 +36,17 @@ use super::{Stateful, StatefulInteractiveElement};
 /// The delay before showing the loading state.
 pub const LOADING_DELAY: Duration = Duration::from_millis(200);
 
+/// How long a failed image load stays cached before it's loaded again, in case the failure was
+/// transient, such as a locked file or a network error.
+const IMAGE_LOAD_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Animation frames with a shorter delay than this, which are often encoded with no delay at all,
+/// are shown for [`DEFAULT_FRAME_DELAY`] instead, as browsers do.
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+
+/// How long animation frames with too short a delay are shown for.
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
 /// A type alias to the resource loader that the `img()` element uses.
 ///
 /// Note: that this is only for Resources, like URLs or file paths.
@@ -127,6 +145,8 @@ where
 pub struct ImageStyle {
     grayscale: bool,
     object_fit: ObjectFit,
+    object_position: Point<DefiniteLength>,
+    nine_patch: Option<NinePatch>,
     loading: Option<Box<dyn Fn() -> AnyElement>>,
     fallback: Option<Box<dyn Fn() -> AnyElement>>,
 }
@@ -136,6 +156,8 @@ impl Default for ImageStyle {
         Self {
             grayscale: false,
             object_fit: ObjectFit::Contain,
+            object_position: point(relative(0.5), relative(0.5)),
+            nine_patch: None,
             loading: None,
             fallback: None,
         }
@@ -159,6 +181,35 @@ pub trait StyledImage: Sized {
         self
     }
 
+    /// Set where the image is placed within the element when its object fit doesn't exactly fill
+    /// it, as fractions of the space left over or as absolute offsets. Defaults to centered.
+    ///
+    /// This has no effect on [`ObjectFit::Fill`] and [`ObjectFit::Contain`].
+    fn object_position(
+        mut self,
+        x: impl Into<DefiniteLength>,
+        y: impl Into<DefiniteLength>,
+    ) -> Self {
+        self.image_style().object_position = point(x.into(), y.into());
+        self
+    }
+
+    /// Paint the image as a nine-patch with the given insets, in the image's pixels. Its corners
+    /// are painted at their natural size and its edges and center are stretched to fill the
+    /// element, which no longer keeps the image's aspect ratio.
+    fn nine_patch(mut self, insets: Edges<Pixels>) -> Self {
+        self.image_style().nine_patch.get_or_insert_default().insets = insets;
+        self
+    }
+
+    /// Set whether the edges and center of a nine-patch image are stretched or tiled.
+    fn nine_patch_fill(mut self, edges: NinePatchFill, center: NinePatchFill) -> Self {
+        let nine_patch = self.image_style().nine_patch.get_or_insert_default();
+        nine_patch.edges = edges;
+        nine_patch.center = center;
+        self
+    }
+
     /// Set a fallback function that will be invoked to render an error view should
     /// the image fail to load.
     fn with_fallback(mut self, fallback: impl Fn() -> AnyElement + 'static) -> Self {
@@ -186,12 +237,333 @@ impl StyledImage for Stateful<Img> {
     }
 }
 
+/// How the edges and center of a nine-patch image fill the space between its corners.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NinePatchFill {
+    /// Stretch the region of the image to fill the space.
+    #[default]
+    Stretch,
+    /// Repeat the region of the image from the top left of the space, clipping the last tiles.
+    Tile,
+}
+
+/// A stretchable image with fixed corners, like CSS's `border-image`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NinePatch {
+    /// The size of the image's corners and edges, in the image's pixels.
+    pub insets: Edges<Pixels>,
+    /// How the edges between the corners are filled.
+    pub edges: NinePatchFill,
+    /// How the center of the image is filled.
+    pub center: NinePatchFill,
+}
+
+impl NinePatch {
+    /// Paints the nine regions of the image to fill the given bounds.
+    ///
+    /// If the bounds are smaller than the corners, all of the regions are scaled down evenly.
+    /// Region boundaries are snapped to device pixels so that the corners stay crisp.
+    fn paint(
+        &self,
+        bounds: Bounds<Pixels>,
+        corner_radii: Corners<Pixels>,
+        data: Arc<RenderImage>,
+        frame_index: usize,
+        grayscale: bool,
+        window: &mut Window,
+    ) -> Result<()> {
+        let image_size = data.size(frame_index);
+        let image_width = image_size.width.0 as f32;
+        let image_height = image_size.height.0 as f32;
+        let left = self.insets.left.0.clamp(0., image_width);
+        let right = self.insets.right.0.clamp(0., image_width - left);
+        let top = self.insets.top.0.clamp(0., image_height);
+        let bottom = self.insets.bottom.0.clamp(0., image_height - top);
+
+        let pixel_size = data.render_size(frame_index).width.0 / image_width;
+        let scale = pixel_size
+            .min(bounds.size.width.0 / (left + right))
+            .min(bounds.size.height.0 / (top + bottom))
+            .max(0.);
+
+        let scale_factor = window.scale_factor();
+        let snap = |value: f32| px((value * scale_factor).round() / scale_factor);
+        let source_columns = [0., left, image_width - right, image_width];
+        let source_rows = [0., top, image_height - bottom, image_height];
+        let columns = [
+            bounds.left(),
+            snap(bounds.left().0 + left * scale),
+            snap(bounds.right().0 - right * scale),
+            bounds.right(),
+        ];
+        let rows = [
+            bounds.top(),
+            snap(bounds.top().0 + top * scale),
+            snap(bounds.bottom().0 - bottom * scale),
+            bounds.bottom(),
+        ];
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let source = Bounds::from_corners(
+                    point(
+                        DevicePixels(source_columns[column].round() as i32),
+                        DevicePixels(source_rows[row].round() as i32),
+                    ),
+                    point(
+                        DevicePixels(source_columns[column + 1].round() as i32),
+                        DevicePixels(source_rows[row + 1].round() as i32),
+                    ),
+                );
+                let region = Bounds::from_corners(
+                    point(columns[column], rows[row]),
+                    point(columns[column + 1], rows[row + 1]),
+                );
+                if source.is_empty() || region.is_empty() {
+                    continue;
+                }
+
+                let tile_size = size(
+                    px(source.size.width.0 as f32 * scale),
+                    px(source.size.height.0 as f32 * scale),
+                );
+                let (fill, tile_size) = match (row, column) {
+                    (1, 1) => (self.center, tile_size),
+                    (1, _) => (self.edges, size(region.size.width, tile_size.height)),
+                    (_, 1) => (self.edges, size(tile_size.width, region.size.height)),
+                    _ => (NinePatchFill::Stretch, region.size),
+                };
+                // Only the corners of the image are rounded, with the corners of the element.
+                let corner_radii = match (row, column) {
+                    (0, 0) => Corners {
+                        top_left: corner_radii.top_left,
+                        ..Default::default()
+                    },
+                    (0, 2) => Corners {
+                        top_right: corner_radii.top_right,
+                        ..Default::default()
+                    },
+                    (2, 2) => Corners {
+                        bottom_right: corner_radii.bottom_right,
+                        ..Default::default()
+                    },
+                    (2, 0) => Corners {
+                        bottom_left: corner_radii.bottom_left,
+                        ..Default::default()
+                    },
+                    _ => Corners::default(),
+                }
+                .clamp_radii_for_quad_size(region.size);
+
+                match fill {
+                    NinePatchFill::Stretch => window.paint_image_region(
+                        region,
+                        corner_radii,
+                        data.clone(),
+                        frame_index,
+                        Some(source),
+                        grayscale,
+                    )?,
+                    NinePatchFill::Tile => window.with_content_mask(
+                        Some(ContentMask { bounds: region }),
+                        |window| {
+                            let mut tile_origin = region.origin;
+                            while tile_origin.y < region.bottom() {
+                                while tile_origin.x < region.right() {
+                                    window.paint_image_region(
+                                        Bounds::new(tile_origin, tile_size),
+                                        corner_radii,
+                                        data.clone(),
+                                        frame_index,
+                                        Some(source),
+                                        grayscale,
+                                    )?;
+                                    tile_origin.x += tile_size.width;
+                                }
+                                tile_origin =
+                                    point(region.left(), tile_origin.y + tile_size.height);
+                            }
+                            anyhow::Ok(())
+                        },
+                    )?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// How an animated image plays its frames.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Playback {
+    /// Play the animation in a loop as soon as the image is shown.
+    #[default]
+    Auto,
+    /// Show the current frame until the animation is played with an [`ImageAnimationHandle`].
+    Paused,
+    /// Play the animation the given number of times, then stay on its last frame.
+    Loop(usize),
+}
+
+/// A handle to the animation of an [`Img`], which views can hold to control its playback.
+#[derive(Clone, Default)]
+pub struct ImageAnimationHandle(Rc<RefCell<ImageAnimationState>>);
+
+#[derive(Default)]
+struct ImageAnimationState {
+    frame_index: usize,
+    frame_count: usize,
+    last_frame_time: Option<Instant>,
+    /// Overrides the element's [`Playback`] once the animation has been played or paused.
+    playing: Option<bool>,
+    completed_loops: usize,
+    finished: bool,
+    /// The view that painted the image, which is notified when the animation changes.
+    view: Option<EntityId>,
+    next_frame: Option<Task<()>>,
+}
+
+impl ImageAnimationHandle {
+    /// Create a new handle, to be passed to [`Img::animation_handle`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of frames in the animation, or zero if the image hasn't been loaded yet.
+    pub fn frame_count(&self) -> usize {
+        self.0.borrow().frame_count
+    }
+
+    /// The index of the frame currently shown.
+    pub fn frame_index(&self) -> usize {
+        self.0.borrow().frame_index
+    }
+
+    /// Play the animation from its current frame, restarting it if it has finished looping.
+    pub fn play(&self, cx: &mut App) {
+        let mut state = self.0.borrow_mut();
+        state.playing = Some(true);
+        if state.finished {
+            state.frame_index = 0;
+            state.completed_loops = 0;
+            state.finished = false;
+        }
+        state.last_frame_time = None;
+        Self::notify(state, cx);
+    }
+
+    /// Pause the animation on its current frame.
+    pub fn pause(&self, cx: &mut App) {
+        let mut state = self.0.borrow_mut();
+        state.playing = Some(false);
+        state.next_frame = None;
+        Self::notify(state, cx);
+    }
+
+    /// Show the frame at the given index, from which the animation continues if it's playing.
+    pub fn seek_frame(&self, frame_index: usize, cx: &mut App) {
+        let mut state = self.0.borrow_mut();
+        state.frame_index = frame_index.min(state.frame_count.saturating_sub(1));
+        state.last_frame_time = None;
+        Self::notify(state, cx);
+    }
+
+    fn notify(state: RefMut<ImageAnimationState>, cx: &mut App) {
+        let view = state.view;
+        drop(state);
+        if let Some(view) = view {
+            cx.notify(view);
+        }
+    }
+
+    /// Advance the animation to the frame that should be shown at the given time, returning how
+    /// long until the next frame is due if the animation is playing.
+    fn advance(&self, image: &RenderImage, playback: Playback, now: Instant) -> Option<Duration> {
+        let mut state = self.0.borrow_mut();
+        let frame_count = image.frame_count();
+        state.frame_count = frame_count;
+        state.frame_index = state.frame_index.min(frame_count.saturating_sub(1));
+
+        let loops = match playback {
+            Playback::Loop(loops) => Some(loops),
+            Playback::Auto | Playback::Paused => None,
+        };
+        let playing = state.playing.unwrap_or(playback != Playback::Paused) && !state.finished;
+        if frame_count <= 1 || !playing {
+            state.last_frame_time = None;
+            return None;
+        }
+
+        let Some(mut last_frame_time) = state.last_frame_time else {
+            state.last_frame_time = Some(now);
+            return Some(frame_delay(image, state.frame_index));
+        };
+        loop {
+            let delay = frame_delay(image, state.frame_index);
+            let elapsed = now.saturating_duration_since(last_frame_time);
+            if elapsed < delay {
+                state.last_frame_time = Some(last_frame_time);
+                return Some(delay - elapsed);
+            }
+
+            last_frame_time += delay;
+            if state.frame_index + 1 < frame_count {
+                state.frame_index += 1;
+            } else {
+                state.completed_loops += 1;
+                if loops.is_some_and(|loops| state.completed_loops >= loops) {
+                    state.finished = true;
+                    state.last_frame_time = None;
+                    return None;
+                }
+                state.frame_index = 0;
+            }
+        }
+    }
+
+    /// Notify the current view once the next frame is due.
+    fn schedule_next_frame(&self, delay: Duration, window: &mut Window, cx: &mut App) {
+        let view = window.current_view();
+        let task = window.spawn(cx, async move |cx| {
+            cx.background_executor().timer(delay).await;
+            cx.update(move |_, cx| cx.notify(view)).ok();
+        });
+        let mut state = self.0.borrow_mut();
+        state.view = Some(view);
+        state.next_frame = Some(task);
+    }
+
+    /// Stop advancing the animation while its image isn't visible. It resumes from the same frame
+    /// once the image is painted again.
+    fn suspend(&self, window: &mut Window) {
+        let mut state = self.0.borrow_mut();
+        state.view = Some(window.current_view());
+        state.last_frame_time = None;
+        state.next_frame = None;
+    }
+}
+
+/// How long the given frame of an animated image is shown for.
+fn frame_delay(image: &RenderImage, frame_index: usize) -> Duration {
+    let delay = Duration::from(image.delay(frame_index));
+    if delay < MIN_FRAME_DELAY {
+        DEFAULT_FRAME_DELAY
+    } else {
+        delay
+    }
+}
+
 /// An image element.
 pub struct Img {
     interactivity: Interactivity,
     source: ImageSource,
     style: ImageStyle,
     image_cache: Option<AnyImageCache>,
+    playback: Playback,
+    animation: Option<ImageAnimationHandle>,
+    ignore_exif_orientation: bool,
+    full_resolution: bool,
 }
 
 /// Create a new image element.
@@ -202,6 +574,10 @@ pub fn img(source: impl Into<ImageSource>) -> Img {
         source: source.into(),
         style: ImageStyle::default(),
         image_cache: None,
+        playback: Playback::default(),
+        animation: None,
+        ignore_exif_orientation: false,
+        full_resolution: false,
     }
 }
 
@@ -231,6 +607,100 @@ impl Img {
             ..self
         }
     }
+
+    /// Sets how the frames of an animated image are played.
+    pub fn animation_playback(self, playback: Playback) -> Self {
+        Self { playback, ..self }
+    }
+
+    /// Controls the animation of this image with the given handle.
+    ///
+    /// Without a handle, the image is only animated if it has an id.
+    pub fn animation_handle(self, handle: &ImageAnimationHandle) -> Self {
+        Self {
+            animation: Some(handle.clone()),
+            ..self
+        }
+    }
+
+    /// Draws the image in the orientation its pixels are stored in, instead of rotating and
+    /// flipping it as its EXIF orientation tag describes.
+    ///
+    /// Images loaded through an image cache always have their orientation applied.
+    pub fn ignore_exif_orientation(self) -> Self {
+        Self {
+            ignore_exif_orientation: true,
+            ..self
+        }
+    }
+
+    /// Uploads the image to the GPU at its full resolution, for viewers that pan and zoom into it.
+    ///
+    /// By default, images are downsampled in the background to at most twice the size they're
+    /// painted at, and painted at full resolution until that's done. Images with an id keep
+    /// painting the version they last painted while a bigger one is downsampled for them, and only
+    /// get a new version when they grow.
+    pub fn full_resolution(self) -> Self {
+        Self {
+            full_resolution: true,
+            ..self
+        }
+    }
+
+    /// Returns the version of the image to paint at the given size, which is downsampled unless
+    /// the element opted out. While it's being downsampled, this is the version that was last
+    /// painted, or the image itself.
+    fn image_to_paint(
+        &self,
+        image: Arc<RenderImage>,
+        frame_index: usize,
+        painted_size: Size<Pixels>,
+        global_id: Option<&GlobalElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Arc<RenderImage> {
+        // Nine-patch insets are in the image's pixels, so those images aren't downsampled.
+        if self.full_resolution || self.style.nine_patch.is_some() {
+            return image;
+        }
+
+        let image_size = image.size(frame_index);
+        let image_dimension = image_size.width.max(image_size.height).0.max(0) as u32;
+        let painted_dimension =
+            painted_size.width.max(painted_size.height).0 * window.scale_factor();
+        let max_dimension = (painted_dimension.ceil().max(1.) as u32).next_power_of_two();
+        if image_dimension <= max_dimension {
+            return image;
+        }
+
+        let request = DownsampleRequest {
+            image,
+            max_dimension,
+        };
+        window.with_optional_element_state(global_id, |state, window| {
+            let mut state = state.map(Option::<ImgState>::unwrap_or_default);
+            let previous = state
+                .as_ref()
+                .and_then(|state| state.downsampled.clone())
+                .filter(|(previous_request, _)| previous_request.image.id == request.image.id);
+            if let Some((previous_request, previous_image)) = &previous
+                && previous_request.max_dimension >= request.max_dimension
+            {
+                return (previous_image.clone(), state);
+            }
+
+            let image = match window.use_asset::<ImageDownsampler>(&request, cx) {
+                Some(image) => {
+                    if let Some(state) = &mut state {
+                        state.downsampled = Some((request, image.clone()));
+                    }
+                    image
+                }
+                None => previous.map_or(request.image, |(_, image)| image),
+            };
+            (image, state)
+        })
+    }
 }
 
 impl Deref for Stateful<Img> {
@@ -248,15 +718,19 @@ impl DerefMut for Stateful<Img> {
 }
 
 /// The image state between frames
+#[derive(Default)]
 struct ImgState {
-    frame_index: usize,
-    last_frame_time: Option<Instant>,
+    animation: ImageAnimationHandle,
     started_loading: Option<(Instant, Task<()>)>,
+    /// The downsampled version of the image that was last painted.
+    downsampled: Option<(DownsampleRequest, Arc<RenderImage>)>,
 }
 
 /// The image layout state between frames
 pub struct ImgLayoutState {
     frame_index: usize,
+    animation: Option<ImageAnimationHandle>,
+    next_frame_delay: Option<Duration>,
     replacement: Option<AnyElement>,
 }
 
@@ -281,19 +755,17 @@ impl Element for Img {
     ) -> (LayoutId, Self::RequestLayoutState) {
         let mut layout_state = ImgLayoutState {
             frame_index: 0,
+            animation: None,
+            next_frame_delay: None,
             replacement: None,
         };
 
         window.with_optional_element_state(global_id, |state, window| {
-            let mut state = state.map(|state| {
-                state.unwrap_or(ImgState {
-                    frame_index: 0,
-                    last_frame_time: None,
-                    started_loading: None,
-                })
-            });
-
-            let frame_index = state.as_ref().map(|state| state.frame_index).unwrap_or(0);
+            let mut state = state.map(Option::unwrap_or_default);
+            layout_state.animation = self
+                .animation
+                .clone()
+                .or_else(|| state.as_ref().map(|state| state.animation.clone()));
 
             let layout_id = self.interactivity.request_layout(
                 global_id,
@@ -307,38 +779,31 @@ impl Element for Img {
                         self.image_cache
                             .clone()
                             .or_else(|| window.image_cache_stack.last().cloned()),
+                        self.ignore_exif_orientation,
                         window,
                         cx,
                     ) {
                         Some(Ok(data)) => {
                             if let Some(state) = &mut state {
-                                let frame_count = data.frame_count();
-                                if frame_count > 1 {
-                                    let current_time = Instant::now();
-                                    if let Some(last_frame_time) = state.last_frame_time {
-                                        let elapsed = current_time - last_frame_time;
-                                        let frame_duration =
-                                            Duration::from(data.delay(state.frame_index));
-
-                                        if elapsed >= frame_duration {
-                                            state.frame_index =
-                                                (state.frame_index + 1) % frame_count;
-                                            state.last_frame_time =
-                                                Some(current_time - (elapsed - frame_duration));
-                                        }
-                                    } else {
-                                        state.last_frame_time = Some(current_time);
-                                    }
-                                }
                                 state.started_loading = None;
                             }
+                            if let Some(animation) = &layout_state.animation {
+                                layout_state.next_frame_delay =
+                                    animation.advance(&data, self.playback, Instant::now());
+                                layout_state.frame_index = animation.frame_index();
+                            }
 
-                            let image_size = data.render_size(frame_index);
-                            style.aspect_ratio = Some(image_size.width / image_size.height);
+                            let image_size = data.render_size(layout_state.frame_index);
+                            let keep_aspect_ratio = self.style.nine_patch.is_none();
+                            if keep_aspect_ratio {
+                                style.aspect_ratio = Some(image_size.width / image_size.height);
+                            }
 
                             if let Length::Auto = style.size.width {
                                 style.size.width = match style.size.height {
-                                    Length::Definite(DefiniteLength::Absolute(abs_length)) => {
+                                    Length::Definite(DefiniteLength::Absolute(abs_length))
+                                        if keep_aspect_ratio =>
+                                    {
                                         let height_px = abs_length.to_pixels(window.rem_size());
                                         Length::Definite(
                                             px(image_size.width.0 * height_px.0
@@ -352,7 +817,9 @@ impl Element for Img {
 
                             if let Length::Auto = style.size.height {
                                 style.size.height = match style.size.width {
-                                    Length::Definite(DefiniteLength::Absolute(abs_length)) => {
+                                    Length::Definite(DefiniteLength::Absolute(abs_length))
+                                        if keep_aspect_ratio =>
+                                    {
                                         let width_px = abs_length.to_pixels(window.rem_size());
                                         Length::Definite(
                                             px(image_size.height.0 * width_px.0
@@ -363,10 +830,6 @@ impl Element for Img {
                                     _ => Length::Definite(image_size.height.into()),
                                 };
                             }
-
-                            if global_id.is_some() && data.frame_count() > 1 {
-                                window.request_animation_frame();
-                            }
                         }
                         Some(_err) => {
                             if let Some(fallback) = self.style.fallback.as_ref() {
@@ -407,8 +870,6 @@ impl Element for Img {
                 },
             );
 
-            layout_state.frame_index = frame_index;
-
             ((layout_id, layout_state), state)
         })
     }
@@ -462,26 +923,64 @@ impl Element for Img {
                     self.image_cache
                         .clone()
                         .or_else(|| window.image_cache_stack.last().cloned()),
+                    self.ignore_exif_orientation,
                     window,
                     cx,
                 ) {
-                    let new_bounds = self
-                        .style
-                        .object_fit
-                        .get_bounds(bounds, data.size(layout_state.frame_index));
-                    let corner_radii = style
-                        .corner_radii
-                        .to_pixels(window.rem_size())
-                        .clamp_radii_for_quad_size(new_bounds.size);
-                    window
-                        .paint_image(
-                            new_bounds,
-                            corner_radii,
+                    if let Some(nine_patch) = &self.style.nine_patch {
+                        let corner_radii = style
+                            .corner_radii
+                            .to_pixels(window.rem_size())
+                            .clamp_radii_for_quad_size(bounds.size);
+                        nine_patch
+                            .paint(
+                                bounds,
+                                corner_radii,
+                                data,
+                                layout_state.frame_index,
+                                self.style.grayscale,
+                                window,
+                            )
+                            .log_err();
+                    } else {
+                        let new_bounds = self.style.object_fit.get_positioned_bounds(
+                            bounds,
+                            data.size(layout_state.frame_index),
+                            self.style.object_position,
+                            window.rem_size(),
+                        );
+                        let corner_radii = style
+                            .corner_radii
+                            .to_pixels(window.rem_size())
+                            .clamp_radii_for_quad_size(new_bounds.size);
+                        let data = self.image_to_paint(
                             data,
                             layout_state.frame_index,
-                            self.style.grayscale,
-                        )
-                        .log_err();
+                            new_bounds.size,
+                            global_id,
+                            window,
+                            cx,
+                        );
+                        window
+                            .paint_image(
+                                new_bounds,
+                                corner_radii,
+                                data,
+                                layout_state.frame_index,
+                                self.style.grayscale,
+                            )
+                            .log_err();
+                    }
+
+                    // Frames are only scheduled for images that are visible, when they're due.
+                    if let Some(animation) = &layout_state.animation {
+                        match layout_state.next_frame_delay {
+                            Some(delay) if bounds.intersects(&window.content_mask().bounds) => {
+                                animation.schedule_next_frame(delay, window, cx);
+                            }
+                            _ => animation.suspend(window),
+                        }
+                    }
                 } else if let Some(replacement) = &mut layout_state.replacement {
                     replacement.paint(window, cx);
                 }
@@ -516,6 +1015,7 @@ impl ImageSource {
     pub(crate) fn use_data(
         &self,
         cache: Option<AnyImageCache>,
+        ignore_exif_orientation: bool,
         window: &mut Window,
         cx: &mut App,
     ) -> Option<Result<Arc<RenderImage>, ImageCacheError>> {
@@ -523,12 +1023,17 @@ impl ImageSource {
             ImageSource::Resource(resource) => {
                 if let Some(cache) = cache {
                     cache.load(resource, window, cx)
+                } else if ignore_exif_orientation {
+                    window.use_asset::<AssetLogger<UnorientedImageAssetLoader>>(resource, cx)
                 } else {
                     window.use_asset::<ImgResourceLoader>(resource, cx)
                 }
             }
             ImageSource::Custom(loading_fn) => loading_fn(window, cx),
             ImageSource::Render(data) => Some(Ok(data.to_owned())),
+            ImageSource::Image(data) if ignore_exif_orientation => {
+                window.use_asset::<AssetLogger<UnorientedImageDecoder>>(data, cx)
+            }
             ImageSource::Image(data) => window.use_asset::<AssetLogger<ImageDecoder>>(data, cx),
         }
     }
@@ -536,6 +1041,7 @@ impl ImageSource {
     pub(crate) fn get_data(
         &self,
         cache: Option<AnyImageCache>,
+        ignore_exif_orientation: bool,
         window: &mut Window,
         cx: &mut App,
     ) -> Option<Result<Arc<RenderImage>, ImageCacheError>> {
@@ -543,12 +1049,17 @@ impl ImageSource {
             ImageSource::Resource(resource) => {
                 if let Some(cache) = cache {
                     cache.load(resource, window, cx)
+                } else if ignore_exif_orientation {
+                    window.get_asset::<AssetLogger<UnorientedImageAssetLoader>>(resource, cx)
                 } else {
                     window.get_asset::<ImgResourceLoader>(resource, cx)
                 }
             }
             ImageSource::Custom(loading_fn) => loading_fn(window, cx),
             ImageSource::Render(data) => Some(Ok(data.to_owned())),
+            ImageSource::Image(data) if ignore_exif_orientation => {
+                window.get_asset::<AssetLogger<UnorientedImageDecoder>>(data, cx)
+            }
             ImageSource::Image(data) => window.get_asset::<AssetLogger<ImageDecoder>>(data, cx),
         }
     }
@@ -558,9 +1069,13 @@ impl ImageSource {
         match self {
             ImageSource::Resource(resource) => {
                 cx.remove_asset::<ImgResourceLoader>(resource);
+                cx.remove_asset::<AssetLogger<UnorientedImageAssetLoader>>(resource);
             }
             ImageSource::Custom(_) | ImageSource::Render(_) => {}
-            ImageSource::Image(data) => cx.remove_asset::<AssetLogger<ImageDecoder>>(data),
+            ImageSource::Image(data) => {
+                cx.remove_asset::<AssetLogger<ImageDecoder>>(data);
+                cx.remove_asset::<AssetLogger<UnorientedImageDecoder>>(data);
+            }
         }
     }
 }
@@ -579,6 +1094,98 @@ impl Asset for ImageDecoder {
         let renderer = cx.svg_renderer();
         async move { source.to_image_data(renderer).map_err(Into::into) }
     }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        output.as_ref().map_or(0, |image| image.size_in_bytes())
+    }
+}
+
+/// Decodes an [`Image`] without applying its EXIF orientation.
+#[derive(Clone)]
+enum UnorientedImageDecoder {}
+
+impl Asset for UnorientedImageDecoder {
+    type Source = Arc<Image>;
+    type Output = Result<Arc<RenderImage>, ImageCacheError>;
+
+    fn load(
+        source: Self::Source,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        let renderer = cx.svg_renderer();
+        async move {
+            source
+                .decode_render_image(renderer, false)
+                .map_err(Into::into)
+        }
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        output.as_ref().map_or(0, |image| image.size_in_bytes())
+    }
+}
+
+/// An image to downsample so that neither of its dimensions is larger than `max_dimension`.
+#[derive(Clone)]
+struct DownsampleRequest {
+    image: Arc<RenderImage>,
+    max_dimension: u32,
+}
+
+impl Hash for DownsampleRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.image.id.hash(state);
+        self.max_dimension.hash(state);
+    }
+}
+
+/// Downsamples decoded images for [`Img`] elements painted smaller than them.
+enum ImageDownsampler {}
+
+impl Asset for ImageDownsampler {
+    type Source = DownsampleRequest;
+    type Output = Arc<RenderImage>;
+
+    fn load(
+        source: Self::Source,
+        _cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        async move { Arc::new(downsample_image(&source.image, source.max_dimension)) }
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        output.size_in_bytes()
+    }
+
+    fn cancellable() -> bool {
+        true
+    }
+}
+
+/// Resizes the frames of an image so that neither of their dimensions is larger than
+/// `max_dimension`, keeping their aspect ratio and the size the image is displayed at.
+fn downsample_image(image: &RenderImage, max_dimension: u32) -> RenderImage {
+    let image_size = image.size(0);
+    let scale = max_dimension as f32 / image_size.width.max(image_size.height).0 as f32;
+    let width = ((image_size.width.0 as f32 * scale).round() as u32).max(1);
+    let height = ((image_size.height.0 as f32 * scale).round() as u32).max(1);
+
+    let frames = (0..image.frame_count())
+        .filter_map(|frame_index| {
+            let frame_size = image.size(frame_index);
+            let buffer = ImageBuffer::<Rgba<u8>, _>::from_raw(
+                frame_size.width.0 as u32,
+                frame_size.height.0 as u32,
+                image.as_bytes(frame_index)?,
+            )?;
+            let resized = imageops::resize(&buffer, width, height, FilterType::Triangle);
+            Some(Frame::from_parts(resized, 0, 0, image.delay(frame_index)))
+        })
+        .collect::<SmallVec<[Frame; 1]>>();
+
+    let mut downsampled = RenderImage::new(frames);
+    downsampled.scale_factor = image.scale_factor * width as f32 / image_size.width.0 as f32;
+    downsampled
 }
 
 /// An image loader for the GPUI asset system
@@ -593,111 +1200,319 @@ impl Asset for ImageAssetLoader {
         source: Self::Source,
         cx: &mut App,
     ) -> impl Future<Output = Self::Output> + Send + 'static {
-        let client = cx.http_client();
-        // TODO: Can we make SVGs always rescale?
-        // let scale_factor = cx.scale_factor();
-        let svg_renderer = cx.svg_renderer();
-        let asset_source = cx.asset_source().clone();
-        async move {
-            let bytes = match source.clone() {
-                Resource::Path(uri) => fs::read(uri.as_ref())?,
-                Resource::Uri(uri) => {
-                    let mut response = client
-                        .get(uri.as_ref(), ().into(), true)
-                        .await
-                        .with_context(|| format!("loading image asset from {uri:?}"))?;
+        load_render_image(source, true, cx)
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        output.as_ref().map_or(0, |image| image.size_in_bytes())
+    }
+
+    fn cancellable() -> bool {
+        true
+    }
+
+    fn retry_after(output: &Self::Output) -> Option<Duration> {
+        output.is_err().then_some(IMAGE_LOAD_RETRY_DELAY)
+    }
+}
+
+/// An image loader for the GPUI asset system that leaves images in the orientation their pixels
+/// are stored in, ignoring their EXIF orientation tags.
+#[derive(Clone)]
+pub enum UnorientedImageAssetLoader {}
+
+impl Asset for UnorientedImageAssetLoader {
+    type Source = Resource;
+    type Output = Result<Arc<RenderImage>, ImageCacheError>;
+
+    fn load(
+        source: Self::Source,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        load_render_image(source, false, cx)
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        output.as_ref().map_or(0, |image| image.size_in_bytes())
+    }
+
+    fn cancellable() -> bool {
+        true
+    }
+
+    fn retry_after(output: &Self::Output) -> Option<Duration> {
+        output.is_err().then_some(IMAGE_LOAD_RETRY_DELAY)
+    }
+}
+
+fn load_render_image(
+    source: Resource,
+    apply_orientation: bool,
+    cx: &App,
+) -> impl Future<Output = Result<Arc<RenderImage>, ImageCacheError>> + Send + 'static {
+    let load = load_resource_bytes(source, None, cx);
+    // TODO: Can we make SVGs always rescale?
+    // let scale_factor = cx.scale_factor();
+    let svg_renderer = cx.svg_renderer();
+    async move {
+        let bytes = load.await?;
+        if let Ok(format) = image::guess_format(&bytes) {
+            Ok(Arc::new(decode_raster_image(
+                &bytes,
+                format,
+                apply_orientation,
+            )?))
+        } else {
+            svg_renderer
+                .render_single_frame(&bytes, 1.0, true)
+                .map_err(Into::into)
+        }
+    }
+}
+
+/// Loads the bytes of a resource, from the file system, over HTTP, from a `data:` URI or from the
+/// app's asset source.
+pub enum FileBytesAsset {}
+
+impl Asset for FileBytesAsset {
+    type Source = Resource;
+    type Output = Result<Arc<[u8]>, ImageCacheError>;
+
+    fn load(
+        source: Self::Source,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        let load = load_resource_bytes(source, None, cx);
+        async move { load.await.map(Arc::from) }
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        output.as_ref().map_or(0, |bytes| bytes.len())
+    }
+
+    fn cancellable() -> bool {
+        true
+    }
+
+    fn retry_after(output: &Self::Output) -> Option<Duration> {
+        output.is_err().then_some(IMAGE_LOAD_RETRY_DELAY)
+    }
+}
+
+/// How much of the body of a failed download is read for the error it's reported with.
+const MAX_ERROR_BODY_BYTES: u64 = 1024;
+
+/// Returns a future loading the bytes of the given resource, which fails if a download is longer
+/// than `max_download_bytes`.
+pub(crate) fn load_resource_bytes(
+    source: Resource,
+    max_download_bytes: Option<u64>,
+    cx: &App,
+) -> impl Future<Output = Result<Vec<u8>, ImageCacheError>> + Send + 'static {
+    let client = cx.http_client();
+    let asset_source = cx.asset_source().clone();
+    async move {
+        match source {
+            Resource::Path(path) => Ok(fs::read(path.as_ref())?),
+            Resource::Uri(uri) if uri.starts_with("data:") => decode_data_uri(&uri),
+            Resource::Uri(uri) => {
+                let mut response = client
+                    .get(uri.as_ref(), ().into(), true)
+                    .await
+                    .with_context(|| format!("loading asset from {uri:?}"))?;
+                if !response.status().is_success() {
                     let mut body = Vec::new();
-                    response.body_mut().read_to_end(&mut body).await?;
-                    if !response.status().is_success() {
-                        let mut body = String::from_utf8_lossy(&body).into_owned();
-                        let first_line = body.lines().next().unwrap_or("").trim_end();
-                        body.truncate(first_line.len());
-                        return Err(ImageCacheError::BadStatus {
-                            uri,
-                            status: response.status(),
-                            body,
-                        });
-                    }
-                    body
+                    response
+                        .body_mut()
+                        .take(MAX_ERROR_BODY_BYTES)
+                        .read_to_end(&mut body)
+                        .await?;
+                    let body = String::from_utf8_lossy(&body);
+                    return Err(ImageCacheError::BadStatus {
+                        uri,
+                        status: response.status(),
+                        body: body.lines().next().unwrap_or("").trim_end().to_string(),
+                    });
                 }
-                Resource::Embedded(path) => {
-                    let data = asset_source.load(&path).ok().flatten();
-                    if let Some(data) = data {
-                        data.to_vec()
-                    } else {
-                        return Err(ImageCacheError::Asset(
-                            format!("Embedded resource not found: {}", path).into(),
-                        ));
-                    }
+                let mut body = Vec::new();
+                response
+                    .body_mut()
+                    .take(max_download_bytes.map_or(u64::MAX, |max_bytes| max_bytes + 1))
+                    .read_to_end(&mut body)
+                    .await?;
+                if let Some(max_bytes) = max_download_bytes
+                    && body.len() as u64 > max_bytes
+                {
+                    return Err(ImageCacheError::Asset(
+                        format!("asset at {uri} exceeds {max_bytes} bytes").into(),
+                    ));
                 }
-            };
+                Ok(body)
+            }
+            Resource::Embedded(path) => {
+                let data = asset_source.load(&path).ok().flatten();
+                if let Some(data) = data {
+                    Ok(data.to_vec())
+                } else {
+                    Err(ImageCacheError::Asset(
+                        format!("Embedded resource not found: {}", path).into(),
+                    ))
+                }
+            }
+        }
+    }
+}
 
-            if let Ok(format) = image::guess_format(&bytes) {
-                let data = match format {
-                    ImageFormat::Gif => {
-                        let decoder = GifDecoder::new(Cursor::new(&bytes))?;
-                        let mut frames = SmallVec::new();
-
-                        for frame in decoder.into_frames() {
-                            let mut frame = frame?;
-                            // Convert from RGBA to BGRA.
-                            for pixel in frame.buffer_mut().chunks_exact_mut(4) {
-                                pixel.swap(0, 2);
-                            }
-                            frames.push(frame);
-                        }
+/// Decodes the bytes of a raster image in the given format, converting its frames to BGRA.
+///
+/// Still images are rotated and flipped as their EXIF orientation tag describes if
+/// `apply_orientation` is set. Animated images are never reoriented.
+fn decode_raster_image(
+    bytes: &[u8],
+    format: ImageFormat,
+    apply_orientation: bool,
+) -> Result<RenderImage, ImageCacheError> {
+    let data = match format {
+        ImageFormat::Gif => {
+            let decoder = GifDecoder::new(Cursor::new(bytes))?;
+            let mut frames = SmallVec::new();
+
+            for frame in decoder.into_frames() {
+                let mut frame = frame?;
+                // Convert from RGBA to BGRA.
+                for pixel in frame.buffer_mut().chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+                frames.push(frame);
+            }
 
-                        frames
+            frames
+        }
+        ImageFormat::WebP => {
+            let mut decoder = WebPDecoder::new(Cursor::new(bytes))?;
+
+            if decoder.has_animation() {
+                let _ = decoder.set_background_color(Rgba([0, 0, 0, 0]));
+                let mut frames = SmallVec::new();
+
+                for frame in decoder.into_frames() {
+                    let mut frame = frame?;
+                    // Convert from RGBA to BGRA.
+                    for pixel in frame.buffer_mut().chunks_exact_mut(4) {
+                        pixel.swap(0, 2);
                     }
-                    ImageFormat::WebP => {
-                        let mut decoder = WebPDecoder::new(Cursor::new(&bytes))?;
-
-                        if decoder.has_animation() {
-                            let _ = decoder.set_background_color(Rgba([0, 0, 0, 0]));
-                            let mut frames = SmallVec::new();
-
-                            for frame in decoder.into_frames() {
-                                let mut frame = frame?;
-                                // Convert from RGBA to BGRA.
-                                for pixel in frame.buffer_mut().chunks_exact_mut(4) {
-                                    pixel.swap(0, 2);
-                                }
-                                frames.push(frame);
-                            }
+                    frames.push(frame);
+                }
 
-                            frames
-                        } else {
-                            let mut data = DynamicImage::from_decoder(decoder)?.into_rgba8();
+                frames
+            } else {
+                let orientation = decoder.orientation()?;
+                let mut image = DynamicImage::from_decoder(decoder)?;
+                if apply_orientation {
+                    image.apply_orientation(orientation);
+                }
+                let mut data = image.into_rgba8();
 
-                            // Convert from RGBA to BGRA.
-                            for pixel in data.chunks_exact_mut(4) {
-                                pixel.swap(0, 2);
-                            }
+                // Convert from RGBA to BGRA.
+                for pixel in data.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
 
-                            SmallVec::from_elem(Frame::new(data), 1)
-                        }
-                    }
-                    _ => {
-                        let mut data =
-                            image::load_from_memory_with_format(&bytes, format)?.into_rgba8();
+                SmallVec::from_elem(Frame::new(data), 1)
+            }
+        }
+        _ => {
+            let mut data = decode_still_image(bytes, format, apply_orientation)?;
 
-                        // Convert from RGBA to BGRA.
-                        for pixel in data.chunks_exact_mut(4) {
-                            pixel.swap(0, 2);
-                        }
+            // Convert from RGBA to BGRA.
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
 
-                        SmallVec::from_elem(Frame::new(data), 1)
-                    }
-                };
+            SmallVec::from_elem(Frame::new(data), 1)
+        }
+    };
 
-                Ok(Arc::new(RenderImage::new(data)))
-            } else {
-                svg_renderer
-                    .render_single_frame(&bytes, 1.0, true)
-                    .map_err(Into::into)
-            }
+    Ok(RenderImage::new(data))
+}
+
+/// Decodes a single-frame image to RGBA, rotating and flipping it as its EXIF orientation tag
+/// describes if `apply_orientation` is set, so that its size is that of the image as displayed.
+pub(crate) fn decode_still_image(
+    bytes: &[u8],
+    format: ImageFormat,
+    apply_orientation: bool,
+) -> ImageResult<RgbaImage> {
+    let mut decoder = ImageReader::with_format(Cursor::new(bytes), format).into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    if apply_orientation {
+        image.apply_orientation(orientation);
+    }
+    Ok(image.into_rgba8())
+}
+
+/// An image decoded by [`DecodedImageAsset`].
+#[derive(Clone)]
+pub enum DecodedImage {
+    /// A raster image, such as a PNG, JPEG, WebP or GIF.
+    Raster(Arc<RenderImage>),
+    /// An SVG document, which can be painted at any size with [`Window::paint_svg`].
+    Svg {
+        /// The bytes of the document.
+        data: Arc<[u8]>,
+        /// The size the document declares for itself.
+        size: Size<f32>,
+    },
+}
+
+/// Loads an image from the bytes of a resource, detecting whether it's a raster image or an SVG
+/// from its contents, so that custom elements can paint any kind of image.
+pub enum DecodedImageAsset {}
+
+impl Asset for DecodedImageAsset {
+    type Source = Resource;
+    type Output = Result<DecodedImage, ImageCacheError>;
+
+    fn load(
+        source: Self::Source,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        let load = FileBytesAsset::load(source, cx);
+        let svg_renderer = cx.svg_renderer();
+        async move { decode_image(load.await?, &svg_renderer) }
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        match output {
+            Ok(DecodedImage::Raster(image)) => image.size_in_bytes(),
+            Ok(DecodedImage::Svg { data, .. }) => data.len(),
+            Err(_) => 0,
         }
     }
+
+    fn cancellable() -> bool {
+        true
+    }
+
+    fn retry_after(output: &Self::Output) -> Option<Duration> {
+        output.is_err().then_some(IMAGE_LOAD_RETRY_DELAY)
+    }
+}
+
+/// Decodes an image, sniffing its format from its magic bytes. Raster formats are detected by the
+/// `image` crate, and anything else is parsed as an SVG.
+fn decode_image(
+    bytes: Arc<[u8]>,
+    svg_renderer: &SvgRenderer,
+) -> Result<DecodedImage, ImageCacheError> {
+    if let Ok(format) = image::guess_format(&bytes) {
+        let image = decode_raster_image(&bytes, format, true)?;
+        return Ok(DecodedImage::Raster(Arc::new(image)));
+    }
+
+    let size = svg_renderer.intrinsic_size(&bytes, &SvgParseOptions::default())?;
+    Ok(DecodedImage::Svg { data: bytes, size })
 }
 
 /// An error that can occur when interacting with the image cache.
@@ -753,3 +1568,311 @@ impl From<image::ImageError> for ImageCacheError {
         Self::Image(Arc::new(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as gpui, Context, InteractiveElement as _, IntoElement, Render, Styled as _,
+        TestAppContext, px,
+    };
+    use image::{Delay, RgbImage};
+
+    fn animated_image(frame_delays_ms: &[u32]) -> RenderImage {
+        RenderImage::new(
+            frame_delays_ms
+                .iter()
+                .map(|delay_ms| {
+                    Frame::from_parts(
+                        RgbaImage::new(1, 1),
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(*delay_ms, 1),
+                    )
+                })
+                .collect::<SmallVec<[Frame; 1]>>(),
+        )
+    }
+
+    #[test]
+    fn test_animation_honors_frame_delays() {
+        let image = animated_image(&[50, 200, 0]);
+        let animation = ImageAnimationHandle::new();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert_eq!(
+            animation.advance(&image, Playback::Auto, start),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(
+            animation.advance(&image, Playback::Auto, at(100)),
+            Some(Duration::from_millis(150))
+        );
+        assert_eq!(animation.frame_index(), 1);
+        // Frames without a delay are shown for the default delay.
+        assert_eq!(
+            animation.advance(&image, Playback::Auto, at(260)),
+            Some(Duration::from_millis(90))
+        );
+        assert_eq!(animation.frame_index(), 2);
+        assert_eq!(
+            animation.advance(&image, Playback::Auto, at(350)),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(animation.frame_index(), 0);
+        assert_eq!(animation.frame_count(), 3);
+    }
+
+    #[test]
+    fn test_animation_playback() {
+        let image = animated_image(&[100, 100]);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        let paused = ImageAnimationHandle::new();
+        assert_eq!(paused.advance(&image, Playback::Paused, start), None);
+        assert_eq!(paused.advance(&image, Playback::Paused, at(500)), None);
+        assert_eq!(paused.frame_index(), 0);
+
+        // Looping animations stop on their last frame.
+        let looping = ImageAnimationHandle::new();
+        looping.advance(&image, Playback::Loop(2), start);
+        assert!(
+            looping
+                .advance(&image, Playback::Loop(2), at(350))
+                .is_some()
+        );
+        assert_eq!(looping.frame_index(), 1);
+        assert_eq!(looping.advance(&image, Playback::Loop(2), at(400)), None);
+        assert_eq!(looping.frame_index(), 1);
+        assert_eq!(looping.advance(&image, Playback::Loop(2), at(1000)), None);
+    }
+
+    /// Encodes a 2x1 JPEG whose EXIF orientation tag asks for it to be rotated 90° clockwise.
+    fn rotated_jpeg() -> Vec<u8> {
+        let mut jpeg = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(2, 1))
+            .write_to(&mut Cursor::new(&mut jpeg), ImageFormat::Jpeg)
+            .expect("encoding a JPEG should succeed");
+
+        let mut exif_segment = vec![0xFF, 0xE1, 0x00, 0x22];
+        exif_segment.extend_from_slice(b"Exif\0\0MM\0\x2a\0\0\0\x08");
+        exif_segment.extend_from_slice(&[0x00, 0x01, 0x01, 0x12, 0x00, 0x03]);
+        exif_segment.extend_from_slice(&[0x00, 0x00, 0x00, 0x01, 0x00, 0x06, 0x00, 0x00]);
+        exif_segment.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+        // Insert the segment right after the start of image marker.
+        jpeg.splice(2..2, exif_segment);
+        jpeg
+    }
+
+    #[test]
+    fn test_decode_applies_exif_orientation() {
+        let jpeg = rotated_jpeg();
+
+        let oriented = decode_raster_image(&jpeg, ImageFormat::Jpeg, true).unwrap();
+        assert_eq!(oriented.size(0), size(DevicePixels(1), DevicePixels(2)));
+
+        let unoriented = decode_raster_image(&jpeg, ImageFormat::Jpeg, false).unwrap();
+        assert_eq!(unoriented.size(0), size(DevicePixels(2), DevicePixels(1)));
+    }
+
+    #[test]
+    fn test_downsample_keeps_display_size() {
+        let image = RenderImage::new(SmallVec::from_elem(
+            Frame::new(RgbaImage::new(1000, 500)),
+            1,
+        ));
+
+        let downsampled = downsample_image(&image, 64);
+        assert_eq!(
+            downsampled.size(0),
+            size(DevicePixels(64), DevicePixels(32))
+        );
+        assert_eq!(downsampled.render_size(0), image.render_size(0));
+    }
+
+    #[gpui::test]
+    fn test_image_is_painted_while_it_is_downsampled(cx: &mut TestAppContext) {
+        struct TestView(Arc<RenderImage>);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                img(self.0.clone()).id("image").w(px(50.)).h(px(25.))
+            }
+        }
+
+        let image = Arc::new(RenderImage::new(SmallVec::from_elem(
+            Frame::new(RgbaImage::new(1000, 500)),
+            1,
+        )));
+        let (_, cx) = cx.add_window_view(|_, _| TestView(image));
+        let painted_size = |cx: &mut gpui::VisualTestContext| {
+            cx.update(|window, _| {
+                let sprites = &window.rendered_frame.scene.polychrome_sprites;
+                assert_eq!(sprites.len(), 1);
+                sprites[0].tile.bounds.size
+            })
+        };
+
+        // The full image is painted until the downsampled one is ready.
+        assert_eq!(
+            painted_size(cx),
+            size(DevicePixels(1000), DevicePixels(500))
+        );
+
+        cx.run_until_parked();
+        assert_eq!(painted_size(cx), size(DevicePixels(128), DevicePixels(64)));
+    }
+
+    #[gpui::test]
+    fn test_object_position(cx: &mut TestAppContext) {
+        struct TestView(Arc<RenderImage>, ObjectFit, Point<DefiniteLength>);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                img(self.0.clone())
+                    .object_fit(self.1)
+                    .object_position(self.2.x, self.2.y)
+                    .size(px(100.))
+            }
+        }
+
+        // The bounds of the image painted into the 100x100 element, in pixels.
+        let paint = |image_size: (u32, u32),
+                     object_fit: ObjectFit,
+                     (x, y): (f32, f32),
+                     cx: &mut TestAppContext| {
+            let image = Arc::new(RenderImage::new(SmallVec::from_elem(
+                Frame::new(RgbaImage::new(image_size.0, image_size.1)),
+                1,
+            )));
+            let position = point(relative(x), relative(y));
+            let (_, cx) = cx.add_window_view(|_, _| TestView(image, object_fit, position));
+            cx.update(|window, _| {
+                let sprites = &window.rendered_frame.scene.polychrome_sprites;
+                assert_eq!(sprites.len(), 1);
+                let bounds = sprites[0].bounds;
+                let scale_factor = window.scale_factor();
+                [
+                    bounds.origin.x.0 / scale_factor,
+                    bounds.origin.y.0 / scale_factor,
+                    bounds.size.width.0 / scale_factor,
+                    bounds.size.height.0 / scale_factor,
+                ]
+            })
+        };
+
+        // Covering images are cropped toward the position along the axis they overflow.
+        let tall_image = (50, 150);
+        assert_eq!(
+            paint(tall_image, ObjectFit::Cover, (0.5, 0.), cx),
+            [0., 0., 100., 300.]
+        );
+        assert_eq!(
+            paint(tall_image, ObjectFit::Cover, (0.5, 1.), cx),
+            [0., -200., 100., 300.]
+        );
+        assert_eq!(
+            paint(tall_image, ObjectFit::Cover, (0., 0.25), cx),
+            [0., -50., 100., 300.]
+        );
+
+        // Contained images stay centered, but scaled down ones are moved within the bounds.
+        let wide_image = (200, 100);
+        assert_eq!(
+            paint(wide_image, ObjectFit::Contain, (0., 0.), cx),
+            [0., 25., 100., 50.]
+        );
+        assert_eq!(
+            paint(wide_image, ObjectFit::ScaleDown, (0., 0.), cx),
+            [0., 0., 100., 50.]
+        );
+        assert_eq!(
+            paint(wide_image, ObjectFit::ScaleDown, (1., 1.), cx),
+            [0., 50., 100., 50.]
+        );
+    }
+
+    #[gpui::test]
+    fn test_nine_patch_regions(cx: &mut TestAppContext) {
+        struct TestView(Arc<RenderImage>, Size<Pixels>);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                img(self.0.clone())
+                    .nine_patch(Edges::all(px(10.)))
+                    .w(self.1.width)
+                    .h(self.1.height)
+            }
+        }
+
+        // The sprites painted for the nine patch, as their destination bounds in device pixels
+        // and the source region of the 30x30 image they sample, from the top left.
+        let paint = |bounds_size: Size<Pixels>, cx: &mut TestAppContext| {
+            let image = Arc::new(RenderImage::new(SmallVec::from_elem(
+                Frame::new(RgbaImage::new(30, 30)),
+                1,
+            )));
+            let (_, cx) = cx.add_window_view(|_, _| TestView(image, bounds_size));
+            cx.update(|window, _| {
+                let mut sprites = window
+                    .rendered_frame
+                    .scene
+                    .polychrome_sprites
+                    .iter()
+                    .map(|sprite| {
+                        let (bounds, source) = (sprite.bounds, sprite.tile.bounds);
+                        (
+                            [
+                                bounds.origin.x.0,
+                                bounds.origin.y.0,
+                                bounds.size.width.0,
+                                bounds.size.height.0,
+                            ],
+                            [
+                                source.origin.x.0,
+                                source.origin.y.0,
+                                source.size.width.0,
+                                source.size.height.0,
+                            ],
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                sprites.sort_by(|a, b| (a.0[1], a.0[0]).partial_cmp(&(b.0[1], b.0[0])).unwrap());
+                sprites
+            })
+        };
+        // The expected sprites for the given destination and source grid lines, skipping
+        // empty cells.
+        let grid = |columns: [f32; 4], rows: [f32; 4]| {
+            let sources = [0, 10, 20, 30];
+            let mut cells = Vec::new();
+            for row in 0..3 {
+                for column in 0..3 {
+                    let width = columns[column + 1] - columns[column];
+                    let height = rows[row + 1] - rows[row];
+                    if width > 0. && height > 0. {
+                        cells.push((
+                            [columns[column], rows[row], width, height],
+                            [sources[column], sources[row], 10, 10],
+                        ));
+                    }
+                }
+            }
+            cells
+        };
+
+        // The corners keep their size at the test platform's scale factor of 2, while the edges
+        // and center stretch.
+        let sprites = paint(size(px(100.), px(60.)), cx);
+        assert_eq!(sprites.len(), 9);
+        assert_eq!(sprites, grid([0., 20., 180., 200.], [0., 20., 100., 120.]));
+
+        // When the insets are larger than half of the bounds, the corners are scaled down evenly
+        // to fit and the row between them is left out.
+        let sprites = paint(size(px(30.), px(16.)), cx);
+        assert_eq!(sprites.len(), 6);
+        assert_eq!(sprites, grid([0., 16., 44., 60.], [0., 16., 16., 32.]));
+    }
+}
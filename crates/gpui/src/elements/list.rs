@@ -10,8 +10,8 @@
 use crate::{
     AnyElement, App, AvailableSpace, Bounds, ContentMask, DispatchPhase, Edges, Element, EntityId,
     FocusHandle, GlobalElementId, Hitbox, HitboxBehavior, InspectorElementId, IntoElement,
-    Overflow, Pixels, Point, ScrollDelta, ScrollWheelEvent, Size, Style, StyleRefinement, Styled,
-    Window, point, px, size,
+    Overflow, OverscrollBehavior, Pixels, Point, ScrollStrategy, ScrollWheelEvent, Size, Style,
+    StyleRefinement, Styled, Window, point, px, size,
 };
 use collections::VecDeque;
 use refineable::Refineable as _;
@@ -47,6 +47,14 @@ impl List {
         self.sizing_behavior = behavior;
         self
     }
+
+    /// Set whether scrolling past the top or bottom of the list scrolls the scroll container it's
+    /// in, which it does by default.
+    /// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/overscroll-behavior)
+    pub fn overscroll_behavior(mut self, behavior: OverscrollBehavior) -> Self {
+        self.style.overscroll_behavior = Some(behavior);
+        self
+    }
 }
 
 /// The list state that views must hold on behalf of the list element.
@@ -71,6 +79,35 @@ struct StateInner {
     scroll_handler: Option<Box<dyn FnMut(&ListScrollEvent, &mut Window, &mut App)>>,
     scrollbar_drag_start_height: Option<Pixels>,
     measuring_behavior: ListMeasuringBehavior,
+    pending_scroll_to_item: Option<(usize, ScrollStrategy)>,
+    reach_end_handlers: Vec<ReachEndHandler>,
+    scroll_anchoring: bool,
+    anchor: Option<ScrollAnchor>,
+}
+
+/// An item that stays at the same position in the viewport, see [`ListState::anchor_to`].
+#[derive(Clone, Copy)]
+struct ScrollAnchor {
+    item_ix: usize,
+    /// The distance from the top of the viewport to the top of the item.
+    offset_from_top: Pixels,
+}
+
+struct ReachEndHandler {
+    edge: ListEdge,
+    threshold_items: usize,
+    armed: bool,
+    #[allow(clippy::type_complexity)]
+    callback: Rc<RefCell<Box<dyn FnMut(&mut Window, &mut App)>>>,
+}
+
+/// One of the two ends of a list, see [`ListState::on_reach_end`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListEdge {
+    /// The first item in the list.
+    Top,
+    /// The last item in the list.
+    Bottom,
 }
 
 /// Whether the list is scrolling from top to bottom or bottom to top.
@@ -225,6 +262,10 @@ impl ListState {
             reset: false,
             scrollbar_drag_start_height: None,
             measuring_behavior: ListMeasuringBehavior::default(),
+            pending_scroll_to_item: None,
+            reach_end_handlers: Vec::new(),
+            scroll_anchoring: false,
+            anchor: None,
         })));
         this.splice(0..0, item_count);
         this
@@ -238,6 +279,38 @@ impl ListState {
         self
     }
 
+    /// Keep the same content on screen when items are spliced in or out above it.
+    ///
+    /// The list already keys its scroll position to an item and an offset within it, so splices
+    /// above that item don't move the content once the list has been scrolled. With anchoring,
+    /// a top-aligned list that hasn't been scrolled yet is keyed to its first item too, instead
+    /// of revealing items that are inserted above it.
+    pub fn with_scroll_anchoring(self) -> Self {
+        self.0.borrow_mut().scroll_anchoring = true;
+        self
+    }
+
+    /// Keep item `ix` at its current position in the viewport across splices, until the list
+    /// is scrolled again.
+    ///
+    /// This is useful when the item at the top of the viewport is about to be replaced, but a
+    /// later item, e.g. the one being read, should stay put.
+    pub fn anchor_to(&self, ix: usize) {
+        let state = &mut *self.0.borrow_mut();
+        if ix >= state.items.summary().count {
+            return;
+        }
+        let scroll_top = state.scroll_top(&state.logical_scroll_top());
+        let (item_start, ..) = state
+            .items
+            .find::<ListItemSummary, _>((), &Count(ix), Bias::Right);
+        state.anchor = Some(ScrollAnchor {
+            item_ix: ix,
+            offset_from_top: item_start.height - scroll_top,
+        });
+        state.pending_scroll_to_item = None;
+    }
+
     /// Reset this instantiation of the list state.
     ///
     /// Note that this will cause scroll events to be dropped until the next paint.
@@ -248,6 +321,8 @@ impl ListState {
             state.measuring_behavior.reset();
             state.logical_scroll_top = None;
             state.scrollbar_drag_start_height = None;
+            state.pending_scroll_to_item = None;
+            state.anchor = None;
             state.items.summary().count
         };
 
@@ -261,7 +336,11 @@ impl ListState {
 
     /// Inform the list state that the items in `old_range` have been replaced
     /// by `count` new items that must be recalculated.
-    pub fn splice(&self, old_range: Range<usize>, count: usize) {
+    ///
+    /// Returns how far the spliced items moved the content the list is scrolled to, which is
+    /// positive when items were inserted above it. The height of the new items is estimated
+    /// until they're measured.
+    pub fn splice(&self, old_range: Range<usize>, count: usize) -> Pixels {
         self.splice_focusable(old_range, (0..count).map(|_| None))
     }
 
@@ -273,8 +352,34 @@ impl ListState {
         &self,
         old_range: Range<usize>,
         focus_handles: impl IntoIterator<Item = Option<FocusHandle>>,
-    ) {
+    ) -> Pixels {
         let state = &mut *self.0.borrow_mut();
+        let old_count = state.items.summary().count;
+
+        if old_count > 0
+            && state.scroll_anchoring
+            && state.logical_scroll_top.is_none()
+            && state.alignment == ListAlignment::Top
+        {
+            state.logical_scroll_top = Some(ListOffset::default());
+        }
+
+        let anchored_ix = state.anchor.map(|anchor| anchor.item_ix).or(state
+            .logical_scroll_top
+            .map(|scroll_top| scroll_top.item_ix));
+        let removed_height = if anchored_ix.is_some_and(|ix| old_range.end <= ix) {
+            let mut cursor = state.items.cursor::<Count>(());
+            cursor.seek(&Count(old_range.start), Bias::Right);
+            let removed = cursor.slice(&Count(old_range.end), Bias::Right);
+            Some(
+                removed
+                    .iter()
+                    .map(|item| state.estimated_item_height(item))
+                    .fold(px(0.), |total, height| total + height),
+            )
+        } else {
+            None
+        };
 
         let mut old_items = state.items.cursor::<Count>(());
         let mut new_items = old_items.slice(&Count(old_range.start), Bias::Right);
@@ -304,6 +409,55 @@ impl ListState {
                 *item_ix = *item_ix - (old_range.end - old_range.start) + spliced_count;
             }
         }
+
+        if let Some(anchor) = state.anchor.as_mut() {
+            if old_range.contains(&anchor.item_ix) {
+                state.anchor = None;
+            } else if old_range.end <= anchor.item_ix {
+                anchor.item_ix = anchor.item_ix - (old_range.end - old_range.start) + spliced_count;
+            }
+        }
+
+        if spliced_count > 0 {
+            for handler in &mut state.reach_end_handlers {
+                match handler.edge {
+                    ListEdge::Top if old_range.start == 0 => handler.armed = true,
+                    ListEdge::Bottom if old_range.end == old_count => handler.armed = true,
+                    _ => {}
+                }
+            }
+        }
+
+        removed_height.map_or(px(0.), |removed_height| {
+            let inserted_height = (0..spliced_count)
+                .map(|_| state.estimated_item_height(&ListItem::Unmeasured { focus_handle: None }))
+                .fold(px(0.), |total, height| total + height);
+            inserted_height - removed_height
+        })
+    }
+
+    /// Call `callback` when the list is scrolled to within `threshold_items` items of `edge`,
+    /// e.g. to load more history or log lines.
+    ///
+    /// The callback fires at most once each time the visible range enters that region, and is
+    /// re-armed when items are spliced in at that edge. Registering a [`ListEdge::Top`] callback
+    /// turns on [`Self::with_scroll_anchoring`], so that loaded items don't push the content down.
+    pub fn on_reach_end(
+        &self,
+        edge: ListEdge,
+        threshold_items: usize,
+        callback: impl FnMut(&mut Window, &mut App) + 'static,
+    ) {
+        let state = &mut *self.0.borrow_mut();
+        if edge == ListEdge::Top {
+            state.scroll_anchoring = true;
+        }
+        state.reach_end_handlers.push(ReachEndHandler {
+            edge,
+            threshold_items,
+            armed: true,
+            callback: Rc::new(RefCell::new(Box::new(callback))),
+        });
     }
 
     /// Set a handler that will be called when the list is scrolled.
@@ -342,6 +496,8 @@ impl ListState {
             item_ix: cursor.start().count,
             offset_in_item: new_pixel_offset - cursor.start().height,
         });
+        state.pending_scroll_to_item = None;
+        state.anchor = None;
     }
 
     /// Scroll the list to the given offset
@@ -354,6 +510,23 @@ impl ListState {
         }
 
         state.logical_scroll_top = Some(scroll_top);
+        state.pending_scroll_to_item = None;
+        state.anchor = None;
+    }
+
+    /// Scroll the list so that the given item is placed according to the given strategy.
+    ///
+    /// This works even if the item or the items around it have never been measured: their
+    /// heights are estimated from the average height of the measured items, and the scroll
+    /// position is corrected on subsequent frames as those items get measured.
+    pub fn scroll_to_item(&self, ix: usize, strategy: ScrollStrategy) {
+        let state = &mut *self.0.borrow_mut();
+        let item_count = state.items.summary().count;
+        if item_count == 0 {
+            return;
+        }
+        state.pending_scroll_to_item = Some((ix.min(item_count - 1), strategy));
+        state.anchor = None;
     }
 
     /// Scroll the list to the given item, such that the item is fully visible.
@@ -389,22 +562,21 @@ impl ListState {
     }
 
     /// Get the bounds for the given item in window coordinates, if it's
-    /// been rendered.
+    /// been measured. The bounds may lie outside of the list's viewport,
+    /// e.g. for items in the overdraw.
     pub fn bounds_for_item(&self, ix: usize) -> Option<Bounds<Pixels>> {
         let state = &*self.0.borrow();
 
         let bounds = state.last_layout_bounds.unwrap_or_default();
+        let padding = state.last_padding.unwrap_or_default();
         let scroll_top = state.logical_scroll_top();
-        if ix < scroll_top.item_ix {
-            return None;
-        }
 
         let mut cursor = state.items.cursor::<Dimensions<Count, Height>>(());
         cursor.seek(&Count(scroll_top.item_ix), Bias::Right);
 
-        let scroll_top = cursor.start().1.0 + scroll_top.offset_in_item;
+        let scroll_top = cursor.start().1.0 + scroll_top.offset_in_item - padding.top;
 
-        cursor.seek_forward(&Count(ix), Bias::Right);
+        cursor.seek(&Count(ix), Bias::Right);
         if let Some(&ListItem::Measured { size, .. }) = cursor.item() {
             let &Dimensions(Count(count), Height(top), _) = cursor.start();
             if count == ix {
@@ -484,6 +656,32 @@ impl StateInner {
         scroll_top.item_ix..cursor.start().count + 1
     }
 
+    /// Disarm and return the reach-end callbacks whose region the visible range has entered.
+    #[allow(clippy::type_complexity)]
+    fn reached_ends(
+        &mut self,
+        visible_range: Range<usize>,
+    ) -> Vec<Rc<RefCell<Box<dyn FnMut(&mut Window, &mut App)>>>> {
+        let item_count = self.items.summary().count;
+        let visible_end = visible_range.end.min(item_count);
+        let mut reached = Vec::new();
+        for handler in &mut self.reach_end_handlers {
+            let within_threshold = match handler.edge {
+                ListEdge::Top => visible_range.start <= handler.threshold_items,
+                ListEdge::Bottom => item_count - visible_end <= handler.threshold_items,
+            };
+            if !within_threshold {
+                handler.armed = true;
+            } else if handler.armed {
+                handler.armed = false;
+                reached.push(handler.callback.clone());
+            }
+        }
+        reached
+    }
+
+    /// Scrolls by the vertical part of the delta from the given scroll top, returning how much of
+    /// it was consumed before reaching the top or bottom of the list.
     fn scroll(
         &mut self,
         scroll_top: &ListOffset,
@@ -492,19 +690,20 @@ impl StateInner {
         current_view: EntityId,
         window: &mut Window,
         cx: &mut App,
-    ) {
+    ) -> Pixels {
         // Drop scroll events after a reset, since we can't calculate
         // the new logical scroll top without the item heights
         if self.reset {
-            return;
+            return px(0.);
         }
+        self.pending_scroll_to_item = None;
+        self.anchor = None;
 
         let padding = self.last_padding.unwrap_or_default();
         let scroll_max =
             (self.items.summary().height + padding.top + padding.bottom - height).max(px(0.));
-        let new_scroll_top = (self.scroll_top(scroll_top) - delta.y)
-            .max(px(0.))
-            .min(scroll_max);
+        let old_scroll_top = self.scroll_top(scroll_top);
+        let new_scroll_top = (old_scroll_top - delta.y).max(px(0.)).min(scroll_max);
 
         if self.alignment == ListAlignment::Bottom && new_scroll_top == scroll_max {
             self.logical_scroll_top = None;
@@ -534,6 +733,7 @@ impl StateInner {
         }
 
         cx.notify(current_view);
+        old_scroll_top - new_scroll_top
     }
 
     fn logical_scroll_top(&self) -> ListOffset {
@@ -559,6 +759,104 @@ impl StateInner {
         start.height + logical_scroll_top.offset_in_item
     }
 
+    /// The scroll top that keeps the anchored item at its offset from the top of the viewport.
+    fn scroll_top_for_anchor(&self, anchor: ScrollAnchor) -> ListOffset {
+        let (item_start, ..) =
+            self.items
+                .find::<ListItemSummary, _>((), &Count(anchor.item_ix), Bias::Right);
+        let scroll_top = (item_start.height - anchor.offset_from_top).max(px(0.));
+        let (start, ..) =
+            self.items
+                .find::<ListItemSummary, _>((), &Height(scroll_top), Bias::Right);
+        ListOffset {
+            item_ix: start.count,
+            offset_in_item: scroll_top - start.height,
+        }
+    }
+
+    /// The height of the given item if it's been measured, and otherwise the average height of
+    /// the measured items.
+    fn estimated_item_height(&self, item: &ListItem) -> Pixels {
+        item.size().map_or_else(
+            || {
+                let summary = self.items.summary();
+                if summary.rendered_count == 0 {
+                    px(0.)
+                } else {
+                    summary.height / summary.rendered_count as f32
+                }
+            },
+            |size| size.height,
+        )
+    }
+
+    /// The scroll top that places the given item according to the given strategy, using
+    /// estimated heights for unmeasured items. Returns `None` if the list shouldn't scroll.
+    fn scroll_top_for_item(
+        &self,
+        ix: usize,
+        strategy: ScrollStrategy,
+        viewport_height: Pixels,
+    ) -> Option<ListOffset> {
+        let summary = self.items.summary();
+        let average_height = if summary.rendered_count == 0 {
+            px(0.)
+        } else {
+            summary.height / summary.rendered_count as f32
+        };
+
+        let mut cursor = self.items.cursor::<ListItemSummary>(());
+        cursor.seek(&Count(ix), Bias::Right);
+        let item_top = cursor.start().height + average_height * cursor.start().unrendered_count;
+        let item_height = cursor
+            .item()
+            .map_or(px(0.), |item| self.estimated_item_height(item));
+
+        let goal_top = match strategy {
+            ScrollStrategy::Top => item_top,
+            ScrollStrategy::Center => item_top + (item_height - viewport_height) / 2.,
+            ScrollStrategy::Bottom => item_top + item_height - viewport_height,
+            ScrollStrategy::Nearest => {
+                let scroll_top = self.scroll_top(&self.logical_scroll_top());
+                if item_top < scroll_top {
+                    item_top
+                } else if item_top + item_height > scroll_top + viewport_height {
+                    item_top + item_height - viewport_height
+                } else {
+                    return None;
+                }
+            }
+        }
+        .max(px(0.));
+
+        if goal_top >= item_top {
+            return Some(ListOffset {
+                item_ix: ix,
+                offset_in_item: goal_top - item_top,
+            });
+        }
+
+        // Walk upwards from the item until we've covered the distance to the goal.
+        let mut remaining = item_top - goal_top;
+        loop {
+            cursor.prev();
+            let Some(item) = cursor.item() else {
+                return Some(ListOffset {
+                    item_ix: 0,
+                    offset_in_item: px(0.),
+                });
+            };
+            let height = self.estimated_item_height(item);
+            if height >= remaining {
+                return Some(ListOffset {
+                    item_ix: cursor.start().count,
+                    offset_in_item: height - remaining,
+                });
+            }
+            remaining -= height;
+        }
+    }
+
     fn layout_all_items(
         &mut self,
         available_width: Pixels,
@@ -874,6 +1172,8 @@ impl StateInner {
             return;
         };
         let height = bounds.size.height;
+        self.pending_scroll_to_item = None;
+        self.anchor = None;
 
         let padding = self.last_padding.unwrap_or_default();
         let content_height = self.items.summary().height;
@@ -911,7 +1211,7 @@ impl std::fmt::Debug for ListItem {
 
 /// An offset into the list's items, in terms of the item index and the number
 /// of pixels off the top left of the item.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct ListOffset {
     /// The index of an item in the list
     pub item_ix: usize,
@@ -1040,6 +1340,32 @@ impl Element for List {
         let padding = style
             .padding
             .to_pixels(bounds.size.into(), window.rem_size());
+
+        // Resolve a pending `scroll_to_item` with the heights we know about, and keep it pending
+        // for another frame if measuring the items around it changes where it should be.
+        let viewport_height = bounds.size.height - padding.top - padding.bottom;
+        if let Some(anchor) = state.anchor {
+            state.logical_scroll_top = Some(state.scroll_top_for_anchor(anchor));
+        }
+
+        let mut scroll_to_item = None;
+        if let Some((ix, strategy)) = state.pending_scroll_to_item.take()
+            && let Some(scroll_top) = state.scroll_top_for_item(ix, strategy, viewport_height)
+        {
+            // Nearest only decides which edge to align to when the request is first resolved.
+            let strategy = match strategy {
+                ScrollStrategy::Nearest
+                    if scroll_top.item_ix == ix && scroll_top.offset_in_item == px(0.) =>
+                {
+                    ScrollStrategy::Top
+                }
+                ScrollStrategy::Nearest => ScrollStrategy::Bottom,
+                strategy => strategy,
+            };
+            state.logical_scroll_top = Some(scroll_top);
+            scroll_to_item = Some((ix, strategy, scroll_top));
+        }
+
         let layout =
             match state.prepaint_items(bounds, padding, true, &mut self.render_item, window, cx) {
                 Ok(layout) => layout,
@@ -1051,6 +1377,20 @@ impl Element for List {
                 }
             };
 
+        if let Some((ix, strategy, scroll_top)) = scroll_to_item
+            && state.scroll_top_for_item(ix, strategy, viewport_height) != Some(scroll_top)
+        {
+            state.pending_scroll_to_item = Some((ix, strategy));
+            window.request_animation_frame();
+        }
+
+        if !state.reach_end_handlers.is_empty() {
+            let visible_range = state.visible_range(viewport_height, &layout.scroll_top);
+            for callback in state.reached_ends(visible_range) {
+                window.defer(cx, move |window, cx| (callback.borrow_mut())(window, cx));
+            }
+        }
+
         state.last_layout_bounds = Some(bounds);
         state.last_padding = Some(padding);
         ListPrepaintState { hitbox, layout }
@@ -1074,22 +1414,26 @@ impl Element for List {
         });
 
         let list_state = self.state.clone();
+        let page_size = bounds.size;
         let height = bounds.size.height;
-        let scroll_top = prepaint.layout.scroll_top;
+        let mut scroll_top = prepaint.layout.scroll_top;
         let hitbox_id = prepaint.hitbox.id;
-        let mut accumulated_scroll_delta = ScrollDelta::default();
+        let overscroll_behavior = self.style.overscroll_behavior.unwrap_or_default();
         window.on_mouse_event(move |event: &ScrollWheelEvent, phase, window, cx| {
             if phase == DispatchPhase::Bubble && hitbox_id.should_handle_scroll(window) {
-                accumulated_scroll_delta = accumulated_scroll_delta.coalesce(event.delta);
-                let pixel_delta = accumulated_scroll_delta.pixel_delta(px(20.));
-                list_state.0.borrow_mut().scroll(
-                    &scroll_top,
-                    height,
-                    pixel_delta,
-                    current_view,
-                    window,
-                    cx,
-                )
+                // Like other scroll containers, the list scrolls by what's left of the delta
+                // after the containers it contains, and leaves the rest to the ones it's in.
+                let delta = window
+                    .unconsumed_scroll_delta
+                    .unwrap_or_else(|| event.delta.pixel_delta_in_page(px(20.), page_size));
+                let mut state = list_state.0.borrow_mut();
+                let consumed = state.scroll(&scroll_top, height, delta, current_view, window, cx);
+                scroll_top = state.logical_scroll_top();
+                drop(state);
+                window.unconsumed_scroll_delta = Some(match overscroll_behavior {
+                    OverscrollBehavior::Auto => point(delta.x, delta.y - consumed),
+                    OverscrollBehavior::Contain => Point::default(),
+                });
             }
         });
     }
@@ -1284,4 +1628,172 @@ mod test {
         assert_eq!(offset.item_ix, 0);
         assert_eq!(offset.offset_in_item, px(0.));
     }
+
+    #[gpui::test]
+    fn test_scroll_to_unmeasured_item(cx: &mut TestAppContext) {
+        use crate::{
+            AppContext, Context, IntoElement, ListOffset, ListState, Render, ScrollStrategy,
+            Styled, Window, div, list, point, px, size,
+        };
+
+        let cx = cx.add_empty_window();
+
+        let state = ListState::new(100, crate::ListAlignment::Top, px(10.));
+
+        struct TestView(ListState);
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                list(self.0.clone(), |ix, _, _| {
+                    let height = if ix % 2 == 0 { px(10.) } else { px(30.) };
+                    div().h(height).w_full().into_any()
+                })
+                .w_full()
+                .h_full()
+            }
+        }
+
+        let draw = |cx: &mut gpui::VisualTestContext| {
+            cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_, cx| {
+                cx.new(|_| TestView(state.clone()))
+            });
+        };
+        draw(cx);
+
+        state.scroll_to_item(10, ScrollStrategy::Top);
+        draw(cx);
+        assert_eq!(
+            state.logical_scroll_top(),
+            ListOffset {
+                item_ix: 10,
+                offset_in_item: px(0.),
+            }
+        );
+
+        // Only the items around item 10 have been measured, so the first attempt is an estimate
+        // that gets corrected over the following frames.
+        state.scroll_to_item(60, ScrollStrategy::Center);
+        for _ in 0..3 {
+            draw(cx);
+        }
+        let item_bounds = state.bounds_for_item(60).expect("item should be measured");
+        assert_eq!(item_bounds.top(), px(45.));
+        assert_eq!(item_bounds.bottom(), px(55.));
+    }
+
+    #[gpui::test]
+    fn test_on_reach_end(cx: &mut TestAppContext) {
+        use crate::{
+            AppContext, Context, IntoElement, ListEdge, ListOffset, ListState, Render, Styled,
+            Window, div, list, point, px, size,
+        };
+        use std::{cell::Cell, rc::Rc};
+
+        let cx = cx.add_empty_window();
+
+        struct TestView(ListState);
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                list(self.0.clone(), |_, _, _| {
+                    div().h(px(10.)).w_full().into_any()
+                })
+                .w_full()
+                .h_full()
+            }
+        }
+
+        let state = ListState::new(20, crate::ListAlignment::Top, px(0.));
+        let reached_top = Rc::new(Cell::new(0));
+        state.on_reach_end(ListEdge::Top, 2, {
+            let reached_top = reached_top.clone();
+            move |_, _| reached_top.set(reached_top.get() + 1)
+        });
+        let draw = |cx: &mut gpui::VisualTestContext| {
+            cx.draw(point(px(0.), px(0.)), size(px(100.), px(50.)), |_, cx| {
+                cx.new(|_| TestView(state.clone()))
+            });
+            cx.run_until_parked();
+        };
+
+        // The list starts at the top, so the callback fires once.
+        draw(cx);
+        draw(cx);
+        assert_eq!(reached_top.get(), 1);
+
+        // Prepending items keeps the previously visible items in place and re-arms the callback.
+        state.splice(0..0, 5);
+        assert_eq!(state.logical_scroll_top().item_ix, 5);
+        draw(cx);
+        assert_eq!(reached_top.get(), 1);
+
+        // Scrolling back near the top fires it again, but only once.
+        state.scroll_to(ListOffset {
+            item_ix: 2,
+            offset_in_item: px(0.),
+        });
+        draw(cx);
+        draw(cx);
+        assert_eq!(reached_top.get(), 2);
+
+        state.splice(0..0, 10);
+        assert_eq!(state.logical_scroll_top().item_ix, 12);
+        state.scroll_to(ListOffset {
+            item_ix: 1,
+            offset_in_item: px(0.),
+        });
+        draw(cx);
+        assert_eq!(reached_top.get(), 3);
+    }
+
+    #[gpui::test]
+    fn test_scroll_anchoring(cx: &mut TestAppContext) {
+        use crate::{
+            AppContext, Context, IntoElement, ListOffset, ListState, Render, Styled, Window, div,
+            list, point, px, size,
+        };
+
+        let cx = cx.add_empty_window();
+
+        struct TestView(ListState);
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                list(self.0.clone(), |_, _, _| {
+                    div().h(px(10.)).w_full().into_any()
+                })
+                .w_full()
+                .h_full()
+            }
+        }
+
+        let state = ListState::new(10, crate::ListAlignment::Top, px(0.))
+            .measure_all()
+            .with_scroll_anchoring();
+        let draw = |cx: &mut gpui::VisualTestContext| {
+            cx.draw(point(px(0.), px(0.)), size(px(100.), px(50.)), |_, cx| {
+                cx.new(|_| TestView(state.clone()))
+            });
+        };
+        draw(cx);
+
+        // Items prepended to a list that hasn't been scrolled yet don't push the content down.
+        assert_eq!(state.splice(0..0, 3), px(30.));
+        assert_eq!(state.logical_scroll_top().item_ix, 3);
+        draw(cx);
+
+        // An anchored item keeps its position when the items above it are removed.
+        state.scroll_to(ListOffset {
+            item_ix: 6,
+            offset_in_item: px(0.),
+        });
+        draw(cx);
+        state.anchor_to(8);
+        assert_eq!(state.splice(6..8, 0), px(-20.));
+        draw(cx);
+        assert_eq!(
+            state.logical_scroll_top(),
+            ListOffset {
+                item_ix: 4,
+                offset_in_item: px(0.),
+            }
+        );
+    }
 }
@@ -6,10 +6,12 @@ mod div;
 mod image_cache;
 mod img;
 mod list;
+mod scrollbars;
 mod surface;
 mod svg;
 mod text;
 mod uniform_list;
+mod virtual_grid;
 
 pub use anchored::*;
 pub use animation::*;
@@ -19,7 +21,9 @@ pub use div::*;
 pub use image_cache::*;
 pub use img::*;
 pub use list::*;
+pub use scrollbars::*;
 pub use surface::*;
 pub use svg::*;
 pub use text::*;
 pub use uniform_list::*;
+pub use virtual_grid::*;
@@ -0,0 +1,689 @@
+//! Scrollbars drawn over a scrollable container and bound to its [`ScrollHandle`], so that
+//! apps don't each need to build their own from the handle's offsets.
+//!
+//! The thumb can be dragged, clicking the track on either side of the thumb scrolls by a
+//! page, and in [`ScrollbarMode::Overlay`] the bars are drawn thin until they're hovered.
+//! Only the thumb blocks the content beneath it from being hovered, and scroll wheel events
+//! always reach the container.
+
+use crate::{
+    Along, AnyElement, App, Axis, Bounds, Corners, DispatchPhase, Element, ElementId,
+    GlobalElementId, Hitbox, HitboxBehavior, Hsla, InspectorElementId, IntoElement, LayoutId,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, ScrollHandle, Task,
+    Window, fill, hsla, point, px, size,
+};
+use smallvec::SmallVec;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+/// Wraps the given scrollable container in scrollbars for the scroll handle it tracks with
+/// [`StatefulInteractiveElement::track_scroll`](crate::StatefulInteractiveElement::track_scroll).
+/// A bar is only shown along an axis on which the content overflows.
+#[track_caller]
+pub fn scrollbars(
+    id: impl Into<ElementId>,
+    scroll_handle: &ScrollHandle,
+    child: impl IntoElement,
+) -> Scrollbars {
+    Scrollbars {
+        id: id.into(),
+        scroll_handle: scroll_handle.clone(),
+        child: child.into_any_element(),
+        style: ScrollbarStyle::default(),
+        axes: ScrollbarAxes::default(),
+    }
+}
+
+/// An element that draws scrollbars over its child. See [`scrollbars`].
+pub struct Scrollbars {
+    id: ElementId,
+    scroll_handle: ScrollHandle,
+    child: AnyElement,
+    style: ScrollbarStyle,
+    axes: ScrollbarAxes,
+}
+
+impl Scrollbars {
+    /// Sets the sizes, colors and behavior of the scrollbars.
+    pub fn scrollbar_style(mut self, style: ScrollbarStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the axes along which scrollbars are shown, which is both by default.
+    pub fn axes(mut self, axes: ScrollbarAxes) -> Self {
+        self.axes = axes;
+        self
+    }
+}
+
+/// The axes along which [`Scrollbars`] are shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScrollbarAxes {
+    /// A vertical bar at the right and a horizontal bar at the bottom.
+    #[default]
+    Both,
+    /// Only a vertical bar at the right.
+    Vertical,
+    /// Only a horizontal bar at the bottom.
+    Horizontal,
+}
+
+impl ScrollbarAxes {
+    fn contains(self, axis: Axis) -> bool {
+        match self {
+            ScrollbarAxes::Both => true,
+            ScrollbarAxes::Vertical => axis == Axis::Vertical,
+            ScrollbarAxes::Horizontal => axis == Axis::Horizontal,
+        }
+    }
+}
+
+/// How [`Scrollbars`] are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScrollbarMode {
+    /// Thin bars over the edges of the content, which expand to their full width while the
+    /// pointer is over them or the thumb is dragged.
+    #[default]
+    Overlay,
+    /// Bars that are always drawn at their full width.
+    Fixed,
+}
+
+/// The sizes, colors and behavior of [`Scrollbars`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrollbarStyle {
+    /// How the bars are drawn.
+    pub mode: ScrollbarMode,
+    /// The width of a bar across its axis, which is also the width of the area in which it
+    /// can be hovered and grabbed.
+    pub width: Pixels,
+    /// The width of a bar in [`ScrollbarMode::Overlay`] while it isn't hovered or dragged.
+    pub collapsed_width: Pixels,
+    /// The space between the thumb and the edges of the track.
+    pub padding: Pixels,
+    /// The shortest the thumb gets, so that it stays easy to grab for very long content.
+    pub min_thumb_length: Pixels,
+    /// The color of the track, which is only drawn while the bar is at its full width.
+    pub track_color: Hsla,
+    /// The color of the thumb.
+    pub thumb_color: Hsla,
+    /// The color of the thumb while it's hovered or dragged.
+    pub thumb_hover_color: Hsla,
+    /// How long the bars stay visible after the content is scrolled or the pointer leaves
+    /// them, or `None` to always show them.
+    pub auto_hide_delay: Option<Duration>,
+}
+
+impl Default for ScrollbarStyle {
+    fn default() -> Self {
+        Self {
+            mode: ScrollbarMode::Overlay,
+            width: px(12.),
+            collapsed_width: px(6.),
+            padding: px(2.),
+            min_thumb_length: px(24.),
+            track_color: hsla(0., 0., 0.5, 0.1),
+            thumb_color: hsla(0., 0., 0.5, 0.5),
+            thumb_hover_color: hsla(0., 0., 0.4, 0.8),
+            auto_hide_delay: Some(Duration::from_secs(1)),
+        }
+    }
+}
+
+/// The state of [`Scrollbars`] that persists across frames, shared with their listeners.
+struct ScrollbarsState {
+    drag: Option<ThumbDrag>,
+    last_offset: Point<Pixels>,
+    /// Whether the bars have been hidden after going unused for the auto-hide delay.
+    idle: bool,
+    _hide_task: Option<Task<()>>,
+}
+
+/// A drag of the thumb along `axis`, which was grabbed at `grab_offset` from its start.
+#[derive(Clone, Copy, Debug)]
+struct ThumbDrag {
+    axis: Axis,
+    grab_offset: Pixels,
+}
+
+/// The position of a scrollbar's track and thumb, from which the scroll offsets for dragging
+/// and paging are computed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ScrollbarGeometry {
+    axis: Axis,
+    track_bounds: Bounds<Pixels>,
+    thumb_bounds: Bounds<Pixels>,
+    /// The first and last positions along the axis that the thumb can start at.
+    thumb_range: (Pixels, Pixels),
+    viewport_length: Pixels,
+    max_offset: Pixels,
+}
+
+impl ScrollbarGeometry {
+    fn new(
+        axis: Axis,
+        track_bounds: Bounds<Pixels>,
+        viewport_length: Pixels,
+        max_offset: Pixels,
+        offset: Pixels,
+        style: &ScrollbarStyle,
+    ) -> Self {
+        let track_start = track_bounds.origin.along(axis) + style.padding;
+        let track_length = (track_bounds.size.along(axis) - style.padding * 2.).max(px(0.));
+        let content_length = viewport_length + max_offset;
+        let thumb_length = (track_length * (viewport_length / content_length))
+            .max(style.min_thumb_length)
+            .min(track_length);
+        let travel = track_length - thumb_length;
+        let progress = if max_offset > px(0.) {
+            (-offset / max_offset).clamp(0., 1.)
+        } else {
+            0.
+        };
+        let thumb_start = track_start + travel * progress;
+
+        let thumb_bounds = Bounds {
+            origin: track_bounds.origin.apply_along(axis, |_| thumb_start),
+            size: track_bounds.size.apply_along(axis, |_| thumb_length),
+        };
+        Self {
+            axis,
+            track_bounds,
+            thumb_bounds,
+            thumb_range: (track_start, track_start + travel),
+            viewport_length,
+            max_offset,
+        }
+    }
+
+    /// The scroll offset along the axis at which the thumb starts at the given position.
+    fn offset_for_thumb_start(&self, thumb_start: Pixels) -> Pixels {
+        let (range_start, range_end) = self.thumb_range;
+        let travel = range_end - range_start;
+        if travel <= px(0.) {
+            return px(0.);
+        }
+        let progress = ((thumb_start - range_start) / travel).clamp(0., 1.);
+        -(self.max_offset * progress)
+    }
+
+    /// The scroll offset along the axis after paging toward the given position on the track.
+    fn paged_offset(&self, offset: Pixels, position: Pixels) -> Pixels {
+        let paged = if position < self.thumb_bounds.origin.along(self.axis) {
+            offset + self.viewport_length
+        } else {
+            offset - self.viewport_length
+        };
+        paged.clamp(-self.max_offset, px(0.))
+    }
+
+    /// The given bounds narrowed to the given width across the axis, `inset` from the far edge
+    /// of the track.
+    fn painted_bounds(
+        &self,
+        bounds: Bounds<Pixels>,
+        width: Pixels,
+        inset: Pixels,
+    ) -> Bounds<Pixels> {
+        let cross_axis = self.axis.invert();
+        let cross_end =
+            self.track_bounds.origin.along(cross_axis) + self.track_bounds.size.along(cross_axis);
+        Bounds {
+            origin: bounds
+                .origin
+                .apply_along(cross_axis, |_| cross_end - inset - width),
+            size: bounds.size.apply_along(cross_axis, |_| width),
+        }
+    }
+}
+
+/// A scrollbar laid out during prepaint.
+pub struct PreparedScrollbar {
+    geometry: ScrollbarGeometry,
+    track_hitbox: Hitbox,
+    thumb_hitbox: Option<Hitbox>,
+}
+
+/// The frame state of [`Scrollbars`].
+pub struct ScrollbarsPrepaintState {
+    state: Rc<RefCell<ScrollbarsState>>,
+    scrollbars: SmallVec<[PreparedScrollbar; 2]>,
+}
+
+impl Element for Scrollbars {
+    type RequestLayoutState = ();
+    type PrepaintState = ScrollbarsPrepaintState;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, ()) {
+        (self.child.request_layout(window, cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        // The container updates the scroll handle as it's prepainted, and the bars' hitboxes are
+        // inserted after the content's so that the thumb is on top of it.
+        self.child.prepaint(window, cx);
+
+        let Some(id) = id else {
+            unreachable!("scrollbars always have an element id");
+        };
+        let state = window.with_element_state(id, |state, _| {
+            let state = state.unwrap_or_else(|| {
+                Rc::new(RefCell::new(ScrollbarsState {
+                    drag: None,
+                    last_offset: self.scroll_handle.offset(),
+                    idle: true,
+                    _hide_task: None,
+                }))
+            });
+            (state.clone(), state)
+        });
+
+        let max_offset = self.scroll_handle.max_offset();
+        let offset = self.scroll_handle.offset();
+        let overflowing_axes = [Axis::Vertical, Axis::Horizontal]
+            .into_iter()
+            .filter(|axis| self.axes.contains(*axis) && max_offset.along(*axis) > px(0.))
+            .collect::<SmallVec<[Axis; 2]>>();
+
+        let mut scrollbars = SmallVec::new();
+        let mut active = false;
+        for &axis in &overflowing_axes {
+            // Leave the corner to the vertical bar when both are shown.
+            let corner = if overflowing_axes.len() > 1 && axis == Axis::Horizontal {
+                self.style.width
+            } else {
+                px(0.)
+            };
+            let track_bounds = match axis {
+                Axis::Vertical => Bounds::new(
+                    point(bounds.right() - self.style.width, bounds.top()),
+                    size(self.style.width, bounds.size.height),
+                ),
+                Axis::Horizontal => Bounds::new(
+                    point(bounds.left(), bounds.bottom() - self.style.width),
+                    size(bounds.size.width - corner, self.style.width),
+                ),
+            };
+            let geometry = ScrollbarGeometry::new(
+                axis,
+                track_bounds,
+                bounds.size.along(axis),
+                max_offset.along(axis),
+                offset.along(axis),
+                &self.style,
+            );
+
+            // Hitboxes are only hit tested after prepaint, so this can't account for anything
+            // drawn over the bar.
+            let pointer_over_track = track_bounds.contains(&window.mouse_position());
+            let dragging = state.borrow().drag.is_some_and(|drag| drag.axis == axis);
+            active |= dragging || pointer_over_track;
+            scrollbars.push(PreparedScrollbar {
+                geometry,
+                track_hitbox: window.insert_hitbox(track_bounds, HitboxBehavior::Normal),
+                thumb_hitbox: None,
+            });
+        }
+
+        let mut state_ref = state.borrow_mut();
+        active |= state_ref.last_offset != offset;
+        state_ref.last_offset = offset;
+        match self.style.auto_hide_delay {
+            None => state_ref.idle = false,
+            Some(delay) if active => {
+                state_ref.idle = false;
+                let state = state.clone();
+                let current_view = window.current_view();
+                state_ref._hide_task = Some(window.spawn(cx, async move |cx| {
+                    cx.background_executor().timer(delay).await;
+                    state.borrow_mut().idle = true;
+                    cx.update(|_, cx| cx.notify(current_view)).ok();
+                }));
+            }
+            Some(_) => {}
+        }
+
+        if !state_ref.idle {
+            for scrollbar in &mut scrollbars {
+                scrollbar.thumb_hitbox = Some(window.insert_hitbox(
+                    scrollbar.geometry.thumb_bounds,
+                    HitboxBehavior::BlockMouseExceptScroll,
+                ));
+            }
+        }
+        drop(state_ref);
+
+        ScrollbarsPrepaintState { state, scrollbars }
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.child.paint(window, cx);
+
+        let current_view = window.current_view();
+        for scrollbar in prepaint.scrollbars.drain(..) {
+            let PreparedScrollbar {
+                geometry,
+                track_hitbox,
+                thumb_hitbox,
+            } = scrollbar;
+            let axis = geometry.axis;
+
+            // Hovering the track expands the bar, and shows it when it's been hidden.
+            let was_hovered = track_hitbox.is_hovered(window);
+            window.on_mouse_event({
+                let track_hitbox = track_hitbox.clone();
+                move |_: &MouseMoveEvent, phase, window, cx| {
+                    if phase == DispatchPhase::Capture
+                        && track_hitbox.is_hovered(window) != was_hovered
+                    {
+                        cx.notify(current_view);
+                    }
+                }
+            });
+
+            let Some(thumb_hitbox) = thumb_hitbox else {
+                continue;
+            };
+
+            let dragging = prepaint
+                .state
+                .borrow()
+                .drag
+                .is_some_and(|drag| drag.axis == axis);
+            let expanded = self.style.mode == ScrollbarMode::Fixed
+                || dragging
+                || track_hitbox.is_hovered(window);
+            let width = if expanded {
+                self.style.width
+            } else {
+                self.style.collapsed_width
+            };
+            if expanded {
+                window.paint_quad(fill(
+                    geometry.painted_bounds(geometry.track_bounds, width, px(0.)),
+                    self.style.track_color,
+                ));
+            }
+            let thumb_bounds = geometry.painted_bounds(
+                geometry.thumb_bounds,
+                (width - self.style.padding * 2.).max(px(0.)),
+                self.style.padding,
+            );
+            let thumb_color = if dragging || thumb_hitbox.is_hovered(window) {
+                self.style.thumb_hover_color
+            } else {
+                self.style.thumb_color
+            };
+            window.paint_quad(
+                fill(thumb_bounds, thumb_color)
+                    .corner_radii(Corners::all(thumb_bounds.size.along(axis.invert()) / 2.)),
+            );
+
+            window.on_mouse_event({
+                let state = prepaint.state.clone();
+                let scroll_handle = self.scroll_handle.clone();
+                move |event: &MouseDownEvent, phase, window, cx| {
+                    if phase != DispatchPhase::Bubble || event.button != MouseButton::Left {
+                        return;
+                    }
+                    let position = event.position.along(axis);
+                    if thumb_hitbox.is_hovered(window) {
+                        state.borrow_mut().drag = Some(ThumbDrag {
+                            axis,
+                            grab_offset: position - geometry.thumb_bounds.origin.along(axis),
+                        });
+                    } else if track_hitbox.is_hovered(window) {
+                        let offset = scroll_handle.offset();
+                        let paged = geometry.paged_offset(offset.along(axis), position);
+                        scroll_handle.set_offset(offset.apply_along(axis, |_| paged));
+                    } else {
+                        return;
+                    }
+                    cx.stop_propagation();
+                    cx.notify(current_view);
+                }
+            });
+
+            if dragging {
+                window.on_mouse_event({
+                    let state = prepaint.state.clone();
+                    let scroll_handle = self.scroll_handle.clone();
+                    move |event: &MouseMoveEvent, phase, _, cx| {
+                        if phase != DispatchPhase::Capture {
+                            return;
+                        }
+                        let Some(drag) = state.borrow().drag else {
+                            return;
+                        };
+                        let thumb_start = event.position.along(axis) - drag.grab_offset;
+                        let offset = scroll_handle.offset();
+                        let dragged = geometry.offset_for_thumb_start(thumb_start);
+                        if dragged != offset.along(axis) {
+                            scroll_handle.set_offset(offset.apply_along(axis, |_| dragged));
+                            cx.notify(current_view);
+                        }
+                    }
+                });
+
+                window.on_mouse_event({
+                    let state = prepaint.state.clone();
+                    move |_: &MouseUpEvent, phase, _, cx| {
+                        if phase == DispatchPhase::Capture {
+                            state.borrow_mut().drag = None;
+                            cx.notify(current_view);
+                        }
+                    }
+                });
+            }
+        }
+    }
+}
+
+impl IntoElement for Scrollbars {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as gpui, Context, InteractiveElement, Modifiers, ParentElement, Render,
+        StatefulInteractiveElement, Styled, TestAppContext, VisualTestContext, div,
+    };
+
+    #[test]
+    fn test_thumb_geometry() {
+        let style = ScrollbarStyle::default();
+        let track_bounds = Bounds::new(point(px(88.), px(0.)), size(px(12.), px(100.)));
+
+        let geometry = ScrollbarGeometry::new(
+            Axis::Vertical,
+            track_bounds,
+            px(100.),
+            px(100.),
+            px(0.),
+            &style,
+        );
+        assert_eq!(geometry.thumb_bounds.origin.y, px(2.));
+        assert_eq!(geometry.thumb_bounds.size.height, px(48.));
+        assert_eq!(geometry.thumb_range, (px(2.), px(50.)));
+
+        let long_content = ScrollbarGeometry::new(
+            Axis::Vertical,
+            track_bounds,
+            px(100.),
+            px(100_000.),
+            px(-100_000.),
+            &style,
+        );
+        assert_eq!(
+            long_content.thumb_bounds.size.height, style.min_thumb_length,
+            "the thumb should stay grabbable for very long content"
+        );
+        assert_eq!(long_content.thumb_bounds.origin.y, px(74.));
+    }
+
+    #[test]
+    fn test_drag_and_page_offsets() {
+        let style = ScrollbarStyle::default();
+        let track_bounds = Bounds::new(point(px(0.), px(88.)), size(px(100.), px(12.)));
+        let geometry = ScrollbarGeometry::new(
+            Axis::Horizontal,
+            track_bounds,
+            px(100.),
+            px(300.),
+            px(-150.),
+            &style,
+        );
+
+        assert_eq!(geometry.offset_for_thumb_start(px(2.)), px(0.));
+        assert_eq!(geometry.offset_for_thumb_start(px(-50.)), px(0.));
+        assert_eq!(geometry.offset_for_thumb_start(px(38.)), px(-150.));
+        assert_eq!(geometry.offset_for_thumb_start(px(500.)), px(-300.));
+
+        assert_eq!(geometry.paged_offset(px(-150.), px(5.)), px(-50.));
+        assert_eq!(geometry.paged_offset(px(-150.), px(95.)), px(-250.));
+        assert_eq!(geometry.paged_offset(px(-250.), px(95.)), px(-300.));
+    }
+
+    struct ScrollingView {
+        scroll_handle: ScrollHandle,
+    }
+
+    impl Render for ScrollingView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div().size(px(100.)).child(scrollbars(
+                "scrollbars",
+                &self.scroll_handle,
+                div()
+                    .id("content")
+                    .size(px(100.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .child(div().w(px(100.)).h(px(1000.))),
+            ))
+        }
+    }
+
+    fn thumb_is_painted(cx: &mut VisualTestContext) -> bool {
+        let style = ScrollbarStyle::default();
+        cx.update(|window, _| {
+            window.rendered_frame.scene.quads.iter().any(|quad| {
+                quad.background == style.thumb_color.into()
+                    || quad.background == style.thumb_hover_color.into()
+            })
+        })
+    }
+
+    #[gpui::test]
+    fn test_drag_thumb_and_page_track(cx: &mut TestAppContext) {
+        let scroll_handle = ScrollHandle::new();
+        let (_, cx) = cx.add_window_view(|_, _| ScrollingView {
+            scroll_handle: scroll_handle.clone(),
+        });
+        assert!(!thumb_is_painted(cx), "bars start out hidden");
+
+        // The thumb is 24px long and can travel the 72px between 2px and 74px.
+        cx.simulate_mouse_move(point(px(94.), px(10.)), None, Modifiers::none());
+        assert!(
+            thumb_is_painted(cx),
+            "hovering the track should show the bar"
+        );
+
+        cx.simulate_mouse_down(
+            point(px(94.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            point(px(94.), px(46.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_up(
+            point(px(94.), px(46.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(scroll_handle.offset().y, px(-450.));
+
+        cx.simulate_mouse_down(
+            point(px(94.), px(90.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_up(
+            point(px(94.), px(90.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(scroll_handle.offset().y, px(-550.));
+
+        cx.simulate_mouse_down(point(px(94.), px(5.)), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_up(point(px(94.), px(5.)), MouseButton::Left, Modifiers::none());
+        assert_eq!(scroll_handle.offset().y, px(-450.));
+    }
+
+    #[gpui::test]
+    fn test_auto_hide(cx: &mut TestAppContext) {
+        let scroll_handle = ScrollHandle::new();
+        let (_, cx) = cx.add_window_view(|_, _| ScrollingView {
+            scroll_handle: scroll_handle.clone(),
+        });
+
+        cx.simulate_mouse_move(point(px(94.), px(10.)), None, Modifiers::none());
+        cx.simulate_mouse_move(point(px(50.), px(50.)), None, Modifiers::none());
+        assert!(thumb_is_painted(cx));
+
+        cx.executor().advance_clock(Duration::from_millis(500));
+        cx.run_until_parked();
+        assert!(thumb_is_painted(cx));
+
+        cx.executor().advance_clock(Duration::from_millis(600));
+        cx.run_until_parked();
+        assert!(
+            !thumb_is_painted(cx),
+            "the bar should hide once it's been idle for the delay"
+        );
+
+        scroll_handle.set_offset(point(px(0.), px(-100.)));
+        cx.update(|window, _| window.refresh());
+        cx.run_until_parked();
+        assert!(thumb_is_painted(cx), "scrolling should show the bar again");
+    }
+}
@@ -1,19 +1,56 @@
-use std::{fs, path::Path, sync::Arc};
+use std::{
+    cell::RefCell,
+    f32::consts::TAU,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    rc::{Rc, Weak},
+    sync::{Arc, LazyLock},
+    time::{Duration, Instant},
+};
 
 use crate::{
-    App, Asset, Bounds, Element, GlobalElementId, Hitbox, InspectorElementId, InteractiveElement,
-    Interactivity, IntoElement, LayoutId, Pixels, Point, Radians, SharedString, Size,
-    StyleRefinement, Styled, TransformationMatrix, Window, geometry::Negate as _, point, px,
-    radians, size,
+    AnyElement, App, Asset, Background, Bounds, BoxShadow, ContentMask, DefiniteLength,
+    DevicePixels, Element, Global, GlobalElementId, Hitbox, HitboxMask, Hsla, ImageCacheError,
+    InspectorElementId, InteractiveElement, Interactivity, IntoElement, LayoutId, Length,
+    ObjectFit, Pixels, Point, Radians, RenderSvgParams, Resource, SharedString, Size, Style,
+    StyleRefinement, Styled, SvgFill, SvgParseOptions, SvgRenderOptions, SvgViewBox, Task,
+    TransformationMatrix, Window, geometry::Negate as _, load_resource_bytes, point, px, radians,
+    relative, size,
 };
+use anyhow::Context as _;
+use base64::Engine as _;
+use collections::HashMap;
+use futures::{StreamExt as _, channel::mpsc};
 use util::ResultExt;
 
 /// An SVG element.
 pub struct Svg {
     interactivity: Interactivity,
     transformation: Option<Transformation>,
+    transformation_affects_hitbox: bool,
+    rotation_period: Option<Duration>,
     path: Option<SharedString>,
     external_path: Option<SharedString>,
+    named_source: Option<(SharedString, SharedString)>,
+    data: Option<(SharedString, Arc<[u8]>)>,
+    placeholder: Option<Box<dyn Fn() -> AnyElement>>,
+    fallback: Option<Box<dyn Fn(&ImageCacheError) -> AnyElement>>,
+    load_listener: Option<Rc<dyn Fn(Size<f32>, &mut Window, &mut App)>>,
+    error_listener: Option<Rc<dyn Fn(&ImageCacheError, &mut Window, &mut App)>>,
+    size: Option<Size<f32>>,
+    aspect_ratio: Option<f32>,
+    object_fit: Option<ObjectFit>,
+    color: Option<Background>,
+    fill: Option<Background>,
+    shape_shadows: Vec<BoxShadow>,
+    paint_overrides: Vec<SvgPaintOverride>,
+    view_box: Option<Bounds<f32>>,
+    symbol: Option<SharedString>,
+    parse_options: SvgParseOptions,
+    preserve_colors: bool,
+    hit_test_alpha: Option<f32>,
+    watch: bool,
 }
 
 /// Create a new SVG element.
@@ -22,8 +59,29 @@ pub fn svg() -> Svg {
     Svg {
         interactivity: Interactivity::new(),
         transformation: None,
+        transformation_affects_hitbox: false,
+        rotation_period: None,
         path: None,
         external_path: None,
+        named_source: None,
+        data: None,
+        placeholder: None,
+        fallback: None,
+        load_listener: None,
+        error_listener: None,
+        size: None,
+        aspect_ratio: None,
+        object_fit: None,
+        color: None,
+        fill: None,
+        shape_shadows: Vec::new(),
+        paint_overrides: Vec::new(),
+        view_box: None,
+        symbol: None,
+        parse_options: SvgParseOptions::default(),
+        preserve_colors: false,
+        hit_test_alpha: None,
+        watch: false,
     }
 }
 
@@ -37,19 +95,265 @@ impl Svg {
     /// Set the path to the SVG file for this element.
     pub fn external_path(mut self, path: impl Into<SharedString>) -> Self {
         self.external_path = Some(path.into());
+        self.named_source = None;
+        self
+    }
+
+    /// Set the path to the SVG within the asset source registered under `source_id` with
+    /// [`App::register_asset_source`]. Paths the named source doesn't have are loaded from the
+    /// app's asset source instead, so a source can override some of the embedded assets.
+    ///
+    /// Like an external path, the SVG is loaded in the background, and the placeholder and
+    /// fallback are used while it loads or if it fails to, such as when no source is registered
+    /// under `source_id`. Unlike one, it isn't watched for changes.
+    pub fn path_in(
+        mut self,
+        source_id: impl Into<SharedString>,
+        path: impl Into<SharedString>,
+    ) -> Self {
+        let source_id = source_id.into();
+        let path = path.into();
+        // The same path in different sources is a different SVG, so it's cached separately.
+        self.external_path = Some(format!("{source_id}://{path}").into());
+        self.named_source = Some((source_id, path));
+        self
+    }
+
+    /// Reload the SVG whenever the file at the external path is modified. The element must have
+    /// an id, which is used to keep the watch alive between frames; without one the file isn't
+    /// watched, and debug builds panic. The file must exist when the element is first rendered.
+    pub fn watch(mut self, watch: bool) -> Self {
+        self.watch = watch;
+        self
+    }
+
+    /// Set the SVG markup to render for this element directly from memory.
+    /// The `cache_key` identifies the data in the rasterization cache, so it must
+    /// change whenever the bytes do.
+    pub fn data(mut self, cache_key: impl Into<SharedString>, bytes: impl Into<Arc<[u8]>>) -> Self {
+        self.data = Some((cache_key.into(), bytes.into()));
+        self
+    }
+
+    /// Set a function that will be invoked to render a view in place of the SVG while an
+    /// external path is still being loaded.
+    pub fn with_placeholder(mut self, placeholder: impl Fn() -> AnyElement + 'static) -> Self {
+        self.placeholder = Some(Box::new(placeholder));
+        self
+    }
+
+    /// Set a function that will be invoked to render a view in place of the SVG should an
+    /// external path fail to load.
+    pub fn with_fallback(
+        mut self,
+        fallback: impl Fn(&ImageCacheError) -> AnyElement + 'static,
+    ) -> Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Register a listener to be called once an external path has loaded, with the size the
+    /// SVG declares for itself. The element must have an id, which is used to call the listener
+    /// only once per path.
+    pub fn on_load(
+        mut self,
+        listener: impl Fn(Size<f32>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.load_listener = Some(Rc::new(listener));
+        self
+    }
+
+    /// Register a listener to be called if an external path fails to load or parse. The element
+    /// must have an id, which is used to call the listener only once per path.
+    pub fn on_error(
+        mut self,
+        listener: impl Fn(&ImageCacheError, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.error_listener = Some(Rc::new(listener));
+        self
+    }
+
+    /// Set the intrinsic size of the SVG, in pixels, instead of deriving it from the document.
+    /// Auto-sized dimensions of the element are computed from this size, and the SVG is not
+    /// parsed during layout.
+    pub fn with_size(mut self, size: Size<f32>) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Set the intrinsic aspect ratio (width / height) of the SVG instead of deriving it from
+    /// the document. The SVG is not parsed during layout.
+    pub fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self {
+        self.aspect_ratio = Some(aspect_ratio);
+        self
+    }
+
+    /// Render only the given region of the SVG, in the document's user units, scaled into the
+    /// bounds of the element. The size of the region is used as the intrinsic size of the SVG.
+    pub fn view_box(mut self, view_box: Bounds<f32>) -> Self {
+        self.view_box = Some(view_box);
+        self
+    }
+
+    /// Render only the `<symbol>` with the given id, sized to the symbol's own view box. Nothing
+    /// is painted if the SVG has no such symbol.
+    pub fn symbol(mut self, symbol_id: impl Into<SharedString>) -> Self {
+        self.symbol = Some(symbol_id.into());
+        self
+    }
+
+    /// Set the options used when parsing the SVG, instead of the defaults shared by all SVGs.
+    pub fn with_parse_options(mut self, parse_options: SvgParseOptions) -> Self {
+        self.parse_options = parse_options;
+        self
+    }
+
+    /// Set how the SVG is fit into the bounds of the element when their aspect ratios differ.
+    /// By default the SVG is scaled to the width of the element and centered vertically.
+    pub fn object_fit(mut self, object_fit: ObjectFit) -> Self {
+        self.object_fit = Some(object_fit);
+        self
+    }
+
+    /// Set the color used to tint the SVG, either a solid color or a background such as a linear
+    /// gradient. This takes precedence over the text color, which is used otherwise.
+    ///
+    /// The tint is applied when the SVG's sprite is drawn rather than when it's rasterized, so
+    /// animating the color, such as fading it on hover, doesn't rasterize the SVG again. Gradients
+    /// are laid out across the SVG's bounds and follow its transformation.
+    pub fn color(mut self, color: impl Into<Background>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Paint the shape of the SVG with the given background, such as a linear gradient, using the
+    /// SVG as a mask. This takes precedence over the other ways of coloring the SVG.
+    pub fn fill(mut self, fill: impl Into<Background>) -> Self {
+        self.fill = Some(fill.into());
+        self
+    }
+
+    /// Paint a shadow behind the shape of the SVG, like CSS `filter: drop-shadow`, rather than
+    /// behind its bounds like `Styled::shadow`. The spread radius and inset flag of the shadow are
+    /// ignored.
+    pub fn shape_shadow(mut self, shadow: BoxShadow) -> Self {
+        self.shape_shadows.push(shadow);
+        self
+    }
+
+    /// Fill the node with the given id, and the children inheriting its fill, with the given color
+    /// instead of tinting it. The rest of the SVG is painted as usual. Ids that aren't valid CSS
+    /// identifiers are ignored.
+    pub fn override_fill(
+        mut self,
+        node_id: impl Into<SharedString>,
+        color: impl Into<Hsla>,
+    ) -> Self {
+        let node_id = node_id.into();
+        if is_css_identifier(&node_id) {
+            self.paint_overrides.push(SvgPaintOverride {
+                node_id,
+                property: "fill",
+                color: color.into(),
+            });
+        }
+        self
+    }
+
+    /// Stroke the node with the given id, and the children inheriting its stroke, with the given
+    /// color instead of tinting it. The rest of the SVG is painted as usual. Ids that aren't valid
+    /// CSS identifiers are ignored.
+    pub fn override_stroke(
+        mut self,
+        node_id: impl Into<SharedString>,
+        color: impl Into<Hsla>,
+    ) -> Self {
+        let node_id = node_id.into();
+        if is_css_identifier(&node_id) {
+            self.paint_overrides.push(SvgPaintOverride {
+                node_id,
+                property: "stroke",
+                color: color.into(),
+            });
+        }
+        self
+    }
+
+    /// Only accept mouse events over the parts of the SVG that are at least as opaque as the given
+    /// threshold, between 0 and 1, rather than anywhere in its bounds. Mouse events over the
+    /// transparent parts reach the elements behind the SVG instead.
+    ///
+    /// The hit test uses a low resolution alpha mask of the SVG, rasterized during prepaint and
+    /// kept until the SVG's size changes when the element has an id.
+    pub fn hit_test_alpha(mut self, threshold: f32) -> Self {
+        self.hit_test_alpha = Some(threshold);
+        self
+    }
+
+    /// Render the SVG with the colors it was authored with, rather than tinting it with the text color.
+    pub fn preserve_colors(mut self, preserve_colors: bool) -> Self {
+        self.preserve_colors = preserve_colors;
         self
     }
 
     /// Transform the SVG element with the given transformation.
-    /// Note that this won't effect the hitbox or layout of the element, only the rendering.
+    /// Note that this won't affect the hitbox or layout of the element, only the rendering. See
+    /// [`Svg::with_transformation_affecting_hitbox`] to hit test the transformed element instead.
     pub fn with_transformation(mut self, transformation: Transformation) -> Self {
         self.transformation = Some(transformation);
         self
     }
+
+    /// Continuously rotate the SVG clockwise, completing a full turn every `period`. The rotation
+    /// is applied before any transformation set with [`Svg::with_transformation`].
+    ///
+    /// When the element has an id, the rotation starts when the element is first painted.
+    /// Otherwise every element without one rotates in step.
+    pub fn rotate_continuously(mut self, period: Duration) -> Self {
+        self.rotation_period = Some(period);
+        self
+    }
+
+    /// Transform the SVG element with the given transformation, and use the axis-aligned bounding
+    /// box of the transformed bounds as the hitbox. The layout of the element is unaffected.
+    pub fn with_transformation_affecting_hitbox(mut self, transformation: Transformation) -> Self {
+        self.transformation = Some(transformation);
+        self.transformation_affects_hitbox = true;
+        self
+    }
+}
+
+/// Where an SVG element takes the color it is tinted with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SvgColorSource {
+    /// The SVG's shape is painted with the background set with [`Svg::fill`].
+    Fill,
+    /// The color set with [`Svg::color`].
+    Color,
+    /// The text color of the element's style.
+    TextColor,
+    /// The SVG is rendered with its own colors, see [`Svg::preserve_colors`].
+    Preserved,
+    /// No color is set, so the SVG isn't painted.
+    None,
+}
+
+/// SVG state displayed in the inspector.
+#[derive(Clone)]
+pub struct SvgInspectorState {
+    /// The color the SVG is tinted with, if any.
+    pub color: Option<Background>,
+    /// Where the color came from.
+    pub color_source: SvgColorSource,
+}
+
+/// The layout state of an SVG element.
+pub struct SvgLayoutState {
+    replacement: Option<AnyElement>,
 }
 
 impl Element for Svg {
-    type RequestLayoutState = ();
+    type RequestLayoutState = SvgLayoutState;
     type PrepaintState = Option<Hitbox>;
 
     fn id(&self) -> Option<crate::ElementId> {
@@ -67,14 +371,77 @@ impl Element for Svg {
         window: &mut Window,
         cx: &mut App,
     ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut layout_state = SvgLayoutState { replacement: None };
+        let external_asset = match (&self.path, &self.external_path) {
+            (None, Some(path)) => {
+                if self.watch && self.named_source.is_none() {
+                    watch_external_path(global_id, path, window, cx);
+                }
+                let asset = use_external_svg(path, self.named_source.as_ref(), window, cx);
+                self.notify_load_listeners(global_id, path, asset.as_ref(), window, cx);
+                Some(asset)
+            }
+            _ => None,
+        };
+
         let layout_id = self.interactivity.request_layout(
             global_id,
             inspector_id,
             window,
             cx,
-            |style, window, cx| window.request_layout(style, None, cx),
+            |mut style, window, cx| {
+                let mut replacement_id = None;
+                let replacement = match &external_asset {
+                    Some(None) => self.placeholder.as_ref().map(|placeholder| placeholder()),
+                    Some(Some(Err(error))) => {
+                        self.fallback.as_ref().map(|fallback| fallback(error))
+                    }
+                    Some(Some(Ok(_))) | None => None,
+                };
+                if let Some(mut element) = replacement {
+                    replacement_id = Some(element.request_layout(window, cx));
+                    layout_state.replacement = Some(element);
+                }
+
+                if let Some(intrinsic_size) = self
+                    .size
+                    .or_else(|| self.view_box.map(|view_box| view_box.size))
+                {
+                    apply_intrinsic_size(&mut style, intrinsic_size, window);
+                } else if let Some(aspect_ratio) = self.aspect_ratio {
+                    style.aspect_ratio = Some(aspect_ratio);
+                } else if matches!(style.size.width, Length::Auto)
+                    || matches!(style.size.height, Length::Auto)
+                {
+                    // Until the size is known, the element is laid out as if it had none, and
+                    // the view is notified once it's loaded. Elements sized in both dimensions
+                    // don't need to wait for it.
+                    let source = match (&self.path, &self.external_path, &self.data) {
+                        (Some(path), _, _) => Some((path, None)),
+                        (None, Some(path), _) => match &external_asset {
+                            Some(Some(Ok(bytes))) => Some((path, Some(bytes))),
+                            _ => None,
+                        },
+                        (None, None, Some((cache_key, bytes))) => Some((cache_key, Some(bytes))),
+                        (None, None, None) => None,
+                    };
+                    if let Some((path, bytes)) = source
+                        && let Some(intrinsic_size) = use_intrinsic_size(
+                            path,
+                            bytes,
+                            self.symbol.as_ref(),
+                            &self.parse_options,
+                            window,
+                            cx,
+                        )
+                    {
+                        apply_intrinsic_size(&mut style, intrinsic_size, window);
+                    }
+                }
+                window.request_layout(style, replacement_id, cx)
+            },
         );
-        (layout_id, ())
+        (layout_id, layout_state)
     }
 
     fn prepaint(
@@ -82,18 +449,39 @@ impl Element for Svg {
         global_id: Option<&GlobalElementId>,
         inspector_id: Option<&InspectorElementId>,
         bounds: Bounds<Pixels>,
-        _request_layout: &mut Self::RequestLayoutState,
+        request_layout: &mut Self::RequestLayoutState,
         window: &mut Window,
         cx: &mut App,
     ) -> Option<Hitbox> {
+        let hitbox_bounds = match self.transformation {
+            Some(transformation)
+                if self.transformation_affects_hitbox && !transformation.is_identity() =>
+            {
+                transformation.transform_bounds(bounds, window.rem_size())
+            }
+            _ => bounds,
+        };
+        let hitbox_mask = self
+            .hit_test_alpha
+            .filter(|_| request_layout.replacement.is_none())
+            .and_then(|threshold| self.hitbox_mask(global_id, bounds, threshold, window, cx));
         self.interactivity.prepaint(
             global_id,
             inspector_id,
-            bounds,
-            bounds.size,
+            hitbox_bounds,
+            hitbox_bounds.size,
             window,
             cx,
-            |_, _, hitbox, _, _| hitbox,
+            |_, _, mut hitbox, window, cx| {
+                if let Some(replacement) = &mut request_layout.replacement {
+                    replacement.prepaint(window, cx);
+                }
+                if let Some((hitbox, mask)) = hitbox.as_mut().zip(hitbox_mask) {
+                    window.set_hitbox_mask(hitbox, mask);
+                }
+
+                hitbox
+            },
         )
     }
 
@@ -102,13 +490,14 @@ impl Element for Svg {
         global_id: Option<&GlobalElementId>,
         inspector_id: Option<&InspectorElementId>,
         bounds: Bounds<Pixels>,
-        _request_layout: &mut Self::RequestLayoutState,
+        request_layout: &mut Self::RequestLayoutState,
         hitbox: &mut Option<Hitbox>,
         window: &mut Window,
         cx: &mut App,
     ) where
         Self: Sized,
     {
+        let transformation = self.animated_transformation(global_id, window);
         self.interactivity.paint(
             global_id,
             inspector_id,
@@ -117,52 +506,763 @@ impl Element for Svg {
             window,
             cx,
             |style, window, cx| {
-                if let Some((path, color)) = self.path.as_ref().zip(style.text.color) {
-                    let transformation = self
-                        .transformation
-                        .as_ref()
-                        .map(|transformation| {
-                            transformation.into_matrix(bounds.center(), window.scale_factor())
-                        })
-                        .unwrap_or_default();
+                if let Some(replacement) = &mut request_layout.replacement {
+                    replacement.paint(window, cx);
+                    return;
+                }
 
-                    window
-                        .paint_svg(bounds, path.clone(), None, transformation, color, cx)
-                        .log_err();
-                } else if let Some((path, color)) =
-                    self.external_path.as_ref().zip(style.text.color)
-                {
-                    let Some(bytes) = window
-                        .use_asset::<SvgAsset>(path, cx)
-                        .and_then(|asset| asset.log_err())
+                let (color, color_source) = if self.fill.is_some() {
+                    (None, SvgColorSource::Fill)
+                } else if self.preserve_colors {
+                    (None, SvgColorSource::Preserved)
+                } else if let Some(color) = self.color {
+                    (Some(color), SvgColorSource::Color)
+                } else if let Some(color) = style.text.color {
+                    (Some(color.into()), SvgColorSource::TextColor)
+                } else {
+                    (None, SvgColorSource::None)
+                };
+
+                #[cfg(any(feature = "inspector", debug_assertions))]
+                window.with_inspector_state(
+                    inspector_id,
+                    cx,
+                    |inspector_state: &mut Option<SvgInspectorState>, _window| {
+                        *inspector_state = Some(SvgInspectorState {
+                            color,
+                            color_source,
+                        });
+                    },
+                );
+
+                if color_source == SvgColorSource::None {
+                    return;
+                }
+
+                let (path, bytes) = if let Some(path) = self.path.as_ref() {
+                    (path, None)
+                } else if let Some(path) = self.external_path.as_ref() {
+                    let Some(bytes) =
+                        use_external_svg(path, self.named_source.as_ref(), window, cx)
+                            .and_then(|asset| asset.log_err())
                     else {
                         return;
                     };
+                    (path, Some(bytes))
+                } else if let Some((cache_key, bytes)) = self.data.as_ref() {
+                    (cache_key, Some(bytes.clone()))
+                } else {
+                    return;
+                };
+
+                let view_box = self.view_box.map(SvgViewBox);
+                let (paint_bounds, intrinsic_size) =
+                    self.object_fit_bounds(bounds, path, bytes.as_ref(), window, cx);
+
+                let content_mask = matches!(self.object_fit, Some(ObjectFit::Cover))
+                    .then_some(ContentMask { bounds });
+                window.with_content_mask(content_mask, |window| {
+                    let base_options = SvgRenderOptions {
+                        view_box,
+                        symbol: self.symbol.clone(),
+                        parse_options: self.parse_options.clone(),
+                        ..Default::default()
+                    };
+                    let hidden_overrides_options = SvgRenderOptions {
+                        style_sheet: (!self.paint_overrides.is_empty())
+                            .then(|| hidden_nodes_style_sheet(&self.paint_overrides).into()),
+                        ..base_options.clone()
+                    };
 
-                    let transformation = self
-                        .transformation
-                        .as_ref()
+                    let mut transformation = transformation
+                        .filter(|transformation| !transformation.is_identity())
                         .map(|transformation| {
-                            transformation.into_matrix(bounds.center(), window.scale_factor())
+                            transformation.into_matrix(
+                                paint_bounds,
+                                window.rem_size(),
+                                window.scale_factor(),
+                            )
                         })
                         .unwrap_or_default();
 
-                    window
-                        .paint_svg(
-                            bounds,
-                            path.clone(),
-                            Some(&bytes),
-                            transformation,
-                            color,
-                            cx,
-                        )
-                        .log_err();
+                    // The SVG is rasterized at the width of the bounds with its own aspect
+                    // ratio, so filling the bounds requires stretching it vertically.
+                    if let Some((ObjectFit::Fill, intrinsic_size)) =
+                        self.object_fit.as_ref().zip(intrinsic_size)
+                    {
+                        let rendered_height = paint_bounds.size.width.0 * intrinsic_size.height
+                            / intrinsic_size.width;
+                        if rendered_height > 0. {
+                            let center = paint_bounds.center().scale(window.scale_factor());
+                            transformation = transformation.compose(
+                                TransformationMatrix::unit()
+                                    .translate(center)
+                                    .scale(size(1., paint_bounds.size.height.0 / rendered_height))
+                                    .translate(center.negate()),
+                            );
+                        }
+                    }
+
+                    for shadow in &self.shape_shadows {
+                        window
+                            .paint_svg_shadow(
+                                paint_bounds,
+                                path.clone(),
+                                bytes.as_deref(),
+                                transformation,
+                                shadow,
+                                base_options.clone(),
+                                cx,
+                            )
+                            .log_err();
+                    }
+
+                    if let Some(fill) = self.fill {
+                        let options = SvgRenderOptions {
+                            fill: Some(SvgFill(fill)),
+                            ..hidden_overrides_options
+                        };
+                        window
+                            .paint_colored_svg_with_options(
+                                paint_bounds,
+                                path.clone(),
+                                bytes.as_deref(),
+                                transformation,
+                                options,
+                                cx,
+                            )
+                            .log_err();
+                    } else if let Some(color) = color {
+                        window
+                            .paint_svg_with_options(
+                                paint_bounds,
+                                path.clone(),
+                                bytes.as_deref(),
+                                transformation,
+                                color,
+                                hidden_overrides_options,
+                                cx,
+                            )
+                            .log_err();
+                    } else {
+                        let options = SvgRenderOptions {
+                            style_sheet: (!self.paint_overrides.is_empty())
+                                .then(|| paint_overrides_style_sheet(&self.paint_overrides).into()),
+                            ..base_options
+                        };
+                        window
+                            .paint_colored_svg_with_options(
+                                paint_bounds,
+                                path.clone(),
+                                bytes.as_deref(),
+                                transformation,
+                                options,
+                                cx,
+                            )
+                            .log_err();
+                        return;
+                    }
+
+                    if !self.paint_overrides.is_empty() {
+                        let options = SvgRenderOptions {
+                            style_sheet: Some(
+                                isolated_nodes_style_sheet(&self.paint_overrides).into(),
+                            ),
+                            ..base_options
+                        };
+                        window
+                            .paint_colored_svg_with_options(
+                                paint_bounds,
+                                path.clone(),
+                                bytes.as_deref(),
+                                transformation,
+                                options,
+                                cx,
+                            )
+                            .log_err();
+                    }
+                });
+            },
+        )
+    }
+}
+
+struct SvgPaintOverride {
+    node_id: SharedString,
+    property: &'static str,
+    color: Hsla,
+}
+
+/// Whether the node id can be used in an id selector without escaping, so that the style sheets
+/// built from paint overrides can't be altered by the ids in them.
+fn is_css_identifier(id: &str) -> bool {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii();
+    let mut chars = id.trim_start_matches('-').chars();
+    match chars.next() {
+        Some(first) => !first.is_ascii_digit() && is_name_char(first) && chars.all(is_name_char),
+        None => false,
+    }
+}
+
+/// CSS that paints the overridden nodes with their colors.
+fn paint_overrides_style_sheet(overrides: &[SvgPaintOverride]) -> String {
+    let mut style_sheet = String::new();
+    for paint_override in overrides {
+        let color = paint_override.color.to_rgb();
+        style_sheet.push_str(&format!(
+            "#{} {{ {}: rgba({}, {}, {}, {}) }}\n",
+            paint_override.node_id,
+            paint_override.property,
+            (color.r * 255.).round(),
+            (color.g * 255.).round(),
+            (color.b * 255.).round(),
+            color.a,
+        ));
+    }
+    style_sheet
+}
+
+/// CSS that hides the overridden nodes, so they can be left out of the tinted rendering.
+fn hidden_nodes_style_sheet(overrides: &[SvgPaintOverride]) -> String {
+    let mut style_sheet = String::new();
+    for paint_override in overrides {
+        style_sheet.push_str(&format!(
+            "#{} {{ visibility: hidden }}\n",
+            paint_override.node_id
+        ));
+    }
+    style_sheet
+}
+
+/// CSS that hides everything but the overridden nodes, and paints those with their colors.
+fn isolated_nodes_style_sheet(overrides: &[SvgPaintOverride]) -> String {
+    let mut style_sheet = String::from("* { visibility: hidden }\n");
+    for paint_override in overrides {
+        style_sheet.push_str(&format!(
+            "#{0}, #{0} * {{ visibility: visible }}\n",
+            paint_override.node_id
+        ));
+    }
+    style_sheet.push_str(&paint_overrides_style_sheet(overrides));
+    style_sheet
+}
+
+/// The file watcher shared by every element watching an external path, so that each file is
+/// only watched once however many elements show it.
+struct SvgWatcher {
+    state: Rc<RefCell<SvgWatcherState>>,
+    _changes: Task<()>,
+}
+
+impl Global for SvgWatcher {}
+
+struct SvgWatcherState {
+    watcher: notify::RecommendedWatcher,
+    /// The external path of each watched file, and how many elements are watching it.
+    watched_files: HashMap<PathBuf, (SharedString, usize)>,
+}
+
+impl SvgWatcher {
+    fn new(cx: &mut App) -> anyhow::Result<Self> {
+        let (changes_tx, mut changes_rx) = mpsc::unbounded();
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Some(event) = event
+                .log_err()
+                .filter(|event| !matches!(event.kind, notify::EventKind::Access(_)))
+            else {
+                return;
+            };
+            for path in event.paths {
+                changes_tx.unbounded_send(path).ok();
+            }
+        })?;
+        let state = Rc::new(RefCell::new(SvgWatcherState {
+            watcher,
+            watched_files: HashMap::default(),
+        }));
+
+        let changes = cx.spawn({
+            let state = Rc::downgrade(&state);
+            async move |cx| {
+                while let Some(changed_file) = changes_rx.next().await {
+                    let Some(state) = state.upgrade() else {
+                        break;
+                    };
+                    let Some(path) = state
+                        .borrow()
+                        .watched_files
+                        .get(&changed_file)
+                        .map(|(path, _)| path.clone())
+                    else {
+                        continue;
+                    };
+                    cx.update(|cx| {
+                        for window in cx.windows() {
+                            window
+                                .update(cx, |_, window, cx| window.invalidate_svg(&path, cx))
+                                .ok();
+                        }
+                    })
+                    .ok();
+                }
+            }
+        });
+
+        Ok(Self {
+            state,
+            _changes: changes,
+        })
+    }
+}
+
+/// Keeps a file watched by the [`SvgWatcher`] until it's dropped.
+struct SvgWatch {
+    file: PathBuf,
+    state: Weak<RefCell<SvgWatcherState>>,
+}
+
+impl SvgWatch {
+    fn new(path: &SharedString, cx: &mut App) -> anyhow::Result<Self> {
+        if !cx.has_global::<SvgWatcher>() {
+            let watcher = SvgWatcher::new(cx)?;
+            cx.set_global(watcher);
+        }
+        let state = cx.global::<SvgWatcher>().state.clone();
+        // The watcher reports the paths it was asked to watch, so watch the canonical one to
+        // recognize the file however the element refers to it.
+        let file = fs::canonicalize(path.as_ref())
+            .with_context(|| format!("resolving watched SVG path {path}"))?;
+
+        let mut state_ref = state.borrow_mut();
+        let SvgWatcherState {
+            watcher,
+            watched_files,
+        } = &mut *state_ref;
+        if let Some((_, watchers)) = watched_files.get_mut(&file) {
+            *watchers += 1;
+        } else {
+            notify::Watcher::watch(watcher, &file, notify::RecursiveMode::NonRecursive)?;
+            watched_files.insert(file.clone(), (path.clone(), 1));
+        }
+        drop(state_ref);
+
+        Ok(Self {
+            file,
+            state: Rc::downgrade(&state),
+        })
+    }
+}
+
+impl Drop for SvgWatch {
+    fn drop(&mut self) {
+        let Some(state) = self.state.upgrade() else {
+            return;
+        };
+        let mut state = state.borrow_mut();
+        let SvgWatcherState {
+            watcher,
+            watched_files,
+        } = &mut *state;
+        let Some((_, watchers)) = watched_files.get_mut(&self.file) else {
+            return;
+        };
+        *watchers -= 1;
+        if *watchers == 0 {
+            watched_files.remove(&self.file);
+            notify::Watcher::unwatch(watcher, &self.file).log_err();
+        }
+    }
+}
+
+struct SvgWatchState {
+    path: SharedString,
+    _watch: Option<SvgWatch>,
+}
+
+fn watch_external_path(
+    global_id: Option<&GlobalElementId>,
+    path: &SharedString,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let Some(global_id) = global_id else {
+        debug_assert!(
+            false,
+            "watching the SVG at {path} requires the element to have an id"
+        );
+        return;
+    };
+    if path.starts_with("data:") || path.starts_with("http://") || path.starts_with("https://") {
+        return;
+    }
+
+    window.with_element_state::<SvgWatchState, _>(global_id, |state, _| {
+        if let Some(state) = state.filter(|state| state.path == *path) {
+            return ((), state);
+        }
+        // A file that can't be watched is only reported once, rather than on every frame.
+        let watch = SvgWatch::new(path, cx).log_err();
+        (
+            (),
+            SvgWatchState {
+                path: path.clone(),
+                _watch: watch,
+            },
+        )
+    })
+}
+
+/// Removes the bytes and intrinsic size cached for an SVG from the asset system.
+pub(crate) fn remove_svg_assets(
+    path: &SharedString,
+    parse_options: impl IntoIterator<Item = SvgParseOptions>,
+    cx: &mut App,
+) {
+    cx.remove_asset::<SvgAsset>(path);
+    cx.remove_asset::<SvgSourceAsset>(path);
+    // The bytes don't contribute to the hash of `SvgData`, so they don't matter here.
+    for parse_options in parse_options {
+        cx.remove_asset::<SvgSizeAsset>(&SvgData {
+            cache_key: path.clone(),
+            bytes: Arc::from([]),
+            parse_options,
+        });
+    }
+}
+
+/// The external path whose load listeners have already been called.
+struct SvgLoadListenerState {
+    notified_path: SharedString,
+}
+
+impl Svg {
+    fn notify_load_listeners(
+        &self,
+        global_id: Option<&GlobalElementId>,
+        path: &SharedString,
+        asset: Option<&Result<Arc<[u8]>, ImageCacheError>>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        if self.load_listener.is_none() && self.error_listener.is_none() {
+            return;
+        }
+        let Some(global_id) = global_id else {
+            return;
+        };
+
+        let result = match asset {
+            None => return,
+            Some(Err(error)) => Err(error.clone()),
+            Some(Ok(bytes)) => match use_svg_sizes(path, bytes, &self.parse_options, window, cx) {
+                Some(result) => result.and_then(|sizes| sizes.get(self.symbol.as_ref())),
+                None => return,
+            },
+        };
+
+        window.with_element_state::<SvgLoadListenerState, _>(global_id, |state, window| {
+            if state.is_none_or(|state| state.notified_path != *path) {
+                let load_listener = self.load_listener.clone();
+                let error_listener = self.error_listener.clone();
+                window.defer(cx, move |window, cx| match result {
+                    Ok(size) => {
+                        if let Some(listener) = load_listener {
+                            listener(size, window, cx);
+                        }
+                    }
+                    Err(error) => {
+                        if let Some(listener) = error_listener {
+                            listener(&error, window, cx);
+                        }
+                    }
+                });
+            }
+            (
+                (),
+                SvgLoadListenerState {
+                    notified_path: path.clone(),
+                },
+            )
+        });
+    }
+}
+
+/// The longest side, in pixels, of the alpha masks used by [`Svg::hit_test_alpha`].
+const MAX_HITBOX_MASK_SIZE: f32 = 128.;
+
+struct SvgHitboxMaskState {
+    params: RenderSvgParams,
+    size: Size<DevicePixels>,
+    alpha: Arc<[u8]>,
+}
+
+/// When the continuous rotation of an element with an id started.
+struct SvgRotationState {
+    start: Instant,
+}
+
+/// The angle of a continuous rotation that turns once every `period`, `elapsed` after it started.
+fn continuous_rotation_angle(elapsed: Duration, period: Duration) -> f32 {
+    let speed = TAU / period.as_secs_f32();
+    (elapsed.as_secs_f32() * speed) % TAU
+}
+
+impl Svg {
+    /// The bounds the SVG is painted in within the element's bounds, fitted to its intrinsic size
+    /// by its [`ObjectFit`], along with that size. The intrinsic size is only resolved when
+    /// there's an object fit, so that SVGs painted into the element's bounds aren't parsed for it.
+    fn object_fit_bounds(
+        &self,
+        bounds: Bounds<Pixels>,
+        path: &SharedString,
+        bytes: Option<&Arc<[u8]>>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (Bounds<Pixels>, Option<Size<f32>>) {
+        let Some(object_fit) = self.object_fit.as_ref() else {
+            return (bounds, None);
+        };
+        let intrinsic_size = known_intrinsic_size(
+            self.size
+                .or_else(|| self.view_box.map(|view_box| view_box.size)),
+            self.aspect_ratio,
+            bounds,
+            path,
+            bytes,
+            self.symbol.as_ref(),
+            &self.parse_options,
+            window,
+            cx,
+        );
+        match intrinsic_size {
+            Some(intrinsic_size) => (
+                object_fit.get_bounds_for_size(bounds, intrinsic_size.map(px)),
+                Some(intrinsic_size),
+            ),
+            None => (bounds, None),
+        }
+    }
+
+    /// The alpha mask restricting the hitbox to the SVG's shape, laid out like it's painted.
+    fn hitbox_mask(
+        &self,
+        global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        threshold: f32,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<HitboxMask> {
+        let (path, bytes) = if let Some(path) = self.path.as_ref() {
+            (path, None)
+        } else if let Some(path) = self.external_path.as_ref() {
+            let bytes =
+                use_external_svg(path, self.named_source.as_ref(), window, cx)?.log_err()?;
+            (path, Some(bytes))
+        } else {
+            let (cache_key, bytes) = self.data.as_ref()?;
+            (cache_key, Some(bytes.clone()))
+        };
+
+        let (paint_bounds, _) = self.object_fit_bounds(bounds, path, bytes.as_ref(), window, cx);
+        if paint_bounds.size.width <= px(0.) || paint_bounds.size.height <= px(0.) {
+            return None;
+        }
+
+        let mask_scale = (MAX_HITBOX_MASK_SIZE
+            / paint_bounds.size.width.0.max(paint_bounds.size.height.0))
+        .min(1.);
+        let params = RenderSvgParams {
+            path: path.clone(),
+            size: paint_bounds
+                .size
+                .map(|length| DevicePixels((length.0 * mask_scale).ceil() as i32)),
+            transformation_scale_percent: 100,
+            preserve_colors: false,
+            options: SvgRenderOptions {
+                view_box: self.view_box.map(SvgViewBox),
+                symbol: self.symbol.clone(),
+                parse_options: self.parse_options.clone(),
+                ..Default::default()
+            },
+        };
+        let (size, alpha) = window.with_optional_element_state::<SvgHitboxMaskState, _>(
+            global_id,
+            |state, _| {
+                if let Some(Some(state)) = state
+                    && state.params == params
+                {
+                    return (Some((state.size, state.alpha.clone())), Some(state));
                 }
+                let Some((size, alpha)) = cx
+                    .svg_renderer
+                    .render_alpha_mask(&params, bytes.as_deref())
+                    .log_err()
+                    .flatten()
+                else {
+                    return (None, None);
+                };
+                let alpha: Arc<[u8]> = alpha.into();
+                (
+                    Some((size, alpha.clone())),
+                    Some(SvgHitboxMaskState {
+                        params,
+                        size,
+                        alpha,
+                    }),
+                )
             },
+        )?;
+
+        let mask_bounds = if matches!(self.object_fit, Some(ObjectFit::Fill)) {
+            paint_bounds
+        } else {
+            Bounds::centered_at(
+                paint_bounds.center(),
+                size.map(|length| px(length.0 as f32 / mask_scale)),
+            )
+        };
+        let inverse_transformation = self
+            .transformation
+            .filter(|transformation| !transformation.is_identity())
+            .and_then(|transformation| {
+                transformation
+                    .into_matrix(paint_bounds, window.rem_size(), 1.)
+                    .inverse()
+            })
+            .unwrap_or_default();
+
+        Some(HitboxMask {
+            bounds: mask_bounds,
+            inverse_transformation,
+            size,
+            alpha,
+            threshold: (threshold.clamp(0., 1.) * 255.).round() as u8,
+        })
+    }
+
+    /// The transformation to paint with this frame, including any continuous rotation.
+    fn animated_transformation(
+        &self,
+        global_id: Option<&GlobalElementId>,
+        window: &mut Window,
+    ) -> Option<Transformation> {
+        let Some(period) = self.rotation_period.filter(|period| !period.is_zero()) else {
+            return self.transformation;
+        };
+
+        static EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+        let now = Instant::now();
+        let start =
+            window.with_optional_element_state::<SvgRotationState, _>(global_id, |state, _| {
+                let Some(state) = state else {
+                    return (*EPOCH, None);
+                };
+                let state = state.unwrap_or(SvgRotationState { start: now });
+                (state.start, Some(state))
+            });
+        let angle = continuous_rotation_angle(now.saturating_duration_since(start), period);
+        window.request_animation_frame();
+
+        let base = self.transformation.unwrap_or_default();
+        Some(
+            Transformation::rotate(radians(angle))
+                .with_origin(base.origin)
+                .then(base),
         )
     }
 }
 
+/// The size the SVG declares for itself, when it's known without blocking on a load.
+fn known_intrinsic_size(
+    explicit_size: Option<Size<f32>>,
+    aspect_ratio: Option<f32>,
+    bounds: Bounds<Pixels>,
+    path: &SharedString,
+    bytes: Option<&Arc<[u8]>>,
+    symbol: Option<&SharedString>,
+    parse_options: &SvgParseOptions,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<Size<f32>> {
+    if let Some(size) = explicit_size {
+        return Some(size);
+    }
+    if let Some(aspect_ratio) = aspect_ratio {
+        // Without an absolute size, treat the SVG as being as tall as the element.
+        return Some(size(
+            bounds.size.height.0 * aspect_ratio,
+            bounds.size.height.0,
+        ));
+    }
+    use_intrinsic_size(path, bytes, symbol, parse_options, window, cx)
+}
+
+/// Returns the size the SVG declares for itself, loading it from the asset source in the
+/// background if no bytes are given.
+fn use_intrinsic_size(
+    path: &SharedString,
+    bytes: Option<&Arc<[u8]>>,
+    symbol: Option<&SharedString>,
+    parse_options: &SvgParseOptions,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<Size<f32>> {
+    let bytes = match bytes {
+        Some(bytes) => bytes.clone(),
+        None => window.use_asset::<SvgSourceAsset>(path, cx)?.log_err()?,
+    };
+    // An unknown symbol is reported when the SVG is rendered.
+    use_svg_sizes(path, &bytes, parse_options, window, cx)
+        .and_then(|sizes| sizes.log_err()?.get(symbol).ok())
+}
+
+fn use_svg_sizes(
+    cache_key: &SharedString,
+    bytes: &Arc<[u8]>,
+    parse_options: &SvgParseOptions,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<Result<SvgSizes, ImageCacheError>> {
+    let data = SvgData {
+        cache_key: cache_key.clone(),
+        bytes: bytes.clone(),
+        parse_options: parse_options.clone(),
+    };
+    window.use_asset::<SvgSizeAsset>(&data, cx)
+}
+
+/// Size an auto-sized dimension of the element from the SVG's own size, preserving its aspect ratio.
+fn apply_intrinsic_size(style: &mut Style, intrinsic_size: Size<f32>, window: &Window) {
+    if intrinsic_size.width <= 0. || intrinsic_size.height <= 0. {
+        return;
+    }
+    style.aspect_ratio = Some(intrinsic_size.width / intrinsic_size.height);
+
+    if let Length::Auto = style.size.width {
+        style.size.width = match style.size.height {
+            Length::Definite(DefiniteLength::Absolute(abs_length)) => {
+                let height_px = abs_length.to_pixels(window.rem_size());
+                Length::Definite(
+                    px(intrinsic_size.width * height_px.0 / intrinsic_size.height).into(),
+                )
+            }
+            _ => Length::Definite(px(intrinsic_size.width).into()),
+        };
+    }
+
+    if let Length::Auto = style.size.height {
+        style.size.height = match style.size.width {
+            Length::Definite(DefiniteLength::Absolute(abs_length)) => {
+                let width_px = abs_length.to_pixels(window.rem_size());
+                Length::Definite(
+                    px(intrinsic_size.height * width_px.0 / intrinsic_size.width).into(),
+                )
+            }
+            _ => Length::Definite(px(intrinsic_size.height).into()),
+        };
+    }
+}
+
 impl IntoElement for Svg {
     type Element = Self;
 
@@ -189,6 +1289,8 @@ pub struct Transformation {
     scale: Size<f32>,
     translate: Point<Pixels>,
     rotate: Radians,
+    skew: (Radians, Radians),
+    origin: Point<DefiniteLength>,
 }
 
 impl Default for Transformation {
@@ -197,6 +1299,8 @@ impl Default for Transformation {
             scale: size(1.0, 1.0),
             translate: point(px(0.0), px(0.0)),
             rotate: radians(0.0),
+            skew: (radians(0.0), radians(0.0)),
+            origin: point(relative(0.5), relative(0.5)),
         }
     }
 }
@@ -206,27 +1310,31 @@ impl Transformation {
     pub fn scale(scale: Size<f32>) -> Self {
         Self {
             scale,
-            translate: point(px(0.0), px(0.0)),
-            rotate: radians(0.0),
+            ..Default::default()
         }
     }
 
     /// Create a new Transformation with the specified translation.
     pub fn translate(translate: Point<Pixels>) -> Self {
         Self {
-            scale: size(1.0, 1.0),
             translate,
-            rotate: radians(0.0),
+            ..Default::default()
         }
     }
 
     /// Create a new Transformation with the specified rotation in radians.
     pub fn rotate(rotate: impl Into<Radians>) -> Self {
-        let rotate = rotate.into();
         Self {
-            scale: size(1.0, 1.0),
-            translate: point(px(0.0), px(0.0)),
-            rotate,
+            rotate: rotate.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new Transformation with the specified skew angles along the x and y axes.
+    pub fn skew(x: impl Into<Radians>, y: impl Into<Radians>) -> Self {
+        Self {
+            skew: (x.into(), y.into()),
+            ..Default::default()
         }
     }
 
@@ -248,30 +1356,1595 @@ impl Transformation {
         self
     }
 
-    fn into_matrix(self, center: Point<Pixels>, scale_factor: f32) -> TransformationMatrix {
-        //Note: if you read this as a sequence of matrix multiplications, start from the bottom
-        TransformationMatrix::unit()
-            .translate(center.scale(scale_factor) + self.translate.scale(scale_factor))
-            .rotate(self.rotate)
-            .scale(self.scale)
-            .translate(center.scale(scale_factor).negate())
+    /// Update the skew angles of this transformation along the x and y axes.
+    pub fn with_skew(mut self, x: impl Into<Radians>, y: impl Into<Radians>) -> Self {
+        self.skew = (x.into(), y.into());
+        self
     }
-}
 
-enum SvgAsset {}
+    /// Update the point that this transformation rotates and scales around, relative to the
+    /// bounds of the element. Defaults to the center of the element.
+    pub fn with_origin(mut self, origin: Point<DefiniteLength>) -> Self {
+        self.origin = origin;
+        self
+    }
 
-impl Asset for SvgAsset {
-    type Source = SharedString;
-    type Output = Result<Arc<[u8]>, Arc<std::io::Error>>;
+    /// Compose this transformation with another, producing a transformation that applies `self`
+    /// first and then `next`. Both transformations are expected to share the same origin; the
+    /// composed transformation uses the origin of `self`.
+    pub fn then(self, next: Transformation) -> Self {
+        let first = self.linear_matrix();
+        let second = next.linear_matrix();
+        let linear = second.compose(first);
+        let translate = next.translate
+            + point(
+                px(second.rotation_scale[0][0] * self.translate.x.0
+                    + second.rotation_scale[0][1] * self.translate.y.0),
+                px(second.rotation_scale[1][0] * self.translate.x.0
+                    + second.rotation_scale[1][1] * self.translate.y.0),
+            );
+
+        // Decompose the combined linear part back into a rotation, a horizontal skew and a scale.
+        let [[a, b], [c, d]] = linear.rotation_scale;
+        let scale_x = a.hypot(c);
+        let rotate = if scale_x == 0. { 0. } else { c.atan2(a) };
+        let (sin, cos) = rotate.sin_cos();
+        let scale_y = cos * d - sin * b;
+        let skew_x = if scale_y == 0. {
+            0.
+        } else {
+            ((cos * b + sin * d) / scale_y).atan()
+        };
+
+        Self {
+            scale: size(scale_x, scale_y),
+            translate,
+            rotate: radians(rotate),
+            skew: (radians(skew_x), radians(0.)),
+            origin: self.origin,
+        }
+    }
+
+    /// Returns whether this transformation leaves the element unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.scale == size(1., 1.)
+            && self.translate == point(px(0.), px(0.))
+            && self.rotate == radians(0.)
+            && self.skew == (radians(0.), radians(0.))
+    }
+
+    /// The scale, skew and rotation of this transformation, without any translation.
+    fn linear_matrix(self) -> TransformationMatrix {
+        TransformationMatrix::unit()
+            .rotate(self.rotate)
+            .skew(self.skew.0, self.skew.1)
+            .scale(self.scale)
+    }
+
+    /// The smallest axis-aligned bounds containing the given bounds once transformed.
+    fn transform_bounds(self, bounds: Bounds<Pixels>, rem_size: Pixels) -> Bounds<Pixels> {
+        let matrix = self.into_matrix(bounds, rem_size, 1.0);
+        let corners = [
+            bounds.origin,
+            bounds.top_right(),
+            bounds.bottom_right(),
+            bounds.bottom_left(),
+        ]
+        .map(|corner| matrix.apply(corner));
+        let top_left = corners
+            .iter()
+            .skip(1)
+            .fold(corners[0], |top_left, corner| top_left.min(corner));
+        let bottom_right = corners
+            .iter()
+            .skip(1)
+            .fold(corners[0], |bottom_right, corner| bottom_right.max(corner));
+        Bounds::from_corners(top_left, bottom_right)
+    }
+
+    fn into_matrix(
+        self,
+        bounds: Bounds<Pixels>,
+        rem_size: Pixels,
+        scale_factor: f32,
+    ) -> TransformationMatrix {
+        let origin = bounds.origin
+            + point(
+                self.origin.x.to_pixels(bounds.size.width.into(), rem_size),
+                self.origin.y.to_pixels(bounds.size.height.into(), rem_size),
+            );
+
+        // Note: if you read this as a sequence of matrix multiplications, start from the bottom.
+        // The element is scaled, then skewed, then rotated, then translated.
+        TransformationMatrix::unit()
+            .translate(origin.scale(scale_factor) + self.translate.scale(scale_factor))
+            .compose(self.linear_matrix())
+            .translate(origin.scale(scale_factor).negate())
+    }
+}
+
+/// The largest SVG document that will be downloaded for an `external_path` URL.
+const MAX_REMOTE_SVG_BYTES: u64 = 16 * 1024 * 1024;
+
+/// How long a failed `external_path` load stays cached before it's loaded again, in case the
+/// failure was transient, such as a locked file or a network error.
+const SVG_LOAD_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+enum SvgAsset {}
+
+impl Asset for SvgAsset {
+    type Source = SharedString;
+    type Output = Result<Arc<[u8]>, ImageCacheError>;
+
+    fn load(
+        source: Self::Source,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        let resource = if source.starts_with("data:")
+            || source.starts_with("http://")
+            || source.starts_with("https://")
+        {
+            Resource::Uri(source.into())
+        } else {
+            Resource::Path(Path::new(source.as_ref()).into())
+        };
+        let load = load_resource_bytes(resource, Some(MAX_REMOTE_SVG_BYTES), cx);
+        async move { load.await.map(Arc::from) }
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        output.as_ref().map_or(0, |bytes| bytes.len())
+    }
+
+    fn cancellable() -> bool {
+        true
+    }
+
+    fn retry_after(output: &Self::Output) -> Option<Duration> {
+        output.is_err().then_some(SVG_LOAD_RETRY_DELAY)
+    }
+}
+
+/// Decode the body of a `data:` URI, which is either base64 or percent encoded.
+pub(crate) fn decode_data_uri(uri: &str) -> Result<Vec<u8>, ImageCacheError> {
+    let malformed = || ImageCacheError::Asset(format!("malformed data URI: {uri:.64}").into());
+    let (header, body) = uri
+        .strip_prefix("data:")
+        .and_then(|uri| uri.split_once(','))
+        .ok_or_else(malformed)?;
+    if header.ends_with(";base64") {
+        let body = body
+            .bytes()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .collect::<Vec<_>>();
+        base64::engine::general_purpose::STANDARD
+            .decode(body)
+            .map_err(|_| malformed())
+    } else {
+        Ok(urlencoding::decode_binary(body.as_bytes()).into_owned())
+    }
+}
+
+/// SVG markup held in memory, identified by a caller-provided cache key.
+#[derive(Clone)]
+struct SvgData {
+    cache_key: SharedString,
+    bytes: Arc<[u8]>,
+    parse_options: SvgParseOptions,
+}
+
+// Hashing the key rather than the bytes keeps asset lookups cheap for large documents.
+impl Hash for SvgData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cache_key.hash(state);
+        self.parse_options.hash(state);
+    }
+}
+
+/// Loads the bytes of an SVG set with [`Svg::external_path`] or [`Svg::path_in`].
+fn use_external_svg(
+    path: &SharedString,
+    named_source: Option<&(SharedString, SharedString)>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<Result<Arc<[u8]>, ImageCacheError>> {
+    match named_source {
+        Some(named_source) => window.use_asset::<SvgNamedSourceAsset>(named_source, cx),
+        None => window.use_asset::<SvgAsset>(path, cx),
+    }
+}
+
+/// Loads an SVG from an asset source registered with [`App::register_asset_source`], falling back
+/// to the app's asset source, for SVGs set with [`Svg::path_in`].
+enum SvgNamedSourceAsset {}
+
+impl Asset for SvgNamedSourceAsset {
+    type Source = (SharedString, SharedString);
+    type Output = Result<Arc<[u8]>, ImageCacheError>;
+
+    fn load(
+        (source_id, path): Self::Source,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        let named_source = cx.named_asset_source(&source_id).cloned();
+        let asset_source = cx.asset_source().clone();
+        async move {
+            let named_source = named_source.ok_or_else(|| {
+                ImageCacheError::Asset(format!("no asset source named {source_id:?}").into())
+            })?;
+            let bytes = match named_source.load(&path)? {
+                Some(bytes) => bytes,
+                None => asset_source
+                    .load(&path)?
+                    .with_context(|| format!("no asset at path {path:?} in {source_id:?}"))?,
+            };
+            Ok(Arc::from(bytes))
+        }
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        output.as_ref().map_or(0, |bytes| bytes.len())
+    }
+
+    fn retry_after(output: &Self::Output) -> Option<Duration> {
+        output.is_err().then_some(SVG_LOAD_RETRY_DELAY)
+    }
+}
+
+/// Loads an SVG from the app's asset source, for SVGs set with [`Svg::path`].
+enum SvgSourceAsset {}
+
+impl Asset for SvgSourceAsset {
+    type Source = SharedString;
+    type Output = Result<Arc<[u8]>, ImageCacheError>;
+
+    fn load(
+        source: Self::Source,
+        cx: &mut App,
+    ) -> impl Future<Output = Self::Output> + Send + 'static {
+        let asset_source = cx.asset_source().clone();
+        async move {
+            let bytes = asset_source
+                .load(&source)?
+                .with_context(|| format!("no asset at path {source:?}"))?;
+            Ok(Arc::from(bytes))
+        }
+    }
+
+    fn size_in_bytes(output: &Self::Output) -> usize {
+        output.as_ref().map_or(0, |bytes| bytes.len())
+    }
+}
+
+/// The size an SVG declares for itself, along with the view box sizes of its symbols.
+#[derive(Clone)]
+struct SvgSizes {
+    document: Size<f32>,
+    symbols: Arc<[(SharedString, Size<f32>)]>,
+}
+
+impl SvgSizes {
+    fn get(&self, symbol: Option<&SharedString>) -> Result<Size<f32>, ImageCacheError> {
+        let Some(symbol) = symbol else {
+            return Ok(self.document);
+        };
+        self.symbols
+            .iter()
+            .find(|(id, _)| id == symbol)
+            .map(|(_, size)| *size)
+            .ok_or_else(|| ImageCacheError::Asset(format!("no symbol with id {symbol:?}").into()))
+    }
+}
+
+enum SvgSizeAsset {}
+
+impl Asset for SvgSizeAsset {
+    type Source = SvgData;
+    type Output = Result<SvgSizes, ImageCacheError>;
 
     fn load(
         source: Self::Source,
-        _cx: &mut App,
+        cx: &mut App,
     ) -> impl Future<Output = Self::Output> + Send + 'static {
+        let renderer = cx.svg_renderer();
         async move {
-            let bytes = fs::read(Path::new(source.as_ref())).map_err(|e| Arc::new(e))?;
-            let bytes = Arc::from(bytes);
-            Ok(bytes)
+            Ok(SvgSizes {
+                document: renderer.intrinsic_size(&source.bytes, &source.parse_options)?,
+                symbols: renderer.symbol_sizes(&source.bytes)?.into(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as gpui, AssetSource, BackgroundTag, Context, DecodedImage, DecodedImageAsset,
+        Modifiers, MouseButton, Render, SMOOTH_SVG_SCALE_FACTOR, ScaledPixels, TestAppContext, div,
+        hsla, linear_color_stop, linear_gradient, prelude::*, rems,
+    };
+    use std::{
+        any::TypeId,
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        f32::consts::FRAC_PI_4,
+        rc::Rc,
+    };
+
+    const SQUARE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10"/></svg>"#;
+    const WIDE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect width="20" height="10"/></svg>"#;
+
+    #[gpui::test]
+    fn test_svg_opacity_composes_with_color_and_parent_opacity(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .opacity(0.5)
+                    .child(
+                        svg()
+                            .data("tinted-square", SQUARE_SVG.as_bytes())
+                            .size(px(10.))
+                            .text_color(hsla(0., 0., 0., 0.8))
+                            .opacity(0.5),
+                    )
+                    .child(
+                        svg()
+                            .data("colored-square", SQUARE_SVG.as_bytes())
+                            .size(px(10.))
+                            .preserve_colors(true)
+                            .opacity(0.5),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert_eq!(scene.monochrome_sprites.len(), 1);
+            assert!((scene.monochrome_sprites[0].color.a - 0.2).abs() < 1e-5);
+            assert_eq!(scene.polychrome_sprites.len(), 1);
+            assert!((scene.polychrome_sprites[0].opacity - 0.25).abs() < 1e-5);
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_color_takes_precedence_over_text_color(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .child(
+                        svg()
+                            .data("square", SQUARE_SVG.as_bytes())
+                            .size(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.))
+                            .color(hsla(0.5, 1., 0.5, 1.)),
+                    )
+                    .child(
+                        svg()
+                            .data("square", SQUARE_SVG.as_bytes())
+                            .size(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+                    .child(svg().data("square", SQUARE_SVG.as_bytes()).size(px(10.)))
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 2);
+            assert_eq!(sprites[0].color, hsla(0.5, 1., 0.5, 1.));
+            assert_eq!(sprites[1].color, hsla(0., 0., 0., 1.));
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_color_changes_reuse_rasterization(cx: &mut TestAppContext) {
+        struct TestView {
+            lightness: f32,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                svg()
+                    .data("square", SQUARE_SVG.as_bytes())
+                    .size(px(10.))
+                    .color(hsla(0.6, 1., self.lightness, 1.))
+            }
+        }
+
+        let (view, cx) = cx.add_window_view(|_, _| TestView { lightness: 0. });
+        let first_tile = cx.update(|window, _| {
+            window.rendered_frame.scene.monochrome_sprites[0]
+                .tile
+                .clone()
+        });
+
+        for step in 1..=10 {
+            view.update(cx, |view, cx| {
+                view.lightness = step as f32 / 10.;
+                cx.notify();
+            });
+            cx.update(|window, _| {
+                let sprites = &window.rendered_frame.scene.monochrome_sprites;
+                assert_eq!(sprites.len(), 1);
+                assert_eq!(sprites[0].color, hsla(0.6, 1., step as f32 / 10., 1.));
+                assert_eq!(sprites[0].tile.tile_id, first_tile.tile_id);
+            });
+        }
+    }
+
+    #[gpui::test]
+    fn test_svg_gradient_color_is_applied_when_drawing(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .child(
+                        svg()
+                            .data("square", SQUARE_SVG.as_bytes())
+                            .size(px(10.))
+                            .color(hsla(0., 0., 0., 1.)),
+                    )
+                    .child(
+                        svg()
+                            .data("square", SQUARE_SVG.as_bytes())
+                            .size(px(10.))
+                            .color(linear_gradient(
+                                90.,
+                                linear_color_stop(hsla(0., 1., 0.5, 1.), 0.),
+                                linear_color_stop(hsla(0.6, 1., 0.5, 1.), 1.),
+                            )),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert_eq!(scene.monochrome_sprites.len(), 1);
+            assert_eq!(scene.monochrome_sprites[0].color, hsla(0., 0., 0., 1.));
+            assert_eq!(scene.gradient_sprites.len(), 1);
+            assert_eq!(
+                scene.gradient_sprites[0].color.tag,
+                BackgroundTag::LinearGradient
+            );
+            assert_eq!(
+                scene.monochrome_sprites[0].tile.tile_id,
+                scene.gradient_sprites[0].tile.tile_id
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_hit_test_alpha(cx: &mut TestAppContext) {
+        const CIRCLE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20"><circle cx="10" cy="10" r="10"/></svg>"#;
+
+        struct TestView {
+            presses: Rc<Cell<usize>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let presses = self.presses.clone();
+                svg()
+                    .data("circle", CIRCLE_SVG.as_bytes())
+                    .size(px(20.))
+                    .text_color(hsla(0., 0., 0., 1.))
+                    .hit_test_alpha(0.5)
+                    .on_mouse_down(MouseButton::Left, move |_, _, _| {
+                        presses.set(presses.get() + 1)
+                    })
+            }
+        }
+
+        let presses = Rc::new(Cell::new(0));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            presses: presses.clone(),
+        });
+
+        cx.simulate_click(point(px(1.), px(1.)), Modifiers::none());
+        assert_eq!(presses.get(), 0, "the transparent corner shouldn't be hit");
+
+        cx.simulate_click(point(px(10.), px(10.)), Modifiers::none());
+        assert_eq!(presses.get(), 1, "the opaque center should be hit");
+    }
+
+    #[test]
+    fn test_transformation_matrix_inverse() {
+        let matrix = Transformation::rotate(radians(FRAC_PI_4))
+            .with_scaling(size(2., 3.))
+            .into_matrix(origin_bounds(), px(16.), 1.);
+        let Some(inverse) = matrix.inverse() else {
+            panic!("a rotation and scale can be inverted");
+        };
+        let original = point(px(3.), px(-5.));
+        assert_point_eq(inverse.apply(matrix.apply(original)), original);
+
+        let collapsed =
+            Transformation::scale(size(0., 1.)).into_matrix(origin_bounds(), px(16.), 1.);
+        assert!(collapsed.inverse().is_none());
+    }
+
+    #[gpui::test]
+    fn test_svg_shape_shadow(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                svg()
+                    .data("square", SQUARE_SVG.as_bytes())
+                    .size(px(10.))
+                    .color(hsla(0., 0., 0., 1.))
+                    .shape_shadow(BoxShadow {
+                        color: hsla(0., 0., 0., 0.5),
+                        offset: point(px(2.), px(2.)),
+                        blur_radius: px(4.),
+                        spread_radius: px(0.),
+                        inset: false,
+                    })
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 2);
+            let (shadow, shape) = (&sprites[0], &sprites[1]);
+            assert_eq!(shadow.color, hsla(0., 0., 0., 0.5));
+            assert_ne!(shadow.tile.tile_id, shape.tile.tile_id);
+            assert!(shadow.bounds.size.width > shape.bounds.size.width);
+            assert!(shadow.bounds.center().x > shape.bounds.center().x);
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_paint_overrides(cx: &mut TestAppContext) {
+        const BELL_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10"/><circle id="badge" cx="8" cy="2" r="2"/></svg>"#;
+
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .child(
+                        svg()
+                            .data("bell", BELL_SVG.as_bytes())
+                            .size(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.))
+                            .override_fill("badge", hsla(0., 1., 0.5, 1.)),
+                    )
+                    .child(
+                        svg()
+                            .data("bell", BELL_SVG.as_bytes())
+                            .size(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.))
+                            .override_fill("badge", hsla(0.3, 1., 0.5, 1.)),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert_eq!(scene.monochrome_sprites.len(), 2);
+            assert_eq!(
+                scene.monochrome_sprites[0].tile.tile_id,
+                scene.monochrome_sprites[1].tile.tile_id
+            );
+            assert_eq!(scene.polychrome_sprites.len(), 2);
+            assert_ne!(
+                scene.polychrome_sprites[0].tile.tile_id,
+                scene.polychrome_sprites[1].tile.tile_id
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_view_box(cx: &mut TestAppContext) {
+        const SHEET_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="10"><rect width="40" height="10"/></svg>"#;
+
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().flex().child(
+                    svg()
+                        .data("sheet", SHEET_SVG.as_bytes())
+                        .view_box(Bounds::new(point(10., 0.), size(10., 10.)))
+                        .h(px(20.))
+                        .text_color(hsla(0., 0., 0., 1.)),
+                )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 1);
+            let scale_factor = window.scale_factor();
+            assert_eq!(sprites[0].bounds.size.width, px(20.).scale(scale_factor));
+            assert_eq!(sprites[0].bounds.size.height, px(20.).scale(scale_factor));
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_symbol(cx: &mut TestAppContext) {
+        const SPRITE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg"><symbol id="wide" viewBox="0 0 20 10"><rect width="20" height="10"/></symbol><symbol id="square" viewBox="0 0 10 10"><rect width="10" height="10"/></symbol></svg>"#;
+
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .flex()
+                    .child(
+                        svg()
+                            .data("sprite", SPRITE_SVG.as_bytes())
+                            .symbol("wide")
+                            .h(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+                    .child(
+                        svg()
+                            .data("sprite", SPRITE_SVG.as_bytes())
+                            .symbol("missing")
+                            .size(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 1);
+            let scale_factor = window.scale_factor();
+            assert_eq!(sprites[0].bounds.size.width, px(20.).scale(scale_factor));
+            assert_eq!(sprites[0].bounds.size.height, px(10.).scale(scale_factor));
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_path_in_named_asset_source(cx: &mut TestAppContext) {
+        struct ThemeAssets;
+
+        impl AssetSource for ThemeAssets {
+            fn load(&self, path: &str) -> anyhow::Result<Option<Cow<'static, [u8]>>> {
+                Ok((path == "icons/wide.svg").then(|| {
+                    Cow::Borrowed(
+                        br#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect width="20" height="10"/></svg>"#
+                            .as_slice(),
+                    )
+                }))
+            }
+
+            fn list(&self, _path: &str) -> anyhow::Result<Vec<SharedString>> {
+                Ok(Vec::new())
+            }
+        }
+
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .flex()
+                    .child(
+                        svg()
+                            .path_in("themes", "icons/wide.svg")
+                            .h(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+                    .child(
+                        svg()
+                            .path_in("unregistered", "icons/wide.svg")
+                            .h(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.))
+                            .with_fallback(|_| {
+                                div()
+                                    .size(px(5.))
+                                    .bg(hsla(0., 1., 0.5, 1.))
+                                    .into_any_element()
+                            }),
+                    )
+            }
+        }
+
+        cx.update(|cx| cx.register_asset_source("themes", ThemeAssets));
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.run_until_parked();
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert_eq!(scene.monochrome_sprites.len(), 1);
+            assert_eq!(
+                scene.monochrome_sprites[0].bounds.size.width,
+                px(20.).scale(window.scale_factor())
+            );
+            assert_eq!(
+                scene.quads.len(),
+                1,
+                "the unknown source should use the fallback"
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_external_svg_intrinsic_size_is_cached(cx: &mut TestAppContext) {
+        const WIDE_SVG_URI: &str = r#"data:image/svg+xml,<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect width="20" height="10"/></svg>"#;
+
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().flex().child(
+                    svg()
+                        .external_path(WIDE_SVG_URI)
+                        .h(px(10.))
+                        .text_color(hsla(0., 0., 0., 1.)),
+                )
+            }
+        }
+
+        let (_, first_cx) = cx.add_window_view(|_, _| TestView);
+        first_cx.run_until_parked();
+        first_cx.update(|window, _| {
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 1);
+            assert_eq!(
+                sprites[0].bounds.size.width,
+                px(20.).scale(window.scale_factor())
+            );
+        });
+
+        // A new element for the same path is sized in its first frame.
+        let (_, second_cx) = cx.add_window_view(|_, _| TestView);
+        second_cx.update(|window, _| {
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 1);
+            assert_eq!(
+                sprites[0].bounds.size.width,
+                px(20.).scale(window.scale_factor())
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_intrinsic_size_is_only_resolved_when_needed(cx: &mut TestAppContext) {
+        struct TestView {
+            auto_height: bool,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let svg = svg()
+                    .data("square", SQUARE_SVG.as_bytes())
+                    .w(px(10.))
+                    .text_color(hsla(0., 0., 0., 1.));
+                if self.auto_height {
+                    svg
+                } else {
+                    svg.h(px(10.))
+                }
+            }
+        }
+
+        let sizes_resolved = |cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                cx.loading_assets
+                    .keys()
+                    .any(|(asset_type, _)| *asset_type == TypeId::of::<SvgSizeAsset>())
+            })
+        };
+
+        cx.add_window_view(|_, _| TestView { auto_height: false });
+        assert!(!sizes_resolved(cx));
+        cx.add_window_view(|_, _| TestView { auto_height: true });
+        assert!(sizes_resolved(cx));
+    }
+
+    #[gpui::test]
+    fn test_svg_fill(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                svg()
+                    .data("square", SQUARE_SVG.as_bytes())
+                    .size(px(10.))
+                    .text_color(hsla(0., 0., 0., 1.))
+                    .fill(linear_gradient(
+                        90.,
+                        linear_color_stop(hsla(0., 1., 0.5, 1.), 0.),
+                        linear_color_stop(hsla(0.6, 1., 0.5, 1.), 1.),
+                    ))
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert!(scene.monochrome_sprites.is_empty());
+            assert_eq!(scene.polychrome_sprites.len(), 1);
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_parse_options(cx: &mut TestAppContext) {
+        const UNSIZED_SVG: &str =
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><rect width="100%" height="100%"/></svg>"#;
+
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .flex()
+                    .child(
+                        svg()
+                            .data("unsized", UNSIZED_SVG.as_bytes())
+                            .h(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+                    .child(
+                        svg()
+                            .data("unsized", UNSIZED_SVG.as_bytes())
+                            .with_parse_options(SvgParseOptions {
+                                default_size: size(40., 20.),
+                                ..Default::default()
+                            })
+                            .h(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 2);
+            let scale_factor = window.scale_factor();
+            assert_eq!(sprites[0].bounds.size.width, px(10.).scale(scale_factor));
+            assert_eq!(sprites[1].bounds.size.width, px(20.).scale(scale_factor));
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_rasterized_at_transformation_scale(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .child(
+                        svg()
+                            .data("square", SQUARE_SVG.as_bytes())
+                            .size(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.))
+                            .with_transformation(Transformation::scale(size(3., 3.))),
+                    )
+                    .child(
+                        svg()
+                            .data("square", SQUARE_SVG.as_bytes())
+                            .size(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.))
+                            .with_transformation(Transformation::scale(size(10., 10.))),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 2);
+            let scale_factor = window.scale_factor();
+            let layout_size = px(10.).scale(scale_factor);
+            for (sprite, transformation_scale) in sprites.iter().zip([3., 4.]) {
+                assert_eq!(sprite.bounds.size.width, layout_size);
+                assert_eq!(
+                    sprite.tile.bounds.size.width.0 as f32,
+                    (layout_size.0 * SMOOTH_SVG_SCALE_FACTOR * transformation_scale).ceil()
+                );
+            }
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_with_preserved_colors_is_transformed(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().child(
+                    svg()
+                        .data("square", SQUARE_SVG.as_bytes())
+                        .size(px(10.))
+                        .preserve_colors(true)
+                        .with_transformation(Transformation::scale(size(3., 3.))),
+                )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert!(scene.monochrome_sprites.is_empty());
+            assert_eq!(scene.polychrome_sprites.len(), 1);
+            let sprite = &scene.polychrome_sprites[0];
+            let layout_size = px(10.).scale(window.scale_factor());
+            assert_eq!(sprite.bounds.size.width, layout_size);
+            // Scaled 3x around the center of the 20x20 device pixel bounds.
+            assert_eq!(
+                sprite.transformation,
+                TransformationMatrix {
+                    rotation_scale: [[3., 0.], [0., 3.]],
+                    translation: [-20., -20.],
+                }
+            );
+            assert_eq!(
+                sprite.tile.bounds.size.width.0 as f32,
+                (layout_size.0 * SMOOTH_SVG_SCALE_FACTOR * 3.).ceil()
+            );
+        });
+    }
+
+    #[test]
+    fn test_is_css_identifier() {
+        assert!(is_css_identifier("badge"));
+        assert!(is_css_identifier("status-dot_2"));
+        assert!(is_css_identifier("-badge"));
+        assert!(is_css_identifier("émoji"));
+        assert!(!is_css_identifier(""));
+        assert!(!is_css_identifier("-"));
+        assert!(!is_css_identifier("2x"));
+        assert!(!is_css_identifier("-2x"));
+        assert!(!is_css_identifier("a, *"));
+        assert!(!is_css_identifier("a{fill:red}"));
+    }
+
+    #[test]
+    fn test_paint_overrides_style_sheet() {
+        let overrides = [SvgPaintOverride {
+            node_id: "badge".into(),
+            property: "fill",
+            color: hsla(0., 1., 0.5, 0.5),
+        }];
+        assert_eq!(
+            paint_overrides_style_sheet(&overrides),
+            "#badge { fill: rgba(255, 0, 0, 0.5) }\n"
+        );
+        assert_eq!(
+            hidden_nodes_style_sheet(&overrides),
+            "#badge { visibility: hidden }\n"
+        );
+        assert_eq!(
+            isolated_nodes_style_sheet(&overrides),
+            "* { visibility: hidden }\n#badge, #badge * { visibility: visible }\n#badge { fill: rgba(255, 0, 0, 0.5) }\n"
+        );
+    }
+
+    #[gpui::test]
+    fn test_decoded_image_asset_sniffs_format(cx: &mut TestAppContext) {
+        let svg = Resource::Uri(format!("data:,{SQUARE_SVG}").into());
+        let decoded = cx.update(|cx| cx.block_on_asset::<DecodedImageAsset>(&svg));
+        assert!(
+            matches!(decoded, Ok(DecodedImage::Svg { size, .. }) if size == crate::size(10., 10.))
+        );
+
+        let mut png = Vec::new();
+        image::RgbaImage::new(2, 3)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let png = Resource::Uri(
+            format!(
+                "data:image/png;base64,{}",
+                base64::engine::general_purpose::STANDARD.encode(&png)
+            )
+            .into(),
+        );
+        let decoded = cx.update(|cx| cx.block_on_asset::<DecodedImageAsset>(&png));
+        assert!(matches!(
+            decoded,
+            Ok(DecodedImage::Raster(image)) if image.size(0) == crate::size(DevicePixels(2), DevicePixels(3))
+        ));
+    }
+
+    #[test]
+    fn test_decode_data_uri() {
+        assert_eq!(
+            decode_data_uri("data:image/svg+xml;base64,PHN2Zy8+").unwrap(),
+            b"<svg/>"
+        );
+        assert_eq!(
+            decode_data_uri("data:image/svg+xml;base64,PHN2\nZy8+").unwrap(),
+            b"<svg/>"
+        );
+        assert_eq!(
+            decode_data_uri("data:image/svg+xml,%3Csvg%2F%3E").unwrap(),
+            b"<svg/>"
+        );
+        assert_eq!(decode_data_uri("data:,<svg/>").unwrap(), b"<svg/>");
+        assert!(decode_data_uri("data:image/svg+xml;base64").is_err());
+        assert!(decode_data_uri("data:image/svg+xml;base64,%%%").is_err());
+    }
+
+    #[track_caller]
+    fn assert_point_eq(actual: Point<Pixels>, expected: Point<Pixels>) {
+        assert!(
+            (actual.x.0 - expected.x.0).abs() < 1e-5 && (actual.y.0 - expected.y.0).abs() < 1e-5,
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[gpui::test]
+    fn test_svg_data_is_sized_from_its_document(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .flex()
+                    .child(
+                        svg()
+                            .data("wide", WIDE_SVG.as_bytes())
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+                    .child(
+                        svg()
+                            .data("wide", WIDE_SVG.as_bytes())
+                            .h(px(20.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.run_until_parked();
+        cx.update(|window, _| {
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 2);
+            let scale_factor = window.scale_factor();
+            assert_eq!(sprites[0].bounds.size.width, px(20.).scale(scale_factor));
+            assert_eq!(sprites[0].bounds.size.height, px(10.).scale(scale_factor));
+            // An auto-sized width follows the document's aspect ratio.
+            assert_eq!(sprites[1].bounds.size.width, px(40.).scale(scale_factor));
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_explicit_intrinsic_size_and_aspect_ratio(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .flex()
+                    .child(
+                        svg()
+                            .data("wide", WIDE_SVG.as_bytes())
+                            .with_size(size(30., 15.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+                    .child(
+                        svg()
+                            .data("wide", WIDE_SVG.as_bytes())
+                            .with_aspect_ratio(4.)
+                            .h(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, cx| {
+            // The document isn't parsed to lay the elements out.
+            assert!(
+                !cx.loading_assets
+                    .keys()
+                    .any(|(asset_type, _)| *asset_type == TypeId::of::<SvgSizeAsset>())
+            );
+            let sprites = &window.rendered_frame.scene.monochrome_sprites;
+            assert_eq!(sprites.len(), 2);
+            let scale_factor = window.scale_factor();
+            assert_eq!(sprites[0].bounds.size.width, px(30.).scale(scale_factor));
+            assert_eq!(sprites[1].bounds.size.width, px(40.).scale(scale_factor));
+        });
+    }
+
+    #[gpui::test]
+    fn test_svg_object_fit(cx: &mut TestAppContext) {
+        struct TestView(fn() -> ObjectFit);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                svg()
+                    .data("wide", WIDE_SVG.as_bytes())
+                    .size(px(20.))
+                    .object_fit((self.0)())
+                    .text_color(hsla(0., 0., 0., 1.))
+            }
+        }
+
+        let paint = |object_fit: fn() -> ObjectFit, cx: &mut TestAppContext| {
+            let (_, cx) = cx.add_window_view(|_, _| TestView(object_fit));
+            cx.run_until_parked();
+            cx.update(|window, _| {
+                let sprites = &window.rendered_frame.scene.monochrome_sprites;
+                assert_eq!(sprites.len(), 1);
+                sprites[0].clone()
+            })
+        };
+        let device = |pixels: f32| ScaledPixels(pixels * 2.);
+
+        // The SVG is scaled down to fit the element, and centered in it.
+        let sprite = paint(|| ObjectFit::Contain, cx);
+        assert_eq!(
+            sprite.bounds,
+            Bounds::new(
+                point(device(0.), device(5.)),
+                size(device(20.), device(10.))
+            )
+        );
+        assert_eq!(sprite.transformation, TransformationMatrix::unit());
+
+        // The SVG is scaled up to cover the element, and clipped to it.
+        let sprite = paint(|| ObjectFit::Cover, cx);
+        assert_eq!(
+            sprite.bounds,
+            Bounds::new(
+                point(device(-10.), device(0.)),
+                size(device(40.), device(20.))
+            )
+        );
+        assert_eq!(
+            sprite.content_mask.bounds,
+            Bounds::new(
+                point(device(0.), device(0.)),
+                size(device(20.), device(20.))
+            )
+        );
+
+        // The SVG is stretched vertically to fill the element.
+        let sprite = paint(|| ObjectFit::Fill, cx);
+        assert_eq!(sprite.bounds.size.width, device(20.));
+        assert_eq!(sprite.transformation.rotation_scale, [[1., 0.], [0., 2.]]);
+    }
+
+    #[gpui::test]
+    fn test_svg_transformation_affecting_hitbox(cx: &mut TestAppContext) {
+        struct TestView {
+            affects_hitbox: bool,
+            presses: Rc<Cell<usize>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let presses = self.presses.clone();
+                let transformation = Transformation::scale(size(2., 2.));
+                let svg = svg()
+                    .data("square", SQUARE_SVG.as_bytes())
+                    .size(px(10.))
+                    .text_color(hsla(0., 0., 0., 1.));
+                let svg = if self.affects_hitbox {
+                    svg.with_transformation_affecting_hitbox(transformation)
+                } else {
+                    svg.with_transformation(transformation)
+                };
+                div().pl(px(20.)).pt(px(20.)).child(
+                    svg.on_mouse_down(MouseButton::Left, move |_, _, _| {
+                        presses.set(presses.get() + 1)
+                    }),
+                )
+            }
+        }
+
+        for affects_hitbox in [false, true] {
+            let presses = Rc::new(Cell::new(0));
+            let (_, cx) = cx.add_window_view(|_, _| TestView {
+                affects_hitbox,
+                presses: presses.clone(),
+            });
+
+            // Within the SVG's bounds.
+            cx.simulate_click(point(px(25.), px(25.)), Modifiers::none());
+            assert_eq!(presses.get(), 1);
+
+            // Outside of the SVG's bounds, but within the bounds of the scaled SVG.
+            cx.simulate_click(point(px(33.), px(17.)), Modifiers::none());
+            assert_eq!(presses.get(), if affects_hitbox { 2 } else { 1 });
+        }
+    }
+
+    #[test]
+    fn test_transformation_origin() {
+        let bounds = Bounds::new(point(px(10.), px(10.)), size(px(10.), px(10.)));
+        let scale = Transformation::scale(size(2., 2.));
+
+        // By default the transformation is around the center of the bounds.
+        let matrix = scale.into_matrix(bounds, px(16.), 1.);
+        assert_point_eq(matrix.apply(bounds.origin), point(px(5.), px(5.)));
+
+        let matrix = scale
+            .with_origin(point(relative(0.), relative(0.)))
+            .into_matrix(bounds, px(16.), 1.);
+        assert_point_eq(matrix.apply(bounds.origin), bounds.origin);
+        assert_point_eq(matrix.apply(bounds.bottom_right()), point(px(30.), px(30.)));
+
+        let matrix = scale
+            .with_origin(point(px(10.).into(), rems(0.5).into()))
+            .into_matrix(bounds, px(16.), 1.);
+        assert_point_eq(
+            matrix.apply(point(px(20.), px(18.))),
+            point(px(20.), px(18.)),
+        );
+        assert_point_eq(matrix.apply(bounds.origin), point(px(0.), px(2.)));
+    }
+
+    fn origin_bounds() -> Bounds<Pixels> {
+        Bounds::new(point(px(0.), px(0.)), size(px(0.), px(0.)))
+    }
+
+    #[test]
+    fn test_skew_matrix() {
+        let matrix = TransformationMatrix::unit().skew(radians(FRAC_PI_4), radians(0.));
+        assert_point_eq(matrix.apply(point(px(0.), px(2.))), point(px(2.), px(2.)));
+
+        let matrix = TransformationMatrix::unit().skew(radians(0.), radians(FRAC_PI_4));
+        assert_point_eq(matrix.apply(point(px(2.), px(0.))), point(px(2.), px(2.)));
+    }
+
+    #[test]
+    fn test_skew_is_applied_before_rotation() {
+        let matrix = Transformation::rotate(radians(2. * FRAC_PI_4))
+            .with_skew(radians(FRAC_PI_4), radians(0.))
+            .into_matrix(origin_bounds(), px(16.), 1.);
+
+        // Skewing (0, 1) gives (1, 1), which a clockwise quarter turn maps to (-1, 1).
+        assert_point_eq(matrix.apply(point(px(0.), px(1.))), point(px(-1.), px(1.)));
+    }
+
+    #[test]
+    fn test_then_applies_transformations_in_order() {
+        let rotate =
+            Transformation::rotate(radians(2. * FRAC_PI_4)).with_translation(point(px(3.), px(0.)));
+        let scale = Transformation::scale(size(2., 1.)).with_translation(point(px(0.), px(5.)));
+
+        for (first, second) in [(rotate, scale), (scale, rotate)] {
+            let composed = first.then(second).into_matrix(origin_bounds(), px(16.), 1.);
+            let expected = second
+                .into_matrix(origin_bounds(), px(16.), 1.)
+                .compose(first.into_matrix(origin_bounds(), px(16.), 1.));
+            for input in [
+                point(px(1.), px(0.)),
+                point(px(0.), px(1.)),
+                point(px(2.), px(-3.)),
+            ] {
+                assert_point_eq(composed.apply(input), expected.apply(input));
+            }
+        }
+    }
+
+    #[test]
+    fn test_then_preserves_skew() {
+        let skew = Transformation::skew(radians(FRAC_PI_4), radians(0.5));
+        let rotate = Transformation::rotate(radians(1.));
+
+        let composed = skew.then(rotate).into_matrix(origin_bounds(), px(16.), 1.);
+        let expected = rotate
+            .into_matrix(origin_bounds(), px(16.), 1.)
+            .compose(skew.into_matrix(origin_bounds(), px(16.), 1.));
+        for input in [point(px(1.), px(0.)), point(px(0.), px(1.))] {
+            assert_point_eq(composed.apply(input), expected.apply(input));
+        }
+    }
+
+    #[test]
+    fn test_is_identity() {
+        assert!(Transformation::default().is_identity());
+        assert!(Transformation::rotate(radians(0.)).is_identity());
+        assert!(!Transformation::rotate(radians(1.)).is_identity());
+        assert!(!Transformation::skew(radians(0.), radians(0.1)).is_identity());
+    }
+
+    #[test]
+    fn test_skew_is_applied_after_scale() {
+        let matrix = Transformation::scale(size(1., 2.))
+            .with_skew(radians(FRAC_PI_4), radians(0.))
+            .into_matrix(origin_bounds(), px(16.), 1.);
+
+        assert_point_eq(matrix.apply(point(px(0.), px(1.))), point(px(2.), px(2.)));
+    }
+
+    #[test]
+    fn test_continuous_rotation_angle() {
+        let period = Duration::from_secs(1);
+        assert_eq!(continuous_rotation_angle(Duration::ZERO, period), 0.);
+
+        let angle = continuous_rotation_angle(Duration::from_millis(50), period);
+        assert!((angle - 0.05 * TAU).abs() < 1e-4);
+
+        // The angle wraps around after each full turn.
+        let angle = continuous_rotation_angle(Duration::from_millis(10_250), period);
+        assert!((angle - 0.25 * TAU).abs() < 1e-3);
+    }
+
+    #[gpui::test]
+    fn test_external_svg_placeholder_and_fallback(cx: &mut TestAppContext) {
+        const SQUARE_SVG_URI: &str = r#"data:image/svg+xml,<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10"/></svg>"#;
+
+        struct TestView(&'static str);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                svg()
+                    .external_path(self.0)
+                    .size(px(10.))
+                    .text_color(hsla(0., 0., 0., 1.))
+                    .with_placeholder(|| {
+                        div()
+                            .size(px(5.))
+                            .bg(hsla(0.6, 1., 0.5, 1.))
+                            .into_any_element()
+                    })
+                    .with_fallback(|_| {
+                        div()
+                            .size(px(5.))
+                            .bg(hsla(0., 1., 0.5, 1.))
+                            .into_any_element()
+                    })
+            }
+        }
+
+        let paint = |path: &'static str, cx: &mut TestAppContext| {
+            let (_, cx) = cx.add_window_view(|_, _| TestView(path));
+            let loading = cx.update(|window, _| {
+                let scene = &window.rendered_frame.scene;
+                assert!(scene.monochrome_sprites.is_empty());
+                assert_eq!(scene.quads.len(), 1);
+                scene.quads[0].background.solid
+            });
+            cx.run_until_parked();
+            let loaded = cx.update(|window, _| {
+                let scene = &window.rendered_frame.scene;
+                (
+                    scene.monochrome_sprites.len(),
+                    scene.quads.first().map(|quad| quad.background.solid),
+                )
+            });
+            (loading, loaded)
+        };
+
+        let (loading, loaded) = paint(SQUARE_SVG_URI, cx);
+        assert_eq!(loading, hsla(0.6, 1., 0.5, 1.));
+        assert_eq!(
+            loaded,
+            (1, None),
+            "the SVG replaces the placeholder once loaded"
+        );
+
+        let (loading, loaded) = paint("data:image/svg+xml;base64,%%%", cx);
+        assert_eq!(loading, hsla(0.6, 1., 0.5, 1.));
+        assert_eq!(loaded, (0, Some(hsla(0., 1., 0.5, 1.))));
+    }
+
+    #[gpui::test]
+    fn test_external_svg_load_listeners(cx: &mut TestAppContext) {
+        const SQUARE_SVG_URI: &str = r#"data:image/svg+xml,<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10"/></svg>"#;
+
+        struct TestView(Rc<RefCell<Vec<Result<Size<f32>, ()>>>>);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let events = self.0.clone();
+                let listener = move |result| events.borrow_mut().push(result);
+                let on_load = listener.clone();
+                let on_error = listener;
+                div()
+                    .flex()
+                    .child(
+                        svg()
+                            .id("loaded")
+                            .external_path(SQUARE_SVG_URI)
+                            .size(px(10.))
+                            .on_load(move |size, _, _| on_load(Ok(size))),
+                    )
+                    .child(
+                        svg()
+                            .id("broken")
+                            .external_path("data:image/svg+xml;base64,%%%")
+                            .size(px(10.))
+                            .on_error(move |_, _, _| on_error(Err(()))),
+                    )
+            }
+        }
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView(events.clone()));
+        assert!(events.borrow().is_empty());
+
+        cx.run_until_parked();
+        assert_eq!(*events.borrow(), [Ok(size(10., 10.)), Err(())]);
+
+        // Later frames don't call the listeners again.
+        cx.update(|window, _| window.refresh());
+        cx.run_until_parked();
+        assert_eq!(events.borrow().len(), 2);
+    }
+
+    #[gpui::test]
+    fn test_external_svg_over_http(cx: &mut TestAppContext) {
+        struct TestView(Rc<RefCell<Vec<(&'static str, Result<Size<f32>, ImageCacheError>)>>>);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .flex()
+                    .children(["icon", "missing", "huge"].map(|name| {
+                        let on_load = self.0.clone();
+                        let on_error = self.0.clone();
+                        svg()
+                            .id(name)
+                            .external_path(format!("https://example.com/{name}.svg"))
+                            .size(px(10.))
+                            .on_load(move |size, _, _| on_load.borrow_mut().push((name, Ok(size))))
+                            .on_error(move |error, _, _| {
+                                on_error.borrow_mut().push((name, Err(error.clone())))
+                            })
+                    }))
+            }
+        }
+
+        cx.update(|cx| {
+            cx.set_http_client(http_client::FakeHttpClient::create(|request| async move {
+                let response = http_client::Response::builder();
+                Ok(match request.uri().path() {
+                    "/icon.svg" => response.status(200).body(SQUARE_SVG.into())?,
+                    "/huge.svg" => response
+                        .status(200)
+                        .body(vec![b' '; MAX_REMOTE_SVG_BYTES as usize + 1].into())?,
+                    _ => response
+                        .status(404)
+                        .body(format!("not found\n{}", "padding".repeat(1024)).into())?,
+                })
+            }))
+        });
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView(events.clone()));
+        cx.run_until_parked();
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 3);
+        for (name, result) in events.iter() {
+            match (*name, result) {
+                ("icon", Ok(size)) => assert_eq!(*size, self::size(10., 10.)),
+                ("missing", Err(ImageCacheError::BadStatus { status, body, .. })) => {
+                    assert_eq!(status.as_u16(), 404);
+                    // Only the first line of the body is kept for the error.
+                    assert_eq!(body, "not found");
+                }
+                ("huge", Err(ImageCacheError::Asset(message))) => {
+                    assert!(message.contains("exceeds"), "unexpected error: {message}");
+                }
+                (name, result) => panic!("unexpected result for {name}: {result:?}"),
+            }
+        }
+    }
+
+    #[gpui::test]
+    fn test_unused_svg_rasterizations_are_evicted(cx: &mut TestAppContext) {
+        struct TestView(Rc<Cell<usize>>);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .children((1..=self.0.get()).map(|ix| {
+                        svg()
+                            .data("square", SQUARE_SVG.as_bytes())
+                            .size(px(ix as f32 / 4.))
+                            .text_color(hsla(0., 0., 0., 1.))
+                    }))
+            }
+        }
+
+        let rendered_count = |window: &Window| {
+            window
+                .rendered_svgs
+                .values()
+                .map(|rendered_svgs| rendered_svgs.len())
+                .sum::<usize>()
+        };
+
+        // Rasterizations that are still drawn are kept, even beyond the limit.
+        let count = Rc::new(Cell::new(crate::MAX_RENDERED_SVGS + 1));
+        let (_, cx) = cx.add_window_view(|_, _| TestView(count.clone()));
+        cx.update(|window, _| assert_eq!(rendered_count(window), crate::MAX_RENDERED_SVGS + 1));
+
+        count.set(1);
+        cx.update(|window, _| window.refresh());
+        cx.run_until_parked();
+        cx.update(|window, _| {
+            assert_eq!(rendered_count(window), 1);
+            assert_eq!(window.rendered_frame.scene.monochrome_sprites.len(), 1);
+        });
+    }
+
+    #[gpui::test]
+    fn test_preloaded_svgs_are_shared_with_elements(cx: &mut TestAppContext) {
+        struct TestAssets;
+
+        impl AssetSource for TestAssets {
+            fn load(&self, path: &str) -> anyhow::Result<Option<Cow<'static, [u8]>>> {
+                Ok((path == "icons/square.svg").then(|| Cow::Borrowed(SQUARE_SVG.as_bytes())))
+            }
+
+            fn list(&self, _path: &str) -> anyhow::Result<Vec<SharedString>> {
+                Ok(Vec::new())
+            }
+        }
+
+        struct TestView(Rc<Cell<bool>>);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().when(self.0.get(), |this| {
+                    this.child(
+                        svg()
+                            .path("icons/square.svg")
+                            .size(px(10.))
+                            .text_color(hsla(0., 0., 0., 1.)),
+                    )
+                })
+            }
         }
+
+        cx.update(|cx| cx.set_asset_source(TestAssets));
+        let shown = Rc::new(Cell::new(false));
+        let (_, cx) = cx.add_window_view(|_, _| TestView(shown.clone()));
+        let rendered_paths = |window: &Window| {
+            window
+                .rendered_svgs
+                .iter()
+                .map(|(path, rendered_svgs)| (path.clone(), rendered_svgs.len()))
+                .collect::<Vec<_>>()
+        };
+
+        cx.update(|window, cx| {
+            window.preload_svgs(
+                [
+                    ("icons/square.svg".into(), size(px(10.), px(10.))),
+                    ("icons/missing.svg".into(), size(px(10.), px(10.))),
+                ],
+                cx,
+            )
+        })
+        .detach();
+        cx.run_until_parked();
+        cx.update(|window, _| {
+            assert_eq!(
+                rendered_paths(window),
+                [(SharedString::from("icons/square.svg"), 1)],
+                "SVGs that can't be loaded aren't preloaded"
+            );
+        });
+
+        // An element painted at the preloaded size shares its rasterization.
+        shown.set(true);
+        cx.update(|window, _| window.refresh());
+        cx.run_until_parked();
+        cx.update(|window, _| {
+            assert_eq!(window.rendered_frame.scene.monochrome_sprites.len(), 1);
+            assert_eq!(
+                rendered_paths(window),
+                [(SharedString::from("icons/square.svg"), 1)]
+            );
+        });
     }
 }
@@ -1,13 +1,16 @@
 use crate::{
-    ActiveTooltip, AnyView, App, Bounds, DispatchPhase, Element, ElementId, GlobalElementId,
-    HighlightStyle, Hitbox, HitboxBehavior, InspectorElementId, IntoElement, LayoutId,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, SharedString, Size, TextOverflow,
-    TextRun, TextStyle, TooltipId, WhiteSpace, Window, WrappedLine, WrappedLineLayout,
-    register_tooltip_mouse_handlers, set_tooltip_on_window,
+    ActiveTooltip, AnyElement, AnyView, App, AvailableSpace, Bounds, ClipboardItem, CursorStyle,
+    DispatchPhase, Element, ElementId, FocusHandle, GlobalElementId, HighlightStyle, Hitbox,
+    HitboxBehavior, Hsla, InspectorElementId, IntoElement, KeyContext, LayoutId, LineBreakStyle,
+    LineLayout, LineWrapper, Modifiers, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    Pixels, Point, SharedString, Size, TextAlign, TextOverflow, TextRun, TextStyle, TooltipId,
+    UnderlineStyle, WhiteSpace, Window, WrappedLine, WrappedLineLayout, actions, fill, point, px,
+    register_tooltip_mouse_handlers, set_tooltip_on_window, transparent_black,
 };
 use anyhow::Context as _;
-use smallvec::SmallVec;
+use smallvec::{SmallVec, smallvec};
 use std::{
+    any::TypeId,
     borrow::Cow,
     cell::{Cell, RefCell},
     mem,
@@ -17,9 +20,18 @@ use std::{
 };
 use util::ResultExt;
 
+actions!(
+    text,
+    [
+        /// Copies the selection of the focused [selectable](StyledText::selectable) text to the
+        /// clipboard. It's dispatched in the `SelectableText` key context.
+        CopySelectedText
+    ]
+);
+
 impl Element for &'static str {
     type RequestLayoutState = TextLayout;
-    type PrepaintState = ();
+    type PrepaintState = Option<TextSelection>;
 
     fn id(&self) -> Option<ElementId> {
         None
@@ -37,7 +49,7 @@ impl Element for &'static str {
         cx: &mut App,
     ) -> (LayoutId, Self::RequestLayoutState) {
         let mut state = TextLayout::default();
-        let layout_id = state.layout(SharedString::from(*self), None, window, cx);
+        let layout_id = state.layout(SharedString::from(*self), None, Vec::new(), window, cx);
         (layout_id, state)
     }
 
@@ -47,10 +59,11 @@ impl Element for &'static str {
         _inspector_id: Option<&InspectorElementId>,
         bounds: Bounds<Pixels>,
         text_layout: &mut Self::RequestLayoutState,
-        _window: &mut Window,
-        _cx: &mut App,
-    ) {
-        text_layout.prepaint(bounds, self)
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<TextSelection> {
+        text_layout.prepaint(bounds, self, window);
+        TextSelection::for_plain_text(SharedString::from(*self), bounds, window, cx)
     }
 
     fn paint(
@@ -59,10 +72,13 @@ impl Element for &'static str {
         _inspector_id: Option<&InspectorElementId>,
         _bounds: Bounds<Pixels>,
         text_layout: &mut TextLayout,
-        _: &mut (),
+        selection: &mut Option<TextSelection>,
         window: &mut Window,
         cx: &mut App,
     ) {
+        if let Some(selection) = selection.take() {
+            selection.paint(text_layout, window);
+        }
         text_layout.paint(self, window, cx)
     }
 }
@@ -85,7 +101,7 @@ impl IntoElement for String {
 
 impl Element for SharedString {
     type RequestLayoutState = TextLayout;
-    type PrepaintState = ();
+    type PrepaintState = Option<TextSelection>;
 
     fn id(&self) -> Option<ElementId> {
         None
@@ -103,7 +119,7 @@ impl Element for SharedString {
         cx: &mut App,
     ) -> (LayoutId, Self::RequestLayoutState) {
         let mut state = TextLayout::default();
-        let layout_id = state.layout(self.clone(), None, window, cx);
+        let layout_id = state.layout(self.clone(), None, Vec::new(), window, cx);
         (layout_id, state)
     }
 
@@ -113,10 +129,11 @@ impl Element for SharedString {
         _inspector_id: Option<&InspectorElementId>,
         bounds: Bounds<Pixels>,
         text_layout: &mut Self::RequestLayoutState,
-        _window: &mut Window,
-        _cx: &mut App,
-    ) {
-        text_layout.prepaint(bounds, self.as_ref())
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<TextSelection> {
+        text_layout.prepaint(bounds, self.as_ref(), window);
+        TextSelection::for_plain_text(self.clone(), bounds, window, cx)
     }
 
     fn paint(
@@ -125,10 +142,13 @@ impl Element for SharedString {
         _inspector_id: Option<&InspectorElementId>,
         _bounds: Bounds<Pixels>,
         text_layout: &mut Self::RequestLayoutState,
-        _: &mut Self::PrepaintState,
+        selection: &mut Self::PrepaintState,
         window: &mut Window,
         cx: &mut App,
     ) {
+        if let Some(selection) = selection.take() {
+            selection.paint(text_layout, window);
+        }
         text_layout.paint(self.as_ref(), window, cx)
     }
 }
@@ -150,7 +170,62 @@ pub struct StyledText {
     text: SharedString,
     runs: Option<Vec<TextRun>>,
     delayed_highlights: Option<Vec<(Range<usize>, HighlightStyle)>>,
+    background_highlights: Vec<(Range<usize>, Hsla)>,
     layout: TextLayout,
+    links: Option<TextLinks>,
+    link_click_listener: Option<Rc<dyn Fn(&SharedString, Modifiers, &mut Window, &mut App)>>,
+    selection: Option<TextSelection>,
+    inline_elements: Vec<InlineElement>,
+}
+
+/// How an element embedded in a [`StyledText`] is aligned vertically within its line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InlineAlignment {
+    /// Align the bottom of the element with the baseline of the text, like a glyph.
+    #[default]
+    Baseline,
+    /// Center the element within the line.
+    Center,
+    /// Align the top of the element with the top of the line.
+    Top,
+}
+
+struct InlineElement {
+    range: Range<usize>,
+    alignment: InlineAlignment,
+    element: AnyElement,
+    size: Size<Pixels>,
+}
+
+struct TextLinks {
+    id: ElementId,
+    urls: Rc<[SharedString]>,
+    ranges: Vec<Range<usize>>,
+    hitboxes: Rc<[LinkHitbox]>,
+}
+
+/// The selection of a selectable text between prepaint and paint.
+pub struct TextSelection {
+    id: ElementId,
+    state: Option<Rc<RefCell<TextSelectionState>>>,
+    hitbox: Option<Hitbox>,
+}
+
+/// The selection of a selectable text, kept in element state across frames.
+struct TextSelectionState {
+    focus_handle: FocusHandle,
+    /// Where the selection started, which stays put when it's extended.
+    anchor: usize,
+    /// Where the selection ends, following the mouse while dragging.
+    head: usize,
+    dragging: bool,
+}
+
+/// The hitbox of one visual line of a link.
+struct LinkHitbox {
+    link_ix: usize,
+    hitbox: Hitbox,
+    underline_offset: Pixels,
 }
 
 impl StyledText {
@@ -160,7 +235,12 @@ impl StyledText {
             text: text.into(),
             runs: None,
             delayed_highlights: None,
+            background_highlights: Vec::new(),
             layout: TextLayout::default(),
+            links: None,
+            link_click_listener: None,
+            selection: None,
+            inline_elements: Vec::new(),
         }
     }
 
@@ -233,6 +313,113 @@ impl StyledText {
         runs
     }
 
+    /// Paint the given ranges of text with background colors, behind the glyphs and independently
+    /// of the text runs, e.g. to highlight search matches.
+    ///
+    /// Ranges are painted in order, so where they overlap a later range paints over an earlier
+    /// one, blending with it if its color is translucent. Overlapping or adjacent neighbors of the
+    /// same color are merged first so they don't blend with each other.
+    pub fn with_background_highlights(mut self, highlights: Vec<(Range<usize>, Hsla)>) -> Self {
+        let mut merged_highlights: Vec<(Range<usize>, Hsla)> = Vec::with_capacity(highlights.len());
+        for (range, color) in highlights {
+            debug_assert!(self.text.is_char_boundary(range.start));
+            debug_assert!(self.text.is_char_boundary(range.end));
+            if let Some((previous_range, previous_color)) = merged_highlights.last_mut()
+                && *previous_color == color
+                && range.start <= previous_range.end
+                && previous_range.start <= range.end
+            {
+                previous_range.start = previous_range.start.min(range.start);
+                previous_range.end = previous_range.end.max(range.end);
+            } else {
+                merged_highlights.push((range, color));
+            }
+        }
+        self.background_highlights = merged_highlights;
+        self
+    }
+
+    /// Make the given ranges of text links to the paired URLs. A link is underlined while it's
+    /// hovered, shows a pointing hand cursor over its glyphs, and calls the
+    /// [`on_link_click`](Self::on_link_click) listener when clicked.
+    ///
+    /// Whether the mouse was pressed on a link is tracked across frames using the location of
+    /// the call to this method, so give each linked text its own parent element id when
+    /// rendering several of them from the same location.
+    #[track_caller]
+    pub fn with_links(mut self, links: Vec<(Range<usize>, SharedString)>) -> Self {
+        let (ranges, urls): (Vec<_>, Vec<_>) = links
+            .into_iter()
+            .inspect(|(range, _)| {
+                debug_assert!(self.text.is_char_boundary(range.start));
+                debug_assert!(self.text.is_char_boundary(range.end));
+            })
+            .unzip();
+        self.links = Some(TextLinks {
+            id: ElementId::CodeLocation(*core::panic::Location::caller()),
+            urls: urls.into(),
+            ranges,
+            hitboxes: Rc::from([]),
+        });
+        self
+    }
+
+    /// Set the listener for clicks on the links set with [`with_links`](Self::with_links). It's
+    /// called with the URL of the clicked link and the modifiers held when it was clicked, and can
+    /// be set before or after the links.
+    pub fn on_link_click(
+        mut self,
+        listener: impl Fn(&SharedString, Modifiers, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.link_click_listener = Some(Rc::new(listener));
+        self
+    }
+
+    /// Embed an element in the text in place of the given range, which is usually a single
+    /// object replacement character (`\u{FFFC}`). The text in the range isn't painted, and is
+    /// instead laid out as wide as the element, wrapping with the rest of the line like a glyph.
+    /// Elements whose range is truncated away aren't painted.
+    pub fn with_inline_element(
+        mut self,
+        range: Range<usize>,
+        alignment: InlineAlignment,
+        element: impl IntoElement,
+    ) -> Self {
+        debug_assert!(self.text.is_char_boundary(range.start));
+        debug_assert!(self.text.is_char_boundary(range.end));
+        debug_assert!(
+            self.inline_elements
+                .iter()
+                .all(|inline_element| inline_element.range.end <= range.start
+                    || range.end <= inline_element.range.start),
+            "inline elements can't overlap"
+        );
+        self.inline_elements.push(InlineElement {
+            range,
+            alignment,
+            element: element.into_any_element(),
+            size: Size::default(),
+        });
+        self
+    }
+
+    /// Let the user select this text: dragging over it selects a range, shift-click extends the
+    /// selection and double-click selects a word. The selection is painted with the text
+    /// style's [`selection_background_color`](TextStyle::selection_background_color), and
+    /// copied to the clipboard by the [`CopySelectedText`] action while the text is focused.
+    /// Bind it to the platform's copy keystroke in the `SelectableText` key context.
+    ///
+    /// Like [`with_links`](Self::with_links), the selection is tracked across frames using the
+    /// location of the call to this method. Plain strings and styled text without a call to this
+    /// method can be made selectable with [`Styled::text_selectable`](crate::Styled::text_selectable).
+    #[track_caller]
+    pub fn selectable(mut self) -> Self {
+        self.selection = Some(TextSelection::new(ElementId::CodeLocation(
+            *core::panic::Location::caller(),
+        )));
+        self
+    }
+
     /// Set the text runs for this piece of text.
     pub fn with_runs(mut self, runs: Vec<TextRun>) -> Self {
         let mut text = &**self.text;
@@ -270,7 +457,23 @@ impl Element for StyledText {
             })
         });
 
-        let layout_id = self.layout.layout(self.text.clone(), runs, window, cx);
+        self.inline_elements
+            .sort_by_key(|inline_element| inline_element.range.start);
+        for inline_element in &mut self.inline_elements {
+            inline_element.size =
+                inline_element
+                    .element
+                    .layout_as_root(AvailableSpace::min_size(), window, cx);
+        }
+        let inline_boxes = self
+            .inline_elements
+            .iter()
+            .map(|inline_element| (inline_element.range.clone(), inline_element.size.width))
+            .collect();
+
+        let layout_id = self
+            .layout
+            .layout(self.text.clone(), runs, inline_boxes, window, cx);
         (layout_id, ())
     }
 
@@ -280,10 +483,46 @@ impl Element for StyledText {
         _inspector_id: Option<&InspectorElementId>,
         bounds: Bounds<Pixels>,
         _: &mut Self::RequestLayoutState,
-        _window: &mut Window,
-        _cx: &mut App,
+        window: &mut Window,
+        cx: &mut App,
     ) {
-        self.layout.prepaint(bounds, &self.text)
+        self.layout.prepaint(bounds, &self.text, window);
+        if self.selection.is_none() && window.text_style().selectable {
+            self.selection = Some(TextSelection::new(ElementId::Name(self.text.clone())));
+        }
+        // The selection's hitbox goes under those of inline elements, so they stay interactive.
+        if let Some(selection) = self.selection.as_mut() {
+            selection.prepaint(bounds, window, cx);
+        }
+        let len = self.layout.len();
+        self.inline_elements.retain_mut(|inline_element| {
+            let origin = if inline_element.range.end <= len {
+                self.layout.inline_element_origin(
+                    inline_element.range.clone(),
+                    inline_element.size,
+                    inline_element.alignment,
+                )
+            } else {
+                None
+            };
+            if let Some(origin) = origin {
+                inline_element.element.prepaint_at(origin, window, cx);
+            }
+            origin.is_some()
+        });
+        if let Some(links) = self.links.as_mut() {
+            let mut hitboxes = Vec::new();
+            for (link_ix, range) in links.ranges.iter().enumerate() {
+                for (bounds, underline_offset) in self.layout.fragments_for_range(range.clone()) {
+                    hitboxes.push(LinkHitbox {
+                        link_ix,
+                        hitbox: window.insert_hitbox(bounds, HitboxBehavior::Normal),
+                        underline_offset,
+                    });
+                }
+            }
+            links.hitboxes = hitboxes.into();
+        }
     }
 
     fn paint(
@@ -296,7 +535,433 @@ impl Element for StyledText {
         window: &mut Window,
         cx: &mut App,
     ) {
-        self.layout.paint(&self.text, window, cx)
+        for (range, color) in mem::take(&mut self.background_highlights) {
+            for (bounds, _) in self.layout.fragments_for_range(range) {
+                window.paint_quad(fill(bounds, color));
+            }
+        }
+        if let Some(selection) = self.selection.take() {
+            selection.paint(&self.layout, window);
+        }
+        self.layout.paint(&self.text, window, cx);
+        for inline_element in &mut self.inline_elements {
+            inline_element.element.paint(window, cx);
+        }
+        if let Some(links) = self.links.take() {
+            links.paint(self.link_click_listener.clone(), window);
+        }
+    }
+}
+
+impl TextSelection {
+    fn new(id: ElementId) -> Self {
+        Self {
+            id,
+            state: None,
+            hitbox: None,
+        }
+    }
+
+    /// The selection of a plain string, if its text style is selectable. Plain strings have no
+    /// element id, so the selection is tracked across frames by the text itself.
+    fn for_plain_text(
+        text: SharedString,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<Self> {
+        if !window.text_style().selectable {
+            return None;
+        }
+        let mut selection = Self::new(ElementId::Name(text));
+        selection.prepaint(bounds, window, cx);
+        Some(selection)
+    }
+
+    fn prepaint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.hitbox = Some(window.insert_hitbox(bounds, HitboxBehavior::Normal));
+        let state = window.with_global_id(self.id.clone(), |global_id, window| {
+            window.with_element_state::<Rc<RefCell<TextSelectionState>>, _>(
+                global_id,
+                |state, _| {
+                    let state = state.unwrap_or_else(|| {
+                        Rc::new(RefCell::new(TextSelectionState {
+                            focus_handle: cx.focus_handle(),
+                            anchor: 0,
+                            head: 0,
+                            dragging: false,
+                        }))
+                    });
+                    (state.clone(), state)
+                },
+            )
+        });
+        window.set_focus_handle(&state.borrow().focus_handle, cx);
+        self.state = Some(state);
+    }
+
+    fn paint(self, layout: &TextLayout, window: &mut Window) {
+        let (Some(state), Some(hitbox)) = (self.state, self.hitbox) else {
+            return;
+        };
+
+        let selected_range = state.borrow().range(layout.len());
+        if !selected_range.is_empty() {
+            let color = window.text_style().selection_background_color;
+            for (bounds, _) in layout.fragments_for_range(selected_range) {
+                window.paint_quad(fill(bounds, color));
+            }
+        }
+        window.set_cursor_style(CursorStyle::IBeam, &hitbox);
+
+        window.on_mouse_event({
+            let state = state.clone();
+            let layout = layout.clone();
+            move |event: &MouseDownEvent, phase, window, _| {
+                if event.button != MouseButton::Left {
+                    return;
+                }
+                let mut state = state.borrow_mut();
+                if !hitbox.is_hovered(window) {
+                    // Clicking anywhere else clears the selection.
+                    if phase == DispatchPhase::Capture && state.anchor != state.head {
+                        state.anchor = state.head;
+                        window.refresh();
+                    }
+                    return;
+                }
+                if phase != DispatchPhase::Bubble {
+                    return;
+                }
+
+                let index = layout
+                    .closest_index_for_position(event.position)
+                    .unwrap_or_else(|index| index);
+                if event.click_count == 2 {
+                    let word = word_range_at(&layout.text(), index);
+                    state.anchor = word.start;
+                    state.head = word.end;
+                } else {
+                    if !event.modifiers.shift {
+                        state.anchor = index;
+                    }
+                    state.head = index;
+                    state.dragging = true;
+                }
+                window.focus(&state.focus_handle);
+                window.refresh();
+            }
+        });
+        window.on_mouse_event({
+            let state = state.clone();
+            let layout = layout.clone();
+            move |event: &MouseMoveEvent, phase, window, _| {
+                let mut state = state.borrow_mut();
+                if phase == DispatchPhase::Bubble
+                    && state.dragging
+                    && event.pressed_button == Some(MouseButton::Left)
+                {
+                    let index = layout
+                        .closest_index_for_position(event.position)
+                        .unwrap_or_else(|index| index);
+                    if index != state.head {
+                        state.head = index;
+                        window.refresh();
+                    }
+                }
+            }
+        });
+        window.on_mouse_event({
+            let state = state.clone();
+            move |event: &MouseUpEvent, phase, _, _| {
+                if phase == DispatchPhase::Bubble && event.button == MouseButton::Left {
+                    state.borrow_mut().dragging = false;
+                }
+            }
+        });
+
+        let mut context = KeyContext::default();
+        context.add("SelectableText");
+        window.set_key_context(context);
+        let layout = layout.clone();
+        window.on_action(TypeId::of::<CopySelectedText>(), move |_, phase, _, cx| {
+            if phase != DispatchPhase::Bubble {
+                return;
+            }
+            let state = state.borrow();
+            let text = layout.text();
+            if let Some(selected_text) = text.get(state.range(text.len()))
+                && !selected_text.is_empty()
+            {
+                cx.write_to_clipboard(ClipboardItem::new_string(selected_text.to_string()));
+            } else {
+                cx.propagate();
+            }
+        });
+    }
+}
+
+impl TextSelectionState {
+    /// The selected range, clamped to the given text length in case the text got shorter.
+    fn range(&self, len: usize) -> Range<usize> {
+        self.anchor.min(self.head).min(len)..self.anchor.max(self.head).min(len)
+    }
+}
+
+/// Split the runs so that the text under inline elements is transparent and undecorated.
+fn hide_text_under_inline_boxes(
+    runs: Vec<TextRun>,
+    inline_boxes: &[(Range<usize>, Pixels)],
+) -> Vec<TextRun> {
+    let mut hidden_runs = Vec::with_capacity(runs.len() + inline_boxes.len() * 2);
+    let mut run_start = 0;
+    for run in runs {
+        let run_end = run_start + run.len;
+        let mut ix = run_start;
+        for (range, _) in inline_boxes
+            .iter()
+            .filter(|(range, _)| range.start < run_end && range.end > run_start)
+        {
+            let hidden_start = range.start.max(ix);
+            let hidden_end = range.end.min(run_end);
+            if ix < hidden_start {
+                hidden_runs.push(TextRun {
+                    len: hidden_start - ix,
+                    ..run.clone()
+                });
+            }
+            hidden_runs.push(TextRun {
+                len: hidden_end - hidden_start,
+                color: transparent_black(),
+                background_color: None,
+                underline: None,
+                strikethrough: None,
+                ..run.clone()
+            });
+            ix = hidden_end;
+        }
+        if ix < run_end {
+            hidden_runs.push(TextRun {
+                len: run_end - ix,
+                ..run
+            });
+        }
+        run_start = run_end;
+    }
+    hidden_runs
+}
+
+/// Make the text under each inline element on a line as wide as the element, then wrap the line.
+/// Words broken on such lines aren't hyphenated.
+fn reserve_inline_boxes(
+    line: WrappedLine,
+    line_start_ix: usize,
+    inline_boxes: &[(Range<usize>, Pixels)],
+    wrap_width: Option<Pixels>,
+    line_break: LineBreakStyle,
+) -> WrappedLine {
+    let unwrapped_layout = &line.layout.unwrapped_layout;
+    let line_end_ix = line_start_ix + line.len();
+
+    // The range of each box within the line, where it starts and the offset it adds to
+    // everything after it.
+    let mut offset = px(0.);
+    let shifts = inline_boxes
+        .iter()
+        .filter(|(range, _)| range.start >= line_start_ix && range.end <= line_end_ix)
+        .map(|(range, width)| {
+            let start_ix = range.start - line_start_ix;
+            let end_ix = range.end - line_start_ix;
+            let start_x = unwrapped_layout.x_for_index(start_ix);
+            let text_width = unwrapped_layout.x_for_index(end_ix) - start_x;
+            let shifted_start_x = start_x + offset;
+            offset += *width - text_width;
+            (start_ix..end_ix, shifted_start_x, offset)
+        })
+        .collect::<SmallVec<[_; 4]>>();
+
+    let mut runs = unwrapped_layout.runs.clone();
+    for glyph in runs.iter_mut().flat_map(|run| run.glyphs.iter_mut()) {
+        glyph.position.x = match shifts
+            .iter()
+            .rev()
+            .find(|(range, _, _)| range.start <= glyph.index)
+        {
+            Some((range, start_x, _)) if glyph.index < range.end => *start_x,
+            Some((_, _, offset)) => glyph.position.x + *offset,
+            None => glyph.position.x,
+        };
+    }
+    let unwrapped_layout = Arc::new(LineLayout {
+        font_size: unwrapped_layout.font_size,
+        width: unwrapped_layout.width + offset,
+        ascent: unwrapped_layout.ascent,
+        descent: unwrapped_layout.descent,
+        runs,
+        len: unwrapped_layout.len,
+        rtl: unwrapped_layout.rtl,
+        bidi_runs: unwrapped_layout.bidi_runs.clone(),
+    });
+    let line_break = LineBreakStyle {
+        hyphenate: false,
+        ..line_break
+    };
+    let wrap_boundaries = wrap_width
+        .map(|wrap_width| {
+            unwrapped_layout.compute_wrap_boundaries(
+                &line.text,
+                wrap_width,
+                None,
+                line_break,
+                px(0.),
+            )
+        })
+        .unwrap_or_default();
+
+    WrappedLine {
+        layout: Arc::new(WrappedLineLayout {
+            unwrapped_layout,
+            wrap_boundaries,
+            wrap_width,
+            hyphen: None,
+        }),
+        text: line.text,
+        decoration_runs: line.decoration_runs,
+    }
+}
+
+/// The byte ranges of the visual rows of shaped text, across both hard and soft line breaks.
+pub(crate) fn visual_rows(lines: &[WrappedLine]) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut line_start_ix = 0;
+    lines.iter().flat_map(move |line| {
+        let start_ix = line_start_ix;
+        line_start_ix += line.len() + 1;
+        let unwrapped_layout = &line.layout.unwrapped_layout;
+        let mut row_start_ix = 0;
+        line.wrap_boundaries
+            .iter()
+            .map(move |boundary| {
+                unwrapped_layout.runs[boundary.run_ix].glyphs[boundary.glyph_ix].index
+            })
+            .chain([line.len()])
+            .map(move |row_end_ix| {
+                let row = start_ix + row_start_ix..start_ix + row_end_ix;
+                row_start_ix = row_end_ix;
+                row
+            })
+    })
+}
+
+/// The range of the run of word characters, whitespace or punctuation around the given index.
+fn word_range_at(text: &str, index: usize) -> Range<usize> {
+    let char_kind = |c: char| (LineWrapper::is_word_char(c), c.is_whitespace());
+    let (Some(before), Some(after)) = (text.get(..index), text.get(index..)) else {
+        return index..index;
+    };
+    let Some(kind) = after
+        .chars()
+        .next()
+        .or_else(|| before.chars().next_back())
+        .map(char_kind)
+    else {
+        return index..index;
+    };
+
+    let start = before
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| char_kind(*c) == kind)
+        .last()
+        .map_or(index, |(ix, _)| ix);
+    let end = after
+        .char_indices()
+        .take_while(|(_, c)| char_kind(*c) == kind)
+        .last()
+        .map_or(index, |(ix, c)| index + ix + c.len_utf8());
+    start..end
+}
+
+impl TextLinks {
+    fn hovered_link(hitboxes: &[LinkHitbox], window: &Window) -> Option<usize> {
+        hitboxes
+            .iter()
+            .find(|link_hitbox| link_hitbox.hitbox.is_hovered(window))
+            .map(|link_hitbox| link_hitbox.link_ix)
+    }
+
+    fn paint(
+        self,
+        click_listener: Option<Rc<dyn Fn(&SharedString, Modifiers, &mut Window, &mut App)>>,
+        window: &mut Window,
+    ) {
+        let hovered_link = Self::hovered_link(&self.hitboxes, window);
+        let underline = UnderlineStyle {
+            thickness: px(1.),
+            color: Some(window.text_style().color),
+            wavy: false,
+            ..Default::default()
+        };
+        for link_hitbox in self.hitboxes.iter() {
+            let bounds = link_hitbox.hitbox.bounds;
+            window.set_cursor_style(CursorStyle::PointingHand, &link_hitbox.hitbox);
+            if hovered_link == Some(link_hitbox.link_ix) {
+                window.paint_underline(
+                    point(bounds.left(), bounds.top() + link_hitbox.underline_offset),
+                    bounds.size.width,
+                    &underline,
+                );
+            }
+        }
+
+        let hitboxes = self.hitboxes;
+        let urls = self.urls;
+        window.with_global_id(self.id, |global_id, window| {
+            window.with_element_state::<Rc<Cell<Option<usize>>>, _>(
+                global_id,
+                |pressed_link, window| {
+                    let pressed_link = pressed_link.unwrap_or_default();
+
+                    window.on_mouse_event({
+                        let hitboxes = hitboxes.clone();
+                        move |_: &MouseMoveEvent, phase, window, _| {
+                            if phase == DispatchPhase::Bubble
+                                && Self::hovered_link(&hitboxes, window) != hovered_link
+                            {
+                                window.refresh();
+                            }
+                        }
+                    });
+
+                    if let Some(click_listener) = click_listener {
+                        window.on_mouse_event({
+                            let hitboxes = hitboxes.clone();
+                            let pressed_link = pressed_link.clone();
+                            move |event: &MouseDownEvent, phase, window, _| {
+                                if phase == DispatchPhase::Bubble
+                                    && event.button == MouseButton::Left
+                                {
+                                    pressed_link.set(Self::hovered_link(&hitboxes, window));
+                                }
+                            }
+                        });
+                        window.on_mouse_event({
+                            let pressed_link = pressed_link.clone();
+                            move |event: &MouseUpEvent, phase, window, cx| {
+                                if phase == DispatchPhase::Bubble
+                                    && event.button == MouseButton::Left
+                                    && let Some(link_ix) = pressed_link.take()
+                                    && Self::hovered_link(&hitboxes, window) == Some(link_ix)
+                                {
+                                    click_listener(&urls[link_ix], event.modifiers, window, cx);
+                                }
+                            }
+                        });
+                    }
+
+                    ((), pressed_link)
+                },
+            )
+        });
     }
 }
 
@@ -317,8 +982,11 @@ struct TextLayoutInner {
     lines: SmallVec<[WrappedLine; 1]>,
     line_height: Pixels,
     wrap_width: Option<Pixels>,
+    max_lines: Option<usize>,
     size: Option<Size<Pixels>>,
     bounds: Option<Bounds<Pixels>>,
+    text_align: TextAlign,
+    truncated: bool,
 }
 
 impl TextLayout {
@@ -326,6 +994,7 @@ impl TextLayout {
         &self,
         text: SharedString,
         runs: Option<Vec<TextRun>>,
+        inline_boxes: Vec<(Range<usize>, Pixels)>,
         window: &mut Window,
         _: &mut App,
     ) -> LayoutId {
@@ -340,74 +1009,152 @@ impl TextLayout {
         } else {
             vec![text_style.to_run(text.len())]
         };
+        let runs = if inline_boxes.is_empty() {
+            runs
+        } else {
+            hide_text_under_inline_boxes(runs, &inline_boxes)
+        };
         window.request_measured_layout(Default::default(), {
             let element_state = self.clone();
 
             move |known_dimensions, available_space, window, cx| {
+                let available_width = known_dimensions.width.or(match available_space.width {
+                    crate::AvailableSpace::Definite(x) => Some(x),
+                    _ => None,
+                });
                 let wrap_width = if text_style.white_space == WhiteSpace::Normal {
-                    known_dimensions.width.or(match available_space.width {
-                        crate::AvailableSpace::Definite(x) => Some(x),
-                        _ => None,
-                    })
+                    available_width
                 } else {
                     None
                 };
 
-                let (truncate_width, truncation_suffix) =
-                    if let Some(text_overflow) = text_style.text_overflow.clone() {
-                        let width = known_dimensions.width.or(match available_space.width {
-                            crate::AvailableSpace::Definite(x) => match text_style.line_clamp {
-                                Some(max_lines) => Some(x * max_lines),
-                                None => Some(x),
-                            },
-                            _ => None,
-                        });
-
-                        match text_overflow {
-                            TextOverflow::Truncate(s) => (width, s),
+                // Whichever is smaller of the line clamp and the number of lines that fit in a
+                // definite height wins.
+                let available_height = known_dimensions.height.or(match available_space.height {
+                    crate::AvailableSpace::Definite(y) => Some(y),
+                    _ => None,
+                });
+                let max_lines = text_style
+                    .line_clamp
+                    .map(|max_lines| match available_height {
+                        Some(height) if line_height > Pixels::ZERO => {
+                            max_lines.min(((height / line_height).floor() as usize).max(1))
                         }
-                    } else {
-                        (None, "".into())
+                        _ => max_lines,
+                    })
+                    .filter(|max_lines| *max_lines > 0);
+
+                // Line clamped text is truncated at the end of its last visible line once it has
+                // been wrapped, rather than to a width up front.
+                let (truncate_width, truncation_suffix) =
+                    match (text_style.text_overflow.clone(), max_lines) {
+                        (Some(TextOverflow::Truncate(s)), None) => (available_width, s),
+                        (Some(TextOverflow::Truncate(s)), Some(_)) => (None, s),
+                        (None, Some(_)) => (None, SharedString::new_static("…")),
+                        (None, None) => (None, "".into()),
                     };
 
                 if let Some(text_layout) = element_state.0.borrow().as_ref()
                     && text_layout.size.is_some()
                     && (wrap_width.is_none() || wrap_width == text_layout.wrap_width)
+                    && max_lines == text_layout.max_lines
                 {
                     return text_layout.size.unwrap();
                 }
 
                 let mut line_wrapper = cx.text_system().line_wrapper(text_style.font(), font_size);
-                let (text, runs) = if let Some(truncate_width) = truncate_width {
+                let (mut shaped_text, runs) = if let Some(truncate_width) = truncate_width {
                     line_wrapper.truncate_line(
                         text.clone(),
                         truncate_width,
                         &truncation_suffix,
+                        text_style.truncation_style,
                         &runs,
                     )
                 } else {
                     (text.clone(), Cow::Borrowed(&*runs))
                 };
-                let len = text.len();
+                let mut truncated = shaped_text != text;
+
+                let shape_text =
+                    |text: SharedString, runs: &[TextRun], line_clamp: Option<usize>| {
+                        if inline_boxes.is_empty() {
+                            return window
+                                .text_system()
+                                .shape_text(
+                                    text,
+                                    font_size,
+                                    runs,
+                                    text_style.direction,
+                                    wrap_width,
+                                    text_style.line_break_style(),
+                                    line_clamp,
+                                )
+                                .log_err();
+                        }
 
-                let Some(lines) = window
-                    .text_system()
-                    .shape_text(
-                        text,
-                        font_size,
+                        // Lines with inline elements are wrapped once the elements' widths have
+                        // been reserved in them.
+                        let lines = window
+                            .text_system()
+                            .shape_text(
+                                text,
+                                font_size,
+                                runs,
+                                text_style.direction,
+                                None,
+                                LineBreakStyle::default(),
+                                None,
+                            )
+                            .log_err()?;
+                        let mut line_start_ix = 0;
+                        Some(
+                            lines
+                                .into_iter()
+                                .map(|line| {
+                                    let start_ix = line_start_ix;
+                                    line_start_ix += line.len() + 1;
+                                    reserve_inline_boxes(
+                                        line,
+                                        start_ix,
+                                        &inline_boxes,
+                                        wrap_width,
+                                        text_style.line_break_style(),
+                                    )
+                                })
+                                .collect(),
+                        )
+                    };
+                let mut lines = shape_text(shaped_text.clone(), &runs, None);
+                if let Some(max_lines) = max_lines
+                    && let Some(shaped_lines) = lines.as_ref()
+                    && visual_rows(shaped_lines).nth(max_lines).is_some()
+                    && let Some(last_row) = visual_rows(shaped_lines).nth(max_lines - 1)
+                {
+                    let (clamped_text, clamped_runs) = line_wrapper.truncate_clamped_text(
+                        &shaped_text,
+                        last_row,
+                        wrap_width.or(available_width),
+                        &truncation_suffix,
                         &runs,
-                        wrap_width,            // Wrap if we know the width.
-                        text_style.line_clamp, // Limit the number of lines if line_clamp is set.
-                    )
-                    .log_err()
-                else {
+                    );
+                    lines = shape_text(clamped_text.clone(), &clamped_runs, Some(max_lines));
+                    shaped_text = clamped_text;
+                    truncated = true;
+                }
+                let len = shaped_text.len();
+
+                let Some(lines) = lines else {
                     element_state.0.borrow_mut().replace(TextLayoutInner {
                         lines: Default::default(),
                         len: 0,
                         line_height,
                         wrap_width,
+                        max_lines,
                         size: Some(Size::default()),
                         bounds: None,
+                        text_align: TextAlign::default(),
+                        truncated,
                     });
                     return Size::default();
                 };
@@ -424,8 +1171,11 @@ impl TextLayout {
                     len,
                     line_height,
                     wrap_width,
+                    max_lines,
                     size: Some(size),
                     bounds: None,
+                    text_align: TextAlign::default(),
+                    truncated,
                 });
 
                 size
@@ -433,13 +1183,14 @@ impl TextLayout {
         })
     }
 
-    fn prepaint(&self, bounds: Bounds<Pixels>, text: &str) {
+    fn prepaint(&self, bounds: Bounds<Pixels>, text: &str, window: &Window) {
         let mut element_state = self.0.borrow_mut();
         let element_state = element_state
             .as_mut()
             .with_context(|| format!("measurement has not been performed on {text}"))
             .unwrap();
         element_state.bounds = Some(bounds);
+        element_state.text_align = window.text_style().text_align;
     }
 
     fn paint(&self, text: &str, window: &mut Window, cx: &mut App) {
@@ -455,12 +1206,11 @@ impl TextLayout {
 
         let line_height = element_state.line_height;
         let mut line_origin = bounds.origin;
-        let text_style = window.text_style();
         for line in &element_state.lines {
             line.paint_background(
                 line_origin,
                 line_height,
-                text_style.text_align,
+                element_state.text_align,
                 Some(bounds),
                 window,
                 cx,
@@ -469,7 +1219,7 @@ impl TextLayout {
             line.paint(
                 line_origin,
                 line_height,
-                text_style.text_align,
+                element_state.text_align,
                 Some(bounds),
                 window,
                 cx,
@@ -479,8 +1229,86 @@ impl TextLayout {
         }
     }
 
+    /// The bounds of each visual line of the given range of text where it's painted, along with
+    /// the offset of the underline from the top of those bounds.
+    fn fragments_for_range(&self, range: Range<usize>) -> SmallVec<[(Bounds<Pixels>, Pixels); 1]> {
+        let mut fragments = SmallVec::new();
+        let element_state = self.0.borrow();
+        let Some(element_state) = element_state.as_ref() else {
+            return fragments;
+        };
+        let Some(bounds) = element_state.bounds else {
+            return fragments;
+        };
+
+        let line_height = element_state.line_height;
+        let mut line_origin = bounds.origin;
+        let mut line_start_ix = 0;
+        for line in &element_state.lines {
+            let unwrapped_layout = &line.layout.unwrapped_layout;
+            let padding_top = (line_height - line.ascent() - line.descent()) / 2.;
+            let underline_offset = padding_top + line.ascent() + line.descent() * 0.618;
+            let row_ends = line
+                .wrap_boundaries
+                .iter()
+                .map(|boundary| {
+                    unwrapped_layout.runs[boundary.run_ix].glyphs[boundary.glyph_ix].index
+                })
+                .chain([line.len()]);
+            let row_offsets = line.aligned_row_offsets(element_state.text_align, bounds.size.width);
+            // Rows with right-to-left text are painted in visual order, in which a logical range
+            // can be split into several spans.
+            let visual_lines = unwrapped_layout
+                .is_bidi()
+                .then(|| unwrapped_layout.visual_lines(&line.wrap_boundaries));
+            let mut row_start_ix = 0;
+            for (row_ix, row_end_ix) in row_ends.enumerate() {
+                let start_ix = range.start.saturating_sub(line_start_ix).max(row_start_ix);
+                let end_ix = range.end.saturating_sub(line_start_ix).min(row_end_ix);
+                if start_ix < end_ix {
+                    let row_origin =
+                        line_origin + point(row_offsets[row_ix], line_height * row_ix as f32);
+                    let spans = if let Some(visual_line) =
+                        visual_lines.as_ref().and_then(|lines| lines.get(row_ix))
+                    {
+                        visual_line.spans_for_range(start_ix..end_ix)
+                    } else {
+                        let row_start_x = unwrapped_layout.x_for_index(row_start_ix);
+                        smallvec![
+                            unwrapped_layout.x_for_index(start_ix) - row_start_x
+                                ..unwrapped_layout.x_for_index(end_ix) - row_start_x
+                        ]
+                    };
+                    for span in spans {
+                        fragments.push((
+                            Bounds::from_corners(
+                                row_origin + point(span.start, px(0.)),
+                                row_origin + point(span.end, line_height),
+                            ),
+                            underline_offset,
+                        ));
+                    }
+                }
+                row_start_ix = row_end_ix;
+            }
+
+            line_origin.y += line.size(line_height).height;
+            line_start_ix += line.len() + 1;
+        }
+        fragments
+    }
+
     /// Get the byte index into the input of the pixel position.
-    pub fn index_for_position(&self, mut position: Point<Pixels>) -> Result<usize, usize> {
+    pub fn index_for_position(&self, position: Point<Pixels>) -> Result<usize, usize> {
+        self._index_for_position(position, false)
+    }
+
+    /// Get the byte index of the character boundary closest to the pixel position.
+    pub fn closest_index_for_position(&self, position: Point<Pixels>) -> Result<usize, usize> {
+        self._index_for_position(position, true)
+    }
+
+    fn _index_for_position(&self, position: Point<Pixels>, closest: bool) -> Result<usize, usize> {
         let element_state = self.0.borrow();
         let element_state = element_state
             .as_ref()
@@ -502,8 +1330,18 @@ impl TextLayout {
                 line_origin.y = line_bottom;
                 line_start_ix += line.len() + 1;
             } else {
-                let position_within_line = position - line_origin;
-                match line.index_for_position(position_within_line, line_height) {
+                let mut position_within_line = position - line_origin;
+                let row_offsets =
+                    line.aligned_row_offsets(element_state.text_align, bounds.size.width);
+                let row_ix = ((position_within_line.y / line_height).max(0.) as usize)
+                    .min(row_offsets.len() - 1);
+                position_within_line.x -= row_offsets[row_ix];
+                let index_within_line = if closest {
+                    line.closest_index_for_position(position_within_line, line_height)
+                } else {
+                    line.index_for_position(position_within_line, line_height)
+                };
+                match index_within_line {
                     Ok(index_within_line) => return Ok(line_start_ix + index_within_line),
                     Err(index_within_line) => return Err(line_start_ix + index_within_line),
                 }
@@ -537,13 +1375,39 @@ impl TextLayout {
                 continue;
             } else {
                 let ix_within_line = index - line_start_ix;
-                return Some(line_origin + line.position_for_index(ix_within_line, line_height)?);
+                let mut position = line.position_for_index(ix_within_line, line_height)?;
+                let row_offsets =
+                    line.aligned_row_offsets(element_state.text_align, bounds.size.width);
+                position.x += row_offsets[(position.y / line_height).round() as usize];
+                return Some(line_origin + position);
             }
         }
 
         None
     }
 
+    /// Where to paint an inline element of the given size embedded in place of the given range.
+    fn inline_element_origin(
+        &self,
+        range: Range<usize>,
+        size: Size<Pixels>,
+        alignment: InlineAlignment,
+    ) -> Option<Point<Pixels>> {
+        let (bounds, _) = self.fragments_for_range(range.clone()).into_iter().next()?;
+        let line_height = self.line_height();
+        let y_offset = match alignment {
+            InlineAlignment::Baseline => {
+                let line_layout = self.line_layout_for_index(range.start)?;
+                let ascent = line_layout.unwrapped_layout.ascent;
+                let descent = line_layout.unwrapped_layout.descent;
+                (line_height - ascent - descent) / 2. + ascent - size.height
+            }
+            InlineAlignment::Center => (line_height - size.height) / 2.,
+            InlineAlignment::Top => px(0.),
+        };
+        Some(point(bounds.left(), bounds.top() + y_offset))
+    }
+
     /// Retrieve the layout for the line containing the given byte index.
     pub fn line_layout_for_index(&self, index: usize) -> Option<Arc<WrappedLineLayout>> {
         let element_state = self.0.borrow();
@@ -589,6 +1453,16 @@ impl TextLayout {
         self.0.borrow().as_ref().unwrap().len
     }
 
+    /// Whether the text had to be truncated to fit, e.g. to show the full text in a tooltip.
+    ///
+    /// Returns `false` until the text has been measured.
+    pub fn was_truncated(&self) -> bool {
+        self.0
+            .borrow()
+            .as_ref()
+            .is_some_and(|element_state| element_state.truncated)
+    }
+
     /// The text for this layout.
     pub fn text(&self) -> String {
         self.0
@@ -912,3 +1786,631 @@ impl IntoElement for InteractiveText {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use crate::{
+        self as gpui, Bounds, Context, CopySelectedText, CursorStyle, InlineAlignment,
+        InteractiveElement, IntoElement, KeyBinding, LineStyle, Modifiers, MouseButton,
+        ParentElement, Pixels, Render, SharedString, Styled, StyledText, TestAppContext,
+        TextDirection, TextLayout, TextOverflow, VisualTestContext, WhiteSpace, Window, blue,
+        canvas, div, green, point, px, red, size,
+    };
+
+    #[gpui::test]
+    fn test_text_links(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+            clicked_links: Rc<RefCell<Vec<SharedString>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let clicked_links = self.clicked_links.clone();
+                let text = StyledText::new("see docs\nhere or there")
+                    .with_links(vec![(4..13, "https://zed.dev/docs".into())])
+                    .on_link_click(move |url, _, _, _| {
+                        clicked_links.borrow_mut().push(url.clone())
+                    });
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div().size(px(500.)).child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let clicked_links = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+            clicked_links: clicked_links.clone(),
+        });
+        let position_of = |ix: usize| {
+            let layout = layout.borrow();
+            let layout = layout.as_ref().unwrap();
+            layout.position_for_index(ix).unwrap() + point(px(1.), layout.line_height() / 2.)
+        };
+        let cursor = |cx: &mut gpui::VisualTestContext| {
+            cx.update(|window, _| window.rendered_frame.cursor_style(window))
+        };
+        let underline_count = |cx: &mut gpui::VisualTestContext| {
+            cx.update(|window, _| window.rendered_frame.scene.underlines.len())
+        };
+
+        // The link spans two lines, and only its glyphs are hoverable.
+        cx.simulate_mouse_move(position_of(1), None, Modifiers::none());
+        assert_ne!(cursor(cx), Some(CursorStyle::PointingHand));
+        assert_eq!(underline_count(cx), 0);
+        cx.simulate_mouse_move(position_of(10), None, Modifiers::none());
+        assert_eq!(cursor(cx), Some(CursorStyle::PointingHand));
+        assert_eq!(underline_count(cx), 2);
+        cx.simulate_mouse_move(position_of(15), None, Modifiers::none());
+        assert_ne!(cursor(cx), Some(CursorStyle::PointingHand));
+        assert_eq!(underline_count(cx), 0);
+
+        cx.simulate_click(position_of(5), Modifiers::none());
+        cx.simulate_click(position_of(15), Modifiers::none());
+        // Dragging off the link doesn't click it.
+        cx.simulate_mouse_down(position_of(9), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_up(position_of(15), MouseButton::Left, Modifiers::none());
+        assert_eq!(
+            *clicked_links.borrow(),
+            [SharedString::from("https://zed.dev/docs")]
+        );
+    }
+
+    #[gpui::test]
+    fn test_aligned_text_links(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+            clicked_links: Rc<RefCell<Vec<SharedString>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let clicked_links = self.clicked_links.clone();
+                // The listener is set before the links it handles.
+                let text = StyledText::new("see docs")
+                    .on_link_click(move |url, _, _, _| clicked_links.borrow_mut().push(url.clone()))
+                    .with_links(vec![(4..8, "https://zed.dev/docs".into())]);
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div().size(px(500.)).text_right().child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let clicked_links = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+            clicked_links: clicked_links.clone(),
+        });
+        let (bounds, link_position) = {
+            let layout = layout.borrow();
+            let layout = layout.as_ref().unwrap();
+            let bounds = layout.bounds();
+            let end = layout.position_for_index(8).unwrap();
+            assert!((end.x - bounds.right()).abs() < px(0.01));
+            assert_eq!(end.y, bounds.top());
+            assert_eq!(
+                layout.index_for_position(bounds.origin + point(px(1.), px(1.))),
+                Err(0)
+            );
+            let link_position =
+                layout.position_for_index(5).unwrap() + point(px(1.), layout.line_height() / 2.);
+            (bounds, link_position)
+        };
+
+        // The link is hoverable where it's painted, at the right edge of the text.
+        cx.simulate_mouse_move(link_position, None, Modifiers::none());
+        cx.update(|window, _| {
+            assert_eq!(
+                window.rendered_frame.cursor_style(window),
+                Some(CursorStyle::PointingHand)
+            );
+            assert!(window.rendered_frame.scene.underlines[0].bounds.left() > bounds.center().x);
+        });
+        cx.simulate_click(link_position, Modifiers::none());
+        assert_eq!(
+            *clicked_links.borrow(),
+            [SharedString::from("https://zed.dev/docs")]
+        );
+    }
+
+    #[gpui::test]
+    fn test_text_selection(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let text = StyledText::new("hello world\nsecond line").selectable();
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div().size(px(500.)).child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+        });
+        bind_copy_selected_text(cx);
+        let position_of = |ix: usize| {
+            let layout = layout.borrow();
+            let layout = layout.as_ref().unwrap();
+            layout.position_for_index(ix).unwrap() + point(px(0.), layout.line_height() / 2.)
+        };
+        let copy = |cx: &mut gpui::VisualTestContext| {
+            cx.simulate_keystrokes("secondary-c");
+            cx.read_from_clipboard().and_then(|item| item.text())
+        };
+        let highlight_count = |cx: &mut gpui::VisualTestContext| {
+            cx.update(|window, _| window.rendered_frame.scene.quads.len())
+        };
+
+        // Dragging selects a range.
+        cx.simulate_mouse_down(position_of(0), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_move(position_of(5), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_up(position_of(5), MouseButton::Left, Modifiers::none());
+        assert_eq!(highlight_count(cx), 1);
+        assert_eq!(copy(cx).as_deref(), Some("hello"));
+
+        // Shift-click extends it, here across lines.
+        cx.simulate_click(position_of(14), Modifiers::shift());
+        assert_eq!(highlight_count(cx), 2);
+        assert_eq!(copy(cx).as_deref(), Some("hello world\nse"));
+
+        // Double-click selects a word.
+        cx.simulate_clicks(position_of(15), 2, Modifiers::none());
+        assert_eq!(copy(cx).as_deref(), Some("second"));
+
+        // Clicking elsewhere clears the selection.
+        cx.simulate_click(point(px(400.), px(400.)), Modifiers::none());
+        assert_eq!(highlight_count(cx), 0);
+    }
+
+    fn bind_copy_selected_text(cx: &mut VisualTestContext) {
+        cx.update(|_, cx| {
+            cx.bind_keys([KeyBinding::new(
+                "secondary-c",
+                CopySelectedText,
+                Some("SelectableText"),
+            )])
+        });
+    }
+
+    #[gpui::test]
+    fn test_plain_text_selection(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .size(px(500.))
+                    .text_selectable()
+                    .child("an error message")
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        bind_copy_selected_text(cx);
+
+        cx.simulate_mouse_down(point(px(0.), px(5.)), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_move(
+            point(px(490.), px(5.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_up(
+            point(px(490.), px(5.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_keystrokes("secondary-c");
+        assert_eq!(
+            cx.read_from_clipboard()
+                .and_then(|item| item.text())
+                .as_deref(),
+            Some("an error message")
+        );
+    }
+
+    #[gpui::test]
+    fn test_inline_elements_over_selectable_text(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+            presses: Rc<Cell<usize>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let presses = self.presses.clone();
+                let text = StyledText::new("a\u{FFFC}b")
+                    .selectable()
+                    .with_inline_element(
+                        1..4,
+                        InlineAlignment::Top,
+                        div()
+                            .size(px(10.))
+                            .occlude()
+                            .on_mouse_down(MouseButton::Left, move |_, _, _| {
+                                presses.set(presses.get() + 1)
+                            }),
+                    );
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div().size(px(500.)).child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let presses = Rc::new(Cell::new(0));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+            presses: presses.clone(),
+        });
+        bind_copy_selected_text(cx);
+        let position_of = |ix: usize| layout.borrow().as_ref().unwrap().position_for_index(ix);
+
+        // Dragging from the inline element presses it instead of starting a selection.
+        let inline_position = position_of(1).unwrap() + point(px(5.), px(5.));
+        let end_position = position_of(5).unwrap() + point(px(0.), px(5.));
+        cx.simulate_mouse_down(inline_position, MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_move(end_position, MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_up(end_position, MouseButton::Left, Modifiers::none());
+        assert_eq!(presses.get(), 1);
+        cx.simulate_keystrokes("secondary-c");
+        assert!(cx.read_from_clipboard().is_none());
+    }
+
+    #[gpui::test]
+    fn test_text_background_highlights(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().size(px(500.)).child(
+                    StyledText::new("one two three\nfour").with_background_highlights(vec![
+                        (0..3, red()),
+                        (3..7, red()),
+                        (5..10, blue()),
+                        (10..16, green()),
+                    ]),
+                )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        // The two adjacent red ranges are merged, and the green one is split across lines.
+        cx.update(|window, _| assert_eq!(window.rendered_frame.scene.quads.len(), 4));
+    }
+
+    #[gpui::test]
+    fn test_text_line_clamp(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let text = StyledText::new("word ".repeat(200));
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div().w(px(300.)).line_clamp(3).child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+        });
+        cx.update(|_, _| {
+            let layout = layout.borrow();
+            let layout = layout.as_ref().unwrap();
+            assert!(layout.was_truncated());
+            assert_eq!(layout.bounds().size.height, layout.line_height() * 3.);
+            assert!(layout.wrapped_text().ends_with('…'));
+            assert_eq!(layout.wrapped_text().lines().count(), 3);
+        });
+    }
+
+    #[gpui::test]
+    fn test_text_inline_elements(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+            chip_bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let chip_bounds = self.chip_bounds.clone();
+                let chip = canvas(
+                    move |bounds, _, _| chip_bounds.set(Some(bounds)),
+                    |_, _, _, _| {},
+                )
+                .w(px(50.))
+                .h(px(10.));
+                let text = StyledText::new("ab\u{FFFC}cd").with_inline_element(
+                    2..5,
+                    InlineAlignment::Top,
+                    chip,
+                );
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div().size(px(500.)).child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let chip_bounds = Rc::new(Cell::new(None));
+        cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+            chip_bounds: chip_bounds.clone(),
+        });
+
+        let layout = layout.borrow();
+        let layout = layout.as_ref().unwrap();
+        let chip_start = layout.position_for_index(2).unwrap();
+        let chip_end = layout.position_for_index(5).unwrap();
+        // The placeholder is laid out as wide as the chip, which is painted in its place.
+        assert_eq!(chip_end.x - chip_start.x, px(50.));
+        assert_eq!(
+            chip_bounds.get(),
+            Some(Bounds::new(chip_start, size(px(50.), px(10.))))
+        );
+    }
+
+    #[gpui::test]
+    fn test_measure_text(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let text = StyledText::new("word ".repeat(20));
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div().w(px(100.)).child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+        });
+        cx.update(|window, _| {
+            let measurement =
+                window.measure_text(&"word ".repeat(20), &window.text_style(), Some(px(100.)));
+            let layout = layout.borrow();
+            let layout = layout.as_ref().unwrap();
+            assert!(measurement.line_count() > 1);
+            assert_eq!(
+                measurement.line_count(),
+                layout.wrapped_text().lines().count()
+            );
+            assert_eq!(measurement.size.width, layout.bounds().size.width);
+            assert_eq!(
+                measurement.size.height,
+                layout.line_height() * measurement.line_count() as f32
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_measure_truncated_text(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let text = StyledText::new("word ".repeat(20));
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div().w(px(100.)).truncate().child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+        });
+        cx.update(|window, _| {
+            let text = "word ".repeat(20);
+            let mut style = window.text_style();
+            style.white_space = WhiteSpace::Nowrap;
+            style.text_overflow = Some(TextOverflow::Truncate("…".into()));
+            let measurement = window.measure_text(&text, &style, Some(px(100.)));
+            let layout = layout.borrow();
+            let layout = layout.as_ref().unwrap();
+            assert_eq!(measurement.line_count(), 1);
+            assert!(measurement.size.width <= px(100.));
+            assert_eq!(measurement.size.width, layout.bounds().size.width);
+
+            let mut style = window.text_style();
+            style.line_clamp = Some(2);
+            let measurement = window.measure_text(&text, &style, Some(px(100.)));
+            assert_eq!(measurement.line_count(), 2);
+            assert_eq!(measurement.size.height, layout.line_height() * 2.);
+        });
+    }
+
+    #[gpui::test]
+    fn test_text_decoration_styles(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .size(px(500.))
+                    .child(
+                        div()
+                            .text_decoration_1()
+                            .text_decoration_dashed()
+                            .child("dashed"),
+                    )
+                    .child(div().line_through().child("struck"))
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let underlines = &window.rendered_frame.scene.underlines;
+            assert_eq!(underlines.len(), 2);
+            assert_eq!(underlines[0].style, LineStyle::Dashed as u32);
+            // The strikethrough takes the font's suggested thickness, snapped to device pixels.
+            assert_eq!(underlines[1].style, LineStyle::Solid as u32);
+            assert_eq!(underlines[1].thickness, underlines[1].bounds.size.height);
+            assert!(underlines[1].thickness.0 >= 1.);
+            assert_eq!(underlines[1].bounds.origin.y.0.fract(), 0.);
+        });
+    }
+
+    #[gpui::test]
+    fn test_text_bidi_hit_testing(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let text = StyledText::new("ab אבג");
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div().child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+        });
+        cx.update(|_, _| {
+            let layout = layout.borrow();
+            let layout = layout.as_ref().unwrap();
+            let origin = layout.bounds().origin;
+            let char_width = layout.position_for_index(1).unwrap().x - origin.x;
+
+            // The Hebrew word is displayed right-to-left after the Latin one, so its first
+            // letter is rightmost and its last letter comes right after the space.
+            let first_letter = layout.position_for_index(3).unwrap();
+            assert_eq!(first_letter.x - origin.x, char_width * 6.);
+            let last_letter = layout.position_for_index(7).unwrap();
+            assert_eq!(last_letter.x - origin.x, char_width * 4.);
+            assert_eq!(
+                layout.index_for_position(origin + point(char_width * 3.5, px(1.))),
+                Ok(7)
+            );
+            assert_eq!(
+                layout.index_for_position(origin + point(char_width * 5.5, px(1.))),
+                Ok(3)
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_rtl_text_fragments_and_alignment(cx: &mut TestAppContext) {
+        struct TestView {
+            layout: Rc<RefCell<Option<TextLayout>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let text =
+                    StyledText::new("ab אבג").with_background_highlights(vec![(1..5, red())]);
+                *self.layout.borrow_mut() = Some(text.layout().clone());
+                div()
+                    .size(px(500.))
+                    .text_direction(TextDirection::Rtl)
+                    .child(text)
+            }
+        }
+
+        let layout = Rc::new(RefCell::new(None));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layout: layout.clone(),
+        });
+        let layout = layout.borrow();
+        let layout = layout.as_ref().unwrap();
+        let bounds = layout.bounds();
+        let char_width =
+            layout.position_for_index(1).unwrap().x - layout.position_for_index(0).unwrap().x;
+        // The right-to-left paragraph starts at the right edge, and is displayed as "גבא ab".
+        let row_start_x = bounds.right() - char_width * 6.;
+        assert_eq!(
+            layout.index_for_position(point(row_start_x + char_width * 0.5, bounds.top() + px(1.))),
+            Ok(7)
+        );
+        assert_eq!(
+            layout.index_for_position(point(row_start_x + char_width * 4.5, bounds.top() + px(1.))),
+            Ok(0)
+        );
+
+        // The highlighted "b א" is split into the visual spans of "א " and "b".
+        cx.update(|window, _| {
+            let mut highlights = window
+                .rendered_frame
+                .scene
+                .quads
+                .iter()
+                .map(|quad| (quad.bounds.left() - row_start_x, quad.bounds.size.width))
+                .collect::<Vec<_>>();
+            highlights.sort_by(|a, b| a.0.0.total_cmp(&b.0.0));
+            assert_eq!(highlights.len(), 2);
+            for ((x, width), (expected_x, expected_width)) in
+                highlights.into_iter().zip([(2., 2.), (5., 1.)])
+            {
+                assert!((x - char_width * expected_x).abs() < px(0.01));
+                assert!((width - char_width * expected_width).abs() < px(0.01));
+            }
+        });
+    }
+
+    #[gpui::test]
+    fn test_text_word_break(cx: &mut TestAppContext) {
+        struct TestView {
+            layouts: Rc<RefCell<Vec<TextLayout>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let texts = [
+                    StyledText::new("a".repeat(20)),
+                    StyledText::new("a".repeat(20)),
+                    StyledText::new("a".repeat(20)),
+                ];
+                *self.layouts.borrow_mut() =
+                    texts.iter().map(|text| text.layout().clone()).collect();
+                let [broken, overflowing, hyphenated] = texts;
+                // Each character is 9.6px wide, so ten of them fit on a line.
+                div()
+                    .flex()
+                    .flex_col()
+                    .w(px(100.))
+                    .child(div().w_full().child(broken))
+                    .child(div().w_full().break_normal().child(overflowing))
+                    .child(div().w_full().hyphenate().child(hyphenated))
+            }
+        }
+
+        let layouts = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            layouts: layouts.clone(),
+        });
+        cx.update(|_, _| {
+            let layouts = layouts.borrow();
+            let wrapped_texts = layouts
+                .iter()
+                .map(|layout| layout.wrapped_text())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                wrapped_texts[0],
+                format!("{}\n{}", "a".repeat(10), "a".repeat(10))
+            );
+            assert_eq!(wrapped_texts[1], "a".repeat(20));
+            // Room is left for the hyphen at the end of each broken line.
+            assert_eq!(
+                wrapped_texts[2],
+                format!("{}\n{}\n{}", "a".repeat(9), "a".repeat(9), "a".repeat(2))
+            );
+        });
+    }
+}
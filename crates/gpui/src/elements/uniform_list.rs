@@ -5,13 +5,13 @@
 //! elements with uniform height.
 
 use crate::{
-    AnyElement, App, AvailableSpace, Bounds, ContentMask, Element, ElementId, Entity,
+    AnyElement, App, AvailableSpace, Axis, Bounds, ContentMask, Element, ElementId, Entity,
     GlobalElementId, Hitbox, InspectorElementId, InteractiveElement, Interactivity, IntoElement,
-    IsZero, LayoutId, ListSizingBehavior, Overflow, Pixels, Point, ScrollHandle, Size,
-    StyleRefinement, Styled, Window, point, size,
+    IsZero, LayoutId, ListSizingBehavior, Overflow, Pixels, Point, ScrollHandle, ScrollTiming,
+    Size, StyleRefinement, Styled, Window, point, size,
 };
 use smallvec::SmallVec;
-use std::{cell::RefCell, cmp, ops::Range, rc::Rc};
+use std::{cell::RefCell, cmp, ops::Range, rc::Rc, time::Duration};
 
 use super::ListHorizontalSizingBehavior;
 
@@ -43,6 +43,8 @@ where
         item_to_measure_index: 0,
         render_items: Box::new(render_range),
         decorations: Vec::new(),
+        sticky_headers: None,
+        axis: Axis::Vertical,
         interactivity: Interactivity {
             element_id: Some(id),
             base_style: Box::new(base_style),
@@ -62,6 +64,8 @@ pub struct UniformList {
         dyn for<'a> Fn(Range<usize>, &'a mut Window, &'a mut App) -> SmallVec<[AnyElement; 64]>,
     >,
     decorations: Vec<Box<dyn UniformListDecoration>>,
+    sticky_headers: Option<StickyHeaders>,
+    axis: Axis,
     interactivity: Interactivity,
     scroll_handle: Option<UniformListScrollHandle>,
     sizing_behavior: ListSizingBehavior,
@@ -72,6 +76,12 @@ pub struct UniformList {
 pub struct UniformListFrameState {
     items: SmallVec<[AnyElement; 32]>,
     decorations: SmallVec<[AnyElement; 2]>,
+    sticky_header: Option<AnyElement>,
+}
+
+struct StickyHeaders {
+    header_for_item: Box<dyn Fn(usize) -> Option<usize>>,
+    render_header: Box<dyn Fn(usize, &mut Window, &mut App) -> AnyElement>,
 }
 
 /// A handle for controlling the scroll position of a uniform list.
@@ -115,6 +125,8 @@ pub struct DeferredScrollToItem {
 pub struct UniformListScrollState {
     pub base_handle: ScrollHandle,
     pub deferred_scroll_to_item: Option<DeferredScrollToItem>,
+    /// How to animate the scroll to the deferred item, or `None` to jump straight to it.
+    pub(crate) deferred_scroll_timing: Option<ScrollTiming>,
     /// Size of the item, captured during last layout.
     pub last_item_size: Option<ItemSize>,
     /// Whether the list was vertically flipped during last layout.
@@ -137,6 +149,7 @@ impl UniformListScrollHandle {
         Self(Rc::new(RefCell::new(UniformListScrollState {
             base_handle: ScrollHandle::new(),
             deferred_scroll_to_item: None,
+            deferred_scroll_timing: None,
             last_item_size: None,
             y_flipped: false,
         })))
@@ -148,12 +161,15 @@ impl UniformListScrollHandle {
     /// If the item is out of view, it scrolls the minimum amount to bring it into view according
     /// to the strategy.
     pub fn scroll_to_item(&self, ix: usize, strategy: ScrollStrategy) {
-        self.0.borrow_mut().deferred_scroll_to_item = Some(DeferredScrollToItem {
-            item_index: ix,
-            strategy,
-            offset: 0,
-            scroll_strict: false,
-        });
+        self.defer_scroll(
+            DeferredScrollToItem {
+                item_index: ix,
+                strategy,
+                offset: 0,
+                scroll_strict: false,
+            },
+            None,
+        );
     }
 
     /// Scroll the list so that the given item index is at scroll strategy position.
@@ -161,12 +177,15 @@ impl UniformListScrollHandle {
     /// This uses strict scrolling: the item will always be scrolled to match the strategy position,
     /// even if it's already visible. Use this when you need precise positioning.
     pub fn scroll_to_item_strict(&self, ix: usize, strategy: ScrollStrategy) {
-        self.0.borrow_mut().deferred_scroll_to_item = Some(DeferredScrollToItem {
-            item_index: ix,
-            strategy,
-            offset: 0,
-            scroll_strict: true,
-        });
+        self.defer_scroll(
+            DeferredScrollToItem {
+                item_index: ix,
+                strategy,
+                offset: 0,
+                scroll_strict: true,
+            },
+            None,
+        );
     }
 
     /// Scroll the list to the given item index with an offset in number of items.
@@ -180,12 +199,15 @@ impl UniformListScrollHandle {
     /// - `ScrollStrategy::Center`: Shrinks from top, centers item in the reduced viewport
     /// - `ScrollStrategy::Bottom`: Shrinks from bottom, positions item at the new bottom
     pub fn scroll_to_item_with_offset(&self, ix: usize, strategy: ScrollStrategy, offset: usize) {
-        self.0.borrow_mut().deferred_scroll_to_item = Some(DeferredScrollToItem {
-            item_index: ix,
-            strategy,
-            offset,
-            scroll_strict: false,
-        });
+        self.defer_scroll(
+            DeferredScrollToItem {
+                item_index: ix,
+                strategy,
+                offset,
+                scroll_strict: false,
+            },
+            None,
+        );
     }
 
     /// Scroll the list so that the given item index is at the exact scroll strategy position with an offset.
@@ -204,12 +226,73 @@ impl UniformListScrollHandle {
         strategy: ScrollStrategy,
         offset: usize,
     ) {
-        self.0.borrow_mut().deferred_scroll_to_item = Some(DeferredScrollToItem {
-            item_index: ix,
-            strategy,
-            offset,
-            scroll_strict: true,
-        });
+        self.defer_scroll(
+            DeferredScrollToItem {
+                item_index: ix,
+                strategy,
+                offset,
+                scroll_strict: true,
+            },
+            None,
+        );
+    }
+
+    /// Scroll the list smoothly so that the given item index is visible, over `duration` with
+    /// the progress of the scroll shaped by `easing`. Like [`Self::scroll_to_item`], this only
+    /// scrolls if the item isn't already fully visible.
+    pub fn scroll_to_item_animated(
+        &self,
+        ix: usize,
+        strategy: ScrollStrategy,
+        duration: Duration,
+        easing: impl Fn(f32) -> f32 + 'static,
+    ) {
+        self.defer_scroll(
+            DeferredScrollToItem {
+                item_index: ix,
+                strategy,
+                offset: 0,
+                scroll_strict: false,
+            },
+            Some(ScrollTiming::new(duration, easing)),
+        );
+    }
+
+    /// Scroll the list smoothly so that the given item index is at the scroll strategy
+    /// position, over `duration` with the progress of the scroll shaped by `easing`. Like
+    /// [`Self::scroll_to_item_strict`], this scrolls even if the item is already visible.
+    pub fn scroll_to_item_strict_animated(
+        &self,
+        ix: usize,
+        strategy: ScrollStrategy,
+        duration: Duration,
+        easing: impl Fn(f32) -> f32 + 'static,
+    ) {
+        self.defer_scroll(
+            DeferredScrollToItem {
+                item_index: ix,
+                strategy,
+                offset: 0,
+                scroll_strict: true,
+            },
+            Some(ScrollTiming::new(duration, easing)),
+        );
+    }
+
+    fn defer_scroll(&self, scroll_to_item: DeferredScrollToItem, timing: Option<ScrollTiming>) {
+        let mut state = self.0.borrow_mut();
+        if timing.is_some() {
+            state.base_handle.redraw();
+        }
+        state.deferred_scroll_to_item = Some(scroll_to_item);
+        state.deferred_scroll_timing = timing;
+    }
+
+    /// Whether an animated scroll to an item is pending or in flight, so that autoscrolling
+    /// can avoid fighting it.
+    pub fn is_animating(&self) -> bool {
+        let state = self.0.borrow();
+        state.deferred_scroll_timing.is_some() || state.base_handle.is_animating()
     }
 
     /// Check if the list is flipped vertically.
@@ -264,6 +347,7 @@ impl Element for UniformList {
     ) -> (LayoutId, Self::RequestLayoutState) {
         let max_items = self.item_count;
         let item_size = self.measure_item(None, window, cx);
+        let axis = self.axis;
         let layout_id = self.interactivity.request_layout(
             global_id,
             inspector_id,
@@ -275,6 +359,24 @@ impl Element for UniformList {
                         window.request_measured_layout(
                             style,
                             move |known_dimensions, available_space, _window, _cx| {
+                                if axis == Axis::Horizontal {
+                                    let desired_width = item_size.width * max_items;
+                                    let width = match available_space.width {
+                                        AvailableSpace::Definite(width) => desired_width.min(width),
+                                        AvailableSpace::MinContent | AvailableSpace::MaxContent => {
+                                            desired_width
+                                        }
+                                    };
+                                    let height = known_dimensions.height.unwrap_or(
+                                        match available_space.height {
+                                            AvailableSpace::Definite(height) => height,
+                                            AvailableSpace::MinContent
+                                            | AvailableSpace::MaxContent => item_size.height,
+                                        },
+                                    );
+                                    return size(width, height);
+                                }
+
                                 let desired_height = item_size.height * max_items;
                                 let width = known_dimensions.width.unwrap_or(match available_space
                                     .width
@@ -307,6 +409,7 @@ impl Element for UniformList {
             UniformListFrameState {
                 items: SmallVec::new(),
                 decorations: SmallVec::new(),
+                sticky_header: None,
             },
         )
     }
@@ -340,14 +443,21 @@ impl Element for UniformList {
         );
 
         let longest_item_size = self.measure_item(None, window, cx);
-        let content_width = if can_scroll_horizontally {
-            padded_bounds.size.width.max(longest_item_size.width)
+        let content_size = if self.axis == Axis::Horizontal {
+            Size {
+                width: longest_item_size.width * self.item_count,
+                height: padded_bounds.size.height,
+            }
         } else {
-            padded_bounds.size.width
-        };
-        let content_size = Size {
-            width: content_width,
-            height: longest_item_size.height * self.item_count,
+            let content_width = if can_scroll_horizontally {
+                padded_bounds.size.width.max(longest_item_size.width)
+            } else {
+                padded_bounds.size.width
+            };
+            Size {
+                width: content_width,
+                height: longest_item_size.height * self.item_count,
+            }
         };
 
         let shared_scroll_offset = self.interactivity.scroll_offset.clone().unwrap();
@@ -358,7 +468,12 @@ impl Element for UniformList {
                 item: padded_bounds.size,
                 contents: content_size,
             });
-            handle.deferred_scroll_to_item.take()
+            let scroll_to_item = handle.deferred_scroll_to_item.take()?;
+            Some((
+                scroll_to_item,
+                handle.deferred_scroll_timing.take(),
+                handle.base_handle.clone(),
+            ))
         });
 
         self.interactivity.prepaint(
@@ -369,6 +484,61 @@ impl Element for UniformList {
             window,
             cx,
             |_style, mut scroll_offset, hitbox, window, cx| {
+                if self.axis == Axis::Horizontal {
+                    if self.item_count == 0 {
+                        return hitbox;
+                    }
+
+                    let item_width = longest_item_size.width;
+                    let max_scroll_offset = padded_bounds.size.width - content_size.width;
+                    if !scroll_offset.x.is_zero() && scroll_offset.x < max_scroll_offset {
+                        shared_scroll_offset.borrow_mut().x = max_scroll_offset;
+                        scroll_offset.x = max_scroll_offset;
+                    }
+
+                    if let Some((scroll_to_item, timing, base_handle)) = shared_scroll_to_item {
+                        let target_x = scroll_offset_for_item(
+                            scroll_to_item,
+                            item_width,
+                            self.item_count,
+                            padded_bounds.size.width,
+                            scroll_offset.x,
+                        );
+                        scroll_offset = apply_deferred_scroll(
+                            &base_handle,
+                            scroll_offset,
+                            point(target_x, scroll_offset.y),
+                            timing,
+                            window,
+                        );
+                    }
+
+                    let first_visible_element_ix =
+                        ((-scroll_offset.x / item_width).floor() as usize).min(self.item_count);
+                    let last_visible_element_ix = ((-scroll_offset.x + padded_bounds.size.width)
+                        / item_width)
+                        .ceil() as usize;
+                    let visible_range = first_visible_element_ix
+                        ..cmp::min(last_visible_element_ix, self.item_count);
+
+                    let items = (self.render_items)(visible_range.clone(), window, cx);
+                    let available_space = size(
+                        AvailableSpace::Definite(item_width),
+                        AvailableSpace::Definite(padded_bounds.size.height),
+                    );
+                    window.with_content_mask(Some(ContentMask { bounds }), |window| {
+                        for (mut item, ix) in items.into_iter().zip(visible_range) {
+                            let item_origin = padded_bounds.origin
+                                + scroll_offset
+                                + point(item_width * ix, Pixels::ZERO);
+                            item.layout_as_root(available_space, window, cx);
+                            item.prepaint_at(item_origin, window, cx);
+                            frame_state.items.push(item);
+                        }
+                    });
+                    return hitbox;
+                }
+
                 let y_flipped = if let Some(scroll_handle) = &self.scroll_handle {
                     let scroll_state = scroll_handle.0.borrow();
                     scroll_state.y_flipped
@@ -395,62 +565,26 @@ impl Element for UniformList {
                         scroll_offset.x = Pixels::ZERO;
                     }
 
-                    if let Some(DeferredScrollToItem {
-                        mut item_index,
-                        mut strategy,
-                        offset,
-                        scroll_strict,
-                    }) = shared_scroll_to_item
-                    {
+                    if let Some((mut scroll_to_item, timing, base_handle)) = shared_scroll_to_item {
                         if y_flipped {
-                            item_index = self.item_count.saturating_sub(item_index + 1);
+                            scroll_to_item.item_index = self
+                                .item_count
+                                .saturating_sub(scroll_to_item.item_index + 1);
                         }
-                        let list_height = padded_bounds.size.height;
-                        let mut updated_scroll_offset = shared_scroll_offset.borrow_mut();
-                        let item_top = item_height * item_index;
-                        let item_bottom = item_top + item_height;
-                        let scroll_top = -updated_scroll_offset.y;
-                        let offset_pixels = item_height * offset;
-
-                        // is the selected item above/below currently visible items
-                        let is_above = item_top < scroll_top + offset_pixels;
-                        let is_below = item_bottom > scroll_top + list_height;
-
-                        if scroll_strict || is_above || is_below {
-                            if strategy == ScrollStrategy::Nearest {
-                                if is_above {
-                                    strategy = ScrollStrategy::Top;
-                                } else if is_below {
-                                    strategy = ScrollStrategy::Bottom;
-                                }
-                            }
-
-                            let max_scroll_offset =
-                                (content_height - list_height).max(Pixels::ZERO);
-                            match strategy {
-                                ScrollStrategy::Top => {
-                                    updated_scroll_offset.y = -(item_top - offset_pixels)
-                                        .clamp(Pixels::ZERO, max_scroll_offset);
-                                }
-                                ScrollStrategy::Center => {
-                                    let item_center = item_top + item_height / 2.0;
-
-                                    let viewport_height = list_height - offset_pixels;
-                                    let viewport_center = offset_pixels + viewport_height / 2.0;
-                                    let target_scroll_top = item_center - viewport_center;
-                                    updated_scroll_offset.y =
-                                        -target_scroll_top.clamp(Pixels::ZERO, max_scroll_offset);
-                                }
-                                ScrollStrategy::Bottom => {
-                                    updated_scroll_offset.y = -(item_bottom - list_height)
-                                        .clamp(Pixels::ZERO, max_scroll_offset);
-                                }
-                                ScrollStrategy::Nearest => {
-                                    // Nearest, but the item is visible -> no scroll is required
-                                }
-                            }
-                        }
-                        scroll_offset = *updated_scroll_offset
+                        let target_y = scroll_offset_for_item(
+                            scroll_to_item,
+                            item_height,
+                            self.item_count,
+                            padded_bounds.size.height,
+                            scroll_offset.y,
+                        );
+                        scroll_offset = apply_deferred_scroll(
+                            &base_handle,
+                            scroll_offset,
+                            point(scroll_offset.x, target_y),
+                            timing,
+                            window,
+                        );
                     }
 
                     let first_visible_element_ix =
@@ -474,20 +608,20 @@ impl Element for UniformList {
 
                     let content_mask = ContentMask { bounds };
                     window.with_content_mask(Some(content_mask), |window| {
+                        let available_width = if can_scroll_horizontally {
+                            padded_bounds.size.width + scroll_offset.x.abs()
+                        } else {
+                            padded_bounds.size.width
+                        };
+                        let available_space = size(
+                            AvailableSpace::Definite(available_width),
+                            AvailableSpace::Definite(item_height),
+                        );
+
                         for (mut item, ix) in items.into_iter().zip(visible_range.clone()) {
                             let item_origin = padded_bounds.origin
                                 + scroll_offset
                                 + point(Pixels::ZERO, item_height * ix);
-
-                            let available_width = if can_scroll_horizontally {
-                                padded_bounds.size.width + scroll_offset.x.abs()
-                            } else {
-                                padded_bounds.size.width
-                            };
-                            let available_space = size(
-                                AvailableSpace::Definite(available_width),
-                                AvailableSpace::Definite(item_height),
-                            );
                             item.layout_as_root(available_space, window, cx);
                             item.prepaint_at(item_origin, window, cx);
                             frame_state.items.push(item);
@@ -513,6 +647,29 @@ impl Element for UniformList {
                             decoration.prepaint_at(bounds.origin, window, cx);
                             frame_state.decorations.push(decoration);
                         }
+
+                        // Prepainted after the items so that the sticky header is hit tested
+                        // before the items it covers.
+                        if let Some(sticky_headers) = self.sticky_headers.as_ref()
+                            && !y_flipped
+                            && let Some((header_ix, header_offset)) = sticky_headers.pinned_header(
+                                visible_range.start,
+                                self.item_count,
+                                -scroll_offset.y,
+                                item_height,
+                            )
+                        {
+                            let mut header = (sticky_headers.render_header)(header_ix, window, cx);
+                            let header_origin =
+                                padded_bounds.origin + point(scroll_offset.x, header_offset);
+                            // The header item may also be rendered in the list, so give the
+                            // pinned copy its own namespace for element state.
+                            window.with_element_namespace("sticky_header", |window| {
+                                header.layout_as_root(available_space, window, cx);
+                                header.prepaint_at(header_origin, window, cx);
+                            });
+                            frame_state.sticky_header = Some(header);
+                        }
                     });
                 }
 
@@ -545,6 +702,11 @@ impl Element for UniformList {
                 for decoration in &mut request_layout.decorations {
                     decoration.paint(window, cx);
                 }
+                if let Some(sticky_header) = request_layout.sticky_header.as_mut() {
+                    window.with_element_namespace("sticky_header", |window| {
+                        sticky_header.paint(window, cx);
+                    });
+                }
             },
         )
     }
@@ -638,6 +800,48 @@ impl UniformList {
         self
     }
 
+    /// Lay out the items in a row that scrolls horizontally, rather than in a column. The width
+    /// of the measured item is used for every item, and only the visible columns are rendered.
+    /// Scrolling to an item with the list's [`UniformListScrollHandle`] scrolls along the x axis,
+    /// with [`ScrollStrategy::Top`] and [`ScrollStrategy::Bottom`] aligning the item to the left
+    /// and right edges. Vertical scroll wheel movement only scrolls the list if
+    /// [`Self::scroll_with_vertical_wheel`] is set. Decorations and sticky headers aren't
+    /// supported for horizontal lists.
+    pub fn horizontal(mut self) -> Self {
+        self.axis = Axis::Horizontal;
+        self.interactivity.base_style.overflow.x = Some(Overflow::Scroll);
+        self.interactivity.base_style.overflow.y = None;
+        self.interactivity.base_style.restrict_scroll_to_axis = Some(true);
+        self
+    }
+
+    /// Let vertical scroll wheel movement scroll a [`horizontal`](Self::horizontal) list while
+    /// it's hovered.
+    pub fn scroll_with_vertical_wheel(mut self) -> Self {
+        self.interactivity.base_style.restrict_scroll_to_axis = Some(false);
+        self
+    }
+
+    /// Pin the header of the section containing the first visible item to the top of the list.
+    ///
+    /// `header_for_item` returns the index of the header item of the section the given item
+    /// belongs to, if any, and `render_header` renders that header on top of the list items,
+    /// at the list's item height. The pinned header is pushed out of the viewport by the header
+    /// of the following section, follows the list's horizontal scroll offset, and receives mouse
+    /// events before the items it covers. Sticky headers are not shown in
+    /// [`y_flipped`](Self::y_flipped) lists.
+    pub fn with_sticky_headers(
+        mut self,
+        header_for_item: impl Fn(usize) -> Option<usize> + 'static,
+        render_header: impl Fn(usize, &mut Window, &mut App) -> AnyElement + 'static,
+    ) -> Self {
+        self.sticky_headers = Some(StickyHeaders {
+            header_for_item: Box::new(header_for_item),
+            render_header: Box::new(render_header),
+        });
+        self
+    }
+
     fn measure_item(
         &self,
         list_width: Option<Pixels>,
@@ -694,6 +898,113 @@ impl UniformList {
     }
 }
 
+/// Scrolls the list toward the offset of a deferred scroll, returning the offset to lay it out
+/// at this frame. Without a timing the list jumps straight to the target; otherwise an animated
+/// scroll is started from the current offset.
+fn apply_deferred_scroll(
+    base_handle: &ScrollHandle,
+    scroll_offset: Point<Pixels>,
+    target: Point<Pixels>,
+    timing: Option<ScrollTiming>,
+    window: &mut Window,
+) -> Point<Pixels> {
+    match timing {
+        Some(timing) if target != scroll_offset => {
+            base_handle.animate_to(target, timing);
+            window.request_animation_frame();
+            scroll_offset
+        }
+        Some(_) => scroll_offset,
+        None => {
+            base_handle.set_offset(target);
+            target
+        }
+    }
+}
+
+/// The scroll offset along the list's axis that places the requested item according to its
+/// strategy, given the current offset.
+fn scroll_offset_for_item(
+    scroll_to_item: DeferredScrollToItem,
+    item_extent: Pixels,
+    item_count: usize,
+    list_extent: Pixels,
+    scroll_offset: Pixels,
+) -> Pixels {
+    let DeferredScrollToItem {
+        item_index,
+        mut strategy,
+        offset,
+        scroll_strict,
+    } = scroll_to_item;
+    let content_extent = item_extent * item_count;
+    let item_top = item_extent * item_index;
+    let item_bottom = item_top + item_extent;
+    let scroll_top = -scroll_offset;
+    let offset_pixels = item_extent * offset;
+
+    // is the selected item above/below currently visible items
+    let is_above = item_top < scroll_top + offset_pixels;
+    let is_below = item_bottom > scroll_top + list_extent;
+
+    if !(scroll_strict || is_above || is_below) {
+        return scroll_offset;
+    }
+
+    if strategy == ScrollStrategy::Nearest {
+        if is_above {
+            strategy = ScrollStrategy::Top;
+        } else if is_below {
+            strategy = ScrollStrategy::Bottom;
+        }
+    }
+
+    let max_scroll_offset = (content_extent - list_extent).max(Pixels::ZERO);
+    match strategy {
+        ScrollStrategy::Top => -(item_top - offset_pixels).clamp(Pixels::ZERO, max_scroll_offset),
+        ScrollStrategy::Center => {
+            let item_center = item_top + item_extent / 2.0;
+
+            let viewport_extent = list_extent - offset_pixels;
+            let viewport_center = offset_pixels + viewport_extent / 2.0;
+            let target_scroll_top = item_center - viewport_center;
+            -target_scroll_top.clamp(Pixels::ZERO, max_scroll_offset)
+        }
+        ScrollStrategy::Bottom => {
+            -(item_bottom - list_extent).clamp(Pixels::ZERO, max_scroll_offset)
+        }
+        // Nearest, but the item is visible -> no scroll is required
+        ScrollStrategy::Nearest => scroll_offset,
+    }
+}
+
+impl StickyHeaders {
+    /// The header to pin for the given first visible item, along with its offset from the top
+    /// of the list. Returns `None` if the item isn't in a section, or if its section's header
+    /// is fully visible and doesn't need pinning.
+    fn pinned_header(
+        &self,
+        first_visible_ix: usize,
+        item_count: usize,
+        scroll_top: Pixels,
+        item_height: Pixels,
+    ) -> Option<(usize, Pixels)> {
+        let header_ix = (self.header_for_item)(first_visible_ix)?;
+        if header_ix == first_visible_ix && item_height * header_ix >= scroll_top {
+            return None;
+        }
+
+        // The header of the next section pushes the pinned header up as it scrolls into place.
+        let next_ix = first_visible_ix + 1;
+        if next_ix < item_count && (self.header_for_item)(next_ix) == Some(next_ix) {
+            let next_header_top = item_height * next_ix - scroll_top;
+            Some((header_ix, (next_header_top - item_height).min(Pixels::ZERO)))
+        } else {
+            Some((header_ix, Pixels::ZERO))
+        }
+    }
+}
+
 impl InteractiveElement for UniformList {
     fn interactivity(&mut self) -> &mut crate::Interactivity {
         &mut self.interactivity
@@ -845,4 +1156,209 @@ mod test {
             })
         }
     }
+
+    #[gpui::test]
+    fn test_sticky_headers(cx: &mut TestAppContext) {
+        use crate::{
+            Context, Modifiers, UniformListScrollHandle, Window, div, point, prelude::*, px,
+            uniform_list,
+        };
+        use std::{cell::Cell, ops::Range, rc::Rc};
+
+        struct TestView {
+            scroll_handle: UniformListScrollHandle,
+            header_clicks: Rc<Cell<usize>>,
+        }
+
+        impl Render for TestView {
+            fn render(
+                &mut self,
+                _window: &mut Window,
+                _cx: &mut Context<Self>,
+            ) -> impl IntoElement {
+                let header_clicks = self.header_clicks.clone();
+                uniform_list("entries", 20, |range: Range<usize>, _, _| {
+                    range
+                        .map(|ix| div().id(ix).h(px(20.0)).child(format!("Item {ix}")))
+                        .collect()
+                })
+                .with_sticky_headers(
+                    |ix| Some(ix / 5 * 5),
+                    move |header_ix, _, _| {
+                        let header_clicks = header_clicks.clone();
+                        div()
+                            .id(header_ix)
+                            .w_full()
+                            .h(px(20.0))
+                            .debug_selector(|| "sticky-header".into())
+                            .on_click(move |_, _, _| header_clicks.set(header_clicks.get() + 1))
+                            .into_any_element()
+                    },
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .w(px(100.0))
+                .h(px(100.0))
+            }
+        }
+
+        let header_clicks = Rc::new(Cell::new(0));
+        let scroll_handle = UniformListScrollHandle::new();
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            scroll_handle: scroll_handle.clone(),
+            header_clicks: header_clicks.clone(),
+        });
+        let scroll_to = |scroll_top: f32, cx: &mut gpui::VisualTestContext| {
+            scroll_handle
+                .0
+                .borrow()
+                .base_handle
+                .set_offset(point(px(0.), px(-scroll_top)));
+            cx.update(|window, _| window.refresh());
+            cx.run_until_parked();
+        };
+
+        assert_eq!(
+            cx.debug_bounds("sticky-header"),
+            None,
+            "a fully visible header isn't pinned"
+        );
+
+        scroll_to(30., cx);
+        let header_bounds = cx
+            .debug_bounds("sticky-header")
+            .expect("the header should be pinned");
+        assert_eq!(header_bounds.top(), px(0.));
+        assert_eq!(header_bounds.size.height, px(20.));
+
+        cx.simulate_click(point(px(10.), px(10.)), Modifiers::none());
+        assert_eq!(header_clicks.get(), 1);
+
+        // The header of the next section pushes the pinned header up.
+        scroll_to(90., cx);
+        let header_bounds = cx
+            .debug_bounds("sticky-header")
+            .expect("the header should be pinned");
+        assert_eq!(header_bounds.top(), px(-10.));
+    }
+
+    #[gpui::test]
+    fn test_horizontal_uniform_list(cx: &mut TestAppContext) {
+        use crate::{
+            Context, ScrollStrategy, UniformListScrollHandle, Window, div, point, prelude::*, px,
+            uniform_list,
+        };
+        use std::{cell::RefCell, ops::Range, rc::Rc};
+
+        struct TestView {
+            scroll_handle: UniformListScrollHandle,
+            visible_range: Rc<RefCell<Range<usize>>>,
+        }
+
+        impl Render for TestView {
+            fn render(
+                &mut self,
+                _window: &mut Window,
+                _cx: &mut Context<Self>,
+            ) -> impl IntoElement {
+                let visible_range = self.visible_range.clone();
+                uniform_list("thumbnails", 50, move |range: Range<usize>, _, _| {
+                    *visible_range.borrow_mut() = range.clone();
+                    range
+                        .map(|ix| div().id(ix).w(px(20.0)).h(px(20.0)))
+                        .collect()
+                })
+                .horizontal()
+                .track_scroll(self.scroll_handle.clone())
+                .w(px(100.0))
+                .h(px(20.0))
+            }
+        }
+
+        let scroll_handle = UniformListScrollHandle::new();
+        let visible_range = Rc::new(RefCell::new(0..0));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            scroll_handle: scroll_handle.clone(),
+            visible_range: visible_range.clone(),
+        });
+        assert_eq!(*visible_range.borrow(), 0..5);
+
+        scroll_handle.scroll_to_item(30, ScrollStrategy::Top);
+        cx.update(|window, _| window.refresh());
+        cx.run_until_parked();
+        assert_eq!(*visible_range.borrow(), 30..35);
+        assert_eq!(
+            scroll_handle.0.borrow().base_handle.offset(),
+            point(px(-600.), px(0.))
+        );
+
+        scroll_handle.scroll_to_item(40, ScrollStrategy::Nearest);
+        cx.update(|window, _| window.refresh());
+        cx.run_until_parked();
+        assert_eq!(*visible_range.borrow(), 36..41);
+    }
+
+    #[gpui::test]
+    fn test_animated_scroll_to_item(cx: &mut TestAppContext) {
+        use crate::{
+            Context, ScrollDelta, ScrollStrategy, ScrollWheelEvent, UniformListScrollHandle,
+            Window, div, linear, point, prelude::*, px, uniform_list,
+        };
+        use std::{ops::Range, time::Duration};
+
+        struct TestView {
+            scroll_handle: UniformListScrollHandle,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _window: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                uniform_list("entries", 50, |range: Range<usize>, _, _| {
+                    range
+                        .map(|ix| div().id(ix).h(px(20.0)).child(format!("Item {ix}")))
+                        .collect()
+                })
+                .track_scroll(self.scroll_handle.clone())
+                .h(px(200.0))
+            }
+        }
+
+        let scroll_handle = UniformListScrollHandle::new();
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            scroll_handle: scroll_handle.clone(),
+        });
+        let offset_y = |cx: &mut gpui::VisualTestContext| {
+            cx.update(|_, _| scroll_handle.0.borrow().base_handle.offset().y)
+        };
+        let redraw = |cx: &mut gpui::VisualTestContext| cx.simulate_animation_frame();
+
+        // The scroll starts on the frame after it's requested, and is already over by the next.
+        scroll_handle.scroll_to_item_animated(20, ScrollStrategy::Top, Duration::ZERO, linear);
+        assert!(scroll_handle.is_animating());
+        redraw(cx);
+        assert!(scroll_handle.is_animating());
+        assert_eq!(offset_y(cx), px(0.));
+        redraw(cx);
+        assert!(!scroll_handle.is_animating());
+        assert_eq!(offset_y(cx), px(-400.));
+
+        // Scrolling by hand cancels an animated scroll mid-flight.
+        scroll_handle.scroll_to_item_animated(
+            0,
+            ScrollStrategy::Top,
+            Duration::from_secs(3600),
+            linear,
+        );
+        redraw(cx);
+        redraw(cx);
+        assert!(scroll_handle.is_animating());
+        cx.simulate_event(ScrollWheelEvent {
+            position: point(px(1.), px(1.)),
+            delta: ScrollDelta::Pixels(point(px(0.), px(-20.))),
+            ..Default::default()
+        });
+        assert!(!scroll_handle.is_animating());
+        let offset_after_wheel = offset_y(cx);
+        redraw(cx);
+        assert_eq!(offset_y(cx), offset_after_wheel);
+        assert!(offset_after_wheel < px(-300.));
+    }
 }
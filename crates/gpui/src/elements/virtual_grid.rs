@@ -0,0 +1,786 @@
+//! A scrollable grid of cells that only renders the cells in view, along both axes.
+//! Rows and columns may each have their own size, which is queried for every track on
+//! every frame, so it should be cheap to compute. The offsets of the tracks are only
+//! recomputed from the first track whose size changed since the previous frame. Leading rows and columns can be pinned,
+//! such that they stay in view while the rest of the grid scrolls underneath them, which
+//! is useful for header rows and frozen columns in data tables.
+
+use crate::{
+    AnyElement, App, AvailableSpace, Bounds, ContentMask, DispatchPhase, Element, ElementId,
+    GlobalElementId, Hitbox, HitboxBehavior, InspectorElementId, InteractiveElement, Interactivity,
+    IntoElement, LayoutId, MouseDownEvent, MouseMoveEvent, Overflow, Pixels, Point, ScrollHandle,
+    StyleRefinement, Styled, Window, point, px, size,
+};
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+/// Construct a new virtualized grid with the given number of rows and columns.
+///
+/// `track_size` returns the height of a row or the width of a column, and `render_cell`
+/// renders a single cell, which is laid out at its row's height and column's width. Each
+/// cell is rendered in its own element namespace, so stateful elements within a cell keep
+/// their state as the grid scrolls.
+#[track_caller]
+pub fn virtual_grid<R>(
+    id: impl Into<ElementId>,
+    rows: usize,
+    columns: usize,
+    track_size: impl Fn(GridTrack) -> Pixels + 'static,
+    render_cell: impl Fn(GridCell, &mut Window, &mut App) -> R + 'static,
+) -> VirtualGrid
+where
+    R: IntoElement,
+{
+    let mut base_style = StyleRefinement::default();
+    base_style.overflow.x = Some(Overflow::Scroll);
+    base_style.overflow.y = Some(Overflow::Scroll);
+
+    VirtualGrid {
+        rows,
+        columns,
+        pinned_rows: 0,
+        pinned_columns: 0,
+        track_size: Box::new(track_size),
+        render_cell: Box::new(move |cell, window, cx| {
+            render_cell(cell, window, cx).into_any_element()
+        }),
+        cell_mouse_down_listener: None,
+        cell_mouse_move_listener: None,
+        interactivity: Interactivity {
+            element_id: Some(id.into()),
+            base_style: Box::new(base_style),
+            ..Interactivity::new()
+        },
+        scroll_handle: None,
+    }
+}
+
+/// A grid element that lazily renders the cells visible in its viewport.
+pub struct VirtualGrid {
+    rows: usize,
+    columns: usize,
+    pinned_rows: usize,
+    pinned_columns: usize,
+    track_size: Box<dyn Fn(GridTrack) -> Pixels>,
+    render_cell: Box<dyn Fn(GridCell, &mut Window, &mut App) -> AnyElement>,
+    cell_mouse_down_listener: Option<Rc<CellListener<MouseDownEvent>>>,
+    cell_mouse_move_listener: Option<Rc<CellListener<MouseMoveEvent>>>,
+    interactivity: Interactivity,
+    scroll_handle: Option<GridScrollHandle>,
+}
+
+type CellListener<Event> = dyn Fn(GridCell, &Event, &mut Window, &mut App);
+
+/// A row or column of a [`VirtualGrid`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GridTrack {
+    /// The row with the given index.
+    Row(usize),
+    /// The column with the given index.
+    Column(usize),
+}
+
+/// The position of a cell in a [`VirtualGrid`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GridCell {
+    /// The row of the cell.
+    pub row: usize,
+    /// The column of the cell.
+    pub column: usize,
+}
+
+/// Frame state used by the [`VirtualGrid`].
+pub struct VirtualGridFrameState {
+    regions: Vec<GridRegion>,
+    cell_hitboxes: Vec<(GridCell, Hitbox)>,
+}
+
+/// The cells that are clipped to one part of the grid: the scrolled body, the pinned rows,
+/// the pinned columns, or the corner where those overlap.
+struct GridRegion {
+    bounds: Bounds<Pixels>,
+    cells: Vec<(GridCell, AnyElement)>,
+}
+
+/// A handle for controlling the scroll position of a virtual grid.
+/// This should be stored in your view and passed to the virtual_grid on each frame.
+#[derive(Clone, Debug, Default)]
+pub struct GridScrollHandle(Rc<RefCell<GridScrollState>>);
+
+#[derive(Clone, Debug, Default)]
+struct GridScrollState {
+    base_handle: ScrollHandle,
+    deferred_scroll_to_cell: Option<GridCell>,
+}
+
+impl GridScrollHandle {
+    /// Create a new scroll handle to bind to a virtual grid.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scroll the grid by the minimum amount that brings the given cell fully into view,
+    /// next to the pinned rows and columns. Doesn't scroll along an axis on which the cell
+    /// is pinned.
+    pub fn scroll_to_cell(&self, row: usize, column: usize) {
+        self.0.borrow_mut().deferred_scroll_to_cell = Some(GridCell { row, column });
+    }
+
+    /// The current scroll offset of the grid.
+    pub fn offset(&self) -> Point<Pixels> {
+        self.0.borrow().base_handle.offset()
+    }
+}
+
+impl VirtualGrid {
+    /// Keep the first `count` rows in view at the top of the grid, e.g. for a header row.
+    pub fn pinned_rows(mut self, count: usize) -> Self {
+        self.pinned_rows = count;
+        self
+    }
+
+    /// Keep the first `count` columns in view at the left of the grid.
+    pub fn pinned_columns(mut self, count: usize) -> Self {
+        self.pinned_columns = count;
+        self
+    }
+
+    /// Track and render scroll state of this grid with reference to the given scroll handle.
+    pub fn track_scroll(mut self, handle: GridScrollHandle) -> Self {
+        self.interactivity.tracked_scroll_handle = Some(handle.0.borrow().base_handle.clone());
+        self.scroll_handle = Some(handle);
+        self
+    }
+
+    /// Register a listener to be called when the mouse is pressed over a cell, e.g. to start
+    /// a selection.
+    pub fn on_cell_mouse_down(
+        mut self,
+        listener: impl Fn(GridCell, &MouseDownEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.cell_mouse_down_listener = Some(Rc::new(listener));
+        self
+    }
+
+    /// Register a listener to be called when the mouse moves over a cell, e.g. to extend a
+    /// selection while a button is held.
+    pub fn on_cell_mouse_move(
+        mut self,
+        listener: impl Fn(GridCell, &MouseMoveEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.cell_mouse_move_listener = Some(Rc::new(listener));
+        self
+    }
+}
+
+impl Styled for VirtualGrid {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.interactivity.base_style
+    }
+}
+
+impl InteractiveElement for VirtualGrid {
+    fn interactivity(&mut self) -> &mut Interactivity {
+        &mut self.interactivity
+    }
+}
+
+impl IntoElement for VirtualGrid {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for VirtualGrid {
+    type RequestLayoutState = VirtualGridFrameState;
+    type PrepaintState = Option<Hitbox>;
+
+    fn id(&self) -> Option<ElementId> {
+        self.interactivity.element_id.clone()
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let layout_id = self.interactivity.request_layout(
+            global_id,
+            inspector_id,
+            window,
+            cx,
+            |style, window, cx| {
+                window.with_text_style(style.text_style().cloned(), |window| {
+                    window.request_layout(style, None, cx)
+                })
+            },
+        );
+
+        (
+            layout_id,
+            VirtualGridFrameState {
+                regions: Vec::new(),
+                cell_hitboxes: Vec::new(),
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        frame_state: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<Hitbox> {
+        let style = self
+            .interactivity
+            .compute_style(global_id, None, window, cx);
+        let border = style.border_widths.to_pixels(window.rem_size());
+        let padding = style
+            .padding
+            .to_pixels(bounds.size.into(), window.rem_size());
+
+        let padded_bounds = Bounds::from_corners(
+            bounds.origin + point(border.left + padding.left, border.top + padding.top),
+            bounds.bottom_right()
+                - point(border.right + padding.right, border.bottom + padding.bottom),
+        );
+
+        let track_starts = window.with_optional_element_state(global_id, |state, _| {
+            let state: Rc<RefCell<GridTrackStarts>> = state.flatten().unwrap_or_default();
+            (state.clone(), Some(state))
+        });
+        {
+            let mut track_starts = track_starts.borrow_mut();
+            track_starts
+                .rows
+                .update(self.rows, |row| (self.track_size)(GridTrack::Row(row)));
+            track_starts.columns.update(self.columns, |column| {
+                (self.track_size)(GridTrack::Column(column))
+            });
+        }
+        let track_starts = track_starts.borrow();
+        let row_starts = track_starts.rows.starts.as_slice();
+        let column_starts = track_starts.columns.starts.as_slice();
+        let pinned_rows = self.pinned_rows.min(self.rows);
+        let pinned_columns = self.pinned_columns.min(self.columns);
+        let content_size = size(column_starts[self.columns], row_starts[self.rows]);
+
+        let shared_scroll_offset = self.interactivity.scroll_offset.clone().unwrap();
+        let scroll_to_cell = self
+            .scroll_handle
+            .as_ref()
+            .and_then(|handle| handle.0.borrow_mut().deferred_scroll_to_cell.take());
+
+        self.interactivity.prepaint(
+            global_id,
+            inspector_id,
+            bounds,
+            content_size,
+            window,
+            cx,
+            |_style, _scroll_offset, hitbox, window, cx| {
+                let viewport = padded_bounds.size;
+                let scroll_offset = {
+                    let mut shared_scroll_offset = shared_scroll_offset.borrow_mut();
+                    shared_scroll_offset.x = -scrolled_extent(
+                        column_starts,
+                        pinned_columns,
+                        -shared_scroll_offset.x,
+                        viewport.width,
+                        scroll_to_cell.map(|cell| cell.column),
+                    );
+                    shared_scroll_offset.y = -scrolled_extent(
+                        row_starts,
+                        pinned_rows,
+                        -shared_scroll_offset.y,
+                        viewport.height,
+                        scroll_to_cell.map(|cell| cell.row),
+                    );
+                    *shared_scroll_offset
+                };
+
+                let pinned_size = size(column_starts[pinned_columns], row_starts[pinned_rows]);
+                let scrolled_rows =
+                    visible_tracks(row_starts, pinned_rows, -scroll_offset.y, viewport.height);
+                let scrolled_columns = visible_tracks(
+                    column_starts,
+                    pinned_columns,
+                    -scroll_offset.x,
+                    viewport.width,
+                );
+
+                // Prepainted from the body to the corner, so that the pinned cells are painted
+                // and hit tested on top of the cells that scroll underneath them.
+                let regions = [
+                    (scrolled_rows.clone(), scrolled_columns.clone()),
+                    (0..pinned_rows, scrolled_columns),
+                    (scrolled_rows, 0..pinned_columns),
+                    (0..pinned_rows, 0..pinned_columns),
+                ];
+                let insert_cell_hitboxes = self.cell_mouse_down_listener.is_some()
+                    || self.cell_mouse_move_listener.is_some();
+                for (rows, columns) in regions {
+                    if rows.is_empty() || columns.is_empty() {
+                        continue;
+                    }
+
+                    let rows_are_pinned = rows.start < pinned_rows;
+                    let columns_are_pinned = columns.start < pinned_columns;
+                    let region_origin = padded_bounds.origin
+                        + point(
+                            if columns_are_pinned {
+                                Pixels::ZERO
+                            } else {
+                                pinned_size.width
+                            },
+                            if rows_are_pinned {
+                                Pixels::ZERO
+                            } else {
+                                pinned_size.height
+                            },
+                        );
+                    let region_bounds = Bounds::from_corners(
+                        region_origin,
+                        point(
+                            if columns_are_pinned {
+                                padded_bounds.origin.x + pinned_size.width
+                            } else {
+                                padded_bounds.right()
+                            },
+                            if rows_are_pinned {
+                                padded_bounds.origin.y + pinned_size.height
+                            } else {
+                                padded_bounds.bottom()
+                            },
+                        ),
+                    );
+                    let cell_scroll_offset = point(
+                        if columns_are_pinned {
+                            Pixels::ZERO
+                        } else {
+                            scroll_offset.x
+                        },
+                        if rows_are_pinned {
+                            Pixels::ZERO
+                        } else {
+                            scroll_offset.y
+                        },
+                    );
+
+                    let mut cells = Vec::with_capacity(rows.len() * columns.len());
+                    window.with_content_mask(
+                        Some(ContentMask {
+                            bounds: region_bounds,
+                        }),
+                        |window| {
+                            for row in rows {
+                                for column in columns.clone() {
+                                    let cell = GridCell { row, column };
+                                    let cell_bounds = Bounds::from_corners(
+                                        padded_bounds.origin
+                                            + cell_scroll_offset
+                                            + point(column_starts[column], row_starts[row]),
+                                        padded_bounds.origin
+                                            + cell_scroll_offset
+                                            + point(column_starts[column + 1], row_starts[row + 1]),
+                                    );
+                                    let available_space = size(
+                                        AvailableSpace::Definite(cell_bounds.size.width),
+                                        AvailableSpace::Definite(cell_bounds.size.height),
+                                    );
+                                    // The cell's hitbox is inserted behind its content, and is
+                                    // the parent of the content's hitboxes, so that it doesn't
+                                    // occlude their hover.
+                                    let cell_hitbox = insert_cell_hitboxes.then(|| {
+                                        window.insert_hitbox(cell_bounds, HitboxBehavior::Normal)
+                                    });
+                                    let element = window.with_hitbox_parent(
+                                        cell_hitbox.as_ref().map(|hitbox| hitbox.id),
+                                        |window| {
+                                            window.with_element_namespace(
+                                                cell_element_id(cell),
+                                                |window| {
+                                                    let mut element =
+                                                        (self.render_cell)(cell, window, cx);
+                                                    element.layout_as_root(
+                                                        available_space,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                    element.prepaint_at(
+                                                        cell_bounds.origin,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                    element
+                                                },
+                                            )
+                                        },
+                                    );
+                                    if let Some(cell_hitbox) = cell_hitbox {
+                                        frame_state.cell_hitboxes.push((cell, cell_hitbox));
+                                    }
+                                    cells.push((cell, element));
+                                }
+                            }
+                        },
+                    );
+                    frame_state.regions.push(GridRegion {
+                        bounds: region_bounds,
+                        cells,
+                    });
+                }
+
+                hitbox
+            },
+        )
+    }
+
+    fn paint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        hitbox: &mut Option<Hitbox>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.interactivity.paint(
+            global_id,
+            inspector_id,
+            bounds,
+            hitbox.as_ref(),
+            window,
+            cx,
+            |_, window, cx| {
+                for region in &mut request_layout.regions {
+                    window.with_content_mask(
+                        Some(ContentMask {
+                            bounds: region.bounds,
+                        }),
+                        |window| {
+                            for (cell, element) in &mut region.cells {
+                                window.with_element_namespace(cell_element_id(*cell), |window| {
+                                    element.paint(window, cx);
+                                });
+                            }
+                        },
+                    );
+                }
+
+                let cell_hitboxes = Rc::new(std::mem::take(&mut request_layout.cell_hitboxes));
+                if let Some(listener) = self.cell_mouse_down_listener.clone() {
+                    let cell_hitboxes = cell_hitboxes.clone();
+                    window.on_mouse_event(move |event: &MouseDownEvent, phase, window, cx| {
+                        if phase == DispatchPhase::Bubble
+                            && let Some(cell) = hovered_cell(&cell_hitboxes, window)
+                        {
+                            listener(cell, event, window, cx);
+                        }
+                    });
+                }
+                if let Some(listener) = self.cell_mouse_move_listener.clone() {
+                    window.on_mouse_event(move |event: &MouseMoveEvent, phase, window, cx| {
+                        if phase == DispatchPhase::Bubble
+                            && let Some(cell) = hovered_cell(&cell_hitboxes, window)
+                        {
+                            listener(cell, event, window, cx);
+                        }
+                    });
+                }
+            },
+        )
+    }
+}
+
+fn cell_element_id(cell: GridCell) -> ElementId {
+    ElementId::NamedInteger(
+        "grid_cell".into(),
+        ((cell.row as u64) << 32) | cell.column as u64,
+    )
+}
+
+fn hovered_cell(cell_hitboxes: &[(GridCell, Hitbox)], window: &Window) -> Option<GridCell> {
+    cell_hitboxes
+        .iter()
+        .rev()
+        .find(|(_, hitbox)| hitbox.is_hovered(window))
+        .map(|(cell, _)| *cell)
+}
+
+/// The track offsets of a grid, kept across frames.
+#[derive(Default)]
+struct GridTrackStarts {
+    rows: TrackStarts,
+    columns: TrackStarts,
+}
+
+/// The size of each track, and the offset of the start of each track from the start of the
+/// grid, followed by the total size of the tracks.
+#[derive(Default)]
+struct TrackStarts {
+    sizes: Vec<Pixels>,
+    starts: Vec<Pixels>,
+}
+
+impl TrackStarts {
+    /// Queries the size of every track, and recomputes the starts from the first track whose
+    /// size changed.
+    fn update(&mut self, count: usize, track_size: impl Fn(usize) -> Pixels) {
+        let mut first_changed = None;
+        self.sizes.truncate(count);
+        for ix in 0..count {
+            let size = track_size(ix);
+            match self.sizes.get_mut(ix) {
+                Some(cached_size) if *cached_size == size => continue,
+                Some(cached_size) => *cached_size = size,
+                None => self.sizes.push(size),
+            }
+            first_changed.get_or_insert(ix);
+        }
+
+        let first_changed = first_changed
+            .unwrap_or(count)
+            .min(self.starts.len().saturating_sub(1));
+        self.starts.truncate(first_changed + 1);
+        if self.starts.is_empty() {
+            self.starts.push(px(0.));
+        }
+        for ix in self.starts.len() - 1..count {
+            let start = self.starts[ix] + self.sizes[ix];
+            self.starts.push(start);
+        }
+    }
+}
+
+/// The distance the unpinned tracks are scrolled by, clamped to the content and adjusted to
+/// reveal the track at `reveal_ix`, if any.
+fn scrolled_extent(
+    starts: &[Pixels],
+    pinned: usize,
+    mut scrolled: Pixels,
+    viewport: Pixels,
+    reveal_ix: Option<usize>,
+) -> Pixels {
+    let count = starts.len() - 1;
+    if let Some(ix) = reveal_ix.filter(|ix| (pinned..count).contains(ix)) {
+        let pinned_extent = starts[pinned];
+        if starts[ix] - scrolled < pinned_extent {
+            scrolled = starts[ix] - pinned_extent;
+        } else if starts[ix + 1] - scrolled > viewport {
+            scrolled = (starts[ix + 1] - viewport).min(starts[ix] - pinned_extent);
+        }
+    }
+    let max_scrolled = (starts[count] - viewport).max(px(0.));
+    scrolled.clamp(px(0.), max_scrolled)
+}
+
+/// The unpinned tracks that are at least partially visible next to the pinned tracks.
+fn visible_tracks(
+    starts: &[Pixels],
+    pinned: usize,
+    scrolled: Pixels,
+    viewport: Pixels,
+) -> Range<usize> {
+    let count = starts.len() - 1;
+    let pinned_extent = starts[pinned];
+    let first =
+        pinned + starts[pinned + 1..].partition_point(|end| *end - scrolled <= pinned_extent);
+    let last = starts[..count].partition_point(|start| *start - scrolled < viewport);
+    first..last.max(first)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::TestAppContext;
+
+    #[test]
+    fn test_track_starts_update_from_first_changed_track() {
+        use super::TrackStarts;
+        use crate::{Pixels, px};
+        use std::cell::Cell;
+
+        let queried = Cell::new(0);
+        let mut track_starts = TrackStarts::default();
+        let update = |track_starts: &mut TrackStarts, count, sizes: &[f32]| {
+            track_starts.update(count, |ix| {
+                queried.set(queried.get() + 1);
+                px(sizes[ix])
+            });
+            track_starts.starts.clone()
+        };
+        let starts = |starts: &[f32]| starts.iter().copied().map(px).collect::<Vec<Pixels>>();
+
+        assert_eq!(
+            update(&mut track_starts, 3, &[10., 20., 30.]),
+            starts(&[0., 10., 30., 60.])
+        );
+        assert_eq!(
+            update(&mut track_starts, 3, &[10., 25., 30.]),
+            starts(&[0., 10., 35., 65.])
+        );
+        assert_eq!(
+            update(&mut track_starts, 4, &[10., 25., 30., 5.]),
+            starts(&[0., 10., 35., 65., 70.])
+        );
+        assert_eq!(
+            update(&mut track_starts, 2, &[10., 25.]),
+            starts(&[0., 10., 35.])
+        );
+        assert_eq!(update(&mut track_starts, 0, &[]), starts(&[0.]));
+        assert_eq!(queried.get(), 12);
+    }
+
+    #[gpui::test]
+    fn test_virtual_grid_cell_hitbox_does_not_occlude_cell_content(cx: &mut TestAppContext) {
+        use crate::{
+            Context, GridCell, Modifiers, Render, Window, div, point, prelude::*, px, virtual_grid,
+        };
+        use std::{cell::RefCell, rc::Rc};
+
+        struct TestView {
+            hovered_cells: Rc<RefCell<Vec<GridCell>>>,
+        }
+
+        impl Render for TestView {
+            fn render(
+                &mut self,
+                _window: &mut Window,
+                _cx: &mut Context<Self>,
+            ) -> impl IntoElement {
+                let hovered_cells = self.hovered_cells.clone();
+                virtual_grid(
+                    "grid",
+                    10,
+                    10,
+                    |_| px(10.),
+                    move |cell, _, _| {
+                        let hovered_cells = hovered_cells.clone();
+                        div()
+                            .id(("cell", cell.row * 10 + cell.column))
+                            .size_full()
+                            .on_hover(move |hovered, _, _| {
+                                if *hovered {
+                                    hovered_cells.borrow_mut().push(cell);
+                                }
+                            })
+                    },
+                )
+                .on_cell_mouse_down(|_, _, _, _| {})
+                .w(px(50.))
+                .h(px(50.))
+            }
+        }
+
+        let hovered_cells = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            hovered_cells: hovered_cells.clone(),
+        });
+
+        cx.simulate_mouse_move(point(px(15.), px(25.)), None, Modifiers::none());
+        assert_eq!(*hovered_cells.borrow(), [GridCell { row: 2, column: 1 }]);
+    }
+
+    #[gpui::test]
+    fn test_virtual_grid_pinned_tracks(cx: &mut TestAppContext) {
+        use crate::{
+            Context, GridCell, GridScrollHandle, GridTrack, Modifiers, Render, Window, div, point,
+            prelude::*, px, virtual_grid,
+        };
+        use std::{cell::RefCell, rc::Rc};
+
+        struct TestView {
+            scroll_handle: GridScrollHandle,
+            rendered_cells: Rc<RefCell<Vec<GridCell>>>,
+            pressed_cells: Rc<RefCell<Vec<GridCell>>>,
+        }
+
+        impl Render for TestView {
+            fn render(
+                &mut self,
+                _window: &mut Window,
+                _cx: &mut Context<Self>,
+            ) -> impl IntoElement {
+                self.rendered_cells.borrow_mut().clear();
+                let rendered_cells = self.rendered_cells.clone();
+                let pressed_cells = self.pressed_cells.clone();
+                virtual_grid(
+                    "grid",
+                    1000,
+                    100,
+                    |track| match track {
+                        GridTrack::Column(0) => px(30.),
+                        GridTrack::Row(_) | GridTrack::Column(_) => px(10.),
+                    },
+                    move |cell, _, _| {
+                        rendered_cells.borrow_mut().push(cell);
+                        div().size_full()
+                    },
+                )
+                .pinned_rows(1)
+                .pinned_columns(1)
+                .track_scroll(self.scroll_handle.clone())
+                .on_cell_mouse_down(move |cell, _, _, _| pressed_cells.borrow_mut().push(cell))
+                .w(px(100.))
+                .h(px(50.))
+            }
+        }
+
+        let scroll_handle = GridScrollHandle::new();
+        let rendered_cells = Rc::new(RefCell::new(Vec::new()));
+        let pressed_cells = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            scroll_handle: scroll_handle.clone(),
+            rendered_cells: rendered_cells.clone(),
+            pressed_cells: pressed_cells.clone(),
+        });
+
+        // Five rows of 10px and the 30px column plus seven 10px columns fit in the viewport.
+        assert_eq!(rendered_cells.borrow().len(), 5 * 8);
+
+        scroll_handle.scroll_to_cell(500, 50);
+        cx.update(|window, _| window.refresh());
+        cx.run_until_parked();
+        assert_eq!(scroll_handle.offset(), point(px(-430.), px(-4960.)));
+        {
+            let rendered_cells = rendered_cells.borrow();
+            for cell in [(500, 50), (0, 50), (500, 0), (0, 0)] {
+                assert!(rendered_cells.contains(&GridCell {
+                    row: cell.0,
+                    column: cell.1,
+                }));
+            }
+            assert!(!rendered_cells.contains(&GridCell { row: 1, column: 1 }));
+        }
+
+        // Pinned cells are hit tested on top of the cells scrolled underneath them.
+        cx.simulate_click(point(px(5.), px(5.)), Modifiers::none());
+        cx.simulate_click(point(px(35.), px(15.)), Modifiers::none());
+        cx.simulate_click(point(px(95.), px(45.)), Modifiers::none());
+        assert_eq!(
+            *pressed_cells.borrow(),
+            [
+                GridCell { row: 0, column: 0 },
+                GridCell {
+                    row: 497,
+                    column: 44,
+                },
+                GridCell {
+                    row: 500,
+                    column: 50,
+                },
+            ]
+        );
+    }
+}
@@ -3475,6 +3475,13 @@ pub enum Length {
     Definite(DefiniteLength),
     /// An automatic length that is determined by the context in which it is used.
     Auto,
+    /// The smallest size that fits the content, such as the width of its longest word.
+    MinContent,
+    /// The size the content takes up when it isn't wrapped, such as the width of its widest child.
+    MaxContent,
+    /// The size the content takes up, but no larger than the given length unless the content
+    /// can't shrink that far, like CSS's `fit-content(<length>)`.
+    FitContent(DefiniteLength),
 }
 
 impl Debug for Length {
@@ -3488,11 +3495,14 @@ impl Display for Length {
         match self {
             Length::Definite(definite_length) => write!(f, "{}", definite_length),
             Length::Auto => write!(f, "auto"),
+            Length::MinContent => write!(f, "min-content"),
+            Length::MaxContent => write!(f, "max-content"),
+            Length::FitContent(limit) => write!(f, "fit-content({limit})"),
         }
     }
 }
 
-const EXPECTED_LENGTH: &str = "expected 'auto' or number with 'px', 'rem', or '%' suffix";
+const EXPECTED_LENGTH: &str = "expected 'auto', 'min-content', 'max-content', 'fit-content(<length>)' or number with 'px', 'rem', or '%' suffix";
 
 impl TryFrom<&'_ str> for Length {
     type Error = anyhow::Error;
@@ -3500,6 +3510,15 @@ impl TryFrom<&'_ str> for Length {
     fn try_from(value: &'_ str) -> Result<Self, Self::Error> {
         if value == "auto" {
             Ok(Length::Auto)
+        } else if value == "min-content" {
+            Ok(Length::MinContent)
+        } else if value == "max-content" {
+            Ok(Length::MaxContent)
+        } else if let Some(limit) = value
+            .strip_prefix("fit-content(")
+            .and_then(|limit| limit.strip_suffix(')'))
+        {
+            Ok(Length::FitContent(limit.try_into()?))
         } else if let Ok(definite_length) = value.try_into() {
             Ok(Length::Definite(definite_length))
         } else {
@@ -3518,7 +3537,7 @@ impl JsonSchema for Length {
     fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
         json_schema!({
             "type": "string",
-            "pattern": r"^(auto|-?\d+(\.\d+)?(px|rem|%))$"
+            "pattern": r"^(auto|min-content|max-content|fit-content\(-?\d+(\.\d+)?(px|rem|%)\)|-?\d+(\.\d+)?(px|rem|%))$"
         })
     }
 }
@@ -3614,6 +3633,22 @@ pub fn auto() -> Length {
     Length::Auto
 }
 
+/// Returns a `Length` representing the smallest size that fits an element's content.
+pub fn min_content() -> Length {
+    Length::MinContent
+}
+
+/// Returns a `Length` representing the size an element's content takes up without wrapping.
+pub fn max_content() -> Length {
+    Length::MaxContent
+}
+
+/// Returns a `Length` representing the size an element's content takes up, clamped to the given
+/// limit unless the content can't shrink that far.
+pub fn fit_content(limit: impl Into<DefiniteLength>) -> Length {
+    Length::FitContent(limit.into())
+}
+
 impl From<Pixels> for Length {
     fn from(pixels: Pixels) -> Self {
         Self::Definite(pixels.into())
@@ -3681,6 +3716,59 @@ impl From<GridPlacement> for taffy::GridPlacement {
     }
 }
 
+/// The size of a column or row track in a grid layout.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub enum GridTrack {
+    /// A fixed size, or a fraction of the grid's size.
+    Definite(DefiniteLength),
+    /// A share of the space left over after the other tracks are sized, like CSS's `fr` unit.
+    Fraction(f32),
+    /// Sized to fit the track's content, growing to fill the space left over.
+    Auto,
+    /// The smallest size that fits the track's content.
+    MinContent,
+    /// The largest size the track's content takes up.
+    MaxContent,
+    /// The size the track's content takes up, but no larger than the given length unless the
+    /// content can't shrink that far.
+    FitContent(DefiniteLength),
+}
+
+/// Returns a grid track taking the given share of a grid's free space, like CSS's `fr` unit.
+pub fn fr(fraction: f32) -> GridTrack {
+    GridTrack::Fraction(fraction)
+}
+
+impl From<Pixels> for GridTrack {
+    fn from(pixels: Pixels) -> Self {
+        Self::Definite(pixels.into())
+    }
+}
+
+impl From<Rems> for GridTrack {
+    fn from(rems: Rems) -> Self {
+        Self::Definite(rems.into())
+    }
+}
+
+impl From<DefiniteLength> for GridTrack {
+    fn from(length: DefiniteLength) -> Self {
+        Self::Definite(length)
+    }
+}
+
+impl From<Length> for GridTrack {
+    fn from(length: Length) -> Self {
+        match length {
+            Length::Definite(length) => Self::Definite(length),
+            Length::Auto => Self::Auto,
+            Length::MinContent => Self::MinContent,
+            Length::MaxContent => Self::MaxContent,
+            Length::FitContent(limit) => Self::FitContent(limit),
+        }
+    }
+}
+
 /// Provides a trait for types that can calculate half of their value.
 ///
 /// The `Half` trait is used for types that can be evenly divided, returning a new instance of the same type
@@ -3834,7 +3922,7 @@ impl IsZero for Length {
     fn is_zero(&self) -> bool {
         match self {
             Length::Definite(length) => length.is_zero(),
-            Length::Auto => false,
+            Length::Auto | Length::MinContent | Length::MaxContent | Length::FitContent(_) => false,
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    Bounds, Capslock, Context, Empty, IntoElement, Keystroke, Modifiers, Pixels, Point, Render,
-    Window, point, seal::Sealed,
+    Bounds, Capslock, Context, Empty, IntoElement, Keystroke, Modifiers, Pixels, Point, Radians,
+    Render, Size, Window, point, seal::Sealed, size,
 };
 use smallvec::SmallVec;
 use std::{any::Any, fmt::Debug, ops::Deref, path::PathBuf};
@@ -81,7 +81,7 @@ impl Deref for ModifiersChangedEvent {
 
 /// The phase of a touch motion event.
 /// Based on the winit enum of the same name.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum TouchPhase {
     /// The touch started.
     Started,
@@ -109,6 +109,19 @@ pub struct MouseDownEvent {
 
     /// Whether this is the first, focusing click.
     pub first_mouse: bool,
+
+    /// The kind of device that pressed the button.
+    pub pointer_kind: PointerKind,
+}
+
+/// The kind of device that produced a pointer event.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PointerKind {
+    /// A mouse, trackpad or other positioning device.
+    #[default]
+    Mouse,
+    /// A touch screen, whose touches are reported as presses of the left mouse button.
+    Touch,
 }
 
 impl Sealed for MouseDownEvent {}
@@ -174,6 +187,20 @@ pub struct MouseClickEvent {
     pub up: MouseUpEvent,
 }
 
+/// A long press, generated when the left mouse button or a touch is held down without moving for
+/// a delay. See [`StatefulInteractiveElement::on_long_press`](crate::StatefulInteractiveElement::on_long_press).
+#[derive(Clone, Debug, Default)]
+pub struct LongPressEvent {
+    /// The position of the press on the window.
+    pub position: Point<Pixels>,
+
+    /// The modifiers that were held down when the press started.
+    pub modifiers: Modifiers,
+
+    /// The kind of device that pressed.
+    pub pointer_kind: PointerKind,
+}
+
 /// A click event that was generated by a keyboard button being pressed and released.
 #[derive(Clone, Debug, Default)]
 pub struct KeyboardClickEvent {
@@ -250,6 +277,19 @@ impl ClickEvent {
         }
     }
 
+    /// Returns if this was a middle click
+    ///
+    /// `Keyboard`: false
+    /// `Mouse`: Whether the middle button was pressed and released
+    pub fn is_middle_click(&self) -> bool {
+        match self {
+            ClickEvent::Keyboard(_) => false,
+            ClickEvent::Mouse(event) => {
+                event.down.button == MouseButton::Middle && event.up.button == MouseButton::Middle
+            }
+        }
+    }
+
     /// Returns whether the click was a standard click
     ///
     /// `Keyboard`: Always true
@@ -345,6 +385,59 @@ pub enum NavigationDirection {
     Forward,
 }
 
+/// A set of mouse buttons, such as the buttons currently held down in a window.
+#[derive(Hash, Default, PartialEq, Eq, Copy, Clone, Debug)]
+pub struct MouseButtonFlags(u8);
+
+impl MouseButtonFlags {
+    fn bit(button: MouseButton) -> u8 {
+        match button {
+            MouseButton::Left => 1 << 0,
+            MouseButton::Right => 1 << 1,
+            MouseButton::Middle => 1 << 2,
+            MouseButton::Navigate(NavigationDirection::Back) => 1 << 3,
+            MouseButton::Navigate(NavigationDirection::Forward) => 1 << 4,
+        }
+    }
+
+    /// Returns true if the given button is in this set.
+    pub fn contains(&self, button: MouseButton) -> bool {
+        self.0 & Self::bit(button) != 0
+    }
+
+    /// Returns true if no buttons are in this set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Add the given button to this set.
+    pub fn insert(&mut self, button: MouseButton) {
+        self.0 |= Self::bit(button);
+    }
+
+    /// Remove the given button from this set.
+    pub fn remove(&mut self, button: MouseButton) {
+        self.0 &= !Self::bit(button);
+    }
+
+    /// Iterate over the buttons in this set.
+    pub fn iter(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        MouseButton::all()
+            .into_iter()
+            .filter(|button| self.contains(*button))
+    }
+}
+
+impl FromIterator<MouseButton> for MouseButtonFlags {
+    fn from_iter<T: IntoIterator<Item = MouseButton>>(iter: T) -> Self {
+        let mut flags = Self::default();
+        for button in iter {
+            flags.insert(button);
+        }
+        flags
+    }
+}
+
 /// A mouse move event from the platform.
 #[derive(Clone, Debug, Default)]
 pub struct MouseMoveEvent {
@@ -387,6 +480,23 @@ pub struct ScrollWheelEvent {
 
     /// The phase of the touch event.
     pub touch_phase: TouchPhase,
+
+    /// The phase of the momentum scrolling that continues after a fling, or `None` when the
+    /// scroll comes directly from the user. Only reported on macOS.
+    pub momentum_phase: Option<TouchPhase>,
+}
+
+impl ScrollWheelEvent {
+    /// The change in scroll position in pixels, converting line and page deltas with
+    /// [`ScrollDelta::pixel_delta`].
+    pub fn delta_in_pixels(&self, line_height: Pixels) -> Point<Pixels> {
+        self.delta.pixel_delta(line_height)
+    }
+
+    /// Whether this event is part of the momentum scrolling that continues after a fling.
+    pub fn is_momentum(&self) -> bool {
+        self.momentum_phase.is_some()
+    }
 }
 
 impl Sealed for ScrollWheelEvent {}
@@ -405,6 +515,71 @@ impl Deref for ScrollWheelEvent {
     }
 }
 
+/// A pinch gesture from the platform, e.g. on a trackpad.
+#[derive(Clone, Debug, Default)]
+pub struct PinchEvent {
+    /// The position of the center of the gesture on the window.
+    pub position: Point<Pixels>,
+
+    /// The change in scale since the previous event of this gesture, e.g. `0.1` when content
+    /// should be zoomed in by ten percent, and `-0.1` when it should be zoomed out.
+    pub scale_delta: f32,
+
+    /// The modifiers that were held down during the gesture.
+    pub modifiers: Modifiers,
+
+    /// The phase of the gesture.
+    pub phase: TouchPhase,
+}
+
+impl Sealed for PinchEvent {}
+impl InputEvent for PinchEvent {
+    fn to_platform_input(self) -> PlatformInput {
+        PlatformInput::Pinch(self)
+    }
+}
+impl MouseEvent for PinchEvent {}
+
+impl Deref for PinchEvent {
+    type Target = Modifiers;
+
+    fn deref(&self) -> &Self::Target {
+        &self.modifiers
+    }
+}
+
+/// A two-finger rotation gesture from the platform, e.g. on a trackpad.
+#[derive(Clone, Debug, Default)]
+pub struct RotateGestureEvent {
+    /// The position of the center of the gesture on the window.
+    pub position: Point<Pixels>,
+
+    /// The change in rotation since the previous event of this gesture, clockwise.
+    pub rotation_delta: Radians,
+
+    /// The modifiers that were held down during the gesture.
+    pub modifiers: Modifiers,
+
+    /// The phase of the gesture.
+    pub phase: TouchPhase,
+}
+
+impl Sealed for RotateGestureEvent {}
+impl InputEvent for RotateGestureEvent {
+    fn to_platform_input(self) -> PlatformInput {
+        PlatformInput::RotateGesture(self)
+    }
+}
+impl MouseEvent for RotateGestureEvent {}
+
+impl Deref for RotateGestureEvent {
+    type Target = Modifiers;
+
+    fn deref(&self) -> &Self::Target {
+        &self.modifiers
+    }
+}
+
 /// The scroll delta for a scroll wheel event.
 #[derive(Clone, Copy, Debug)]
 pub enum ScrollDelta {
@@ -412,8 +587,13 @@ pub enum ScrollDelta {
     Pixels(Point<Pixels>),
     /// An inexact scroll delta in lines.
     Lines(Point<f32>),
+    /// An inexact scroll delta in pages, from wheels that are configured to scroll by pages.
+    Page(Point<f32>),
 }
 
+/// The number of lines a page is converted to when the size of the page isn't known.
+const LINES_PER_PAGE: f32 = 20.;
+
 impl Default for ScrollDelta {
     fn default() -> Self {
         Self::Lines(Default::default())
@@ -425,15 +605,30 @@ impl ScrollDelta {
     pub fn precise(&self) -> bool {
         match self {
             ScrollDelta::Pixels(_) => true,
-            ScrollDelta::Lines(_) => false,
+            ScrollDelta::Lines(_) | ScrollDelta::Page(_) => false,
         }
     }
 
-    /// Converts this scroll event into exact pixels.
+    /// Converts this scroll event into exact pixels. Pages are converted as if they were 20 lines
+    /// long, use [`Self::pixel_delta_in_page`] when the size of the page is known.
     pub fn pixel_delta(&self, line_height: Pixels) -> Point<Pixels> {
+        let page_length = line_height * LINES_PER_PAGE;
+        self.pixel_delta_in_page(line_height, size(page_length, page_length))
+    }
+
+    /// Converts this scroll event into exact pixels, scrolling by the given page size for page
+    /// deltas.
+    pub fn pixel_delta_in_page(
+        &self,
+        line_height: Pixels,
+        page_size: Size<Pixels>,
+    ) -> Point<Pixels> {
         match self {
             ScrollDelta::Pixels(delta) => *delta,
             ScrollDelta::Lines(delta) => point(line_height * delta.x, line_height * delta.y),
+            ScrollDelta::Page(delta) => {
+                point(page_size.width * delta.x, page_size.height * delta.y)
+            }
         }
     }
 
@@ -475,6 +670,22 @@ impl ScrollDelta {
                 ScrollDelta::Lines(point(x, y))
             }
 
+            (ScrollDelta::Page(a), ScrollDelta::Page(b)) => {
+                let x = if a.x.signum() == b.x.signum() {
+                    a.x + b.x
+                } else {
+                    b.x
+                };
+
+                let y = if a.y.signum() == b.y.signum() {
+                    a.y + b.y
+                } else {
+                    b.y
+                };
+
+                ScrollDelta::Page(point(x, y))
+            }
+
             _ => other,
         }
     }
@@ -577,6 +788,10 @@ pub enum PlatformInput {
     MouseExited(MouseExitEvent),
     /// The scroll wheel was used.
     ScrollWheel(ScrollWheelEvent),
+    /// A pinch gesture was performed.
+    Pinch(PinchEvent),
+    /// A rotation gesture was performed.
+    RotateGesture(RotateGestureEvent),
     /// Files were dragged and dropped onto the window.
     FileDrop(FileDropEvent),
 }
@@ -592,6 +807,8 @@ impl PlatformInput {
             PlatformInput::MouseMove(event) => Some(event),
             PlatformInput::MouseExited(event) => Some(event),
             PlatformInput::ScrollWheel(event) => Some(event),
+            PlatformInput::Pinch(event) => Some(event),
+            PlatformInput::RotateGesture(event) => Some(event),
             PlatformInput::FileDrop(event) => Some(event),
         }
     }
@@ -606,6 +823,8 @@ impl PlatformInput {
             PlatformInput::MouseMove(_) => None,
             PlatformInput::MouseExited(_) => None,
             PlatformInput::ScrollWheel(_) => None,
+            PlatformInput::Pinch(_) => None,
+            PlatformInput::RotateGesture(_) => None,
             PlatformInput::FileDrop(_) => None,
         }
     }
@@ -614,9 +833,21 @@ impl PlatformInput {
 #[cfg(test)]
 mod test {
 
+    use std::{
+        cell::{Cell, RefCell},
+        path::PathBuf,
+        rc::Rc,
+        time::Duration,
+    };
+
     use crate::{
-        self as gpui, AppContext as _, Context, FocusHandle, InteractiveElement, IntoElement,
-        KeyBinding, Keystroke, ParentElement, Render, TestAppContext, Window, div,
+        self as gpui, AppContext as _, Bounds, ClickEvent, Context, CursorStyle, Deferred,
+        DevicePixels, EmptyView, FocusHandle, HitboxShape, InteractiveElement, IntoElement,
+        KeyBinding, KeyDownEvent, KeyUpEvent, KeyboardButton, Keystroke, LongPressEvent, Modifiers,
+        MouseButton, ParentElement, PinchEvent, Pixels, PointerKind, Render, RotateGestureEvent,
+        ScrollDelta, ScrollWheelEvent, StatefulInteractiveElement, Styled, TestAppContext,
+        TouchPhase, VisualTestContext, Window, blue, deferred, div, green, point, px, radians, red,
+        size,
     };
 
     struct TestView {
@@ -683,4 +914,1072 @@ mod test {
             })
             .unwrap();
     }
+
+    #[gpui::test]
+    fn test_hitbox_shape(cx: &mut TestAppContext) {
+        struct TestView {
+            presses: Rc<Cell<(usize, usize)>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let round_presses = self.presses.clone();
+                let triangle_presses = self.presses.clone();
+                div()
+                    .flex()
+                    .child(div().size(px(20.)).rounded_full().on_mouse_down(
+                        MouseButton::Left,
+                        move |_, _, _| {
+                            let (round, triangle) = round_presses.get();
+                            round_presses.set((round + 1, triangle));
+                        },
+                    ))
+                    .child(
+                        div()
+                            .size(px(20.))
+                            .hitbox_shape(HitboxShape::Polygon(vec![
+                                point(px(0.), px(0.)),
+                                point(px(20.), px(0.)),
+                                point(px(0.), px(20.)),
+                            ]))
+                            .on_mouse_down(MouseButton::Left, move |_, _, _| {
+                                let (round, triangle) = triangle_presses.get();
+                                triangle_presses.set((round, triangle + 1));
+                            }),
+                    )
+            }
+        }
+
+        let presses = Rc::new(Cell::new((0, 0)));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            presses: presses.clone(),
+        });
+
+        cx.simulate_click(point(px(1.), px(1.)), Modifiers::none());
+        assert_eq!(presses.get(), (0, 0), "the rounded corner shouldn't be hit");
+        cx.simulate_click(point(px(10.), px(1.)), Modifiers::none());
+        assert_eq!(presses.get(), (1, 0));
+
+        cx.simulate_click(point(px(38.), px(18.)), Modifiers::none());
+        assert_eq!(
+            presses.get(),
+            (1, 0),
+            "points outside the polygon shouldn't be hit"
+        );
+        cx.simulate_click(point(px(22.), px(2.)), Modifiers::none());
+        assert_eq!(presses.get(), (1, 1));
+    }
+
+    #[test]
+    fn test_ellipse_hitbox_shape() {
+        let bounds = Bounds::new(point(px(10.), px(10.)), size(px(40.), px(20.)));
+        let ellipse = HitboxShape::Ellipse;
+        assert!(ellipse.contains(&bounds, point(px(30.), px(20.))));
+        assert!(ellipse.contains(&bounds, point(px(49.), px(20.))));
+        assert!(!ellipse.contains(&bounds, point(px(12.), px(12.))));
+    }
+
+    #[test]
+    fn test_scroll_delta_in_pixels() {
+        let line_height = px(10.);
+        let event = |delta| ScrollWheelEvent {
+            delta,
+            ..Default::default()
+        };
+        assert_eq!(
+            event(ScrollDelta::Pixels(point(px(3.), px(-4.)))).delta_in_pixels(line_height),
+            point(px(3.), px(-4.))
+        );
+        assert_eq!(
+            event(ScrollDelta::Lines(point(0., -3.))).delta_in_pixels(line_height),
+            point(px(0.), px(-30.))
+        );
+        assert_eq!(
+            event(ScrollDelta::Page(point(0., 1.))).delta_in_pixels(line_height),
+            point(px(0.), px(200.)),
+            "pages of unknown size should be converted to 20 lines"
+        );
+        assert_eq!(
+            ScrollDelta::Page(point(0.5, -1.))
+                .pixel_delta_in_page(line_height, size(px(300.), px(400.))),
+            point(px(150.), px(-400.))
+        );
+        assert_eq!(
+            ScrollDelta::Page(point(0., 1.))
+                .coalesce(ScrollDelta::Page(point(0., 2.)))
+                .pixel_delta_in_page(line_height, size(px(100.), px(100.))),
+            point(px(0.), px(300.))
+        );
+    }
+
+    #[gpui::test]
+    fn test_on_hover_with_delay(cx: &mut TestAppContext) {
+        struct TestView {
+            hovers: Rc<RefCell<Vec<bool>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let hovers = self.hovers.clone();
+                div().size(px(100.)).child(
+                    div()
+                        .id("delayed")
+                        .size(px(20.))
+                        .on_hover_with_delay(Duration::from_millis(300), move |hovered, _, _| {
+                            hovers.borrow_mut().push(*hovered)
+                        }),
+                )
+            }
+        }
+
+        let hovers = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            hovers: hovers.clone(),
+        });
+
+        cx.simulate_mouse_move(point(px(10.), px(10.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.simulate_mouse_move(point(px(50.), px(50.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(500));
+        assert!(
+            hovers.borrow().is_empty(),
+            "hovers that end before the delay shouldn't be reported"
+        );
+
+        cx.simulate_mouse_move(point(px(10.), px(10.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(300));
+        assert_eq!(*hovers.borrow(), [true]);
+        cx.simulate_mouse_move(point(px(50.), px(50.)), None, Modifiers::none());
+        assert_eq!(
+            *hovers.borrow(),
+            [true, false],
+            "the end of a hover should be reported immediately"
+        );
+    }
+
+    #[gpui::test]
+    fn test_hover_respects_occlusion(cx: &mut TestAppContext) {
+        struct TestView {
+            hovered: Rc<RefCell<Vec<&'static str>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let track = |name: &'static str| {
+                    let hovered = self.hovered.clone();
+                    move |is_hovered: &bool, _: &mut Window, _: &mut gpui::App| {
+                        let mut hovered = hovered.borrow_mut();
+                        if *is_hovered {
+                            hovered.push(name);
+                        } else {
+                            hovered.retain(|hovered_name| *hovered_name != name);
+                        }
+                    }
+                };
+                div()
+                    .id("parent")
+                    .size(px(100.))
+                    .on_hover(track("parent"))
+                    .child(
+                        div()
+                            .id("drop_target")
+                            .absolute()
+                            .size(px(100.))
+                            .hover_even_when_occluded()
+                            .on_hover(track("drop_target")),
+                    )
+                    .child(
+                        div()
+                            .id("back")
+                            .absolute()
+                            .size(px(40.))
+                            .on_hover(track("back")),
+                    )
+                    .child(
+                        div()
+                            .id("front")
+                            .absolute()
+                            .top(px(20.))
+                            .left(px(20.))
+                            .size(px(40.))
+                            .on_hover(track("front")),
+                    )
+            }
+        }
+
+        let hovered = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            hovered: hovered.clone(),
+        });
+        let sorted_hovered = || {
+            let mut sorted = hovered.borrow().clone();
+            sorted.sort();
+            sorted
+        };
+
+        cx.simulate_mouse_move(point(px(10.), px(10.)), None, Modifiers::none());
+        assert_eq!(sorted_hovered(), ["back", "drop_target", "parent"]);
+
+        cx.simulate_mouse_move(point(px(30.), px(30.)), None, Modifiers::none());
+        assert_eq!(
+            sorted_hovered(),
+            ["drop_target", "front", "parent"],
+            "only the sibling in front should be hovered where they overlap"
+        );
+
+        cx.simulate_mouse_move(point(px(80.), px(80.)), None, Modifiers::none());
+        assert_eq!(sorted_hovered(), ["drop_target", "parent"]);
+    }
+
+    #[gpui::test]
+    fn test_deferred_draw_occludes_hover(cx: &mut TestAppContext) {
+        struct TestView {
+            hovered: Rc<RefCell<Vec<(&'static str, bool)>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let anchor_hovered = self.hovered.clone();
+                let overlay_hovered = self.hovered.clone();
+                div().size(px(100.)).child(
+                    div()
+                        .id("anchor")
+                        .size(px(40.))
+                        .on_hover(move |hovered, _, _| {
+                            anchor_hovered.borrow_mut().push(("anchor", *hovered))
+                        })
+                        .child(deferred(
+                            div()
+                                .id("overlay")
+                                .absolute()
+                                .top(px(20.))
+                                .size(px(40.))
+                                .on_hover(move |hovered, _, _| {
+                                    overlay_hovered.borrow_mut().push(("overlay", *hovered))
+                                }),
+                        )),
+                )
+            }
+        }
+
+        let hovered = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            hovered: hovered.clone(),
+        });
+
+        cx.simulate_mouse_move(point(px(10.), px(10.)), None, Modifiers::none());
+        assert_eq!(*hovered.borrow(), [("anchor", true)]);
+
+        hovered.borrow_mut().clear();
+        cx.simulate_mouse_move(point(px(10.), px(30.)), None, Modifiers::none());
+        assert_eq!(
+            *hovered.borrow(),
+            [("overlay", true), ("anchor", false)],
+            "a deferred overlay should occlude the element it's anchored to"
+        );
+    }
+
+    #[gpui::test]
+    fn test_deferred_priority_tiers(cx: &mut TestAppContext) {
+        struct DragPreview;
+
+        impl Render for DragPreview {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().size(px(10.)).bg(blue())
+            }
+        }
+
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .size(px(100.))
+                    .child(
+                        div()
+                            .id("source")
+                            .size(px(20.))
+                            .on_drag((), |_, _, _, cx| cx.new(|_| DragPreview)),
+                    )
+                    .child(
+                        deferred(div().size(px(10.)).bg(green()))
+                            .with_priority(Deferred::DRAG_PREVIEW_PRIORITY + 1),
+                    )
+                    .child(deferred(div().size(px(10.)).bg(red())).with_priority(1))
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        let painted_colors = |cx: &mut gpui::VisualTestContext| {
+            cx.update(|window, _| {
+                window
+                    .rendered_frame
+                    .scene
+                    .quads
+                    .iter()
+                    .map(|quad| quad.background)
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            painted_colors(cx),
+            [red().into(), green().into()],
+            "higher priorities should paint later"
+        );
+
+        cx.simulate_mouse_down(point(px(5.), px(5.)), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_move(
+            point(px(50.), px(50.)),
+            Some(MouseButton::Left),
+            Modifiers::none(),
+        );
+        assert_eq!(
+            painted_colors(cx),
+            [red().into(), blue().into(), green().into()],
+            "the drag preview should paint between the deferred priority tiers"
+        );
+    }
+
+    #[gpui::test]
+    fn test_tooltip_respects_occlusion(cx: &mut TestAppContext) {
+        struct TestView {
+            tooltips_built: Rc<Cell<usize>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let tooltips_built = self.tooltips_built.clone();
+                div()
+                    .size(px(100.))
+                    .child(
+                        div()
+                            .id("back")
+                            .absolute()
+                            .size(px(40.))
+                            .tooltip(move |_, cx| {
+                                tooltips_built.set(tooltips_built.get() + 1);
+                                cx.new(|_| EmptyView).into()
+                            }),
+                    )
+                    .child(
+                        div()
+                            .id("front")
+                            .absolute()
+                            .top(px(20.))
+                            .size(px(40.))
+                            .on_hover(|_, _, _| {}),
+                    )
+            }
+        }
+
+        let tooltips_built = Rc::new(Cell::new(0));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            tooltips_built: tooltips_built.clone(),
+        });
+
+        cx.simulate_mouse_move(point(px(10.), px(30.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_secs(1));
+        assert_eq!(
+            tooltips_built.get(),
+            0,
+            "an occluded element shouldn't show its tooltip"
+        );
+
+        cx.simulate_mouse_move(point(px(10.), px(10.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_secs(1));
+        assert_eq!(tooltips_built.get(), 1);
+    }
+
+    #[gpui::test]
+    fn test_cursor_per_state(cx: &mut TestAppContext) {
+        struct TestView {
+            disabled: bool,
+            clicks: Rc<Cell<usize>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let clicks = self.clicks.clone();
+                div().size(px(100.)).cursor(CursorStyle::IBeam).child(
+                    div()
+                        .id("button")
+                        .size(px(20.))
+                        .cursor(CursorStyle::PointingHand)
+                        .cursor_when_active(CursorStyle::ClosedHand)
+                        .cursor_when_disabled(CursorStyle::OperationNotAllowed)
+                        .disabled(self.disabled)
+                        .on_click(move |_, _, _| clicks.set(clicks.get() + 1)),
+                )
+            }
+        }
+
+        let clicks = Rc::new(Cell::new(0));
+        let (view, cx) = cx.add_window_view(|_, _| TestView {
+            disabled: false,
+            clicks: clicks.clone(),
+        });
+        let cursor = |cx: &mut gpui::VisualTestContext| {
+            cx.update(|window, _| window.rendered_frame.cursor_style(window))
+        };
+
+        cx.simulate_mouse_move(point(px(10.), px(10.)), None, Modifiers::none());
+        assert_eq!(cursor(cx), Some(CursorStyle::PointingHand));
+        cx.simulate_mouse_down(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(cursor(cx), Some(CursorStyle::ClosedHand));
+        cx.simulate_mouse_up(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(cursor(cx), Some(CursorStyle::PointingHand));
+        assert_eq!(clicks.get(), 1);
+
+        cx.simulate_mouse_move(point(px(50.), px(50.)), None, Modifiers::none());
+        assert_eq!(
+            cursor(cx),
+            Some(CursorStyle::IBeam),
+            "the element's cursor shouldn't apply outside of its hitbox"
+        );
+
+        view.update(cx, |view, cx| {
+            view.disabled = true;
+            cx.notify();
+        });
+        cx.simulate_click(point(px(10.), px(10.)), Modifiers::none());
+        assert_eq!(cursor(cx), Some(CursorStyle::OperationNotAllowed));
+        assert_eq!(clicks.get(), 1, "disabled elements shouldn't be clicked");
+    }
+
+    #[gpui::test]
+    fn test_group_hover_and_active(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().flex().flex_col().children((0..2).map(|row| {
+                    div().id(row).group("row").h(px(20.)).w(px(100.)).child(
+                        div()
+                            .size(px(10.))
+                            .group_hover("row", |style| style.bg(red()))
+                            .group_active("row", |style| style.bg(blue())),
+                    )
+                }))
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        let painted_icons = |cx: &mut gpui::VisualTestContext| {
+            cx.update(|window, _| {
+                let scale_factor = window.scale_factor();
+                window
+                    .rendered_frame
+                    .scene
+                    .quads
+                    .iter()
+                    .map(|quad| {
+                        let origin_y = quad.bounds.origin.y.0 / scale_factor;
+                        (origin_y, quad.background == blue().into())
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert!(painted_icons(cx).is_empty());
+        cx.simulate_mouse_move(point(px(50.), px(25.)), None, Modifiers::none());
+        assert_eq!(
+            painted_icons(cx),
+            [(20., false)],
+            "only the icon of the hovered row should show"
+        );
+
+        cx.simulate_mouse_down(
+            point(px(50.), px(25.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(painted_icons(cx), [(20., true)]);
+        cx.simulate_mouse_up(
+            point(px(50.), px(25.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(painted_icons(cx), [(20., false)]);
+
+        cx.simulate_mouse_move(point(px(50.), px(5.)), None, Modifiers::none());
+        assert_eq!(painted_icons(cx), [(0., false)]);
+    }
+
+    #[gpui::test]
+    fn test_on_secondary_and_middle_click(cx: &mut TestAppContext) {
+        struct TestView {
+            clicks: Rc<RefCell<Vec<&'static str>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let clicks = self.clicks.clone();
+                let secondary_clicks = self.clicks.clone();
+                let middle_clicks = self.clicks.clone();
+                div().size(px(100.)).child(
+                    div()
+                        .id("clickable")
+                        .size(px(20.))
+                        .on_click(move |_, _, _| clicks.borrow_mut().push("click"))
+                        .on_secondary_click(move |event, _, _| {
+                            assert!(event.is_right_click());
+                            secondary_clicks.borrow_mut().push("secondary")
+                        })
+                        .on_middle_click(move |event, _, _| {
+                            assert!(event.is_middle_click());
+                            middle_clicks.borrow_mut().push("middle")
+                        }),
+                )
+            }
+        }
+
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            clicks: clicks.clone(),
+        });
+        let position = point(px(10.), px(10.));
+        let press = |cx: &mut gpui::VisualTestContext, button, release_position| {
+            cx.simulate_mouse_down(position, button, Modifiers::none());
+            cx.simulate_mouse_up(release_position, button, Modifiers::none());
+        };
+
+        press(cx, MouseButton::Right, position);
+        press(cx, MouseButton::Middle, position);
+        press(cx, MouseButton::Left, position);
+        assert_eq!(*clicks.borrow(), ["secondary", "middle", "click"]);
+
+        clicks.borrow_mut().clear();
+        press(cx, MouseButton::Right, point(px(50.), px(50.)));
+        assert!(
+            clicks.borrow().is_empty(),
+            "releasing outside of the element shouldn't click"
+        );
+
+        cx.simulate_mouse_down(position, MouseButton::Middle, Modifiers::none());
+        cx.simulate_mouse_move(
+            point(px(18.), px(18.)),
+            Some(MouseButton::Middle),
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_up(
+            point(px(18.), px(18.)),
+            MouseButton::Middle,
+            Modifiers::none(),
+        );
+        assert!(
+            clicks.borrow().is_empty(),
+            "moving past the drag threshold should cancel the click"
+        );
+    }
+
+    #[gpui::test]
+    fn test_mouse_capture_listeners(cx: &mut TestAppContext) {
+        struct TestView {
+            events: Rc<RefCell<Vec<&'static str>>>,
+            stop_in_capture: Rc<Cell<bool>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let first_capture = self.events.clone();
+                let second_capture = self.events.clone();
+                let capture_up = self.events.clone();
+                let child_down = self.events.clone();
+                let child_up = self.events.clone();
+                let stop_in_capture = self.stop_in_capture.clone();
+                div()
+                    .size(px(100.))
+                    .on_mouse_down_capture(MouseButton::Left, move |_, _, cx| {
+                        first_capture.borrow_mut().push("first capture");
+                        if stop_in_capture.get() {
+                            cx.stop_propagation();
+                        }
+                    })
+                    .on_mouse_down_capture(MouseButton::Left, move |_, _, _| {
+                        second_capture.borrow_mut().push("second capture")
+                    })
+                    .on_mouse_up_capture(MouseButton::Left, move |_, _, _| {
+                        capture_up.borrow_mut().push("capture up")
+                    })
+                    .child(
+                        div()
+                            .size(px(20.))
+                            .on_mouse_down(MouseButton::Left, move |_, _, _| {
+                                child_down.borrow_mut().push("child down")
+                            })
+                            .on_mouse_up(MouseButton::Left, move |_, _, _| {
+                                child_up.borrow_mut().push("child up")
+                            }),
+                    )
+            }
+        }
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let stop_in_capture = Rc::new(Cell::new(false));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            events: events.clone(),
+            stop_in_capture: stop_in_capture.clone(),
+        });
+
+        let position = point(px(10.), px(10.));
+        cx.simulate_mouse_down(position, MouseButton::Right, Modifiers::none());
+        cx.simulate_mouse_up(position, MouseButton::Right, Modifiers::none());
+        assert!(events.borrow().is_empty());
+
+        cx.simulate_click(position, Modifiers::none());
+        assert_eq!(
+            *events.borrow(),
+            [
+                "first capture",
+                "second capture",
+                "child down",
+                "capture up",
+                "child up"
+            ]
+        );
+
+        events.borrow_mut().clear();
+        stop_in_capture.set(true);
+        cx.simulate_mouse_down(position, MouseButton::Left, Modifiers::none());
+        assert_eq!(*events.borrow(), ["first capture"]);
+    }
+
+    #[gpui::test]
+    fn test_disabled_element(cx: &mut TestAppContext) {
+        struct TestView {
+            focus_handles: Vec<FocusHandle>,
+            clicks: Rc<Cell<usize>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let clicks = self.clicks.clone();
+                div()
+                    .flex()
+                    .flex_col()
+                    .children(self.focus_handles.iter().enumerate().map(|(ix, handle)| {
+                        div()
+                            .track_focus(handle)
+                            .tab_index(ix as isize)
+                            .disabled(ix == 1)
+                            .h(px(20.))
+                            .w(px(100.))
+                    }))
+                    .child(
+                        div()
+                            .id("disabled")
+                            .size(px(20.))
+                            .disabled(true)
+                            .when_disabled(|style| style.bg(red()))
+                            .on_click(move |_, _, _| clicks.set(clicks.get() + 1)),
+                    )
+            }
+        }
+
+        let clicks = Rc::new(Cell::new(0));
+        let (view, cx) = cx.add_window_view(|_, cx| TestView {
+            focus_handles: (0..3).map(|_| cx.focus_handle()).collect(),
+            clicks: clicks.clone(),
+        });
+
+        cx.simulate_click(point(px(10.), px(70.)), Modifiers::none());
+        assert_eq!(clicks.get(), 0, "disabled elements shouldn't be clicked");
+        cx.update(|window, _| {
+            assert_eq!(
+                window.rendered_frame.cursor_style(window),
+                Some(CursorStyle::OperationNotAllowed)
+            );
+            assert!(
+                window
+                    .rendered_frame
+                    .scene
+                    .quads
+                    .iter()
+                    .any(|quad| quad.background == red().into()),
+                "the disabled style should be painted"
+            );
+        });
+
+        view.update_in(cx, |view, window, _| {
+            window.focus(&view.focus_handles[0]);
+            window.focus_next();
+            assert!(
+                view.focus_handles[2].is_focused(window),
+                "tab traversal should skip disabled elements"
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_pressed_mouse_buttons(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_, _| EmptyView);
+        let pressed_buttons = |cx: &mut gpui::VisualTestContext| {
+            cx.update(|window, _| window.pressed_mouse_buttons())
+        };
+
+        let position = point(px(10.), px(20.));
+        cx.simulate_mouse_down(position, MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_down(position, MouseButton::Right, Modifiers::none());
+        assert_eq!(
+            pressed_buttons(cx).iter().collect::<Vec<_>>(),
+            [MouseButton::Left, MouseButton::Right]
+        );
+        assert_eq!(cx.update(|window, _| window.mouse_position()), position);
+
+        cx.simulate_mouse_up(position, MouseButton::Left, Modifiers::none());
+        assert!(!pressed_buttons(cx).contains(MouseButton::Left));
+        assert!(pressed_buttons(cx).contains(MouseButton::Right));
+
+        cx.simulate_mouse_move(point(px(30.), px(40.)), None, Modifiers::none());
+        assert!(
+            pressed_buttons(cx).is_empty(),
+            "a move without pressed buttons means they were released outside the window"
+        );
+        assert_eq!(
+            cx.update(|window, _| window.mouse_position()),
+            point(px(30.), px(40.))
+        );
+    }
+
+    #[gpui::test]
+    fn test_on_long_press(cx: &mut TestAppContext) {
+        struct TestView {
+            long_presses: Rc<RefCell<Vec<LongPressEvent>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let long_presses = self.long_presses.clone();
+                div()
+                    .id("pressable")
+                    .size(px(100.))
+                    .on_long_press(Duration::from_millis(500), move |event, _, _| {
+                        long_presses.borrow_mut().push(event.clone())
+                    })
+            }
+        }
+
+        let long_presses = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            long_presses: long_presses.clone(),
+        });
+
+        cx.simulate_mouse_down(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.executor().advance_clock(Duration::from_millis(200));
+        cx.simulate_mouse_up(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.executor().advance_clock(Duration::from_millis(500));
+        assert!(
+            long_presses.borrow().is_empty(),
+            "releasing before the delay should cancel the long press"
+        );
+
+        cx.simulate_mouse_down(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            point(px(40.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.executor().advance_clock(Duration::from_millis(500));
+        cx.simulate_mouse_up(
+            point(px(40.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert!(
+            long_presses.borrow().is_empty(),
+            "moving past the slop should cancel the long press"
+        );
+
+        cx.simulate_mouse_down(
+            point(px(20.), px(30.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            point(px(22.), px(30.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.executor().advance_clock(Duration::from_millis(500));
+        let long_presses = long_presses.borrow();
+        assert_eq!(long_presses.len(), 1);
+        assert_eq!(long_presses[0].position, point(px(20.), px(30.)));
+        assert_eq!(long_presses[0].pointer_kind, PointerKind::Mouse);
+    }
+
+    #[gpui::test]
+    fn test_on_double_and_triple_click(cx: &mut TestAppContext) {
+        struct TestView {
+            clicks: Rc<RefCell<Vec<&'static str>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let clicks = self.clicks.clone();
+                let double_clicks = self.clicks.clone();
+                let triple_clicks = self.clicks.clone();
+                div()
+                    .id("clickable")
+                    .size(px(100.))
+                    .on_click(move |_, _, _| clicks.borrow_mut().push("click"))
+                    .on_double_click(move |_, _, _| double_clicks.borrow_mut().push("double"))
+                    .on_triple_click(move |_, _, _| triple_clicks.borrow_mut().push("triple"))
+            }
+        }
+
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            clicks: clicks.clone(),
+        });
+
+        cx.simulate_click(point(px(10.), px(10.)), Modifiers::none());
+        assert_eq!(*clicks.borrow(), ["click"]);
+
+        clicks.borrow_mut().clear();
+        cx.simulate_clicks(point(px(10.), px(10.)), 3, Modifiers::none());
+        assert_eq!(
+            *clicks.borrow(),
+            ["click", "click", "double", "click", "triple"]
+        );
+    }
+
+    #[gpui::test]
+    fn test_on_drag_out(cx: &mut TestAppContext) {
+        struct TestView {
+            clicked: Rc<Cell<bool>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let clicked = self.clicked.clone();
+                div()
+                    .id("file")
+                    .size(px(100.))
+                    .on_click(move |_, _, _| clicked.set(true))
+                    .on_drag_out(vec![PathBuf::from("/tmp/report.pdf")])
+            }
+        }
+
+        let clicked = Rc::new(Cell::new(false));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            clicked: clicked.clone(),
+        });
+
+        cx.simulate_mouse_down(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            point(px(11.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(cx.dragged_out_paths(), None);
+
+        cx.simulate_mouse_move(
+            point(px(30.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_up(
+            point(px(30.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(
+            cx.dragged_out_paths(),
+            Some(vec![PathBuf::from("/tmp/report.pdf")])
+        );
+        // The element is snapshotted at the window's scale factor, where the drag started.
+        assert_eq!(
+            cx.dragged_out_image_size(),
+            Some((
+                size(DevicePixels(200), DevicePixels(200)),
+                point(DevicePixels(60), DevicePixels(20))
+            ))
+        );
+        assert!(!clicked.get(), "dragging out shouldn't also report a click");
+    }
+
+    #[gpui::test]
+    fn test_disabled_element_does_not_drag_out(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .id("file")
+                    .size(px(100.))
+                    .disabled(true)
+                    .on_drag_out(vec![PathBuf::from("/tmp/report.pdf")])
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+
+        cx.simulate_mouse_down(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            point(px(30.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_up(
+            point(px(30.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(cx.dragged_out_paths(), None);
+    }
+
+    #[gpui::test]
+    fn test_on_pinch_and_rotate_gesture(cx: &mut TestAppContext) {
+        struct TestView {
+            scale: Rc<Cell<f32>>,
+            rotation: Rc<Cell<f32>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let scale = self.scale.clone();
+                let rotation = self.rotation.clone();
+                div()
+                    .size(px(100.))
+                    .on_pinch(move |event, _, _| scale.set(scale.get() * (1. + event.scale_delta)))
+                    .on_rotate_gesture(move |event, _, _| {
+                        rotation.set(rotation.get() + event.rotation_delta.0)
+                    })
+            }
+        }
+
+        let scale = Rc::new(Cell::new(1.));
+        let rotation = Rc::new(Cell::new(0.));
+        let (_, cx) = cx.add_window_view(|_, _| TestView {
+            scale: scale.clone(),
+            rotation: rotation.clone(),
+        });
+
+        for (phase, scale_delta) in [
+            (TouchPhase::Started, 0.),
+            (TouchPhase::Moved, 1.),
+            (TouchPhase::Ended, 0.),
+        ] {
+            cx.simulate_event(PinchEvent {
+                position: point(px(50.), px(50.)),
+                scale_delta,
+                modifiers: Modifiers::none(),
+                phase,
+            });
+        }
+        assert_eq!(scale.get(), 2.);
+
+        cx.simulate_event(RotateGestureEvent {
+            position: point(px(50.), px(50.)),
+            rotation_delta: radians(0.5),
+            modifiers: Modifiers::none(),
+            phase: TouchPhase::Moved,
+        });
+        assert_eq!(rotation.get(), 0.5);
+
+        cx.simulate_event(PinchEvent {
+            position: point(px(150.), px(50.)),
+            scale_delta: 1.,
+            modifiers: Modifiers::none(),
+            phase: TouchPhase::Moved,
+        });
+        assert_eq!(scale.get(), 2., "pinches outside the element are ignored");
+    }
+
+    #[gpui::test]
+    fn test_keyboard_activation(cx: &mut TestAppContext) {
+        struct TestView {
+            focus_handle: FocusHandle,
+            activate_on_key: bool,
+            clicks: Rc<RefCell<Vec<KeyboardButton>>>,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let clicks = self.clicks.clone();
+                div()
+                    .id("button")
+                    .track_focus(&self.focus_handle)
+                    .activate_on_key(self.activate_on_key)
+                    .on_click(move |event, _, _| {
+                        if let ClickEvent::Keyboard(event) = event {
+                            clicks.borrow_mut().push(event.button);
+                        }
+                    })
+            }
+        }
+
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let (view, cx) = cx.add_window_view(|_, cx| TestView {
+            focus_handle: cx.focus_handle(),
+            activate_on_key: true,
+            clicks: clicks.clone(),
+        });
+        cx.update(|window, cx| window.focus(&view.read(cx).focus_handle.clone()));
+
+        let key_down = |key: &str, is_held| KeyDownEvent {
+            keystroke: Keystroke::parse(key).unwrap(),
+            is_held,
+            prefer_character_input: false,
+        };
+        let key_up = |key: &str| KeyUpEvent {
+            keystroke: Keystroke::parse(key).unwrap(),
+        };
+
+        cx.simulate_event(key_up("space"));
+        assert!(
+            clicks.borrow().is_empty(),
+            "releasing space that wasn't pressed on the element shouldn't click"
+        );
+
+        cx.simulate_event(key_down("space", false));
+        cx.simulate_event(key_down("space", true));
+        cx.simulate_event(key_down("space", true));
+        assert!(clicks.borrow().is_empty(), "space clicks on release");
+        cx.simulate_event(key_up("space"));
+        cx.simulate_event(key_up("enter"));
+        assert_eq!(
+            *clicks.borrow(),
+            [KeyboardButton::Space, KeyboardButton::Enter]
+        );
+
+        clicks.borrow_mut().clear();
+        view.update(cx, |view, cx| {
+            view.activate_on_key = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_event(key_down("space", false));
+        cx.simulate_event(key_up("space"));
+        cx.simulate_event(key_up("enter"));
+        assert!(clicks.borrow().is_empty());
+    }
 }
@@ -11,7 +11,7 @@ use lyon::tessellation::{
 pub use lyon::math::Transform;
 pub use lyon::tessellation::{FillOptions, FillRule, StrokeOptions};
 
-use crate::{Path, Pixels, Point, point, px};
+use crate::{Background, Path, Pixels, Point, black, point, px};
 
 /// Style of the PathBuilder
 pub enum PathStyle {
@@ -21,6 +21,124 @@ pub enum PathStyle {
     Fill(FillOptions),
 }
 
+/// How the outline of a path is stroked with [`Window::stroke_path`](crate::Window::stroke_path).
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrokeStyle {
+    /// The width of the stroke, centered on the outline.
+    pub width: Pixels,
+    /// The shape of the ends of open sub-paths and of dashes.
+    pub cap: LineCap,
+    /// The shape of the corners where segments meet.
+    pub join: LineJoin,
+    /// The longest a [`LineJoin::Miter`] corner gets, as a multiple of the stroke width, before
+    /// it's beveled instead. Sharp angles otherwise produce very long spikes.
+    pub miter_limit: f32,
+    /// The dashes to break the stroke into, or `None` for a solid stroke.
+    pub dash: Option<DashPattern>,
+    /// The color of the stroke.
+    pub color: Background,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            width: px(1.),
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: StrokeOptions::DEFAULT_MITER_LIMIT,
+            dash: None,
+            color: black().into(),
+        }
+    }
+}
+
+impl StrokeStyle {
+    fn options(&self) -> StrokeOptions {
+        StrokeOptions::default()
+            .with_line_width(self.width.0)
+            .with_line_cap(self.cap.into())
+            .with_line_join(self.join.into())
+            .with_miter_limit(self.miter_limit.max(StrokeOptions::MINIMUM_MITER_LIMIT))
+    }
+}
+
+/// The shape of the ends of a stroke.
+///
+/// [MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Reference/Attribute/stroke-linecap)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// The stroke ends flat at the end of the outline.
+    #[default]
+    Butt,
+    /// The stroke ends flat, half the stroke width past the end of the outline.
+    Square,
+    /// The stroke ends in a semicircle.
+    Round,
+}
+
+impl From<LineCap> for lyon::tessellation::LineCap {
+    fn from(cap: LineCap) -> Self {
+        match cap {
+            LineCap::Butt => Self::Butt,
+            LineCap::Square => Self::Square,
+            LineCap::Round => Self::Round,
+        }
+    }
+}
+
+/// The shape of the corners of a stroke.
+///
+/// [MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Reference/Attribute/stroke-linejoin)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet, up to the [`StrokeStyle::miter_limit`].
+    #[default]
+    Miter,
+    /// The corner is rounded off.
+    Round,
+    /// The corner is cut off.
+    Bevel,
+}
+
+impl From<LineJoin> for lyon::tessellation::LineJoin {
+    fn from(join: LineJoin) -> Self {
+        match join {
+            LineJoin::Miter => Self::Miter,
+            LineJoin::Round => Self::Round,
+            LineJoin::Bevel => Self::Bevel,
+        }
+    }
+}
+
+/// The dashes a stroke is broken into.
+///
+/// [MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Reference/Attribute/stroke-dasharray)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DashPattern {
+    /// The lengths of the dashes and the gaps between them, alternating and starting with a
+    /// dash. An odd number of lengths is repeated to get an even number.
+    pub lengths: Vec<Pixels>,
+    /// How far into the pattern the stroke starts. Animating this moves the dashes along the
+    /// outline, e.g. for the "marching ants" of a selection marquee.
+    pub phase: Pixels,
+}
+
+impl DashPattern {
+    /// Creates a dash pattern from alternating dash and gap lengths, starting at its beginning.
+    pub fn new(lengths: &[Pixels]) -> Self {
+        Self {
+            lengths: lengths.to_vec(),
+            phase: Pixels::ZERO,
+        }
+    }
+
+    /// Sets how far into the pattern the stroke starts.
+    pub fn phase(mut self, phase: Pixels) -> Self {
+        self.phase = phase;
+        self
+    }
+}
+
 /// A [`Path`] builder.
 pub struct PathBuilder {
     raw: lyon::path::builder::WithSvg<lyon::path::BuilderImpl>,
@@ -28,6 +146,7 @@ pub struct PathBuilder {
     /// PathStyle of the PathBuilder
     pub style: PathStyle,
     dash_array: Option<Vec<Pixels>>,
+    scale_factor: f32,
 }
 
 impl From<lyon::path::Builder> for PathBuilder {
@@ -79,6 +198,7 @@ impl Default for PathBuilder {
             style: PathStyle::Fill(FillOptions::default()),
             transform: None,
             dash_array: None,
+            scale_factor: 1.,
         }
     }
 }
@@ -102,6 +222,30 @@ impl PathBuilder {
         Self { style, ..self }
     }
 
+    /// Sets the rule deciding which regions of a self-intersecting or nested fill are inside the
+    /// shape. Has no effect on strokes.
+    ///
+    /// [MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Reference/Attribute/fill-rule)
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        if let PathStyle::Fill(options) = &mut self.style {
+            options.fill_rule = fill_rule;
+        }
+        self
+    }
+
+    /// Sets the scale factor of the window the path will be painted in.
+    ///
+    /// Curves are flattened into line segments within the style's tolerance, which is measured
+    /// in device pixels, so on high-DPI displays the path is flattened more finely.
+    /// [`Window::fill_path`](crate::Window::fill_path) and
+    /// [`Window::stroke_path`](crate::Window::stroke_path) set this automatically.
+    pub fn with_scale_factor(self, scale_factor: f32) -> Self {
+        Self {
+            scale_factor,
+            ..self
+        }
+    }
+
     /// Sets the dash array of the [`PathBuilder`].
     ///
     /// [MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Reference/Attribute/stroke-dasharray)
@@ -151,6 +295,27 @@ impl PathBuilder {
             .cubic_bezier_to(control_a.into(), control_b.into(), to.into());
     }
 
+    /// Adds a circular arc continuing from the current point, which lies at `start_angle` on
+    /// the circle. The arc sweeps to `end_angle`, clockwise if it's greater than `start_angle`.
+    ///
+    /// The angles are in degrees, with 0 pointing along the positive x axis.
+    pub fn circular_arc_to(&mut self, radius: Pixels, start_angle: f32, end_angle: f32) {
+        let start_angle = Angle::degrees(start_angle);
+        let (sin, cos) = start_angle.sin_cos();
+        let from = self.raw.current_position();
+        let arc = lyon::geom::Arc {
+            center: from - vector(cos, sin) * radius.0,
+            radii: vector(radius.0, radius.0),
+            start_angle,
+            sweep_angle: Angle::degrees(end_angle) - start_angle,
+            x_rotation: Angle::zero(),
+        };
+        arc.for_each_cubic_bezier(&mut |segment| {
+            self.raw
+                .cubic_bezier_to(segment.ctrl1, segment.ctrl2, segment.to);
+        });
+    }
+
     /// Adds an elliptical arc.
     pub fn arc_to(
         &mut self,
@@ -249,11 +414,38 @@ impl PathBuilder {
         };
 
         match self.style {
-            PathStyle::Stroke(options) => Self::tessellate_stroke(self.dash_array, &path, &options),
-            PathStyle::Fill(options) => Self::tessellate_fill(&path, &options),
+            PathStyle::Stroke(options) => {
+                let path = match self.dash_array {
+                    Some(dash_array) => dashed(&path, &dash_array, Pixels::ZERO),
+                    None => path,
+                };
+                let tolerance = options.tolerance / self.scale_factor;
+                Self::tessellate_stroke(&path, &options.with_tolerance(tolerance))
+            }
+            PathStyle::Fill(options) => {
+                let tolerance = options.tolerance / self.scale_factor;
+                Self::tessellate_fill(&path, &options.with_tolerance(tolerance))
+            }
         }
     }
 
+    /// Builds the stroke of the outline into a [`Path`] with the given style, ignoring the
+    /// builder's own [`PathStyle`] and dash array.
+    pub fn build_stroke(self, style: &StrokeStyle) -> Result<Path<Pixels>, Error> {
+        let path = if let Some(transform) = self.transform {
+            self.raw.build().transformed(&transform)
+        } else {
+            self.raw.build()
+        };
+        let path = match style.dash.as_ref() {
+            Some(dash) => dashed(&path, &dash.lengths, dash.phase),
+            None => path,
+        };
+        let options = style.options();
+        let tolerance = options.tolerance / self.scale_factor;
+        Self::tessellate_stroke(&path, &options.with_tolerance(tolerance))
+    }
+
     fn tessellate_fill(
         path: &lyon::path::Path,
         options: &FillOptions,
@@ -273,37 +465,9 @@ impl PathBuilder {
     }
 
     fn tessellate_stroke(
-        dash_array: Option<Vec<Pixels>>,
         path: &lyon::path::Path,
         options: &StrokeOptions,
     ) -> Result<Path<Pixels>, Error> {
-        let path = if let Some(dash_array) = dash_array {
-            let measurements = lyon::algorithms::measure::PathMeasurements::from_path(path, 0.01);
-            let mut sampler = measurements
-                .create_sampler(path, lyon::algorithms::measure::SampleType::Normalized);
-            let mut builder = lyon::path::Path::builder();
-
-            let total_length = sampler.length();
-            let dash_array_len = dash_array.len();
-            let mut pos = 0.;
-            let mut dash_index = 0;
-            while pos < total_length {
-                let dash_length = dash_array[dash_index % dash_array_len].0;
-                let next_pos = (pos + dash_length).min(total_length);
-                if dash_index % 2 == 0 {
-                    let start = pos / total_length;
-                    let end = next_pos / total_length;
-                    sampler.split_range(start..end, &mut builder);
-                }
-                pos = next_pos;
-                dash_index += 1;
-            }
-
-            &builder.build()
-        } else {
-            path
-        };
-
         // Will contain the result of the tessellation.
         let mut buf: VertexBuffers<lyon::math::Point, u16> = VertexBuffers::new();
         let mut tessellator = StrokeTessellator::new();
@@ -345,3 +509,178 @@ impl PathBuilder {
         path
     }
 }
+
+/// Splits the path into the dashes of the given pattern, starting `phase` into the pattern.
+fn dashed(path: &lyon::path::Path, dash_lengths: &[Pixels], phase: Pixels) -> lyon::path::Path {
+    // If an odd number of values is provided, then the list of values is repeated to yield an
+    // even number of values.
+    let dash_lengths = dash_lengths
+        .iter()
+        .cycle()
+        .take(dash_lengths.len() * (1 + dash_lengths.len() % 2))
+        .map(|length| length.0.max(0.))
+        .collect::<Vec<_>>();
+    let pattern_length = dash_lengths.iter().sum::<f32>();
+    if pattern_length <= 0. {
+        return path.clone();
+    }
+
+    let measurements = lyon::algorithms::measure::PathMeasurements::from_path(path, 0.01);
+    let mut sampler =
+        measurements.create_sampler(path, lyon::algorithms::measure::SampleType::Normalized);
+    let mut builder = lyon::path::Path::builder();
+    let total_length = sampler.length();
+    if total_length <= 0. {
+        return builder.build();
+    }
+
+    // Find the dash the phase lands in, and how much of it is left.
+    let mut dash_index = 0;
+    let mut remaining = phase.0.rem_euclid(pattern_length);
+    while remaining >= dash_lengths[dash_index] && dash_index + 1 < dash_lengths.len() {
+        remaining -= dash_lengths[dash_index];
+        dash_index += 1;
+    }
+    let mut dash_length = dash_lengths[dash_index] - remaining;
+
+    let mut position = 0.;
+    while position < total_length {
+        let next_position = (position + dash_length).min(total_length);
+        if dash_index % 2 == 0 && next_position > position {
+            sampler.split_range(
+                position / total_length..next_position / total_length,
+                &mut builder,
+            );
+        }
+        position = next_position;
+        dash_index = (dash_index + 1) % dash_lengths.len();
+        dash_length = dash_lengths[dash_index];
+    }
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Strokes a horizontal line from x = 0 to x = 50 and returns the x positions of its vertices.
+    fn stroke_vertex_xs(style: &StrokeStyle) -> Vec<f32> {
+        let mut builder = PathBuilder::fill();
+        builder.move_to(point(px(0.), px(0.)));
+        builder.line_to(point(px(50.), px(0.)));
+        let stroke = builder.build_stroke(style).unwrap();
+        stroke
+            .vertices
+            .iter()
+            .map(|vertex| vertex.xy_position.x.0)
+            .collect()
+    }
+
+    fn assert_within_dashes(xs: &[f32], dashes: &[(f32, f32)]) {
+        for x in xs {
+            assert!(
+                dashes
+                    .iter()
+                    .any(|(start, end)| *x >= start - 0.01 && *x <= end + 0.01),
+                "vertex at x = {x} is outside of the dashes {dashes:?}"
+            );
+        }
+        for (start, end) in dashes {
+            assert!(xs.iter().any(|x| (x - start).abs() < 0.01));
+            assert!(xs.iter().any(|x| (x - end).abs() < 0.01));
+        }
+    }
+
+    #[test]
+    fn test_dash_phase() {
+        let style = StrokeStyle {
+            width: px(2.),
+            dash: Some(DashPattern::new(&[px(10.), px(10.)])),
+            ..Default::default()
+        };
+        assert_within_dashes(
+            &stroke_vertex_xs(&style),
+            &[(0., 10.), (20., 30.), (40., 50.)],
+        );
+
+        // Starting 25px into the pattern lands halfway through the first dash.
+        let style = StrokeStyle {
+            dash: Some(DashPattern::new(&[px(10.), px(10.)]).phase(px(25.))),
+            ..style
+        };
+        assert_within_dashes(
+            &stroke_vertex_xs(&style),
+            &[(0., 5.), (15., 25.), (35., 45.)],
+        );
+    }
+
+    #[test]
+    fn test_square_caps_extend_stroke() {
+        let style = StrokeStyle {
+            width: px(4.),
+            cap: LineCap::Square,
+            ..Default::default()
+        };
+        let xs = stroke_vertex_xs(&style);
+        let min = xs.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = xs.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        assert_eq!((min, max), (-2., 52.));
+    }
+
+    fn filled_area(path: &Path<Pixels>) -> f32 {
+        path.vertices
+            .chunks(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|index| triangle[index].xy_position);
+                ((b.x - a.x).0 * (c.y - a.y).0 - (c.x - a.x).0 * (b.y - a.y).0).abs() / 2.
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_fill_rule() {
+        let nested_squares = |fill_rule| {
+            let mut builder = PathBuilder::fill().fill_rule(fill_rule);
+            for (origin, size) in [(0., 100.), (25., 50.)] {
+                builder.add_polygon(
+                    &[
+                        point(px(origin), px(origin)),
+                        point(px(origin + size), px(origin)),
+                        point(px(origin + size), px(origin + size)),
+                        point(px(origin), px(origin + size)),
+                    ],
+                    true,
+                );
+            }
+            builder.build().unwrap()
+        };
+
+        // Both squares wind the same way, so only even-odd leaves a hole.
+        assert_eq!(filled_area(&nested_squares(FillRule::NonZero)), 10000.);
+        assert_eq!(filled_area(&nested_squares(FillRule::EvenOdd)), 7500.);
+    }
+
+    #[test]
+    fn test_circular_arc_bounds_and_tolerance() {
+        let circle = |scale_factor| {
+            let mut builder = PathBuilder::fill().with_scale_factor(scale_factor);
+            builder.move_to(point(px(100.), px(50.)));
+            builder.circular_arc_to(px(50.), 0., 360.);
+            builder.close();
+            builder.build().unwrap()
+        };
+
+        let path = circle(1.);
+        let bounds = path.bounds();
+        assert!((bounds.origin.x.0 - 0.).abs() < 0.5, "{bounds:?}");
+        assert!((bounds.origin.y.0 - 0.).abs() < 0.5, "{bounds:?}");
+        assert!((bounds.size.width.0 - 100.).abs() < 0.5, "{bounds:?}");
+        assert!((bounds.size.height.0 - 100.).abs() < 0.5, "{bounds:?}");
+
+        assert!(
+            circle(2.).vertices.len() > path.vertices.len(),
+            "curves should be flattened more finely at higher scale factors"
+        );
+    }
+}
@@ -41,13 +41,13 @@ use crate::{
     ForegroundExecutor, GlyphId, GpuSpecs, ImageSource, Keymap, LineLayout, Pixels, PlatformInput,
     Point, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams, Scene, ShapedGlyph,
     ShapedRun, SharedString, Size, SvgRenderer, SystemWindowTab, Task, TaskLabel, TaskTiming,
-    ThreadTaskTimings, Window, WindowControlArea, hash, point, px, size,
+    ThreadTaskTimings, Window, WindowControlArea, decode_still_image, hash, point, px, size,
 };
 use anyhow::Result;
 use async_task::Runnable;
 use futures::channel::oneshot;
 use image::codecs::gif::GifDecoder;
-use image::{AnimationDecoder as _, Frame};
+use image::{AnimationDecoder as _, Frame, RgbaImage};
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use schemars::JsonSchema;
 use seahash::SeaHasher;
@@ -459,6 +459,16 @@ pub(crate) struct RequestFrameOptions {
     pub(crate) force_render: bool,
 }
 
+/// A snapshot of the dragged element, shown under the pointer while files are dragged out of a
+/// window.
+#[derive(Debug, Clone)]
+pub(crate) struct DragImage {
+    /// The element as it was last rendered, in straight alpha RGBA at the window's scale factor.
+    pub(crate) image: RgbaImage,
+    /// Where the pointer is within the image.
+    pub(crate) cursor_offset: Point<DevicePixels>,
+}
+
 pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     fn bounds(&self) -> Bounds<Pixels>;
     fn is_maximized(&self) -> bool;
@@ -503,6 +513,17 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     fn completed_frame(&self) {}
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas>;
 
+    /// Renders the scene without presenting it and reads back the given part of the frame.
+    fn render_to_image(&self, _scene: &Scene, _bounds: Bounds<DevicePixels>) -> Option<RgbaImage> {
+        None
+    }
+
+    /// Starts a drag session that carries the given files out of the window, returning whether
+    /// the platform supports it.
+    fn start_drag_out(&self, _paths: &[PathBuf], _image: Option<DragImage>) -> bool {
+        false
+    }
+
     // macOS specific methods
     fn get_title(&self) -> String {
         String::new()
@@ -641,6 +662,8 @@ impl PlatformTextSystem for NoopTextSystem {
             line_gap: 0.0,
             underline_position: -95.0,
             underline_thickness: 60.0,
+            strikethrough_position: 288.0,
+            strikethrough_thickness: 60.0,
             cap_height: 698.0,
             x_height: 516.0,
             bounding_box: Bounds {
@@ -727,6 +750,7 @@ impl PlatformTextSystem for NoopTextSystem {
             descent: font_size * (metrics.descent / metrics.units_per_em as f32),
             runs,
             len: text.len(),
+            ..Default::default()
         }
     }
 }
@@ -790,7 +814,13 @@ impl AtlasKey {
                     AtlasTextureKind::Monochrome
                 }
             }
-            AtlasKey::Svg(_) => AtlasTextureKind::Monochrome,
+            AtlasKey::Svg(params) => {
+                if params.preserve_colors {
+                    AtlasTextureKind::Polychrome
+                } else {
+                    AtlasTextureKind::Monochrome
+                }
+            }
             AtlasKey::Image(_) => AtlasTextureKind::Polychrome,
         }
     }
@@ -1802,7 +1832,7 @@ impl Image {
         cx: &mut App,
     ) -> Option<Arc<RenderImage>> {
         ImageSource::Image(self)
-            .use_data(None, window, cx)
+            .use_data(None, false, window, cx)
             .and_then(|result| result.ok())
     }
 
@@ -1813,7 +1843,7 @@ impl Image {
         cx: &mut App,
     ) -> Option<Arc<RenderImage>> {
         ImageSource::Image(self)
-            .get_data(None, window, cx)
+            .get_data(None, false, window, cx)
             .and_then(|result| result.ok())
     }
 
@@ -1822,21 +1852,27 @@ impl Image {
         ImageSource::Image(self).remove_asset(cx);
     }
 
-    /// Convert the clipboard image to an `ImageData` object.
+    /// Convert the clipboard image to an `ImageData` object, applying its EXIF orientation.
     pub fn to_image_data(&self, svg_renderer: SvgRenderer) -> Result<Arc<RenderImage>> {
-        fn frames_for_image(
-            bytes: &[u8],
-            format: image::ImageFormat,
-        ) -> Result<SmallVec<[Frame; 1]>> {
-            let mut data = image::load_from_memory_with_format(bytes, format)?.into_rgba8();
-
-            // Convert from RGBA to BGRA.
-            for pixel in data.chunks_exact_mut(4) {
-                pixel.swap(0, 2);
-            }
+        self.decode_render_image(svg_renderer, true)
+    }
 
-            Ok(SmallVec::from_elem(Frame::new(data), 1))
-        }
+    pub(crate) fn decode_render_image(
+        &self,
+        svg_renderer: SvgRenderer,
+        apply_orientation: bool,
+    ) -> Result<Arc<RenderImage>> {
+        let frames_for_image =
+            |bytes: &[u8], format: image::ImageFormat| -> Result<SmallVec<[Frame; 1]>> {
+                let mut data = decode_still_image(bytes, format, apply_orientation)?;
+
+                // Convert from RGBA to BGRA.
+                for pixel in data.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+
+                Ok(SmallVec::from_elem(Frame::new(data), 1))
+            };
 
         let frames = match self.format {
             ImageFormat::Gif => {
@@ -3,16 +3,17 @@
 
 use super::{BladeAtlas, BladeContext};
 use crate::{
-    Background, Bounds, DevicePixels, GpuSpecs, MonochromeSprite, Path, Point, PolychromeSprite,
-    PrimitiveBatch, Quad, ScaledPixels, Scene, Shadow, Size, Underline,
-    get_gamma_correction_ratios,
+    Backdrop, Background, BlendMode, Bounds, ClipRegion, DevicePixels, GpuSpecs, GradientSprite,
+    MonochromeSprite, Path, Point, PolychromeSprite, PrimitiveBatch, Quad, ScaledPixels, Scene,
+    Shadow, Size, Underline, get_gamma_correction_ratios,
 };
 use blade_graphics as gpu;
 use blade_util::{BufferBelt, BufferBeltDescriptor};
 use bytemuck::{Pod, Zeroable};
+use image::RgbaImage;
 #[cfg(target_os = "macos")]
 use media::core_video::CVMetalTextureCache;
-use std::sync::Arc;
+use std::{slice, sync::Arc};
 
 const MAX_FRAME_TIME_MS: u32 = 10000;
 
@@ -53,18 +54,34 @@ struct SurfaceParams {
 struct ShaderQuadsData {
     globals: GlobalParams,
     b_quads: gpu::BufferPiece,
+    b_clip_regions: gpu::BufferPiece,
+    b_clip_region_vertices: gpu::BufferPiece,
 }
 
 #[derive(blade_macros::ShaderData)]
 struct ShaderShadowsData {
     globals: GlobalParams,
     b_shadows: gpu::BufferPiece,
+    b_clip_regions: gpu::BufferPiece,
+    b_clip_region_vertices: gpu::BufferPiece,
+}
+
+#[derive(blade_macros::ShaderData)]
+struct ShaderBackdropsData {
+    globals: GlobalParams,
+    t_sprite: gpu::TextureView,
+    s_sprite: gpu::Sampler,
+    b_backdrops: gpu::BufferPiece,
+    b_clip_regions: gpu::BufferPiece,
+    b_clip_region_vertices: gpu::BufferPiece,
 }
 
 #[derive(blade_macros::ShaderData)]
 struct ShaderPathRasterizationData {
     globals: GlobalParams,
     b_path_vertices: gpu::BufferPiece,
+    b_clip_regions: gpu::BufferPiece,
+    b_clip_region_vertices: gpu::BufferPiece,
 }
 
 #[derive(blade_macros::ShaderData)]
@@ -75,10 +92,20 @@ struct ShaderPathsData {
     b_path_sprites: gpu::BufferPiece,
 }
 
+#[derive(blade_macros::ShaderData)]
+struct ShaderOverlayData {
+    globals: GlobalParams,
+    t_sprite: gpu::TextureView,
+    t_overlay_backdrop: gpu::TextureView,
+    b_path_sprites: gpu::BufferPiece,
+}
+
 #[derive(blade_macros::ShaderData)]
 struct ShaderUnderlinesData {
     globals: GlobalParams,
     b_underlines: gpu::BufferPiece,
+    b_clip_regions: gpu::BufferPiece,
+    b_clip_region_vertices: gpu::BufferPiece,
 }
 
 #[derive(blade_macros::ShaderData)]
@@ -89,6 +116,20 @@ struct ShaderMonoSpritesData {
     t_sprite: gpu::TextureView,
     s_sprite: gpu::Sampler,
     b_mono_sprites: gpu::BufferPiece,
+    b_clip_regions: gpu::BufferPiece,
+    b_clip_region_vertices: gpu::BufferPiece,
+}
+
+#[derive(blade_macros::ShaderData)]
+struct ShaderGradientSpritesData {
+    globals: GlobalParams,
+    gamma_ratios: [f32; 4],
+    grayscale_enhanced_contrast: f32,
+    t_sprite: gpu::TextureView,
+    s_sprite: gpu::Sampler,
+    b_gradient_sprites: gpu::BufferPiece,
+    b_clip_regions: gpu::BufferPiece,
+    b_clip_region_vertices: gpu::BufferPiece,
 }
 
 #[derive(blade_macros::ShaderData)]
@@ -97,6 +138,8 @@ struct ShaderPolySpritesData {
     t_sprite: gpu::TextureView,
     s_sprite: gpu::Sampler,
     b_poly_sprites: gpu::BufferPiece,
+    b_clip_regions: gpu::BufferPiece,
+    b_clip_region_vertices: gpu::BufferPiece,
 }
 
 #[derive(blade_macros::ShaderData)]
@@ -108,6 +151,14 @@ struct ShaderSurfacesData {
     s_surface: gpu::Sampler,
 }
 
+/// The clip regions of the scene being drawn, which are bound along with every kind of primitive
+/// that can be clipped.
+#[derive(Clone, Copy)]
+struct ClipRegionBuffers {
+    regions: gpu::BufferPiece,
+    vertices: gpu::BufferPiece,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[repr(C)]
 struct PathSprite {
@@ -121,16 +172,102 @@ struct PathRasterizationVertex {
     st_position: Point<f32>,
     color: Background,
     bounds: Bounds<ScaledPixels>,
+    clip_region: u32,
+    pad: u32,
+}
+
+/// Composites premultiplied colors by multiplying them with the colors behind.
+const MULTIPLY_BLENDING: gpu::BlendState = gpu::BlendState {
+    color: gpu::BlendComponent {
+        src_factor: gpu::BlendFactor::Dst,
+        dst_factor: gpu::BlendFactor::OneMinusSrcAlpha,
+        operation: gpu::BlendOperation::Add,
+    },
+    alpha: gpu::BlendComponent::OVER,
+};
+
+/// Composites premultiplied colors by inverting the product of their inverse and the inverse of
+/// the colors behind.
+const SCREEN_BLENDING: gpu::BlendState = gpu::BlendState {
+    color: gpu::BlendComponent {
+        src_factor: gpu::BlendFactor::One,
+        dst_factor: gpu::BlendFactor::OneMinusSrc,
+        operation: gpu::BlendOperation::Add,
+    },
+    alpha: gpu::BlendComponent::OVER,
+};
+
+/// The variants of a pipeline for each [`BlendMode`] it can be drawn with.
+struct BlendModePipelines {
+    normal: gpu::RenderPipeline,
+    multiply: gpu::RenderPipeline,
+    screen: gpu::RenderPipeline,
+}
+
+impl BlendModePipelines {
+    fn new(
+        gpu: &gpu::Context,
+        name: &str,
+        data_layout: &gpu::ShaderDataLayout,
+        shader: &gpu::Shader,
+        vertex_fn_name: &str,
+        fragment_fn_name: &str,
+        color_target: &gpu::ColorTargetState,
+    ) -> Self {
+        let create_pipeline = |name: &str, blend: Option<gpu::BlendState>| {
+            gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name,
+                data_layouts: &[data_layout],
+                vertex: shader.at(vertex_fn_name),
+                vertex_fetches: &[],
+                primitive: gpu::PrimitiveState {
+                    topology: gpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                fragment: Some(shader.at(fragment_fn_name)),
+                color_targets: &[gpu::ColorTargetState {
+                    blend,
+                    ..*color_target
+                }],
+                multisample_state: gpu::MultisampleState::default(),
+            })
+        };
+        Self {
+            normal: create_pipeline(name, color_target.blend),
+            multiply: create_pipeline(&format!("{name}-multiply"), Some(MULTIPLY_BLENDING)),
+            screen: create_pipeline(&format!("{name}-screen"), Some(SCREEN_BLENDING)),
+        }
+    }
+
+    fn get(&self, blend_mode: BlendMode) -> &gpu::RenderPipeline {
+        // Overlay blended batches are painted normally into a layer before being composited.
+        match blend_mode {
+            BlendMode::Normal | BlendMode::Overlay => &self.normal,
+            BlendMode::Multiply => &self.multiply,
+            BlendMode::Screen => &self.screen,
+        }
+    }
+
+    fn destroy(&mut self, gpu: &gpu::Context) {
+        gpu.destroy_render_pipeline(&mut self.normal);
+        gpu.destroy_render_pipeline(&mut self.multiply);
+        gpu.destroy_render_pipeline(&mut self.screen);
+    }
 }
 
 struct BladePipelines {
-    quads: gpu::RenderPipeline,
+    quads: BlendModePipelines,
     shadows: gpu::RenderPipeline,
+    backdrop_horizontal_blur: gpu::RenderPipeline,
+    backdrop_vertical_blur: gpu::RenderPipeline,
+    overlay: gpu::RenderPipeline,
     path_rasterization: gpu::RenderPipeline,
     paths: gpu::RenderPipeline,
     underlines: gpu::RenderPipeline,
-    mono_sprites: gpu::RenderPipeline,
-    poly_sprites: gpu::RenderPipeline,
+    mono_sprites: BlendModePipelines,
+    gradient_sprites: BlendModePipelines,
+    poly_sprites: BlendModePipelines,
     surfaces: gpu::RenderPipeline,
 }
 
@@ -149,11 +286,14 @@ impl BladePipelines {
         shader.check_struct_size::<SurfaceParams>();
         shader.check_struct_size::<Quad>();
         shader.check_struct_size::<Shadow>();
+        shader.check_struct_size::<Backdrop>();
         shader.check_struct_size::<PathRasterizationVertex>();
         shader.check_struct_size::<PathSprite>();
         shader.check_struct_size::<Underline>();
         shader.check_struct_size::<MonochromeSprite>();
+        shader.check_struct_size::<GradientSprite>();
         shader.check_struct_size::<PolychromeSprite>();
+        shader.check_struct_size::<ClipRegion>();
 
         // See https://apoorvaj.io/alpha-compositing-opengl-blending-and-premultiplied-alpha/
         let blend_mode = match surface_info.alpha {
@@ -161,39 +301,92 @@ impl BladePipelines {
             gpu::AlphaMode::PreMultiplied => gpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
             gpu::AlphaMode::PostMultiplied => gpu::BlendState::ALPHA_BLENDING,
         };
-        let color_targets = &[gpu::ColorTargetState {
+        let color_target = gpu::ColorTargetState {
             format: surface_info.format,
             blend: Some(blend_mode),
             write_mask: gpu::ColorWrites::default(),
-        }];
+        };
+        let color_targets = slice::from_ref(&color_target);
 
         Self {
-            quads: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
-                name: "quads",
-                data_layouts: &[&ShaderQuadsData::layout()],
-                vertex: shader.at("vs_quad"),
+            quads: BlendModePipelines::new(
+                gpu,
+                "quads",
+                &ShaderQuadsData::layout(),
+                &shader,
+                "vs_quad",
+                "fs_quad",
+                &color_target,
+            ),
+            shadows: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "shadows",
+                data_layouts: &[&ShaderShadowsData::layout()],
+                vertex: shader.at("vs_shadow"),
                 vertex_fetches: &[],
                 primitive: gpu::PrimitiveState {
                     topology: gpu::PrimitiveTopology::TriangleStrip,
                     ..Default::default()
                 },
                 depth_stencil: None,
-                fragment: Some(shader.at("fs_quad")),
+                fragment: Some(shader.at("fs_shadow")),
                 color_targets,
                 multisample_state: gpu::MultisampleState::default(),
             }),
-            shadows: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
-                name: "shadows",
-                data_layouts: &[&ShaderShadowsData::layout()],
-                vertex: shader.at("vs_shadow"),
+            backdrop_horizontal_blur: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "backdrop_horizontal_blur",
+                data_layouts: &[&ShaderBackdropsData::layout()],
+                vertex: shader.at("vs_backdrop_horizontal"),
                 vertex_fetches: &[],
                 primitive: gpu::PrimitiveState {
                     topology: gpu::PrimitiveTopology::TriangleStrip,
                     ..Default::default()
                 },
                 depth_stencil: None,
-                fragment: Some(shader.at("fs_shadow")),
-                color_targets,
+                fragment: Some(shader.at("fs_backdrop_horizontal")),
+                // The first pass replaces the contents of the blur texture.
+                color_targets: &[gpu::ColorTargetState {
+                    format: surface_info.format,
+                    blend: None,
+                    write_mask: gpu::ColorWrites::default(),
+                }],
+                multisample_state: gpu::MultisampleState::default(),
+            }),
+            backdrop_vertical_blur: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "backdrop_vertical_blur",
+                data_layouts: &[&ShaderBackdropsData::layout()],
+                vertex: shader.at("vs_backdrop_vertical"),
+                vertex_fetches: &[],
+                primitive: gpu::PrimitiveState {
+                    topology: gpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                fragment: Some(shader.at("fs_backdrop_vertical")),
+                // The blurred frame is already in the surface's color space, and is only
+                // faded out by the coverage of the rounded corners.
+                color_targets: &[gpu::ColorTargetState {
+                    format: surface_info.format,
+                    blend: Some(gpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: gpu::ColorWrites::default(),
+                }],
+                multisample_state: gpu::MultisampleState::default(),
+            }),
+            overlay: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "overlay",
+                data_layouts: &[&ShaderOverlayData::layout()],
+                vertex: shader.at("vs_path"),
+                vertex_fetches: &[],
+                primitive: gpu::PrimitiveState {
+                    topology: gpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                fragment: Some(shader.at("fs_overlay")),
+                color_targets: &[gpu::ColorTargetState {
+                    format: surface_info.format,
+                    blend: Some(gpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: gpu::ColorWrites::default(),
+                }],
                 multisample_state: gpu::MultisampleState::default(),
             }),
             path_rasterization: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
@@ -259,34 +452,33 @@ impl BladePipelines {
                 color_targets,
                 multisample_state: gpu::MultisampleState::default(),
             }),
-            mono_sprites: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
-                name: "mono-sprites",
-                data_layouts: &[&ShaderMonoSpritesData::layout()],
-                vertex: shader.at("vs_mono_sprite"),
-                vertex_fetches: &[],
-                primitive: gpu::PrimitiveState {
-                    topology: gpu::PrimitiveTopology::TriangleStrip,
-                    ..Default::default()
-                },
-                depth_stencil: None,
-                fragment: Some(shader.at("fs_mono_sprite")),
-                color_targets,
-                multisample_state: gpu::MultisampleState::default(),
-            }),
-            poly_sprites: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
-                name: "poly-sprites",
-                data_layouts: &[&ShaderPolySpritesData::layout()],
-                vertex: shader.at("vs_poly_sprite"),
-                vertex_fetches: &[],
-                primitive: gpu::PrimitiveState {
-                    topology: gpu::PrimitiveTopology::TriangleStrip,
-                    ..Default::default()
-                },
-                depth_stencil: None,
-                fragment: Some(shader.at("fs_poly_sprite")),
-                color_targets,
-                multisample_state: gpu::MultisampleState::default(),
-            }),
+            mono_sprites: BlendModePipelines::new(
+                gpu,
+                "mono-sprites",
+                &ShaderMonoSpritesData::layout(),
+                &shader,
+                "vs_mono_sprite",
+                "fs_mono_sprite",
+                &color_target,
+            ),
+            gradient_sprites: BlendModePipelines::new(
+                gpu,
+                "gradient-sprites",
+                &ShaderGradientSpritesData::layout(),
+                &shader,
+                "vs_gradient_sprite",
+                "fs_gradient_sprite",
+                &color_target,
+            ),
+            poly_sprites: BlendModePipelines::new(
+                gpu,
+                "poly-sprites",
+                &ShaderPolySpritesData::layout(),
+                &shader,
+                "vs_poly_sprite",
+                "fs_poly_sprite",
+                &color_target,
+            ),
             surfaces: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
                 name: "surfaces",
                 data_layouts: &[&ShaderSurfacesData::layout()],
@@ -305,13 +497,17 @@ impl BladePipelines {
     }
 
     fn destroy(&mut self, gpu: &gpu::Context) {
-        gpu.destroy_render_pipeline(&mut self.quads);
+        self.quads.destroy(gpu);
         gpu.destroy_render_pipeline(&mut self.shadows);
+        gpu.destroy_render_pipeline(&mut self.backdrop_horizontal_blur);
+        gpu.destroy_render_pipeline(&mut self.backdrop_vertical_blur);
+        gpu.destroy_render_pipeline(&mut self.overlay);
         gpu.destroy_render_pipeline(&mut self.path_rasterization);
         gpu.destroy_render_pipeline(&mut self.paths);
         gpu.destroy_render_pipeline(&mut self.underlines);
-        gpu.destroy_render_pipeline(&mut self.mono_sprites);
-        gpu.destroy_render_pipeline(&mut self.poly_sprites);
+        self.mono_sprites.destroy(gpu);
+        self.gradient_sprites.destroy(gpu);
+        self.poly_sprites.destroy(gpu);
         gpu.destroy_render_pipeline(&mut self.surfaces);
     }
 }
@@ -341,6 +537,10 @@ pub struct BladeRenderer {
     path_intermediate_texture_view: gpu::TextureView,
     path_intermediate_msaa_texture: Option<gpu::Texture>,
     path_intermediate_msaa_texture_view: Option<gpu::TextureView>,
+    backdrop_blur_texture: gpu::Texture,
+    backdrop_blur_texture_view: gpu::TextureView,
+    overlay_layer_texture: gpu::Texture,
+    overlay_layer_texture_view: gpu::TextureView,
     rendering_parameters: RenderingParameters,
 }
 
@@ -352,7 +552,8 @@ impl BladeRenderer {
     ) -> anyhow::Result<Self> {
         let surface_config = gpu::SurfaceConfig {
             size: config.size,
-            usage: gpu::TextureUsage::TARGET,
+            // Backdrop blurs copy the frame to read back what's been painted so far.
+            usage: gpu::TextureUsage::TARGET | gpu::TextureUsage::COPY,
             display_sync: gpu::DisplaySync::Recent,
             color_space: gpu::ColorSpace::Srgb,
             allow_exclusive_full_screen: false,
@@ -402,6 +603,20 @@ impl BladeRenderer {
                 rendering_parameters.path_sample_count,
             )
             .unzip();
+        let (backdrop_blur_texture, backdrop_blur_texture_view) = create_target_texture(
+            &context.gpu,
+            "backdrop blur",
+            surface.info().format,
+            config.size.width,
+            config.size.height,
+        );
+        let (overlay_layer_texture, overlay_layer_texture_view) = create_target_texture(
+            &context.gpu,
+            "overlay layer",
+            surface.info().format,
+            config.size.width,
+            config.size.height,
+        );
 
         #[cfg(target_os = "macos")]
         let core_video_texture_cache = unsafe {
@@ -427,6 +642,10 @@ impl BladeRenderer {
             path_intermediate_texture_view,
             path_intermediate_msaa_texture,
             path_intermediate_msaa_texture_view,
+            backdrop_blur_texture,
+            backdrop_blur_texture_view,
+            overlay_layer_texture,
+            overlay_layer_texture_view,
             rendering_parameters,
         })
     }
@@ -509,6 +728,30 @@ impl BladeRenderer {
                 .unzip();
             self.path_intermediate_msaa_texture = path_intermediate_msaa_texture;
             self.path_intermediate_msaa_texture_view = path_intermediate_msaa_texture_view;
+            self.gpu.destroy_texture(self.backdrop_blur_texture);
+            self.gpu
+                .destroy_texture_view(self.backdrop_blur_texture_view);
+            let (backdrop_blur_texture, backdrop_blur_texture_view) = create_target_texture(
+                &self.gpu,
+                "backdrop blur",
+                self.surface.info().format,
+                gpu_size.width,
+                gpu_size.height,
+            );
+            self.backdrop_blur_texture = backdrop_blur_texture;
+            self.backdrop_blur_texture_view = backdrop_blur_texture_view;
+            self.gpu.destroy_texture(self.overlay_layer_texture);
+            self.gpu
+                .destroy_texture_view(self.overlay_layer_texture_view);
+            let (overlay_layer_texture, overlay_layer_texture_view) = create_target_texture(
+                &self.gpu,
+                "overlay layer",
+                self.surface.info().format,
+                gpu_size.width,
+                gpu_size.height,
+            );
+            self.overlay_layer_texture = overlay_layer_texture;
+            self.overlay_layer_texture_view = overlay_layer_texture_view;
         }
     }
 
@@ -567,6 +810,7 @@ impl BladeRenderer {
         paths: &[Path<ScaledPixels>],
         width: f32,
         height: f32,
+        clip_regions: ClipRegionBuffers,
     ) {
         self.command_encoder
             .init_texture(self.path_intermediate_texture);
@@ -608,6 +852,8 @@ impl BladeRenderer {
                     st_position: v.st_position,
                     color: path.color,
                     bounds: path.clipped_bounds(),
+                    clip_region: path.clip_region,
+                    pad: 0,
                 }));
             }
             let vertex_buf = unsafe { self.instance_belt.alloc_typed(&vertices, &self.gpu) };
@@ -616,12 +862,181 @@ impl BladeRenderer {
                 &ShaderPathRasterizationData {
                     globals,
                     b_path_vertices: vertex_buf,
+                    b_clip_regions: clip_regions.regions,
+                    b_clip_region_vertices: clip_regions.vertices,
                 },
             );
             encoder.draw(0, vertices.len() as u32, 0, 1);
         }
     }
 
+    /// Blurs the part of the frame behind the backdrop in two passes, first horizontally from a
+    /// copy of the frame into the blur texture, then vertically from there back into the frame.
+    #[profiling::function]
+    fn draw_backdrop(
+        &mut self,
+        backdrop: &Backdrop,
+        frame_texture: gpu::Texture,
+        frame_view: gpu::TextureView,
+        globals: GlobalParams,
+        clip_regions: ClipRegionBuffers,
+    ) {
+        let viewport_size = Size {
+            width: DevicePixels(self.surface_config.size.width as i32),
+            height: DevicePixels(self.surface_config.size.height as i32),
+        };
+        let Some(sample_bounds) = backdrop.sample_bounds(viewport_size) else {
+            return;
+        };
+
+        let origin = [
+            sample_bounds.origin.x.0 as u32,
+            sample_bounds.origin.y.0 as u32,
+            0,
+        ];
+        self.command_encoder
+            .transfer("backdrop")
+            .copy_texture_to_texture(
+                gpu::TexturePiece {
+                    texture: frame_texture,
+                    mip_level: 0,
+                    array_layer: 0,
+                    origin,
+                },
+                gpu::TexturePiece {
+                    texture: self.path_intermediate_texture,
+                    mip_level: 0,
+                    array_layer: 0,
+                    origin,
+                },
+                gpu::Extent {
+                    width: sample_bounds.size.width.0 as u32,
+                    height: sample_bounds.size.height.0 as u32,
+                    depth: 1,
+                },
+            );
+
+        let instance_buf = unsafe {
+            self.instance_belt
+                .alloc_typed(std::slice::from_ref(backdrop), &self.gpu)
+        };
+        if let mut pass = self.command_encoder.render(
+            "backdrop horizontal blur",
+            gpu::RenderTargetSet {
+                colors: &[gpu::RenderTarget {
+                    view: self.backdrop_blur_texture_view,
+                    init_op: gpu::InitOp::Load,
+                    finish_op: gpu::FinishOp::Store,
+                }],
+                depth_stencil: None,
+            },
+        ) {
+            let mut encoder = pass.with(&self.pipelines.backdrop_horizontal_blur);
+            encoder.bind(
+                0,
+                &ShaderBackdropsData {
+                    globals,
+                    t_sprite: self.path_intermediate_texture_view,
+                    s_sprite: self.atlas_sampler,
+                    b_backdrops: instance_buf,
+                    b_clip_regions: clip_regions.regions,
+                    b_clip_region_vertices: clip_regions.vertices,
+                },
+            );
+            encoder.draw(0, 4, 0, 1);
+        }
+        if let mut pass = self.command_encoder.render(
+            "backdrop vertical blur",
+            gpu::RenderTargetSet {
+                colors: &[gpu::RenderTarget {
+                    view: frame_view,
+                    init_op: gpu::InitOp::Load,
+                    finish_op: gpu::FinishOp::Store,
+                }],
+                depth_stencil: None,
+            },
+        ) {
+            let mut encoder = pass.with(&self.pipelines.backdrop_vertical_blur);
+            encoder.bind(
+                0,
+                &ShaderBackdropsData {
+                    globals,
+                    t_sprite: self.backdrop_blur_texture_view,
+                    s_sprite: self.atlas_sampler,
+                    b_backdrops: instance_buf,
+                    b_clip_regions: clip_regions.regions,
+                    b_clip_region_vertices: clip_regions.vertices,
+                },
+            );
+            encoder.draw(0, 4, 0, 1);
+        }
+    }
+
+    /// Composites an overlay blended batch painted into the layer texture over a copy of the part
+    /// of the frame behind it.
+    #[profiling::function]
+    fn draw_overlay(
+        &mut self,
+        bounds: Bounds<DevicePixels>,
+        frame_texture: gpu::Texture,
+        frame_view: gpu::TextureView,
+        globals: GlobalParams,
+    ) {
+        let origin = [bounds.origin.x.0 as u32, bounds.origin.y.0 as u32, 0];
+        self.command_encoder
+            .transfer("overlay")
+            .copy_texture_to_texture(
+                gpu::TexturePiece {
+                    texture: frame_texture,
+                    mip_level: 0,
+                    array_layer: 0,
+                    origin,
+                },
+                gpu::TexturePiece {
+                    texture: self.path_intermediate_texture,
+                    mip_level: 0,
+                    array_layer: 0,
+                    origin,
+                },
+                gpu::Extent {
+                    width: bounds.size.width.0 as u32,
+                    height: bounds.size.height.0 as u32,
+                    depth: 1,
+                },
+            );
+
+        let sprite = PathSprite {
+            bounds: bounds.map(ScaledPixels::from),
+        };
+        let instance_buf = unsafe {
+            self.instance_belt
+                .alloc_typed(std::slice::from_ref(&sprite), &self.gpu)
+        };
+        if let mut pass = self.command_encoder.render(
+            "overlay",
+            gpu::RenderTargetSet {
+                colors: &[gpu::RenderTarget {
+                    view: frame_view,
+                    init_op: gpu::InitOp::Load,
+                    finish_op: gpu::FinishOp::Store,
+                }],
+                depth_stencil: None,
+            },
+        ) {
+            let mut encoder = pass.with(&self.pipelines.overlay);
+            encoder.bind(
+                0,
+                &ShaderOverlayData {
+                    globals,
+                    t_sprite: self.overlay_layer_texture_view,
+                    t_overlay_backdrop: self.path_intermediate_texture_view,
+                    b_path_sprites: instance_buf,
+                },
+            );
+            encoder.draw(0, 4, 0, 1);
+        }
+    }
+
     pub fn destroy(&mut self) {
         self.wait_for_gpu();
         self.atlas.destroy();
@@ -639,6 +1054,12 @@ impl BladeRenderer {
         if let Some(msaa_view) = self.path_intermediate_msaa_texture_view {
             self.gpu.destroy_texture_view(msaa_view);
         }
+        self.gpu.destroy_texture(self.backdrop_blur_texture);
+        self.gpu
+            .destroy_texture_view(self.backdrop_blur_texture_view);
+        self.gpu.destroy_texture(self.overlay_layer_texture);
+        self.gpu
+            .destroy_texture_view(self.overlay_layer_texture_view);
     }
 
     pub fn draw(&mut self, scene: &Scene) {
@@ -649,7 +1070,120 @@ impl BladeRenderer {
             profiling::scope!("acquire frame");
             self.surface.acquire_frame()
         };
-        self.command_encoder.init_texture(frame.texture());
+        self.encode_scene(scene, frame.texture(), frame.texture_view());
+
+        self.command_encoder.present(frame);
+        let sync_point = self.gpu.submit(&mut self.command_encoder);
+
+        profiling::scope!("finish");
+        self.instance_belt.flush(&sync_point);
+        self.atlas.after_frame(&sync_point);
+
+        self.wait_for_gpu();
+        self.last_sync_point = Some(sync_point);
+    }
+
+    /// Renders the scene into an offscreen texture and reads back the given part of it, e.g. to
+    /// show an element as the drag image when it's dragged out of the window.
+    pub fn render_to_image(
+        &mut self,
+        scene: &Scene,
+        bounds: Bounds<DevicePixels>,
+    ) -> anyhow::Result<RgbaImage> {
+        let format = self.surface.info().format;
+        let swap_red_and_blue = match format {
+            gpu::TextureFormat::Rgba8Unorm | gpu::TextureFormat::Rgba8UnormSrgb => false,
+            gpu::TextureFormat::Bgra8Unorm | gpu::TextureFormat::Bgra8UnormSrgb => true,
+            format => anyhow::bail!("can't read back frames in {format:?}"),
+        };
+        let viewport = Bounds {
+            origin: Point::default(),
+            size: Size {
+                width: DevicePixels(self.surface_config.size.width as i32),
+                height: DevicePixels(self.surface_config.size.height as i32),
+            },
+        };
+        let bounds = bounds.intersect(&viewport);
+        if bounds.is_empty() {
+            anyhow::bail!("{bounds:?} is outside of the frame");
+        }
+        let width = bounds.size.width.0 as u32;
+        let height = bounds.size.height.0 as u32;
+
+        let texture = self.gpu.create_texture(gpu::TextureDesc {
+            name: "offscreen frame",
+            format,
+            size: self.surface_config.size,
+            array_layer_count: 1,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: gpu::TextureDimension::D2,
+            usage: gpu::TextureUsage::COPY | gpu::TextureUsage::TARGET,
+            external: None,
+        });
+        let texture_view = self.gpu.create_texture_view(
+            texture,
+            gpu::TextureViewDesc {
+                name: "offscreen frame view",
+                format,
+                dimension: gpu::ViewDimension::D2,
+                subresources: &Default::default(),
+            },
+        );
+        let buffer = self.gpu.create_buffer(gpu::BufferDesc {
+            name: "offscreen frame readback",
+            size: width as u64 * height as u64 * 4,
+            memory: gpu::Memory::Shared,
+        });
+
+        self.wait_for_gpu();
+        self.command_encoder.start();
+        self.atlas.before_frame(&mut self.command_encoder);
+        self.encode_scene(scene, texture, texture_view);
+        self.command_encoder
+            .transfer("readback")
+            .copy_texture_to_buffer(
+                gpu::TexturePiece {
+                    texture,
+                    mip_level: 0,
+                    array_layer: 0,
+                    origin: [bounds.origin.x.0 as u32, bounds.origin.y.0 as u32, 0],
+                },
+                buffer.into(),
+                width * 4,
+                gpu::Extent {
+                    width,
+                    height,
+                    depth: 1,
+                },
+            );
+        let sync_point = self.gpu.submit(&mut self.command_encoder);
+        self.instance_belt.flush(&sync_point);
+        self.atlas.after_frame(&sync_point);
+        self.last_sync_point = Some(sync_point);
+        self.wait_for_gpu();
+
+        let mut bytes =
+            unsafe { slice::from_raw_parts(buffer.data(), (width * height * 4) as usize) }.to_vec();
+        self.gpu.destroy_buffer(buffer);
+        self.gpu.destroy_texture_view(texture_view);
+        self.gpu.destroy_texture(texture);
+
+        if swap_red_and_blue {
+            for pixel in bytes.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        RgbaImage::from_raw(width, height, bytes)
+            .ok_or_else(|| anyhow::anyhow!("readback of {width}x{height} pixels is truncated"))
+    }
+
+    fn encode_scene(&mut self, scene: &Scene, target: gpu::Texture, target_view: gpu::TextureView) {
+        self.command_encoder.init_texture(target);
+        self.command_encoder
+            .init_texture(self.backdrop_blur_texture);
+        self.command_encoder
+            .init_texture(self.overlay_layer_texture);
 
         let globals = GlobalParams {
             viewport_size: [
@@ -667,7 +1201,7 @@ impl BladeRenderer {
             "main",
             gpu::RenderTargetSet {
                 colors: &[gpu::RenderTarget {
-                    view: frame.texture_view(),
+                    view: target_view,
                     init_op: gpu::InitOp::Clear(gpu::TextureColor::TransparentBlack),
                     finish_op: gpu::FinishOp::Store,
                 }],
@@ -675,17 +1209,66 @@ impl BladeRenderer {
             },
         );
 
+        // Shaders look clip regions up by their index plus one, so the buffers can't be empty
+        // even when nothing is clipped.
+        let default_clip_region = [ClipRegion::default()];
+        let default_clip_region_vertex = [Point::<ScaledPixels>::default()];
+        let clip_regions = ClipRegionBuffers {
+            regions: unsafe {
+                self.instance_belt.alloc_typed(
+                    if scene.clip_regions.is_empty() {
+                        &default_clip_region[..]
+                    } else {
+                        scene.clip_regions.as_slice()
+                    },
+                    &self.gpu,
+                )
+            },
+            vertices: unsafe {
+                self.instance_belt.alloc_typed(
+                    if scene.clip_region_vertices.is_empty() {
+                        &default_clip_region_vertex[..]
+                    } else {
+                        scene.clip_region_vertices.as_slice()
+                    },
+                    &self.gpu,
+                )
+            },
+        };
+
+        let viewport_size = Size {
+            width: DevicePixels(self.surface_config.size.width as i32),
+            height: DevicePixels(self.surface_config.size.height as i32),
+        };
         profiling::scope!("render pass");
         for batch in scene.batches() {
+            let overlay_bounds = batch.overlay_bounds(viewport_size);
+            if overlay_bounds.is_some() {
+                drop(pass);
+                pass = self.command_encoder.render(
+                    "overlay layer",
+                    gpu::RenderTargetSet {
+                        colors: &[gpu::RenderTarget {
+                            view: self.overlay_layer_texture_view,
+                            init_op: gpu::InitOp::Clear(gpu::TextureColor::TransparentBlack),
+                            finish_op: gpu::FinishOp::Store,
+                        }],
+                        depth_stencil: None,
+                    },
+                );
+            }
+
             match batch {
                 PrimitiveBatch::Quads(quads) => {
                     let instance_buf = unsafe { self.instance_belt.alloc_typed(quads, &self.gpu) };
-                    let mut encoder = pass.with(&self.pipelines.quads);
+                    let mut encoder = pass.with(self.pipelines.quads.get(quads[0].blend_mode));
                     encoder.bind(
                         0,
                         &ShaderQuadsData {
                             globals,
                             b_quads: instance_buf,
+                            b_clip_regions: clip_regions.regions,
+                            b_clip_region_vertices: clip_regions.vertices,
                         },
                     );
                     encoder.draw(0, 4, 0, quads.len() as u32);
@@ -699,10 +1282,29 @@ impl BladeRenderer {
                         &ShaderShadowsData {
                             globals,
                             b_shadows: instance_buf,
+                            b_clip_regions: clip_regions.regions,
+                            b_clip_region_vertices: clip_regions.vertices,
                         },
                     );
                     encoder.draw(0, 4, 0, shadows.len() as u32);
                 }
+                PrimitiveBatch::Backdrops(backdrops) => {
+                    drop(pass);
+                    for backdrop in backdrops {
+                        self.draw_backdrop(backdrop, target, target_view, globals, clip_regions);
+                    }
+                    pass = self.command_encoder.render(
+                        "main",
+                        gpu::RenderTargetSet {
+                            colors: &[gpu::RenderTarget {
+                                view: target_view,
+                                init_op: gpu::InitOp::Load,
+                                finish_op: gpu::FinishOp::Store,
+                            }],
+                            depth_stencil: None,
+                        },
+                    );
+                }
                 PrimitiveBatch::Paths(paths) => {
                     let Some(first_path) = paths.first() else {
                         continue;
@@ -712,12 +1314,13 @@ impl BladeRenderer {
                         paths,
                         self.surface_config.size.width as f32,
                         self.surface_config.size.height as f32,
+                        clip_regions,
                     );
                     pass = self.command_encoder.render(
                         "main",
                         gpu::RenderTargetSet {
                             colors: &[gpu::RenderTarget {
-                                view: frame.texture_view(),
+                                view: target_view,
                                 init_op: gpu::InitOp::Load,
                                 finish_op: gpu::FinishOp::Store,
                             }],
@@ -768,6 +1371,8 @@ impl BladeRenderer {
                         &ShaderUnderlinesData {
                             globals,
                             b_underlines: instance_buf,
+                            b_clip_regions: clip_regions.regions,
+                            b_clip_region_vertices: clip_regions.vertices,
                         },
                     );
                     encoder.draw(0, 4, 0, underlines.len() as u32);
@@ -779,7 +1384,8 @@ impl BladeRenderer {
                     let tex_info = self.atlas.get_texture_info(texture_id);
                     let instance_buf =
                         unsafe { self.instance_belt.alloc_typed(sprites, &self.gpu) };
-                    let mut encoder = pass.with(&self.pipelines.mono_sprites);
+                    let mut encoder =
+                        pass.with(self.pipelines.mono_sprites.get(sprites[0].blend_mode));
                     encoder.bind(
                         0,
                         &ShaderMonoSpritesData {
@@ -791,6 +1397,34 @@ impl BladeRenderer {
                             t_sprite: tex_info.raw_view,
                             s_sprite: self.atlas_sampler,
                             b_mono_sprites: instance_buf,
+                            b_clip_regions: clip_regions.regions,
+                            b_clip_region_vertices: clip_regions.vertices,
+                        },
+                    );
+                    encoder.draw(0, 4, 0, sprites.len() as u32);
+                }
+                PrimitiveBatch::GradientSprites {
+                    texture_id,
+                    sprites,
+                } => {
+                    let tex_info = self.atlas.get_texture_info(texture_id);
+                    let instance_buf =
+                        unsafe { self.instance_belt.alloc_typed(sprites, &self.gpu) };
+                    let mut encoder =
+                        pass.with(self.pipelines.gradient_sprites.get(sprites[0].blend_mode));
+                    encoder.bind(
+                        0,
+                        &ShaderGradientSpritesData {
+                            globals,
+                            gamma_ratios: self.rendering_parameters.gamma_ratios,
+                            grayscale_enhanced_contrast: self
+                                .rendering_parameters
+                                .grayscale_enhanced_contrast,
+                            t_sprite: tex_info.raw_view,
+                            s_sprite: self.atlas_sampler,
+                            b_gradient_sprites: instance_buf,
+                            b_clip_regions: clip_regions.regions,
+                            b_clip_region_vertices: clip_regions.vertices,
                         },
                     );
                     encoder.draw(0, 4, 0, sprites.len() as u32);
@@ -802,7 +1436,8 @@ impl BladeRenderer {
                     let tex_info = self.atlas.get_texture_info(texture_id);
                     let instance_buf =
                         unsafe { self.instance_belt.alloc_typed(sprites, &self.gpu) };
-                    let mut encoder = pass.with(&self.pipelines.poly_sprites);
+                    let mut encoder =
+                        pass.with(self.pipelines.poly_sprites.get(sprites[0].blend_mode));
                     encoder.bind(
                         0,
                         &ShaderPolySpritesData {
@@ -810,6 +1445,8 @@ impl BladeRenderer {
                             t_sprite: tex_info.raw_view,
                             s_sprite: self.atlas_sampler,
                             b_poly_sprites: instance_buf,
+                            b_clip_regions: clip_regions.regions,
+                            b_clip_region_vertices: clip_regions.vertices,
                         },
                     );
                     encoder.draw(0, 4, 0, sprites.len() as u32);
@@ -904,18 +1541,24 @@ impl BladeRenderer {
                     }
                 }
             }
+
+            if let Some(bounds) = overlay_bounds {
+                drop(pass);
+                self.draw_overlay(bounds, target, target_view, globals);
+                pass = self.command_encoder.render(
+                    "main",
+                    gpu::RenderTargetSet {
+                        colors: &[gpu::RenderTarget {
+                            view: target_view,
+                            init_op: gpu::InitOp::Load,
+                            finish_op: gpu::FinishOp::Store,
+                        }],
+                        depth_stencil: None,
+                    },
+                );
+            }
         }
         drop(pass);
-
-        self.command_encoder.present(frame);
-        let sync_point = self.gpu.submit(&mut self.command_encoder);
-
-        profiling::scope!("finish");
-        self.instance_belt.flush(&sync_point);
-        self.atlas.after_frame(&sync_point);
-
-        self.wait_for_gpu();
-        self.last_sync_point = Some(sync_point);
     }
 }
 
@@ -952,6 +1595,40 @@ fn create_path_intermediate_texture(
     (texture, texture_view)
 }
 
+fn create_target_texture(
+    gpu: &gpu::Context,
+    name: &str,
+    format: gpu::TextureFormat,
+    width: u32,
+    height: u32,
+) -> (gpu::Texture, gpu::TextureView) {
+    let texture = gpu.create_texture(gpu::TextureDesc {
+        name,
+        format,
+        size: gpu::Extent {
+            width,
+            height,
+            depth: 1,
+        },
+        array_layer_count: 1,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: gpu::TextureDimension::D2,
+        usage: gpu::TextureUsage::RESOURCE | gpu::TextureUsage::TARGET,
+        external: None,
+    });
+    let texture_view = gpu.create_texture_view(
+        texture,
+        gpu::TextureViewDesc {
+            name: &format!("{name} view"),
+            format,
+            dimension: gpu::ViewDimension::D2,
+            subresources: &Default::default(),
+        },
+    );
+    (texture, texture_view)
+}
+
 fn create_msaa_texture_if_needed(
     gpu: &gpu::Context,
     format: gpu::TextureFormat,
@@ -101,6 +101,13 @@ struct Hsla {
     a: f32,
 }
 
+struct HslaEdges {
+    top: Hsla,
+    right: Hsla,
+    bottom: Hsla,
+    left: Hsla,
+}
+
 struct LinearColorStop {
     color: Hsla,
     percentage: f32,
@@ -110,6 +117,8 @@ struct Background {
     // 0u is Solid
     // 1u is LinearGradient
     // 2u is PatternSlash
+    // 3u is RadialGradient
+    // 4u is ConicGradient
     tag: u32,
     // 0u is sRGB linear color
     // 1u is Oklab color
@@ -117,7 +126,9 @@ struct Background {
     solid: Hsla,
     gradient_angle_or_pattern_height: f32,
     colors: array<LinearColorStop, 2>,
-    pad: u32,
+    gradient_center_x: f32,
+    gradient_center_y: f32,
+    gradient_radius: f32,
 }
 
 struct AtlasTextureId {
@@ -329,6 +340,33 @@ fn pick_corner_radius(center_to_point: vec2<f32>, radii: Corners) -> f32 {
     }
 }
 
+// Returns the color of the border nearest to the point. Where two sides meet at
+// a corner, they are split along the diagonal from the outer corner to the
+// inner corner, like in CSS.
+fn pick_border_color(center_to_point: vec2<f32>, corner_to_point: vec2<f32>,
+                     border: vec2<f32>, colors: HslaEdges,
+                     antialias_threshold: f32) -> vec4<f32> {
+    var horizontal = colors.bottom;
+    if (center_to_point.y < 0.0) {
+        horizontal = colors.top;
+    }
+    var vertical = colors.right;
+    if (center_to_point.x < 0.0) {
+        vertical = colors.left;
+    }
+    let horizontal_color = hsla_to_rgba(horizontal);
+    let miter_length = length(border);
+    if (miter_length == 0.0) {
+        return horizontal_color;
+    }
+    // Signed distance of the point to the diagonal, positive on the side of
+    // the horizontal border.
+    let inset = -corner_to_point;
+    let miter_sdf = (inset.x * border.y - inset.y * border.x) / miter_length;
+    return mix(hsla_to_rgba(vertical), horizontal_color,
+               saturate(miter_sdf + antialias_threshold));
+}
+
 // Signed distance of the point to the quad's border - positive outside the
 // border, and negative inside.
 //
@@ -361,6 +399,83 @@ fn quad_sdf_impl(corner_center_to_point: vec2<f32>, corner_radius: f32) -> f32 {
     }
 }
 
+// --- clip regions --- //
+
+struct ClipRegion {
+    bounds: Bounds,
+    corner_radii: Corners,
+    kind: u32,
+    parent: u32,
+    vertices_start: u32,
+    vertex_count: u32,
+}
+var<storage, read> b_clip_regions: array<ClipRegion>;
+var<storage, read> b_clip_region_vertices: array<vec2<f32>>;
+
+// Approximate signed distance of the point to the ellipse inscribed in the bounds.
+fn ellipse_sdf(point: vec2<f32>, bounds: Bounds) -> f32 {
+    let radii = bounds.size / 2.0;
+    if (radii.x <= 0.0 || radii.y <= 0.0) {
+        return 1.0;
+    }
+    let center_to_point = point - (bounds.origin + radii);
+    let k1 = length(center_to_point / radii);
+    let k2 = length(center_to_point / (radii * radii));
+    if (k2 == 0.0) {
+        return -min(radii.x, radii.y);
+    }
+    return k1 * (k1 - 1.0) / k2;
+}
+
+// Signed distance of the point to the polygon, which is negative inside of
+// it according to the even-odd rule.
+fn polygon_sdf(point: vec2<f32>, vertices_start: u32, vertex_count: u32) -> f32 {
+    if (vertex_count < 3u) {
+        return 1.0;
+    }
+    var previous = b_clip_region_vertices[vertices_start + vertex_count - 1u];
+    var distance_squared = 1e30;
+    var side = 1.0;
+    for (var i = 0u; i < vertex_count; i += 1u) {
+        let vertex = b_clip_region_vertices[vertices_start + i];
+        let edge = previous - vertex;
+        let vertex_to_point = point - vertex;
+        let t = saturate(dot(vertex_to_point, edge) / max(dot(edge, edge), 1e-6));
+        let edge_to_point = vertex_to_point - edge * t;
+        distance_squared = min(distance_squared, dot(edge_to_point, edge_to_point));
+        // Each edge crossed by a ray going right from the point toggles the side.
+        if ((vertex.y > point.y) != (previous.y > point.y)) {
+            let crossing_x = vertex.x + (point.y - vertex.y) / (previous.y - vertex.y) * edge.x;
+            if (point.x < crossing_x) {
+                side = -side;
+            }
+        }
+        previous = vertex;
+    }
+    return side * sqrt(distance_squared);
+}
+
+// The coverage of the point by the clip region with the given id and the
+// regions enclosing it. An id of 0 means that there is no clip region.
+fn clip_region_alpha(clip_region: u32, point: vec2<f32>) -> f32 {
+    var alpha = 1.0;
+    var id = clip_region;
+    while (id != 0u && alpha > 0.0) {
+        let region = b_clip_regions[id - 1u];
+        var distance: f32;
+        if (region.kind == 1u) {
+            distance = ellipse_sdf(point, region.bounds);
+        } else if (region.kind == 2u) {
+            distance = polygon_sdf(point, region.vertices_start, region.vertex_count);
+        } else {
+            distance = quad_sdf(point, region.bounds, region.corner_radii);
+        }
+        alpha *= saturate(0.5 - distance);
+        id = region.parent;
+    }
+    return alpha;
+}
+
 // Abstract away the final color transformation based on the
 // target alpha compositing mode.
 fn blend_color(color: vec4<f32>, alpha_factor: f32) -> vec4<f32> {
@@ -369,6 +484,16 @@ fn blend_color(color: vec4<f32>, alpha_factor: f32) -> vec4<f32> {
     return vec4<f32>(color.rgb * multiplier, alpha);
 }
 
+// Multiply (1) and screen (2) are drawn with blend states that expect premultiplied colors,
+// whatever the alpha mode of the surface.
+fn blend_mode_color(blend_mode: u32, color: vec4<f32>, alpha_factor: f32) -> vec4<f32> {
+    if (blend_mode == 1u || blend_mode == 2u) {
+        let alpha = color.a * alpha_factor;
+        return vec4<f32>(color.rgb * alpha, alpha);
+    }
+    return blend_color(color, alpha_factor);
+}
+
 
 struct GradientColor {
     solid: vec4<f32>,
@@ -382,7 +507,7 @@ fn prepare_gradient_color(tag: u32, color_space: u32,
 
     if (tag == 0u || tag == 2u) {
         result.solid = hsla_to_rgba(solid);
-    } else if (tag == 1u) {
+    } else {
         // The hsla_to_rgba is returns a linear sRGB color
         result.color0 = hsla_to_rgba(colors[0].color);
         result.color1 = hsla_to_rgba(colors[1].color);
@@ -403,6 +528,23 @@ fn prepare_gradient_color(tag: u32, color_space: u32,
     return result;
 }
 
+// Returns the color at `t` along a gradient, adjusted by the stop percentages.
+fn gradient_stops_color(background: Background, color0: vec4<f32>, color1: vec4<f32>, t: f32) -> vec4<f32> {
+    let stop0_percentage = background.colors[0].percentage;
+    let stop1_percentage = background.colors[1].percentage;
+    let stop_t = clamp((t - stop0_percentage) / (stop1_percentage - stop0_percentage), 0.0, 1.0);
+
+    switch (background.color_space) {
+        default: {
+            return srgba_to_linear(mix(color0, color1, stop_t));
+        }
+        case 1u: {
+            let oklab_color = mix(color0, color1, stop_t);
+            return oklab_to_linear_srgb(oklab_color);
+        }
+    }
+}
+
 fn gradient_color(background: Background, position: vec2<f32>, bounds: Bounds,
     solid_color: vec4<f32>, color0: vec4<f32>, color1: vec4<f32>) -> vec4<f32> {
     var background_color = vec4<f32>(0.0);
@@ -417,8 +559,6 @@ fn gradient_color(background: Background, position: vec2<f32>, bounds: Bounds,
             let angle = background.gradient_angle_or_pattern_height;
             let radians = (angle % 360.0 - 90.0) * M_PI_F / 180.0;
             var direction = vec2<f32>(cos(radians), sin(radians));
-            let stop0_percentage = background.colors[0].percentage;
-            let stop1_percentage = background.colors[1].percentage;
 
             // Expand the short side to be the same as the long side
             if (bounds.size.x > bounds.size.y) {
@@ -439,19 +579,22 @@ fn gradient_color(background: Background, position: vec2<f32>, bounds: Bounds,
                 t = (t + half_size.y) / bounds.size.y;
             }
 
-            // Adjust t based on the stop percentages
-            t = (t - stop0_percentage) / (stop1_percentage - stop0_percentage);
-            t = clamp(t, 0.0, 1.0);
-
-            switch (background.color_space) {
-                default: {
-                    background_color = srgba_to_linear(mix(color0, color1, t));
-                }
-                case 1u: {
-                    let oklab_color = mix(color0, color1, t);
-                    background_color = oklab_to_linear_srgb(oklab_color);
-                }
-            }
+            background_color = gradient_stops_color(background, color0, color1, t);
+        }
+        case 3u: {
+            // Radial gradient, where a radius of 1 reaches the farthest corner.
+            let center = bounds.origin + vec2<f32>(background.gradient_center_x, background.gradient_center_y) * bounds.size;
+            let farthest_corner = max(abs(center - bounds.origin), abs(bounds.origin + bounds.size - center));
+            let t = distance(position, center) / (background.gradient_radius * length(farthest_corner));
+            background_color = gradient_stops_color(background, color0, color1, t);
+        }
+        case 4u: {
+            // Conic gradient, where 0 degrees is up and angles increase clockwise.
+            let center = bounds.origin + vec2<f32>(background.gradient_center_x, background.gradient_center_y) * bounds.size;
+            let center_to_point = position - center;
+            let angle = atan2(center_to_point.x, -center_to_point.y) * 180.0 / M_PI_F;
+            let t = fract((angle - background.gradient_angle_or_pattern_height) / 360.0);
+            background_color = gradient_stops_color(background, color0, color1, t);
         }
         case 2u: {
             let gradient_angle_or_pattern_height = background.gradient_angle_or_pattern_height;
@@ -484,21 +627,22 @@ struct Quad {
     bounds: Bounds,
     content_mask: Bounds,
     background: Background,
-    border_color: Hsla,
+    border_colors: HslaEdges,
     corner_radii: Corners,
     border_widths: Edges,
+    clip_region: u32,
+    blend_mode: u32,
 }
 var<storage, read> b_quads: array<Quad>;
 
 struct QuadVarying {
     @builtin(position) position: vec4<f32>,
-    @location(0) @interpolate(flat) border_color: vec4<f32>,
-    @location(1) @interpolate(flat) quad_id: u32,
+    @location(0) @interpolate(flat) quad_id: u32,
     // TODO: use `clip_distance` once Naga supports it
-    @location(2) clip_distances: vec4<f32>,
-    @location(3) @interpolate(flat) background_solid: vec4<f32>,
-    @location(4) @interpolate(flat) background_color0: vec4<f32>,
-    @location(5) @interpolate(flat) background_color1: vec4<f32>,
+    @location(1) clip_distances: vec4<f32>,
+    @location(2) @interpolate(flat) background_solid: vec4<f32>,
+    @location(3) @interpolate(flat) background_color0: vec4<f32>,
+    @location(4) @interpolate(flat) background_color1: vec4<f32>,
 }
 
 @vertex
@@ -518,7 +662,6 @@ fn vs_quad(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) insta
     out.background_solid = gradient.solid;
     out.background_color0 = gradient.color0;
     out.background_color1 = gradient.color1;
-    out.border_color = hsla_to_rgba(quad.border_color);
     out.quad_id = instance_id;
     out.clip_distances = distance_from_clip_rect(unit_vertex, quad.bounds, quad.content_mask);
     return out;
@@ -532,6 +675,10 @@ fn fs_quad(input: QuadVarying) -> @location(0) vec4<f32> {
     }
 
     let quad = b_quads[input.quad_id];
+    let clip_alpha = clip_region_alpha(quad.clip_region, input.position.xy);
+    if (clip_alpha <= 0.0) {
+        return vec4<f32>(0.0);
+    }
 
     let background_color = gradient_color(quad.background, input.position.xy, quad.bounds,
         input.background_solid, input.background_color0, input.background_color1);
@@ -547,7 +694,7 @@ fn fs_quad(input: QuadVarying) -> @location(0) vec4<f32> {
             quad.border_widths.right == 0.0 &&
             quad.border_widths.bottom == 0.0 &&
             unrounded) {
-        return blend_color(background_color, 1.0);
+        return blend_mode_color(quad.blend_mode, background_color, clip_alpha);
     }
 
     let size = quad.bounds.size;
@@ -613,7 +760,7 @@ fn fs_quad(input: QuadVarying) -> @location(0) vec4<f32> {
     // However, that might negatively impact performance in the case of
     // reasonable sizes for rounded corners.
     if (is_within_inner_straight_border && !is_near_rounded_corner) {
-        return blend_color(background_color, 1.0);
+        return blend_mode_color(quad.blend_mode, background_color, clip_alpha);
     }
 
     // Signed distance of the point to the outside edge of the quad's border. It
@@ -649,7 +796,8 @@ fn fs_quad(input: QuadVarying) -> @location(0) vec4<f32> {
 
     var color = background_color;
     if (border_sdf < antialias_threshold) {
-        var border_color = input.border_color;
+        var border_color = pick_border_color(center_to_point, corner_to_point,
+            border, quad.border_colors, antialias_threshold);
 
         // Dashed border logic when border_style == 1
         if (quad.border_style == 1) {
@@ -852,7 +1000,7 @@ fn fs_quad(input: QuadVarying) -> @location(0) vec4<f32> {
                     saturate(antialias_threshold - inner_sdf));
     }
 
-    return blend_color(color, saturate(antialias_threshold - outer_sdf));
+    return blend_mode_color(quad.blend_mode, color, saturate(antialias_threshold - outer_sdf) * clip_alpha);
 }
 
 // Returns the dash velocity of a corner given the dash velocity of the two
@@ -917,6 +1065,10 @@ struct Shadow {
     corner_radii: Corners,
     content_mask: Bounds,
     color: Hsla,
+    clip_bounds: Bounds,
+    clip_corner_radii: Corners,
+    inset: u32,
+    clip_region: u32,
 }
 var<storage, read> b_shadows: array<Shadow>;
 
@@ -933,11 +1085,16 @@ fn vs_shadow(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) ins
     let unit_vertex = vec2<f32>(f32(vertex_id & 1u), 0.5 * f32(vertex_id & 2u));
     var shadow = b_shadows[instance_id];
 
-    let margin = 3.0 * shadow.blur_radius;
-    // Set the bounds of the shadow and adjust its size based on the shadow's
-    // spread radius to achieve the spreading effect
-    shadow.bounds.origin -= vec2<f32>(margin);
-    shadow.bounds.size += 2.0 * vec2<f32>(margin);
+    if (shadow.inset != 0u) {
+        // Inset shadows are only painted within the element's bounds
+        shadow.bounds = shadow.clip_bounds;
+    } else {
+        let margin = 3.0 * shadow.blur_radius;
+        // Set the bounds of the shadow and adjust its size based on the shadow's
+        // spread radius to achieve the spreading effect
+        shadow.bounds.origin -= vec2<f32>(margin);
+        shadow.bounds.size += 2.0 * vec2<f32>(margin);
+    }
 
     var out = ShadowVarying();
     out.position = to_device_position(unit_vertex, shadow.bounds);
@@ -961,26 +1118,131 @@ fn fs_shadow(input: ShadowVarying) -> @location(0) vec4<f32> {
 
     let corner_radius = pick_corner_radius(center_to_point, shadow.corner_radii);
 
-    // The signal is only non-zero in a limited range, so don't waste samples
-    let low = center_to_point.y - half_size.y;
-    let high = center_to_point.y + half_size.y;
-    let start = clamp(-3.0 * shadow.blur_radius, low, high);
-    let end = clamp(3.0 * shadow.blur_radius, low, high);
-
-    // Accumulate samples (we can get away with surprisingly few samples)
-    let step = (end - start) / 4.0;
-    var y = start + step * 0.5;
     var alpha = 0.0;
-    for (var i = 0; i < 4; i += 1) {
-        let blur = blur_along_x(center_to_point.x, center_to_point.y - y,
-            shadow.blur_radius, corner_radius, half_size);
-        alpha +=  blur * gaussian(y, shadow.blur_radius) * step;
-        y += step;
+    if (shadow.blur_radius == 0.0) {
+        let distance = quad_sdf(input.position.xy, shadow.bounds, shadow.corner_radii);
+        alpha = saturate(0.5 - distance);
+    } else {
+        // The signal is only non-zero in a limited range, so don't waste samples
+        let low = center_to_point.y - half_size.y;
+        let high = center_to_point.y + half_size.y;
+        let start = clamp(-3.0 * shadow.blur_radius, low, high);
+        let end = clamp(3.0 * shadow.blur_radius, low, high);
+
+        // Accumulate samples (we can get away with surprisingly few samples)
+        let step = (end - start) / 4.0;
+        var y = start + step * 0.5;
+        for (var i = 0; i < 4; i += 1) {
+            let blur = blur_along_x(center_to_point.x, center_to_point.y - y,
+                shadow.blur_radius, corner_radius, half_size);
+            alpha +=  blur * gaussian(y, shadow.blur_radius) * step;
+            y += step;
+        }
     }
 
+    if (shadow.inset != 0u) {
+        // Inset shadows are cast by everything outside of the shadow's rounded
+        // rect, clipped to the inside of the element's.
+        let clip_distance = quad_sdf(input.position.xy, shadow.clip_bounds,
+            shadow.clip_corner_radii);
+        alpha = (1.0 - alpha) * saturate(0.5 - clip_distance);
+    }
+    alpha *= clip_region_alpha(shadow.clip_region, input.position.xy);
+
     return blend_color(input.color, alpha);
 }
 
+// --- backdrops --- //
+
+struct Backdrop {
+    order: u32,
+    blur_radius: f32,
+    bounds: Bounds,
+    corner_radii: Corners,
+    content_mask: Bounds,
+    fallback_color: Hsla,
+    clip_region: u32,
+    pad: u32,
+}
+var<storage, read> b_backdrops: array<Backdrop>;
+
+struct BackdropVarying {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) backdrop_id: u32,
+    //TODO: use `clip_distance` once Naga supports it
+    @location(1) clip_distances: vec4<f32>,
+}
+
+// Blurs `t_sprite` along one direction around the given position, using a gaussian
+// with the given sigma. Samples are spread out for large radii to bound their count.
+fn blur_backdrop(position: vec2<f32>, direction: vec2<f32>, sigma: f32) -> vec4<f32> {
+    let texture_size = vec2<f32>(textureDimensions(t_sprite, 0));
+    let reach = ceil(3.0 * sigma);
+    let step = max(1.0, reach / 16.0);
+    var color = vec4<f32>(0.0);
+    var total_weight = 0.0;
+    for (var offset = -reach; offset <= reach; offset += step) {
+        let weight = gaussian(offset, sigma);
+        let coords = (position + direction * offset) / texture_size;
+        color += weight * textureSampleLevel(t_sprite, s_sprite, coords, 0.0);
+        total_weight += weight;
+    }
+    return color / total_weight;
+}
+
+// The horizontal pass covers the visible part of the backdrop, extended vertically by the
+// reach of the blur so that the vertical pass has every row it samples.
+@vertex
+fn vs_backdrop_horizontal(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) instance_id: u32) -> BackdropVarying {
+    let unit_vertex = vec2<f32>(f32(vertex_id & 1u), 0.5 * f32(vertex_id & 2u));
+    let backdrop = b_backdrops[instance_id];
+    let margin = 3.0 * backdrop.blur_radius;
+    var bounds = backdrop.content_mask;
+    let top_left = max(backdrop.bounds.origin, bounds.origin);
+    let bottom_right = min(backdrop.bounds.origin + backdrop.bounds.size, bounds.origin + bounds.size);
+    bounds.origin = top_left - vec2<f32>(0.0, margin);
+    bounds.size = max(bottom_right - top_left, vec2<f32>(0.0)) + vec2<f32>(0.0, 2.0 * margin);
+
+    var out = BackdropVarying();
+    out.position = to_device_position(unit_vertex, bounds);
+    out.backdrop_id = instance_id;
+    out.clip_distances = vec4<f32>(0.0);
+    return out;
+}
+
+@fragment
+fn fs_backdrop_horizontal(input: BackdropVarying) -> @location(0) vec4<f32> {
+    let backdrop = b_backdrops[input.backdrop_id];
+    return blur_backdrop(input.position.xy, vec2<f32>(1.0, 0.0), backdrop.blur_radius);
+}
+
+@vertex
+fn vs_backdrop_vertical(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) instance_id: u32) -> BackdropVarying {
+    let unit_vertex = vec2<f32>(f32(vertex_id & 1u), 0.5 * f32(vertex_id & 2u));
+    let backdrop = b_backdrops[instance_id];
+
+    var out = BackdropVarying();
+    out.position = to_device_position(unit_vertex, backdrop.bounds);
+    out.backdrop_id = instance_id;
+    out.clip_distances = distance_from_clip_rect(unit_vertex, backdrop.bounds, backdrop.content_mask);
+    return out;
+}
+
+@fragment
+fn fs_backdrop_vertical(input: BackdropVarying) -> @location(0) vec4<f32> {
+    // Alpha clip first, since we don't have `clip_distance`.
+    if (any(input.clip_distances < vec4<f32>(0.0))) {
+        return vec4<f32>(0.0);
+    }
+
+    let backdrop = b_backdrops[input.backdrop_id];
+    let distance = quad_sdf(input.position.xy, backdrop.bounds, backdrop.corner_radii);
+    let coverage = saturate(0.5 - distance)
+        * clip_region_alpha(backdrop.clip_region, input.position.xy);
+    let color = blur_backdrop(input.position.xy, vec2<f32>(0.0, 1.0), backdrop.blur_radius);
+    return color * coverage;
+}
+
 // --- path rasterization --- //
 
 struct PathRasterizationVertex {
@@ -988,6 +1250,8 @@ struct PathRasterizationVertex {
     st_position: vec2<f32>,
     color: Background,
     bounds: Bounds,
+    clip_region: u32,
+    pad: u32,
 }
 
 var<storage, read> b_path_vertices: array<PathRasterizationVertex>;
@@ -1034,6 +1298,7 @@ fn fs_path_rasterization(input: PathRasterizationVarying) -> @location(0) vec4<f
         let distance = f / length(gradient);
         alpha = saturate(0.5 - distance);
     }
+    alpha *= clip_region_alpha(v.clip_region, input.position.xy);
     let gradient_color = prepare_gradient_color(
         background.tag,
         background.color_space,
@@ -1080,16 +1345,43 @@ fn fs_path(input: PathVarying) -> @location(0) vec4<f32> {
     return sample;
 }
 
+// --- overlays --- //
+
+var t_overlay_backdrop: texture_2d<f32>;
+
+// Composites a layer painted with premultiplied colors over a copy of what's behind it,
+// drawn over the bounds of the layer with `vs_path`.
+@fragment
+fn fs_overlay(input: PathVarying) -> @location(0) vec4<f32> {
+    let position = vec2<i32>(input.position.xy);
+    let layer = textureLoad(t_sprite, position, 0);
+    if (layer.a <= 0.0) {
+        return vec4<f32>(0.0);
+    }
+    let backdrop = textureLoad(t_overlay_backdrop, position, 0);
+    let source = saturate(layer.rgb / layer.a);
+    var behind = vec3<f32>(0.0);
+    if (backdrop.a > 0.0) {
+        behind = saturate(backdrop.rgb / backdrop.a);
+    }
+    let overlay = select(
+        1.0 - 2.0 * (1.0 - source) * (1.0 - behind),
+        2.0 * source * behind,
+        behind <= vec3<f32>(0.5),
+    );
+    return vec4<f32>(mix(source, overlay, backdrop.a) * layer.a, layer.a);
+}
+
 // --- underlines --- //
 
 struct Underline {
     order: u32,
-    pad: u32,
+    clip_region: u32,
     bounds: Bounds,
     content_mask: Bounds,
     color: Hsla,
     thickness: f32,
-    wavy: u32,
+    style: u32,
 }
 var<storage, read> b_underlines: array<Underline>;
 
@@ -1125,9 +1417,21 @@ fn fs_underline(input: UnderlineVarying) -> @location(0) vec4<f32> {
     }
 
     let underline = b_underlines[input.underline_id];
-    if ((underline.wavy & 0xFFu) == 0u)
+    let clip_alpha = clip_region_alpha(underline.clip_region, input.position.xy);
+    let style = underline.style & 0xFFu;
+    if (style == 2u || style == 3u) {
+        // Dotted lines alternate square dots with equal gaps, dashed lines alternate
+        // dashes three times as long as the line is thick with gaps twice as long.
+        let dotted = style == 2u;
+        let dash_length = select(underline.thickness * 3.0, underline.thickness, dotted);
+        let gap_length = select(underline.thickness * 2.0, underline.thickness, dotted);
+        let x = input.position.x - underline.bounds.origin.x;
+        let alpha = select(0.0, 1.0, x % (dash_length + gap_length) < dash_length);
+        return blend_color(input.color, alpha * input.color.a * clip_alpha);
+    }
+    if (style != 1u)
     {
-        return blend_color(input.color, input.color.a);
+        return blend_color(input.color, input.color.a * clip_alpha);
     }
 
     let half_thickness = underline.thickness * 0.5;
@@ -1143,19 +1447,21 @@ fn fs_underline(input: UnderlineVarying) -> @location(0) vec4<f32> {
     let distance_from_top_border = distance_in_pixels - half_thickness;
     let distance_from_bottom_border = distance_in_pixels + half_thickness;
     let alpha = saturate(0.5 - max(-distance_from_bottom_border, distance_from_top_border));
-    return blend_color(input.color, alpha * input.color.a);
+    return blend_color(input.color, alpha * input.color.a * clip_alpha);
 }
 
 // --- monochrome sprites --- //
 
 struct MonochromeSprite {
     order: u32,
-    pad: u32,
+    clip_region: u32,
     bounds: Bounds,
     content_mask: Bounds,
     color: Hsla,
     tile: AtlasTile,
     transformation: TransformationMatrix,
+    blend_mode: u32,
+    pad: u32,
 }
 var<storage, read> b_mono_sprites: array<MonochromeSprite>;
 
@@ -1164,6 +1470,7 @@ struct MonoSpriteVarying {
     @location(0) tile_position: vec2<f32>,
     @location(1) @interpolate(flat) color: vec4<f32>,
     @location(3) clip_distances: vec4<f32>,
+    @location(4) @interpolate(flat) sprite_id: u32,
 }
 
 @vertex
@@ -1176,6 +1483,7 @@ fn vs_mono_sprite(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index
 
     out.tile_position = to_tile_position(unit_vertex, sprite.tile);
     out.color = hsla_to_rgba(sprite.color);
+    out.sprite_id = instance_id;
     out.clip_distances = distance_from_clip_rect_transformed(unit_vertex, sprite.bounds, sprite.content_mask, sprite.transformation);
     return out;
 }
@@ -1190,21 +1498,96 @@ fn fs_mono_sprite(input: MonoSpriteVarying) -> @location(0) vec4<f32> {
         return vec4<f32>(0.0);
     }
 
+    let sprite = b_mono_sprites[input.sprite_id];
+    let clip_alpha = clip_region_alpha(sprite.clip_region, input.position.xy);
+    // convert to srgb space as the rest of the code (output swapchain) expects that
+    return blend_mode_color(sprite.blend_mode, input.color, alpha_corrected * clip_alpha);
+}
+
+// --- gradient sprites --- //
+
+struct GradientSprite {
+    order: u32,
+    clip_region: u32,
+    bounds: Bounds,
+    content_mask: Bounds,
+    color: Background,
+    tile: AtlasTile,
+    transformation: TransformationMatrix,
+    blend_mode: u32,
+    pad: u32,
+}
+var<storage, read> b_gradient_sprites: array<GradientSprite>;
+
+struct GradientSpriteVarying {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tile_position: vec2<f32>,
+    @location(1) @interpolate(flat) color: vec4<f32>,
+    @location(2) sprite_position: vec2<f32>,
+    @location(3) clip_distances: vec4<f32>,
+    @location(4) @interpolate(flat) color0: vec4<f32>,
+    @location(5) @interpolate(flat) color1: vec4<f32>,
+    @location(6) @interpolate(flat) sprite_id: u32,
+}
+
+@vertex
+fn vs_gradient_sprite(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) instance_id: u32) -> GradientSpriteVarying {
+    let unit_vertex = vec2<f32>(f32(vertex_id & 1u), 0.5 * f32(vertex_id & 2u));
+    let sprite = b_gradient_sprites[instance_id];
+
+    var out = GradientSpriteVarying();
+    out.position = to_device_position_transformed(unit_vertex, sprite.bounds, sprite.transformation);
+
+    out.tile_position = to_tile_position(unit_vertex, sprite.tile);
+    // Gradients are laid out across the untransformed bounds, so they follow the transformation.
+    out.sprite_position = sprite.bounds.origin + unit_vertex * sprite.bounds.size;
+    let gradient = prepare_gradient_color(
+        sprite.color.tag,
+        sprite.color.color_space,
+        sprite.color.solid,
+        sprite.color.colors
+    );
+    out.color = gradient.solid;
+    out.color0 = gradient.color0;
+    out.color1 = gradient.color1;
+    out.sprite_id = instance_id;
+    out.clip_distances = distance_from_clip_rect_transformed(unit_vertex, sprite.bounds, sprite.content_mask, sprite.transformation);
+    return out;
+}
+
+@fragment
+fn fs_gradient_sprite(input: GradientSpriteVarying) -> @location(0) vec4<f32> {
+    let sample = textureSample(t_sprite, s_sprite, input.tile_position).r;
+
+    let sprite = b_gradient_sprites[input.sprite_id];
+    let color = gradient_color(sprite.color, input.sprite_position, sprite.bounds,
+        input.color, input.color0, input.color1);
+    let alpha_corrected = apply_contrast_and_gamma_correction(sample, color.rgb, grayscale_enhanced_contrast, gamma_ratios);
+
+    // Alpha clip after using the derivatives.
+    if (any(input.clip_distances < vec4<f32>(0.0))) {
+        return vec4<f32>(0.0);
+    }
+
+    let clip_alpha = clip_region_alpha(sprite.clip_region, input.position.xy);
     // convert to srgb space as the rest of the code (output swapchain) expects that
-    return blend_color(input.color, alpha_corrected);
+    return blend_mode_color(sprite.blend_mode, color, alpha_corrected * clip_alpha);
 }
 
 // --- polychrome sprites --- //
 
 struct PolychromeSprite {
     order: u32,
-    pad: u32,
+    clip_region: u32,
     grayscale: u32,
     opacity: f32,
     bounds: Bounds,
     content_mask: Bounds,
     corner_radii: Corners,
     tile: AtlasTile,
+    transformation: TransformationMatrix,
+    blend_mode: u32,
+    pad: u32,
 }
 var<storage, read> b_poly_sprites: array<PolychromeSprite>;
 
@@ -1212,6 +1595,7 @@ struct PolySpriteVarying {
     @builtin(position) position: vec4<f32>,
     @location(0) tile_position: vec2<f32>,
     @location(1) @interpolate(flat) sprite_id: u32,
+    @location(2) sprite_position: vec2<f32>,
     @location(3) clip_distances: vec4<f32>,
 }
 
@@ -1221,10 +1605,12 @@ fn vs_poly_sprite(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index
     let sprite = b_poly_sprites[instance_id];
 
     var out = PolySpriteVarying();
-    out.position = to_device_position(unit_vertex, sprite.bounds);
+    out.position = to_device_position_transformed(unit_vertex, sprite.bounds, sprite.transformation);
     out.tile_position = to_tile_position(unit_vertex, sprite.tile);
+    // Corners are rounded in the untransformed bounds, so they follow the transformation.
+    out.sprite_position = sprite.bounds.origin + unit_vertex * sprite.bounds.size;
     out.sprite_id = instance_id;
-    out.clip_distances = distance_from_clip_rect(unit_vertex, sprite.bounds, sprite.content_mask);
+    out.clip_distances = distance_from_clip_rect_transformed(unit_vertex, sprite.bounds, sprite.content_mask, sprite.transformation);
     return out;
 }
 
@@ -1237,14 +1623,15 @@ fn fs_poly_sprite(input: PolySpriteVarying) -> @location(0) vec4<f32> {
     }
 
     let sprite = b_poly_sprites[input.sprite_id];
-    let distance = quad_sdf(input.position.xy, sprite.bounds, sprite.corner_radii);
+    let distance = quad_sdf(input.sprite_position, sprite.bounds, sprite.corner_radii);
 
     var color = sample;
     if ((sprite.grayscale & 0xFFu) != 0u) {
         let grayscale = dot(color.rgb, GRAYSCALE_FACTORS);
         color = vec4<f32>(vec3<f32>(grayscale), sample.a);
     }
-    return blend_color(color, sprite.opacity * saturate(0.5 - distance));
+    let clip_alpha = clip_region_alpha(sprite.clip_region, input.position.xy);
+    return blend_mode_color(sprite.blend_mode, color, sprite.opacity * saturate(0.5 - distance) * clip_alpha);
 }
 
 // --- surfaces --- //
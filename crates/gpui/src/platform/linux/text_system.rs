@@ -1,13 +1,14 @@
 use crate::{
-    Bounds, DevicePixels, Font, FontFeatures, FontId, FontMetrics, FontRun, FontStyle, FontWeight,
-    GlyphId, LineLayout, Pixels, PlatformTextSystem, Point, RenderGlyphParams, SUBPIXEL_VARIANTS_X,
-    SUBPIXEL_VARIANTS_Y, ShapedGlyph, ShapedRun, SharedString, Size, point, size,
+    Bounds, DevicePixels, Font, FontFeatures, FontId, FontMetrics, FontRun, FontStyle,
+    FontVariations, FontWeight, GlyphId, LineLayout, Pixels, PlatformTextSystem, Point,
+    RenderGlyphParams, SUBPIXEL_VARIANTS_X, SUBPIXEL_VARIANTS_Y, ShapedGlyph, ShapedRun,
+    SharedString, Size, point, size,
 };
 use anyhow::{Context as _, Ok, Result};
 use collections::HashMap;
 use cosmic_text::{
     Attrs, AttrsList, CacheKey, Family, Font as CosmicTextFont, FontFeatures as CosmicFontFeatures,
-    FontSystem, ShapeBuffer, ShapeLine, SwashCache,
+    FontSystem, ShapeBuffer, ShapeLine, SwashCache, SwashImage,
 };
 
 use itertools::Itertools;
@@ -18,6 +19,11 @@ use pathfinder_geometry::{
 };
 use smallvec::SmallVec;
 use std::{borrow::Cow, sync::Arc};
+use swash::{
+    NormalizedCoord,
+    scale::{Render, ScaleContext, Source, StrikeWith},
+    zeno::{Format, Vector},
+};
 
 pub(crate) struct CosmicTextSystem(RwLock<CosmicTextSystemState>);
 
@@ -25,16 +31,24 @@ pub(crate) struct CosmicTextSystem(RwLock<CosmicTextSystemState>);
 struct FontKey {
     family: SharedString,
     features: FontFeatures,
+    variations: FontVariations,
 }
 
 impl FontKey {
-    fn new(family: SharedString, features: FontFeatures) -> Self {
-        Self { family, features }
+    fn new(family: SharedString, features: FontFeatures, variations: FontVariations) -> Self {
+        Self {
+            family,
+            features,
+            variations,
+        }
     }
 }
 
 struct CosmicTextSystemState {
     swash_cache: SwashCache,
+    /// Rasterizes glyphs of variable fonts, which the swash cache can only render at their
+    /// default axis values.
+    scale_context: ScaleContext,
     font_system: FontSystem,
     scratch: ShapeBuffer,
     /// Contains all already loaded fonts, including all faces. Indexed by `FontId`.
@@ -47,6 +61,8 @@ struct CosmicTextSystemState {
 struct LoadedFont {
     font: Arc<CosmicTextFont>,
     features: CosmicFontFeatures,
+    /// The normalized value of each of the font's variation axes, or empty to use the defaults.
+    coords: Vec<NormalizedCoord>,
     is_known_emoji_font: bool,
 }
 
@@ -58,6 +74,7 @@ impl CosmicTextSystem {
         Self(RwLock::new(CosmicTextSystemState {
             font_system,
             swash_cache: SwashCache::new(),
+            scale_context: ScaleContext::new(),
             scratch: ShapeBuffer::default(),
             loaded_fonts: Vec::new(),
             font_ids_by_family_cache: HashMap::default(),
@@ -93,11 +110,15 @@ impl PlatformTextSystem for CosmicTextSystem {
     fn font_id(&self, font: &Font) -> Result<FontId> {
         // todo(linux): Do we need to use CosmicText's Font APIs? Can we consolidate this to use font_kit?
         let mut state = self.0.write();
-        let key = FontKey::new(font.family.clone(), font.features.clone());
+        let key = FontKey::new(
+            font.family.clone(),
+            font.features.clone(),
+            font.variations.clone(),
+        );
         let candidates = if let Some(font_ids) = state.font_ids_by_family_cache.get(&key) {
             font_ids.as_slice()
         } else {
-            let font_ids = state.load_family(&font.family, &font.features)?;
+            let font_ids = state.load_family(&font.family, &font.features, &font.variations)?;
             state.font_ids_by_family_cache.insert(key.clone(), font_ids);
             state.font_ids_by_family_cache[&key].as_ref()
         };
@@ -120,13 +141,9 @@ impl PlatformTextSystem for CosmicTextSystem {
     }
 
     fn font_metrics(&self, font_id: FontId) -> FontMetrics {
-        let metrics = self
-            .0
-            .read()
-            .loaded_font(font_id)
-            .font
-            .as_swash()
-            .metrics(&[]);
+        let lock = self.0.read();
+        let loaded_font = lock.loaded_font(font_id);
+        let metrics = loaded_font.font.as_swash().metrics(&loaded_font.coords);
 
         FontMetrics {
             units_per_em: metrics.units_per_em as u32,
@@ -135,6 +152,8 @@ impl PlatformTextSystem for CosmicTextSystem {
             line_gap: metrics.leading,
             underline_position: metrics.underline_offset,
             underline_thickness: metrics.stroke_size,
+            strikethrough_position: metrics.strikeout_offset,
+            strikethrough_thickness: metrics.stroke_size,
             cap_height: metrics.cap_height,
             x_height: metrics.x_height,
             // todo(linux): Compute this correctly
@@ -147,7 +166,11 @@ impl PlatformTextSystem for CosmicTextSystem {
 
     fn typographic_bounds(&self, font_id: FontId, glyph_id: GlyphId) -> Result<Bounds<f32>> {
         let lock = self.0.read();
-        let glyph_metrics = lock.loaded_font(font_id).font.as_swash().glyph_metrics(&[]);
+        let loaded_font = lock.loaded_font(font_id);
+        let glyph_metrics = loaded_font
+            .font
+            .as_swash()
+            .glyph_metrics(&loaded_font.coords);
         let glyph_id = glyph_id.0 as u16;
         // todo(linux): Compute this correctly
         // see https://github.com/servo/font-kit/blob/master/src/loaders/freetype.rs#L614-L620
@@ -211,6 +234,7 @@ impl CosmicTextSystemState {
         &mut self,
         name: &str,
         features: &FontFeatures,
+        variations: &FontVariations,
     ) -> Result<SmallVec<[FontId; 4]>> {
         // TODO: Determine the proper system UI font.
         let name = crate::text_system::font_name_with_fallbacks(name, "IBM Plex Sans");
@@ -245,9 +269,11 @@ impl CosmicTextSystemState {
 
             let font_id = FontId(self.loaded_fonts.len());
             loaded_font_ids.push(font_id);
+            let coords = normalized_coords(&font, variations);
             self.loaded_fonts.push(LoadedFont {
                 font,
                 features: features.try_into()?,
+                coords,
                 is_known_emoji_font: check_is_known_emoji_font(&postscript_name),
             });
         }
@@ -256,7 +282,11 @@ impl CosmicTextSystemState {
     }
 
     fn advance(&self, font_id: FontId, glyph_id: GlyphId) -> Result<Size<f32>> {
-        let glyph_metrics = self.loaded_font(font_id).font.as_swash().glyph_metrics(&[]);
+        let loaded_font = self.loaded_font(font_id);
+        let glyph_metrics = loaded_font
+            .font
+            .as_swash()
+            .glyph_metrics(&loaded_font.coords);
         Ok(Size {
             width: glyph_metrics.advance_width(glyph_id.0 as u16),
             height: glyph_metrics.advance_height(glyph_id.0 as u16),
@@ -273,26 +303,7 @@ impl CosmicTextSystemState {
     }
 
     fn raster_bounds(&mut self, params: &RenderGlyphParams) -> Result<Bounds<DevicePixels>> {
-        let font = &self.loaded_fonts[params.font_id.0].font;
-        let subpixel_shift = point(
-            params.subpixel_variant.x as f32 / SUBPIXEL_VARIANTS_X as f32 / params.scale_factor,
-            params.subpixel_variant.y as f32 / SUBPIXEL_VARIANTS_Y as f32 / params.scale_factor,
-        );
-        let image = self
-            .swash_cache
-            .get_image(
-                &mut self.font_system,
-                CacheKey::new(
-                    font.id(),
-                    params.glyph_id.0 as u16,
-                    (params.font_size * params.scale_factor).into(),
-                    (subpixel_shift.x, subpixel_shift.y.trunc()),
-                    cosmic_text::CacheKeyFlags::empty(),
-                )
-                .0,
-            )
-            .clone()
-            .with_context(|| format!("no image for {params:?} in font {font:?}"))?;
+        let image = self.glyph_image(params)?;
         Ok(Bounds {
             origin: point(image.placement.left.into(), (-image.placement.top).into()),
             size: size(image.placement.width.into(), image.placement.height.into()),
@@ -309,13 +320,28 @@ impl CosmicTextSystemState {
             anyhow::bail!("glyph bounds are empty");
         } else {
             let bitmap_size = glyph_bounds.size;
-            let font = &self.loaded_fonts[params.font_id.0].font;
-            let subpixel_shift = point(
-                params.subpixel_variant.x as f32 / SUBPIXEL_VARIANTS_X as f32 / params.scale_factor,
-                params.subpixel_variant.y as f32 / SUBPIXEL_VARIANTS_Y as f32 / params.scale_factor,
-            );
-            let mut image = self
-                .swash_cache
+            let mut image = self.glyph_image(params)?;
+
+            if params.is_emoji {
+                // Convert from RGBA to BGRA.
+                for pixel in image.data.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+            }
+
+            Ok((bitmap_size, image.data))
+        }
+    }
+
+    fn glyph_image(&mut self, params: &RenderGlyphParams) -> Result<SwashImage> {
+        let loaded_font = &self.loaded_fonts[params.font_id.0];
+        let font = &loaded_font.font;
+        let subpixel_shift = point(
+            params.subpixel_variant.x as f32 / SUBPIXEL_VARIANTS_X as f32 / params.scale_factor,
+            params.subpixel_variant.y as f32 / SUBPIXEL_VARIANTS_Y as f32 / params.scale_factor,
+        );
+        let image = if loaded_font.coords.is_empty() {
+            self.swash_cache
                 .get_image(
                     &mut self.font_system,
                     CacheKey::new(
@@ -328,17 +354,25 @@ impl CosmicTextSystemState {
                     .0,
                 )
                 .clone()
-                .with_context(|| format!("no image for {params:?} in font {font:?}"))?;
-
-            if params.is_emoji {
-                // Convert from RGBA to BGRA.
-                for pixel in image.data.chunks_exact_mut(4) {
-                    pixel.swap(0, 2);
-                }
-            }
-
-            Ok((bitmap_size, image.data))
-        }
+        } else {
+            // Render the same way the swash cache does, at the font's axis values.
+            let mut scaler = self
+                .scale_context
+                .builder(font.as_swash())
+                .size((params.font_size * params.scale_factor).into())
+                .hint(true)
+                .normalized_coords(&loaded_font.coords)
+                .build();
+            Render::new(&[
+                Source::ColorOutline(0),
+                Source::ColorBitmap(StrikeWith::BestFit),
+                Source::Outline,
+            ])
+            .format(Format::Alpha)
+            .offset(Vector::new(subpixel_shift.x, subpixel_shift.y.trunc()))
+            .render(&mut scaler, params.glyph_id.0 as u16)
+        };
+        image.with_context(|| format!("no image for {params:?} in font {font:?}"))
     }
 
     /// This is used when cosmic_text has chosen a fallback font instead of using the requested
@@ -353,7 +387,7 @@ impl CosmicTextSystemState {
         if let Some(ix) = self
             .loaded_fonts
             .iter()
-            .position(|loaded_font| loaded_font.font.id() == id)
+            .position(|loaded_font| loaded_font.font.id() == id && loaded_font.coords.is_empty())
         {
             FontId(ix)
         } else {
@@ -364,6 +398,7 @@ impl CosmicTextSystemState {
             self.loaded_fonts.push(LoadedFont {
                 font,
                 features: CosmicFontFeatures::new(),
+                coords: Vec::new(),
                 is_known_emoji_font: check_is_known_emoji_font(&face.post_script_name),
             });
 
@@ -412,6 +447,9 @@ impl CosmicTextSystemState {
         let layout = layout_lines.first().unwrap();
 
         let mut runs: Vec<ShapedRun> = Vec::new();
+        // cosmic-text shapes variable fonts at their default axis values, so glyphs after one from
+        // a font with other values are shifted by how much its advance changes at those values.
+        let mut variation_offset = 0.;
         for glyph in &layout.glyphs {
             let mut font_id = FontId(glyph.metadata);
             let mut loaded_font = self.loaded_font(font_id);
@@ -426,9 +464,22 @@ impl CosmicTextSystemState {
                 continue;
             }
 
+            let position = point((glyph.x + variation_offset).into(), glyph.y.into());
+            if !loaded_font.coords.is_empty() {
+                let swash_font = loaded_font.font.as_swash();
+                let units_per_em = swash_font.metrics(&[]).units_per_em as f32;
+                let advance = |coords: &[NormalizedCoord]| {
+                    swash_font
+                        .glyph_metrics(coords)
+                        .advance_width(glyph.glyph_id)
+                };
+                variation_offset +=
+                    (advance(&loaded_font.coords) - advance(&[])) / units_per_em * font_size.0;
+            }
+
             let shaped_glyph = ShapedGlyph {
                 id: GlyphId(glyph.glyph_id as u32),
-                position: point(glyph.x.into(), glyph.y.into()),
+                position,
                 index: glyph.start,
                 is_emoji,
             };
@@ -448,11 +499,12 @@ impl CosmicTextSystemState {
 
         LineLayout {
             font_size,
-            width: layout.w.into(),
+            width: (layout.w + variation_offset).into(),
             ascent: layout.max_ascent.into(),
             descent: layout.max_descent.into(),
             runs,
             len: text.len(),
+            ..Default::default()
         }
     }
 }
@@ -575,6 +627,30 @@ fn face_info_into_properties(
     }
 }
 
+/// Resolves the requested axis values to the font's normalized coordinates, leaving out axes the
+/// font doesn't have.
+fn normalized_coords(font: &CosmicTextFont, variations: &FontVariations) -> Vec<NormalizedCoord> {
+    if variations.is_empty() {
+        return Vec::new();
+    }
+    let coords = font
+        .as_swash()
+        .variations()
+        .map(|axis| {
+            let tag = axis.tag().to_be_bytes();
+            std::str::from_utf8(&tag)
+                .ok()
+                .and_then(|tag| variations.value_for(tag))
+                .map_or(0, |value| axis.normalize(value))
+        })
+        .collect::<Vec<_>>();
+    if coords.iter().all(|coord| *coord == 0) {
+        Vec::new()
+    } else {
+        coords
+    }
+}
+
 fn check_is_known_emoji_font(postscript_name: &str) -> bool {
     // TODO: Include other common emoji fonts
     postscript_name == "NotoColorEmoji"
@@ -1,12 +1,15 @@
 use std::{
     cell::{RefCell, RefMut},
+    fs::File,
     hash::Hash,
-    os::fd::{AsRawFd, BorrowedFd},
+    io::Write as _,
+    os::fd::{AsFd as _, AsRawFd, BorrowedFd, FromRawFd as _},
     path::PathBuf,
     rc::{Rc, Weak},
     time::{Duration, Instant},
 };
 
+use anyhow::Context as _;
 use ashpd::WindowIdentifier;
 use calloop::{
     EventLoop, LoopHandle,
@@ -42,6 +45,9 @@ use wayland_protocols::wp::cursor_shape::v1::client::{
 use wayland_protocols::wp::fractional_scale::v1::client::{
     wp_fractional_scale_manager_v1, wp_fractional_scale_v1,
 };
+use wayland_protocols::wp::pointer_gestures::zv1::client::{
+    zwp_pointer_gesture_pinch_v1, zwp_pointer_gestures_v1,
+};
 use wayland_protocols::wp::primary_selection::zv1::client::zwp_primary_selection_offer_v1::{
     self, ZwpPrimarySelectionOfferV1,
 };
@@ -72,12 +78,13 @@ use super::{
 };
 
 use crate::{
-    AnyWindowHandle, Bounds, Capslock, CursorStyle, DOUBLE_CLICK_INTERVAL, DevicePixels, DisplayId,
+    AnyWindowHandle, Bounds, Capslock, CursorStyle, DevicePixels, DisplayId, DragImage,
     FileDropEvent, ForegroundExecutor, KeyDownEvent, KeyUpEvent, Keystroke, LinuxCommon,
     LinuxKeyboardLayout, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent,
-    MouseExitEvent, MouseMoveEvent, MouseUpEvent, NavigationDirection, Pixels, PlatformDisplay,
-    PlatformInput, PlatformKeyboardLayout, Point, SCROLL_LINES, ScrollDelta, ScrollWheelEvent,
-    Size, TouchPhase, WindowParams, point, px, size,
+    MouseExitEvent, MouseMoveEvent, MouseUpEvent, NavigationDirection, PinchEvent, Pixels,
+    PlatformDisplay, PlatformInput, PlatformKeyboardLayout, Point, PointerKind, RotateGestureEvent,
+    SCROLL_LINES, ScrollDelta, ScrollWheelEvent, Size, TouchPhase, WindowParams, point, px,
+    radians, size,
 };
 use crate::{
     LinuxDispatcher, RunnableVariant, TaskTiming,
@@ -109,6 +116,7 @@ pub struct Globals {
     pub activation: Option<xdg_activation_v1::XdgActivationV1>,
     pub compositor: wl_compositor::WlCompositor,
     pub cursor_shape_manager: Option<wp_cursor_shape_manager_v1::WpCursorShapeManagerV1>,
+    pub pointer_gestures: Option<zwp_pointer_gestures_v1::ZwpPointerGesturesV1>,
     pub data_device_manager: Option<wl_data_device_manager::WlDataDeviceManager>,
     pub primary_selection_manager:
         Option<zwp_primary_selection_device_manager_v1::ZwpPrimarySelectionDeviceManagerV1>,
@@ -143,6 +151,7 @@ impl Globals {
                 )
                 .unwrap(),
             cursor_shape_manager: globals.bind(&qh, 1..=1, ()).ok(),
+            pointer_gestures: globals.bind(&qh, 1..=1, ()).ok(),
             data_device_manager: globals
                 .bind(
                     &qh,
@@ -204,6 +213,9 @@ pub(crate) struct WaylandClientState {
     wl_pointer: Option<wl_pointer::WlPointer>,
     wl_keyboard: Option<wl_keyboard::WlKeyboard>,
     cursor_shape_device: Option<wp_cursor_shape_device_v1::WpCursorShapeDeviceV1>,
+    pinch_gesture: Option<zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1>,
+    // The scale reported by the last event of the current pinch, relative to its start
+    pinch_scale: f64,
     data_device: Option<wl_data_device::WlDataDevice>,
     primary_selection: Option<zwp_primary_selection_device_v1::ZwpPrimarySelectionDeviceV1>,
     text_input: Option<zwp_text_input_v3::ZwpTextInputV3>,
@@ -219,6 +231,7 @@ pub(crate) struct WaylandClientState {
     keymap_state: Option<xkb::State>,
     compose_state: Option<xkb::compose::State>,
     drag: DragState,
+    drag_out: Option<DragOutState>,
     click: ClickState,
     repeat: KeyRepeat,
     pub modifiers: Modifiers,
@@ -251,6 +264,105 @@ pub struct DragState {
     position: Point<Pixels>,
 }
 
+/// Files being dragged out of one of our windows.
+pub struct DragOutState {
+    data_source: wl_data_source::WlDataSource,
+    uri_list: String,
+    icon: Option<DragOutIcon>,
+}
+
+/// The surface showing the dragged element under the pointer.
+pub struct DragOutIcon {
+    surface: wl_surface::WlSurface,
+    viewport: Option<wp_viewport::WpViewport>,
+    buffer: wl_buffer::WlBuffer,
+}
+
+impl DragOutIcon {
+    fn new(globals: &Globals, image: &DragImage, scale: f32) -> anyhow::Result<Self> {
+        let width = image.image.width() as i32;
+        let height = image.image.height() as i32;
+        let stride = width * 4;
+        // wl_shm's ARGB8888 is premultiplied BGRA in memory.
+        let mut pixels = Vec::with_capacity((stride * height) as usize);
+        for pixel in image.image.pixels() {
+            let [r, g, b, a] = pixel.0;
+            let premultiply = |channel: u8| (channel as u16 * a as u16 / 255) as u8;
+            pixels.extend_from_slice(&[premultiply(b), premultiply(g), premultiply(r), a]);
+        }
+        let fd = unsafe { libc::memfd_create(c"gpui-drag-icon".as_ptr(), libc::MFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error()).context("creating a drag icon buffer");
+        }
+        let mut file = unsafe { File::from_raw_fd(fd) };
+        file.write_all(&pixels)
+            .context("writing the drag icon buffer")?;
+
+        let pool = globals
+            .shm
+            .create_pool(file.as_fd(), pixels.len() as i32, &globals.qh, ());
+        let buffer = pool.create_buffer(
+            0,
+            width,
+            height,
+            stride,
+            wl_shm::Format::Argb8888,
+            &globals.qh,
+            (),
+        );
+        pool.destroy();
+
+        let surface = globals.compositor.create_surface(&globals.qh, ());
+        // The buffer is at the window's scale factor, so show it at the element's logical size.
+        let viewport = globals.viewporter.as_ref().map(|viewporter| {
+            let viewport = viewporter.get_viewport(&surface, &globals.qh, ());
+            viewport.set_destination(
+                (width as f32 / scale).round() as i32,
+                (height as f32 / scale).round() as i32,
+            );
+            viewport
+        });
+        if viewport.is_none() {
+            surface.set_buffer_scale(scale.round().max(1.) as i32);
+        }
+
+        // The icon's top left corner starts at the pointer, so move it up and to the left by
+        // where the pointer was within the element.
+        let x = -(image.cursor_offset.x.0 as f32 / scale).round() as i32;
+        let y = -(image.cursor_offset.y.0 as f32 / scale).round() as i32;
+        if surface.version() >= wl_surface::REQ_OFFSET_SINCE {
+            surface.attach(Some(&buffer), 0, 0);
+            surface.offset(x, y);
+        } else {
+            surface.attach(Some(&buffer), x, y);
+        }
+        surface.damage_buffer(0, 0, width, height);
+
+        Ok(Self {
+            surface,
+            viewport,
+            buffer,
+        })
+    }
+
+    fn destroy(self) {
+        if let Some(viewport) = self.viewport {
+            viewport.destroy();
+        }
+        self.surface.destroy();
+        self.buffer.destroy();
+    }
+}
+
+impl DragOutState {
+    fn destroy(self) {
+        self.data_source.destroy();
+        if let Some(icon) = self.icon {
+            icon.destroy();
+        }
+    }
+}
+
 pub struct ClickState {
     last_mouse_button: Option<MouseButton>,
     last_click: Instant,
@@ -295,6 +407,59 @@ impl WaylandClientStatePtr {
             Some(PendingActivation::Window(window));
     }
 
+    pub fn start_drag_out(
+        &self,
+        surface: &wl_surface::WlSurface,
+        paths: &[PathBuf],
+        image: Option<DragImage>,
+        scale: f32,
+    ) -> bool {
+        let client = self.get_client();
+        let mut state = client.borrow_mut();
+        let (Some(data_device_manager), Some(data_device)) = (
+            state.globals.data_device_manager.clone(),
+            state.data_device.clone(),
+        ) else {
+            return false;
+        };
+        let uri_list = paths
+            .iter()
+            .filter_map(|path| Url::from_file_path(path).ok())
+            .map(|url| format!("{url}\r\n"))
+            .collect::<String>();
+        if uri_list.is_empty() {
+            return false;
+        }
+
+        let serial = state.serial_tracker.get(SerialKind::MousePress);
+        let data_source = data_device_manager.create_data_source(&state.globals.qh, ());
+        data_source.offer(FILE_LIST_MIME_TYPE.to_string());
+        data_source.set_actions(DndAction::Copy);
+        let icon = image.and_then(|image| {
+            DragOutIcon::new(&state.globals, &image, scale)
+                .context("creating the drag icon")
+                .log_err()
+        });
+        data_device.start_drag(
+            Some(&data_source),
+            surface,
+            icon.as_ref().map(|icon| &icon.surface),
+            serial,
+        );
+        // The icon's contents are applied once it has the drag icon role.
+        if let Some(icon) = &icon {
+            icon.surface.commit();
+        }
+        if let Some(previous) = state.drag_out.replace(DragOutState {
+            data_source,
+            uri_list,
+            icon,
+        }) {
+            previous.destroy();
+        }
+        true
+    }
+
     pub fn enable_ime(&self) {
         let client = self.get_client();
         let mut state = client.borrow_mut();
@@ -579,6 +744,11 @@ impl WaylandClient {
                             client.cursor.set_size(size);
                         }
                     }
+                    XDPEvent::DoubleClickInterval(interval) => {
+                        if let Some(client) = client.0.upgrade() {
+                            client.borrow_mut().common.double_click_interval = interval;
+                        }
+                    }
                 }
             })
             .unwrap();
@@ -591,6 +761,8 @@ impl WaylandClient {
             wl_pointer: None,
             wl_keyboard: None,
             cursor_shape_device: None,
+            pinch_gesture: None,
+            pinch_scale: 1.0,
             data_device,
             primary_selection,
             text_input: None,
@@ -609,6 +781,7 @@ impl WaylandClient {
                 window: None,
                 position: Point::default(),
             },
+            drag_out: None,
             click: ClickState {
                 last_click: Instant::now(),
                 last_mouse_button: None,
@@ -973,6 +1146,7 @@ delegate_noop!(WaylandClientStatePtr: ignore xdg_activation_v1::XdgActivationV1)
 delegate_noop!(WaylandClientStatePtr: ignore wl_compositor::WlCompositor);
 delegate_noop!(WaylandClientStatePtr: ignore wp_cursor_shape_device_v1::WpCursorShapeDeviceV1);
 delegate_noop!(WaylandClientStatePtr: ignore wp_cursor_shape_manager_v1::WpCursorShapeManagerV1);
+delegate_noop!(WaylandClientStatePtr: ignore zwp_pointer_gestures_v1::ZwpPointerGesturesV1);
 delegate_noop!(WaylandClientStatePtr: ignore wl_data_device_manager::WlDataDeviceManager);
 delegate_noop!(WaylandClientStatePtr: ignore zwp_primary_selection_device_manager_v1::ZwpPrimarySelectionDeviceManagerV1);
 delegate_noop!(WaylandClientStatePtr: ignore wl_shm::WlShm);
@@ -1236,6 +1410,15 @@ impl Dispatch<wl_seat::WlSeat, ()> for WaylandClientStatePtr {
                     .as_ref()
                     .map(|cursor_shape_manager| cursor_shape_manager.get_pointer(&pointer, qh, ()));
 
+                if let Some(pinch_gesture) = state.pinch_gesture.take() {
+                    pinch_gesture.destroy();
+                }
+                state.pinch_gesture = state
+                    .globals
+                    .pointer_gestures
+                    .as_ref()
+                    .map(|pointer_gestures| pointer_gestures.get_pinch_gesture(&pointer, qh, ()));
+
                 if let Some(wl_pointer) = &state.wl_pointer {
                     wl_pointer.release();
                 }
@@ -1700,7 +1883,7 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                         }
                         let click_elapsed = state.click.last_click.elapsed();
 
-                        if click_elapsed < DOUBLE_CLICK_INTERVAL
+                        if click_elapsed < state.common.double_click_interval
                             && state
                                 .click
                                 .last_mouse_button
@@ -1728,6 +1911,7 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                                 modifiers: state.modifiers,
                                 click_count: state.click.current_count,
                                 first_mouse: state.enter_token.take().is_some(),
+                                pointer_kind: PointerKind::Mouse,
                             });
                             drop(state);
                             window.handle_input(input);
@@ -1857,6 +2041,7 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                                 delta: ScrollDelta::Pixels(continuous),
                                 modifiers: state.modifiers,
                                 touch_phase: TouchPhase::Moved,
+                                momentum_phase: None,
                             });
                             drop(state);
                             window.handle_input(input);
@@ -1869,6 +2054,7 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                             delta: ScrollDelta::Lines(discrete),
                             modifiers: state.modifiers,
                             touch_phase: TouchPhase::Moved,
+                            momentum_phase: None,
                         });
                         drop(state);
                         window.handle_input(input);
@@ -1880,6 +2066,70 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
     }
 }
 
+impl Dispatch<zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1, ()>
+    for WaylandClientStatePtr
+{
+    fn event(
+        this: &mut Self,
+        _: &zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1,
+        event: zwp_pointer_gesture_pinch_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let client = this.get_client();
+        let mut state = client.borrow_mut();
+        let Some(window) = state.mouse_focused_window.clone() else {
+            return;
+        };
+        let position = state.mouse_location.unwrap_or_default();
+        let modifiers = state.modifiers;
+
+        // The compositor reports both transforms together, so the pinch and rotate events share
+        // a phase. The rotation is already clockwise, in degrees.
+        let (phase, scale_delta, rotation_delta) = match event {
+            zwp_pointer_gesture_pinch_v1::Event::Begin { .. } => {
+                state.pinch_scale = 1.0;
+                (TouchPhase::Started, 0.0, 0.0)
+            }
+            zwp_pointer_gesture_pinch_v1::Event::Update {
+                scale, rotation, ..
+            } => {
+                let scale_delta = if state.pinch_scale > 0.0 {
+                    scale / state.pinch_scale - 1.0
+                } else {
+                    0.0
+                };
+                state.pinch_scale = scale;
+                (TouchPhase::Moved, scale_delta, rotation)
+            }
+            zwp_pointer_gesture_pinch_v1::Event::End { .. } => {
+                state.pinch_scale = 1.0;
+                (TouchPhase::Ended, 0.0, 0.0)
+            }
+            _ => return,
+        };
+        drop(state);
+
+        if phase != TouchPhase::Moved || scale_delta != 0.0 {
+            window.handle_input(PlatformInput::Pinch(PinchEvent {
+                position,
+                scale_delta: scale_delta as f32,
+                modifiers,
+                phase,
+            }));
+        }
+        if phase != TouchPhase::Moved || rotation_delta != 0.0 {
+            window.handle_input(PlatformInput::RotateGesture(RotateGestureEvent {
+                position,
+                rotation_delta: radians((rotation_delta as f32).to_radians()),
+                modifiers,
+                phase,
+            }));
+        }
+    }
+}
+
 impl Dispatch<wp_fractional_scale_v1::WpFractionalScaleV1, ObjectId> for WaylandClientStatePtr {
     fn event(
         this: &mut Self,
@@ -2127,10 +2377,26 @@ impl Dispatch<wl_data_source::WlDataSource, ()> for WaylandClientStatePtr {
 
         match event {
             wl_data_source::Event::Send { mime_type, fd } => {
-                state.clipboard.send(mime_type, fd);
+                let drag_out_uri_list = state
+                    .drag_out
+                    .as_ref()
+                    .filter(|drag_out| &drag_out.data_source == data_source)
+                    .map(|drag_out| drag_out.uri_list.clone());
+                if let Some(uri_list) = drag_out_uri_list {
+                    state.clipboard.send_bytes(fd, uri_list.into_bytes());
+                } else {
+                    state.clipboard.send(mime_type, fd);
+                }
             }
-            wl_data_source::Event::Cancelled => {
-                data_source.destroy();
+            wl_data_source::Event::Cancelled | wl_data_source::Event::DndFinished => {
+                if let Some(drag_out) = state
+                    .drag_out
+                    .take_if(|drag_out| &drag_out.data_source == data_source)
+                {
+                    drag_out.destroy();
+                } else {
+                    data_source.destroy();
+                }
             }
             _ => {}
         }
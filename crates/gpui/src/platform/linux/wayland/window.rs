@@ -1,6 +1,7 @@
 use std::{
     cell::{Ref, RefCell, RefMut},
     ffi::c_void,
+    path::PathBuf,
     ptr::NonNull,
     rc::Rc,
     sync::Arc,
@@ -9,8 +10,10 @@ use std::{
 use blade_graphics as gpu;
 use collections::HashMap;
 use futures::channel::oneshot::Receiver;
+use image::RgbaImage;
 
 use raw_window_handle as rwh;
+use util::ResultExt;
 use wayland_backend::client::ObjectId;
 use wayland_client::WEnum;
 use wayland_client::{Proxy, protocol::wl_surface};
@@ -26,11 +29,11 @@ use wayland_protocols_plasma::blur::client::org_kde_kwin_blur;
 use wayland_protocols_wlr::layer_shell::v1::client::zwlr_layer_surface_v1;
 
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, Globals, GpuSpecs, Modifiers, Output, Pixels,
-    PlatformDisplay, PlatformInput, Point, PromptButton, PromptLevel, RequestFrameOptions,
-    ResizeEdge, Size, Tiling, WaylandClientStatePtr, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControlArea, WindowControls, WindowDecorations, WindowParams,
-    layer_shell::LayerShellNotSupportedError, px, size,
+    AnyWindowHandle, Bounds, Decorations, DevicePixels, DragImage, Globals, GpuSpecs, Modifiers,
+    Output, Pixels, PlatformDisplay, PlatformInput, Point, PromptButton, PromptLevel,
+    RequestFrameOptions, ResizeEdge, Size, Tiling, WaylandClientStatePtr, WindowAppearance,
+    WindowBackgroundAppearance, WindowBounds, WindowControlArea, WindowControls, WindowDecorations,
+    WindowParams, layer_shell::LayerShellNotSupportedError, px, size,
 };
 use crate::{
     Capslock,
@@ -1226,6 +1229,20 @@ impl PlatformWindow for WaylandWindow {
         state.renderer.sprite_atlas().clone()
     }
 
+    fn render_to_image(&self, scene: &Scene, bounds: Bounds<DevicePixels>) -> Option<RgbaImage> {
+        self.borrow_mut()
+            .renderer
+            .render_to_image(scene, bounds)
+            .log_err()
+    }
+
+    fn start_drag_out(&self, paths: &[PathBuf], image: Option<DragImage>) -> bool {
+        let state = self.borrow();
+        state
+            .client
+            .start_drag_out(&state.surface, paths, image, state.scale)
+    }
+
     fn show_window_menu(&self, position: Point<Pixels>) {
         let state = self.borrow();
         let serial = state.client.get_serial(SerialKind::MousePress);
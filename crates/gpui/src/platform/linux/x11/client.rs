@@ -41,8 +41,8 @@ use xkbc::x11::ffi::{XKB_X11_MIN_MAJOR_XKB_VERSION, XKB_X11_MIN_MINOR_XKB_VERSIO
 use xkbcommon::xkb::{self as xkbc, STATE_LAYOUT_EFFECTIVE};
 
 use super::{
-    ButtonOrScroll, ScrollDirection, X11Display, X11WindowStatePtr, XcbAtoms, XimCallbackEvent,
-    XimHandler, button_or_scroll_from_event_detail, check_reply,
+    ButtonOrScroll, ScrollDirection, X11Display, X11WindowStatePtr, XcbAtoms, XdndIcon,
+    XimCallbackEvent, XimHandler, button_or_scroll_from_event_detail, check_reply,
     clipboard::{self, Clipboard},
     get_reply, get_valuator_axis_index, handle_connection_error, modifiers_from_state,
     pressed_button_from_mask,
@@ -54,7 +54,7 @@ use crate::platform::{
     linux::{
         DEFAULT_CURSOR_ICON_NAME, LinuxClient, get_xkb_compose_state, is_within_click_distance,
         log_cursor_icon_warning, open_uri_internal,
-        platform::{DOUBLE_CLICK_INTERVAL, SCROLL_LINES},
+        platform::SCROLL_LINES,
         reveal_path_internal,
         xdg_desktop_portal::{Event as XDPEvent, XDPEventSource},
     },
@@ -148,6 +148,29 @@ pub struct Xdnd {
     position: Point<Pixels>,
 }
 
+/// The XDND protocol version we speak, both as a drop target and as a drag source.
+const XDND_VERSION: u32 = 5;
+
+/// Files being dragged out of one of our windows, with it as the XDND source.
+pub struct XdndSource {
+    window: xproto::Window,
+    uri_list: String,
+    icon: Option<XdndIcon>,
+    target: Option<XdndSourceTarget>,
+    /// Set once a position has been sent, until the target replies with its status. Newer
+    /// positions wait for that reply, as the protocol asks.
+    awaiting_status: bool,
+    pending_position: Option<(i16, i16, xproto::Timestamp)>,
+    /// Set once the pointer is released over a target that accepted the drop, until it finishes.
+    dropped: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct XdndSourceTarget {
+    window: xproto::Window,
+    accepted: bool,
+}
+
 #[derive(Debug)]
 struct PointerDeviceState {
     horizontal: ScrollAxisState,
@@ -216,6 +239,7 @@ pub struct X11ClientState {
     pub(crate) clipboard: Clipboard,
     pub(crate) clipboard_item: Option<ClipboardItem>,
     pub(crate) xdnd_state: Xdnd,
+    pub(crate) xdnd_source: Option<XdndSource>,
 }
 
 #[derive(Clone)]
@@ -248,6 +272,70 @@ impl X11ClientStatePtr {
         state.cursor_styles.remove(&x_window);
     }
 
+    pub fn start_drag_out(
+        &self,
+        x_window: xproto::Window,
+        paths: &[PathBuf],
+        icon: Option<XdndIcon>,
+    ) -> bool {
+        let Some(client) = self.get_client() else {
+            return false;
+        };
+        let mut state = client.0.borrow_mut();
+        let uri_list = paths
+            .iter()
+            .filter_map(|path| Url::from_file_path(path).ok())
+            .map(|url| format!("{url}\r\n"))
+            .collect::<String>();
+        let owns_selection = !uri_list.is_empty()
+            && check_reply(
+                || "Failed to own the XdndSelection",
+                state.xcb_connection.set_selection_owner(
+                    x_window,
+                    state.atoms.XdndSelection,
+                    x11rb::CURRENT_TIME,
+                ),
+            )
+            .log_err()
+            .is_some();
+        if !owns_selection {
+            if let Some(icon) = icon {
+                icon.destroy(&state.xcb_connection);
+            }
+            return false;
+        }
+
+        // The button press's implicit grab keeps pointer events coming to this window until the
+        // button is released, wherever the pointer goes.
+        let root = state.xcb_connection.setup().roots[state.x_root_index].root;
+        let pointer = get_reply(
+            || "Failed to query pointer position",
+            state.xcb_connection.query_pointer(root),
+        )
+        .log_err();
+        if let Some(previous) = state.xdnd_source.replace(XdndSource {
+            window: x_window,
+            uri_list,
+            icon,
+            target: None,
+            awaiting_status: false,
+            pending_position: None,
+            dropped: false,
+        }) {
+            xdnd_source_finish(&state.xcb_connection, &state.atoms, previous);
+        }
+        if let Some(pointer) = pointer {
+            xdnd_source_motion(
+                &mut state,
+                pointer.root_x,
+                pointer.root_y,
+                x11rb::CURRENT_TIME,
+            );
+        }
+        xcb_flush(&state.xcb_connection);
+        true
+    }
+
     pub fn update_ime_position(&self, bounds: Bounds<Pixels>) {
         let Some(client) = self.get_client() else {
             return;
@@ -490,6 +578,9 @@ impl X11Client {
                     XDPEvent::CursorTheme(_) | XDPEvent::CursorSize(_) => {
                         // noop, X11 manages this for us.
                     }
+                    XDPEvent::DoubleClickInterval(interval) => {
+                        client.with_common(|common| common.double_click_interval = interval);
+                    }
                 }
             })
             .map_err(|err| anyhow!("Failed to initialize XDP event source: {err:?}"))?;
@@ -540,6 +631,7 @@ impl X11Client {
             clipboard,
             clipboard_item: None,
             xdnd_state: Xdnd::default(),
+            xdnd_source: None,
         }))))
     }
 
@@ -803,7 +895,29 @@ impl X11Client {
                         })
                 }
 
-                if event.type_ == state.atoms.XdndEnter {
+                if event.type_ == state.atoms.XdndStatus {
+                    let accepted = arg1 & 0x1 == 0x1;
+                    let state = &mut *state;
+                    if let Some(source) = state.xdnd_source.as_mut()
+                        && let Some(target) = source.target.as_mut()
+                        && target.window == atom
+                    {
+                        target.accepted = accepted;
+                        source.awaiting_status = false;
+                        if let Some((x, y, time)) = source.pending_position.take() {
+                            xdnd_source_motion(state, x, y, time);
+                        }
+                    }
+                } else if event.type_ == state.atoms.XdndFinished {
+                    if state
+                        .xdnd_source
+                        .as_ref()
+                        .is_some_and(|source| source.dropped && source.window == event.window)
+                        && let Some(source) = state.xdnd_source.take()
+                    {
+                        xdnd_source_finish(&state.xcb_connection, &state.atoms, source);
+                    }
+                } else if event.type_ == state.atoms.XdndEnter {
                     state.xdnd_state.other_window = atom;
                     if (arg1 & 0x1) == 0x1 {
                         state.xdnd_state.drag_type = xdnd_get_supported_atom(
@@ -872,6 +986,58 @@ impl X11Client {
                     self.0.borrow_mut().xdnd_state = Xdnd::default();
                 }
             }
+            Event::SelectionRequest(event) => {
+                let state = self.0.borrow();
+                let source = state
+                    .xdnd_source
+                    .as_ref()
+                    .filter(|source| source.window == event.owner)?;
+                if event.selection != state.atoms.XdndSelection {
+                    return Some(());
+                }
+                // Older clients pass no property, and expect the data in the target's.
+                let property = if event.property == x11rb::NONE {
+                    event.target
+                } else {
+                    event.property
+                };
+                let property = if event.target == state.atoms.TextUriList {
+                    check_reply(
+                        || "Failed to send the dragged files",
+                        state.xcb_connection.change_property8(
+                            xproto::PropMode::REPLACE,
+                            event.requestor,
+                            property,
+                            state.atoms.TextUriList,
+                            source.uri_list.as_bytes(),
+                        ),
+                    )
+                    .log_err()
+                    .map_or(x11rb::NONE, |_| property)
+                } else {
+                    x11rb::NONE
+                };
+                let notify = xproto::SelectionNotifyEvent {
+                    response_type: xproto::SELECTION_NOTIFY_EVENT,
+                    sequence: 0,
+                    time: event.time,
+                    requestor: event.requestor,
+                    selection: event.selection,
+                    target: event.target,
+                    property,
+                };
+                check_reply(
+                    || "Failed to notify the drop target",
+                    state.xcb_connection.send_event(
+                        false,
+                        event.requestor,
+                        EventMask::NO_EVENT,
+                        notify,
+                    ),
+                )
+                .log_err();
+                xcb_flush(&state.xcb_connection);
+            }
             Event::SelectionNotify(event) => {
                 let window = self.get_window(event.requestor)?;
                 let mut state = self.0.borrow_mut();
@@ -1128,7 +1294,7 @@ impl X11Client {
                 match button_or_scroll_from_event_detail(event.detail) {
                     Some(ButtonOrScroll::Button(button)) => {
                         let click_elapsed = state.last_click.elapsed();
-                        if click_elapsed < DOUBLE_CLICK_INTERVAL
+                        if click_elapsed < state.common.double_click_interval
                             && state
                                 .last_mouse_button
                                 .is_some_and(|prev_button| prev_button == button)
@@ -1151,6 +1317,7 @@ impl X11Client {
                             modifiers,
                             click_count: current_count,
                             first_mouse: false,
+                            pointer_kind: crate::PointerKind::Mouse,
                         }));
                     }
                     Some(ButtonOrScroll::Scroll(direction)) => {
@@ -1183,6 +1350,17 @@ impl X11Client {
                 let modifiers = modifiers_from_xinput_info(event.mods);
                 state.modifiers = modifiers;
 
+                // Releasing the button drops the files being dragged out, and the window
+                // already forgot the press when the drag started.
+                if state
+                    .xdnd_source
+                    .as_ref()
+                    .is_some_and(|source| !source.dropped)
+                {
+                    xdnd_source_drop(&mut state, event.time);
+                    return Some(());
+                }
+
                 let position = point(
                     px(event.event_x as f32 / u16::MAX as f32 / state.scale_factor),
                     px(event.event_y as f32 / u16::MAX as f32 / state.scale_factor),
@@ -1205,6 +1383,19 @@ impl X11Client {
             Event::XinputMotion(event) => {
                 let window = self.get_window(event.event)?;
                 let mut state = self.0.borrow_mut();
+                if state
+                    .xdnd_source
+                    .as_ref()
+                    .is_some_and(|source| !source.dropped)
+                {
+                    xdnd_source_motion(
+                        &mut state,
+                        (event.root_x >> 16) as i16,
+                        (event.root_y >> 16) as i16,
+                        event.time,
+                    );
+                    return Some(());
+                }
                 let pressed_button = pressed_button_from_mask(event.button_mask[0]);
                 let position = point(
                     px(event.event_x as f32 / u16::MAX as f32 / state.scale_factor),
@@ -2110,6 +2301,169 @@ fn xdnd_send_status(
     xcb_connection.flush().log_err();
 }
 
+fn xdnd_send_message(
+    xcb_connection: &XCBConnection,
+    target: xproto::Window,
+    message_type: xproto::Atom,
+    data: [u32; 5],
+) {
+    let message = ClientMessageEvent {
+        format: 32,
+        window: target,
+        type_: message_type,
+        data: ClientMessageData::from(data),
+        sequence: 0,
+        response_type: xproto::CLIENT_MESSAGE_EVENT,
+    };
+    check_reply(
+        || "Failed to send XDnD message",
+        xcb_connection.send_event(false, target, EventMask::default(), message),
+    )
+    .log_err();
+}
+
+/// Finds the XDND aware window under the given point on the root window, along with the
+/// protocol version it speaks.
+fn xdnd_find_target(
+    xcb_connection: &XCBConnection,
+    atoms: &XcbAtoms,
+    root: xproto::Window,
+    x: i16,
+    y: i16,
+) -> Option<(xproto::Window, u32)> {
+    let mut window = root;
+    loop {
+        let child = get_reply(
+            || "Failed to find the window under the pointer",
+            xcb_connection.translate_coordinates(root, window, x, y),
+        )
+        .log_err()?
+        .child;
+        if child == x11rb::NONE {
+            return None;
+        }
+        window = child;
+        let aware = get_reply(
+            || "Failed to get XdndAware",
+            xcb_connection.get_property(false, window, atoms.XdndAware, AtomEnum::ANY, 0, 1),
+        )
+        .log_err()?;
+        if let Some(version) = aware.value32().and_then(|mut values| values.next()) {
+            return Some((window, version));
+        }
+    }
+}
+
+/// Moves the files being dragged out to the given point on the root window, entering and
+/// leaving drop targets as the pointer crosses them.
+fn xdnd_source_motion(state: &mut X11ClientState, x: i16, y: i16, time: xproto::Timestamp) {
+    let root = state.xcb_connection.setup().roots[state.x_root_index].root;
+    let found = xdnd_find_target(&state.xcb_connection, &state.atoms, root, x, y);
+    let xcb_connection = &state.xcb_connection;
+    let atoms = &state.atoms;
+    let Some(source) = state.xdnd_source.as_mut() else {
+        return;
+    };
+    if let Some(icon) = &source.icon {
+        icon.move_to(xcb_connection, x, y);
+    }
+
+    let found = found.filter(|(_, version)| *version >= 3);
+    if source.target.map(|target| target.window) != found.map(|(window, _)| window) {
+        if let Some(target) = source.target.take() {
+            xdnd_send_message(
+                xcb_connection,
+                target.window,
+                atoms.XdndLeave,
+                [source.window, 0, 0, 0, 0],
+            );
+        }
+        source.awaiting_status = false;
+        source.pending_position = None;
+        if let Some((window, version)) = found {
+            xdnd_send_message(
+                xcb_connection,
+                window,
+                atoms.XdndEnter,
+                [
+                    source.window,
+                    version.min(XDND_VERSION) << 24,
+                    atoms.TextUriList,
+                    0,
+                    0,
+                ],
+            );
+            source.target = Some(XdndSourceTarget {
+                window,
+                accepted: false,
+            });
+        }
+    }
+
+    if let Some(target) = source.target {
+        if source.awaiting_status {
+            source.pending_position = Some((x, y, time));
+        } else {
+            xdnd_send_message(
+                xcb_connection,
+                target.window,
+                atoms.XdndPosition,
+                [
+                    source.window,
+                    0,
+                    ((x as u16 as u32) << 16) | y as u16 as u32,
+                    time,
+                    atoms.XdndActionCopy,
+                ],
+            );
+            source.awaiting_status = true;
+        }
+    }
+    xcb_flush(xcb_connection);
+}
+
+/// Drops the files being dragged out on the target under the pointer, if it accepted them.
+fn xdnd_source_drop(state: &mut X11ClientState, time: xproto::Timestamp) {
+    let Some(mut source) = state.xdnd_source.take() else {
+        return;
+    };
+    if let Some(icon) = source.icon.take() {
+        icon.destroy(&state.xcb_connection);
+    }
+    match source.target {
+        Some(target) if target.accepted => {
+            xdnd_send_message(
+                &state.xcb_connection,
+                target.window,
+                state.atoms.XdndDrop,
+                [source.window, 0, time, 0, 0],
+            );
+            xcb_flush(&state.xcb_connection);
+            // Keep serving the selection until the target is done reading it.
+            source.dropped = true;
+            state.xdnd_source = Some(source);
+        }
+        _ => xdnd_source_finish(&state.xcb_connection, &state.atoms, source),
+    }
+}
+
+fn xdnd_source_finish(xcb_connection: &XCBConnection, atoms: &XcbAtoms, source: XdndSource) {
+    if let Some(target) = source.target
+        && !source.dropped
+    {
+        xdnd_send_message(
+            xcb_connection,
+            target.window,
+            atoms.XdndLeave,
+            [source.window, 0, 0, 0, 0],
+        );
+    }
+    if let Some(icon) = source.icon {
+        icon.destroy(xcb_connection);
+    }
+    xcb_flush(xcb_connection);
+}
+
 /// Recomputes `pointer_device_states` by querying all pointer devices.
 /// When a device is present in `scroll_values_to_preserve`, its value for `ScrollAxisState.scroll_value` is used.
 fn current_pointer_device_states(
@@ -2246,6 +2600,7 @@ fn make_scroll_wheel_event(
         delta: ScrollDelta::Lines(delta),
         modifiers,
         touch_phase: TouchPhase::default(),
+        momentum_phase: None,
     }
 }
 
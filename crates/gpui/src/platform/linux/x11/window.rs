@@ -3,14 +3,15 @@ use x11rb::connection::RequestConnection;
 
 use crate::platform::blade::{BladeContext, BladeRenderer, BladeSurfaceConfig};
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor, GpuSpecs, Modifiers,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, PromptButton, PromptLevel, RequestFrameOptions, ResizeEdge, ScaledPixels, Scene, Size,
-    Tiling, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea,
-    WindowDecorations, WindowKind, WindowParams, X11ClientStatePtr, px, size,
+    AnyWindowHandle, Bounds, Decorations, DevicePixels, DragImage, ForegroundExecutor, GpuSpecs,
+    Modifiers, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler,
+    PlatformWindow, Point, PromptButton, PromptLevel, RequestFrameOptions, ResizeEdge,
+    ScaledPixels, Scene, Size, Tiling, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowControlArea, WindowDecorations, WindowKind, WindowParams, X11ClientStatePtr, px, size,
 };
 
 use blade_graphics as gpu;
+use image::RgbaImage;
 use raw_window_handle as rwh;
 use util::{ResultExt, maybe};
 use x11rb::{
@@ -19,6 +20,7 @@ use x11rb::{
     errors::ConnectionError,
     properties::WmSizeHints,
     protocol::{
+        shape::{self, ConnectionExt as _},
         sync,
         xinput::{self, ConnectionExt as _},
         xproto::{self, ClientMessageEvent, ConnectionExt, TranslateCoordinatesReply},
@@ -28,8 +30,8 @@ use x11rb::{
 };
 
 use std::{
-    cell::RefCell, ffi::c_void, fmt::Display, num::NonZeroU32, ops::Div, ptr::NonNull, rc::Rc,
-    sync::Arc,
+    cell::RefCell, ffi::c_void, fmt::Display, num::NonZeroU32, ops::Div, path::PathBuf,
+    ptr::NonNull, rc::Rc, sync::Arc,
 };
 
 use super::{X11Display, XINPUT_ALL_DEVICE_GROUPS, XINPUT_ALL_DEVICES};
@@ -227,6 +229,163 @@ fn find_visuals(xcb: &XCBConnection, screen_index: usize) -> VisualSet {
     set
 }
 
+/// An override-redirect window showing the dragged element under the pointer while files are
+/// dragged out of one of our windows.
+pub(crate) struct XdndIcon {
+    window: xproto::Window,
+    pixmap: xproto::Pixmap,
+    colormap: Option<xproto::Colormap>,
+    cursor_offset: Point<DevicePixels>,
+}
+
+impl XdndIcon {
+    fn new(
+        xcb: &XCBConnection,
+        x_root_window: xproto::Window,
+        image: &DragImage,
+    ) -> anyhow::Result<Self> {
+        let screen_index = xcb
+            .setup()
+            .roots
+            .iter()
+            .position(|screen| screen.root == x_root_window)
+            .context("no screen for the root window")?;
+        let visual_set = find_visuals(xcb, screen_index);
+        // With a compositor, the transparent parts of the element show through.
+        let (visual, colormap) = match visual_set.transparent {
+            Some(visual) => {
+                let colormap = xcb.generate_id()?;
+                check_reply(
+                    || "X11 CreateColormap for the drag icon failed.",
+                    xcb.create_colormap(
+                        xproto::ColormapAlloc::NONE,
+                        colormap,
+                        x_root_window,
+                        visual.id,
+                    ),
+                )?;
+                (visual, Some(colormap))
+            }
+            None => (visual_set.inherit, None),
+        };
+        let width = image.image.width() as u16;
+        let height = image.image.height() as u16;
+
+        let pixmap = xcb.generate_id()?;
+        check_reply(
+            || "X11 CreatePixmap for the drag icon failed.",
+            xcb.create_pixmap(visual.depth, pixmap, x_root_window, width, height),
+        )?;
+        let gc = xcb.generate_id()?;
+        check_reply(
+            || "X11 CreateGC for the drag icon failed.",
+            xcb.create_gc(gc, pixmap, &xproto::CreateGCAux::new()),
+        )?;
+        // ZPixmap data for 24 and 32 bit visuals is premultiplied BGRA.
+        let mut pixels = Vec::with_capacity(image.image.as_raw().len());
+        for pixel in image.image.pixels() {
+            let [r, g, b, a] = pixel.0;
+            let premultiply = |channel: u8| (channel as u16 * a as u16 / 255) as u8;
+            pixels.extend_from_slice(&[premultiply(b), premultiply(g), premultiply(r), a]);
+        }
+        // Upload in strips of rows that fit in a request.
+        let stride = width as usize * 4;
+        let rows_per_request = (xcb.maximum_request_bytes().saturating_sub(64) / stride).max(1);
+        for (ix, rows) in pixels.chunks(stride * rows_per_request).enumerate() {
+            check_reply(
+                || "X11 PutImage for the drag icon failed.",
+                xcb.put_image(
+                    xproto::ImageFormat::Z_PIXMAP,
+                    pixmap,
+                    gc,
+                    width,
+                    (rows.len() / stride) as u16,
+                    0,
+                    (ix * rows_per_request) as i16,
+                    0,
+                    visual.depth,
+                    rows,
+                ),
+            )?;
+        }
+        xcb.free_gc(gc)?;
+
+        let window = xcb.generate_id()?;
+        let mut window_aux = xproto::CreateWindowAux::new()
+            .background_pixmap(pixmap)
+            .border_pixel(visual_set.black_pixel)
+            .override_redirect(1);
+        if let Some(colormap) = colormap {
+            window_aux = window_aux.colormap(colormap);
+        }
+        check_reply(
+            || "X11 CreateWindow for the drag icon failed.",
+            xcb.create_window(
+                visual.depth,
+                window,
+                x_root_window,
+                0,
+                0,
+                width,
+                height,
+                0,
+                xproto::WindowClass::INPUT_OUTPUT,
+                visual.id,
+                &window_aux,
+            ),
+        )?;
+        // Let the pointer through to the window under the icon, which is the drop target.
+        check_reply(
+            || "X11 ShapeRectangles for the drag icon failed.",
+            xcb.shape_rectangles(
+                shape::SO::SET,
+                shape::SK::INPUT,
+                xproto::ClipOrdering::UNSORTED,
+                window,
+                0,
+                0,
+                &[],
+            ),
+        )?;
+
+        Ok(Self {
+            window,
+            pixmap,
+            colormap,
+            cursor_offset: image.cursor_offset,
+        })
+    }
+
+    /// Moves the icon so the pointer is where it was within the dragged element.
+    pub(crate) fn move_to(&self, xcb: &XCBConnection, root_x: i16, root_y: i16) {
+        check_reply(
+            || "X11 ConfigureWindow for the drag icon failed.",
+            xcb.configure_window(
+                self.window,
+                &xproto::ConfigureWindowAux::new()
+                    .x(root_x as i32 - self.cursor_offset.x.0)
+                    .y(root_y as i32 - self.cursor_offset.y.0)
+                    .stack_mode(xproto::StackMode::ABOVE),
+            ),
+        )
+        .log_err();
+        check_reply(
+            || "X11 MapWindow for the drag icon failed.",
+            xcb.map_window(self.window),
+        )
+        .log_err();
+    }
+
+    pub(crate) fn destroy(self, xcb: &XCBConnection) {
+        xcb.destroy_window(self.window).log_err();
+        xcb.free_pixmap(self.pixmap).log_err();
+        if let Some(colormap) = self.colormap {
+            xcb.free_colormap(colormap).log_err();
+        }
+        xcb_flush(xcb);
+    }
+}
+
 struct RawWindow {
     connection: *mut c_void,
     screen_id: usize,
@@ -1480,6 +1639,21 @@ impl PlatformWindow for X11Window {
         inner.renderer.sprite_atlas().clone()
     }
 
+    fn render_to_image(&self, scene: &Scene, bounds: Bounds<DevicePixels>) -> Option<RgbaImage> {
+        let mut inner = self.0.state.borrow_mut();
+        inner.renderer.render_to_image(scene, bounds).log_err()
+    }
+
+    fn start_drag_out(&self, paths: &[PathBuf], image: Option<DragImage>) -> bool {
+        let state = self.0.state.borrow();
+        let icon = image.and_then(|image| {
+            XdndIcon::new(&self.0.xcb, state.x_root_window, &image)
+                .context("creating the drag icon")
+                .log_err()
+        });
+        state.client.start_drag_out(self.0.x_window, paths, icon)
+    }
+
     fn show_window_menu(&self, position: Point<Pixels>) {
         let state = self.0.state.borrow();
 
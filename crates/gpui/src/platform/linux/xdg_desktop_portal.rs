@@ -6,6 +6,7 @@ use ashpd::desktop::settings::{ColorScheme, Settings};
 use calloop::channel::Channel;
 use calloop::{EventSource, Poll, PostAction, Readiness, Token, TokenFactory};
 use smol::stream::StreamExt;
+use std::time::Duration;
 
 use crate::{BackgroundExecutor, WindowAppearance};
 
@@ -15,6 +16,7 @@ pub enum Event {
     CursorTheme(String),
     #[cfg_attr(feature = "x11", allow(dead_code))]
     CursorSize(u32),
+    DoubleClickInterval(Duration),
 }
 
 pub struct XDPEventSource {
@@ -51,6 +53,15 @@ impl XDPEventSource {
                     sender.send(Event::CursorSize(initial_size as u32))?;
                 }
 
+                if let Ok(initial_interval) = settings
+                    .read::<i32>("org.gnome.desktop.peripherals.mouse", "double-click")
+                    .await
+                {
+                    sender.send(Event::DoubleClickInterval(Duration::from_millis(
+                        initial_interval.max(0) as u64,
+                    )))?;
+                }
+
                 if let Ok(mut cursor_theme_changed) = settings
                     .receive_setting_changed_with_args(
                         "org.gnome.desktop.interface",
@@ -89,6 +100,27 @@ impl XDPEventSource {
                         .detach();
                 }
 
+                if let Ok(mut double_click_interval_changed) = settings
+                    .receive_setting_changed_with_args::<i32>(
+                        "org.gnome.desktop.peripherals.mouse",
+                        "double-click",
+                    )
+                    .await
+                {
+                    let sender = sender.clone();
+                    background
+                        .spawn(async move {
+                            while let Some(interval) = double_click_interval_changed.next().await {
+                                let interval = interval?;
+                                sender.send(Event::DoubleClickInterval(Duration::from_millis(
+                                    interval.max(0) as u64,
+                                )))?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach();
+                }
+
                 let mut appearance_changed = settings.receive_color_scheme_changed().await?;
                 while let Some(scheme) = appearance_changed.next().await {
                     sender.send(Event::WindowAppearance(WindowAppearance::from_native(
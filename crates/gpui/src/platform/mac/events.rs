@@ -1,12 +1,13 @@
 use crate::{
     Capslock, KeyDownEvent, KeyUpEvent, Keystroke, Modifiers, ModifiersChangedEvent, MouseButton,
-    MouseDownEvent, MouseExitEvent, MouseMoveEvent, MouseUpEvent, NavigationDirection, Pixels,
-    PlatformInput, ScrollDelta, ScrollWheelEvent, TouchPhase,
+    MouseDownEvent, MouseExitEvent, MouseMoveEvent, MouseUpEvent, NavigationDirection, PinchEvent,
+    Pixels, PlatformInput, PointerKind, RotateGestureEvent, ScrollDelta, ScrollWheelEvent,
+    TouchPhase,
     platform::mac::{
         LMGetKbdType, NSStringExt, TISCopyCurrentKeyboardLayoutInputSource,
         TISGetInputSourceProperty, UCKeyTranslate, kTISPropertyUnicodeKeyLayoutData,
     },
-    point, px,
+    point, px, radians,
 };
 use cocoa::{
     appkit::{NSEvent, NSEventModifierFlags, NSEventPhase, NSEventType},
@@ -159,6 +160,7 @@ impl PlatformInput {
                             modifiers: read_modifiers(native_event),
                             click_count: native_event.clickCount() as usize,
                             first_mouse: false,
+                            pointer_kind: PointerKind::Mouse,
                         })
                     })
                 }
@@ -209,6 +211,7 @@ impl PlatformInput {
                                 modifiers: read_modifiers(native_event),
                                 click_count: 1,
                                 first_mouse: false,
+                                pointer_kind: PointerKind::Mouse,
                             })
                         }),
                         _ => None,
@@ -241,9 +244,35 @@ impl PlatformInput {
                         ),
                         delta,
                         touch_phase: phase,
+                        momentum_phase: momentum_phase(native_event),
                         modifiers: read_modifiers(native_event),
                     })
                 }),
+                NSEventType::NSEventTypeMagnify => window_height.map(|window_height| {
+                    let magnification: f64 = msg_send![native_event, magnification];
+                    Self::Pinch(PinchEvent {
+                        position: point(
+                            px(native_event.locationInWindow().x as f32),
+                            window_height - px(native_event.locationInWindow().y as f32),
+                        ),
+                        scale_delta: magnification as f32,
+                        modifiers: read_modifiers(native_event),
+                        phase: gesture_phase(native_event),
+                    })
+                }),
+                NSEventType::NSEventTypeRotate => window_height.map(|window_height| {
+                    // AppKit reports degrees, counterclockwise.
+                    let rotation: f32 = msg_send![native_event, rotation];
+                    Self::RotateGesture(RotateGestureEvent {
+                        position: point(
+                            px(native_event.locationInWindow().x as f32),
+                            window_height - px(native_event.locationInWindow().y as f32),
+                        ),
+                        rotation_delta: radians(-rotation.to_radians()),
+                        modifiers: read_modifiers(native_event),
+                        phase: gesture_phase(native_event),
+                    })
+                }),
                 NSEventType::NSLeftMouseDragged
                 | NSEventType::NSRightMouseDragged
                 | NSEventType::NSOtherMouseDragged => {
@@ -295,6 +324,27 @@ impl PlatformInput {
     }
 }
 
+unsafe fn gesture_phase(native_event: id) -> TouchPhase {
+    match unsafe { native_event.phase() } {
+        NSEventPhase::NSEventPhaseBegan => TouchPhase::Started,
+        NSEventPhase::NSEventPhaseEnded | NSEventPhase::NSEventPhaseCancelled => TouchPhase::Ended,
+        _ => TouchPhase::Moved,
+    }
+}
+
+/// The phase of the momentum scrolling after a fling, which AppKit reports separately from the
+/// phase of the scroll that the fingers are making.
+unsafe fn momentum_phase(native_event: id) -> Option<TouchPhase> {
+    match unsafe { native_event.momentumPhase() } {
+        NSEventPhase::NSEventPhaseNone => None,
+        NSEventPhase::NSEventPhaseBegan => Some(TouchPhase::Started),
+        NSEventPhase::NSEventPhaseEnded | NSEventPhase::NSEventPhaseCancelled => {
+            Some(TouchPhase::Ended)
+        }
+        _ => Some(TouchPhase::Moved),
+    }
+}
+
 unsafe fn parse_keystroke(native_event: id) -> Keystroke {
     unsafe {
         use cocoa::appkit::*;
@@ -1,8 +1,8 @@
 use super::metal_atlas::MetalAtlas;
 use crate::{
-    AtlasTextureId, Background, Bounds, ContentMask, DevicePixels, MonochromeSprite, PaintSurface,
-    Path, Point, PolychromeSprite, PrimitiveBatch, Quad, ScaledPixels, Scene, Shadow, Size,
-    Surface, Underline, point, size,
+    AtlasTextureId, Backdrop, Background, BlendMode, Bounds, ContentMask, DevicePixels,
+    MonochromeSprite, PaintSurface, Path, Point, PolychromeSprite, PrimitiveBatch, Quad,
+    ScaledPixels, Scene, Shadow, Size, Surface, Underline, point, size,
 };
 use anyhow::Result;
 use block::ConcreteBlock;
@@ -18,6 +18,7 @@ use core_video::{
     pixel_buffer::kCVPixelFormatType_420YpCbCr8BiPlanarFullRange,
 };
 use foreign_types::{ForeignType, ForeignTypeRef};
+use image::RgbaImage;
 use metal::{
     CAMetalLayer, CommandQueue, MTLPixelFormat, MTLResourceOptions, NSRange,
     RenderPassColorAttachmentDescriptorRef,
@@ -25,7 +26,7 @@ use metal::{
 use objc::{self, msg_send, sel, sel_impl};
 use parking_lot::Mutex;
 
-use std::{cell::Cell, ffi::c_void, mem, ptr, sync::Arc};
+use std::{cell::Cell, ffi::c_void, mem, ptr, slice, sync::Arc};
 
 // Exported to metal
 pub(crate) type PointF = crate::Point<f32>;
@@ -104,10 +105,14 @@ pub(crate) struct MetalRenderer {
     paths_rasterization_pipeline_state: metal::RenderPipelineState,
     path_sprites_pipeline_state: metal::RenderPipelineState,
     shadows_pipeline_state: metal::RenderPipelineState,
-    quads_pipeline_state: metal::RenderPipelineState,
+    backdrop_horizontal_blur_pipeline_state: metal::RenderPipelineState,
+    backdrop_vertical_blur_pipeline_state: metal::RenderPipelineState,
+    overlay_pipeline_state: metal::RenderPipelineState,
+    quads_pipeline_states: BlendModePipelineStates,
     underlines_pipeline_state: metal::RenderPipelineState,
-    monochrome_sprites_pipeline_state: metal::RenderPipelineState,
-    polychrome_sprites_pipeline_state: metal::RenderPipelineState,
+    monochrome_sprites_pipeline_states: BlendModePipelineStates,
+    gradient_sprites_pipeline_states: BlendModePipelineStates,
+    polychrome_sprites_pipeline_states: BlendModePipelineStates,
     surfaces_pipeline_state: metal::RenderPipelineState,
     unit_vertices: metal::Buffer,
     #[allow(clippy::arc_with_non_send_sync)]
@@ -116,6 +121,8 @@ pub(crate) struct MetalRenderer {
     core_video_texture_cache: core_video::metal_texture_cache::CVMetalTextureCache,
     path_intermediate_texture: Option<metal::Texture>,
     path_intermediate_msaa_texture: Option<metal::Texture>,
+    backdrop_blur_texture: Option<metal::Texture>,
+    overlay_layer_texture: Option<metal::Texture>,
     path_sample_count: u32,
 }
 
@@ -125,6 +132,8 @@ pub struct PathRasterizationVertex {
     pub st_position: Point<f32>,
     pub color: Background,
     pub bounds: Bounds<ScaledPixels>,
+    pub clip_region: u32,
+    pub pad: u32, // align to 8 bytes
 }
 
 impl MetalRenderer {
@@ -218,7 +227,32 @@ impl MetalRenderer {
             "shadow_fragment",
             MTLPixelFormat::BGRA8Unorm,
         );
-        let quads_pipeline_state = build_pipeline_state(
+        let backdrop_horizontal_blur_pipeline_state = build_backdrop_blur_pipeline_state(
+            &device,
+            &library,
+            "backdrop_horizontal_blur",
+            "backdrop_horizontal_vertex",
+            "backdrop_horizontal_fragment",
+            MTLPixelFormat::BGRA8Unorm,
+        );
+        // The blurred drawable is already premultiplied by the coverage of the rounded corners.
+        let backdrop_vertical_blur_pipeline_state = build_path_sprite_pipeline_state(
+            &device,
+            &library,
+            "backdrop_vertical_blur",
+            "backdrop_vertical_vertex",
+            "backdrop_vertical_fragment",
+            MTLPixelFormat::BGRA8Unorm,
+        );
+        let overlay_pipeline_state = build_pipeline_state(
+            &device,
+            &library,
+            "overlay",
+            "overlay_vertex",
+            "overlay_fragment",
+            MTLPixelFormat::BGRA8Unorm,
+        );
+        let quads_pipeline_states = BlendModePipelineStates::new(
             &device,
             &library,
             "quads",
@@ -234,7 +268,7 @@ impl MetalRenderer {
             "underline_fragment",
             MTLPixelFormat::BGRA8Unorm,
         );
-        let monochrome_sprites_pipeline_state = build_pipeline_state(
+        let monochrome_sprites_pipeline_states = BlendModePipelineStates::new(
             &device,
             &library,
             "monochrome_sprites",
@@ -242,7 +276,15 @@ impl MetalRenderer {
             "monochrome_sprite_fragment",
             MTLPixelFormat::BGRA8Unorm,
         );
-        let polychrome_sprites_pipeline_state = build_pipeline_state(
+        let gradient_sprites_pipeline_states = BlendModePipelineStates::new(
+            &device,
+            &library,
+            "gradient_sprites",
+            "gradient_sprite_vertex",
+            "gradient_sprite_fragment",
+            MTLPixelFormat::BGRA8Unorm,
+        );
+        let polychrome_sprites_pipeline_states = BlendModePipelineStates::new(
             &device,
             &library,
             "polychrome_sprites",
@@ -272,10 +314,14 @@ impl MetalRenderer {
             paths_rasterization_pipeline_state,
             path_sprites_pipeline_state,
             shadows_pipeline_state,
-            quads_pipeline_state,
+            backdrop_horizontal_blur_pipeline_state,
+            backdrop_vertical_blur_pipeline_state,
+            overlay_pipeline_state,
+            quads_pipeline_states,
             underlines_pipeline_state,
-            monochrome_sprites_pipeline_state,
-            polychrome_sprites_pipeline_state,
+            monochrome_sprites_pipeline_states,
+            gradient_sprites_pipeline_states,
+            polychrome_sprites_pipeline_states,
             surfaces_pipeline_state,
             unit_vertices,
             instance_buffer_pool,
@@ -283,6 +329,8 @@ impl MetalRenderer {
             core_video_texture_cache,
             path_intermediate_texture: None,
             path_intermediate_msaa_texture: None,
+            backdrop_blur_texture: None,
+            overlay_layer_texture: None,
             path_sample_count: PATH_SAMPLE_COUNT,
         }
     }
@@ -330,6 +378,8 @@ impl MetalRenderer {
         if size.width.0 <= 0 || size.height.0 <= 0 {
             self.path_intermediate_texture = None;
             self.path_intermediate_msaa_texture = None;
+            self.backdrop_blur_texture = None;
+            self.overlay_layer_texture = None;
             return;
         }
 
@@ -340,6 +390,8 @@ impl MetalRenderer {
         texture_descriptor
             .set_usage(metal::MTLTextureUsage::RenderTarget | metal::MTLTextureUsage::ShaderRead);
         self.path_intermediate_texture = Some(self.device.new_texture(&texture_descriptor));
+        self.backdrop_blur_texture = Some(self.device.new_texture(&texture_descriptor));
+        self.overlay_layer_texture = Some(self.device.new_texture(&texture_descriptor));
 
         if self.path_sample_count > 1 {
             let mut msaa_descriptor = texture_descriptor;
@@ -362,6 +414,12 @@ impl MetalRenderer {
 
     pub fn draw(&mut self, scene: &Scene) {
         let layer = self.layer.clone();
+        // Backdrop blurs and overlay blends copy from the drawable, which framebuffer-only
+        // drawables don't allow.
+        let framebuffer_only = scene.backdrops.is_empty() && !scene.has_overlay_blend();
+        if layer.framebuffer_only() != framebuffer_only {
+            layer.set_framebuffer_only(framebuffer_only);
+        }
         let viewport_size = layer.drawable_size();
         let viewport_size: Size<DevicePixels> = size(
             (viewport_size.width.ceil() as i32).into(),
@@ -380,8 +438,12 @@ impl MetalRenderer {
         loop {
             let mut instance_buffer = self.instance_buffer_pool.lock().acquire(&self.device);
 
-            let command_buffer =
-                self.draw_primitives(scene, &mut instance_buffer, drawable, viewport_size);
+            let command_buffer = self.draw_primitives(
+                scene,
+                &mut instance_buffer,
+                drawable.texture(),
+                viewport_size,
+            );
 
             match command_buffer {
                 Ok(command_buffer) => {
@@ -426,11 +488,76 @@ impl MetalRenderer {
         }
     }
 
+    /// Renders the scene into an offscreen texture and reads back the given part of it, e.g. to
+    /// show an element as the drag image when it's dragged out of the window.
+    pub fn render_to_image(
+        &mut self,
+        scene: &Scene,
+        bounds: Bounds<DevicePixels>,
+    ) -> Result<RgbaImage> {
+        let viewport_size = self.layer.drawable_size();
+        let viewport_size: Size<DevicePixels> = size(
+            (viewport_size.width.ceil() as i32).into(),
+            (viewport_size.height.ceil() as i32).into(),
+        );
+        let bounds = bounds.intersect(&Bounds::new(Point::default(), viewport_size));
+        if bounds.is_empty() {
+            anyhow::bail!("{bounds:?} is outside of the drawable");
+        }
+
+        let texture_descriptor = metal::TextureDescriptor::new();
+        texture_descriptor.set_width(viewport_size.width.0 as u64);
+        texture_descriptor.set_height(viewport_size.height.0 as u64);
+        texture_descriptor.set_pixel_format(metal::MTLPixelFormat::BGRA8Unorm);
+        texture_descriptor
+            .set_usage(metal::MTLTextureUsage::RenderTarget | metal::MTLTextureUsage::ShaderRead);
+        texture_descriptor.set_storage_mode(metal::MTLStorageMode::Managed);
+        let texture = self.device.new_texture(&texture_descriptor);
+
+        let mut instance_buffer = self.instance_buffer_pool.lock().acquire(&self.device);
+        let command_buffer =
+            self.draw_primitives(scene, &mut instance_buffer, &texture, viewport_size);
+        let command_buffer = match command_buffer {
+            Ok(command_buffer) => command_buffer,
+            Err(error) => {
+                self.instance_buffer_pool.lock().release(instance_buffer);
+                return Err(error);
+            }
+        };
+        // Managed textures have to be synchronized before the CPU can read what the GPU wrote.
+        let blit_encoder = command_buffer.new_blit_command_encoder();
+        blit_encoder.synchronize_resource(&texture);
+        blit_encoder.end_encoding();
+        command_buffer.commit();
+        command_buffer.wait_until_completed();
+        self.instance_buffer_pool.lock().release(instance_buffer);
+
+        let width = bounds.size.width.0 as u32;
+        let height = bounds.size.height.0 as u32;
+        let mut bytes = vec![0u8; width as usize * height as usize * 4];
+        texture.get_bytes(
+            bytes.as_mut_ptr() as *mut c_void,
+            width as u64 * 4,
+            metal::MTLRegion::new_2d(
+                bounds.origin.x.0 as u64,
+                bounds.origin.y.0 as u64,
+                width as u64,
+                height as u64,
+            ),
+            0,
+        );
+        for pixel in bytes.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+        RgbaImage::from_raw(width, height, bytes)
+            .ok_or_else(|| anyhow::anyhow!("readback of {width}x{height} pixels is truncated"))
+    }
+
     fn draw_primitives(
         &mut self,
         scene: &Scene,
         instance_buffer: &mut InstanceBuffer,
-        drawable: &metal::MetalDrawableRef,
+        target: &metal::TextureRef,
         viewport_size: Size<DevicePixels>,
     ) -> Result<metal::CommandBuffer> {
         let command_queue = self.command_queue.clone();
@@ -438,17 +565,48 @@ impl MetalRenderer {
         let alpha = if self.layer.is_opaque() { 1. } else { 0. };
         let mut instance_offset = 0;
 
-        let mut command_encoder = new_command_encoder(
-            command_buffer,
-            drawable,
-            viewport_size,
-            |color_attachment| {
+        let Some(clip_regions) = upload_clip_regions(scene, instance_buffer, &mut instance_offset)
+        else {
+            anyhow::bail!(
+                "scene too large: {} clip regions with {} vertices",
+                scene.clip_regions.len(),
+                scene.clip_region_vertices.len(),
+            );
+        };
+
+        let mut command_encoder =
+            new_command_encoder(command_buffer, target, viewport_size, |color_attachment| {
                 color_attachment.set_load_action(metal::MTLLoadAction::Clear);
                 color_attachment.set_clear_color(metal::MTLClearColor::new(0., 0., 0., alpha));
-            },
-        );
+            });
+        bind_clip_regions(command_encoder, instance_buffer, clip_regions);
 
         for batch in scene.batches() {
+            let overlay = batch.overlay_bounds(viewport_size).and_then(|bounds| {
+                match (&self.overlay_layer_texture, &self.path_intermediate_texture) {
+                    (Some(layer_texture), Some(backdrop_texture)) => {
+                        Some((bounds, layer_texture, backdrop_texture))
+                    }
+                    _ => {
+                        BlendMode::log_overlay_fallback();
+                        None
+                    }
+                }
+            });
+            if let Some((_, layer_texture, _)) = overlay {
+                command_encoder.end_encoding();
+                command_encoder = new_command_encoder(
+                    command_buffer,
+                    layer_texture,
+                    viewport_size,
+                    |color_attachment| {
+                        color_attachment.set_load_action(metal::MTLLoadAction::Clear);
+                        color_attachment.set_clear_color(metal::MTLClearColor::new(0., 0., 0., 0.));
+                    },
+                );
+                bind_clip_regions(command_encoder, instance_buffer, clip_regions);
+            }
+
             let ok = match batch {
                 PrimitiveBatch::Shadows(shadows) => self.draw_shadows(
                     shadows,
@@ -464,6 +622,44 @@ impl MetalRenderer {
                     viewport_size,
                     command_encoder,
                 ),
+                PrimitiveBatch::Backdrops(backdrops) => {
+                    if self.path_intermediate_texture.is_some()
+                        && self.backdrop_blur_texture.is_some()
+                    {
+                        command_encoder.end_encoding();
+
+                        let did_draw = self.draw_backdrops(
+                            backdrops,
+                            instance_buffer,
+                            &mut instance_offset,
+                            viewport_size,
+                            target,
+                            command_buffer,
+                            clip_regions,
+                        );
+
+                        command_encoder = new_command_encoder(
+                            command_buffer,
+                            target,
+                            viewport_size,
+                            |color_attachment| {
+                                color_attachment.set_load_action(metal::MTLLoadAction::Load);
+                            },
+                        );
+                        bind_clip_regions(command_encoder, instance_buffer, clip_regions);
+                        did_draw
+                    } else {
+                        let quads: Vec<Quad> =
+                            backdrops.iter().map(Backdrop::fallback_quad).collect();
+                        self.draw_quads(
+                            &quads,
+                            instance_buffer,
+                            &mut instance_offset,
+                            viewport_size,
+                            command_encoder,
+                        )
+                    }
+                }
                 PrimitiveBatch::Paths(paths) => {
                     command_encoder.end_encoding();
 
@@ -473,16 +669,18 @@ impl MetalRenderer {
                         &mut instance_offset,
                         viewport_size,
                         command_buffer,
+                        clip_regions,
                     );
 
                     command_encoder = new_command_encoder(
                         command_buffer,
-                        drawable,
+                        target,
                         viewport_size,
                         |color_attachment| {
                             color_attachment.set_load_action(metal::MTLLoadAction::Load);
                         },
                     );
+                    bind_clip_regions(command_encoder, instance_buffer, clip_regions);
 
                     if did_draw {
                         self.draw_paths_from_intermediate(
@@ -509,6 +707,21 @@ impl MetalRenderer {
                 } => self.draw_monochrome_sprites(
                     texture_id,
                     sprites,
+                    self.monochrome_sprites_pipeline_states
+                        .get(sprites[0].blend_mode),
+                    instance_buffer,
+                    &mut instance_offset,
+                    viewport_size,
+                    command_encoder,
+                ),
+                PrimitiveBatch::GradientSprites {
+                    texture_id,
+                    sprites,
+                } => self.draw_monochrome_sprites(
+                    texture_id,
+                    sprites,
+                    self.gradient_sprites_pipeline_states
+                        .get(sprites[0].blend_mode),
                     instance_buffer,
                     &mut instance_offset,
                     viewport_size,
@@ -533,15 +746,37 @@ impl MetalRenderer {
                     command_encoder,
                 ),
             };
+
+            if ok && let Some((bounds, layer_texture, backdrop_texture)) = overlay {
+                command_encoder.end_encoding();
+                self.copy_overlay_backdrop(bounds, target, backdrop_texture, command_buffer);
+                command_encoder = new_command_encoder(
+                    command_buffer,
+                    target,
+                    viewport_size,
+                    |color_attachment| {
+                        color_attachment.set_load_action(metal::MTLLoadAction::Load);
+                    },
+                );
+                bind_clip_regions(command_encoder, instance_buffer, clip_regions);
+                self.draw_overlay(
+                    bounds,
+                    layer_texture,
+                    backdrop_texture,
+                    viewport_size,
+                    command_encoder,
+                );
+            }
             if !ok {
                 command_encoder.end_encoding();
                 anyhow::bail!(
-                    "scene too large: {} paths, {} shadows, {} quads, {} underlines, {} mono, {} poly, {} surfaces",
+                    "scene too large: {} paths, {} shadows, {} backdrops, {} quads, {} underlines, {} mono, {} poly, {} surfaces",
                     scene.paths.len(),
                     scene.shadows.len(),
+                    scene.backdrops.len(),
                     scene.quads.len(),
                     scene.underlines.len(),
-                    scene.monochrome_sprites.len(),
+                    scene.monochrome_sprites.len() + scene.gradient_sprites.len(),
                     scene.polychrome_sprites.len(),
                     scene.surfaces.len(),
                 );
@@ -564,6 +799,7 @@ impl MetalRenderer {
         instance_offset: &mut usize,
         viewport_size: Size<DevicePixels>,
         command_buffer: &metal::CommandBufferRef,
+        clip_regions: ClipRegionOffsets,
     ) -> bool {
         if paths.is_empty() {
             return true;
@@ -591,6 +827,7 @@ impl MetalRenderer {
 
         let command_encoder = command_buffer.new_render_command_encoder(render_pass_descriptor);
         command_encoder.set_render_pipeline_state(&self.paths_rasterization_pipeline_state);
+        bind_clip_regions(command_encoder, instance_buffer, clip_regions);
 
         align_offset(instance_offset);
         let mut vertices = Vec::new();
@@ -600,6 +837,8 @@ impl MetalRenderer {
                 st_position: v.st_position,
                 color: path.color,
                 bounds: path.bounds.intersect(&path.content_mask.bounds),
+                clip_region: path.clip_region,
+                pad: 0,
             }));
         }
         let vertices_bytes_len = mem::size_of_val(vertices.as_slice());
@@ -706,6 +945,212 @@ impl MetalRenderer {
         true
     }
 
+    /// Blurs the part of the target behind each backdrop in two passes, first horizontally from
+    /// a copy of the target into the blur texture, then vertically from there back into the
+    /// target.
+    fn draw_backdrops(
+        &self,
+        backdrops: &[Backdrop],
+        instance_buffer: &mut InstanceBuffer,
+        instance_offset: &mut usize,
+        viewport_size: Size<DevicePixels>,
+        target: &metal::TextureRef,
+        command_buffer: &metal::CommandBufferRef,
+        clip_regions: ClipRegionOffsets,
+    ) -> bool {
+        let (Some(intermediate_texture), Some(blur_texture)) =
+            (&self.path_intermediate_texture, &self.backdrop_blur_texture)
+        else {
+            return false;
+        };
+
+        for backdrop in backdrops {
+            let Some(sample_bounds) = backdrop.sample_bounds(viewport_size) else {
+                continue;
+            };
+
+            align_offset(instance_offset);
+            let backdrop_bytes_len = mem::size_of::<Backdrop>();
+            let next_offset = *instance_offset + backdrop_bytes_len;
+            if next_offset > instance_buffer.size {
+                return false;
+            }
+            let buffer_contents = unsafe {
+                (instance_buffer.metal_buffer.contents() as *mut u8).add(*instance_offset)
+            };
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    backdrop as *const Backdrop as *const u8,
+                    buffer_contents,
+                    backdrop_bytes_len,
+                );
+            }
+            let backdrop_offset = *instance_offset;
+            *instance_offset = next_offset;
+
+            let origin = metal::MTLOrigin {
+                x: sample_bounds.origin.x.0 as u64,
+                y: sample_bounds.origin.y.0 as u64,
+                z: 0,
+            };
+            let blit_encoder = command_buffer.new_blit_command_encoder();
+            blit_encoder.copy_from_texture(
+                target,
+                0,
+                0,
+                origin,
+                metal::MTLSize {
+                    width: sample_bounds.size.width.0 as u64,
+                    height: sample_bounds.size.height.0 as u64,
+                    depth: 1,
+                },
+                intermediate_texture,
+                0,
+                0,
+                origin,
+            );
+            blit_encoder.end_encoding();
+
+            self.draw_backdrop_pass(
+                &self.backdrop_horizontal_blur_pipeline_state,
+                intermediate_texture,
+                blur_texture,
+                instance_buffer,
+                backdrop_offset,
+                viewport_size,
+                command_buffer,
+                clip_regions,
+            );
+            self.draw_backdrop_pass(
+                &self.backdrop_vertical_blur_pipeline_state,
+                blur_texture,
+                target,
+                instance_buffer,
+                backdrop_offset,
+                viewport_size,
+                command_buffer,
+                clip_regions,
+            );
+        }
+        true
+    }
+
+    fn draw_backdrop_pass(
+        &self,
+        pipeline_state: &metal::RenderPipelineStateRef,
+        source_texture: &metal::TextureRef,
+        target_texture: &metal::TextureRef,
+        instance_buffer: &InstanceBuffer,
+        backdrop_offset: usize,
+        viewport_size: Size<DevicePixels>,
+        command_buffer: &metal::CommandBufferRef,
+        clip_regions: ClipRegionOffsets,
+    ) {
+        let render_pass_descriptor = metal::RenderPassDescriptor::new();
+        let color_attachment = render_pass_descriptor
+            .color_attachments()
+            .object_at(0)
+            .unwrap();
+        color_attachment.set_texture(Some(target_texture));
+        color_attachment.set_load_action(metal::MTLLoadAction::Load);
+        color_attachment.set_store_action(metal::MTLStoreAction::Store);
+
+        let command_encoder = command_buffer.new_render_command_encoder(render_pass_descriptor);
+        command_encoder.set_render_pipeline_state(pipeline_state);
+        bind_clip_regions(command_encoder, instance_buffer, clip_regions);
+        command_encoder.set_vertex_buffer(
+            BackdropInputIndex::Vertices as u64,
+            Some(&self.unit_vertices),
+            0,
+        );
+        command_encoder.set_vertex_buffer(
+            BackdropInputIndex::Backdrops as u64,
+            Some(&instance_buffer.metal_buffer),
+            backdrop_offset as u64,
+        );
+        command_encoder.set_fragment_buffer(
+            BackdropInputIndex::Backdrops as u64,
+            Some(&instance_buffer.metal_buffer),
+            backdrop_offset as u64,
+        );
+        command_encoder.set_vertex_bytes(
+            BackdropInputIndex::ViewportSize as u64,
+            mem::size_of_val(&viewport_size) as u64,
+            &viewport_size as *const Size<DevicePixels> as *const _,
+        );
+        command_encoder
+            .set_fragment_texture(BackdropInputIndex::Texture as u64, Some(source_texture));
+        command_encoder.draw_primitives_instanced(metal::MTLPrimitiveType::Triangle, 0, 6, 1);
+        command_encoder.end_encoding();
+    }
+
+    fn copy_overlay_backdrop(
+        &self,
+        bounds: Bounds<DevicePixels>,
+        target: &metal::TextureRef,
+        backdrop_texture: &metal::TextureRef,
+        command_buffer: &metal::CommandBufferRef,
+    ) {
+        let origin = metal::MTLOrigin {
+            x: bounds.origin.x.0 as u64,
+            y: bounds.origin.y.0 as u64,
+            z: 0,
+        };
+        let blit_encoder = command_buffer.new_blit_command_encoder();
+        blit_encoder.copy_from_texture(
+            target,
+            0,
+            0,
+            origin,
+            metal::MTLSize {
+                width: bounds.size.width.0 as u64,
+                height: bounds.size.height.0 as u64,
+                depth: 1,
+            },
+            backdrop_texture,
+            0,
+            0,
+            origin,
+        );
+        blit_encoder.end_encoding();
+    }
+
+    /// Composites an overlay blended batch painted into the layer texture over the copy of what
+    /// was behind it.
+    fn draw_overlay(
+        &self,
+        bounds: Bounds<DevicePixels>,
+        layer_texture: &metal::TextureRef,
+        backdrop_texture: &metal::TextureRef,
+        viewport_size: Size<DevicePixels>,
+        command_encoder: &metal::RenderCommandEncoderRef,
+    ) {
+        let bounds = bounds.map(ScaledPixels::from);
+        command_encoder.set_render_pipeline_state(&self.overlay_pipeline_state);
+        command_encoder.set_vertex_buffer(
+            OverlayInputIndex::Vertices as u64,
+            Some(&self.unit_vertices),
+            0,
+        );
+        command_encoder.set_vertex_bytes(
+            OverlayInputIndex::Bounds as u64,
+            mem::size_of_val(&bounds) as u64,
+            &bounds as *const Bounds<ScaledPixels> as *const _,
+        );
+        command_encoder.set_vertex_bytes(
+            OverlayInputIndex::ViewportSize as u64,
+            mem::size_of_val(&viewport_size) as u64,
+            &viewport_size as *const Size<DevicePixels> as *const _,
+        );
+        command_encoder.set_fragment_texture(
+            OverlayInputIndex::BackdropTexture as u64,
+            Some(backdrop_texture),
+        );
+        command_encoder
+            .set_fragment_texture(OverlayInputIndex::LayerTexture as u64, Some(layer_texture));
+        command_encoder.draw_primitives(metal::MTLPrimitiveType::Triangle, 0, 6);
+    }
+
     fn draw_quads(
         &self,
         quads: &[Quad],
@@ -719,7 +1164,8 @@ impl MetalRenderer {
         }
         align_offset(instance_offset);
 
-        command_encoder.set_render_pipeline_state(&self.quads_pipeline_state);
+        command_encoder
+            .set_render_pipeline_state(self.quads_pipeline_states.get(quads[0].blend_mode));
         command_encoder.set_vertex_buffer(
             QuadInputIndex::Vertices as u64,
             Some(&self.unit_vertices),
@@ -918,10 +1364,13 @@ impl MetalRenderer {
         true
     }
 
-    fn draw_monochrome_sprites(
+    /// Draws monochrome or gradient sprites, which share their inputs and only differ in their
+    /// pipeline state.
+    fn draw_monochrome_sprites<T>(
         &self,
         texture_id: AtlasTextureId,
-        sprites: &[MonochromeSprite],
+        sprites: &[T],
+        pipeline_state: &metal::RenderPipelineStateRef,
         instance_buffer: &mut InstanceBuffer,
         instance_offset: &mut usize,
         viewport_size: Size<DevicePixels>,
@@ -946,7 +1395,7 @@ impl MetalRenderer {
             DevicePixels(texture.width() as i32),
             DevicePixels(texture.height() as i32),
         );
-        command_encoder.set_render_pipeline_state(&self.monochrome_sprites_pipeline_state);
+        command_encoder.set_render_pipeline_state(pipeline_state);
         command_encoder.set_vertex_buffer(
             SpriteInputIndex::Vertices as u64,
             Some(&self.unit_vertices),
@@ -1011,7 +1460,10 @@ impl MetalRenderer {
             DevicePixels(texture.width() as i32),
             DevicePixels(texture.height() as i32),
         );
-        command_encoder.set_render_pipeline_state(&self.polychrome_sprites_pipeline_state);
+        command_encoder.set_render_pipeline_state(
+            self.polychrome_sprites_pipeline_states
+                .get(sprites[0].blend_mode),
+        );
         command_encoder.set_vertex_buffer(
             SpriteInputIndex::Vertices as u64,
             Some(&self.unit_vertices),
@@ -1168,7 +1620,7 @@ impl MetalRenderer {
 
 fn new_command_encoder<'a>(
     command_buffer: &'a metal::CommandBufferRef,
-    drawable: &'a metal::MetalDrawableRef,
+    target_texture: &'a metal::TextureRef,
     viewport_size: Size<DevicePixels>,
     configure_color_attachment: impl Fn(&RenderPassColorAttachmentDescriptorRef),
 ) -> &'a metal::RenderCommandEncoderRef {
@@ -1177,7 +1629,7 @@ fn new_command_encoder<'a>(
         .color_attachments()
         .object_at(0)
         .unwrap();
-    color_attachment.set_texture(Some(drawable.texture()));
+    color_attachment.set_texture(Some(target_texture));
     color_attachment.set_store_action(metal::MTLStoreAction::Store);
     configure_color_attachment(color_attachment);
 
@@ -1227,6 +1679,143 @@ fn build_pipeline_state(
         .expect("could not create render pipeline state")
 }
 
+/// The variants of a pipeline state for each [`BlendMode`] it can be drawn with.
+struct BlendModePipelineStates {
+    normal: metal::RenderPipelineState,
+    multiply: metal::RenderPipelineState,
+    screen: metal::RenderPipelineState,
+}
+
+impl BlendModePipelineStates {
+    fn new(
+        device: &metal::DeviceRef,
+        library: &metal::LibraryRef,
+        label: &str,
+        vertex_fn_name: &str,
+        fragment_fn_name: &str,
+        pixel_format: metal::MTLPixelFormat,
+    ) -> Self {
+        Self {
+            normal: build_pipeline_state(
+                device,
+                library,
+                label,
+                vertex_fn_name,
+                fragment_fn_name,
+                pixel_format,
+            ),
+            multiply: build_blend_mode_pipeline_state(
+                device,
+                library,
+                &format!("{label}_multiply"),
+                vertex_fn_name,
+                fragment_fn_name,
+                pixel_format,
+                BlendMode::Multiply,
+            ),
+            screen: build_blend_mode_pipeline_state(
+                device,
+                library,
+                &format!("{label}_screen"),
+                vertex_fn_name,
+                fragment_fn_name,
+                pixel_format,
+                BlendMode::Screen,
+            ),
+        }
+    }
+
+    fn get(&self, blend_mode: BlendMode) -> &metal::RenderPipelineState {
+        // Overlay blended batches are painted normally into a layer before being composited.
+        match blend_mode {
+            BlendMode::Normal | BlendMode::Overlay => &self.normal,
+            BlendMode::Multiply => &self.multiply,
+            BlendMode::Screen => &self.screen,
+        }
+    }
+}
+
+/// Builds a pipeline state that composites the premultiplied colors output for the given blend
+/// mode with what's behind them.
+fn build_blend_mode_pipeline_state(
+    device: &metal::DeviceRef,
+    library: &metal::LibraryRef,
+    label: &str,
+    vertex_fn_name: &str,
+    fragment_fn_name: &str,
+    pixel_format: metal::MTLPixelFormat,
+    blend_mode: BlendMode,
+) -> metal::RenderPipelineState {
+    let vertex_fn = library
+        .get_function(vertex_fn_name, None)
+        .expect("error locating vertex function");
+    let fragment_fn = library
+        .get_function(fragment_fn_name, None)
+        .expect("error locating fragment function");
+
+    let (source_rgb_blend_factor, destination_rgb_blend_factor) = match blend_mode {
+        BlendMode::Multiply => (
+            metal::MTLBlendFactor::DestinationColor,
+            metal::MTLBlendFactor::OneMinusSourceAlpha,
+        ),
+        BlendMode::Screen => (
+            metal::MTLBlendFactor::One,
+            metal::MTLBlendFactor::OneMinusSourceColor,
+        ),
+        BlendMode::Normal | BlendMode::Overlay => (
+            metal::MTLBlendFactor::One,
+            metal::MTLBlendFactor::OneMinusSourceAlpha,
+        ),
+    };
+
+    let descriptor = metal::RenderPipelineDescriptor::new();
+    descriptor.set_label(label);
+    descriptor.set_vertex_function(Some(vertex_fn.as_ref()));
+    descriptor.set_fragment_function(Some(fragment_fn.as_ref()));
+    let color_attachment = descriptor.color_attachments().object_at(0).unwrap();
+    color_attachment.set_pixel_format(pixel_format);
+    color_attachment.set_blending_enabled(true);
+    color_attachment.set_rgb_blend_operation(metal::MTLBlendOperation::Add);
+    color_attachment.set_alpha_blend_operation(metal::MTLBlendOperation::Add);
+    color_attachment.set_source_rgb_blend_factor(source_rgb_blend_factor);
+    color_attachment.set_source_alpha_blend_factor(metal::MTLBlendFactor::One);
+    color_attachment.set_destination_rgb_blend_factor(destination_rgb_blend_factor);
+    color_attachment.set_destination_alpha_blend_factor(metal::MTLBlendFactor::One);
+
+    device
+        .new_render_pipeline_state(&descriptor)
+        .expect("could not create render pipeline state")
+}
+
+fn build_backdrop_blur_pipeline_state(
+    device: &metal::DeviceRef,
+    library: &metal::LibraryRef,
+    label: &str,
+    vertex_fn_name: &str,
+    fragment_fn_name: &str,
+    pixel_format: metal::MTLPixelFormat,
+) -> metal::RenderPipelineState {
+    let vertex_fn = library
+        .get_function(vertex_fn_name, None)
+        .expect("error locating vertex function");
+    let fragment_fn = library
+        .get_function(fragment_fn_name, None)
+        .expect("error locating fragment function");
+
+    let descriptor = metal::RenderPipelineDescriptor::new();
+    descriptor.set_label(label);
+    descriptor.set_vertex_function(Some(vertex_fn.as_ref()));
+    descriptor.set_fragment_function(Some(fragment_fn.as_ref()));
+    let color_attachment = descriptor.color_attachments().object_at(0).unwrap();
+    color_attachment.set_pixel_format(pixel_format);
+    // The first pass replaces the contents of the blur texture.
+    color_attachment.set_blending_enabled(false);
+
+    device
+        .new_render_pipeline_state(&descriptor)
+        .expect("could not create render pipeline state")
+}
+
 fn build_path_sprite_pipeline_state(
     device: &metal::DeviceRef,
     library: &metal::LibraryRef,
@@ -1305,6 +1894,70 @@ fn align_offset(offset: &mut usize) {
     *offset = (*offset).div_ceil(256) * 256;
 }
 
+/// The offsets of the scene's clip regions and their vertices in the instance buffer.
+#[derive(Clone, Copy)]
+struct ClipRegionOffsets {
+    regions: usize,
+    vertices: usize,
+}
+
+/// Copies the scene's clip regions into the instance buffer, returning `None` if they don't fit.
+fn upload_clip_regions(
+    scene: &Scene,
+    instance_buffer: &mut InstanceBuffer,
+    instance_offset: &mut usize,
+) -> Option<ClipRegionOffsets> {
+    let mut upload = |bytes: &[u8]| {
+        align_offset(instance_offset);
+        let offset = *instance_offset;
+        let next_offset = offset + bytes.len();
+        if next_offset > instance_buffer.size {
+            return None;
+        }
+        unsafe {
+            ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                (instance_buffer.metal_buffer.contents() as *mut u8).add(offset),
+                bytes.len(),
+            );
+        }
+        *instance_offset = next_offset;
+        Some(offset)
+    };
+    let regions = upload(unsafe {
+        slice::from_raw_parts(
+            scene.clip_regions.as_ptr() as *const u8,
+            mem::size_of_val(scene.clip_regions.as_slice()),
+        )
+    })?;
+    let vertices = upload(unsafe {
+        slice::from_raw_parts(
+            scene.clip_region_vertices.as_ptr() as *const u8,
+            mem::size_of_val(scene.clip_region_vertices.as_slice()),
+        )
+    })?;
+    Some(ClipRegionOffsets { regions, vertices })
+}
+
+/// Binds the clip regions for the fragment shaders of every pipeline used with the encoder,
+/// which are looked up by the `clip_region` of each primitive.
+fn bind_clip_regions(
+    command_encoder: &metal::RenderCommandEncoderRef,
+    instance_buffer: &InstanceBuffer,
+    clip_regions: ClipRegionOffsets,
+) {
+    command_encoder.set_fragment_buffer(
+        ClipRegionInputIndex::Regions as u64,
+        Some(&instance_buffer.metal_buffer),
+        clip_regions.regions as u64,
+    );
+    command_encoder.set_fragment_buffer(
+        ClipRegionInputIndex::Vertices as u64,
+        Some(&instance_buffer.metal_buffer),
+        clip_regions.vertices as u64,
+    );
+}
+
 #[repr(C)]
 enum ShadowInputIndex {
     Vertices = 0,
@@ -1312,6 +1965,23 @@ enum ShadowInputIndex {
     ViewportSize = 2,
 }
 
+#[repr(C)]
+enum BackdropInputIndex {
+    Vertices = 0,
+    Backdrops = 1,
+    ViewportSize = 2,
+    Texture = 3,
+}
+
+#[repr(C)]
+enum OverlayInputIndex {
+    Vertices = 0,
+    Bounds = 1,
+    ViewportSize = 2,
+    BackdropTexture = 3,
+    LayerTexture = 4,
+}
+
 #[repr(C)]
 enum QuadInputIndex {
     Vertices = 0,
@@ -1351,6 +2021,14 @@ enum PathRasterizationInputIndex {
     ViewportSize = 1,
 }
 
+/// The fragment buffers holding the clip regions, which are bound for every pipeline so they
+/// don't overlap with the buffers of any primitive.
+#[repr(C)]
+enum ClipRegionInputIndex {
+    Regions = 6,
+    Vertices = 7,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[repr(C)]
 pub struct PathSprite {
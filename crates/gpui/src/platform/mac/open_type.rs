@@ -1,6 +1,6 @@
 #![allow(unused, non_upper_case_globals)]
 
-use crate::{FontFallbacks, FontFeatures};
+use crate::{FontFallbacks, FontFeatures, FontVariations};
 use cocoa::appkit::CGFloat;
 use core_foundation::{
     array::{
@@ -9,7 +9,8 @@ use core_foundation::{
     },
     base::{CFRelease, TCFType, kCFAllocatorDefault},
     dictionary::{
-        CFDictionaryCreate, kCFTypeDictionaryKeyCallBacks, kCFTypeDictionaryValueCallBacks,
+        CFDictionary as CFTypeDictionary, CFDictionaryCreate, kCFTypeDictionaryKeyCallBacks,
+        kCFTypeDictionaryValueCallBacks,
     },
     number::CFNumber,
     string::{CFString, CFStringRef},
@@ -27,14 +28,20 @@ use core_text::{
 use font_kit::font::Font as FontKitFont;
 use std::ptr;
 
-pub fn apply_features_and_fallbacks(
+pub fn apply_features_variations_and_fallbacks(
     font: &mut FontKitFont,
     features: &FontFeatures,
+    variations: &FontVariations,
     fallbacks: Option<&FontFallbacks>,
 ) -> anyhow::Result<()> {
     unsafe {
         let mut keys = vec![kCTFontFeatureSettingsAttribute];
         let mut values = vec![generate_feature_array(features)];
+        let variation_dictionary = generate_variation_dictionary(variations);
+        if let Some(variation_dictionary) = &variation_dictionary {
+            keys.push(kCTFontVariationAttribute);
+            values.push(variation_dictionary.as_concrete_TypeRef() as _);
+        }
         if let Some(fallbacks) = fallbacks
             && !fallbacks.fallback_list().is_empty()
         {
@@ -93,6 +100,28 @@ fn generate_feature_array(features: &FontFeatures) -> CFMutableArrayRef {
     }
 }
 
+/// Core Text identifies variation axes by their OpenType tag read as a big-endian integer.
+fn generate_variation_dictionary(
+    variations: &FontVariations,
+) -> Option<CFTypeDictionary<CFNumber, CFNumber>> {
+    let pairs = variations
+        .axis_value_list()
+        .iter()
+        .filter_map(|(tag, value)| {
+            let tag: [u8; 4] = tag.as_bytes().try_into().ok()?;
+            Some((
+                CFNumber::from(u32::from_be_bytes(tag) as i64),
+                CFNumber::from(*value as f64),
+            ))
+        })
+        .collect::<Vec<_>>();
+    if pairs.is_empty() {
+        None
+    } else {
+        Some(CFTypeDictionary::from_CFType_pairs(&pairs))
+    }
+}
+
 fn generate_fallback_array(fallbacks: &FontFallbacks, font_ref: CTFontRef) -> CFMutableArrayRef {
     unsafe {
         let fallback_array = CFArrayCreateMutable(kCFAllocatorDefault, 0, &kCFTypeArrayCallBacks);
@@ -133,6 +162,7 @@ fn append_system_fallbacks(fallback_array: CFMutableArrayRef, font_ref: CTFontRe
 unsafe extern "C" {
     static kCTFontOpenTypeFeatureTag: CFStringRef;
     static kCTFontOpenTypeFeatureValue: CFStringRef;
+    static kCTFontVariationAttribute: CFStringRef;
 
     fn CTFontCreateCopyWithAttributes(
         font: CTFontRef,
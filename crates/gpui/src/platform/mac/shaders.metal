@@ -25,14 +25,27 @@ float dash_alpha(float t, float period, float length, float dash_velocity,
                  float antialias_threshold);
 float quarter_ellipse_sdf(float2 point, float2 radii);
 float pick_corner_radius(float2 center_to_point, Corners_ScaledPixels corner_radii);
+float4 pick_border_color(float2 center_to_point, float2 corner_to_point,
+                         float2 border, Edges_Hsla colors,
+                         float antialias_threshold);
 float quad_sdf(float2 point, Bounds_ScaledPixels bounds,
                Corners_ScaledPixels corner_radii);
 float quad_sdf_impl(float2 center_to_point, float corner_radius);
+float ellipse_sdf(float2 point, Bounds_ScaledPixels bounds);
+float polygon_sdf(float2 point, constant float2 *vertices, uint vertex_count);
+float clip_region_alpha(uint clip_region, float2 point,
+                        constant ClipRegion *clip_regions,
+                        constant float2 *clip_region_vertices);
 float gaussian(float x, float sigma);
 float2 erf(float2 x);
 float blur_along_x(float x, float y, float sigma, float corner,
                    float2 half_size);
 float4 over(float4 below, float4 above);
+float4 blend_mode_color(BlendMode blend_mode, float4 color);
+float4 gradient_stops_color(Background background, float4 color0, float4 color1,
+                            float t);
+float4 blur_backdrop(texture2d<float> texture, float2 position, float2 direction,
+                     float sigma);
 float radians(float degrees);
 float4 fill_color(Background background, float2 position, Bounds_ScaledPixels bounds,
   float4 solid_color, float4 color0, float4 color1);
@@ -47,7 +60,6 @@ GradientColor prepare_fill_color(uint tag, uint color_space, Hsla solid, Hsla co
 struct QuadVertexOutput {
   uint quad_id [[flat]];
   float4 position [[position]];
-  float4 border_color [[flat]];
   float4 background_solid [[flat]];
   float4 background_color0 [[flat]];
   float4 background_color1 [[flat]];
@@ -57,7 +69,6 @@ struct QuadVertexOutput {
 struct QuadFragmentInput {
   uint quad_id [[flat]];
   float4 position [[position]];
-  float4 border_color [[flat]];
   float4 background_solid [[flat]];
   float4 background_color0 [[flat]];
   float4 background_color1 [[flat]];
@@ -77,8 +88,6 @@ vertex QuadVertexOutput quad_vertex(uint unit_vertex_id [[vertex_id]],
       to_device_position(unit_vertex, quad.bounds, viewport_size);
   float4 clip_distance = distance_from_clip_rect(unit_vertex, quad.bounds,
                                                  quad.content_mask.bounds);
-  float4 border_color = hsla_to_rgba(quad.border_color);
-
   GradientColor gradient = prepare_fill_color(
     quad.background.tag,
     quad.background.color_space,
@@ -90,7 +99,6 @@ vertex QuadVertexOutput quad_vertex(uint unit_vertex_id [[vertex_id]],
   return QuadVertexOutput{
       quad_id,
       device_position,
-      border_color,
       gradient.solid,
       gradient.color0,
       gradient.color1,
@@ -99,8 +107,16 @@ vertex QuadVertexOutput quad_vertex(uint unit_vertex_id [[vertex_id]],
 
 fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
                               constant Quad *quads
-                              [[buffer(QuadInputIndex_Quads)]]) {
+                              [[buffer(QuadInputIndex_Quads)]],
+    constant ClipRegion *clip_regions [[buffer(ClipRegionInputIndex_Regions)]],
+    constant float2 *clip_region_vertices
+    [[buffer(ClipRegionInputIndex_Vertices)]]) {
   Quad quad = quads[input.quad_id];
+  float clip_alpha = clip_region_alpha(quad.clip_region, input.position.xy,
+                                       clip_regions, clip_region_vertices);
+  if (clip_alpha <= 0.0) {
+    return float4(0.0);
+  }
   float4 background_color = fill_color(quad.background, input.position.xy, quad.bounds,
     input.background_solid, input.background_color0, input.background_color1);
 
@@ -115,7 +131,8 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
       quad.border_widths.right == 0.0 &&
       quad.border_widths.bottom == 0.0 &&
       unrounded) {
-    return background_color;
+    return blend_mode_color(quad.blend_mode,
+                            background_color * float4(1.0, 1.0, 1.0, clip_alpha));
   }
 
   float2 size = float2(quad.bounds.size.width, quad.bounds.size.height);
@@ -175,7 +192,8 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
 
   // Fast path for points that must be part of the background
   if (is_within_inner_straight_border && !is_near_rounded_corner) {
-    return background_color;
+    return blend_mode_color(quad.blend_mode,
+                            background_color * float4(1.0, 1.0, 1.0, clip_alpha));
   }
 
   // Signed distance of the point to the outside edge of the quad's border
@@ -210,7 +228,9 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
 
   float4 color = background_color;
   if (border_sdf < antialias_threshold) {
-    float4 border_color = input.border_color;
+    float4 border_color = pick_border_color(center_to_point, corner_to_point,
+                                            border, quad.border_colors,
+                                            antialias_threshold);
 
     // Dashed border logic when border_style == 1
     if (quad.border_style == 1) {
@@ -393,7 +413,10 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
                 saturate(antialias_threshold - inner_sdf));
   }
 
-  return color * float4(1.0, 1.0, 1.0, saturate(antialias_threshold - outer_sdf));
+  return blend_mode_color(
+      quad.blend_mode,
+      color * float4(1.0, 1.0, 1.0,
+                     saturate(antialias_threshold - outer_sdf) * clip_alpha));
 }
 
 // Returns the dash velocity of a corner given the dash velocity of the two
@@ -468,14 +491,19 @@ vertex ShadowVertexOutput shadow_vertex(
   float2 unit_vertex = unit_vertices[unit_vertex_id];
   Shadow shadow = shadows[shadow_id];
 
-  float margin = 3. * shadow.blur_radius;
-  // Set the bounds of the shadow and adjust its size based on the shadow's
-  // spread radius to achieve the spreading effect
   Bounds_ScaledPixels bounds = shadow.bounds;
-  bounds.origin.x -= margin;
-  bounds.origin.y -= margin;
-  bounds.size.width += 2. * margin;
-  bounds.size.height += 2. * margin;
+  if (shadow.inset != 0) {
+    // Inset shadows are only painted within the element's bounds
+    bounds = shadow.clip_bounds;
+  } else {
+    float margin = 3. * shadow.blur_radius;
+    // Set the bounds of the shadow and adjust its size based on the shadow's
+    // spread radius to achieve the spreading effect
+    bounds.origin.x -= margin;
+    bounds.origin.y -= margin;
+    bounds.size.width += 2. * margin;
+    bounds.size.height += 2. * margin;
+  }
 
   float4 device_position =
       to_device_position(unit_vertex, bounds, viewport_size);
@@ -492,7 +520,10 @@ vertex ShadowVertexOutput shadow_vertex(
 
 fragment float4 shadow_fragment(ShadowFragmentInput input [[stage_in]],
                                 constant Shadow *shadows
-                                [[buffer(ShadowInputIndex_Shadows)]]) {
+                                [[buffer(ShadowInputIndex_Shadows)]],
+    constant ClipRegion *clip_regions [[buffer(ClipRegionInputIndex_Regions)]],
+    constant float2 *clip_region_vertices
+    [[buffer(ClipRegionInputIndex_Vertices)]]) {
   Shadow shadow = shadows[input.shadow_id];
 
   float2 origin = float2(shadow.bounds.origin.x, shadow.bounds.origin.y);
@@ -538,9 +569,148 @@ fragment float4 shadow_fragment(ShadowFragmentInput input [[stage_in]],
     }
   }
 
+  if (shadow.inset != 0) {
+    // Inset shadows are cast by everything outside of the shadow's rounded
+    // rect, clipped to the inside of the element's.
+    float clip_distance = quad_sdf(input.position.xy, shadow.clip_bounds,
+                                   shadow.clip_corner_radii);
+    alpha = (1. - alpha) * saturate(0.5 - clip_distance);
+  }
+  alpha *= clip_region_alpha(shadow.clip_region, input.position.xy,
+                             clip_regions, clip_region_vertices);
+
   return input.color * float4(1., 1., 1., alpha);
 }
 
+struct BackdropVertexOutput {
+  float4 position [[position]];
+  uint backdrop_id [[flat]];
+  float clip_distance [[clip_distance]][4];
+};
+
+struct BackdropFragmentInput {
+  float4 position [[position]];
+  uint backdrop_id [[flat]];
+};
+
+// The horizontal pass covers the visible part of the backdrop, extended
+// vertically by the reach of the blur so that the vertical pass has every row
+// it samples.
+vertex BackdropVertexOutput backdrop_horizontal_vertex(
+    uint unit_vertex_id [[vertex_id]], uint backdrop_id [[instance_id]],
+    constant float2 *unit_vertices [[buffer(BackdropInputIndex_Vertices)]],
+    constant Backdrop *backdrops [[buffer(BackdropInputIndex_Backdrops)]],
+    constant Size_DevicePixels *viewport_size
+    [[buffer(BackdropInputIndex_ViewportSize)]]) {
+  float2 unit_vertex = unit_vertices[unit_vertex_id];
+  Backdrop backdrop = backdrops[backdrop_id];
+
+  float margin = 3. * backdrop.blur_radius;
+  float2 top_left = max(
+      float2(backdrop.bounds.origin.x, backdrop.bounds.origin.y),
+      float2(backdrop.content_mask.bounds.origin.x,
+             backdrop.content_mask.bounds.origin.y));
+  float2 bottom_right = min(
+      float2(backdrop.bounds.origin.x + backdrop.bounds.size.width,
+             backdrop.bounds.origin.y + backdrop.bounds.size.height),
+      float2(backdrop.content_mask.bounds.origin.x +
+                 backdrop.content_mask.bounds.size.width,
+             backdrop.content_mask.bounds.origin.y +
+                 backdrop.content_mask.bounds.size.height));
+  Bounds_ScaledPixels bounds = backdrop.bounds;
+  bounds.origin.x = top_left.x;
+  bounds.origin.y = top_left.y - margin;
+  bounds.size.width = max(bottom_right.x - top_left.x, 0.);
+  bounds.size.height = max(bottom_right.y - top_left.y, 0.) + 2. * margin;
+
+  float4 device_position =
+      to_device_position(unit_vertex, bounds, viewport_size);
+  return BackdropVertexOutput{device_position, backdrop_id, {0., 0., 0., 0.}};
+}
+
+fragment float4 backdrop_horizontal_fragment(
+    BackdropFragmentInput input [[stage_in]],
+    constant Backdrop *backdrops [[buffer(BackdropInputIndex_Backdrops)]],
+    texture2d<float> texture [[texture(BackdropInputIndex_Texture)]]) {
+  Backdrop backdrop = backdrops[input.backdrop_id];
+  return blur_backdrop(texture, input.position.xy, float2(1., 0.),
+                       backdrop.blur_radius);
+}
+
+vertex BackdropVertexOutput backdrop_vertical_vertex(
+    uint unit_vertex_id [[vertex_id]], uint backdrop_id [[instance_id]],
+    constant float2 *unit_vertices [[buffer(BackdropInputIndex_Vertices)]],
+    constant Backdrop *backdrops [[buffer(BackdropInputIndex_Backdrops)]],
+    constant Size_DevicePixels *viewport_size
+    [[buffer(BackdropInputIndex_ViewportSize)]]) {
+  float2 unit_vertex = unit_vertices[unit_vertex_id];
+  Backdrop backdrop = backdrops[backdrop_id];
+
+  float4 device_position =
+      to_device_position(unit_vertex, backdrop.bounds, viewport_size);
+  float4 clip_distance = distance_from_clip_rect(
+      unit_vertex, backdrop.bounds, backdrop.content_mask.bounds);
+  return BackdropVertexOutput{
+      device_position,
+      backdrop_id,
+      {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
+}
+
+fragment float4 backdrop_vertical_fragment(
+    BackdropFragmentInput input [[stage_in]],
+    constant Backdrop *backdrops [[buffer(BackdropInputIndex_Backdrops)]],
+    texture2d<float> texture [[texture(BackdropInputIndex_Texture)]],
+    constant ClipRegion *clip_regions [[buffer(ClipRegionInputIndex_Regions)]],
+    constant float2 *clip_region_vertices
+    [[buffer(ClipRegionInputIndex_Vertices)]]) {
+  Backdrop backdrop = backdrops[input.backdrop_id];
+  float distance =
+      quad_sdf(input.position.xy, backdrop.bounds, backdrop.corner_radii);
+  float coverage = saturate(0.5 - distance) *
+                   clip_region_alpha(backdrop.clip_region, input.position.xy,
+                                     clip_regions, clip_region_vertices);
+  float4 color = blur_backdrop(texture, input.position.xy, float2(0., 1.),
+                               backdrop.blur_radius);
+  return color * coverage;
+}
+
+struct OverlayVertexOutput {
+  float4 position [[position]];
+};
+
+vertex OverlayVertexOutput overlay_vertex(
+    uint unit_vertex_id [[vertex_id]],
+    constant float2 *unit_vertices [[buffer(OverlayInputIndex_Vertices)]],
+    constant Bounds_ScaledPixels *bounds [[buffer(OverlayInputIndex_Bounds)]],
+    constant Size_DevicePixels *viewport_size
+    [[buffer(OverlayInputIndex_ViewportSize)]]) {
+  float2 unit_vertex = unit_vertices[unit_vertex_id];
+  float4 device_position =
+      to_device_position(unit_vertex, *bounds, viewport_size);
+  return OverlayVertexOutput{device_position};
+}
+
+// Composites a layer painted with premultiplied colors over a copy of what's
+// behind it, outputting a straight color for the normal blend state.
+fragment float4 overlay_fragment(
+    OverlayVertexOutput input [[stage_in]],
+    texture2d<float> backdrop_texture
+    [[texture(OverlayInputIndex_BackdropTexture)]],
+    texture2d<float> layer_texture [[texture(OverlayInputIndex_LayerTexture)]]) {
+  uint2 position = uint2(input.position.xy);
+  float4 layer = layer_texture.read(position);
+  if (layer.a <= 0.) {
+    return float4(0.);
+  }
+  float4 backdrop = backdrop_texture.read(position);
+  float3 source = saturate(layer.rgb / layer.a);
+  float3 behind =
+      backdrop.a > 0. ? saturate(backdrop.rgb / backdrop.a) : float3(0.);
+  float3 overlay = select(1. - 2. * (1. - source) * (1. - behind),
+                          2. * source * behind, behind <= 0.5);
+  return float4(mix(source, overlay, backdrop.a), layer.a);
+}
+
 struct UnderlineVertexOutput {
   float4 position [[position]];
   float4 color [[flat]];
@@ -576,12 +746,17 @@ vertex UnderlineVertexOutput underline_vertex(
 
 fragment float4 underline_fragment(UnderlineFragmentInput input [[stage_in]],
                                    constant Underline *underlines
-                                   [[buffer(UnderlineInputIndex_Underlines)]]) {
+                                   [[buffer(UnderlineInputIndex_Underlines)]],
+    constant ClipRegion *clip_regions [[buffer(ClipRegionInputIndex_Regions)]],
+    constant float2 *clip_region_vertices
+    [[buffer(ClipRegionInputIndex_Vertices)]]) {
   const float WAVE_FREQUENCY = 2.0;
   const float WAVE_HEIGHT_RATIO = 0.8;
 
   Underline underline = underlines[input.underline_id];
-  if (underline.wavy) {
+  float clip_alpha = clip_region_alpha(underline.clip_region, input.position.xy,
+                                       clip_regions, clip_region_vertices);
+  if (underline.style == 1) {
     float half_thickness = underline.thickness * 0.5;
     float2 origin =
         float2(underline.bounds.origin.x, underline.bounds.origin.y);
@@ -599,15 +774,25 @@ fragment float4 underline_fragment(UnderlineFragmentInput input [[stage_in]],
     float distance_from_bottom_border = distance_in_pixels + half_thickness;
     float alpha = saturate(
         0.5 - max(-distance_from_bottom_border, distance_from_top_border));
-    return input.color * float4(1., 1., 1., alpha);
+    return input.color * float4(1., 1., 1., alpha * clip_alpha);
+  } else if (underline.style == 2 || underline.style == 3) {
+    // Dotted lines alternate square dots with equal gaps, dashed lines alternate
+    // dashes three times as long as the line is thick with gaps twice as long.
+    bool dotted = underline.style == 2;
+    float dash_length = dotted ? underline.thickness : underline.thickness * 3.;
+    float gap_length = dotted ? underline.thickness : underline.thickness * 2.;
+    float x = input.position.x - underline.bounds.origin.x;
+    float alpha = fmod(x, dash_length + gap_length) < dash_length ? 1. : 0.;
+    return input.color * float4(1., 1., 1., alpha * clip_alpha);
   } else {
-    return input.color;
+    return input.color * float4(1., 1., 1., clip_alpha);
   }
 }
 
 struct MonochromeSpriteVertexOutput {
   float4 position [[position]];
   float2 tile_position;
+  uint sprite_id [[flat]];
   float4 color [[flat]];
   float4 clip_distance;
 };
@@ -615,6 +800,7 @@ struct MonochromeSpriteVertexOutput {
 struct MonochromeSpriteFragmentInput {
   float4 position [[position]];
   float2 tile_position;
+  uint sprite_id [[flat]];
   float4 color [[flat]];
   float4 clip_distance;
 };
@@ -638,6 +824,7 @@ vertex MonochromeSpriteVertexOutput monochrome_sprite_vertex(
   return MonochromeSpriteVertexOutput{
       device_position,
       tile_position,
+      sprite_id,
       color,
       {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
 }
@@ -645,7 +832,10 @@ vertex MonochromeSpriteVertexOutput monochrome_sprite_vertex(
 fragment float4 monochrome_sprite_fragment(
     MonochromeSpriteFragmentInput input [[stage_in]],
     constant MonochromeSprite *sprites [[buffer(SpriteInputIndex_Sprites)]],
-    texture2d<float> atlas_texture [[texture(SpriteInputIndex_AtlasTexture)]]) {
+    texture2d<float> atlas_texture [[texture(SpriteInputIndex_AtlasTexture)]],
+    constant ClipRegion *clip_regions [[buffer(ClipRegionInputIndex_Regions)]],
+    constant float2 *clip_region_vertices
+    [[buffer(ClipRegionInputIndex_Vertices)]]) {
   if (any(input.clip_distance < float4(0.0))) {
     return float4(0.0);
   }
@@ -655,13 +845,98 @@ fragment float4 monochrome_sprite_fragment(
   float4 sample =
       atlas_texture.sample(atlas_texture_sampler, input.tile_position);
   float4 color = input.color;
-  color.a *= sample.a;
-  return color;
+  MonochromeSprite sprite = sprites[input.sprite_id];
+  color.a *= sample.a * clip_region_alpha(sprite.clip_region, input.position.xy,
+                                         clip_regions, clip_region_vertices);
+  return blend_mode_color(sprite.blend_mode, color);
+}
+
+struct GradientSpriteVertexOutput {
+  float4 position [[position]];
+  float2 tile_position;
+  float2 sprite_position;
+  uint sprite_id [[flat]];
+  float4 color [[flat]];
+  float4 color0 [[flat]];
+  float4 color1 [[flat]];
+  float4 clip_distance;
+};
+
+struct GradientSpriteFragmentInput {
+  float4 position [[position]];
+  float2 tile_position;
+  float2 sprite_position;
+  uint sprite_id [[flat]];
+  float4 color [[flat]];
+  float4 color0 [[flat]];
+  float4 color1 [[flat]];
+  float4 clip_distance;
+};
+
+vertex GradientSpriteVertexOutput gradient_sprite_vertex(
+    uint unit_vertex_id [[vertex_id]], uint sprite_id [[instance_id]],
+    constant float2 *unit_vertices [[buffer(SpriteInputIndex_Vertices)]],
+    constant GradientSprite *sprites [[buffer(SpriteInputIndex_Sprites)]],
+    constant Size_DevicePixels *viewport_size
+    [[buffer(SpriteInputIndex_ViewportSize)]],
+    constant Size_DevicePixels *atlas_size
+    [[buffer(SpriteInputIndex_AtlasTextureSize)]]) {
+  float2 unit_vertex = unit_vertices[unit_vertex_id];
+  GradientSprite sprite = sprites[sprite_id];
+  float4 device_position =
+      to_device_position_transformed(unit_vertex, sprite.bounds, sprite.transformation, viewport_size);
+  float4 clip_distance = distance_from_clip_rect_transformed(unit_vertex, sprite.bounds,
+                                                 sprite.content_mask.bounds, sprite.transformation);
+  float2 tile_position = to_tile_position(unit_vertex, sprite.tile, atlas_size);
+  // Gradients are laid out across the untransformed bounds, so they follow the transformation.
+  float2 sprite_position =
+      unit_vertex * float2(sprite.bounds.size.width, sprite.bounds.size.height) +
+      float2(sprite.bounds.origin.x, sprite.bounds.origin.y);
+  GradientColor gradient = prepare_fill_color(
+    sprite.color.tag,
+    sprite.color.color_space,
+    sprite.color.solid,
+    sprite.color.colors[0].color,
+    sprite.color.colors[1].color
+  );
+  return GradientSpriteVertexOutput{
+      device_position,
+      tile_position,
+      sprite_position,
+      sprite_id,
+      gradient.solid,
+      gradient.color0,
+      gradient.color1,
+      {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
+}
+
+fragment float4 gradient_sprite_fragment(
+    GradientSpriteFragmentInput input [[stage_in]],
+    constant GradientSprite *sprites [[buffer(SpriteInputIndex_Sprites)]],
+    texture2d<float> atlas_texture [[texture(SpriteInputIndex_AtlasTexture)]],
+    constant ClipRegion *clip_regions [[buffer(ClipRegionInputIndex_Regions)]],
+    constant float2 *clip_region_vertices
+    [[buffer(ClipRegionInputIndex_Vertices)]]) {
+  if (any(input.clip_distance < float4(0.0))) {
+    return float4(0.0);
+  }
+
+  constexpr sampler atlas_texture_sampler(mag_filter::linear,
+                                          min_filter::linear);
+  float4 sample =
+      atlas_texture.sample(atlas_texture_sampler, input.tile_position);
+  GradientSprite sprite = sprites[input.sprite_id];
+  float4 color = fill_color(sprite.color, input.sprite_position, sprite.bounds,
+                            input.color, input.color0, input.color1);
+  color.a *= sample.a * clip_region_alpha(sprite.clip_region, input.position.xy,
+                                         clip_regions, clip_region_vertices);
+  return blend_mode_color(sprite.blend_mode, color);
 }
 
 struct PolychromeSpriteVertexOutput {
   float4 position [[position]];
   float2 tile_position;
+  float2 sprite_position;
   uint sprite_id [[flat]];
   float clip_distance [[clip_distance]][4];
 };
@@ -669,6 +944,7 @@ struct PolychromeSpriteVertexOutput {
 struct PolychromeSpriteFragmentInput {
   float4 position [[position]];
   float2 tile_position;
+  float2 sprite_position;
   uint sprite_id [[flat]];
 };
 
@@ -684,13 +960,18 @@ vertex PolychromeSpriteVertexOutput polychrome_sprite_vertex(
   float2 unit_vertex = unit_vertices[unit_vertex_id];
   PolychromeSprite sprite = sprites[sprite_id];
   float4 device_position =
-      to_device_position(unit_vertex, sprite.bounds, viewport_size);
-  float4 clip_distance = distance_from_clip_rect(unit_vertex, sprite.bounds,
-                                                 sprite.content_mask.bounds);
+      to_device_position_transformed(unit_vertex, sprite.bounds, sprite.transformation, viewport_size);
+  float4 clip_distance = distance_from_clip_rect_transformed(unit_vertex, sprite.bounds,
+                                                 sprite.content_mask.bounds, sprite.transformation);
   float2 tile_position = to_tile_position(unit_vertex, sprite.tile, atlas_size);
+  // Corners are rounded in the untransformed bounds, so they follow the transformation.
+  float2 sprite_position =
+      unit_vertex * float2(sprite.bounds.size.width, sprite.bounds.size.height) +
+      float2(sprite.bounds.origin.x, sprite.bounds.origin.y);
   return PolychromeSpriteVertexOutput{
       device_position,
       tile_position,
+      sprite_position,
       sprite_id,
       {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
 }
@@ -698,14 +979,17 @@ vertex PolychromeSpriteVertexOutput polychrome_sprite_vertex(
 fragment float4 polychrome_sprite_fragment(
     PolychromeSpriteFragmentInput input [[stage_in]],
     constant PolychromeSprite *sprites [[buffer(SpriteInputIndex_Sprites)]],
-    texture2d<float> atlas_texture [[texture(SpriteInputIndex_AtlasTexture)]]) {
+    texture2d<float> atlas_texture [[texture(SpriteInputIndex_AtlasTexture)]],
+    constant ClipRegion *clip_regions [[buffer(ClipRegionInputIndex_Regions)]],
+    constant float2 *clip_region_vertices
+    [[buffer(ClipRegionInputIndex_Vertices)]]) {
   PolychromeSprite sprite = sprites[input.sprite_id];
   constexpr sampler atlas_texture_sampler(mag_filter::linear,
                                           min_filter::linear);
   float4 sample =
       atlas_texture.sample(atlas_texture_sampler, input.tile_position);
   float distance =
-      quad_sdf(input.position.xy, sprite.bounds, sprite.corner_radii);
+      quad_sdf(input.sprite_position, sprite.bounds, sprite.corner_radii);
 
   float4 color = sample;
   if (sprite.grayscale) {
@@ -714,8 +998,10 @@ fragment float4 polychrome_sprite_fragment(
     color.g = grayscale;
     color.b = grayscale;
   }
-  color.a *= sprite.opacity * saturate(0.5 - distance);
-  return color;
+  color.a *= sprite.opacity * saturate(0.5 - distance) *
+             clip_region_alpha(sprite.clip_region, input.position.xy,
+                               clip_regions, clip_region_vertices);
+  return blend_mode_color(sprite.blend_mode, color);
 }
 
 struct PathRasterizationVertexOutput {
@@ -758,7 +1044,9 @@ vertex PathRasterizationVertexOutput path_rasterization_vertex(
 
 fragment float4 path_rasterization_fragment(
   PathRasterizationFragmentInput input [[stage_in]],
-  constant PathRasterizationVertex *vertices [[buffer(PathRasterizationInputIndex_Vertices)]]
+  constant PathRasterizationVertex *vertices [[buffer(PathRasterizationInputIndex_Vertices)]],
+  constant ClipRegion *clip_regions [[buffer(ClipRegionInputIndex_Regions)]],
+  constant float2 *clip_region_vertices [[buffer(ClipRegionInputIndex_Vertices)]]
 ) {
   float2 dx = dfdx(input.st_position);
   float2 dy = dfdy(input.st_position);
@@ -778,6 +1066,8 @@ fragment float4 path_rasterization_fragment(
     float distance = f / length(gradient);
     alpha = saturate(0.5 - distance);
   }
+  alpha *= clip_region_alpha(v.clip_region, input.position.xy, clip_regions,
+                             clip_region_vertices);
 
   GradientColor gradient_color = prepare_fill_color(
     background.tag,
@@ -886,6 +1176,28 @@ fragment float4 surface_fragment(SurfaceFragmentInput input [[stage_in]],
   return ycbcrToRGBTransform * ycbcr;
 }
 
+// Blurs the texture along one direction around the given position, using a
+// gaussian with the given sigma. Samples are spread out for large radii to
+// bound their count.
+float4 blur_backdrop(texture2d<float> texture, float2 position, float2 direction,
+                     float sigma) {
+  constexpr sampler texture_sampler(mag_filter::linear, min_filter::linear,
+                                    address::clamp_to_edge);
+  float2 texture_size = float2(texture.get_width(), texture.get_height());
+  float reach = ceil(3. * sigma);
+  float step = max(1., reach / 16.);
+  float4 color = float4(0.);
+  float total_weight = 0.;
+  for (float offset = -reach; offset <= reach; offset += step) {
+    float weight = gaussian(offset, sigma);
+    color += weight * texture.sample(texture_sampler,
+                                     (position + direction * offset) /
+                                         texture_size);
+    total_weight += weight;
+  }
+  return color / total_weight;
+}
+
 float4 hsla_to_rgba(Hsla hsla) {
   float h = hsla.h * 6.0; // Now, it's an angle but scaled in [0, 6) range
   float s = hsla.s;
@@ -1045,6 +1357,27 @@ float pick_corner_radius(float2 center_to_point, Corners_ScaledPixels corner_rad
   }
 }
 
+// Returns the color of the border nearest to the point. Where two sides meet at
+// a corner, they are split along the diagonal from the outer corner to the
+// inner corner, like in CSS.
+float4 pick_border_color(float2 center_to_point, float2 corner_to_point,
+                         float2 border, Edges_Hsla colors,
+                         float antialias_threshold) {
+  float4 horizontal =
+      hsla_to_rgba(center_to_point.y < 0.0 ? colors.top : colors.bottom);
+  float miter_length = length(border);
+  if (miter_length == 0.0) {
+    return horizontal;
+  }
+  float4 vertical =
+      hsla_to_rgba(center_to_point.x < 0.0 ? colors.left : colors.right);
+  // Signed distance of the point to the diagonal, positive on the side of the
+  // horizontal border.
+  float2 inset = -corner_to_point;
+  float miter_sdf = (inset.x * border.y - inset.y * border.x) / miter_length;
+  return mix(vertical, horizontal, saturate(miter_sdf + antialias_threshold));
+}
+
 // Signed distance of the point to the quad's border - positive outside the
 // border, and negative inside.
 float quad_sdf(float2 point, Bounds_ScaledPixels bounds,
@@ -1076,6 +1409,77 @@ float quad_sdf_impl(float2 corner_center_to_point, float corner_radius) {
     }
 }
 
+// Approximate signed distance of the point to the ellipse inscribed in the
+// bounds.
+float ellipse_sdf(float2 point, Bounds_ScaledPixels bounds) {
+    float2 radii = float2(bounds.size.width, bounds.size.height) / 2.0;
+    if (radii.x <= 0.0 || radii.y <= 0.0) {
+        return 1.0;
+    }
+    float2 center_to_point =
+        point - (float2(bounds.origin.x, bounds.origin.y) + radii);
+    float k1 = length(center_to_point / radii);
+    float k2 = length(center_to_point / (radii * radii));
+    if (k2 == 0.0) {
+        return -min(radii.x, radii.y);
+    }
+    return k1 * (k1 - 1.0) / k2;
+}
+
+// Signed distance of the point to the polygon, which is negative inside of it
+// according to the even-odd rule.
+float polygon_sdf(float2 point, constant float2 *vertices, uint vertex_count) {
+    if (vertex_count < 3) {
+        return 1.0;
+    }
+    float2 previous = vertices[vertex_count - 1];
+    float distance_squared = 1e30;
+    float side = 1.0;
+    for (uint i = 0; i < vertex_count; i++) {
+        float2 vertex = vertices[i];
+        float2 edge = previous - vertex;
+        float2 vertex_to_point = point - vertex;
+        float t = saturate(dot(vertex_to_point, edge) / max(dot(edge, edge), 1e-6));
+        float2 edge_to_point = vertex_to_point - edge * t;
+        distance_squared = min(distance_squared, dot(edge_to_point, edge_to_point));
+        // Each edge crossed by a ray going right from the point toggles the side
+        if ((vertex.y > point.y) != (previous.y > point.y)) {
+            float crossing_x =
+                vertex.x + (point.y - vertex.y) / (previous.y - vertex.y) * edge.x;
+            if (point.x < crossing_x) {
+                side = -side;
+            }
+        }
+        previous = vertex;
+    }
+    return side * sqrt(distance_squared);
+}
+
+// The coverage of the point by the clip region with the given id and the
+// regions enclosing it. An id of 0 means that there is no clip region.
+float clip_region_alpha(uint clip_region, float2 point,
+                        constant ClipRegion *clip_regions,
+                        constant float2 *clip_region_vertices) {
+    float alpha = 1.0;
+    uint id = clip_region;
+    while (id != 0 && alpha > 0.0) {
+        ClipRegion region = clip_regions[id - 1];
+        float distance;
+        if (region.kind == ClipRegionKind_Ellipse) {
+            distance = ellipse_sdf(point, region.bounds);
+        } else if (region.kind == ClipRegionKind_Polygon) {
+            distance = polygon_sdf(point,
+                                   clip_region_vertices + region.vertices_start,
+                                   region.vertex_count);
+        } else {
+            distance = quad_sdf(point, region.bounds, region.corner_radii);
+        }
+        alpha *= saturate(0.5 - distance);
+        id = region.parent;
+    }
+    return alpha;
+}
+
 // A standard gaussian function, used for weighting samples
 float gaussian(float x, float sigma) {
   return exp(-(x * x) / (2. * sigma * sigma)) / (sqrt(2. * M_PI_F) * sigma);
@@ -1137,12 +1541,21 @@ float4 over(float4 below, float4 above) {
   return result;
 }
 
+// Multiply and screen are drawn with blend states that expect premultiplied
+// colors, while the other modes are drawn with straight alpha.
+float4 blend_mode_color(BlendMode blend_mode, float4 color) {
+  if (blend_mode == BlendMode_Multiply || blend_mode == BlendMode_Screen) {
+    return float4(color.rgb * color.a, color.a);
+  }
+  return color;
+}
+
 GradientColor prepare_fill_color(uint tag, uint color_space, Hsla solid,
                                      Hsla color0, Hsla color1) {
   GradientColor out;
   if (tag == 0 || tag == 2) {
     out.solid = hsla_to_rgba(solid);
-  } else if (tag == 1) {
+  } else {
     out.color0 = hsla_to_rgba(color0);
     out.color1 = hsla_to_rgba(color1);
 
@@ -1158,6 +1571,21 @@ GradientColor prepare_fill_color(uint tag, uint color_space, Hsla solid,
   return out;
 }
 
+// Returns the color at `t` along a gradient, adjusted by the stop percentages.
+float4 gradient_stops_color(Background background, float4 color0, float4 color1,
+                            float t) {
+  t = (t - background.colors[0].percentage)
+    / (background.colors[1].percentage
+    - background.colors[0].percentage);
+  t = clamp(t, 0.0, 1.0);
+
+  float4 color = mix(color0, color1, t);
+  if (background.color_space == 1) {
+    color = oklab_to_srgb(color);
+  }
+  return color;
+}
+
 float2x2 rotate2d(float angle) {
     float s = sin(angle);
     float c = cos(angle);
@@ -1199,22 +1627,32 @@ float4 fill_color(Background background,
           t = (t + half_size.y) / bounds.size.height;
       }
 
-      // Adjust t based on the stop percentages
-      t = (t - background.colors[0].percentage)
-        / (background.colors[1].percentage
-        - background.colors[0].percentage);
-      t = clamp(t, 0.0, 1.0);
-
-      switch (background.color_space) {
-        case 0:
-          color = mix(color0, color1, t);
-          break;
-        case 1: {
-          float4 oklab_color = mix(color0, color1, t);
-          color = oklab_to_srgb(oklab_color);
-          break;
-        }
-      }
+      color = gradient_stops_color(background, color0, color1, t);
+      break;
+    }
+    case 3: {
+      // Radial gradient, where a radius of 1 reaches the farthest corner.
+      float2 origin = float2(bounds.origin.x, bounds.origin.y);
+      float2 size = float2(bounds.size.width, bounds.size.height);
+      float2 center = origin + float2(background.gradient_center_x,
+                                      background.gradient_center_y) * size;
+      float2 farthest_corner =
+          max(abs(center - origin), abs(origin + size - center));
+      float t = distance(position, center) /
+                (background.gradient_radius * length(farthest_corner));
+      color = gradient_stops_color(background, color0, color1, t);
+      break;
+    }
+    case 4: {
+      // Conic gradient, where 0 degrees is up and angles increase clockwise.
+      float2 origin = float2(bounds.origin.x, bounds.origin.y);
+      float2 size = float2(bounds.size.width, bounds.size.height);
+      float2 center = origin + float2(background.gradient_center_x,
+                                      background.gradient_center_y) * size;
+      float2 center_to_point = position - center;
+      float angle = atan2(center_to_point.x, -center_to_point.y) * (180.0 / M_PI_F);
+      float t = fract((angle - background.gradient_angle_or_pattern_height) / 360.0);
+      color = gradient_stops_color(background, color0, color1, t);
       break;
     }
     case 2: {
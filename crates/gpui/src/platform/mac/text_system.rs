@@ -1,6 +1,6 @@
 use crate::{
     Bounds, DevicePixels, Font, FontFallbacks, FontFeatures, FontId, FontMetrics, FontRun,
-    FontStyle, FontWeight, GlyphId, LineLayout, Pixels, PlatformTextSystem, Point,
+    FontStyle, FontVariations, FontWeight, GlyphId, LineLayout, Pixels, PlatformTextSystem, Point,
     RenderGlyphParams, Result, SUBPIXEL_VARIANTS_X, ShapedGlyph, ShapedRun, SharedString, Size,
     point, px, size, swap_rgba_pa_to_bgra,
 };
@@ -45,7 +45,7 @@ use pathfinder_geometry::{
 use smallvec::SmallVec;
 use std::{borrow::Cow, char, convert::TryFrom, sync::Arc};
 
-use super::open_type::apply_features_and_fallbacks;
+use super::open_type::apply_features_variations_and_fallbacks;
 
 #[allow(non_upper_case_globals)]
 const kCGImageAlphaOnly: u32 = 7;
@@ -56,6 +56,7 @@ pub(crate) struct MacTextSystem(RwLock<MacTextSystemState>);
 struct FontKey {
     font_family: SharedString,
     font_features: FontFeatures,
+    font_variations: FontVariations,
     font_fallbacks: Option<FontFallbacks>,
 }
 
@@ -118,13 +119,18 @@ impl PlatformTextSystem for MacTextSystem {
             let font_key = FontKey {
                 font_family: font.family.clone(),
                 font_features: font.features.clone(),
+                font_variations: font.variations.clone(),
                 font_fallbacks: font.fallbacks.clone(),
             };
             let candidates = if let Some(font_ids) = lock.font_ids_by_font_key.get(&font_key) {
                 font_ids.as_slice()
             } else {
-                let font_ids =
-                    lock.load_family(&font.family, &font.features, font.fallbacks.as_ref())?;
+                let font_ids = lock.load_family(
+                    &font.family,
+                    &font.features,
+                    &font.variations,
+                    font.fallbacks.as_ref(),
+                )?;
                 lock.font_ids_by_font_key.insert(font_key.clone(), font_ids);
                 lock.font_ids_by_font_key[&font_key].as_ref()
             };
@@ -209,6 +215,7 @@ impl MacTextSystemState {
         &mut self,
         name: &str,
         features: &FontFeatures,
+        variations: &FontVariations,
         fallbacks: Option<&FontFallbacks>,
     ) -> Result<SmallVec<[FontId; 4]>> {
         let name = crate::text_system::font_name_with_fallbacks(name, ".AppleSystemUIFont");
@@ -221,7 +228,7 @@ impl MacTextSystemState {
         for font in family.fonts() {
             let mut font = font.load()?;
 
-            apply_features_and_fallbacks(&mut font, features, fallbacks)?;
+            apply_features_variations_and_fallbacks(&mut font, features, variations, fallbacks)?;
             // This block contains a precautionary fix to guard against loading fonts
             // that might cause panics due to `.unwrap()`s up the chain.
             {
@@ -522,6 +529,7 @@ impl MacTextSystemState {
             ascent: max_ascent.into(),
             descent: max_descent.into(),
             len: text.len(),
+            ..Default::default()
         }
     }
 }
@@ -565,6 +573,10 @@ impl From<Metrics> for FontMetrics {
             line_gap: metrics.line_gap,
             underline_position: metrics.underline_position,
             underline_thickness: metrics.underline_thickness,
+            // Core Text doesn't expose the font's strikeout metrics, so center the strikethrough
+            // on lowercase letters.
+            strikethrough_position: (metrics.x_height + metrics.underline_thickness) / 2.,
+            strikethrough_thickness: metrics.underline_thickness,
             cap_height: metrics.cap_height,
             x_height: metrics.x_height,
             bounding_box: metrics.bounding_box.into(),
@@ -1,12 +1,13 @@
 use super::{BoolExt, MacDisplay, NSRange, NSStringExt, ns_string, renderer};
 use crate::{
-    AnyWindowHandle, Bounds, Capslock, DisplayLink, ExternalPaths, FileDropEvent,
-    ForegroundExecutor, KeyDownEvent, Keystroke, Modifiers, ModifiersChangedEvent, MouseButton,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, PlatformAtlas, PlatformDisplay,
-    PlatformInput, PlatformWindow, Point, PromptButton, PromptLevel, RequestFrameOptions,
-    SharedString, Size, SystemWindowTab, Timer, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControlArea, WindowKind, WindowParams, dispatch_get_main_queue,
-    dispatch_sys::dispatch_async_f, platform::PlatformInputHandler, point, px, size,
+    AnyWindowHandle, Bounds, Capslock, DevicePixels, DisplayLink, DragImage, ExternalPaths,
+    FileDropEvent, ForegroundExecutor, KeyDownEvent, Keystroke, Modifiers, ModifiersChangedEvent,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, PlatformAtlas,
+    PlatformDisplay, PlatformInput, PlatformWindow, Point, PromptButton, PromptLevel,
+    RequestFrameOptions, SharedString, Size, SystemWindowTab, Timer, WindowAppearance,
+    WindowBackgroundAppearance, WindowBounds, WindowControlArea, WindowKind, WindowParams,
+    dispatch_get_main_queue, dispatch_sys::dispatch_async_f, platform::PlatformInputHandler, point,
+    px, size,
 };
 use block::ConcreteBlock;
 use cocoa::{
@@ -29,6 +30,7 @@ use cocoa::{
 use core_graphics::display::{CGDirectDisplayID, CGPoint, CGRect};
 use ctor::ctor;
 use futures::channel::oneshot;
+use image::RgbaImage;
 use objc::{
     class,
     declare::ClassDecl,
@@ -84,6 +86,11 @@ type NSDragOperation = NSUInteger;
 const NSDragOperationNone: NSDragOperation = 0;
 #[allow(non_upper_case_globals)]
 const NSDragOperationCopy: NSDragOperation = 1;
+// The size of the file icons shown while dragging files out of a window, when the dragged element
+// couldn't be snapshotted.
+const DRAG_OUT_ICON_SIZE: f64 = 48.;
+// NSBitmapFormatAlphaNonpremultiplied
+const NS_BITMAP_FORMAT_ALPHA_NONPREMULTIPLIED: NSUInteger = 1 << 1;
 #[derive(PartialEq)]
 pub enum UserTabbingPreference {
     Never,
@@ -169,6 +176,14 @@ unsafe fn build_classes() {
                     sel!(swipeWithEvent:),
                     handle_view_event as extern "C" fn(&Object, Sel, id),
                 );
+                decl.add_method(
+                    sel!(magnifyWithEvent:),
+                    handle_view_event as extern "C" fn(&Object, Sel, id),
+                );
+                decl.add_method(
+                    sel!(rotateWithEvent:),
+                    handle_view_event as extern "C" fn(&Object, Sel, id),
+                );
                 decl.add_method(
                     sel!(flagsChanged:),
                     handle_view_event as extern "C" fn(&Object, Sel, id),
@@ -249,6 +264,13 @@ unsafe fn build_classes() {
                     sel!(characterIndexForPoint:),
                     character_index_for_point as extern "C" fn(&Object, Sel, NSPoint) -> u64,
                 );
+
+                decl.add_method(
+                    sel!(draggingSession:sourceOperationMaskForDraggingContext:),
+                    dragging_source_operation_mask
+                        as extern "C" fn(&Object, Sel, id, NSInteger) -> NSDragOperation,
+                );
+                decl.add_protocol(Protocol::get("NSDraggingSource").unwrap());
             }
             decl.register()
         };
@@ -1478,6 +1500,96 @@ impl PlatformWindow for MacWindow {
         self.0.lock().renderer.sprite_atlas().clone()
     }
 
+    fn render_to_image(
+        &self,
+        scene: &crate::Scene,
+        bounds: Bounds<DevicePixels>,
+    ) -> Option<RgbaImage> {
+        self.0
+            .lock()
+            .renderer
+            .render_to_image(scene, bounds)
+            .log_err()
+    }
+
+    fn start_drag_out(&self, paths: &[PathBuf], image: Option<DragImage>) -> bool {
+        if paths.is_empty() {
+            return false;
+        }
+        let (native_view, scale_factor) = {
+            let lock = self.0.lock();
+            (lock.native_view.as_ptr(), lock.scale_factor() as f64)
+        };
+        unsafe {
+            // AppKit needs the mouse event that started the drag, which is the one being handled.
+            let app = NSApplication::sharedApplication(nil);
+            let event: id = msg_send![app, currentEvent];
+            if event == nil {
+                return false;
+            }
+            let location_in_window: NSPoint = msg_send![event, locationInWindow];
+            let location: NSPoint =
+                msg_send![native_view, convertPoint: location_in_window fromView: nil];
+
+            // The snapshot of the element stays where it was under the pointer. If the frame
+            // couldn't be read back, the file icons stand in for it.
+            let snapshot = image.map(|image| {
+                let size = NSSize::new(
+                    image.image.width() as f64 / scale_factor,
+                    image.image.height() as f64 / scale_factor,
+                );
+                let origin = NSPoint::new(
+                    location.x - image.cursor_offset.x.0 as f64 / scale_factor,
+                    location.y + image.cursor_offset.y.0 as f64 / scale_factor - size.height,
+                );
+                (
+                    ns_image_from_rgba(&image.image, size),
+                    NSRect::new(origin, size),
+                )
+            });
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let items: id = msg_send![class!(NSMutableArray), array];
+            for (ix, path) in paths.iter().enumerate() {
+                let path = ns_string(&path.to_string_lossy());
+                let url: id = msg_send![class!(NSURL), fileURLWithPath: path];
+                let item: id = msg_send![class!(NSDraggingItem), alloc];
+                let item: id = msg_send![item, initWithPasteboardWriter: url];
+                // Fan out the items slightly so multiple files read as a stack.
+                let offset = ix.min(4) as f64 * 4.;
+                let (contents, mut frame) = match snapshot {
+                    Some((snapshot, frame)) => (snapshot, frame),
+                    None => {
+                        let icon: id = msg_send![workspace, iconForFile: path];
+                        let frame = NSRect::new(
+                            NSPoint::new(
+                                location.x - DRAG_OUT_ICON_SIZE / 2.,
+                                location.y - DRAG_OUT_ICON_SIZE / 2.,
+                            ),
+                            NSSize::new(DRAG_OUT_ICON_SIZE, DRAG_OUT_ICON_SIZE),
+                        );
+                        (icon, frame)
+                    }
+                };
+                frame.origin.x += offset;
+                frame.origin.y -= offset;
+                let _: () = msg_send![item, setDraggingFrame: frame contents: contents];
+                let _: () = msg_send![items, addObject: item];
+                let _: () = msg_send![item, release];
+            }
+            if let Some((snapshot, _)) = snapshot {
+                let _: () = msg_send![snapshot, release];
+            }
+
+            let session: id = msg_send![
+                native_view,
+                beginDraggingSessionWithItems: items
+                event: event
+                source: native_view
+            ];
+            session != nil
+        }
+    }
+
     fn gpu_specs(&self) -> Option<crate::GpuSpecs> {
         None
     }
@@ -2380,6 +2492,45 @@ fn screen_point_to_gpui_point(this: &Object, position: NSPoint) -> Point<Pixels>
     point(px(window_x as f32), px(window_y as f32))
 }
 
+/// Copies straight alpha RGBA pixels into a retained image of the given size in points.
+unsafe fn ns_image_from_rgba(image: &RgbaImage, size: NSSize) -> id {
+    unsafe {
+        let color_space = ns_string("NSDeviceRGBColorSpace");
+        let bitmap: id = msg_send![class!(NSBitmapImageRep), alloc];
+        let bitmap: id = msg_send![
+            bitmap,
+            initWithBitmapDataPlanes: ptr::null_mut::<*mut u8>()
+            pixelsWide: image.width() as NSInteger
+            pixelsHigh: image.height() as NSInteger
+            bitsPerSample: 8 as NSInteger
+            samplesPerPixel: 4 as NSInteger
+            hasAlpha: YES
+            isPlanar: NO
+            colorSpaceName: color_space
+            bitmapFormat: NS_BITMAP_FORMAT_ALPHA_NONPREMULTIPLIED
+            bytesPerRow: image.width() as NSInteger * 4
+            bitsPerPixel: 32 as NSInteger
+        ];
+        let data: *mut u8 = msg_send![bitmap, bitmapData];
+        ptr::copy_nonoverlapping(image.as_raw().as_ptr(), data, image.as_raw().len());
+
+        let ns_image: id = msg_send![class!(NSImage), alloc];
+        let ns_image: id = msg_send![ns_image, initWithSize: size];
+        let _: () = msg_send![ns_image, addRepresentation: bitmap];
+        let _: () = msg_send![bitmap, release];
+        ns_image
+    }
+}
+
+extern "C" fn dragging_source_operation_mask(
+    _: &Object,
+    _: Sel,
+    _session: id,
+    _context: NSInteger,
+) -> NSDragOperation {
+    NSDragOperationCopy
+}
+
 extern "C" fn dragging_entered(this: &Object, _: Sel, dragging_info: id) -> NSDragOperation {
     let window_state = unsafe { get_window_state(this) };
     let position = drag_event_position(&window_state, dragging_info);
@@ -1,13 +1,16 @@
 use crate::{
-    AnyWindowHandle, AtlasKey, AtlasTextureId, AtlasTile, Bounds, DispatchEventResult, GpuSpecs,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, PromptButton, RequestFrameOptions, Size, TestPlatform, TileId, WindowAppearance,
-    WindowBackgroundAppearance, WindowBounds, WindowControlArea, WindowParams,
+    AnyWindowHandle, AtlasKey, AtlasTextureId, AtlasTile, Bounds, DevicePixels,
+    DispatchEventResult, DragImage, GpuSpecs, Pixels, PlatformAtlas, PlatformDisplay,
+    PlatformInput, PlatformInputHandler, PlatformWindow, Point, PromptButton, RequestFrameOptions,
+    Scene, Size, TestPlatform, TileId, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowControlArea, WindowParams,
 };
 use collections::HashMap;
+use image::RgbaImage;
 use parking_lot::Mutex;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use std::{
+    path::PathBuf,
     rc::{Rc, Weak},
     sync::{self, Arc},
 };
@@ -27,8 +30,11 @@ pub(crate) struct TestWindowState {
     hover_status_change_callback: Option<Box<dyn FnMut(bool)>>,
     resize_callback: Option<Box<dyn FnMut(Size<Pixels>, f32)>>,
     moved_callback: Option<Box<dyn FnMut()>>,
+    request_frame_callback: Option<Box<dyn FnMut(RequestFrameOptions)>>,
     input_handler: Option<PlatformInputHandler>,
     is_fullscreen: bool,
+    pub(crate) dragged_out_paths: Option<Vec<PathBuf>>,
+    pub(crate) dragged_out_image: Option<DragImage>,
 }
 
 #[derive(Clone)]
@@ -72,8 +78,11 @@ impl TestWindow {
             hover_status_change_callback: None,
             resize_callback: None,
             moved_callback: None,
+            request_frame_callback: None,
             input_handler: None,
             is_fullscreen: false,
+            dragged_out_paths: None,
+            dragged_out_image: None,
         })))
     }
 
@@ -99,6 +108,16 @@ impl TestWindow {
         self.0.lock().active_status_change_callback = Some(callback);
     }
 
+    pub(crate) fn simulate_request_frame(&self) {
+        let mut lock = self.0.lock();
+        let Some(mut callback) = lock.request_frame_callback.take() else {
+            return;
+        };
+        drop(lock);
+        callback(RequestFrameOptions::default());
+        self.0.lock().request_frame_callback = Some(callback);
+    }
+
     pub fn simulate_input(&mut self, event: PlatformInput) -> bool {
         let mut lock = self.0.lock();
         let Some(mut callback) = lock.input_callback.take() else {
@@ -232,7 +251,9 @@ impl PlatformWindow for TestWindow {
         self.0.lock().is_fullscreen
     }
 
-    fn on_request_frame(&self, _callback: Box<dyn FnMut(RequestFrameOptions)>) {}
+    fn on_request_frame(&self, callback: Box<dyn FnMut(RequestFrameOptions)>) {
+        self.0.lock().request_frame_callback = Some(callback)
+    }
 
     fn on_input(&self, callback: Box<dyn FnMut(crate::PlatformInput) -> DispatchEventResult>) {
         self.0.lock().input_callback = Some(callback)
@@ -272,6 +293,21 @@ impl PlatformWindow for TestWindow {
         self.0.lock().sprite_atlas.clone()
     }
 
+    fn render_to_image(&self, _scene: &Scene, bounds: Bounds<DevicePixels>) -> Option<RgbaImage> {
+        // Nothing is rasterized in tests, so stand in a transparent image of the requested size.
+        Some(RgbaImage::new(
+            bounds.size.width.0 as u32,
+            bounds.size.height.0 as u32,
+        ))
+    }
+
+    fn start_drag_out(&self, paths: &[PathBuf], image: Option<DragImage>) -> bool {
+        let mut state = self.0.lock();
+        state.dragged_out_paths = Some(paths.to_vec());
+        state.dragged_out_image = image;
+        true
+    }
+
     fn as_test(&mut self) -> Option<&mut TestWindow> {
         Some(self)
     }
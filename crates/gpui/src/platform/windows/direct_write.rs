@@ -27,6 +27,7 @@ struct FontInfo {
     font_family: String,
     font_face: IDWriteFontFace3,
     features: IDWriteTypography,
+    axis_values: Vec<DWRITE_FONT_AXIS_VALUE>,
     fallbacks: Option<IDWriteFontFallback>,
     is_system_font: bool,
 }
@@ -67,6 +68,8 @@ struct FontIdentifier {
     postscript_name: String,
     weight: i32,
     style: i32,
+    /// The tag and value bits of each axis of a variable font instance.
+    axis_values: Vec<(u32, u32)>,
 }
 
 impl DirectWriteComponent {
@@ -393,6 +396,7 @@ impl DirectWriteState {
         font_weight: FontWeight,
         font_style: FontStyle,
         font_features: &FontFeatures,
+        font_variations: &FontVariations,
         font_fallbacks: Option<&FontFallbacks>,
         is_system_font: bool,
     ) -> Option<FontId> {
@@ -421,6 +425,12 @@ impl DirectWriteState {
             let Some(font_face) = (unsafe { font_face_ref.CreateFontFace().log_err() }) else {
                 continue;
             };
+            let axis_values = make_direct_write_axis_values(font_variations);
+            let Some(font_face) =
+                (unsafe { instance_font_face(font_face, &axis_values).log_err() })
+            else {
+                continue;
+            };
             let Some(identifier) = get_font_identifier(&font_face, &self.components.locale) else {
                 continue;
             };
@@ -435,6 +445,7 @@ impl DirectWriteState {
                 font_family: family_name.to_owned(),
                 font_face,
                 features: direct_write_features,
+                axis_values,
                 fallbacks,
                 is_system_font,
             };
@@ -468,6 +479,7 @@ impl DirectWriteState {
                     target_font.weight,
                     target_font.style,
                     &target_font.features,
+                    &target_font.variations,
                     target_font.fallbacks.as_ref(),
                 )
             } else {
@@ -477,6 +489,7 @@ impl DirectWriteState {
                     target_font.weight,
                     target_font.style,
                     &target_font.features,
+                    &target_font.variations,
                     target_font.fallbacks.as_ref(),
                 )
                 .or_else(|| {
@@ -492,6 +505,7 @@ impl DirectWriteState {
                             target_font.weight,
                             target_font.style,
                             &target_font.features,
+                            &target_font.variations,
                             target_font.fallbacks.as_ref(),
                             true,
                         )
@@ -507,6 +521,7 @@ impl DirectWriteState {
         weight: FontWeight,
         style: FontStyle,
         features: &FontFeatures,
+        variations: &FontVariations,
         fallbacks: Option<&FontFallbacks>,
     ) -> Option<FontId> {
         // try to find target font in custom font collection first
@@ -516,6 +531,7 @@ impl DirectWriteState {
                 weight,
                 style,
                 features,
+                variations,
                 fallbacks,
                 false,
             )
@@ -525,6 +541,7 @@ impl DirectWriteState {
                     weight,
                     style,
                     features,
+                    variations,
                     fallbacks,
                     true,
                 )
@@ -536,6 +553,7 @@ impl DirectWriteState {
                     weight,
                     style,
                     features,
+                    variations,
                     fallbacks,
                     true,
                 )
@@ -600,6 +618,11 @@ impl DirectWriteState {
                     length: current_text_utf16_length,
                 };
                 layout.SetTypography(&font_info.features, text_range)?;
+                if !font_info.axis_values.is_empty() {
+                    layout
+                        .cast::<IDWriteTextLayout4>()?
+                        .SetFontAxisValues(&font_info.axis_values, text_range)?;
+                }
                 utf16_offset += current_text_utf16_length;
 
                 layout
@@ -647,6 +670,11 @@ impl DirectWriteState {
                 text_layout.SetFontStyle(font_info.font_face.GetStyle(), text_range)?;
                 text_layout.SetFontWeight(font_info.font_face.GetWeight(), text_range)?;
                 text_layout.SetTypography(&font_info.features, text_range)?;
+                if !font_info.axis_values.is_empty() {
+                    text_layout
+                        .cast::<IDWriteTextLayout4>()?
+                        .SetFontAxisValues(&font_info.axis_values, text_range)?;
+                }
 
                 break_ligatures = !break_ligatures;
             }
@@ -673,6 +701,7 @@ impl DirectWriteState {
                 descent,
                 runs,
                 len: text.len(),
+                ..Default::default()
             })
         }
     }
@@ -690,6 +719,8 @@ impl DirectWriteState {
                 line_gap: metrics.Base.lineGap as _,
                 underline_position: metrics.Base.underlinePosition as _,
                 underline_thickness: metrics.Base.underlineThickness as _,
+                strikethrough_position: metrics.Base.strikethroughPosition as _,
+                strikethrough_thickness: metrics.Base.strikethroughThickness as _,
                 cap_height: metrics.Base.capHeight as _,
                 x_height: metrics.Base.xHeight as _,
                 bounding_box: Bounds {
@@ -1682,10 +1713,12 @@ fn get_font_identifier_and_font_struct(
         postscript_name,
         weight: weight.0,
         style: style.0,
+        axis_values: get_font_axis_values(font_face),
     };
     let font_struct = Font {
         family: family_name.into(),
         features: FontFeatures::default(),
+        variations: FontVariations::default(),
         weight: weight.into(),
         style: style.into(),
         fallbacks: None,
@@ -1704,9 +1737,56 @@ fn get_font_identifier(font_face: &IDWriteFontFace3, locale: &str) -> Option<Fon
             postscript_name,
             weight,
             style,
+            axis_values: get_font_axis_values(font_face),
         })
 }
 
+/// Creates an instance of a variable font face with the given axis values, so that glyphs are
+/// rasterized with the same outlines they were shaped with.
+unsafe fn instance_font_face(
+    font_face: IDWriteFontFace3,
+    axis_values: &[DWRITE_FONT_AXIS_VALUE],
+) -> Result<IDWriteFontFace3> {
+    if axis_values.is_empty() {
+        return Ok(font_face);
+    }
+    let Ok(variable_font_face) = font_face.cast::<IDWriteFontFace5>() else {
+        return Ok(font_face);
+    };
+    unsafe {
+        if !variable_font_face.HasVariations().as_bool() {
+            return Ok(font_face);
+        }
+        let font_resource = variable_font_face.GetFontResource()?;
+        let instance = font_resource.CreateFontFace(DWRITE_FONT_SIMULATIONS_NONE, axis_values)?;
+        Ok(instance.cast()?)
+    }
+}
+
+fn get_font_axis_values(font_face: &IDWriteFontFace3) -> Vec<(u32, u32)> {
+    let Ok(font_face) = font_face.cast::<IDWriteFontFace5>() else {
+        return Vec::new();
+    };
+    unsafe {
+        if !font_face.HasVariations().as_bool() {
+            return Vec::new();
+        }
+        let mut axis_values =
+            vec![DWRITE_FONT_AXIS_VALUE::default(); font_face.GetFontAxisValueCount() as usize];
+        if font_face
+            .GetFontAxisValues(&mut axis_values)
+            .log_err()
+            .is_none()
+        {
+            return Vec::new();
+        }
+        axis_values
+            .into_iter()
+            .map(|axis_value| (axis_value.axisTag.0, axis_value.value.to_bits()))
+            .collect()
+    }
+}
+
 #[inline]
 fn get_postscript_name(font_face: &IDWriteFontFace3, locale: &str) -> Result<String> {
     let mut info = None;
@@ -1777,6 +1857,18 @@ const fn make_direct_write_feature(feature_name: &str, parameter: u32) -> DWRITE
     }
 }
 
+fn make_direct_write_axis_values(variations: &FontVariations) -> Vec<DWRITE_FONT_AXIS_VALUE> {
+    variations
+        .axis_value_list()
+        .iter()
+        .filter(|(tag, _)| tag.len() == 4)
+        .map(|(tag, value)| DWRITE_FONT_AXIS_VALUE {
+            axisTag: DWRITE_FONT_AXIS_TAG(make_open_type_tag(tag)),
+            value: *value,
+        })
+        .collect()
+}
+
 #[inline]
 const fn make_open_type_tag(tag_name: &str) -> u32 {
     let bytes = tag_name.as_bytes();
@@ -5,6 +5,7 @@ use std::{
 
 use ::util::ResultExt;
 use anyhow::{Context, Result};
+use image::RgbaImage;
 use windows::{
     Win32::{
         Foundation::HWND,
@@ -78,18 +79,32 @@ struct DirectXResources {
     path_intermediate_msaa_texture: ID3D11Texture2D,
     path_intermediate_msaa_view: Option<ID3D11RenderTargetView>,
 
+    // Backdrop blur texture, holding the horizontally blurred frame
+    backdrop_blur_srv: Option<ID3D11ShaderResourceView>,
+    backdrop_blur_view: Option<ID3D11RenderTargetView>,
+
+    // Overlay layer texture, holding an overlay blended batch before it's composited
+    overlay_layer_srv: Option<ID3D11ShaderResourceView>,
+    overlay_layer_view: Option<ID3D11RenderTargetView>,
+
     // Cached viewport
     viewport: D3D11_VIEWPORT,
 }
 
 struct DirectXRenderPipelines {
     shadow_pipeline: PipelineState<Shadow>,
+    backdrop_horizontal_blur_pipeline: PipelineState<Backdrop>,
+    backdrop_vertical_blur_pipeline: PipelineState<Backdrop>,
+    overlay_pipeline: PipelineState<PathSprite>,
     quad_pipeline: PipelineState<Quad>,
     path_rasterization_pipeline: PipelineState<PathRasterizationSprite>,
     path_sprite_pipeline: PipelineState<PathSprite>,
     underline_pipeline: PipelineState<Underline>,
     mono_sprites: PipelineState<MonochromeSprite>,
+    gradient_sprites: PipelineState<GradientSprite>,
     poly_sprites: PipelineState<PolychromeSprite>,
+    clip_regions: StructuredBuffer<ClipRegion>,
+    clip_region_vertices: StructuredBuffer<Point<ScaledPixels>>,
 }
 
 struct DirectXGlobalElements {
@@ -305,10 +320,135 @@ impl DirectXRenderer {
             // and so likely do not have the textures anymore that are required for drawing
             return Ok(());
         }
+        self.draw_scene(scene)?;
+        self.present()
+    }
+
+    /// Renders the scene and reads back the given part of it, e.g. to show an element as the
+    /// drag image when it's dragged out of the window. The frame isn't presented.
+    pub(crate) fn render_to_image(
+        &mut self,
+        scene: &Scene,
+        bounds: Bounds<DevicePixels>,
+    ) -> Result<RgbaImage> {
+        if self.skip_draws {
+            anyhow::bail!("the device was just recovered");
+        }
+        let viewport_size = size(
+            DevicePixels(self.width as i32),
+            DevicePixels(self.height as i32),
+        );
+        let bounds = bounds.intersect(&Bounds::new(Point::default(), viewport_size));
+        if bounds.is_empty() {
+            anyhow::bail!("{bounds:?} is outside of the frame");
+        }
+        self.draw_scene(scene)?;
+
+        let devices = self.devices.as_ref().context("devices missing")?;
+        let resources = self.resources.as_ref().context("resources missing")?;
+        let render_target = resources
+            .render_target
+            .as_ref()
+            .context("missing render target")?;
+        let width = bounds.size.width.0 as u32;
+        let height = bounds.size.height.0 as u32;
+        let staging_texture = {
+            let mut texture = None;
+            let desc = D3D11_TEXTURE2D_DESC {
+                Width: width,
+                Height: height,
+                MipLevels: 1,
+                ArraySize: 1,
+                Format: RENDER_TARGET_FORMAT,
+                SampleDesc: DXGI_SAMPLE_DESC {
+                    Count: 1,
+                    Quality: 0,
+                },
+                Usage: D3D11_USAGE_STAGING,
+                BindFlags: 0,
+                CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+                MiscFlags: 0,
+            };
+            unsafe {
+                devices
+                    .device
+                    .CreateTexture2D(&desc, None, Some(&mut texture))
+            }?;
+            texture.context("failed to create staging texture")?
+        };
+        let device_context = &devices.device_context;
+        unsafe {
+            device_context.CopySubresourceRegion(
+                &staging_texture,
+                0,
+                0,
+                0,
+                0,
+                render_target,
+                0,
+                Some(&D3D11_BOX {
+                    left: bounds.left().0 as u32,
+                    top: bounds.top().0 as u32,
+                    front: 0,
+                    right: bounds.right().0 as u32,
+                    bottom: bounds.bottom().0 as u32,
+                    back: 1,
+                }),
+            )
+        };
+
+        let mut mapped_data = D3D11_MAPPED_SUBRESOURCE::default();
+        unsafe {
+            device_context.Map(
+                &staging_texture,
+                0,
+                D3D11_MAP_READ,
+                0,
+                Some(&mut mapped_data),
+            )
+        }?;
+        let row_len = width as usize * 4;
+        let mut bytes = vec![0u8; row_len * height as usize];
+        for (y, row) in bytes.chunks_exact_mut(row_len).enumerate() {
+            unsafe {
+                std::ptr::copy_nonoverlapping::<u8>(
+                    (mapped_data.pData as *const u8).byte_add(mapped_data.RowPitch as usize * y),
+                    row.as_mut_ptr(),
+                    row_len,
+                )
+            };
+        }
+        unsafe { device_context.Unmap(&staging_texture, 0) };
+
+        // Convert from premultiplied BGRA to straight RGBA
+        for pixel in bytes.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+            let alpha = pixel[3] as f32;
+            if alpha > 0.0 {
+                for channel in &mut pixel[..3] {
+                    *channel = (*channel as f32 * 255.0 / alpha).clamp(0.0, 255.0) as u8;
+                }
+            }
+        }
+        RgbaImage::from_raw(width, height, bytes)
+            .context(format!("readback of {width}x{height} pixels is truncated"))
+    }
+
+    fn draw_scene(&mut self, scene: &Scene) -> Result<()> {
         self.pre_draw()?;
+        self.bind_clip_regions(scene)?;
+        let viewport_size = size(
+            DevicePixels(self.width as i32),
+            DevicePixels(self.height as i32),
+        );
         for batch in scene.batches() {
+            let overlay_bounds = batch.overlay_bounds(viewport_size);
+            if overlay_bounds.is_some() {
+                self.begin_overlay_layer()?;
+            }
             match batch {
                 PrimitiveBatch::Shadows(shadows) => self.draw_shadows(shadows),
+                PrimitiveBatch::Backdrops(backdrops) => self.draw_backdrops(backdrops),
                 PrimitiveBatch::Quads(quads) => self.draw_quads(quads),
                 PrimitiveBatch::Paths(paths) => {
                     self.draw_paths_to_intermediate(paths)?;
@@ -319,6 +459,10 @@ impl DirectXRenderer {
                     texture_id,
                     sprites,
                 } => self.draw_monochrome_sprites(texture_id, sprites),
+                PrimitiveBatch::GradientSprites {
+                    texture_id,
+                    sprites,
+                } => self.draw_gradient_sprites(texture_id, sprites),
                 PrimitiveBatch::PolychromeSprites {
                     texture_id,
                     sprites,
@@ -327,17 +471,21 @@ impl DirectXRenderer {
             }
             .context(format!(
                 "scene too large:\
-                {} paths, {} shadows, {} quads, {} underlines, {} mono, {} poly, {} surfaces",
+                {} paths, {} shadows, {} backdrops, {} quads, {} underlines, {} mono, {} poly, {} surfaces",
                 scene.paths.len(),
                 scene.shadows.len(),
+                scene.backdrops.len(),
                 scene.quads.len(),
                 scene.underlines.len(),
-                scene.monochrome_sprites.len(),
+                scene.monochrome_sprites.len() + scene.gradient_sprites.len(),
                 scene.polychrome_sprites.len(),
                 scene.surfaces.len(),
             ))?;
+            if let Some(bounds) = overlay_bounds {
+                self.draw_overlay(bounds)?;
+            }
         }
-        self.present()
+        Ok(())
     }
 
     pub(crate) fn resize(&mut self, new_size: Size<DevicePixels>) -> Result<()> {
@@ -384,6 +532,32 @@ impl DirectXRenderer {
         Ok(())
     }
 
+    /// Uploads the scene's clip regions and binds them for the pixel shaders of every pipeline,
+    /// which look them up by the `clip_region` of each primitive.
+    fn bind_clip_regions(&mut self, scene: &Scene) -> Result<()> {
+        let devices = self.devices.as_ref().context("devices missing")?;
+        self.pipelines.clip_regions.update(
+            &devices.device,
+            &devices.device_context,
+            &scene.clip_regions,
+        )?;
+        self.pipelines.clip_region_vertices.update(
+            &devices.device,
+            &devices.device_context,
+            &scene.clip_region_vertices,
+        )?;
+        unsafe {
+            devices.device_context.PSSetShaderResources(
+                2,
+                Some(&[
+                    self.pipelines.clip_regions.view.clone(),
+                    self.pipelines.clip_region_vertices.view.clone(),
+                ]),
+            );
+        }
+        Ok(())
+    }
+
     fn draw_shadows(&mut self, shadows: &[Shadow]) -> Result<()> {
         if shadows.is_empty() {
             return Ok(());
@@ -410,11 +584,190 @@ impl DirectXRenderer {
         )
     }
 
+    /// Blurs the part of the frame behind each backdrop in two passes, first horizontally from a
+    /// copy of the frame into the blur texture, then vertically from there back into the frame.
+    fn draw_backdrops(&mut self, backdrops: &[Backdrop]) -> Result<()> {
+        let devices = self.devices.as_ref().context("devices missing")?;
+        let resources = self.resources.as_ref().context("resources missing")?;
+        let render_target = resources
+            .render_target
+            .as_ref()
+            .context("missing render target")?;
+        let viewport_size = size(
+            DevicePixels(self.width as i32),
+            DevicePixels(self.height as i32),
+        );
+        for backdrop in backdrops {
+            let Some(sample_bounds) = backdrop.sample_bounds(viewport_size) else {
+                continue;
+            };
+            unsafe {
+                // Unbind the blurred textures before writing to them.
+                devices
+                    .device_context
+                    .VSSetShaderResources(0, Some(&[None]));
+                devices
+                    .device_context
+                    .PSSetShaderResources(0, Some(&[None]));
+                devices.device_context.CopySubresourceRegion(
+                    &resources.path_intermediate_texture,
+                    0,
+                    sample_bounds.left().0 as u32,
+                    sample_bounds.top().0 as u32,
+                    0,
+                    render_target,
+                    0,
+                    Some(&D3D11_BOX {
+                        left: sample_bounds.left().0 as u32,
+                        top: sample_bounds.top().0 as u32,
+                        front: 0,
+                        right: sample_bounds.right().0 as u32,
+                        bottom: sample_bounds.bottom().0 as u32,
+                        back: 1,
+                    }),
+                );
+                devices
+                    .device_context
+                    .OMSetRenderTargets(Some(slice::from_ref(&resources.backdrop_blur_view)), None);
+            }
+            self.pipelines
+                .backdrop_horizontal_blur_pipeline
+                .update_buffer(
+                    &devices.device,
+                    &devices.device_context,
+                    slice::from_ref(backdrop),
+                )?;
+            self.pipelines
+                .backdrop_horizontal_blur_pipeline
+                .draw_with_texture(
+                    &devices.device_context,
+                    slice::from_ref(&resources.path_intermediate_srv),
+                    slice::from_ref(&resources.viewport),
+                    slice::from_ref(&self.globals.global_params_buffer),
+                    slice::from_ref(&self.globals.sampler),
+                    1,
+                )?;
+
+            unsafe {
+                devices
+                    .device_context
+                    .VSSetShaderResources(0, Some(&[None]));
+                devices
+                    .device_context
+                    .PSSetShaderResources(0, Some(&[None]));
+                devices
+                    .device_context
+                    .OMSetRenderTargets(Some(slice::from_ref(&resources.render_target_view)), None);
+            }
+            self.pipelines
+                .backdrop_vertical_blur_pipeline
+                .update_buffer(
+                    &devices.device,
+                    &devices.device_context,
+                    slice::from_ref(backdrop),
+                )?;
+            self.pipelines
+                .backdrop_vertical_blur_pipeline
+                .draw_with_texture(
+                    &devices.device_context,
+                    slice::from_ref(&resources.backdrop_blur_srv),
+                    slice::from_ref(&resources.viewport),
+                    slice::from_ref(&self.globals.global_params_buffer),
+                    slice::from_ref(&self.globals.sampler),
+                    1,
+                )?;
+        }
+        Ok(())
+    }
+
+    /// Clears the overlay layer and draws subsequent batches into it.
+    fn begin_overlay_layer(&mut self) -> Result<()> {
+        let devices = self.devices.as_ref().context("devices missing")?;
+        let resources = self.resources.as_ref().context("resources missing")?;
+        unsafe {
+            devices
+                .device_context
+                .ClearRenderTargetView(resources.overlay_layer_view.as_ref().unwrap(), &[0.0; 4]);
+            devices
+                .device_context
+                .OMSetRenderTargets(Some(slice::from_ref(&resources.overlay_layer_view)), None);
+        }
+        Ok(())
+    }
+
+    /// Composites an overlay blended batch drawn into the layer over a copy of the part of the
+    /// frame behind it.
+    fn draw_overlay(&mut self, bounds: Bounds<DevicePixels>) -> Result<()> {
+        let devices = self.devices.as_ref().context("devices missing")?;
+        let resources = self.resources.as_ref().context("resources missing")?;
+        let render_target = resources
+            .render_target
+            .as_ref()
+            .context("missing render target")?;
+        unsafe {
+            devices
+                .device_context
+                .VSSetShaderResources(0, Some(&[None]));
+            devices
+                .device_context
+                .PSSetShaderResources(0, Some(&[None]));
+            devices
+                .device_context
+                .OMSetRenderTargets(Some(slice::from_ref(&resources.render_target_view)), None);
+            devices.device_context.CopySubresourceRegion(
+                &resources.path_intermediate_texture,
+                0,
+                bounds.left().0 as u32,
+                bounds.top().0 as u32,
+                0,
+                render_target,
+                0,
+                Some(&D3D11_BOX {
+                    left: bounds.left().0 as u32,
+                    top: bounds.top().0 as u32,
+                    front: 0,
+                    right: bounds.right().0 as u32,
+                    bottom: bounds.bottom().0 as u32,
+                    back: 1,
+                }),
+            );
+            devices
+                .device_context
+                .PSSetShaderResources(4, Some(slice::from_ref(&resources.path_intermediate_srv)));
+        }
+        let sprite = PathSprite {
+            bounds: bounds.map(ScaledPixels::from),
+        };
+        self.pipelines.overlay_pipeline.update_buffer(
+            &devices.device,
+            &devices.device_context,
+            slice::from_ref(&sprite),
+        )?;
+        self.pipelines.overlay_pipeline.draw_with_texture(
+            &devices.device_context,
+            slice::from_ref(&resources.overlay_layer_srv),
+            slice::from_ref(&resources.viewport),
+            slice::from_ref(&self.globals.global_params_buffer),
+            slice::from_ref(&self.globals.sampler),
+            1,
+        )?;
+        unsafe {
+            // Unbind the copy before it's written to again.
+            devices
+                .device_context
+                .PSSetShaderResources(4, Some(&[None]));
+        }
+        Ok(())
+    }
+
     fn draw_quads(&mut self, quads: &[Quad]) -> Result<()> {
         if quads.is_empty() {
             return Ok(());
         }
         let devices = self.devices.as_ref().context("devices missing")?;
+        self.pipelines
+            .quad_pipeline
+            .set_blend_mode(quads[0].blend_mode);
         self.pipelines.quad_pipeline.update_buffer(
             &devices.device,
             &devices.device_context,
@@ -465,6 +818,8 @@ impl DirectXRenderer {
                 st_position: v.st_position,
                 color: path.color,
                 bounds: path.clipped_bounds(),
+                clip_region: path.clip_region,
+                _pad: 0,
             }));
         }
 
@@ -578,6 +933,9 @@ impl DirectXRenderer {
         }
         let devices = self.devices.as_ref().context("devices missing")?;
         let resources = self.resources.as_ref().context("resources missing")?;
+        self.pipelines
+            .mono_sprites
+            .set_blend_mode(sprites[0].blend_mode);
         self.pipelines.mono_sprites.update_buffer(
             &devices.device,
             &devices.device_context,
@@ -594,6 +952,35 @@ impl DirectXRenderer {
         )
     }
 
+    fn draw_gradient_sprites(
+        &mut self,
+        texture_id: AtlasTextureId,
+        sprites: &[GradientSprite],
+    ) -> Result<()> {
+        if sprites.is_empty() {
+            return Ok(());
+        }
+        let devices = self.devices.as_ref().context("devices missing")?;
+        let resources = self.resources.as_ref().context("resources missing")?;
+        self.pipelines
+            .gradient_sprites
+            .set_blend_mode(sprites[0].blend_mode);
+        self.pipelines.gradient_sprites.update_buffer(
+            &devices.device,
+            &devices.device_context,
+            sprites,
+        )?;
+        let texture_view = self.atlas.get_texture_view(texture_id);
+        self.pipelines.gradient_sprites.draw_with_texture(
+            &devices.device_context,
+            &texture_view,
+            slice::from_ref(&resources.viewport),
+            slice::from_ref(&self.globals.global_params_buffer),
+            slice::from_ref(&self.globals.sampler),
+            sprites.len() as u32,
+        )
+    }
+
     fn draw_polychrome_sprites(
         &mut self,
         texture_id: AtlasTextureId,
@@ -605,6 +992,9 @@ impl DirectXRenderer {
 
         let devices = self.devices.as_ref().context("devices missing")?;
         let resources = self.resources.as_ref().context("resources missing")?;
+        self.pipelines
+            .poly_sprites
+            .set_blend_mode(sprites[0].blend_mode);
         self.pipelines.poly_sprites.update_buffer(
             &devices.device,
             &devices.device_context,
@@ -702,6 +1092,10 @@ impl DirectXResources {
             path_intermediate_srv,
             path_intermediate_msaa_texture,
             path_intermediate_msaa_view,
+            backdrop_blur_srv,
+            backdrop_blur_view,
+            overlay_layer_srv,
+            overlay_layer_view,
             viewport,
         ) = create_resources(devices, &swap_chain, width, height)?;
         set_rasterizer_state(&devices.device, &devices.device_context)?;
@@ -714,6 +1108,10 @@ impl DirectXResources {
             path_intermediate_msaa_texture,
             path_intermediate_msaa_view,
             path_intermediate_srv,
+            backdrop_blur_srv,
+            backdrop_blur_view,
+            overlay_layer_srv,
+            overlay_layer_view,
             viewport,
         })
     }
@@ -732,6 +1130,10 @@ impl DirectXResources {
             path_intermediate_srv,
             path_intermediate_msaa_texture,
             path_intermediate_msaa_view,
+            backdrop_blur_srv,
+            backdrop_blur_view,
+            overlay_layer_srv,
+            overlay_layer_view,
             viewport,
         ) = create_resources(devices, &self.swap_chain, width, height)?;
         self.render_target = Some(render_target);
@@ -740,6 +1142,10 @@ impl DirectXResources {
         self.path_intermediate_msaa_texture = path_intermediate_msaa_texture;
         self.path_intermediate_msaa_view = path_intermediate_msaa_view;
         self.path_intermediate_srv = path_intermediate_srv;
+        self.backdrop_blur_srv = backdrop_blur_srv;
+        self.backdrop_blur_view = backdrop_blur_view;
+        self.overlay_layer_srv = overlay_layer_srv;
+        self.overlay_layer_view = overlay_layer_view;
         self.viewport = viewport;
         Ok(())
     }
@@ -754,13 +1160,37 @@ impl DirectXRenderPipelines {
             4,
             create_blend_state(device)?,
         )?;
+        let backdrop_horizontal_blur_pipeline = PipelineState::new(
+            device,
+            "backdrop_horizontal_blur_pipeline",
+            ShaderModule::BackdropHorizontalBlur,
+            4,
+            create_blend_state_for_backdrop_blur(device)?,
+        )?;
+        // The blurred frame is already premultiplied by the coverage of the rounded corners.
+        let backdrop_vertical_blur_pipeline = PipelineState::new(
+            device,
+            "backdrop_vertical_blur_pipeline",
+            ShaderModule::BackdropVerticalBlur,
+            4,
+            create_blend_state_for_path_sprite(device)?,
+        )?;
+        // The composited layer is premultiplied like the blurred frame.
+        let overlay_pipeline = PipelineState::new(
+            device,
+            "overlay_pipeline",
+            ShaderModule::Overlay,
+            1,
+            create_blend_state_for_path_sprite(device)?,
+        )?;
         let quad_pipeline = PipelineState::new(
             device,
             "quad_pipeline",
             ShaderModule::Quad,
             64,
             create_blend_state(device)?,
-        )?;
+        )?
+        .with_blend_modes(device)?;
         let path_rasterization_pipeline = PipelineState::new(
             device,
             "path_rasterization_pipeline",
@@ -788,23 +1218,39 @@ impl DirectXRenderPipelines {
             ShaderModule::MonochromeSprite,
             512,
             create_blend_state(device)?,
-        )?;
+        )?
+        .with_blend_modes(device)?;
+        let gradient_sprites = PipelineState::new(
+            device,
+            "gradient_sprite_pipeline",
+            ShaderModule::GradientSprite,
+            16,
+            create_blend_state(device)?,
+        )?
+        .with_blend_modes(device)?;
         let poly_sprites = PipelineState::new(
             device,
             "polychrome_sprite_pipeline",
             ShaderModule::PolychromeSprite,
             16,
             create_blend_state(device)?,
-        )?;
+        )?
+        .with_blend_modes(device)?;
 
         Ok(Self {
             shadow_pipeline,
+            backdrop_horizontal_blur_pipeline,
+            backdrop_vertical_blur_pipeline,
+            overlay_pipeline,
             quad_pipeline,
             path_rasterization_pipeline,
             path_sprite_pipeline,
             underline_pipeline,
             mono_sprites,
+            gradient_sprites,
             poly_sprites,
+            clip_regions: StructuredBuffer::new(device, "clip_regions", 4)?,
+            clip_region_vertices: StructuredBuffer::new(device, "clip_region_vertices", 16)?,
         })
     }
 }
@@ -889,9 +1335,17 @@ struct PipelineState<T> {
     buffer_size: usize,
     view: Option<ID3D11ShaderResourceView>,
     blend_state: ID3D11BlendState,
+    blend_mode_states: Option<BlendModeStates>,
+    blend_mode: BlendMode,
     _marker: std::marker::PhantomData<T>,
 }
 
+/// The blend states for the blend modes other than normal that can be drawn with one.
+struct BlendModeStates {
+    multiply: ID3D11BlendState,
+    screen: ID3D11BlendState,
+}
+
 impl<T> PipelineState<T> {
     fn new(
         device: &ID3D11Device,
@@ -919,10 +1373,36 @@ impl<T> PipelineState<T> {
             buffer_size,
             view,
             blend_state,
+            blend_mode_states: None,
+            blend_mode: BlendMode::Normal,
             _marker: std::marker::PhantomData,
         })
     }
 
+    /// Adds blend states for drawing with the blend modes set by `set_blend_mode`.
+    fn with_blend_modes(mut self, device: &ID3D11Device) -> Result<Self> {
+        self.blend_mode_states = Some(BlendModeStates {
+            multiply: create_blend_state_for_blend_mode(device, BlendMode::Multiply)?,
+            screen: create_blend_state_for_blend_mode(device, BlendMode::Screen)?,
+        });
+        Ok(self)
+    }
+
+    /// Sets the blend mode that subsequent draws composite with, which is normal for pipelines
+    /// without blend states for it. Overlay blended batches are drawn normally into a layer before
+    /// being composited.
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    fn current_blend_state(&self) -> &ID3D11BlendState {
+        match (&self.blend_mode_states, self.blend_mode) {
+            (Some(states), BlendMode::Multiply) => &states.multiply,
+            (Some(states), BlendMode::Screen) => &states.screen,
+            _ => &self.blend_state,
+        }
+    }
+
     fn update_buffer(
         &mut self,
         device: &ID3D11Device,
@@ -963,7 +1443,7 @@ impl<T> PipelineState<T> {
             &self.vertex,
             &self.fragment,
             global_params,
-            &self.blend_state,
+            self.current_blend_state(),
         );
         unsafe {
             device_context.DrawInstanced(vertex_count, instance_count, 0, 0);
@@ -988,7 +1468,7 @@ impl<T> PipelineState<T> {
             &self.vertex,
             &self.fragment,
             global_params,
-            &self.blend_state,
+            self.current_blend_state(),
         );
         unsafe {
             device_context.PSSetSamplers(0, Some(sampler));
@@ -1008,6 +1488,8 @@ struct PathRasterizationSprite {
     st_position: Point<f32>,
     color: Background,
     bounds: Bounds<ScaledPixels>,
+    clip_region: u32,
+    _pad: u32,
 }
 
 #[derive(Clone, Copy)]
@@ -1100,6 +1582,10 @@ fn create_resources(
     Option<ID3D11ShaderResourceView>,
     ID3D11Texture2D,
     Option<ID3D11RenderTargetView>,
+    Option<ID3D11ShaderResourceView>,
+    Option<ID3D11RenderTargetView>,
+    Option<ID3D11ShaderResourceView>,
+    Option<ID3D11RenderTargetView>,
     D3D11_VIEWPORT,
 )> {
     let (render_target, render_target_view) =
@@ -1108,6 +1594,10 @@ fn create_resources(
         create_path_intermediate_texture(&devices.device, width, height)?;
     let (path_intermediate_msaa_texture, path_intermediate_msaa_view) =
         create_path_intermediate_msaa_texture_and_view(&devices.device, width, height)?;
+    let (backdrop_blur_srv, backdrop_blur_view) =
+        create_render_texture_views(&devices.device, width, height)?;
+    let (overlay_layer_srv, overlay_layer_view) =
+        create_render_texture_views(&devices.device, width, height)?;
     let viewport = set_viewport(&devices.device_context, width as f32, height as f32);
     Ok((
         render_target,
@@ -1116,6 +1606,10 @@ fn create_resources(
         path_intermediate_srv,
         path_intermediate_msaa_texture,
         path_intermediate_msaa_view,
+        backdrop_blur_srv,
+        backdrop_blur_view,
+        overlay_layer_srv,
+        overlay_layer_view,
         viewport,
     ))
 }
@@ -1164,6 +1658,21 @@ fn create_path_intermediate_texture(
     Ok((texture, Some(shader_resource_view.unwrap())))
 }
 
+#[inline]
+fn create_render_texture_views(
+    device: &ID3D11Device,
+    width: u32,
+    height: u32,
+) -> Result<(
+    Option<ID3D11ShaderResourceView>,
+    Option<ID3D11RenderTargetView>,
+)> {
+    let (texture, shader_resource_view) = create_path_intermediate_texture(device, width, height)?;
+    let mut render_target_view = None;
+    unsafe { device.CreateRenderTargetView(&texture, None, Some(&mut render_target_view))? };
+    Ok((shader_resource_view, Some(render_target_view.unwrap())))
+}
+
 #[inline]
 fn create_path_intermediate_msaa_texture_and_view(
     device: &ID3D11Device,
@@ -1273,6 +1782,46 @@ fn create_blend_state_for_path_rasterization(device: &ID3D11Device) -> Result<ID
     }
 }
 
+#[inline]
+fn create_blend_state_for_blend_mode(
+    device: &ID3D11Device,
+    blend_mode: BlendMode,
+) -> Result<ID3D11BlendState> {
+    // Shaders output premultiplied colors for the modes drawn with these states.
+    let (src_blend, dest_blend) = match blend_mode {
+        BlendMode::Multiply => (D3D11_BLEND_DEST_COLOR, D3D11_BLEND_INV_SRC_ALPHA),
+        BlendMode::Screen => (D3D11_BLEND_ONE, D3D11_BLEND_INV_SRC_COLOR),
+        BlendMode::Normal | BlendMode::Overlay => (D3D11_BLEND_ONE, D3D11_BLEND_INV_SRC_ALPHA),
+    };
+    let mut desc = D3D11_BLEND_DESC::default();
+    desc.RenderTarget[0].BlendEnable = true.into();
+    desc.RenderTarget[0].BlendOp = D3D11_BLEND_OP_ADD;
+    desc.RenderTarget[0].BlendOpAlpha = D3D11_BLEND_OP_ADD;
+    desc.RenderTarget[0].SrcBlend = src_blend;
+    desc.RenderTarget[0].SrcBlendAlpha = D3D11_BLEND_ONE;
+    desc.RenderTarget[0].DestBlend = dest_blend;
+    desc.RenderTarget[0].DestBlendAlpha = D3D11_BLEND_ONE;
+    desc.RenderTarget[0].RenderTargetWriteMask = D3D11_COLOR_WRITE_ENABLE_ALL.0 as u8;
+    unsafe {
+        let mut state = None;
+        device.CreateBlendState(&desc, Some(&mut state))?;
+        Ok(state.unwrap())
+    }
+}
+
+#[inline]
+fn create_blend_state_for_backdrop_blur(device: &ID3D11Device) -> Result<ID3D11BlendState> {
+    // The horizontal pass replaces the contents of the blur texture.
+    let mut desc = D3D11_BLEND_DESC::default();
+    desc.RenderTarget[0].BlendEnable = false.into();
+    desc.RenderTarget[0].RenderTargetWriteMask = D3D11_COLOR_WRITE_ENABLE_ALL.0 as u8;
+    unsafe {
+        let mut state = None;
+        device.CreateBlendState(&desc, Some(&mut state))?;
+        Ok(state.unwrap())
+    }
+}
+
 #[inline]
 fn create_blend_state_for_path_sprite(device: &ID3D11Device) -> Result<ID3D11BlendState> {
     // If the feature level is set to greater than D3D_FEATURE_LEVEL_9_3, the display
@@ -1311,6 +1860,52 @@ fn create_fragment_shader(device: &ID3D11Device, bytes: &[u8]) -> Result<ID3D11P
     }
 }
 
+/// A structured buffer that isn't tied to a pipeline, growing to fit the data written to it.
+struct StructuredBuffer<T> {
+    label: &'static str,
+    buffer: ID3D11Buffer,
+    buffer_size: usize,
+    view: Option<ID3D11ShaderResourceView>,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> StructuredBuffer<T> {
+    fn new(device: &ID3D11Device, label: &'static str, buffer_size: usize) -> Result<Self> {
+        let buffer = create_buffer(device, std::mem::size_of::<T>(), buffer_size)?;
+        let view = create_buffer_view(device, &buffer)?;
+        Ok(Self {
+            label,
+            buffer,
+            buffer_size,
+            view,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    fn update(
+        &mut self,
+        device: &ID3D11Device,
+        device_context: &ID3D11DeviceContext,
+        data: &[T],
+    ) -> Result<()> {
+        if self.buffer_size < data.len() {
+            let new_buffer_size = data.len().next_power_of_two();
+            log::info!(
+                "Updating {} buffer size from {} to {}",
+                self.label,
+                self.buffer_size,
+                new_buffer_size
+            );
+            let buffer = create_buffer(device, std::mem::size_of::<T>(), new_buffer_size)?;
+            let view = create_buffer_view(device, &buffer)?;
+            self.buffer = buffer;
+            self.view = view;
+            self.buffer_size = new_buffer_size;
+        }
+        update_buffer(device_context, &self.buffer, data)
+    }
+}
+
 #[inline]
 fn create_buffer(
     device: &ID3D11Device,
@@ -1406,10 +2001,14 @@ pub(crate) mod shader_resources {
     pub(crate) enum ShaderModule {
         Quad,
         Shadow,
+        BackdropHorizontalBlur,
+        BackdropVerticalBlur,
+        Overlay,
         Underline,
         PathRasterization,
         PathSprite,
         MonochromeSprite,
+        GradientSprite,
         PolychromeSprite,
         EmojiRasterization,
     }
@@ -1461,6 +2060,18 @@ pub(crate) mod shader_resources {
                     ShaderTarget::Vertex => SHADOW_VERTEX_BYTES,
                     ShaderTarget::Fragment => SHADOW_FRAGMENT_BYTES,
                 },
+                ShaderModule::BackdropHorizontalBlur => match target {
+                    ShaderTarget::Vertex => BACKDROP_HORIZONTAL_BLUR_VERTEX_BYTES,
+                    ShaderTarget::Fragment => BACKDROP_HORIZONTAL_BLUR_FRAGMENT_BYTES,
+                },
+                ShaderModule::BackdropVerticalBlur => match target {
+                    ShaderTarget::Vertex => BACKDROP_VERTICAL_BLUR_VERTEX_BYTES,
+                    ShaderTarget::Fragment => BACKDROP_VERTICAL_BLUR_FRAGMENT_BYTES,
+                },
+                ShaderModule::Overlay => match target {
+                    ShaderTarget::Vertex => OVERLAY_VERTEX_BYTES,
+                    ShaderTarget::Fragment => OVERLAY_FRAGMENT_BYTES,
+                },
                 ShaderModule::Underline => match target {
                     ShaderTarget::Vertex => UNDERLINE_VERTEX_BYTES,
                     ShaderTarget::Fragment => UNDERLINE_FRAGMENT_BYTES,
@@ -1477,6 +2088,10 @@ pub(crate) mod shader_resources {
                     ShaderTarget::Vertex => MONOCHROME_SPRITE_VERTEX_BYTES,
                     ShaderTarget::Fragment => MONOCHROME_SPRITE_FRAGMENT_BYTES,
                 },
+                ShaderModule::GradientSprite => match target {
+                    ShaderTarget::Vertex => GRADIENT_SPRITE_VERTEX_BYTES,
+                    ShaderTarget::Fragment => GRADIENT_SPRITE_FRAGMENT_BYTES,
+                },
                 ShaderModule::PolychromeSprite => match target {
                     ShaderTarget::Vertex => POLYCHROME_SPRITE_VERTEX_BYTES,
                     ShaderTarget::Fragment => POLYCHROME_SPRITE_FRAGMENT_BYTES,
@@ -1565,10 +2180,14 @@ pub(crate) mod shader_resources {
             match self {
                 ShaderModule::Quad => "quad",
                 ShaderModule::Shadow => "shadow",
+                ShaderModule::BackdropHorizontalBlur => "backdrop_horizontal_blur",
+                ShaderModule::BackdropVerticalBlur => "backdrop_vertical_blur",
+                ShaderModule::Overlay => "overlay",
                 ShaderModule::Underline => "underline",
                 ShaderModule::PathRasterization => "path_rasterization",
                 ShaderModule::PathSprite => "path_sprite",
                 ShaderModule::MonochromeSprite => "monochrome_sprite",
+                ShaderModule::GradientSprite => "gradient_sprite",
                 ShaderModule::PolychromeSprite => "polychrome_sprite",
                 ShaderModule::EmojiRasterization => "emoji_rasterization",
             }
@@ -10,7 +10,7 @@ use windows::{
         UI::{
             Controls::*,
             HiDpi::*,
-            Input::{Ime::*, KeyboardAndMouse::*},
+            Input::{Ime::*, KeyboardAndMouse::*, Touch::*},
             WindowsAndMessaging::*,
         },
     },
@@ -93,6 +93,8 @@ impl WindowsWindowInner {
             }
             WM_MOUSEWHEEL => self.handle_mouse_wheel_msg(handle, wparam, lparam),
             WM_MOUSEHWHEEL => self.handle_mouse_horizontal_wheel_msg(handle, wparam, lparam),
+            WM_GESTURENOTIFY => self.handle_gesture_notify_msg(handle),
+            WM_GESTURE => self.handle_gesture_msg(handle, lparam),
             WM_SYSKEYUP => self.handle_syskeyup_msg(wparam, lparam),
             WM_KEYUP => self.handle_keyup_msg(wparam, lparam),
             WM_GPUI_KEYDOWN => self.handle_keydown_msg(wparam, lparam),
@@ -424,6 +426,7 @@ impl WindowsWindowInner {
             modifiers: current_modifiers(),
             click_count,
             first_mouse: false,
+            pointer_kind: current_pointer_kind(),
         });
         let handled = !func(input).propagate;
         self.state.borrow_mut().callbacks.input = Some(func);
@@ -501,8 +504,24 @@ impl WindowsWindowInner {
         };
         drop(lock);
 
-        let wheel_distance =
-            (wparam.signed_hiword() as f32 / WHEEL_DELTA as f32) * wheel_scroll_amount as f32;
+        let wheel_ticks = wparam.signed_hiword() as f32 / WHEEL_DELTA as f32;
+        // The wheel can be configured to scroll a page per tick instead of a number of lines.
+        let scrolls_pages = wheel_scroll_amount == WHEEL_PAGESCROLL;
+        let wheel_distance = if scrolls_pages {
+            wheel_ticks
+        } else {
+            wheel_ticks * wheel_scroll_amount as f32
+        };
+        let wheel_distance = match modifiers.shift {
+            true => Point {
+                x: wheel_distance,
+                y: 0.0,
+            },
+            false => Point {
+                y: wheel_distance,
+                x: 0.0,
+            },
+        };
         let mut cursor_point = POINT {
             x: lparam.signed_loword().into(),
             y: lparam.signed_hiword().into(),
@@ -510,18 +529,14 @@ impl WindowsWindowInner {
         unsafe { ScreenToClient(handle, &mut cursor_point).ok().log_err() };
         let input = PlatformInput::ScrollWheel(ScrollWheelEvent {
             position: logical_point(cursor_point.x as f32, cursor_point.y as f32, scale_factor),
-            delta: ScrollDelta::Lines(match modifiers.shift {
-                true => Point {
-                    x: wheel_distance,
-                    y: 0.0,
-                },
-                false => Point {
-                    y: wheel_distance,
-                    x: 0.0,
-                },
-            }),
+            delta: if scrolls_pages {
+                ScrollDelta::Page(wheel_distance)
+            } else {
+                ScrollDelta::Lines(wheel_distance)
+            },
             modifiers,
             touch_phase: TouchPhase::Moved,
+            momentum_phase: None,
         });
         let handled = !func(input).propagate;
         self.state.borrow_mut().callbacks.input = Some(func);
@@ -561,6 +576,7 @@ impl WindowsWindowInner {
             }),
             modifiers: current_modifiers(),
             touch_phase: TouchPhase::Moved,
+            momentum_phase: None,
         });
         let handled = !func(event).propagate;
         self.state.borrow_mut().callbacks.input = Some(func);
@@ -568,6 +584,101 @@ impl WindowsWindowInner {
         if handled { Some(0) } else { Some(1) }
     }
 
+    fn handle_gesture_notify_msg(&self, handle: HWND) -> Option<isize> {
+        // Rotation gestures are off by default, the other gestures keep their default config.
+        let config = GESTURECONFIG {
+            dwID: GID_ROTATE,
+            dwWant: GC_ROTATE,
+            dwBlock: 0,
+        };
+        unsafe {
+            SetGestureConfig(
+                handle,
+                0,
+                &[config],
+                std::mem::size_of::<GESTURECONFIG>() as u32,
+            )
+        }
+        .log_err();
+        // Let the default handling finish setting up the gesture.
+        None
+    }
+
+    fn handle_gesture_msg(&self, handle: HWND, lparam: LPARAM) -> Option<isize> {
+        let gesture_handle = HGESTUREINFO(lparam.0 as _);
+        let mut info = GESTUREINFO {
+            cbSize: std::mem::size_of::<GESTUREINFO>() as u32,
+            ..Default::default()
+        };
+        unsafe { GetGestureInfo(gesture_handle, &mut info) }.log_err()?;
+        if info.dwID != GID_ZOOM.0 && info.dwID != GID_ROTATE.0 {
+            return None;
+        }
+
+        let mut lock = self.state.borrow_mut();
+        let phase = if info.dwFlags & GF_BEGIN != 0 {
+            TouchPhase::Started
+        } else if info.dwFlags & GF_END != 0 {
+            TouchPhase::Ended
+        } else {
+            TouchPhase::Moved
+        };
+        let previous_argument = match phase {
+            TouchPhase::Started => None,
+            TouchPhase::Moved | TouchPhase::Ended => lock.gesture_argument,
+        };
+        lock.gesture_argument = match phase {
+            TouchPhase::Ended => None,
+            TouchPhase::Started | TouchPhase::Moved => Some(info.ullArguments),
+        };
+        let scale_factor = lock.scale_factor;
+        let Some(mut func) = lock.callbacks.input.take() else {
+            unsafe { CloseGestureInfoHandle(gesture_handle) }.log_err();
+            return Some(0);
+        };
+        drop(lock);
+
+        let mut location = POINT {
+            x: info.ptsLocation.x.into(),
+            y: info.ptsLocation.y.into(),
+        };
+        unsafe { ScreenToClient(handle, &mut location).ok().log_err() };
+        let position = logical_point(location.x as f32, location.y as f32, scale_factor);
+        let modifiers = current_modifiers();
+
+        let input = if info.dwID == GID_ZOOM.0 {
+            // The argument is the distance between the touch points.
+            let scale_delta = match previous_argument {
+                Some(previous) if previous > 0 => info.ullArguments as f32 / previous as f32 - 1.,
+                _ => 0.,
+            };
+            PlatformInput::Pinch(PinchEvent {
+                position,
+                scale_delta,
+                modifiers,
+                phase,
+            })
+        } else {
+            // The argument encodes the counterclockwise angle since the gesture began.
+            let rotation_delta = previous_argument
+                .map(|previous| {
+                    rotation_from_argument(previous) - rotation_from_argument(info.ullArguments)
+                })
+                .unwrap_or(0.);
+            PlatformInput::RotateGesture(RotateGestureEvent {
+                position,
+                rotation_delta: radians(rotation_delta),
+                modifiers,
+                phase,
+            })
+        };
+        func(input);
+        self.state.borrow_mut().callbacks.input = Some(func);
+
+        unsafe { CloseGestureInfoHandle(gesture_handle) }.log_err();
+        Some(0)
+    }
+
     fn retrieve_caret_position(&self) -> Option<POINT> {
         self.with_input_handler_and_scale_factor(|input_handler, scale_factor| {
             let caret_range = input_handler.selected_text_range(false)?;
@@ -952,6 +1063,7 @@ impl WindowsWindowInner {
                 modifiers: current_modifiers(),
                 click_count,
                 first_mouse: false,
+                pointer_kind: current_pointer_kind(),
             });
             let result = func(input);
             let handled = !result.propagate || result.default_prevented;
@@ -1500,6 +1612,25 @@ pub(crate) fn current_modifiers() -> Modifiers {
     }
 }
 
+/// The kind of device that sent the mouse message being handled. Windows marks the mouse messages
+/// it synthesizes from touch input with a signature in the message's extra info.
+/// Decodes the angle of a rotate gesture, as `GID_ROTATE_ANGLE_FROM_ARGUMENT` does.
+fn rotation_from_argument(argument: u64) -> f32 {
+    ((argument as f64 / 65535.0) * 4.0 * std::f64::consts::PI - 2.0 * std::f64::consts::PI) as f32
+}
+
+fn current_pointer_kind() -> PointerKind {
+    const SIGNATURE_MASK: u32 = 0xFFFFFF00;
+    const MI_WP_SIGNATURE: u32 = 0xFF515700;
+    const TOUCH_FLAG: u32 = 0x80;
+    let extra_info = unsafe { GetMessageExtraInfo() }.0 as u32;
+    if extra_info & SIGNATURE_MASK == MI_WP_SIGNATURE && extra_info & TOUCH_FLAG != 0 {
+        PointerKind::Touch
+    } else {
+        PointerKind::Mouse
+    }
+}
+
 #[inline]
 pub(crate) fn current_capslock() -> Capslock {
     let on = unsafe { GetKeyState(VK_CAPITAL.0 as i32) & 1 } > 0;
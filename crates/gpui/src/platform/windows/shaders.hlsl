@@ -36,6 +36,13 @@ struct Hsla {
     float a;
 };
 
+struct HslaEdges {
+    Hsla top;
+    Hsla right;
+    Hsla bottom;
+    Hsla left;
+};
+
 struct LinearColorStop {
     Hsla color;
     float percentage;
@@ -45,6 +52,8 @@ struct Background {
     // 0u is Solid
     // 1u is LinearGradient
     // 2u is PatternSlash
+    // 3u is RadialGradient
+    // 4u is ConicGradient
     uint tag;
     // 0u is sRGB linear color
     // 1u is Oklab color
@@ -52,7 +61,9 @@ struct Background {
     Hsla solid;
     float gradient_angle_or_pattern_height;
     LinearColorStop colors[2];
-    uint pad;
+    float gradient_center_x;
+    float gradient_center_y;
+    float gradient_radius;
 };
 
 struct GradientColor {
@@ -243,6 +254,15 @@ float4 over(float4 below, float4 above) {
     return result;
 }
 
+// Multiply (1) and screen (2) are drawn with blend states that expect premultiplied colors, while
+// the other modes are drawn with straight alpha.
+float4 blend_mode_color(uint blend_mode, float4 color) {
+    if (blend_mode == 1 || blend_mode == 2) {
+        return float4(color.rgb * color.a, color.a);
+    }
+    return color;
+}
+
 float2 to_tile_position(float2 unit_vertex, AtlasTile tile) {
     float2 atlas_size;
     t_sprite.GetDimensions(atlas_size.x, atlas_size.y);
@@ -266,6 +286,33 @@ float pick_corner_radius(float2 center_to_point, Corners corner_radii) {
     }
 }
 
+// Returns the color of the border nearest to the point. Where two sides meet at
+// a corner, they are split along the diagonal from the outer corner to the
+// inner corner, like in CSS.
+float4 pick_border_color(float2 center_to_point, float2 corner_to_point,
+                         float2 border, HslaEdges colors,
+                         float antialias_threshold) {
+    Hsla horizontal_hsla = colors.bottom;
+    if (center_to_point.y < 0.0) {
+        horizontal_hsla = colors.top;
+    }
+    Hsla vertical_hsla = colors.right;
+    if (center_to_point.x < 0.0) {
+        vertical_hsla = colors.left;
+    }
+    float4 horizontal = hsla_to_rgba(horizontal_hsla);
+    float miter_length = length(border);
+    if (miter_length == 0.0) {
+        return horizontal;
+    }
+    float4 vertical = hsla_to_rgba(vertical_hsla);
+    // Signed distance of the point to the diagonal, positive on the side of the
+    // horizontal border.
+    float2 inset = -corner_to_point;
+    float miter_sdf = (inset.x * border.y - inset.y * border.x) / miter_length;
+    return lerp(vertical, horizontal, saturate(miter_sdf + antialias_threshold));
+}
+
 float4 to_device_position_transformed(float2 unit_vertex, Bounds bounds,
                                       TransformationMatrix transformation) {
     float2 position = unit_vertex * bounds.size + bounds.origin;
@@ -302,11 +349,87 @@ float quad_sdf(float2 pt, Bounds bounds, Corners corner_radii) {
     return quad_sdf_impl(corner_center_to_point, corner_radius);
 }
 
+struct ClipRegion {
+    Bounds bounds;
+    Corners corner_radii;
+    uint kind;
+    uint parent;
+    uint vertices_start;
+    uint vertex_count;
+};
+
+StructuredBuffer<ClipRegion> clip_regions: register(t2);
+StructuredBuffer<float2> clip_region_vertices: register(t3);
+
+// Approximate signed distance of the point to the ellipse inscribed in the bounds.
+float ellipse_sdf(float2 pt, Bounds bounds) {
+    float2 radii = bounds.size / 2.;
+    if (radii.x <= 0. || radii.y <= 0.) {
+        return 1.;
+    }
+    float2 center_to_point = pt - (bounds.origin + radii);
+    float k1 = length(center_to_point / radii);
+    float k2 = length(center_to_point / (radii * radii));
+    if (k2 == 0.) {
+        return -min(radii.x, radii.y);
+    }
+    return k1 * (k1 - 1.) / k2;
+}
+
+// Signed distance of the point to the polygon, which is negative inside of it
+// according to the even-odd rule.
+float polygon_sdf(float2 pt, uint vertices_start, uint vertex_count) {
+    if (vertex_count < 3) {
+        return 1.;
+    }
+    float2 previous = clip_region_vertices[vertices_start + vertex_count - 1];
+    float distance_squared = 1e30;
+    float side = 1.;
+    for (uint i = 0; i < vertex_count; i++) {
+        float2 vertex = clip_region_vertices[vertices_start + i];
+        float2 edge = previous - vertex;
+        float2 vertex_to_point = pt - vertex;
+        float t = saturate(dot(vertex_to_point, edge) / max(dot(edge, edge), 1e-6));
+        float2 edge_to_point = vertex_to_point - edge * t;
+        distance_squared = min(distance_squared, dot(edge_to_point, edge_to_point));
+        // Each edge crossed by a ray going right from the point toggles the side.
+        if ((vertex.y > pt.y) != (previous.y > pt.y)) {
+            float crossing_x = vertex.x + (pt.y - vertex.y) / (previous.y - vertex.y) * edge.x;
+            if (pt.x < crossing_x) {
+                side = -side;
+            }
+        }
+        previous = vertex;
+    }
+    return side * sqrt(distance_squared);
+}
+
+// The coverage of the point by the clip region with the given id and the
+// regions enclosing it. An id of 0 means that there is no clip region.
+float clip_region_alpha(uint clip_region, float2 pt) {
+    float alpha = 1.;
+    uint id = clip_region;
+    while (id != 0 && alpha > 0.) {
+        ClipRegion region = clip_regions[id - 1];
+        float distance;
+        if (region.kind == 1) {
+            distance = ellipse_sdf(pt, region.bounds);
+        } else if (region.kind == 2) {
+            distance = polygon_sdf(pt, region.vertices_start, region.vertex_count);
+        } else {
+            distance = quad_sdf(pt, region.bounds, region.corner_radii);
+        }
+        alpha *= saturate(0.5 - distance);
+        id = region.parent;
+    }
+    return alpha;
+}
+
 GradientColor prepare_gradient_color(uint tag, uint color_space, Hsla solid, LinearColorStop colors[2]) {
     GradientColor output;
     if (tag == 0 || tag == 2) {
         output.solid = hsla_to_rgba(solid);
-    } else if (tag == 1) {
+    } else {
         output.color0 = hsla_to_rgba(colors[0].color);
         output.color1 = hsla_to_rgba(colors[1].color);
 
@@ -322,6 +445,20 @@ GradientColor prepare_gradient_color(uint tag, uint color_space, Hsla solid, Lin
     return output;
 }
 
+// Returns the color at `t` along a gradient, adjusted by the stop percentages.
+float4 gradient_stops_color(Background background, float4 color0, float4 color1, float t) {
+    t = (t - background.colors[0].percentage)
+        / (background.colors[1].percentage
+        - background.colors[0].percentage);
+    t = clamp(t, 0.0, 1.0);
+
+    float4 color = lerp(color0, color1, t);
+    if (background.color_space == 1) {
+        color = oklab_to_srgb(color);
+    }
+    return color;
+}
+
 float2x2 rotate2d(float angle) {
     float s = sin(angle);
     float c = cos(angle);
@@ -363,22 +500,24 @@ float4 gradient_color(Background background,
                 t = (t + half_size.y) / bounds.size.y;
             }
 
-            // Adjust t based on the stop percentages
-            t = (t - background.colors[0].percentage)
-                / (background.colors[1].percentage
-                - background.colors[0].percentage);
-            t = clamp(t, 0.0, 1.0);
-
-            switch (background.color_space) {
-                case 0:
-                    color = lerp(color0, color1, t);
-                    break;
-                case 1: {
-                    float4 oklab_color = lerp(color0, color1, t);
-                    color = oklab_to_srgb(oklab_color);
-                    break;
-                }
-            }
+            color = gradient_stops_color(background, color0, color1, t);
+            break;
+        }
+        case 3: {
+            // Radial gradient, where a radius of 1 reaches the farthest corner.
+            float2 center = bounds.origin + float2(background.gradient_center_x, background.gradient_center_y) * bounds.size;
+            float2 farthest_corner = max(abs(center - bounds.origin), abs(bounds.origin + bounds.size - center));
+            float t = distance(position, center) / (background.gradient_radius * length(farthest_corner));
+            color = gradient_stops_color(background, color0, color1, t);
+            break;
+        }
+        case 4: {
+            // Conic gradient, where 0 degrees is up and angles increase clockwise.
+            float2 center = bounds.origin + float2(background.gradient_center_x, background.gradient_center_y) * bounds.size;
+            float2 center_to_point = position - center;
+            float angle = atan2(center_to_point.x, -center_to_point.y) * (180.0 / M_PI_F);
+            float t = frac((angle - background.gradient_angle_or_pattern_height) / 360.0);
+            color = gradient_stops_color(background, color0, color1, t);
             break;
         }
         case 2: {
@@ -465,28 +604,28 @@ struct Quad {
     Bounds bounds;
     Bounds content_mask;
     Background background;
-    Hsla border_color;
+    HslaEdges border_colors;
     Corners corner_radii;
     Edges border_widths;
+    uint clip_region;
+    uint blend_mode;
 };
 
 struct QuadVertexOutput {
     nointerpolation uint quad_id: TEXCOORD0;
     float4 position: SV_Position;
-    nointerpolation float4 border_color: COLOR0;
-    nointerpolation float4 background_solid: COLOR1;
-    nointerpolation float4 background_color0: COLOR2;
-    nointerpolation float4 background_color1: COLOR3;
+    nointerpolation float4 background_solid: COLOR0;
+    nointerpolation float4 background_color0: COLOR1;
+    nointerpolation float4 background_color1: COLOR2;
     float4 clip_distance: SV_ClipDistance;
 };
 
 struct QuadFragmentInput {
     nointerpolation uint quad_id: TEXCOORD0;
     float4 position: SV_Position;
-    nointerpolation float4 border_color: COLOR0;
-    nointerpolation float4 background_solid: COLOR1;
-    nointerpolation float4 background_color0: COLOR2;
-    nointerpolation float4 background_color1: COLOR3;
+    nointerpolation float4 background_solid: COLOR0;
+    nointerpolation float4 background_color0: COLOR1;
+    nointerpolation float4 background_color1: COLOR2;
 };
 
 StructuredBuffer<Quad> quads: register(t1);
@@ -503,11 +642,8 @@ QuadVertexOutput quad_vertex(uint vertex_id: SV_VertexID, uint quad_id: SV_Insta
         quad.background.colors
     );
     float4 clip_distance = distance_from_clip_rect(unit_vertex, quad.bounds, quad.content_mask);
-    float4 border_color = hsla_to_rgba(quad.border_color);
-
     QuadVertexOutput output;
     output.position = device_position;
-    output.border_color = border_color;
     output.quad_id = quad_id;
     output.background_solid = gradient.solid;
     output.background_color0 = gradient.color0;
@@ -518,6 +654,10 @@ QuadVertexOutput quad_vertex(uint vertex_id: SV_VertexID, uint quad_id: SV_Insta
 
 float4 quad_fragment(QuadFragmentInput input): SV_Target {
     Quad quad = quads[input.quad_id];
+    float clip_alpha = clip_region_alpha(quad.clip_region, input.position.xy);
+    if (clip_alpha <= 0.) {
+        return float4(0., 0., 0., 0.);
+    }
     float4 background_color = gradient_color(quad.background, input.position.xy, quad.bounds,
     input.background_solid, input.background_color0, input.background_color1);
 
@@ -532,7 +672,7 @@ float4 quad_fragment(QuadFragmentInput input): SV_Target {
         quad.border_widths.right == 0.0 &&
         quad.border_widths.bottom == 0.0 &&
         unrounded) {
-        return background_color;
+        return blend_mode_color(quad.blend_mode, background_color * float4(1.0, 1.0, 1.0, clip_alpha));
     }
 
     float2 size = quad.bounds.size;
@@ -591,7 +731,7 @@ float4 quad_fragment(QuadFragmentInput input): SV_Target {
 
     // Fast path for points that must be part of the background
     if (is_within_inner_straight_border && !is_near_rounded_corner) {
-        return background_color;
+        return blend_mode_color(quad.blend_mode, background_color * float4(1.0, 1.0, 1.0, clip_alpha));
     }
 
     // Signed distance of the point to the outside edge of the quad's border
@@ -626,7 +766,8 @@ float4 quad_fragment(QuadFragmentInput input): SV_Target {
 
     float4 color = background_color;
     if (border_sdf < antialias_threshold) {
-        float4 border_color = input.border_color;
+        float4 border_color = pick_border_color(center_to_point, corner_to_point,
+            border, quad.border_colors, antialias_threshold);
         // Dashed border logic when border_style == 1
         if (quad.border_style == 1) {
             // Position along the perimeter in "dash space", where each dash
@@ -804,7 +945,7 @@ float4 quad_fragment(QuadFragmentInput input): SV_Target {
                     saturate(antialias_threshold - inner_sdf));
     }
 
-    return color * float4(1.0, 1.0, 1.0, saturate(antialias_threshold - outer_sdf));
+    return blend_mode_color(quad.blend_mode, color * float4(1.0, 1.0, 1.0, saturate(antialias_threshold - outer_sdf) * clip_alpha));
 }
 
 /*
@@ -820,6 +961,10 @@ struct Shadow {
     Corners corner_radii;
     Bounds content_mask;
     Hsla color;
+    Bounds clip_bounds;
+    Corners clip_corner_radii;
+    uint inset;
+    uint clip_region;
 };
 
 struct ShadowVertexOutput {
@@ -841,10 +986,15 @@ ShadowVertexOutput shadow_vertex(uint vertex_id: SV_VertexID, uint shadow_id: SV
     float2 unit_vertex = float2(float(vertex_id & 1u), 0.5 * float(vertex_id & 2u));
     Shadow shadow = shadows[shadow_id];
 
-    float margin = 3.0 * shadow.blur_radius;
     Bounds bounds = shadow.bounds;
-    bounds.origin -= margin;
-    bounds.size += 2.0 * margin;
+    if (shadow.inset != 0) {
+        // Inset shadows are only painted within the element's bounds
+        bounds = shadow.clip_bounds;
+    } else {
+        float margin = 3.0 * shadow.blur_radius;
+        bounds.origin -= margin;
+        bounds.size += 2.0 * margin;
+    }
 
     float4 device_position = to_device_position(unit_vertex, bounds);
     float4 clip_distance = distance_from_clip_rect(unit_vertex, bounds, shadow.content_mask);
@@ -867,26 +1017,137 @@ float4 shadow_fragment(ShadowFragmentInput input): SV_TARGET {
     float2 point0 = input.position.xy - center;
     float corner_radius = pick_corner_radius(point0, shadow.corner_radii);
 
-    // The signal is only non-zero in a limited range, so don't waste samples
-    float low = point0.y - half_size.y;
-    float high = point0.y + half_size.y;
-    float start = clamp(-3. * shadow.blur_radius, low, high);
-    float end = clamp(3. * shadow.blur_radius, low, high);
-
-    // Accumulate samples (we can get away with surprisingly few samples)
-    float step = (end - start) / 4.;
-    float y = start + step * 0.5;
     float alpha = 0.;
-    for (int i = 0; i < 4; i++) {
-        alpha += blur_along_x(point0.x, point0.y - y, shadow.blur_radius,
-                            corner_radius, half_size) *
-                gaussian(y, shadow.blur_radius) * step;
-        y += step;
+    if (shadow.blur_radius == 0.) {
+        float distance = quad_sdf(input.position.xy, shadow.bounds, shadow.corner_radii);
+        alpha = saturate(0.5 - distance);
+    } else {
+        // The signal is only non-zero in a limited range, so don't waste samples
+        float low = point0.y - half_size.y;
+        float high = point0.y + half_size.y;
+        float start = clamp(-3. * shadow.blur_radius, low, high);
+        float end = clamp(3. * shadow.blur_radius, low, high);
+
+        // Accumulate samples (we can get away with surprisingly few samples)
+        float step = (end - start) / 4.;
+        float y = start + step * 0.5;
+        for (int i = 0; i < 4; i++) {
+            alpha += blur_along_x(point0.x, point0.y - y, shadow.blur_radius,
+                                corner_radius, half_size) *
+                    gaussian(y, shadow.blur_radius) * step;
+            y += step;
+        }
+    }
+
+    if (shadow.inset != 0) {
+        // Inset shadows are cast by everything outside of the shadow's rounded
+        // rect, clipped to the inside of the element's.
+        float clip_distance = quad_sdf(input.position.xy, shadow.clip_bounds,
+                                       shadow.clip_corner_radii);
+        alpha = (1. - alpha) * saturate(0.5 - clip_distance);
     }
+    alpha *= clip_region_alpha(shadow.clip_region, input.position.xy);
 
     return input.color * float4(1., 1., 1., alpha);
 }
 
+/*
+**
+**              Backdrops
+**
+*/
+
+struct Backdrop {
+    uint order;
+    float blur_radius;
+    Bounds bounds;
+    Corners corner_radii;
+    Bounds content_mask;
+    Hsla fallback_color;
+    uint clip_region;
+    uint pad;
+};
+
+struct BackdropVertexOutput {
+    nointerpolation uint backdrop_id: TEXCOORD0;
+    float4 position: SV_Position;
+    float4 clip_distance: SV_ClipDistance;
+};
+
+struct BackdropFragmentInput {
+    nointerpolation uint backdrop_id: TEXCOORD0;
+    float4 position: SV_Position;
+};
+
+StructuredBuffer<Backdrop> backdrops: register(t1);
+
+// Blurs `t_sprite` along one direction around the given position, using a gaussian
+// with the given sigma. Samples are spread out for large radii to bound their count.
+float4 blur_backdrop(float2 position, float2 direction, float sigma) {
+    float2 texture_size;
+    t_sprite.GetDimensions(texture_size.x, texture_size.y);
+    // The sampler wraps, so keep samples from bleeding in from the opposite edge.
+    float2 min_coords = 0.5 / texture_size;
+    float2 max_coords = 1.0 - min_coords;
+    float reach = ceil(3.0 * sigma);
+    float step = max(1.0, reach / 16.0);
+    float4 color = float4(0.0, 0.0, 0.0, 0.0);
+    float total_weight = 0.0;
+    for (float offset = -reach; offset <= reach; offset += step) {
+        float weight = gaussian(offset, sigma);
+        float2 coords = clamp((position + direction * offset) / texture_size, min_coords, max_coords);
+        color += weight * t_sprite.SampleLevel(s_sprite, coords, 0.0);
+        total_weight += weight;
+    }
+    return color / total_weight;
+}
+
+// The horizontal pass covers the visible part of the backdrop, extended vertically by the
+// reach of the blur so that the vertical pass has every row it samples.
+BackdropVertexOutput backdrop_horizontal_blur_vertex(uint vertex_id: SV_VertexID, uint backdrop_id: SV_InstanceID) {
+    float2 unit_vertex = float2(float(vertex_id & 1u), 0.5 * float(vertex_id & 2u));
+    Backdrop backdrop = backdrops[backdrop_id];
+
+    float margin = 3.0 * backdrop.blur_radius;
+    float2 top_left = max(backdrop.bounds.origin, backdrop.content_mask.origin);
+    float2 bottom_right = min(backdrop.bounds.origin + backdrop.bounds.size,
+                              backdrop.content_mask.origin + backdrop.content_mask.size);
+    Bounds bounds;
+    bounds.origin = top_left - float2(0.0, margin);
+    bounds.size = max(bottom_right - top_left, 0.0) + float2(0.0, 2.0 * margin);
+
+    BackdropVertexOutput output;
+    output.position = to_device_position(unit_vertex, bounds);
+    output.backdrop_id = backdrop_id;
+    output.clip_distance = float4(0.0, 0.0, 0.0, 0.0);
+    return output;
+}
+
+float4 backdrop_horizontal_blur_fragment(BackdropFragmentInput input): SV_Target {
+    Backdrop backdrop = backdrops[input.backdrop_id];
+    return blur_backdrop(input.position.xy, float2(1.0, 0.0), backdrop.blur_radius);
+}
+
+BackdropVertexOutput backdrop_vertical_blur_vertex(uint vertex_id: SV_VertexID, uint backdrop_id: SV_InstanceID) {
+    float2 unit_vertex = float2(float(vertex_id & 1u), 0.5 * float(vertex_id & 2u));
+    Backdrop backdrop = backdrops[backdrop_id];
+
+    BackdropVertexOutput output;
+    output.position = to_device_position(unit_vertex, backdrop.bounds);
+    output.backdrop_id = backdrop_id;
+    output.clip_distance = distance_from_clip_rect(unit_vertex, backdrop.bounds, backdrop.content_mask);
+    return output;
+}
+
+float4 backdrop_vertical_blur_fragment(BackdropFragmentInput input): SV_Target {
+    Backdrop backdrop = backdrops[input.backdrop_id];
+    float distance = quad_sdf(input.position.xy, backdrop.bounds, backdrop.corner_radii);
+    float coverage = saturate(0.5 - distance)
+        * clip_region_alpha(backdrop.clip_region, input.position.xy);
+    float4 color = blur_backdrop(input.position.xy, float2(0.0, 1.0), backdrop.blur_radius);
+    return color * coverage;
+}
+
 /*
 **
 **              Path Rasterization
@@ -898,6 +1159,8 @@ struct PathRasterizationSprite {
     float2 st_position;
     Background color;
     Bounds bounds;
+    uint clip_region;
+    uint pad;
 };
 
 StructuredBuffer<PathRasterizationSprite> path_rasterization_sprites: register(t1);
@@ -944,6 +1207,7 @@ float4 path_rasterization_fragment(PathFragmentInput input): SV_Target {
         float distance = f / length(gradient);
         alpha = saturate(0.5 - distance);
     }
+    alpha *= clip_region_alpha(sprite.clip_region, input.position.xy);
 
     GradientColor gradient = prepare_gradient_color(
         background.tag, background.color_space, background.solid, background.colors);
@@ -990,6 +1254,35 @@ float4 path_sprite_fragment(PathSpriteVertexOutput input): SV_Target {
     return t_sprite.Sample(s_sprite, input.texture_coords);
 }
 
+/*
+**
+**              Overlays
+**
+*/
+
+Texture2D<float4> t_overlay_backdrop: register(t4);
+
+// Covers the bounds of an overlay blended layer, which is given as a path sprite.
+PathSpriteVertexOutput overlay_vertex(uint vertex_id: SV_VertexID, uint sprite_id: SV_InstanceID) {
+    return path_sprite_vertex(vertex_id, sprite_id);
+}
+
+// Composites a layer painted with premultiplied colors over a copy of what's behind it.
+float4 overlay_fragment(PathSpriteVertexOutput input): SV_Target {
+    int3 position = int3(input.position.xy, 0);
+    float4 layer = t_sprite.Load(position);
+    if (layer.a <= 0.0) {
+        return float4(0.0, 0.0, 0.0, 0.0);
+    }
+    float4 backdrop = t_overlay_backdrop.Load(position);
+    float3 source = saturate(layer.rgb / layer.a);
+    float3 behind = backdrop.a > 0.0 ? saturate(backdrop.rgb / backdrop.a) : float3(0.0, 0.0, 0.0);
+    float3 multiplied = 2.0 * source * behind;
+    float3 screened = 1.0 - 2.0 * (1.0 - source) * (1.0 - behind);
+    float3 overlay = behind <= 0.5 ? multiplied : screened;
+    return float4(lerp(source, overlay, backdrop.a) * layer.a, layer.a);
+}
+
 /*
 **
 **              Underlines
@@ -998,12 +1291,12 @@ float4 path_sprite_fragment(PathSpriteVertexOutput input): SV_Target {
 
 struct Underline {
     uint order;
-    uint pad;
+    uint clip_region;
     Bounds bounds;
     Bounds content_mask;
     Hsla color;
     float thickness;
-    uint wavy;
+    uint style;
 };
 
 struct UnderlineVertexOutput {
@@ -1042,7 +1335,8 @@ float4 underline_fragment(UnderlineFragmentInput input): SV_Target {
     const float WAVE_HEIGHT_RATIO = 0.8;
 
     Underline underline = underlines[input.underline_id];
-    if (underline.wavy) {
+    float clip_alpha = clip_region_alpha(underline.clip_region, input.position.xy);
+    if (underline.style == 1) {
         float half_thickness = underline.thickness * 0.5;
         float2 origin = underline.bounds.origin;
 
@@ -1058,9 +1352,18 @@ float4 underline_fragment(UnderlineFragmentInput input): SV_Target {
         float distance_from_bottom_border = distance_in_pixels + half_thickness;
         float alpha = saturate(
             0.5 - max(-distance_from_bottom_border, distance_from_top_border));
-        return input.color * float4(1., 1., 1., alpha);
+        return input.color * float4(1., 1., 1., alpha * clip_alpha);
+    } else if (underline.style == 2 || underline.style == 3) {
+        // Dotted lines alternate square dots with equal gaps, dashed lines alternate
+        // dashes three times as long as the line is thick with gaps twice as long.
+        bool dotted = underline.style == 2;
+        float dash_length = dotted ? underline.thickness : underline.thickness * 3.;
+        float gap_length = dotted ? underline.thickness : underline.thickness * 2.;
+        float x = input.position.x - underline.bounds.origin.x;
+        float alpha = fmod(x, dash_length + gap_length) < dash_length ? 1. : 0.;
+        return input.color * float4(1., 1., 1., alpha * clip_alpha);
     } else {
-        return input.color;
+        return input.color * float4(1., 1., 1., clip_alpha);
     }
 }
 
@@ -1072,17 +1375,20 @@ float4 underline_fragment(UnderlineFragmentInput input): SV_Target {
 
 struct MonochromeSprite {
     uint order;
-    uint pad;
+    uint clip_region;
     Bounds bounds;
     Bounds content_mask;
     Hsla color;
     AtlasTile tile;
     TransformationMatrix transformation;
+    uint blend_mode;
+    uint pad;
 };
 
 struct MonochromeSpriteVertexOutput {
     float4 position: SV_Position;
     float2 tile_position: POSITION;
+    nointerpolation uint sprite_id: TEXCOORD0;
     nointerpolation float4 color: COLOR;
     float4 clip_distance: SV_ClipDistance;
 };
@@ -1090,6 +1396,7 @@ struct MonochromeSpriteVertexOutput {
 struct MonochromeSpriteFragmentInput {
     float4 position: SV_Position;
     float2 tile_position: POSITION;
+    nointerpolation uint sprite_id: TEXCOORD0;
     nointerpolation float4 color: COLOR;
     float4 clip_distance: SV_ClipDistance;
 };
@@ -1108,6 +1415,7 @@ MonochromeSpriteVertexOutput monochrome_sprite_vertex(uint vertex_id: SV_VertexI
     MonochromeSpriteVertexOutput output;
     output.position = device_position;
     output.tile_position = tile_position;
+    output.sprite_id = sprite_id;
     output.color = color;
     output.clip_distance = clip_distance;
     return output;
@@ -1116,7 +1424,88 @@ MonochromeSpriteVertexOutput monochrome_sprite_vertex(uint vertex_id: SV_VertexI
 float4 monochrome_sprite_fragment(MonochromeSpriteFragmentInput input): SV_Target {
     float sample = t_sprite.Sample(s_sprite, input.tile_position).r;
     float alpha_corrected = apply_contrast_and_gamma_correction(sample, input.color.rgb, grayscale_enhanced_contrast, gamma_ratios);
-    return float4(input.color.rgb, input.color.a * alpha_corrected);
+    MonochromeSprite sprite = mono_sprites[input.sprite_id];
+    float clip_alpha = clip_region_alpha(sprite.clip_region, input.position.xy);
+    return blend_mode_color(sprite.blend_mode, float4(input.color.rgb, input.color.a * alpha_corrected * clip_alpha));
+}
+
+/*
+**
+**              Gradient sprites
+**
+*/
+
+struct GradientSprite {
+    uint order;
+    uint clip_region;
+    Bounds bounds;
+    Bounds content_mask;
+    Background color;
+    AtlasTile tile;
+    TransformationMatrix transformation;
+    uint blend_mode;
+    uint pad;
+};
+
+struct GradientSpriteVertexOutput {
+    float4 position: SV_Position;
+    float2 tile_position: POSITION;
+    float2 sprite_position: TEXCOORD1;
+    nointerpolation uint sprite_id: TEXCOORD0;
+    nointerpolation float4 color: COLOR0;
+    nointerpolation float4 color0: COLOR1;
+    nointerpolation float4 color1: COLOR2;
+    float4 clip_distance: SV_ClipDistance;
+};
+
+struct GradientSpriteFragmentInput {
+    float4 position: SV_Position;
+    float2 tile_position: POSITION;
+    float2 sprite_position: TEXCOORD1;
+    nointerpolation uint sprite_id: TEXCOORD0;
+    nointerpolation float4 color: COLOR0;
+    nointerpolation float4 color0: COLOR1;
+    nointerpolation float4 color1: COLOR2;
+    float4 clip_distance: SV_ClipDistance;
+};
+
+StructuredBuffer<GradientSprite> gradient_sprites: register(t1);
+
+GradientSpriteVertexOutput gradient_sprite_vertex(uint vertex_id: SV_VertexID, uint sprite_id: SV_InstanceID) {
+    float2 unit_vertex = float2(float(vertex_id & 1u), 0.5 * float(vertex_id & 2u));
+    GradientSprite sprite = gradient_sprites[sprite_id];
+    float4 device_position =
+        to_device_position_transformed(unit_vertex, sprite.bounds, sprite.transformation);
+    float4 clip_distance = distance_from_clip_rect_transformed(unit_vertex, sprite.bounds, sprite.content_mask, sprite.transformation);
+    float2 tile_position = to_tile_position(unit_vertex, sprite.tile);
+    GradientColor gradient = prepare_gradient_color(
+        sprite.color.tag,
+        sprite.color.color_space,
+        sprite.color.solid,
+        sprite.color.colors
+    );
+
+    GradientSpriteVertexOutput output;
+    output.position = device_position;
+    output.tile_position = tile_position;
+    // Gradients are laid out across the untransformed bounds, so they follow the transformation.
+    output.sprite_position = sprite.bounds.origin + unit_vertex * sprite.bounds.size;
+    output.sprite_id = sprite_id;
+    output.color = gradient.solid;
+    output.color0 = gradient.color0;
+    output.color1 = gradient.color1;
+    output.clip_distance = clip_distance;
+    return output;
+}
+
+float4 gradient_sprite_fragment(GradientSpriteFragmentInput input): SV_Target {
+    float sample = t_sprite.Sample(s_sprite, input.tile_position).r;
+    GradientSprite sprite = gradient_sprites[input.sprite_id];
+    float4 color = gradient_color(sprite.color, input.sprite_position, sprite.bounds,
+        input.color, input.color0, input.color1);
+    float alpha_corrected = apply_contrast_and_gamma_correction(sample, color.rgb, grayscale_enhanced_contrast, gamma_ratios);
+    float clip_alpha = clip_region_alpha(sprite.clip_region, input.position.xy);
+    return blend_mode_color(sprite.blend_mode, float4(color.rgb, color.a * alpha_corrected * clip_alpha));
 }
 
 /*
@@ -1127,19 +1516,23 @@ float4 monochrome_sprite_fragment(MonochromeSpriteFragmentInput input): SV_Targe
 
 struct PolychromeSprite {
     uint order;
-    uint pad;
+    uint clip_region;
     uint grayscale;
     float opacity;
     Bounds bounds;
     Bounds content_mask;
     Corners corner_radii;
     AtlasTile tile;
+    TransformationMatrix transformation;
+    uint blend_mode;
+    uint pad;
 };
 
 struct PolychromeSpriteVertexOutput {
     nointerpolation uint sprite_id: TEXCOORD0;
     float4 position: SV_Position;
     float2 tile_position: POSITION;
+    float2 sprite_position: TEXCOORD1;
     float4 clip_distance: SV_ClipDistance;
 };
 
@@ -1147,6 +1540,7 @@ struct PolychromeSpriteFragmentInput {
     nointerpolation uint sprite_id: TEXCOORD0;
     float4 position: SV_Position;
     float2 tile_position: POSITION;
+    float2 sprite_position: TEXCOORD1;
 };
 
 StructuredBuffer<PolychromeSprite> poly_sprites: register(t1);
@@ -1154,14 +1548,16 @@ StructuredBuffer<PolychromeSprite> poly_sprites: register(t1);
 PolychromeSpriteVertexOutput polychrome_sprite_vertex(uint vertex_id: SV_VertexID, uint sprite_id: SV_InstanceID) {
     float2 unit_vertex = float2(float(vertex_id & 1u), 0.5 * float(vertex_id & 2u));
     PolychromeSprite sprite = poly_sprites[sprite_id];
-    float4 device_position = to_device_position(unit_vertex, sprite.bounds);
-    float4 clip_distance = distance_from_clip_rect(unit_vertex, sprite.bounds,
-                                                    sprite.content_mask);
+    float4 device_position =
+        to_device_position_transformed(unit_vertex, sprite.bounds, sprite.transformation);
+    float4 clip_distance = distance_from_clip_rect_transformed(unit_vertex, sprite.bounds, sprite.content_mask, sprite.transformation);
     float2 tile_position = to_tile_position(unit_vertex, sprite.tile);
 
     PolychromeSpriteVertexOutput output;
     output.position = device_position;
     output.tile_position = tile_position;
+    // Corners are rounded in the untransformed bounds, so they follow the transformation.
+    output.sprite_position = sprite.bounds.origin + unit_vertex * sprite.bounds.size;
     output.sprite_id = sprite_id;
     output.clip_distance = clip_distance;
     return output;
@@ -1170,13 +1566,14 @@ PolychromeSpriteVertexOutput polychrome_sprite_vertex(uint vertex_id: SV_VertexI
 float4 polychrome_sprite_fragment(PolychromeSpriteFragmentInput input): SV_Target {
     PolychromeSprite sprite = poly_sprites[input.sprite_id];
     float4 sample = t_sprite.Sample(s_sprite, input.tile_position);
-    float distance = quad_sdf(input.position.xy, sprite.bounds, sprite.corner_radii);
+    float distance = quad_sdf(input.sprite_position, sprite.bounds, sprite.corner_radii);
 
     float4 color = sample;
     if ((sprite.grayscale & 0xFFu) != 0u) {
         float3 grayscale = dot(color.rgb, GRAYSCALE_FACTORS);
         color = float4(grayscale, sample.a);
     }
-    color.a *= sprite.opacity * saturate(0.5 - distance);
-    return color;
+    color.a *= sprite.opacity * saturate(0.5 - distance)
+        * clip_region_alpha(sprite.clip_region, input.position.xy);
+    return blend_mode_color(sprite.blend_mode, color);
 }
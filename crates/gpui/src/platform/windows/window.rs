@@ -13,6 +13,7 @@ use std::{
 use ::util::ResultExt;
 use anyhow::{Context as _, Result};
 use futures::channel::oneshot::{self, Receiver};
+use image::RgbaImage;
 use raw_window_handle as rwh;
 use smallvec::SmallVec;
 use windows::{
@@ -50,6 +51,8 @@ pub struct WindowsWindowState {
     pub renderer: DirectXRenderer,
 
     pub click_state: ClickState,
+    /// The argument of the previous zoom or rotate gesture message, while one is in progress.
+    pub gesture_argument: Option<u64>,
     pub current_cursor: Option<HCURSOR>,
     pub nc_button_pressed: Option<u32>,
 
@@ -116,6 +119,7 @@ impl WindowsWindowState {
         let last_reported_capslock = None;
         let hovered = false;
         let click_state = ClickState::new();
+        let gesture_argument = None;
         let nc_button_pressed = None;
         let fullscreen = None;
         let initial_placement = None;
@@ -137,6 +141,7 @@ impl WindowsWindowState {
             hovered,
             renderer,
             click_state,
+            gesture_argument,
             current_cursor,
             nc_button_pressed,
             display,
@@ -879,11 +884,119 @@ impl PlatformWindow for WindowsWindow {
         self.0.state.borrow().renderer.gpu_specs().log_err()
     }
 
+    fn render_to_image(&self, scene: &Scene, bounds: Bounds<DevicePixels>) -> Option<RgbaImage> {
+        self.0
+            .state
+            .borrow_mut()
+            .renderer
+            .render_to_image(scene, bounds)
+            .log_err()
+    }
+
+    fn start_drag_out(&self, paths: &[PathBuf], image: Option<DragImage>) -> bool {
+        if paths.is_empty() {
+            return false;
+        }
+        let Some(data_object) = file_data_object(paths).log_err() else {
+            return false;
+        };
+        if let Some(image) = image {
+            // Without a drag image on the data object, the shell shows the file icons instead.
+            set_drag_image(&self.0.drop_target_helper, &data_object, &image)
+                .context("setting the drag image")
+                .log_err();
+        }
+        let hwnd = self.0.hwnd;
+        // The shell runs a modal loop until the files are dropped, so start it once the current
+        // mouse event has been handled. It also provides the drop source.
+        self.0
+            .executor
+            .spawn(async move {
+                unsafe { SHDoDragDrop(Some(hwnd), &data_object, None, DROPEFFECT_COPY) }
+                    .context("dragging files out of the window")
+                    .log_err();
+            })
+            .detach();
+        true
+    }
+
     fn update_ime_position(&self, _bounds: Bounds<Pixels>) {
         // There is no such thing on Windows.
     }
 }
 
+fn file_data_object(paths: &[PathBuf]) -> Result<IDataObject> {
+    let mut pidls = Vec::with_capacity(paths.len());
+    for path in paths {
+        let pidl = unsafe { ILCreateFromPathW(&HSTRING::from(path.as_os_str())) };
+        if pidl.is_null() {
+            for pidl in pidls {
+                unsafe { ILFree(Some(pidl)) };
+            }
+            anyhow::bail!("no shell item for dragged path {path:?}");
+        }
+        pidls.push(pidl.cast_const());
+    }
+    let items = unsafe { SHCreateShellItemArrayFromIDLists(&pidls) };
+    for pidl in pidls {
+        unsafe { ILFree(Some(pidl)) };
+    }
+    let data_object = unsafe { items?.BindToHandler(None, &BHID_DataObject)? };
+    Ok(data_object)
+}
+
+fn set_drag_image(
+    drag_drop_helper: &IDropTargetHelper,
+    data_object: &IDataObject,
+    image: &DragImage,
+) -> Result<()> {
+    let width = image.image.width() as i32;
+    let height = image.image.height() as i32;
+    let info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            // Top-down rows, like the image.
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut bits = std::ptr::null_mut();
+    let bitmap = unsafe { CreateDIBSection(None, &info, DIB_RGB_COLORS, &mut bits, None, 0) }?;
+    // The shell expects premultiplied BGRA.
+    let pixels =
+        unsafe { std::slice::from_raw_parts_mut(bits as *mut u8, (width * height * 4) as usize) };
+    for (dst, src) in pixels.chunks_exact_mut(4).zip(image.image.pixels()) {
+        let [r, g, b, a] = src.0;
+        let premultiply = |channel: u8| (channel as u16 * a as u16 / 255) as u8;
+        dst.copy_from_slice(&[premultiply(b), premultiply(g), premultiply(r), a]);
+    }
+    let drag_image = SHDRAGIMAGE {
+        sizeDragImage: SIZE {
+            cx: width,
+            cy: height,
+        },
+        ptOffset: POINT {
+            x: image.cursor_offset.x.0,
+            y: image.cursor_offset.y.0,
+        },
+        hbmpDragImage: bitmap,
+        crColorKey: COLORREF(u32::MAX),
+    };
+    let drag_source_helper: IDragSourceHelper = drag_drop_helper.cast()?;
+    let result = unsafe { drag_source_helper.InitializeFromBitmap(&drag_image, data_object) };
+    if result.is_err() {
+        // The helper only takes ownership of the bitmap when it succeeds.
+        unsafe { DeleteObject(bitmap.into()) }.ok().log_err();
+    }
+    result?;
+    Ok(())
+}
+
 #[implement(IDropTarget)]
 struct WindowsDragDropHandler(pub Rc<WindowsWindowInner>);
 
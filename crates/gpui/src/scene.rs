@@ -5,14 +5,15 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    AtlasTextureId, AtlasTile, Background, Bounds, ContentMask, Corners, Edges, Hsla, Pixels,
-    Point, Radians, ScaledPixels, Size, bounds_tree::BoundsTree, point,
+    AtlasTextureId, AtlasTile, Background, Bounds, ContentMask, Corners, DevicePixels, Edges, Hsla,
+    Pixels, Point, Radians, ScaledPixels, Size, bounds_tree::BoundsTree, point,
 };
 use std::{
     fmt::Debug,
     iter::Peekable,
     ops::{Add, Range, Sub},
     slice,
+    sync::Once,
 };
 
 #[allow(non_camel_case_types, unused)]
@@ -25,11 +26,16 @@ pub(crate) struct Scene {
     pub(crate) paint_operations: Vec<PaintOperation>,
     primitive_bounds: BoundsTree<ScaledPixels>,
     layer_stack: Vec<DrawOrder>,
+    clip_region_stack: Vec<u32>,
+    pub(crate) clip_regions: Vec<ClipRegion>,
+    pub(crate) clip_region_vertices: Vec<Point<ScaledPixels>>,
     pub(crate) shadows: Vec<Shadow>,
+    pub(crate) backdrops: Vec<Backdrop>,
     pub(crate) quads: Vec<Quad>,
     pub(crate) paths: Vec<Path<ScaledPixels>>,
     pub(crate) underlines: Vec<Underline>,
     pub(crate) monochrome_sprites: Vec<MonochromeSprite>,
+    pub(crate) gradient_sprites: Vec<GradientSprite>,
     pub(crate) polychrome_sprites: Vec<PolychromeSprite>,
     pub(crate) surfaces: Vec<PaintSurface>,
 }
@@ -39,11 +45,16 @@ impl Scene {
         self.paint_operations.clear();
         self.primitive_bounds.clear();
         self.layer_stack.clear();
+        self.clip_region_stack.clear();
+        self.clip_regions.clear();
+        self.clip_region_vertices.clear();
         self.paths.clear();
         self.shadows.clear();
+        self.backdrops.clear();
         self.quads.clear();
         self.underlines.clear();
         self.monochrome_sprites.clear();
+        self.gradient_sprites.clear();
         self.polychrome_sprites.clear();
         self.surfaces.clear();
     }
@@ -64,6 +75,25 @@ impl Scene {
         self.paint_operations.push(PaintOperation::EndLayer);
     }
 
+    /// Clips the primitives inserted until the matching [`Self::pop_clip_region`] to the given
+    /// region, within the region that's currently pushed, if any. The region's `parent` and
+    /// vertex range are assigned here.
+    pub fn push_clip_region(&mut self, mut region: ClipRegion, vertices: &[Point<ScaledPixels>]) {
+        region.parent = self.clip_region_stack.last().copied().unwrap_or(0);
+        region.vertices_start = self.clip_region_vertices.len() as u32;
+        region.vertex_count = vertices.len() as u32;
+        self.clip_region_vertices.extend_from_slice(vertices);
+        self.clip_regions.push(region.clone());
+        self.clip_region_stack.push(self.clip_regions.len() as u32);
+        self.paint_operations
+            .push(PaintOperation::StartClipRegion(region));
+    }
+
+    pub fn pop_clip_region(&mut self) {
+        self.clip_region_stack.pop();
+        self.paint_operations.push(PaintOperation::EndClipRegion);
+    }
+
     pub fn insert_primitive(&mut self, primitive: impl Into<Primitive>) {
         let mut primitive = primitive.into();
         let clipped_bounds = primitive
@@ -79,30 +109,47 @@ impl Scene {
             .last()
             .copied()
             .unwrap_or_else(|| self.primitive_bounds.insert(clipped_bounds));
+        let clip_region = self.clip_region_stack.last().copied().unwrap_or(0);
         match &mut primitive {
             Primitive::Shadow(shadow) => {
                 shadow.order = order;
+                shadow.clip_region = clip_region;
                 self.shadows.push(shadow.clone());
             }
+            Primitive::Backdrop(backdrop) => {
+                backdrop.order = order;
+                backdrop.clip_region = clip_region;
+                self.backdrops.push(backdrop.clone());
+            }
             Primitive::Quad(quad) => {
                 quad.order = order;
+                quad.clip_region = clip_region;
                 self.quads.push(quad.clone());
             }
             Primitive::Path(path) => {
                 path.order = order;
+                path.clip_region = clip_region;
                 path.id = PathId(self.paths.len());
                 self.paths.push(path.clone());
             }
             Primitive::Underline(underline) => {
                 underline.order = order;
+                underline.clip_region = clip_region;
                 self.underlines.push(underline.clone());
             }
             Primitive::MonochromeSprite(sprite) => {
                 sprite.order = order;
+                sprite.clip_region = clip_region;
                 self.monochrome_sprites.push(sprite.clone());
             }
+            Primitive::GradientSprite(sprite) => {
+                sprite.order = order;
+                sprite.clip_region = clip_region;
+                self.gradient_sprites.push(sprite.clone());
+            }
             Primitive::PolychromeSprite(sprite) => {
                 sprite.order = order;
+                sprite.clip_region = clip_region;
                 self.polychrome_sprites.push(sprite.clone());
             }
             Primitive::Surface(surface) => {
@@ -120,22 +167,55 @@ impl Scene {
                 PaintOperation::Primitive(primitive) => self.insert_primitive(primitive.clone()),
                 PaintOperation::StartLayer(bounds) => self.push_layer(*bounds),
                 PaintOperation::EndLayer => self.pop_layer(),
+                PaintOperation::StartClipRegion(region) => {
+                    let start = region.vertices_start as usize;
+                    let end = start + region.vertex_count as usize;
+                    self.push_clip_region(
+                        region.clone(),
+                        &prev_scene.clip_region_vertices[start..end],
+                    );
+                }
+                PaintOperation::EndClipRegion => self.pop_clip_region(),
             }
         }
     }
 
     pub fn finish(&mut self) {
         self.shadows.sort_by_key(|shadow| shadow.order);
+        self.backdrops.sort_by_key(|backdrop| backdrop.order);
         self.quads.sort_by_key(|quad| quad.order);
         self.paths.sort_by_key(|path| path.order);
         self.underlines.sort_by_key(|underline| underline.order);
         self.monochrome_sprites
             .sort_by_key(|sprite| (sprite.order, sprite.tile.tile_id));
+        self.gradient_sprites
+            .sort_by_key(|sprite| (sprite.order, sprite.tile.tile_id));
         self.polychrome_sprites
             .sort_by_key(|sprite| (sprite.order, sprite.tile.tile_id));
         self.surfaces.sort_by_key(|surface| surface.order);
     }
 
+    /// Whether any primitive is blended with [`BlendMode::Overlay`], which renderers composite
+    /// from a copy of the frame.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) fn has_overlay_blend(&self) -> bool {
+        self.quads
+            .iter()
+            .any(|quad| quad.blend_mode == BlendMode::Overlay)
+            || self
+                .monochrome_sprites
+                .iter()
+                .any(|sprite| sprite.blend_mode == BlendMode::Overlay)
+            || self
+                .gradient_sprites
+                .iter()
+                .any(|sprite| sprite.blend_mode == BlendMode::Overlay)
+            || self
+                .polychrome_sprites
+                .iter()
+                .any(|sprite| sprite.blend_mode == BlendMode::Overlay)
+    }
+
     #[cfg_attr(
         all(
             any(target_os = "linux", target_os = "freebsd"),
@@ -148,6 +228,9 @@ impl Scene {
             shadows: &self.shadows,
             shadows_start: 0,
             shadows_iter: self.shadows.iter().peekable(),
+            backdrops: &self.backdrops,
+            backdrops_start: 0,
+            backdrops_iter: self.backdrops.iter().peekable(),
             quads: &self.quads,
             quads_start: 0,
             quads_iter: self.quads.iter().peekable(),
@@ -160,6 +243,9 @@ impl Scene {
             monochrome_sprites: &self.monochrome_sprites,
             monochrome_sprites_start: 0,
             monochrome_sprites_iter: self.monochrome_sprites.iter().peekable(),
+            gradient_sprites: &self.gradient_sprites,
+            gradient_sprites_start: 0,
+            gradient_sprites_iter: self.gradient_sprites.iter().peekable(),
             polychrome_sprites: &self.polychrome_sprites,
             polychrome_sprites_start: 0,
             polychrome_sprites_iter: self.polychrome_sprites.iter().peekable(),
@@ -180,11 +266,13 @@ impl Scene {
 )]
 pub(crate) enum PrimitiveKind {
     Shadow,
+    Backdrop,
     #[default]
     Quad,
     Path,
     Underline,
     MonochromeSprite,
+    GradientSprite,
     PolychromeSprite,
     Surface,
 }
@@ -193,15 +281,19 @@ pub(crate) enum PaintOperation {
     Primitive(Primitive),
     StartLayer(Bounds<ScaledPixels>),
     EndLayer,
+    StartClipRegion(ClipRegion),
+    EndClipRegion,
 }
 
 #[derive(Clone)]
 pub(crate) enum Primitive {
     Shadow(Shadow),
+    Backdrop(Backdrop),
     Quad(Quad),
     Path(Path<ScaledPixels>),
     Underline(Underline),
     MonochromeSprite(MonochromeSprite),
+    GradientSprite(GradientSprite),
     PolychromeSprite(PolychromeSprite),
     Surface(PaintSurface),
 }
@@ -210,10 +302,12 @@ impl Primitive {
     pub fn bounds(&self) -> &Bounds<ScaledPixels> {
         match self {
             Primitive::Shadow(shadow) => &shadow.bounds,
+            Primitive::Backdrop(backdrop) => &backdrop.bounds,
             Primitive::Quad(quad) => &quad.bounds,
             Primitive::Path(path) => &path.bounds,
             Primitive::Underline(underline) => &underline.bounds,
             Primitive::MonochromeSprite(sprite) => &sprite.bounds,
+            Primitive::GradientSprite(sprite) => &sprite.bounds,
             Primitive::PolychromeSprite(sprite) => &sprite.bounds,
             Primitive::Surface(surface) => &surface.bounds,
         }
@@ -222,10 +316,12 @@ impl Primitive {
     pub fn content_mask(&self) -> &ContentMask<ScaledPixels> {
         match self {
             Primitive::Shadow(shadow) => &shadow.content_mask,
+            Primitive::Backdrop(backdrop) => &backdrop.content_mask,
             Primitive::Quad(quad) => &quad.content_mask,
             Primitive::Path(path) => &path.content_mask,
             Primitive::Underline(underline) => &underline.content_mask,
             Primitive::MonochromeSprite(sprite) => &sprite.content_mask,
+            Primitive::GradientSprite(sprite) => &sprite.content_mask,
             Primitive::PolychromeSprite(sprite) => &sprite.content_mask,
             Primitive::Surface(surface) => &surface.content_mask,
         }
@@ -243,6 +339,9 @@ struct BatchIterator<'a> {
     shadows: &'a [Shadow],
     shadows_start: usize,
     shadows_iter: Peekable<slice::Iter<'a, Shadow>>,
+    backdrops: &'a [Backdrop],
+    backdrops_start: usize,
+    backdrops_iter: Peekable<slice::Iter<'a, Backdrop>>,
     quads: &'a [Quad],
     quads_start: usize,
     quads_iter: Peekable<slice::Iter<'a, Quad>>,
@@ -255,6 +354,9 @@ struct BatchIterator<'a> {
     monochrome_sprites: &'a [MonochromeSprite],
     monochrome_sprites_start: usize,
     monochrome_sprites_iter: Peekable<slice::Iter<'a, MonochromeSprite>>,
+    gradient_sprites: &'a [GradientSprite],
+    gradient_sprites_start: usize,
+    gradient_sprites_iter: Peekable<slice::Iter<'a, GradientSprite>>,
     polychrome_sprites: &'a [PolychromeSprite],
     polychrome_sprites_start: usize,
     polychrome_sprites_iter: Peekable<slice::Iter<'a, PolychromeSprite>>,
@@ -272,6 +374,10 @@ impl<'a> Iterator for BatchIterator<'a> {
                 self.shadows_iter.peek().map(|s| s.order),
                 PrimitiveKind::Shadow,
             ),
+            (
+                self.backdrops_iter.peek().map(|b| b.order),
+                PrimitiveKind::Backdrop,
+            ),
             (self.quads_iter.peek().map(|q| q.order), PrimitiveKind::Quad),
             (self.paths_iter.peek().map(|q| q.order), PrimitiveKind::Path),
             (
@@ -282,6 +388,10 @@ impl<'a> Iterator for BatchIterator<'a> {
                 self.monochrome_sprites_iter.peek().map(|s| s.order),
                 PrimitiveKind::MonochromeSprite,
             ),
+            (
+                self.gradient_sprites_iter.peek().map(|s| s.order),
+                PrimitiveKind::GradientSprite,
+            ),
             (
                 self.polychrome_sprites_iter.peek().map(|s| s.order),
                 PrimitiveKind::PolychromeSprite,
@@ -318,13 +428,33 @@ impl<'a> Iterator for BatchIterator<'a> {
                     &self.shadows[shadows_start..shadows_end],
                 ))
             }
+            PrimitiveKind::Backdrop => {
+                let backdrops_start = self.backdrops_start;
+                let mut backdrops_end = backdrops_start + 1;
+                self.backdrops_iter.next();
+                while self
+                    .backdrops_iter
+                    .next_if(|backdrop| (backdrop.order, batch_kind) < max_order_and_kind)
+                    .is_some()
+                {
+                    backdrops_end += 1;
+                }
+                self.backdrops_start = backdrops_end;
+                Some(PrimitiveBatch::Backdrops(
+                    &self.backdrops[backdrops_start..backdrops_end],
+                ))
+            }
             PrimitiveKind::Quad => {
+                let blend_mode = self.quads_iter.peek().unwrap().blend_mode;
                 let quads_start = self.quads_start;
                 let mut quads_end = quads_start + 1;
                 self.quads_iter.next();
                 while self
                     .quads_iter
-                    .next_if(|quad| (quad.order, batch_kind) < max_order_and_kind)
+                    .next_if(|quad| {
+                        (quad.order, batch_kind) < max_order_and_kind
+                            && quad.blend_mode == blend_mode
+                    })
                     .is_some()
                 {
                     quads_end += 1;
@@ -363,7 +493,9 @@ impl<'a> Iterator for BatchIterator<'a> {
                 ))
             }
             PrimitiveKind::MonochromeSprite => {
-                let texture_id = self.monochrome_sprites_iter.peek().unwrap().tile.texture_id;
+                let first_sprite = self.monochrome_sprites_iter.peek().unwrap();
+                let texture_id = first_sprite.tile.texture_id;
+                let blend_mode = first_sprite.blend_mode;
                 let sprites_start = self.monochrome_sprites_start;
                 let mut sprites_end = sprites_start + 1;
                 self.monochrome_sprites_iter.next();
@@ -372,6 +504,7 @@ impl<'a> Iterator for BatchIterator<'a> {
                     .next_if(|sprite| {
                         (sprite.order, batch_kind) < max_order_and_kind
                             && sprite.tile.texture_id == texture_id
+                            && sprite.blend_mode == blend_mode
                     })
                     .is_some()
                 {
@@ -383,8 +516,34 @@ impl<'a> Iterator for BatchIterator<'a> {
                     sprites: &self.monochrome_sprites[sprites_start..sprites_end],
                 })
             }
+            PrimitiveKind::GradientSprite => {
+                let first_sprite = self.gradient_sprites_iter.peek().unwrap();
+                let texture_id = first_sprite.tile.texture_id;
+                let blend_mode = first_sprite.blend_mode;
+                let sprites_start = self.gradient_sprites_start;
+                let mut sprites_end = sprites_start + 1;
+                self.gradient_sprites_iter.next();
+                while self
+                    .gradient_sprites_iter
+                    .next_if(|sprite| {
+                        (sprite.order, batch_kind) < max_order_and_kind
+                            && sprite.tile.texture_id == texture_id
+                            && sprite.blend_mode == blend_mode
+                    })
+                    .is_some()
+                {
+                    sprites_end += 1;
+                }
+                self.gradient_sprites_start = sprites_end;
+                Some(PrimitiveBatch::GradientSprites {
+                    texture_id,
+                    sprites: &self.gradient_sprites[sprites_start..sprites_end],
+                })
+            }
             PrimitiveKind::PolychromeSprite => {
-                let texture_id = self.polychrome_sprites_iter.peek().unwrap().tile.texture_id;
+                let first_sprite = self.polychrome_sprites_iter.peek().unwrap();
+                let texture_id = first_sprite.tile.texture_id;
+                let blend_mode = first_sprite.blend_mode;
                 let sprites_start = self.polychrome_sprites_start;
                 let mut sprites_end = self.polychrome_sprites_start + 1;
                 self.polychrome_sprites_iter.next();
@@ -393,6 +552,7 @@ impl<'a> Iterator for BatchIterator<'a> {
                     .next_if(|sprite| {
                         (sprite.order, batch_kind) < max_order_and_kind
                             && sprite.tile.texture_id == texture_id
+                            && sprite.blend_mode == blend_mode
                     })
                     .is_some()
                 {
@@ -434,6 +594,7 @@ impl<'a> Iterator for BatchIterator<'a> {
 )]
 pub(crate) enum PrimitiveBatch<'a> {
     Shadows(&'a [Shadow]),
+    Backdrops(&'a [Backdrop]),
     Quads(&'a [Quad]),
     Paths(&'a [Path<ScaledPixels>]),
     Underlines(&'a [Underline]),
@@ -441,6 +602,10 @@ pub(crate) enum PrimitiveBatch<'a> {
         texture_id: AtlasTextureId,
         sprites: &'a [MonochromeSprite],
     },
+    GradientSprites {
+        texture_id: AtlasTextureId,
+        sprites: &'a [GradientSprite],
+    },
     PolychromeSprites {
         texture_id: AtlasTextureId,
         sprites: &'a [PolychromeSprite],
@@ -448,6 +613,105 @@ pub(crate) enum PrimitiveBatch<'a> {
     Surfaces(&'a [PaintSurface]),
 }
 
+impl PrimitiveBatch<'_> {
+    /// The region of a frame with the given size painted by this batch if it's blended with
+    /// [`BlendMode::Overlay`], rounded out to whole device pixels. Renderers that can copy what
+    /// they've drawn paint such a batch into a separate layer, and then composite the layer over a
+    /// copy of this region of the frame, so the batch's primitives are blended with what's behind
+    /// them as a group.
+    #[cfg_attr(
+        all(
+            any(target_os = "linux", target_os = "freebsd"),
+            not(any(feature = "x11", feature = "wayland"))
+        ),
+        allow(dead_code)
+    )]
+    pub fn overlay_bounds(
+        &self,
+        viewport_size: Size<DevicePixels>,
+    ) -> Option<Bounds<DevicePixels>> {
+        fn visible_bounds(
+            bounds: &Bounds<ScaledPixels>,
+            content_mask: &ContentMask<ScaledPixels>,
+            transformation: &TransformationMatrix,
+        ) -> Bounds<ScaledPixels> {
+            // Transformed sprites can be painted anywhere within their content mask.
+            if *transformation == TransformationMatrix::unit() {
+                bounds.intersect(&content_mask.bounds)
+            } else {
+                content_mask.bounds
+            }
+        }
+
+        let unit = TransformationMatrix::unit();
+        let (blend_mode, bounds) = match self {
+            PrimitiveBatch::Quads(quads) => (
+                quads.first()?.blend_mode,
+                quads
+                    .iter()
+                    .map(|quad| visible_bounds(&quad.bounds, &quad.content_mask, &unit))
+                    .reduce(|a, b| a.union(&b))?,
+            ),
+            PrimitiveBatch::MonochromeSprites { sprites, .. } => (
+                sprites.first()?.blend_mode,
+                sprites
+                    .iter()
+                    .map(|sprite| {
+                        visible_bounds(&sprite.bounds, &sprite.content_mask, &sprite.transformation)
+                    })
+                    .reduce(|a, b| a.union(&b))?,
+            ),
+            PrimitiveBatch::GradientSprites { sprites, .. } => (
+                sprites.first()?.blend_mode,
+                sprites
+                    .iter()
+                    .map(|sprite| {
+                        visible_bounds(&sprite.bounds, &sprite.content_mask, &sprite.transformation)
+                    })
+                    .reduce(|a, b| a.union(&b))?,
+            ),
+            PrimitiveBatch::PolychromeSprites { sprites, .. } => (
+                sprites.first()?.blend_mode,
+                sprites
+                    .iter()
+                    .map(|sprite| {
+                        visible_bounds(&sprite.bounds, &sprite.content_mask, &sprite.transformation)
+                    })
+                    .reduce(|a, b| a.union(&b))?,
+            ),
+            _ => return None,
+        };
+        if blend_mode != BlendMode::Overlay {
+            return None;
+        }
+        device_bounds_within_viewport(bounds, viewport_size)
+    }
+}
+
+/// The part of the given bounds within a frame of the given size, rounded out to whole device
+/// pixels. Returns `None` if none of the bounds are within the frame.
+fn device_bounds_within_viewport(
+    bounds: Bounds<ScaledPixels>,
+    viewport_size: Size<DevicePixels>,
+) -> Option<Bounds<DevicePixels>> {
+    let viewport = Bounds::new(
+        Point::default(),
+        Size {
+            width: viewport_size.width.into(),
+            height: viewport_size.height.into(),
+        },
+    );
+    let bounds = bounds.intersect(&viewport);
+    if bounds.is_empty() {
+        return None;
+    }
+    let top_left = bounds
+        .origin
+        .map(|coordinate| DevicePixels(coordinate.0.floor() as i32));
+    let bottom_right = bounds.bottom_right().map(DevicePixels::from);
+    Some(Bounds::from_corners(top_left, bottom_right))
+}
+
 #[derive(Default, Debug, Clone)]
 #[repr(C)]
 pub(crate) struct Quad {
@@ -456,9 +720,11 @@ pub(crate) struct Quad {
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub background: Background,
-    pub border_color: Hsla,
+    pub border_colors: Edges<Hsla>,
     pub corner_radii: Corners<ScaledPixels>,
     pub border_widths: Edges<ScaledPixels>,
+    pub clip_region: u32,
+    pub blend_mode: BlendMode,
 }
 
 impl From<Quad> for Primitive {
@@ -471,12 +737,12 @@ impl From<Quad> for Primitive {
 #[repr(C)]
 pub(crate) struct Underline {
     pub order: DrawOrder,
-    pub pad: u32, // align to 8 bytes
+    pub clip_region: u32,
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub color: Hsla,
     pub thickness: ScaledPixels,
-    pub wavy: u32,
+    pub style: u32,
 }
 
 impl From<Underline> for Primitive {
@@ -494,6 +760,11 @@ pub(crate) struct Shadow {
     pub corner_radii: Corners<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub color: Hsla,
+    /// The bounds that inset shadows are cast into, outside of which they aren't painted.
+    pub clip_bounds: Bounds<ScaledPixels>,
+    pub clip_corner_radii: Corners<ScaledPixels>,
+    pub inset: u32,
+    pub clip_region: u32,
 }
 
 impl From<Shadow> for Primitive {
@@ -502,6 +773,58 @@ impl From<Shadow> for Primitive {
     }
 }
 
+/// A region whose already painted content is blurred, for frosted glass effects.
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub(crate) struct Backdrop {
+    pub order: DrawOrder,
+    pub blur_radius: ScaledPixels,
+    pub bounds: Bounds<ScaledPixels>,
+    pub corner_radii: Corners<ScaledPixels>,
+    pub content_mask: ContentMask<ScaledPixels>,
+    /// The color painted instead of the blur by renderers that can't read back what's behind it.
+    pub fallback_color: Hsla,
+    pub clip_region: u32,
+    pub pad: u32, // align to 8 bytes
+}
+
+impl Backdrop {
+    /// The reach of the blur beyond each side of the region, where it samples from.
+    pub fn margin(&self) -> ScaledPixels {
+        self.blur_radius * 3.
+    }
+
+    /// The region of a frame with the given size that the blur reads from, rounded out to whole
+    /// device pixels. Returns `None` if the backdrop isn't visible.
+    pub fn sample_bounds(&self, viewport_size: Size<DevicePixels>) -> Option<Bounds<DevicePixels>> {
+        let visible_bounds = self.bounds.intersect(&self.content_mask.bounds);
+        if visible_bounds.is_empty() {
+            return None;
+        }
+        device_bounds_within_viewport(visible_bounds.dilate(self.margin()), viewport_size)
+    }
+
+    /// The translucent quad painted in place of the blur when it's not supported.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn fallback_quad(&self) -> Quad {
+        Quad {
+            order: self.order,
+            bounds: self.bounds,
+            content_mask: self.content_mask.clone(),
+            background: self.fallback_color.into(),
+            corner_radii: self.corner_radii.clone(),
+            clip_region: self.clip_region,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<Backdrop> for Primitive {
+    fn from(backdrop: Backdrop) -> Self {
+        Primitive::Backdrop(backdrop)
+    }
+}
+
 /// The style of a border.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[repr(C)]
@@ -513,6 +836,37 @@ pub enum BorderStyle {
     Dashed = 1,
 }
 
+/// How an element's quads and sprites are composited with what's already been painted behind them.
+/// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/mix-blend-mode)
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[repr(C)]
+pub enum BlendMode {
+    /// Paints over what's behind.
+    #[default]
+    Normal = 0,
+    /// Multiplies the colors with what's behind, which darkens everything but white.
+    Multiply = 1,
+    /// Multiplies the inverted colors with what's behind, which lightens everything but black.
+    Screen = 2,
+    /// Multiplies dark colors behind and screens light ones. This depends on the color behind
+    /// rather than just the color being painted, so renderers composite it from a copy of what
+    /// they've drawn, and paint it as `Normal` where they can't make one.
+    Overlay = 3,
+}
+
+impl BlendMode {
+    /// Log that an overlay blended batch is painted normally because the renderer couldn't copy
+    /// what's behind it, the first time it happens.
+    pub(crate) fn log_overlay_fallback() {
+        static LOGGED_FALLBACK: Once = Once::new();
+        LOGGED_FALLBACK.call_once(|| {
+            log::debug!(
+                "couldn't copy the frame to composite an overlay blend, painting it normally"
+            );
+        });
+    }
+}
+
 /// A data type representing a 2 dimensional transformation that can be applied to an element.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
@@ -562,6 +916,14 @@ impl TransformationMatrix {
         })
     }
 
+    /// Skew around the origin, by the given angles along the x and y axes
+    pub fn skew(self, x: Radians, y: Radians) -> Self {
+        self.compose(Self {
+            rotation_scale: [[1.0, x.0.tan()], [y.0.tan(), 1.0]],
+            translation: [0.0, 0.0],
+        })
+    }
+
     /// Perform matrix multiplication with another transformation
     /// to produce a new transformation that is the result of
     /// applying both transformations: first, `other`, then `self`.
@@ -597,6 +959,28 @@ impl TransformationMatrix {
         }
     }
 
+    /// The transformation that undoes this one, or `None` if this one collapses points onto a line
+    /// and can't be undone.
+    pub fn inverse(&self) -> Option<TransformationMatrix> {
+        let [[a, b], [c, d]] = self.rotation_scale;
+        let determinant = a * d - b * c;
+        if determinant.abs() < f32::EPSILON {
+            return None;
+        }
+        let rotation_scale = [
+            [d / determinant, -b / determinant],
+            [-c / determinant, a / determinant],
+        ];
+        let [x, y] = self.translation;
+        Some(TransformationMatrix {
+            rotation_scale,
+            translation: [
+                -(rotation_scale[0][0] * x + rotation_scale[0][1] * y),
+                -(rotation_scale[1][0] * x + rotation_scale[1][1] * y),
+            ],
+        })
+    }
+
     /// Apply transformation to a point, mainly useful for debugging
     pub fn apply(&self, point: Point<Pixels>) -> Point<Pixels> {
         let input = [point.x.0, point.y.0];
@@ -620,12 +1004,14 @@ impl Default for TransformationMatrix {
 #[repr(C)]
 pub(crate) struct MonochromeSprite {
     pub order: DrawOrder,
-    pub pad: u32, // align to 8 bytes
+    pub clip_region: u32,
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub color: Hsla,
     pub tile: AtlasTile,
     pub transformation: TransformationMatrix,
+    pub blend_mode: BlendMode,
+    pub pad: u32, // align to 8 bytes
 }
 
 impl From<MonochromeSprite> for Primitive {
@@ -634,17 +1020,43 @@ impl From<MonochromeSprite> for Primitive {
     }
 }
 
+/// A monochrome sprite tinted with a gradient. These are batched separately from
+/// [`MonochromeSprite`]s, so that glyphs, which are always tinted with a solid color, don't carry
+/// or evaluate a gradient.
 #[derive(Clone, Debug)]
 #[repr(C)]
-pub(crate) struct PolychromeSprite {
+pub(crate) struct GradientSprite {
     pub order: DrawOrder,
+    pub clip_region: u32,
+    pub bounds: Bounds<ScaledPixels>,
+    pub content_mask: ContentMask<ScaledPixels>,
+    pub color: Background,
+    pub tile: AtlasTile,
+    pub transformation: TransformationMatrix,
+    pub blend_mode: BlendMode,
     pub pad: u32, // align to 8 bytes
+}
+
+impl From<GradientSprite> for Primitive {
+    fn from(sprite: GradientSprite) -> Self {
+        Primitive::GradientSprite(sprite)
+    }
+}
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct PolychromeSprite {
+    pub order: DrawOrder,
+    pub clip_region: u32,
     pub grayscale: bool,
     pub opacity: f32,
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub corner_radii: Corners<ScaledPixels>,
     pub tile: AtlasTile,
+    pub transformation: TransformationMatrix,
+    pub blend_mode: BlendMode,
+    pub pad: u32, // align to 8 bytes
 }
 
 impl From<PolychromeSprite> for Primitive {
@@ -668,6 +1080,35 @@ impl From<PaintSurface> for Primitive {
     }
 }
 
+/// A shape that primitives are clipped to in addition to their content mask, see
+/// [`Window::with_clip_path`](crate::Window::with_clip_path). Regions are identified by their
+/// index in [`Scene::clip_regions`] plus one, so that a `clip_region` of 0 clips nothing.
+#[derive(Clone, Debug, Default)]
+#[repr(C)]
+pub(crate) struct ClipRegion {
+    pub bounds: Bounds<ScaledPixels>,
+    pub corner_radii: Corners<ScaledPixels>,
+    pub kind: ClipRegionKind,
+    /// The region enclosing this one, which primitives are clipped to as well.
+    pub parent: u32,
+    /// The range of [`Scene::clip_region_vertices`] holding the vertices of a polygon.
+    pub vertices_start: u32,
+    pub vertex_count: u32,
+}
+
+/// The shape of a [`ClipRegion`] within its bounds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub(crate) enum ClipRegionKind {
+    /// The bounds with corners rounded by the region's corner radii.
+    #[default]
+    RoundedRect = 0,
+    /// The ellipse inscribed in the bounds.
+    Ellipse = 1,
+    /// A polygon filled by the even-odd rule, whose vertices are in window coordinates.
+    Polygon = 2,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub(crate) struct PathId(pub(crate) usize);
 
@@ -680,6 +1121,7 @@ pub struct Path<P: Clone + Debug + Default + PartialEq> {
     pub(crate) content_mask: ContentMask<P>,
     pub(crate) vertices: Vec<PathVertex<P>>,
     pub(crate) color: Background,
+    pub(crate) clip_region: u32,
     start: Point<P>,
     current: Point<P>,
     contour_count: usize,
@@ -700,10 +1142,16 @@ impl Path<Pixels> {
             },
             content_mask: Default::default(),
             color: Default::default(),
+            clip_region: 0,
             contour_count: 0,
         }
     }
 
+    /// The bounds of the filled area of this path, e.g. for inserting a hitbox over it.
+    pub fn bounds(&self) -> Bounds<Pixels> {
+        self.bounds
+    }
+
     /// Scale this path by the given factor.
     pub fn scale(&self, factor: f32) -> Path<ScaledPixels> {
         Path {
@@ -720,6 +1168,7 @@ impl Path<Pixels> {
             current: self.current.scale(factor),
             contour_count: self.contour_count,
             color: self.color,
+            clip_region: self.clip_region,
         }
     }
 
@@ -831,3 +1280,114 @@ impl PathVertex<Pixels> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{red, size};
+
+    fn backdrop(
+        bounds: Bounds<ScaledPixels>,
+        content_mask: Bounds<ScaledPixels>,
+        blur_radius: f32,
+    ) -> Backdrop {
+        Backdrop {
+            order: 1,
+            blur_radius: ScaledPixels(blur_radius),
+            bounds,
+            corner_radii: Corners::all(ScaledPixels(4.)),
+            content_mask: ContentMask {
+                bounds: content_mask,
+            },
+            fallback_color: red().opacity(0.5),
+            clip_region: 0,
+            pad: 0,
+        }
+    }
+
+    fn scaled_bounds(x: f32, y: f32, width: f32, height: f32) -> Bounds<ScaledPixels> {
+        Bounds::new(
+            point(ScaledPixels(x), ScaledPixels(y)),
+            size(ScaledPixels(width), ScaledPixels(height)),
+        )
+    }
+
+    fn device_bounds(x: i32, y: i32, width: i32, height: i32) -> Bounds<DevicePixels> {
+        Bounds::new(
+            point(DevicePixels(x), DevicePixels(y)),
+            size(DevicePixels(width), DevicePixels(height)),
+        )
+    }
+
+    #[test]
+    fn test_backdrop_sample_bounds() {
+        let viewport_size = size(DevicePixels(100), DevicePixels(100));
+        let unmasked = scaled_bounds(0., 0., 1000., 1000.);
+
+        // The blur reads three radii beyond each side.
+        let unclipped = backdrop(scaled_bounds(10., 10., 20., 20.), unmasked, 2.);
+        assert_eq!(
+            unclipped.sample_bounds(viewport_size),
+            Some(device_bounds(4, 4, 32, 32))
+        );
+
+        // Fractional bounds are rounded out to whole device pixels.
+        let fractional = backdrop(scaled_bounds(10.5, 10.5, 10., 10.), unmasked, 0.);
+        assert_eq!(
+            fractional.sample_bounds(viewport_size),
+            Some(device_bounds(10, 10, 11, 11))
+        );
+
+        // It can't read outside the frame.
+        let clipped = backdrop(scaled_bounds(90., -10., 20., 20.), unmasked, 2.);
+        assert_eq!(
+            clipped.sample_bounds(viewport_size),
+            Some(device_bounds(84, 0, 16, 16))
+        );
+
+        // Only the part of the backdrop within its content mask is blurred.
+        let masked = backdrop(
+            scaled_bounds(10., 10., 40., 40.),
+            scaled_bounds(0., 0., 30., 30.),
+            2.,
+        );
+        assert_eq!(
+            masked.sample_bounds(viewport_size),
+            Some(device_bounds(4, 4, 32, 32))
+        );
+    }
+
+    #[test]
+    fn test_backdrop_sample_bounds_when_not_visible() {
+        let viewport_size = size(DevicePixels(100), DevicePixels(100));
+        let unmasked = scaled_bounds(0., 0., 1000., 1000.);
+
+        let masked_out = backdrop(
+            scaled_bounds(50., 50., 20., 20.),
+            scaled_bounds(0., 0., 40., 40.),
+            2.,
+        );
+        assert_eq!(masked_out.sample_bounds(viewport_size), None);
+
+        let offscreen = backdrop(scaled_bounds(200., 200., 20., 20.), unmasked, 2.);
+        assert_eq!(offscreen.sample_bounds(viewport_size), None);
+
+        let empty = backdrop(scaled_bounds(10., 10., 0., 20.), unmasked, 2.);
+        assert_eq!(empty.sample_bounds(viewport_size), None);
+    }
+
+    #[test]
+    fn test_backdrop_fallback_quad() {
+        let backdrop = backdrop(
+            scaled_bounds(10., 10., 20., 20.),
+            scaled_bounds(0., 0., 25., 25.),
+            2.,
+        );
+        let quad = backdrop.fallback_quad();
+        assert_eq!(quad.order, backdrop.order);
+        assert_eq!(quad.bounds, backdrop.bounds);
+        assert_eq!(quad.content_mask, backdrop.content_mask);
+        assert_eq!(quad.corner_radii, backdrop.corner_radii);
+        assert_eq!(quad.background, Background::from(backdrop.fallback_color));
+    }
+}
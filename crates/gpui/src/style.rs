@@ -1,15 +1,19 @@
 use std::{
+    fmt::Debug,
     hash::{Hash, Hasher},
     iter, mem,
     ops::Range,
+    time::{Duration, Instant},
 };
 
 use crate::{
-    AbsoluteLength, App, Background, BackgroundTag, BorderStyle, Bounds, ContentMask, Corners,
-    CornersRefinement, CursorStyle, DefiniteLength, DevicePixels, Edges, EdgesRefinement, Font,
-    FontFallbacks, FontFeatures, FontStyle, FontWeight, GridLocation, Hsla, Length, Pixels, Point,
-    PointRefinement, Rgba, SharedString, Size, SizeRefinement, Styled, TextRun, Window, black, phi,
-    point, quad, rems, size,
+    AbsoluteLength, App, Axis, Background, BackgroundTag, BlendMode, BorderStyle, Bounds,
+    ContentMask, Corners, CornersRefinement, CursorStyle, DefiniteLength, DevicePixels, Edges,
+    EdgesRefinement, Font, FontFallbacks, FontFeatures, FontStyle, FontVariations, FontWeight,
+    GridLocation, GridTrack, HitboxShape, Hsla, Length, LineBreakStyle, PaintQuad, Pixels, Point,
+    PointRefinement, Rgba, SharedString, Size, SizeRefinement, Styled, TextRun, Window, black,
+    blue, ease_in_out, ease_out_quint, linear, opaque_grey, phi, point, quad, quadratic, rems,
+    size,
 };
 use collections::HashSet;
 use refineable::Refineable;
@@ -46,7 +50,18 @@ impl ObjectFit {
         bounds: Bounds<Pixels>,
         image_size: Size<DevicePixels>,
     ) -> Bounds<Pixels> {
-        let image_size = image_size.map(|dimension| Pixels::from(u32::from(dimension)));
+        self.get_bounds_for_size(
+            bounds,
+            image_size.map(|dimension| Pixels::from(u32::from(dimension))),
+        )
+    }
+
+    /// Get the bounds of content with the given size within the given bounds.
+    pub fn get_bounds_for_size(
+        &self,
+        bounds: Bounds<Pixels>,
+        image_size: Size<Pixels>,
+    ) -> Bounds<Pixels> {
         let image_ratio = image_size.width / image_size.height;
         let bounds_ratio = bounds.size.width / bounds.size.height;
 
@@ -136,6 +151,40 @@ impl ObjectFit {
             },
         }
     }
+
+    /// Get the bounds of the image within the given bounds, placing images that don't exactly
+    /// fill the bounds at the given position, like CSS's `object-position`.
+    ///
+    /// Fractions are of the space left over between the image and the bounds, so `0.0` aligns the
+    /// image with the top left of the bounds and `1.0` with the bottom right. Offsets are clamped
+    /// so that covering images never reveal the bounds and smaller images stay inside them. The
+    /// position has no effect on [`ObjectFit::Fill`] and [`ObjectFit::Contain`].
+    pub fn get_positioned_bounds(
+        &self,
+        bounds: Bounds<Pixels>,
+        image_size: Size<DevicePixels>,
+        position: Point<DefiniteLength>,
+        rem_size: Pixels,
+    ) -> Bounds<Pixels> {
+        let fitted_bounds = self.get_bounds(bounds, image_size);
+        if matches!(self, ObjectFit::Fill | ObjectFit::Contain) {
+            return fitted_bounds;
+        }
+
+        let offset = |position: DefiniteLength, free_space: Pixels| {
+            position
+                .to_pixels(AbsoluteLength::Pixels(free_space), rem_size)
+                .clamp(free_space.min(Pixels::ZERO), free_space.max(Pixels::ZERO))
+        };
+        Bounds {
+            origin: point(
+                bounds.origin.x + offset(position.x, bounds.size.width - fitted_bounds.size.width),
+                bounds.origin.y
+                    + offset(position.y, bounds.size.height - fitted_bounds.size.height),
+            ),
+            size: fitted_bounds.size,
+        }
+    }
 }
 
 /// The CSS styling that can be applied to an element via the `Styled` trait
@@ -178,6 +227,14 @@ pub struct Style {
     /// Ideally we would match the web's behavior and not have a need for this, but right now we're adding this opt-in
     /// style property to limit the potential blast radius.
     pub restrict_scroll_to_axis: bool,
+    /// Whether scrolling past the edge of this scroll container scrolls the container it's in.
+    /// Equivalent to the CSS `overscroll-behavior` property
+    pub overscroll_behavior: OverscrollBehavior,
+    /// How a scroll container settles on the snap points of its children once scrolling stops.
+    /// Equivalent to the CSS `scroll-snap-type` and `scroll-snap-align` properties
+    pub scroll_snap: Option<ScrollSnap>,
+    /// Whether this element is a snap point of the scroll container it's in
+    pub snap_point: bool,
 
     // Position properties
     /// What should the `position` value of this struct use as a base offset?
@@ -241,6 +298,10 @@ pub struct Style {
     /// The border color of this element
     pub border_color: Option<Hsla>,
 
+    /// The colors of individual borders of this element, which take precedence over `border_color`
+    #[refineable]
+    pub border_colors: Edges<Option<Hsla>>,
+
     /// The border style of this element
     pub border_style: BorderStyle,
 
@@ -251,6 +312,25 @@ pub struct Style {
     /// Box shadow of the element
     pub box_shadow: Vec<BoxShadow>,
 
+    /// The radius of the blur applied to the content painted behind this element
+    /// Equivalent to the CSS `backdrop-filter: blur(<radius>)`
+    pub backdrop_blur: Option<Pixels>,
+
+    /// The width of the outline painted outside of this element, which doesn't affect layout
+    pub outline_width: Pixels,
+
+    /// The color of the outline painted outside of this element
+    pub outline_color: Option<Hsla>,
+
+    /// The gap between this element's border box and its outline
+    /// Equivalent to the CSS `outline-offset` property
+    pub outline_offset: Pixels,
+
+    /// The shape this element and its children are clipped to, outside of which they can't be
+    /// hovered or clicked either
+    /// Equivalent to the CSS `clip-path` property
+    pub clip_path: Option<ClipPath>,
+
     /// The text style of this element
     pub text: TextStyleRefinement,
 
@@ -260,6 +340,10 @@ pub struct Style {
     /// The opacity of this element
     pub opacity: Option<f32>,
 
+    /// How this element's quads and sprites are composited with what's painted behind them
+    /// Equivalent to the CSS `mix-blend-mode` property
+    pub blend_mode: Option<BlendMode>,
+
     /// The grid columns of this element
     /// Equivalent to the Tailwind `grid-cols-<number>`
     pub grid_cols: Option<u16>,
@@ -268,9 +352,20 @@ pub struct Style {
     /// Equivalent to the Tailwind `grid-rows-<number>`
     pub grid_rows: Option<u16>,
 
+    /// The sizes of the columns of this grid, which take precedence over `grid_cols`
+    /// Equivalent to the CSS `grid-template-columns`
+    pub grid_template_columns: Option<Vec<GridTrack>>,
+
+    /// The sizes of the rows of this grid, which take precedence over `grid_rows`
+    /// Equivalent to the CSS `grid-template-rows`
+    pub grid_template_rows: Option<Vec<GridTrack>>,
+
     /// The grid location of this element
     pub grid_location: Option<GridLocation>,
 
+    /// The properties of this element whose changes are animated
+    pub transitions: Vec<Transition>,
+
     /// Whether to draw a red debugging outline around this element
     #[cfg(debug_assertions)]
     pub debug: bool,
@@ -314,6 +409,103 @@ pub struct BoxShadow {
     pub blur_radius: Pixels,
     /// How much should the shadow spread?
     pub spread_radius: Pixels,
+    /// Should the shadow be cast inside of its element, rather than behind it?
+    /// Equivalent to the CSS `inset` keyword
+    #[serde(default)]
+    pub inset: bool,
+}
+
+/// The possible values of the clip-path property
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum ClipPath {
+    /// The element's bounds inset by the given lengths, with rounded corners
+    /// Equivalent to the CSS `inset(<top> <right> <bottom> <left> round <corner_radius>)` shape
+    InsetRound {
+        /// How far the top edge is moved down
+        top: DefiniteLength,
+        /// How far the right edge is moved left
+        right: DefiniteLength,
+        /// How far the bottom edge is moved up
+        bottom: DefiniteLength,
+        /// How far the left edge is moved right
+        left: DefiniteLength,
+        /// The radius of every corner
+        corner_radius: AbsoluteLength,
+    },
+    /// The ellipse inscribed in the element's bounds
+    /// Equivalent to the CSS `ellipse()` shape
+    Ellipse,
+    /// A polygon whose vertices are relative to the element's origin, filled by the even-odd
+    /// rule. Fractions are relative to the element's width for `x` and its height for `y`
+    /// Equivalent to the CSS `polygon(evenodd, ...)` shape
+    Polygon(Vec<Point<DefiniteLength>>),
+}
+
+impl ClipPath {
+    /// The element's bounds inset by the same length on every side, with rounded corners.
+    pub fn inset_round(
+        inset: impl Into<DefiniteLength>,
+        corner_radius: impl Into<AbsoluteLength>,
+    ) -> Self {
+        let inset = inset.into();
+        Self::InsetRound {
+            top: inset,
+            right: inset,
+            bottom: inset,
+            left: inset,
+            corner_radius: corner_radius.into(),
+        }
+    }
+
+    /// Resolves the shape for an element with the given bounds into the bounds of the shape
+    /// and the shape within them.
+    pub fn resolve(
+        &self,
+        bounds: Bounds<Pixels>,
+        rem_size: Pixels,
+    ) -> (Bounds<Pixels>, HitboxShape) {
+        let width = AbsoluteLength::Pixels(bounds.size.width);
+        let height = AbsoluteLength::Pixels(bounds.size.height);
+        match self {
+            Self::InsetRound {
+                top,
+                right,
+                bottom,
+                left,
+                corner_radius,
+            } => {
+                let top_left = bounds.origin
+                    + point(
+                        left.to_pixels(width, rem_size),
+                        top.to_pixels(height, rem_size),
+                    );
+                let bottom_right = bounds.bottom_right()
+                    - point(
+                        right.to_pixels(width, rem_size),
+                        bottom.to_pixels(height, rem_size),
+                    );
+                let bounds = Bounds::from_corners(top_left, bottom_right.max(&top_left));
+                let corner_radii = Corners::all(corner_radius.to_pixels(rem_size))
+                    .clamp_radii_for_quad_size(bounds.size);
+                (bounds, HitboxShape::RoundedRect(corner_radii))
+            }
+            Self::Ellipse => (bounds, HitboxShape::Ellipse),
+            Self::Polygon(vertices) => (
+                bounds,
+                HitboxShape::Polygon(
+                    vertices
+                        .iter()
+                        .map(|vertex| {
+                            point(
+                                vertex.x.to_pixels(width, rem_size),
+                                vertex.y.to_pixels(height, rem_size),
+                            )
+                        })
+                        .collect(),
+                ),
+            ),
+        }
+    }
 }
 
 /// How to handle whitespace in text
@@ -326,6 +518,29 @@ pub enum WhiteSpace {
     Nowrap,
 }
 
+/// Where lines of wrapped text may be broken within words
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum WordBreak {
+    /// Break lines between words, leaving words that don't fit to the overflow wrap
+    #[default]
+    Normal,
+    /// Break lines between any two characters, filling every line
+    BreakAll,
+    /// Break lines between words, and within words that don't fit on a line of their own,
+    /// regardless of the overflow wrap
+    BreakWord,
+}
+
+/// What to do with words too long to fit on a line of their own
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum OverflowWrap {
+    /// Let the word overflow the width of the element
+    Normal,
+    /// Break the word at the last character that fits on the line
+    #[default]
+    BreakWord,
+}
+
 /// How to truncate text that overflows the width of the element
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum TextOverflow {
@@ -334,6 +549,20 @@ pub enum TextOverflow {
     Truncate(SharedString),
 }
 
+/// Which part of overflowing text to remove when it is truncated
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TruncationStyle {
+    /// Keep the start of the text and truncate its end, e.g. `src/elements/sv…`
+    #[default]
+    End,
+
+    /// Keep the end of the text and truncate its start, e.g. `…elements/svg.rs`
+    Start,
+
+    /// Keep both ends of the text and truncate its middle, e.g. `src/el…/svg.rs`
+    Middle,
+}
+
 /// How to align text within the element
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum TextAlign {
@@ -346,6 +575,30 @@ pub enum TextAlign {
 
     /// Align the text to the right of the element
     Right,
+
+    /// Align the text to the side its paragraph starts on: the left for left-to-right text and
+    /// the right for right-to-left text
+    Start,
+
+    /// Align the text to the side its paragraph ends on: the right for left-to-right text and
+    /// the left for right-to-left text
+    End,
+}
+
+/// The direction of a paragraph of text, which decides how runs of left-to-right and
+/// right-to-left text are ordered within its lines
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum TextDirection {
+    /// Take the direction from the first character with a strong direction in each line,
+    /// falling back to left-to-right
+    #[default]
+    Auto,
+
+    /// Lay out paragraphs left-to-right
+    Ltr,
+
+    /// Lay out paragraphs right-to-left
+    Rtl,
 }
 
 /// The properties that can be used to style text in GPUI
@@ -361,6 +614,9 @@ pub struct TextStyle {
     /// The font features to use
     pub font_features: FontFeatures,
 
+    /// The variable font axis values to use
+    pub font_variations: FontVariations,
+
     /// The fallback fonts to use
     pub font_fallbacks: Option<FontFallbacks>,
 
@@ -385,15 +641,36 @@ pub struct TextStyle {
     /// The strikethrough style of the text
     pub strikethrough: Option<StrikethroughStyle>,
 
+    /// The background color of selected text, in text that can be selected
+    pub selection_background_color: Hsla,
+
+    /// Whether the user can select the text, including plain strings
+    pub selectable: bool,
+
     /// How to handle whitespace in the text
     pub white_space: WhiteSpace,
 
+    /// Where wrapped lines may be broken within words
+    pub word_break: WordBreak,
+
+    /// What to do with words too long to fit on a line of their own
+    pub overflow_wrap: OverflowWrap,
+
+    /// Whether to insert a hyphen where a word is broken across lines
+    pub hyphenate: bool,
+
     /// The text should be truncated if it overflows the width of the element
     pub text_overflow: Option<TextOverflow>,
 
+    /// Which part of the text is removed when it is truncated
+    pub truncation_style: TruncationStyle,
+
     /// How the text should be aligned within the element
     pub text_align: TextAlign,
 
+    /// The direction of each paragraph of the text
+    pub direction: TextDirection,
+
     /// The number of lines to display before truncating the text
     pub line_clamp: Option<usize>,
 }
@@ -405,6 +682,7 @@ impl Default for TextStyle {
             // todo(linux) make this configurable or choose better default
             font_family: ".SystemUIFont".into(),
             font_features: FontFeatures::default(),
+            font_variations: FontVariations::default(),
             font_fallbacks: None,
             font_size: rems(1.).into(),
             line_height: phi(),
@@ -413,9 +691,16 @@ impl Default for TextStyle {
             background_color: None,
             underline: None,
             strikethrough: None,
+            selection_background_color: blue().opacity(0.3),
+            selectable: false,
             white_space: WhiteSpace::Normal,
+            word_break: WordBreak::default(),
+            overflow_wrap: OverflowWrap::default(),
+            hyphenate: false,
             text_overflow: None,
+            truncation_style: TruncationStyle::default(),
             text_align: TextAlign::default(),
+            direction: TextDirection::default(),
             line_clamp: None,
         }
     }
@@ -460,12 +745,22 @@ impl TextStyle {
         Font {
             family: self.font_family.clone(),
             features: self.font_features.clone(),
+            variations: self.font_variations.clone(),
             fallbacks: self.font_fallbacks.clone(),
             weight: self.font_weight,
             style: self.font_style,
         }
     }
 
+    /// Get the rules for breaking wrapped lines configured for this text style.
+    pub fn line_break_style(&self) -> LineBreakStyle {
+        LineBreakStyle {
+            word_break: self.word_break,
+            overflow_wrap: self.overflow_wrap,
+            hyphenate: self.hyphenate,
+        }
+    }
+
     /// Returns the rounded line height in pixels.
     pub fn line_height_in_pixels(&self, rem_size: Pixels) -> Pixels {
         self.line_height.to_pixels(self.font_size, rem_size).round()
@@ -478,6 +773,7 @@ impl TextStyle {
             font: Font {
                 family: self.font_family.clone(),
                 features: self.font_features.clone(),
+                variations: self.font_variations.clone(),
                 fallbacks: self.font_fallbacks.clone(),
                 weight: self.font_weight,
                 style: self.font_style,
@@ -566,8 +862,8 @@ impl Style {
                 let mut max = bounds.bottom_right();
 
                 if self
-                    .border_color
-                    .is_some_and(|color| !color.is_transparent())
+                    .resolved_border_colors()
+                    .any(|color| !color.is_transparent())
                 {
                     min.x += self.border_widths.left.to_pixels(rem_size);
                     max.x -= self.border_widths.right.to_pixels(rem_size);
@@ -600,6 +896,22 @@ impl Style {
         }
     }
 
+    /// Invokes the given function with everything painted and every hitbox inserted within it
+    /// clipped to the style's clip path, if it has one.
+    pub fn with_clip_path<R>(
+        &self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        f: impl FnOnce(&mut Window) -> R,
+    ) -> R {
+        if let Some(clip_path) = &self.clip_path {
+            let (bounds, shape) = clip_path.resolve(bounds, window.rem_size());
+            window.with_clip_path(bounds, shape, f)
+        } else {
+            f(window)
+        }
+    }
+
     /// Paints the background of an element styled with this style.
     pub fn paint(
         &self,
@@ -624,14 +936,41 @@ impl Style {
             .to_pixels(rem_size)
             .clamp_radii_for_quad_size(bounds.size);
 
-        window.paint_shadows(bounds, corner_radii, &self.box_shadow);
+        // Inset shadows are painted above the background, and outset ones beneath it.
+        let (inset_shadows, outset_shadows): (Vec<_>, Vec<_>) = self
+            .box_shadow
+            .iter()
+            .cloned()
+            .partition(|shadow| shadow.inset);
+        window.paint_shadows(bounds, corner_radii, &outset_shadows);
 
         let background_color = self.background.as_ref().and_then(Fill::color);
+        if let Some(blur_radius) = self.backdrop_blur.filter(|radius| *radius > Pixels::ZERO) {
+            let fallback_color = match background_color {
+                Some(color) => match color.tag {
+                    BackgroundTag::LinearGradient
+                    | BackgroundTag::RadialGradient
+                    | BackgroundTag::ConicGradient => color
+                        .colors
+                        .first()
+                        .map(|stop| stop.color)
+                        .unwrap_or_default(),
+                    BackgroundTag::Solid | BackgroundTag::PatternSlash => color.solid,
+                },
+                None => opaque_grey(0.5, 1.),
+            };
+            // Without a blur, make the element opaque enough to keep its content legible.
+            let fallback_color = fallback_color.alpha(fallback_color.a.max(0.85));
+            window.paint_backdrop_blur(bounds, corner_radii, blur_radius, fallback_color);
+        }
+
         if background_color.is_some_and(|color| !color.is_transparent()) {
             let mut border_color = match background_color {
                 Some(color) => match color.tag {
                     BackgroundTag::Solid => color.solid,
-                    BackgroundTag::LinearGradient => color
+                    BackgroundTag::LinearGradient
+                    | BackgroundTag::RadialGradient
+                    | BackgroundTag::ConicGradient => color
                         .colors
                         .first()
                         .map(|stop| stop.color)
@@ -651,6 +990,8 @@ impl Style {
             ));
         }
 
+        window.paint_shadows(bounds, corner_radii, &inset_shadows);
+
         continuation(window, cx);
 
         if self.is_border_visible() {
@@ -675,16 +1016,18 @@ impl Style {
                 bottom_bounds.top_right(),
             );
 
-            let mut background = self.border_color.unwrap_or_default();
+            let border_colors = self.resolved_border_colors();
+            let mut background = border_colors.top;
             background.a = 0.;
             let quad = quad(
                 bounds,
                 corner_radii,
                 background,
                 border_widths,
-                self.border_color.unwrap_or_default(),
+                border_colors.top,
                 self.border_style,
-            );
+            )
+            .border_colors(border_colors);
 
             window.with_content_mask(Some(ContentMask { bounds: top_bounds }), |window| {
                 window.paint_quad(quad.clone());
@@ -715,6 +1058,13 @@ impl Style {
             );
         }
 
+        if let Some(outline_color) = self
+            .outline_color
+            .filter(|color| !color.is_transparent() && self.outline_width > Pixels::ZERO)
+        {
+            window.paint_quad(self.outline_quad(bounds, corner_radii, outline_color));
+        }
+
         #[cfg(debug_assertions)]
         if self.debug_below {
             cx.remove_global::<DebugBelow>();
@@ -722,10 +1072,44 @@ impl Style {
     }
 
     fn is_border_visible(&self) -> bool {
-        self.border_color
-            .is_some_and(|color| !color.is_transparent())
+        self.resolved_border_colors()
+            .any(|color| !color.is_transparent())
             && self.border_widths.any(|length| !length.is_zero())
     }
+
+    /// Returns the quad of the outline around the given bounds, whose corners follow the
+    /// element's rounded corners, grown by the offset and width of the outline.
+    fn outline_quad(
+        &self,
+        bounds: Bounds<Pixels>,
+        corner_radii: Corners<Pixels>,
+        outline_color: Hsla,
+    ) -> PaintQuad {
+        let outset = self.outline_offset + self.outline_width;
+        let corner_radii = corner_radii.map(|radius| {
+            if *radius > Pixels::ZERO {
+                (*radius + outset).max(Pixels::ZERO)
+            } else {
+                Pixels::ZERO
+            }
+        });
+        let mut background = outline_color;
+        background.a = 0.;
+        quad(
+            bounds.dilate(outset),
+            corner_radii,
+            background,
+            self.outline_width,
+            outline_color,
+            BorderStyle::Solid,
+        )
+    }
+
+    /// Returns the color of each border, using `border_color` for those without their own.
+    pub fn resolved_border_colors(&self) -> Edges<Hsla> {
+        self.border_colors
+            .map(|color| color.or(self.border_color).unwrap_or_default())
+    }
 }
 
 impl Default for Style {
@@ -739,6 +1123,9 @@ impl Default for Style {
             },
             allow_concurrent_scroll: false,
             restrict_scroll_to_axis: false,
+            overscroll_behavior: OverscrollBehavior::default(),
+            scroll_snap: None,
+            snap_point: false,
             scrollbar_width: AbsoluteLength::default(),
             position: Position::Relative,
             inset: Edges::auto(),
@@ -763,15 +1150,25 @@ impl Default for Style {
             flex_basis: Length::Auto,
             background: None,
             border_color: None,
+            border_colors: Edges::default(),
             border_style: BorderStyle::default(),
             corner_radii: Corners::default(),
             box_shadow: Default::default(),
+            backdrop_blur: None,
+            outline_width: Pixels::ZERO,
+            outline_color: None,
+            outline_offset: Pixels::ZERO,
+            clip_path: None,
             text: TextStyleRefinement::default(),
             mouse_cursor: None,
             opacity: None,
+            blend_mode: None,
             grid_rows: None,
             grid_cols: None,
+            grid_template_columns: None,
+            grid_template_rows: None,
             grid_location: None,
+            transitions: Vec::new(),
 
             #[cfg(debug_assertions)]
             debug: false,
@@ -781,6 +1178,325 @@ impl Default for Style {
     }
 }
 
+/// A property of a [`Style`] whose changes can be animated, see [`Styled::transition`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize, JsonSchema)]
+pub enum TransitionProperty {
+    /// Every property that can be transitioned.
+    All,
+    /// The background, when it changes between solid colors.
+    Background,
+    /// The border color.
+    BorderColor,
+    /// The text color.
+    TextColor,
+    /// The opacity.
+    Opacity,
+    /// The radius of the corners.
+    CornerRadii,
+    /// The inset of a positioned element, when it changes between absolute lengths.
+    Inset,
+}
+
+impl TransitionProperty {
+    fn includes(self, property: TransitionProperty) -> bool {
+        self == TransitionProperty::All || self == property
+    }
+}
+
+/// The rate at which a [`Transition`] moves from the old value to the new one.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug, Serialize, Deserialize, JsonSchema)]
+pub enum TransitionEasing {
+    /// See [`crate::linear`].
+    #[default]
+    Linear,
+    /// See [`crate::quadratic`].
+    Quadratic,
+    /// See [`crate::ease_in_out`].
+    EaseInOut,
+    /// See [`crate::ease_out_quint`].
+    EaseOutQuint,
+}
+
+impl TransitionEasing {
+    /// Applies this easing to a time delta between 0 and 1.
+    pub fn apply(self, delta: f32) -> f32 {
+        match self {
+            TransitionEasing::Linear => linear(delta),
+            TransitionEasing::Quadratic => quadratic(delta),
+            TransitionEasing::EaseInOut => ease_in_out(delta),
+            TransitionEasing::EaseOutQuint => ease_out_quint()(delta),
+        }
+    }
+}
+
+/// Animates the changes to a property of an element's style, rather than applying them at once.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Transition {
+    /// The property to animate.
+    pub property: TransitionProperty,
+    /// How long it takes to move to a new value.
+    pub duration: Duration,
+    /// The rate at which the property moves to a new value.
+    pub easing: TransitionEasing,
+}
+
+/// The transitioned properties of an element, kept across frames to animate their changes.
+#[derive(Default)]
+pub(crate) struct TransitionState {
+    background: Option<PropertyTransition<Option<Fill>>>,
+    border_color: Option<PropertyTransition<Option<Hsla>>>,
+    text_color: Option<PropertyTransition<Option<Hsla>>>,
+    opacity: Option<PropertyTransition<Option<f32>>>,
+    corner_radii: Option<PropertyTransition<Corners<AbsoluteLength>>>,
+    inset: Option<PropertyTransition<Edges<Length>>>,
+}
+
+impl TransitionState {
+    /// Replaces the transitioned properties of the style with their values at the given time,
+    /// returning whether any of them are still moving.
+    pub(crate) fn apply(&mut self, style: &mut Style, now: Instant, rem_size: Pixels) -> bool {
+        let transition_for = |property| {
+            style
+                .transitions
+                .iter()
+                .rev()
+                .find(|transition| transition.property.includes(property))
+                .copied()
+        };
+        let background = transition_for(TransitionProperty::Background);
+        let border_color = transition_for(TransitionProperty::BorderColor);
+        let text_color = transition_for(TransitionProperty::TextColor);
+        let opacity = transition_for(TransitionProperty::Opacity);
+        let corner_radii = transition_for(TransitionProperty::CornerRadii);
+        let inset = transition_for(TransitionProperty::Inset);
+
+        let mut moving = false;
+        moving |= PropertyTransition::update(
+            &mut self.background,
+            &mut style.background,
+            background,
+            now,
+            rem_size,
+        );
+        moving |= PropertyTransition::update(
+            &mut self.border_color,
+            &mut style.border_color,
+            border_color,
+            now,
+            rem_size,
+        );
+        moving |= PropertyTransition::update(
+            &mut self.text_color,
+            &mut style.text.color,
+            text_color,
+            now,
+            rem_size,
+        );
+        moving |= PropertyTransition::update(
+            &mut self.opacity,
+            &mut style.opacity,
+            opacity,
+            now,
+            rem_size,
+        );
+        moving |= PropertyTransition::update(
+            &mut self.corner_radii,
+            &mut style.corner_radii,
+            corner_radii,
+            now,
+            rem_size,
+        );
+        moving |=
+            PropertyTransition::update(&mut self.inset, &mut style.inset, inset, now, rem_size);
+        moving
+    }
+}
+
+/// The movement of a single property from the value it had when it last changed to its new value.
+struct PropertyTransition<T> {
+    from: T,
+    to: T,
+    start: Instant,
+    duration: Duration,
+    easing: TransitionEasing,
+}
+
+impl<T: Interpolate> PropertyTransition<T> {
+    /// Records the resolved value of the property and replaces it with the value it has moved to,
+    /// returning whether it's still moving.
+    fn update(
+        state: &mut Option<Self>,
+        value: &mut T,
+        transition: Option<Transition>,
+        now: Instant,
+        rem_size: Pixels,
+    ) -> bool {
+        let Some(transition) = transition else {
+            *state = None;
+            return false;
+        };
+
+        match state {
+            None => {
+                *state = Some(Self {
+                    from: value.clone(),
+                    to: value.clone(),
+                    start: now,
+                    duration: transition.duration,
+                    easing: transition.easing,
+                });
+                false
+            }
+            Some(state) => {
+                if state.to != *value {
+                    // Start from wherever an interrupted transition got to.
+                    let from = state
+                        .value_at(now, rem_size)
+                        .unwrap_or_else(|| state.to.clone());
+                    *state = Self {
+                        from,
+                        to: value.clone(),
+                        start: now,
+                        duration: transition.duration,
+                        easing: transition.easing,
+                    };
+                }
+
+                if let Some(current) = state.value_at(now, rem_size) {
+                    *value = current;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Returns the value of the property at the given time, or `None` if it's settled.
+    fn value_at(&self, now: Instant, rem_size: Pixels) -> Option<T> {
+        let elapsed = now.saturating_duration_since(self.start);
+        if elapsed >= self.duration {
+            return None;
+        }
+        let delta = self.easing.apply(elapsed.div_duration_f32(self.duration));
+        self.from.interpolate(&self.to, delta, rem_size)
+    }
+}
+
+/// A value that can be blended with another value of the same type for a [`Transition`].
+pub(crate) trait Interpolate: Clone + PartialEq {
+    /// Returns the value the given fraction of the way to `target`, or `None` if the two values
+    /// can't be blended, in which case the property jumps to `target`.
+    fn interpolate(&self, target: &Self, delta: f32, rem_size: Pixels) -> Option<Self>;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, target: &Self, delta: f32, _rem_size: Pixels) -> Option<Self> {
+        Some(self + (target - self) * delta)
+    }
+}
+
+impl Interpolate for Option<f32> {
+    fn interpolate(&self, target: &Self, delta: f32, rem_size: Pixels) -> Option<Self> {
+        self.unwrap_or(1.)
+            .interpolate(&target.unwrap_or(1.), delta, rem_size)
+            .map(Some)
+    }
+}
+
+impl Interpolate for Hsla {
+    fn interpolate(&self, target: &Self, delta: f32, rem_size: Pixels) -> Option<Self> {
+        let from = self.to_rgb();
+        let to = target.to_rgb();
+        Some(
+            Rgba {
+                r: from.r.interpolate(&to.r, delta, rem_size)?,
+                g: from.g.interpolate(&to.g, delta, rem_size)?,
+                b: from.b.interpolate(&to.b, delta, rem_size)?,
+                a: from.a.interpolate(&to.a, delta, rem_size)?,
+            }
+            .into(),
+        )
+    }
+}
+
+impl Interpolate for Option<Hsla> {
+    fn interpolate(&self, target: &Self, delta: f32, rem_size: Pixels) -> Option<Self> {
+        // A missing color fades from or to a transparent version of the other one.
+        match (self, target) {
+            (Some(from), Some(to)) => from.interpolate(to, delta, rem_size).map(Some),
+            (None, Some(to)) => to.alpha(0.).interpolate(to, delta, rem_size).map(Some),
+            (Some(from), None) => from.interpolate(&from.alpha(0.), delta, rem_size).map(Some),
+            (None, None) => Some(None),
+        }
+    }
+}
+
+impl Interpolate for Option<Fill> {
+    fn interpolate(&self, target: &Self, delta: f32, rem_size: Pixels) -> Option<Self> {
+        let solid_color = |fill: &Option<Fill>| match fill {
+            Some(Fill::Color(background)) if background.tag == BackgroundTag::Solid => {
+                Some(Some(background.solid))
+            }
+            Some(_) => None,
+            None => Some(None),
+        };
+        let color = solid_color(self)?.interpolate(&solid_color(target)?, delta, rem_size)?;
+        Some(color.map(Fill::from))
+    }
+}
+
+impl Interpolate for AbsoluteLength {
+    fn interpolate(&self, target: &Self, delta: f32, rem_size: Pixels) -> Option<Self> {
+        let from = self.to_pixels(rem_size);
+        let to = target.to_pixels(rem_size);
+        Some(AbsoluteLength::Pixels(from + (to - from) * delta))
+    }
+}
+
+impl Interpolate for Length {
+    fn interpolate(&self, target: &Self, delta: f32, rem_size: Pixels) -> Option<Self> {
+        match (self, target) {
+            (
+                Length::Definite(DefiniteLength::Absolute(from)),
+                Length::Definite(DefiniteLength::Absolute(to)),
+            ) => Some(from.interpolate(to, delta, rem_size)?.into()),
+            _ if self == target => Some(*self),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Interpolate + Default + Debug> Interpolate for Corners<T> {
+    fn interpolate(&self, target: &Self, delta: f32, rem_size: Pixels) -> Option<Self> {
+        Some(Corners {
+            top_left: self
+                .top_left
+                .interpolate(&target.top_left, delta, rem_size)?,
+            top_right: self
+                .top_right
+                .interpolate(&target.top_right, delta, rem_size)?,
+            bottom_right: self
+                .bottom_right
+                .interpolate(&target.bottom_right, delta, rem_size)?,
+            bottom_left: self
+                .bottom_left
+                .interpolate(&target.bottom_left, delta, rem_size)?,
+        })
+    }
+}
+
+impl<T: Interpolate + Default + Debug> Interpolate for Edges<T> {
+    fn interpolate(&self, target: &Self, delta: f32, rem_size: Pixels) -> Option<Self> {
+        Some(Edges {
+            top: self.top.interpolate(&target.top, delta, rem_size)?,
+            right: self.right.interpolate(&target.right, delta, rem_size)?,
+            bottom: self.bottom.interpolate(&target.bottom, delta, rem_size)?,
+            left: self.left.interpolate(&target.left, delta, rem_size)?,
+        })
+    }
+}
+
 /// The properties that can be applied to an underline.
 #[derive(
     Refineable, Copy, Clone, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema,
@@ -793,7 +1509,37 @@ pub struct UnderlineStyle {
     pub color: Option<Hsla>,
 
     /// Whether the underline should be wavy, like in a spell checker.
+    /// Takes precedence over [`UnderlineStyle::style`].
     pub wavy: bool,
+
+    /// The style of the line, such as dotted or dashed.
+    pub style: LineStyle,
+}
+
+impl UnderlineStyle {
+    /// The style the underline is drawn with, taking [`UnderlineStyle::wavy`] into account.
+    pub fn line_style(&self) -> LineStyle {
+        if self.wavy {
+            LineStyle::Wavy
+        } else {
+            self.style
+        }
+    }
+}
+
+/// The shape of the line used to draw an underline.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[repr(u32)]
+pub enum LineStyle {
+    /// A continuous line.
+    #[default]
+    Solid = 0,
+    /// A wavy line, like in a spell checker.
+    Wavy = 1,
+    /// A line of square dots, each as long as the line is thick.
+    Dotted = 2,
+    /// A line of dashes, each three times as long as the line is thick.
+    Dashed = 3,
 }
 
 /// The properties that can be applied to a strikethrough.
@@ -801,7 +1547,8 @@ pub struct UnderlineStyle {
     Refineable, Copy, Clone, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema,
 )]
 pub struct StrikethroughStyle {
-    /// The thickness of the strikethrough.
+    /// The thickness of the strikethrough. A thickness of zero uses the font's suggested
+    /// strikethrough thickness.
     pub thickness: Pixels,
 
     /// The color of the strikethrough.
@@ -1182,6 +1929,68 @@ pub enum Overflow {
     Scroll,
 }
 
+/// What happens to the part of a scroll that a scroll container can't consume because it's
+/// reached its edge.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/overscroll-behavior>
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub enum OverscrollBehavior {
+    /// The rest of the scroll is passed on to the nearest scroll container this one is in,
+    /// separately along each axis.
+    #[default]
+    Auto,
+    /// The scroll is contained, so containers further out are never scrolled by scrolling over
+    /// this one, e.g. for a list in a modal.
+    Contain,
+}
+
+/// How a scroll container settles on the snap points of its children, set with
+/// [`StatefulInteractiveElement::scroll_snap`](crate::StatefulInteractiveElement::scroll_snap).
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/scroll-snap-type>
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ScrollSnap {
+    /// The axes along which the scroll offset snaps.
+    pub axis: SnapAxis,
+    /// Which part of a snap point is aligned with the same part of the container.
+    pub align: SnapAlign,
+}
+
+/// The axes along which a scroll container snaps.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+pub enum SnapAxis {
+    /// Only the horizontal scroll offset snaps.
+    X,
+    /// Only the vertical scroll offset snaps.
+    Y,
+    /// Both scroll offsets snap, each to its nearest snap point.
+    Both,
+}
+
+impl SnapAxis {
+    /// Whether the scroll offset snaps along the given axis.
+    pub fn contains(self, axis: Axis) -> bool {
+        match self {
+            SnapAxis::X => axis == Axis::Horizontal,
+            SnapAxis::Y => axis == Axis::Vertical,
+            SnapAxis::Both => true,
+        }
+    }
+}
+
+/// Which part of a snap point is aligned with the same part of its scroll container.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/scroll-snap-align>
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+pub enum SnapAlign {
+    /// The start edges, e.g. the left edge of a card in a horizontal carousel.
+    Start,
+    /// The centers.
+    Center,
+    /// The end edges.
+    End,
+}
+
 /// The positioning strategy for this item.
 ///
 /// This controls both how the origin is determined for the [`Style::position`] field,
@@ -1290,9 +2099,14 @@ impl From<Position> for taffy::style::Position {
 
 #[cfg(test)]
 mod tests {
-    use crate::{blue, green, px, red, yellow};
+    use crate::{
+        ClipRegionKind, Context, InteractiveElement as _, IntoElement, ParentElement as _,
+        PrimitiveBatch, Render, TestAppContext, VisualTestContext, blue, canvas, div, fit_content,
+        fr, green, max_content, min_content, px, red, relative, transparent_black, yellow,
+    };
 
     use super::*;
+    use std::{cell::RefCell, rc::Rc};
 
     use util_macros::perf;
 
@@ -1321,6 +2135,7 @@ mod tests {
                 thickness: px(2.),
                 color: Some(red()),
                 wavy: true,
+                ..Default::default()
             }),
         };
         let expected_style = style_b;
@@ -1353,6 +2168,7 @@ mod tests {
                 thickness: px(4.),
                 color: None,
                 wavy: false,
+                ..Default::default()
             }),
         };
 
@@ -1371,6 +2187,7 @@ mod tests {
                 thickness: px(4.),
                 color: None,
                 wavy: false,
+                ..Default::default()
             }),
         };
 
@@ -1469,4 +2286,641 @@ mod tests {
             ]
         );
     }
+
+    #[perf]
+    fn test_font_variations_resolve_into_font() {
+        let mut style = TextStyle::default();
+        style.refine(&TextStyleRefinement {
+            font_variations: Some(FontVariations::new(&[("wght", 500.)])),
+            ..Default::default()
+        });
+        let font = style.font();
+        assert_eq!(font.variations.value_for("wght"), Some(500.));
+        assert_ne!(font, TextStyle::default().font());
+
+        style.refine(&TextStyleRefinement {
+            font_variations: Some(FontVariations::new(&[("wght", 300.), ("wdth", 75.)])),
+            ..Default::default()
+        });
+        let font = style.font();
+        assert_eq!(font.variations.value_for("wght"), Some(300.));
+        assert_eq!(font.variations.value_for("wdth"), Some(75.));
+    }
+
+    #[perf]
+    fn test_object_position() {
+        let bounds = Bounds::new(point(px(10.), px(20.)), size(px(100.), px(100.)));
+        let wide_image = size(DevicePixels(200), DevicePixels(100));
+        let tall_image = size(DevicePixels(100), DevicePixels(300));
+        let rem_size = px(16.);
+
+        let centered = point(relative(0.5), relative(0.5));
+        assert_eq!(
+            ObjectFit::Cover.get_positioned_bounds(bounds, wide_image, centered, rem_size),
+            ObjectFit::Cover.get_bounds(bounds, wide_image)
+        );
+
+        // Covering images are biased along the axis they overflow.
+        let top = point(relative(0.5), relative(0.));
+        assert_eq!(
+            ObjectFit::Cover.get_positioned_bounds(bounds, tall_image, top, rem_size),
+            Bounds::new(point(px(10.), px(20.)), size(px(100.), px(300.)))
+        );
+        let fraction = point(relative(0.25), relative(0.25));
+        assert_eq!(
+            ObjectFit::Cover.get_positioned_bounds(bounds, tall_image, fraction, rem_size),
+            Bounds::new(point(px(10.), px(-30.)), size(px(100.), px(300.)))
+        );
+
+        // Absolute offsets are clamped so that the image still covers the bounds.
+        let pixels = point(px(0.).into(), px(-50.).into());
+        assert_eq!(
+            ObjectFit::Cover.get_positioned_bounds(bounds, tall_image, pixels, rem_size),
+            Bounds::new(point(px(10.), px(-30.)), size(px(100.), px(300.)))
+        );
+        let past_end = point(px(0.).into(), px(-500.).into());
+        assert_eq!(
+            ObjectFit::Cover.get_positioned_bounds(bounds, tall_image, past_end, rem_size),
+            Bounds::new(point(px(10.), px(-180.)), size(px(100.), px(300.)))
+        );
+
+        // Smaller images are kept inside the bounds.
+        let small_image = size(DevicePixels(40), DevicePixels(20));
+        let offset = point(px(30.).into(), px(200.).into());
+        assert_eq!(
+            ObjectFit::None.get_positioned_bounds(bounds, small_image, offset, rem_size),
+            Bounds::new(point(px(40.), px(100.)), size(px(40.), px(20.)))
+        );
+        assert_eq!(
+            ObjectFit::Contain.get_positioned_bounds(bounds, wide_image, top, rem_size),
+            ObjectFit::Contain.get_bounds(bounds, wide_image)
+        );
+    }
+
+    #[gpui::test]
+    fn test_grid_template_tracks(cx: &mut TestAppContext) {
+        let cell_bounds = Rc::new(RefCell::new(Vec::new()));
+        let cell = || {
+            let cell_bounds = cell_bounds.clone();
+            canvas(
+                move |bounds, _, _| cell_bounds.borrow_mut().push(bounds),
+                |_, _, _, _| {},
+            )
+            .h(px(20.))
+        };
+
+        let cx = cx.add_empty_window();
+        cx.draw(point(px(0.), px(0.)), size(px(400.), px(100.)), |_, _| {
+            div()
+                .grid()
+                .w(px(400.))
+                .gap_x(px(10.))
+                .grid_template_columns([fr(1.), px(200.).into(), fr(1.)])
+                .child(cell())
+                .child(cell())
+                .child(cell())
+                .child(cell().grid_area(2..3, 1..-1))
+        });
+
+        assert_eq!(
+            *cell_bounds.borrow(),
+            vec![
+                Bounds::new(point(px(0.), px(0.)), size(px(90.), px(20.))),
+                Bounds::new(point(px(100.), px(0.)), size(px(200.), px(20.))),
+                Bounds::new(point(px(310.), px(0.)), size(px(90.), px(20.))),
+                Bounds::new(point(px(0.), px(20.)), size(px(400.), px(20.))),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_content_sizes(cx: &mut TestAppContext) {
+        let widths = Rc::new(RefCell::new(Vec::new()));
+        let wrapping_row = |width: Length| {
+            let widths = widths.clone();
+            div()
+                .relative()
+                .flex()
+                .flex_wrap()
+                .w(width)
+                .children((0..3).map(|_| div().w(px(60.)).h(px(10.))))
+                .child(
+                    canvas(
+                        move |bounds, _, _| widths.borrow_mut().push(bounds.size.width),
+                        |_, _, _, _| {},
+                    )
+                    .absolute()
+                    .size_full(),
+                )
+        };
+
+        let cx = cx.add_empty_window();
+        cx.draw(point(px(0.), px(0.)), size(px(400.), px(100.)), |_, _| {
+            div()
+                .flex()
+                .flex_col()
+                .items_start()
+                .child(wrapping_row(max_content()))
+                .child(wrapping_row(min_content()))
+                .child(wrapping_row(fit_content(px(100.))))
+                .child(wrapping_row(fit_content(px(20.))))
+        });
+
+        assert_eq!(*widths.borrow(), vec![px(180.), px(60.), px(100.), px(60.)]);
+    }
+
+    #[perf]
+    fn test_transitions_retarget_from_current_value() {
+        let start = Instant::now();
+        let transitioned_style = |opacity: f32| {
+            let mut style = Style::default();
+            style.refine(
+                &StyleRefinement::default()
+                    .opacity(opacity)
+                    .bg(red())
+                    .transition(
+                        TransitionProperty::Opacity,
+                        Duration::from_millis(100),
+                        TransitionEasing::Linear,
+                    ),
+            );
+            style
+        };
+        let mut state = TransitionState::default();
+        let mut apply = |opacity: f32, elapsed_millis: u64| {
+            let mut style = transitioned_style(opacity);
+            let now = start + Duration::from_millis(elapsed_millis);
+            let moving = state.apply(&mut style, now, px(16.));
+            (style.opacity.unwrap_or_default(), moving)
+        };
+
+        // The first value is applied at once, changes are animated.
+        assert_eq!(apply(0., 0), (0., false));
+        assert_eq!(apply(1., 0), (0., true));
+        assert_eq!(apply(1., 50), (0.5, true));
+
+        // Interrupting the transition starts from the value reached so far.
+        assert_eq!(apply(0., 50), (0.5, true));
+        assert_eq!(apply(0., 100), (0.25, true));
+        assert_eq!(apply(0., 150), (0., false));
+
+        // Properties without a transition aren't tracked.
+        let mut style = transitioned_style(0.);
+        style.background = Some(blue().into());
+        assert!(!state.apply(&mut style, start, px(16.)));
+        assert_eq!(style.background, Some(blue().into()));
+    }
+
+    #[gpui::test]
+    fn test_transitions_follow_the_executor_clock(cx: &mut TestAppContext) {
+        struct TestView {
+            opaque: bool,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .id("transitioned")
+                    .size(px(20.))
+                    .bg(red())
+                    .opacity(if self.opaque { 1. } else { 0.2 })
+                    .transition(
+                        TransitionProperty::Opacity,
+                        Duration::from_millis(100),
+                        TransitionEasing::Linear,
+                    )
+            }
+        }
+
+        let (view, cx) = cx.add_window_view(|_, _| TestView { opaque: false });
+        let opacity = |cx: &mut VisualTestContext| {
+            cx.update(|window, _| window.rendered_frame.scene.quads[0].background.solid.a)
+        };
+        assert_eq!(opacity(cx), 0.2);
+
+        view.update(cx, |view, cx| {
+            view.opaque = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        assert_eq!(opacity(cx), 0.2);
+
+        cx.executor().advance_clock(Duration::from_millis(50));
+        cx.simulate_animation_frame();
+        assert!((opacity(cx) - 0.6).abs() < 1e-4);
+
+        cx.executor().advance_clock(Duration::from_millis(50));
+        cx.simulate_animation_frame();
+        assert_eq!(opacity(cx), 1.);
+    }
+
+    #[gpui::test]
+    fn test_inset_shadows_paint_above_background(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let shadow = |inset| BoxShadow {
+                    color: black(),
+                    offset: point(px(0.), px(2.)),
+                    blur_radius: px(4.),
+                    spread_radius: px(1.),
+                    inset,
+                };
+                div()
+                    .size(px(20.))
+                    .bg(red())
+                    .shadow(vec![shadow(true), shadow(false)])
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert_eq!(scene.quads.len(), 1);
+            assert_eq!(scene.shadows.len(), 2);
+            let background = &scene.quads[0];
+            let (inset_shadows, outset_shadows): (Vec<_>, Vec<_>) =
+                scene.shadows.iter().partition(|shadow| shadow.inset != 0);
+            assert!(outset_shadows[0].order < background.order);
+            assert!(inset_shadows[0].order > background.order);
+            assert_eq!(inset_shadows[0].clip_bounds, background.bounds);
+        });
+    }
+
+    #[perf]
+    fn test_resolve_clip_path() {
+        let bounds = Bounds::new(point(px(10.), px(20.)), size(px(100.), px(50.)));
+
+        let (inset_bounds, shape) = ClipPath::inset_round(px(5.), px(40.)).resolve(bounds, px(16.));
+        assert_eq!(
+            inset_bounds,
+            Bounds::new(point(px(15.), px(25.)), size(px(90.), px(40.)))
+        );
+        assert_eq!(shape, HitboxShape::RoundedRect(Corners::all(px(20.))));
+
+        let polygon = ClipPath::Polygon(vec![
+            point(relative(0.5), px(0.).into()),
+            point(relative(1.), relative(1.)),
+            point(px(0.).into(), relative(1.)),
+        ]);
+        assert_eq!(
+            polygon.resolve(bounds, px(16.)),
+            (
+                bounds,
+                HitboxShape::Polygon(vec![
+                    point(px(50.), px(0.)),
+                    point(px(100.), px(50.)),
+                    point(px(0.), px(50.)),
+                ])
+            )
+        );
+    }
+
+    #[gpui::test]
+    fn test_clip_path_clips_children(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .size(px(100.))
+                    .clip_path(ClipPath::Ellipse)
+                    .cursor_pointer()
+                    .child(div().size_full().bg(red()))
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert_eq!(scene.clip_regions.len(), 1);
+            assert_eq!(scene.clip_regions[0].kind, ClipRegionKind::Ellipse);
+            assert_eq!(scene.quads.len(), 1);
+            assert_eq!(scene.quads[0].clip_region, 1);
+
+            let frame = &window.rendered_frame;
+            assert!(!frame.hit_test(point(px(50.), px(50.))).ids.is_empty());
+            assert!(frame.hit_test(point(px(5.), px(5.))).ids.is_empty());
+        });
+    }
+
+    #[gpui::test]
+    fn test_blend_mode_splits_batches(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .size(px(100.))
+                    .bg(blue())
+                    .child(
+                        div()
+                            .size(px(50.))
+                            .blend_mode(BlendMode::Multiply)
+                            .child(div().size(px(20.)).bg(red())),
+                    )
+                    .child(div().size(px(50.)).bg(yellow()))
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            let blend_modes = scene
+                .quads
+                .iter()
+                .map(|quad| quad.blend_mode)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                blend_modes,
+                [BlendMode::Normal, BlendMode::Multiply, BlendMode::Normal]
+            );
+
+            let quad_batches = scene
+                .batches()
+                .filter(|batch| matches!(batch, PrimitiveBatch::Quads(_)))
+                .count();
+            assert_eq!(quad_batches, 3);
+        });
+    }
+
+    #[gpui::test]
+    fn test_backdrop_blur_paints_before_background(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().size(px(100.)).bg(blue()).child(
+                    div()
+                        .size(px(50.))
+                        .rounded(px(8.))
+                        .bg(red().opacity(0.5))
+                        .backdrop_blur(px(4.)),
+                )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert_eq!(scene.backdrops.len(), 1);
+            assert_eq!(scene.quads.len(), 2);
+            let backdrop = &scene.backdrops[0];
+            let scale_factor = window.scale_factor();
+            assert_eq!(
+                backdrop.bounds,
+                Bounds::new(point(px(0.), px(0.)), size(px(50.), px(50.))).scale(scale_factor)
+            );
+            assert_eq!(backdrop.blur_radius, px(4.).scale(scale_factor));
+            assert_eq!(
+                backdrop.corner_radii,
+                Corners::all(px(8.)).scale(scale_factor)
+            );
+            // Without a blur the element is made opaque enough to keep its content legible.
+            assert_eq!(backdrop.fallback_color, red().opacity(0.85));
+
+            // The backdrop blurs what the parent painted, and the element's own background is
+            // painted over the blur.
+            assert!(scene.quads[0].order < backdrop.order);
+            assert!(backdrop.order < scene.quads[1].order);
+            let batch_kinds = scene
+                .batches()
+                .map(|batch| match batch {
+                    PrimitiveBatch::Quads(_) => "quads",
+                    PrimitiveBatch::Backdrops(_) => "backdrops",
+                    _ => "other",
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(batch_kinds, ["quads", "backdrops", "quads"]);
+        });
+    }
+
+    #[gpui::test]
+    fn test_overlay_blend_bounds(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().size(px(100.)).bg(blue()).child(
+                    div()
+                        .size(px(50.))
+                        .blend_mode(BlendMode::Overlay)
+                        .child(div().size(px(20.)).bg(red()))
+                        .child(div().size(px(10.)).bg(yellow())),
+                )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let scene = &window.rendered_frame.scene;
+            assert!(scene.has_overlay_blend());
+
+            let viewport_size = size(DevicePixels(1000), DevicePixels(1000));
+            let overlay_bounds = scene
+                .batches()
+                .filter_map(|batch| batch.overlay_bounds(viewport_size))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                overlay_bounds,
+                [Bounds::new(
+                    point(DevicePixels(0), DevicePixels(0)),
+                    size(DevicePixels(40), DevicePixels(60)),
+                )]
+            );
+
+            // The copied region is clipped to the frame.
+            let viewport_size = size(DevicePixels(30), DevicePixels(30));
+            let overlay_bounds = scene
+                .batches()
+                .filter_map(|batch| batch.overlay_bounds(viewport_size))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                overlay_bounds,
+                [Bounds::new(
+                    point(DevicePixels(0), DevicePixels(0)),
+                    size(DevicePixels(30), DevicePixels(30)),
+                )]
+            );
+        });
+    }
+
+    #[perf]
+    fn test_outline_quad() {
+        let mut style = Style::default();
+        style.refine(
+            &StyleRefinement::default()
+                .outline(px(2.), red())
+                .outline_offset(px(1.)),
+        );
+        let bounds = Bounds::new(point(px(10.), px(10.)), size(px(20.), px(20.)));
+        let corner_radii = Corners {
+            top_left: px(4.),
+            ..Default::default()
+        };
+
+        let outline = style.outline_quad(bounds, corner_radii, red());
+        assert_eq!(
+            outline.bounds,
+            Bounds::new(point(px(7.), px(7.)), size(px(26.), px(26.)))
+        );
+        assert_eq!(outline.corner_radii.top_left, px(7.));
+        assert_eq!(outline.corner_radii.bottom_right, px(0.));
+        assert_eq!(outline.border_widths, Edges::all(px(2.)));
+        assert_eq!(outline.border_colors, Edges::all(red()));
+    }
+
+    #[perf]
+    fn test_resolved_border_colors() {
+        let mut style = Style::default();
+        style.refine(
+            &StyleRefinement::default()
+                .border_color(red())
+                .border_l_color(blue()),
+        );
+        assert_eq!(
+            style.resolved_border_colors(),
+            Edges {
+                top: red(),
+                right: red(),
+                bottom: red(),
+                left: blue(),
+            }
+        );
+
+        style.refine(&StyleRefinement::default().border_color(green()));
+        assert_eq!(style.resolved_border_colors(), Edges::all(green()));
+
+        let mut style = Style::default();
+        style.refine(
+            &StyleRefinement::default()
+                .border_l_color(blue())
+                .border_color(green()),
+        );
+        assert_eq!(style.resolved_border_colors(), Edges::all(green()));
+    }
+
+    #[gpui::test]
+    fn test_border_colors_reach_the_scene(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .flex()
+                    .child(
+                        canvas(
+                            |_, _, _| {},
+                            |bounds, _, window, _| {
+                                window.paint_quad(
+                                    quad(
+                                        bounds,
+                                        px(4.),
+                                        transparent_black(),
+                                        px(2.),
+                                        black(),
+                                        BorderStyle::Solid,
+                                    )
+                                    .border_colors(Edges {
+                                        top: red(),
+                                        right: green(),
+                                        bottom: blue(),
+                                        left: yellow(),
+                                    }),
+                                )
+                            },
+                        )
+                        .size(px(20.)),
+                    )
+                    .child(
+                        div()
+                            .size(px(20.))
+                            .border_2()
+                            .border_color(red())
+                            .border_l_color(blue()),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_, _| TestView);
+        cx.update(|window, _| {
+            let quads = &window.rendered_frame.scene.quads;
+            assert_eq!(
+                quads[0].border_colors,
+                Edges {
+                    top: red(),
+                    right: green(),
+                    bottom: blue(),
+                    left: yellow(),
+                }
+            );
+
+            // Sides without their own color fall back to `border_color`.
+            let border_quads = &quads[1..];
+            assert!(!border_quads.is_empty());
+            for quad in border_quads {
+                assert_eq!(
+                    quad.border_colors,
+                    Edges {
+                        top: red(),
+                        right: red(),
+                        bottom: red(),
+                        left: blue(),
+                    }
+                );
+            }
+        });
+    }
+
+    #[gpui::test]
+    fn test_outline_is_painted_outside_the_border_box(cx: &mut TestAppContext) {
+        struct TestView(Rc<RefCell<Option<Bounds<Pixels>>>>);
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let sibling_bounds = self.0.clone();
+                div()
+                    .flex()
+                    .p(px(10.))
+                    .child(
+                        div()
+                            .size(px(20.))
+                            .outline(px(2.), red())
+                            .outline_offset(px(1.)),
+                    )
+                    .child(
+                        canvas(
+                            move |bounds, _, _| *sibling_bounds.borrow_mut() = Some(bounds),
+                            |_, _, _, _| {},
+                        )
+                        .size(px(20.)),
+                    )
+            }
+        }
+
+        let sibling_bounds = Rc::new(RefCell::new(None));
+        let (_, cx) = cx.add_window_view(|_, _| TestView(sibling_bounds.clone()));
+
+        // The outline doesn't take up any space.
+        assert_eq!(
+            *sibling_bounds.borrow(),
+            Some(Bounds::new(point(px(30.), px(10.)), size(px(20.), px(20.))))
+        );
+        cx.update(|window, _| {
+            let scale_factor = window.scale_factor();
+            let quads = &window.rendered_frame.scene.quads;
+            assert_eq!(quads.len(), 1);
+            let outline = &quads[0];
+            assert_eq!(
+                outline.bounds,
+                Bounds::new(point(px(7.), px(7.)), size(px(26.), px(26.))).scale(scale_factor)
+            );
+            assert_eq!(
+                outline.border_widths,
+                Edges::all(px(2.)).scale(scale_factor)
+            );
+            assert_eq!(outline.border_colors, Edges::all(red()));
+        });
+    }
 }
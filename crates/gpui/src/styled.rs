@@ -1,14 +1,18 @@
 use crate::{
-    self as gpui, AbsoluteLength, AlignContent, AlignItems, BorderStyle, CursorStyle,
-    DefiniteLength, Display, Fill, FlexDirection, FlexWrap, Font, FontStyle, FontWeight,
-    GridPlacement, Hsla, JustifyContent, Length, SharedString, StrikethroughStyle, StyleRefinement,
-    TextAlign, TextOverflow, TextStyleRefinement, UnderlineStyle, WhiteSpace, px, relative, rems,
+    self as gpui, AbsoluteLength, AlignContent, AlignItems, BlendMode, BorderStyle, ClipPath,
+    CursorStyle, DefiniteLength, Display, Fill, FlexDirection, FlexWrap, Font, FontFallbacks,
+    FontFeatures, FontStyle, FontVariations, FontWeight, GridPlacement, GridTrack, Hsla,
+    JustifyContent, Length, LineStyle, OverflowWrap, Pixels, SharedString, StrikethroughStyle,
+    StyleRefinement, TextAlign, TextDirection, TextOverflow, TextStyleRefinement, Transition,
+    TransitionEasing, TransitionProperty, TruncationStyle, UnderlineStyle, WhiteSpace, WordBreak,
+    px, relative, rems,
 };
 pub use gpui_macros::{
     border_style_methods, box_shadow_style_methods, cursor_style_methods, margin_style_methods,
     overflow_style_methods, padding_style_methods, position_style_methods,
     visibility_style_methods,
 };
+use std::{ops::Range, time::Duration};
 const ELLIPSIS: SharedString = SharedString::new_static("…");
 
 /// A trait for elements that can be styled.
@@ -78,6 +82,49 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets where wrapped lines of text may be broken within words.
+    fn word_break(mut self, word_break: WordBreak) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .word_break = Some(word_break);
+        self
+    }
+
+    /// Sets what to do with words too long to fit on a line of their own.
+    fn overflow_wrap(mut self, overflow_wrap: OverflowWrap) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .overflow_wrap = Some(overflow_wrap);
+        self
+    }
+
+    /// Only breaks lines between words, letting words too long for a line overflow it.
+    /// [Docs](https://tailwindcss.com/docs/word-break#normal)
+    fn break_normal(self) -> Self {
+        self.word_break(WordBreak::Normal)
+            .overflow_wrap(OverflowWrap::Normal)
+    }
+
+    /// Breaks words too long to fit on a line of their own.
+    /// [Docs](https://tailwindcss.com/docs/overflow-wrap#break-word)
+    fn break_words(self) -> Self {
+        self.overflow_wrap(OverflowWrap::BreakWord)
+    }
+
+    /// Breaks lines between any two characters.
+    /// [Docs](https://tailwindcss.com/docs/word-break#break-all)
+    fn break_all(self) -> Self {
+        self.word_break(WordBreak::BreakAll)
+    }
+
+    /// Inserts a hyphen where a word is broken across lines.
+    fn hyphenate(mut self) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .hyphenate = Some(true);
+        self
+    }
+
     /// Sets the truncate overflowing text with an ellipsis (…) if needed.
     /// [Docs](https://tailwindcss.com/docs/text-overflow#ellipsis)
     fn text_ellipsis(mut self) -> Self {
@@ -87,6 +134,24 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets the background color of selected text within the element.
+    fn text_selection_background(mut self, color: impl Into<Hsla>) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .selection_background_color = Some(color.into());
+        self
+    }
+
+    /// Lets the user select the text within the element, like text made
+    /// [selectable](crate::StyledText::selectable). Plain strings are told apart by their content,
+    /// so give identical strings their own parent element id.
+    fn text_selectable(mut self) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .selectable = Some(true);
+        self
+    }
+
     /// Sets the text overflow behavior of the element.
     fn text_overflow(mut self, overflow: TextOverflow) -> Self {
         self.text_style()
@@ -95,6 +160,23 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets which part of overflowing text is replaced by the truncation suffix.
+    fn text_truncation_style(mut self, truncation_style: TruncationStyle) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .truncation_style = Some(truncation_style);
+        self
+    }
+
+    /// Sets the truncate overflowing text with an ellipsis (…) in the middle if needed,
+    /// keeping both its start and end visible.
+    fn text_ellipsis_middle(mut self) -> Self {
+        let text_style = self.text_style().get_or_insert_with(Default::default);
+        text_style.text_overflow = Some(TextOverflow::Truncate(ELLIPSIS));
+        text_style.truncation_style = Some(TruncationStyle::Middle);
+        self
+    }
+
     /// Set the text alignment of the element.
     fn text_align(mut self, align: TextAlign) -> Self {
         self.text_style()
@@ -118,6 +200,24 @@ pub trait Styled: Sized {
         self.text_align(TextAlign::Right)
     }
 
+    /// Sets the text alignment to the side the text's paragraphs start on
+    fn text_start(mut self) -> Self {
+        self.text_align(TextAlign::Start)
+    }
+
+    /// Sets the text alignment to the side the text's paragraphs end on
+    fn text_end(mut self) -> Self {
+        self.text_align(TextAlign::End)
+    }
+
+    /// Sets the paragraph direction of the text of this element and its children.
+    fn text_direction(mut self, direction: TextDirection) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .direction = Some(direction);
+        self
+    }
+
     /// Sets the truncate to prevent text from wrapping and truncate overflowing text with an ellipsis (…) if needed.
     /// [Docs](https://tailwindcss.com/docs/text-overflow#truncate)
     fn truncate(mut self) -> Self {
@@ -534,10 +634,7 @@ pub trait Styled: Sized {
     /// [Docs](https://tailwindcss.com/docs/text-decoration-line#adding-a-line-through-text)
     fn line_through(mut self) -> Self {
         let style = self.text_style().get_or_insert_with(Default::default);
-        style.strikethrough = Some(StrikethroughStyle {
-            thickness: px(1.),
-            ..Default::default()
-        });
+        style.strikethrough = Some(StrikethroughStyle::default());
         self
     }
 
@@ -565,6 +662,27 @@ pub trait Styled: Sized {
         let style = self.text_style().get_or_insert_with(Default::default);
         let underline = style.underline.get_or_insert_with(Default::default);
         underline.wavy = false;
+        underline.style = LineStyle::Solid;
+        self
+    }
+
+    /// Sets the text decoration style to a dotted line.
+    /// [Docs](https://tailwindcss.com/docs/text-decoration-style)
+    fn text_decoration_dotted(mut self) -> Self {
+        let style = self.text_style().get_or_insert_with(Default::default);
+        let underline = style.underline.get_or_insert_with(Default::default);
+        underline.wavy = false;
+        underline.style = LineStyle::Dotted;
+        self
+    }
+
+    /// Sets the text decoration style to a dashed line.
+    /// [Docs](https://tailwindcss.com/docs/text-decoration-style)
+    fn text_decoration_dashed(mut self) -> Self {
+        let style = self.text_style().get_or_insert_with(Default::default);
+        let underline = style.underline.get_or_insert_with(Default::default);
+        underline.wavy = false;
+        underline.style = LineStyle::Dashed;
         self
     }
 
@@ -630,11 +748,42 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets the font families to lay out characters in when this element's font has no glyph for
+    /// them, tried in order before the platform's own fallback fonts. Families that aren't
+    /// available are skipped.
+    fn font_fallbacks(mut self, families: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .font_fallbacks = Some(FontFallbacks::from_fonts(
+            families.into_iter().map(Into::into).collect(),
+        ));
+        self
+    }
+
+    /// Sets the OpenType features, such as `tnum` for tabular numerals, to shape the text of
+    /// this element and its children with.
+    fn font_features(mut self, features: FontFeatures) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .font_features = Some(features);
+        self
+    }
+
+    /// Sets the axis values, such as `("wght", 450.0)`, to use when this element's font is a
+    /// variable font. Axes the font doesn't have are ignored.
+    fn font_variations(mut self, axes: &[(&str, f32)]) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .font_variations = Some(FontVariations::new(axes));
+        self
+    }
+
     /// Sets the font of this element and its children.
     fn font(mut self, font: Font) -> Self {
         let Font {
             family,
             features,
+            variations,
             fallbacks,
             weight,
             style,
@@ -643,6 +792,7 @@ pub trait Styled: Sized {
         let text_style = self.text_style().get_or_insert_with(Default::default);
         text_style.font_family = Some(family);
         text_style.font_features = Some(features);
+        text_style.font_variations = Some(variations);
         text_style.font_weight = Some(weight);
         text_style.font_style = Some(style);
         text_style.font_fallbacks = fallbacks;
@@ -658,12 +808,81 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Marks this element as a snap point of the scroll container it's in, which settles on it
+    /// once scrolling stops if the container has
+    /// [`scroll_snap`](crate::StatefulInteractiveElement::scroll_snap) set.
+    /// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/scroll-snap-align)
+    fn snap_point(mut self) -> Self {
+        self.style().snap_point = Some(true);
+        self
+    }
+
     /// Sets the opacity of this element and its children.
     fn opacity(mut self, opacity: f32) -> Self {
         self.style().opacity = Some(opacity);
         self
     }
 
+    /// Sets how the quads and sprites of this element and its children are composited with what's
+    /// painted behind them.
+    /// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/mix-blend-mode)
+    fn blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.style().blend_mode = Some(blend_mode);
+        self
+    }
+
+    /// Blurs the content painted behind this element by the given radius, which shows through
+    /// wherever the element's background is translucent.
+    /// [Docs](https://tailwindcss.com/docs/backdrop-blur)
+    fn backdrop_blur(mut self, radius: Pixels) -> Self {
+        self.style().backdrop_blur = Some(radius);
+        self
+    }
+
+    /// Sets the width and color of the outline painted outside of the element's border box.
+    /// Unlike a border, the outline doesn't affect layout, which makes it suited to focus rings.
+    /// [Docs](https://tailwindcss.com/docs/outline-width)
+    fn outline(mut self, width: Pixels, color: impl Into<Hsla>) -> Self {
+        let style = self.style();
+        style.outline_width = Some(width);
+        style.outline_color = Some(color.into());
+        self
+    }
+
+    /// Sets the gap between the element's border box and its outline.
+    /// [Docs](https://tailwindcss.com/docs/outline-offset)
+    fn outline_offset(mut self, offset: Pixels) -> Self {
+        self.style().outline_offset = Some(offset);
+        self
+    }
+
+    /// Clips the element and its children to the given shape, which also restricts where they
+    /// can be hovered or clicked.
+    /// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/clip-path)
+    fn clip_path(mut self, clip_path: ClipPath) -> Self {
+        self.style().clip_path = Some(clip_path);
+        self
+    }
+
+    /// Animates the changes to the given property over the given duration, rather than applying
+    /// them at once. A change that interrupts a transition starts from the value shown at the time.
+    /// Only elements with an id keep track of their properties across frames, so have transitions.
+    fn transition(
+        mut self,
+        property: TransitionProperty,
+        duration: Duration,
+        easing: TransitionEasing,
+    ) -> Self {
+        let transitions = self.style().transitions.get_or_insert_default();
+        transitions.retain(|transition| transition.property != property);
+        transitions.push(Transition {
+            property,
+            duration,
+            easing,
+        });
+        self
+    }
+
     /// Sets the grid columns of this element.
     fn grid_cols(mut self, cols: u16) -> Self {
         self.style().grid_cols = Some(cols);
@@ -676,6 +895,36 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets the sizes of the columns of this grid, which take precedence over
+    /// [`grid_cols`](Self::grid_cols).
+    ///
+    /// Equivalent to the CSS `grid-template-columns`, as in
+    /// `grid_template_columns([fr(1.), px(200.).into(), auto().into()])`.
+    fn grid_template_columns(mut self, tracks: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.style().grid_template_columns = Some(tracks.into_iter().collect());
+        self
+    }
+
+    /// Sets the sizes of the rows of this grid, which take precedence over
+    /// [`grid_rows`](Self::grid_rows).
+    ///
+    /// Equivalent to the CSS `grid-template-rows`.
+    fn grid_template_rows(mut self, tracks: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.style().grid_template_rows = Some(tracks.into_iter().collect());
+        self
+    }
+
+    /// Places this element in the grid between the given row and column lines, which are
+    /// numbered from 1, or from -1 at the end of the grid.
+    ///
+    /// Equivalent to the CSS `grid-area: <row start> / <column start> / <row end> / <column end>`.
+    fn grid_area(mut self, rows: Range<i16>, columns: Range<i16>) -> Self {
+        let grid_location = self.style().grid_location_mut();
+        grid_location.row = GridPlacement::Line(rows.start)..GridPlacement::Line(rows.end);
+        grid_location.column = GridPlacement::Line(columns.start)..GridPlacement::Line(columns.end);
+        self
+    }
+
     /// Sets the column start of this element.
     fn col_start(mut self, start: i16) -> Self {
         let grid_location = self.style().grid_location_mut();
@@ -1,22 +1,216 @@
 use crate::{
-    AssetSource, DevicePixels, IsZero, RenderImage, Result, SharedString, Size,
-    swap_rgba_pa_to_bgra,
+    AssetSource, Background, BackgroundTag, Bounds, DevicePixels, Hsla, IsZero, Point, RenderImage,
+    Result, Rgba, SharedString, Size, TransformationMatrix, point, size, swap_rgba_pa_to_bgra,
 };
+use anyhow::Context as _;
 use image::Frame;
 use resvg::tiny_skia::Pixmap;
 use smallvec::SmallVec;
 use std::{
-    hash::Hash,
+    hash::{Hash, Hasher},
     sync::{Arc, LazyLock},
 };
 
 /// When rendering SVGs, we render them at twice the size to get a higher-quality result.
 pub const SMOOTH_SVG_SCALE_FACTOR: f32 = 2.;
 
+/// The largest factor by which an SVG is rasterized above its layout size to stay sharp when a
+/// transformation scales it up.
+const MAX_SVG_TRANSFORMATION_SCALE: f32 = 4.;
+
+/// Returns how much the given transformation scales an SVG up along its most stretched axis,
+/// rounded up to quarter steps so that similar scales share a rasterization.
+pub(crate) fn svg_transformation_scale(transformation: &TransformationMatrix) -> f32 {
+    let [[a, b], [c, d]] = transformation.rotation_scale;
+    let scale = (a * a + c * c).sqrt().max((b * b + d * d).sqrt());
+    ((scale * 4.).ceil() / 4.).clamp(1., MAX_SVG_TRANSFORMATION_SCALE)
+}
+
 #[derive(Clone, PartialEq, Hash, Eq)]
 pub(crate) struct RenderSvgParams {
     pub(crate) path: SharedString,
     pub(crate) size: Size<DevicePixels>,
+    pub(crate) transformation_scale_percent: u32,
+    pub(crate) preserve_colors: bool,
+    pub(crate) options: SvgRenderOptions,
+}
+
+/// Adjustments applied to an SVG document before it is rasterized. These are part of the
+/// rasterization cache key.
+#[derive(Clone, Default, PartialEq, Hash, Eq)]
+pub(crate) struct SvgRenderOptions {
+    /// CSS injected into the document, taking precedence over its presentation attributes.
+    pub(crate) style_sheet: Option<SharedString>,
+    /// The region of the document to render, in user units.
+    pub(crate) view_box: Option<SvgViewBox>,
+    /// The id of the `<symbol>` to render instead of the whole document.
+    pub(crate) symbol: Option<SharedString>,
+    /// The options the document is parsed with.
+    pub(crate) parse_options: SvgParseOptions,
+    /// The background painted through the shape of the document, instead of its own colors.
+    pub(crate) fill: Option<SvgFill>,
+    /// The radius, in rasterized pixels, of the blur applied to the alpha mask for shadows.
+    pub(crate) blur_radius: Option<u32>,
+}
+
+/// A background painted through the shape of an SVG, hashed by the bits of its values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SvgFill(pub(crate) Background);
+
+impl Eq for SvgFill {}
+
+impl Hash for SvgFill {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.0.tag as u32).hash(state);
+        (self.0.color_space as u32).hash(state);
+        self.0.solid.hash(state);
+        self.0
+            .gradient_angle_or_pattern_height
+            .to_bits()
+            .hash(state);
+        self.0.gradient_center_x.to_bits().hash(state);
+        self.0.gradient_center_y.to_bits().hash(state);
+        self.0.gradient_radius.to_bits().hash(state);
+        for stop in &self.0.colors {
+            stop.color.hash(state);
+            stop.percentage.to_bits().hash(state);
+        }
+    }
+}
+
+impl SvgFill {
+    /// Returns the color of the fill at the given position within bounds of the given size,
+    /// mirroring how quads are painted. Gradients are interpolated in sRGB.
+    fn color_at(&self, position: Point<f32>, size: Size<f32>) -> Rgba {
+        let background = &self.0;
+        let t = match background.tag {
+            BackgroundTag::Solid | BackgroundTag::PatternSlash => {
+                return background.solid.to_rgb();
+            }
+            BackgroundTag::LinearGradient => Self::linear_gradient_position(
+                background.gradient_angle_or_pattern_height,
+                position,
+                size,
+            ),
+            BackgroundTag::RadialGradient => {
+                let center = self.center(size);
+                let farthest_corner = point(
+                    center.x.max(size.width - center.x),
+                    center.y.max(size.height - center.y),
+                );
+                let farthest_distance = (farthest_corner.x * farthest_corner.x
+                    + farthest_corner.y * farthest_corner.y)
+                    .sqrt();
+                let offset = point(position.x - center.x, position.y - center.y);
+                let distance = (offset.x * offset.x + offset.y * offset.y).sqrt();
+                distance / (background.gradient_radius * farthest_distance).max(f32::EPSILON)
+            }
+            BackgroundTag::ConicGradient => {
+                let center = self.center(size);
+                // Measured clockwise from the top, matching CSS conic gradients.
+                let angle = (position.x - center.x)
+                    .atan2(center.y - position.y)
+                    .to_degrees();
+                ((angle - background.gradient_angle_or_pattern_height) / 360.).rem_euclid(1.)
+            }
+        };
+
+        let [stop0, stop1] = background.colors;
+        let t = ((t - stop0.percentage) / (stop1.percentage - stop0.percentage)).clamp(0., 1.);
+        let color0 = stop0.color.to_rgb();
+        let color1 = stop1.color.to_rgb();
+        Rgba {
+            r: color0.r + (color1.r - color0.r) * t,
+            g: color0.g + (color1.g - color0.g) * t,
+            b: color0.b + (color1.b - color0.b) * t,
+            a: color0.a + (color1.a - color0.a) * t,
+        }
+    }
+
+    /// Returns the center of a radial or conic gradient within bounds of the given size.
+    fn center(&self, size: Size<f32>) -> Point<f32> {
+        point(
+            self.0.gradient_center_x * size.width,
+            self.0.gradient_center_y * size.height,
+        )
+    }
+
+    /// Returns how far along a linear gradient with the given angle the position is.
+    fn linear_gradient_position(angle: f32, position: Point<f32>, size: Size<f32>) -> f32 {
+        // -90 degrees to match the CSS gradient angle.
+        let radians = (angle % 360. - 90.).to_radians();
+        let mut direction = point(radians.cos(), radians.sin());
+        // Expand the short side to be the same as the long side.
+        if size.width > size.height {
+            direction.y *= size.height / size.width;
+        } else {
+            direction.x *= size.width / size.height;
+        }
+
+        let half_size = size.map(|dimension| dimension / 2.);
+        let center_to_point = point(position.x - half_size.width, position.y - half_size.height);
+        let direction_length = (direction.x * direction.x + direction.y * direction.y).sqrt();
+        let mut t =
+            (center_to_point.x * direction.x + center_to_point.y * direction.y) / direction_length;
+        if direction.x.abs() > direction.y.abs() {
+            t = (t + half_size.width) / size.width;
+        } else {
+            t = (t + half_size.height) / size.height;
+        }
+        t
+    }
+}
+
+/// Options used when parsing an SVG document, for documents that depend on more than the
+/// defaults, such as ones containing `<text>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgParseOptions {
+    /// The resolution used to convert physical units, like `mm` and `in`, to pixels.
+    pub dpi: f32,
+    /// The font family used for text that doesn't specify one.
+    pub font_family: SharedString,
+    /// The font size used for text that doesn't specify one.
+    pub font_size: f32,
+    /// The size of documents that don't declare their own width and height.
+    pub default_size: Size<f32>,
+}
+
+impl Default for SvgParseOptions {
+    fn default() -> Self {
+        Self {
+            dpi: 96.,
+            font_family: "Times New Roman".into(),
+            font_size: 12.,
+            default_size: size(100., 100.),
+        }
+    }
+}
+
+impl Eq for SvgParseOptions {}
+
+impl Hash for SvgParseOptions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dpi.to_bits().hash(state);
+        self.font_family.hash(state);
+        self.font_size.to_bits().hash(state);
+        self.default_size.width.to_bits().hash(state);
+        self.default_size.height.to_bits().hash(state);
+    }
+}
+
+/// A region of an SVG document in user units, hashed by the bits of its coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SvgViewBox(pub(crate) Bounds<f32>);
+
+impl Eq for SvgViewBox {}
+
+impl Hash for SvgViewBox {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.origin.x.to_bits().hash(state);
+        self.0.origin.y.to_bits().hash(state);
+        self.0.size.width.to_bits().hash(state);
+        self.0.size.height.to_bits().hash(state);
+    }
 }
 
 #[derive(Clone)]
@@ -37,33 +231,43 @@ pub enum SvgSize {
 impl SvgRenderer {
     /// Creates a new SVG renderer with the provided asset source.
     pub fn new(asset_source: Arc<dyn AssetSource>) -> Self {
-        static FONT_DB: LazyLock<Arc<usvg::fontdb::Database>> = LazyLock::new(|| {
-            let mut db = usvg::fontdb::Database::new();
-            db.load_system_fonts();
-            Arc::new(db)
-        });
-        let default_font_resolver = usvg::FontResolver::default_font_selector();
-        let font_resolver = Box::new(
-            move |font: &usvg::Font, db: &mut Arc<usvg::fontdb::Database>| {
-                if db.is_empty() {
-                    *db = FONT_DB.clone();
-                }
-                default_font_resolver(font, db)
-            },
-        );
-        let options = usvg::Options {
-            font_resolver: usvg::FontResolver {
-                select_font: font_resolver,
-                select_fallback: usvg::FontResolver::default_fallback_selector(),
-            },
-            ..Default::default()
-        };
         Self {
             asset_source,
-            usvg_options: Arc::new(options),
+            usvg_options: Arc::new(usvg_options(None, &SvgParseOptions::default())),
         }
     }
 
+    /// Parses the given bytes and returns the size the SVG declares for itself.
+    pub(crate) fn intrinsic_size(
+        &self,
+        bytes: &[u8],
+        parse_options: &SvgParseOptions,
+    ) -> Result<Size<f32>, usvg::Error> {
+        let tree = self.parse_tree(bytes, None, parse_options)?;
+        Ok(size(tree.size().width(), tree.size().height()))
+    }
+
+    /// Returns the size of the view box of each `<symbol>` defined in the given bytes.
+    pub(crate) fn symbol_sizes(&self, bytes: &[u8]) -> Result<Vec<(SharedString, Size<f32>)>> {
+        if !bytes
+            .windows(b"<symbol".len())
+            .any(|window| window == b"<symbol")
+        {
+            return Ok(Vec::new());
+        }
+        let text = std::str::from_utf8(bytes)?;
+        let document = roxmltree::Document::parse(text)?;
+        Ok(document
+            .descendants()
+            .filter(|node| node.has_tag_name("symbol"))
+            .filter_map(|symbol| {
+                let id = symbol.attribute("id")?;
+                let view_box = parse_view_box(symbol.attribute("viewBox")?)?;
+                Some((SharedString::from(id.to_string()), view_box.size))
+            })
+            .collect())
+    }
+
     /// Renders the given bytes into an image buffer.
     pub fn render_single_frame(
         &self,
@@ -74,6 +278,7 @@ impl SvgRenderer {
         self.render_pixmap(
             bytes,
             SvgSize::ScaleFactor(scale_factor * SMOOTH_SVG_SCALE_FACTOR),
+            &SvgRenderOptions::default(),
         )
         .map(|pixmap| {
             let mut buffer =
@@ -92,28 +297,105 @@ impl SvgRenderer {
         })
     }
 
+    /// Rasterizes the given bytes at the width of the given size, keeping the SVG's aspect ratio.
+    /// The SVG is tinted with the given color, or keeps its own colors otherwise.
+    ///
+    /// Unlike the images gpui decodes, the resulting image is RGBA with straight alpha, so it can
+    /// be encoded or written to disk as is. Swap its red and blue channels to paint it with
+    /// [`crate::img`], which expects BGRA.
+    pub fn rasterize(
+        &self,
+        bytes: &[u8],
+        size: Size<DevicePixels>,
+        color: Option<Hsla>,
+    ) -> Result<Arc<RenderImage>> {
+        anyhow::ensure!(!size.is_zero(), "can't render at a zero size");
+
+        let pixmap =
+            self.render_pixmap(bytes, SvgSize::Size(size), &SvgRenderOptions::default())?;
+        let (width, height) = (pixmap.width(), pixmap.height());
+        let pixels = match color {
+            Some(color) => {
+                let mut pixels = fill_alpha_mask(&pixmap, SvgFill(color.into()));
+                // Convert from BGRA to RGBA.
+                for pixel in pixels.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+                pixels
+            }
+            None => pixmap
+                .pixels()
+                .iter()
+                .flat_map(|pixel| {
+                    let pixel = pixel.demultiply();
+                    [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+                })
+                .collect(),
+        };
+        let buffer = image::ImageBuffer::from_raw(width, height, pixels)
+            .context("pixel buffer doesn't match the image size")?;
+        Ok(Arc::new(RenderImage::new(SmallVec::from_const([
+            Frame::new(buffer),
+        ]))))
+    }
+
     pub(crate) fn render_alpha_mask(
         &self,
         params: &RenderSvgParams,
         bytes: Option<&[u8]>,
     ) -> Result<Option<(Size<DevicePixels>, Vec<u8>)>> {
-        anyhow::ensure!(!params.size.is_zero(), "can't render at a zero size");
+        let mask = self.render_pixels(params, bytes, |pixmap| {
+            // Convert the pixmap's pixels into an alpha mask.
+            pixmap.pixels().iter().map(|p| p.alpha()).collect()
+        })?;
+        Ok(match params.options.blur_radius {
+            Some(blur_radius) => mask.map(|(size, mask)| blur_alpha_mask(size, &mask, blur_radius)),
+            None => mask,
+        })
+    }
+
+    /// Renders the SVG with its own fills and strokes for the sprite atlas, which takes BGRA
+    /// pixels with straight alpha.
+    pub(crate) fn render_colors(
+        &self,
+        params: &RenderSvgParams,
+        bytes: Option<&[u8]>,
+    ) -> Result<Option<(Size<DevicePixels>, Vec<u8>)>> {
+        self.render_pixels(params, bytes, |pixmap| {
+            if let Some(fill) = params.options.fill {
+                return fill_alpha_mask(&pixmap, fill);
+            }
+            let mut pixels = pixmap.take();
+            for pixel in pixels.chunks_exact_mut(4) {
+                swap_rgba_pa_to_bgra(pixel);
+            }
+            pixels
+        })
+    }
 
-        let render_pixmap = |bytes| {
-            let pixmap = self.render_pixmap(bytes, SvgSize::Size(params.size))?;
+    fn render_pixels(
+        &self,
+        params: &RenderSvgParams,
+        bytes: Option<&[u8]>,
+        convert: impl FnOnce(Pixmap) -> Vec<u8>,
+    ) -> Result<Option<(Size<DevicePixels>, Vec<u8>)>> {
+        anyhow::ensure!(!params.size.is_zero(), "can't render at a zero size");
 
-            // Convert the pixmap's pixels into an alpha mask.
+        let render_pixmap = |bytes: &[u8]| {
+            let symbol_document;
+            let bytes = match &params.options.symbol {
+                Some(symbol_id) => {
+                    symbol_document = isolate_symbol(bytes, symbol_id)?;
+                    symbol_document.as_bytes()
+                }
+                None => bytes,
+            };
+            let pixmap = self.render_pixmap(bytes, SvgSize::Size(params.size), &params.options)?;
             let size = Size::new(
                 DevicePixels(pixmap.width() as i32),
                 DevicePixels(pixmap.height() as i32),
             );
-            let alpha_mask = pixmap
-                .pixels()
-                .iter()
-                .map(|p| p.alpha())
-                .collect::<Vec<_>>();
-
-            Ok(Some((size, alpha_mask)))
+            Ok(Some((size, convert(pixmap))))
         };
 
         if let Some(bytes) = bytes {
@@ -125,9 +407,47 @@ impl SvgRenderer {
         }
     }
 
-    fn render_pixmap(&self, bytes: &[u8], size: SvgSize) -> Result<Pixmap, usvg::Error> {
-        let tree = usvg::Tree::from_data(bytes, &self.usvg_options)?;
-        let svg_size = tree.size();
+    fn parse_tree(
+        &self,
+        bytes: &[u8],
+        style_sheet: Option<&SharedString>,
+        parse_options: &SvgParseOptions,
+    ) -> Result<usvg::Tree, usvg::Error> {
+        if style_sheet.is_none() && *parse_options == SvgParseOptions::default() {
+            usvg::Tree::from_data(bytes, &self.usvg_options)
+        } else {
+            let options = usvg_options(
+                style_sheet.map(|style_sheet| style_sheet.to_string()),
+                parse_options,
+            );
+            usvg::Tree::from_data(bytes, &options)
+        }
+    }
+
+    fn render_pixmap(
+        &self,
+        bytes: &[u8],
+        size: SvgSize,
+        options: &SvgRenderOptions,
+    ) -> Result<Pixmap, usvg::Error> {
+        let tree = self.parse_tree(bytes, options.style_sheet.as_ref(), &options.parse_options)?;
+        let (svg_size, offset) = match options.view_box {
+            Some(SvgViewBox(view_box)) => {
+                // The root transform maps user units to the document's own size.
+                let region = usvg::Rect::from_xywh(
+                    view_box.origin.x,
+                    view_box.origin.y,
+                    view_box.size.width,
+                    view_box.size.height,
+                )
+                .and_then(|region| region.transform(tree.root().transform()))
+                .ok_or(usvg::Error::InvalidSize)?;
+                let region_size = usvg::Size::from_wh(region.width(), region.height())
+                    .ok_or(usvg::Error::InvalidSize)?;
+                (region_size, (region.x(), region.y()))
+            }
+            None => (tree.size(), (0., 0.)),
+        };
         let scale = match size {
             SvgSize::Size(size) => size.width.0 as f32 / svg_size.width(),
             SvgSize::ScaleFactor(scale) => scale,
@@ -140,10 +460,320 @@ impl SvgRenderer {
         )
         .ok_or(usvg::Error::InvalidSize)?;
 
-        let transform = resvg::tiny_skia::Transform::from_scale(scale, scale);
+        let transform = resvg::tiny_skia::Transform::from_scale(scale, scale)
+            .pre_translate(-offset.0, -offset.1);
 
         resvg::render(&tree, transform, &mut pixmap.as_mut());
 
         Ok(pixmap)
     }
 }
+
+/// Paints the fill through the alpha of the pixmap, producing BGRA pixels with straight alpha for
+/// the sprite atlas. [`SvgRenderer::rasterize`] swaps them to RGBA.
+fn fill_alpha_mask(pixmap: &Pixmap, fill: SvgFill) -> Vec<u8> {
+    let size = size(pixmap.width() as f32, pixmap.height() as f32);
+    let mut pixels = Vec::with_capacity(pixmap.pixels().len() * 4);
+    for (index, pixel) in pixmap.pixels().iter().enumerate() {
+        let x = (index as u32 % pixmap.width()) as f32 + 0.5;
+        let y = (index as u32 / pixmap.width()) as f32 + 0.5;
+        let color = fill.color_at(point(x, y), size);
+        let alpha = color.a * pixel.alpha() as f32 / 255.;
+        pixels.extend([
+            (color.b * 255.).round() as u8,
+            (color.g * 255.).round() as u8,
+            (color.r * 255.).round() as u8,
+            (alpha * 255.).round() as u8,
+        ]);
+    }
+    pixels
+}
+
+/// Blurs an alpha mask like a CSS shadow, approximating a gaussian blur with a standard deviation
+/// of half the radius by three box blurs. The mask is padded on each side by how far the blur
+/// spreads, so it isn't cut off.
+fn blur_alpha_mask(
+    size: Size<DevicePixels>,
+    mask: &[u8],
+    radius: u32,
+) -> (Size<DevicePixels>, Vec<u8>) {
+    // Three box blurs of half-width k have a variance of k * (k + 1).
+    let sigma = radius as f32 / 2.;
+    let half_width = ((-1. + (1. + 4. * sigma * sigma).sqrt()) / 2.).round() as usize;
+    let padding = half_width * 3;
+    let width = size.width.0 as usize;
+    let height = size.height.0 as usize;
+    let padded_width = width + padding * 2;
+    let padded_height = height + padding * 2;
+
+    let mut values = vec![0f32; padded_width * padded_height];
+    for (row, mask_row) in mask.chunks_exact(width.max(1)).take(height).enumerate() {
+        let start = (row + padding) * padded_width + padding;
+        for (value, alpha) in values[start..start + width].iter_mut().zip(mask_row) {
+            *value = *alpha as f32;
+        }
+    }
+
+    if half_width > 0 {
+        let mut scratch = vec![0f32; padded_width.max(padded_height)];
+        for _ in 0..3 {
+            for row in 0..padded_height {
+                let start = row * padded_width;
+                box_blur_line(
+                    &mut values[start..start + padded_width],
+                    &mut scratch,
+                    half_width,
+                );
+            }
+            let mut column = vec![0f32; padded_height];
+            for column_index in 0..padded_width {
+                for (row, value) in column.iter_mut().enumerate() {
+                    *value = values[row * padded_width + column_index];
+                }
+                box_blur_line(&mut column, &mut scratch, half_width);
+                for (row, value) in column.iter().enumerate() {
+                    values[row * padded_width + column_index] = *value;
+                }
+            }
+        }
+    }
+
+    let size = Size::new(
+        DevicePixels(padded_width as i32),
+        DevicePixels(padded_height as i32),
+    );
+    let mask = values
+        .into_iter()
+        .map(|value| value.round().clamp(0., 255.) as u8)
+        .collect();
+    (size, mask)
+}
+
+/// Replaces each value with the average of the values within `half_width` of it, treating values
+/// past the ends as zero.
+fn box_blur_line(line: &mut [f32], scratch: &mut [f32], half_width: usize) {
+    let window_width = (half_width * 2 + 1) as f32;
+    let mut sum: f32 = line.iter().take(half_width).sum();
+    for index in 0..line.len() {
+        if let Some(entering) = line.get(index + half_width) {
+            sum += entering;
+        }
+        scratch[index] = sum / window_width;
+        if index >= half_width {
+            sum -= line[index - half_width];
+        }
+    }
+    line.copy_from_slice(&scratch[..line.len()]);
+}
+
+fn usvg_options(
+    style_sheet: Option<String>,
+    parse_options: &SvgParseOptions,
+) -> usvg::Options<'static> {
+    static FONT_DB: LazyLock<Arc<usvg::fontdb::Database>> = LazyLock::new(|| {
+        let mut db = usvg::fontdb::Database::new();
+        db.load_system_fonts();
+        Arc::new(db)
+    });
+    let default_font_resolver = usvg::FontResolver::default_font_selector();
+    let font_resolver = Box::new(
+        move |font: &usvg::Font, db: &mut Arc<usvg::fontdb::Database>| {
+            if db.is_empty() {
+                *db = FONT_DB.clone();
+            }
+            default_font_resolver(font, db)
+        },
+    );
+    let mut options = usvg::Options {
+        font_resolver: usvg::FontResolver {
+            select_font: font_resolver,
+            select_fallback: usvg::FontResolver::default_fallback_selector(),
+        },
+        style_sheet,
+        dpi: parse_options.dpi,
+        font_family: parse_options.font_family.to_string(),
+        font_size: parse_options.font_size,
+        ..Default::default()
+    };
+    if let Some(default_size) = usvg::Size::from_wh(
+        parse_options.default_size.width,
+        parse_options.default_size.height,
+    ) {
+        options.default_size = default_size;
+    }
+    options
+}
+
+/// Wraps the document so that only the `<symbol>` with the given id is rendered, sized to the
+/// symbol's view box.
+fn isolate_symbol(bytes: &[u8], symbol_id: &str) -> Result<String> {
+    let text = std::str::from_utf8(bytes)?;
+    let document = roxmltree::Document::parse(text)?;
+    let symbol = document
+        .descendants()
+        .find(|node| node.has_tag_name("symbol") && node.attribute("id") == Some(symbol_id))
+        .with_context(|| format!("no symbol with id {symbol_id:?}"))?;
+    let view_box = symbol
+        .attribute("viewBox")
+        .and_then(parse_view_box)
+        .with_context(|| format!("symbol {symbol_id:?} has no valid viewBox"))?;
+
+    // Elements in `<defs>` aren't rendered, but can still be referenced by `<use>`.
+    let root = &text[document.root_element().range()];
+    let width = view_box.size.width;
+    let height = view_box.size.height;
+    Ok(format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{width}" height="{height}" viewBox="0 0 {width} {height}"><defs>{root}</defs><use xlink:href="#{symbol_id}" width="{width}" height="{height}"/></svg>"##
+    ))
+}
+
+fn parse_view_box(view_box: &str) -> Option<Bounds<f32>> {
+    let mut values = view_box
+        .split(|character: char| character.is_whitespace() || character == ',')
+        .filter(|value| !value.is_empty())
+        .map(|value| value.parse::<f32>().ok());
+    let (x, y, width, height) = (
+        values.next()??,
+        values.next()??,
+        values.next()??,
+        values.next()??,
+    );
+    if values.next().is_some() || width <= 0. || height <= 0. {
+        return None;
+    }
+    Some(Bounds::new(point(x, y), size(width, height)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as gpui, TestAppContext, conic_gradient, hsla, linear_color_stop, linear_gradient,
+        radial_gradient,
+    };
+
+    #[test]
+    fn test_fill_color_at() {
+        let black = hsla(0., 0., 0., 1.);
+        let white = hsla(0., 0., 1., 1.);
+        let fill = SvgFill(linear_gradient(
+            90.,
+            linear_color_stop(black, 0.),
+            linear_color_stop(white, 1.),
+        ));
+        let size = size(10., 10.);
+        assert_eq!(fill.color_at(point(0., 5.), size), black.to_rgb());
+        assert_eq!(fill.color_at(point(10., 5.), size), white.to_rgb());
+        assert!((fill.color_at(point(5., 0.), size).r - 0.5).abs() < 1e-5);
+
+        let fill = SvgFill(radial_gradient(
+            point(0.5, 0.5),
+            1.,
+            linear_color_stop(black, 0.),
+            linear_color_stop(white, 1.),
+        ));
+        assert_eq!(fill.color_at(point(5., 5.), size), black.to_rgb());
+        assert_eq!(fill.color_at(point(10., 10.), size), white.to_rgb());
+
+        let fill = SvgFill(conic_gradient(
+            point(0.5, 0.5),
+            0.,
+            linear_color_stop(black, 0.),
+            linear_color_stop(white, 1.),
+        ));
+        assert_eq!(fill.color_at(point(5., 0.), size), black.to_rgb());
+        assert!((fill.color_at(point(5., 10.), size).r - 0.5).abs() < 1e-5);
+
+        let fill = SvgFill(black.into());
+        assert_eq!(fill.color_at(point(10., 5.), size), black.to_rgb());
+    }
+
+    #[test]
+    fn test_blur_alpha_mask() {
+        let size = size(DevicePixels(3), DevicePixels(1));
+        let (blurred_size, blurred) = blur_alpha_mask(size, &[0, 255, 0], 4);
+        assert_eq!(blurred_size, self::size(DevicePixels(15), DevicePixels(13)));
+        assert_eq!(blurred.len(), 15 * 13);
+
+        let total: u32 = blurred.iter().map(|alpha| *alpha as u32).sum();
+        assert!(
+            total.abs_diff(255) < 16,
+            "blur should preserve coverage, got {total}"
+        );
+        let center = blurred[6 * 15 + 7];
+        assert!(blurred.iter().all(|alpha| *alpha <= center));
+        assert!(center < 255);
+    }
+
+    #[gpui::test]
+    async fn test_rasterize_svg(cx: &mut TestAppContext) {
+        const WIDE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect width="20" height="10" fill="red"/></svg>"#;
+
+        let image = cx
+            .update(|cx| {
+                cx.rasterize_svg(
+                    WIDE_SVG.as_bytes(),
+                    size(DevicePixels(40), DevicePixels(40)),
+                    None,
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(image.size(0), size(DevicePixels(40), DevicePixels(20)));
+        assert_eq!(image.as_bytes(0).unwrap()[..4], [255, 0, 0, 255]);
+
+        let image = cx
+            .update(|cx| {
+                cx.rasterize_svg(
+                    WIDE_SVG.as_bytes(),
+                    size(DevicePixels(40), DevicePixels(40)),
+                    Some(hsla(0., 0., 1., 1.)),
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(image.as_bytes(0).unwrap()[..4], [255, 255, 255, 255]);
+    }
+
+    #[gpui::test]
+    async fn test_rasterize_svg_channel_order(cx: &mut TestAppContext) {
+        const TRANSLUCENT_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10" fill="rgb(255,128,0)" fill-opacity="0.5"/></svg>"#;
+
+        let image = cx
+            .update(|cx| {
+                cx.rasterize_svg(
+                    TRANSLUCENT_SVG.as_bytes(),
+                    size(DevicePixels(10), DevicePixels(10)),
+                    None,
+                )
+            })
+            .await
+            .unwrap();
+        // The pixels are RGBA with straight alpha, rather than premultiplied.
+        let expected = [255, 128, 0, 128];
+        for (channel, expected) in image.as_bytes(0).unwrap()[..4].iter().zip(expected) {
+            assert!(
+                channel.abs_diff(expected) <= 1,
+                "expected roughly {expected}, got {channel}"
+            );
+        }
+
+        let image = cx
+            .update(|cx| {
+                cx.rasterize_svg(
+                    TRANSLUCENT_SVG.as_bytes(),
+                    size(DevicePixels(10), DevicePixels(10)),
+                    Some(hsla(240. / 360., 1., 0.5, 1.)),
+                )
+            })
+            .await
+            .unwrap();
+        let expected = [0, 0, 255, 128];
+        for (channel, expected) in image.as_bytes(0).unwrap()[..4].iter().zip(expected) {
+            assert!(
+                channel.abs_diff(expected) <= 1,
+                "expected roughly {expected}, got {channel}"
+            );
+        }
+    }
+}
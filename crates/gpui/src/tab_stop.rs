@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::Range};
 
 use ::sum_tree::SumTree;
 use collections::FxHashMap;
@@ -13,12 +13,17 @@ pub(crate) struct TabStopMap {
     pub(crate) insertion_history: Vec<TabStopOperation>,
     by_id: FxHashMap<FocusId, TabStopNode>,
     order: SumTree<TabStopNode>,
+    /// For each open group, the insertion index where it began if it's scoped.
+    group_starts: Vec<Option<usize>>,
+    /// The ranges of insertion indices of the scoped groups, which traversal doesn't leave.
+    scopes: Vec<Range<usize>>,
 }
 
 #[derive(Debug, Clone)]
 pub enum TabStopOperation {
     Insert(FocusHandle),
     Group(TabIndex),
+    ScopedGroup(TabIndex),
     GroupEnd,
 }
 
@@ -70,6 +75,8 @@ impl Default for TabStopMap {
             insertion_history: Vec::new(),
             by_id: FxHashMap::default(),
             order: SumTree::new(()),
+            group_starts: Vec::new(),
+            scopes: Vec::new(),
         }
     }
 }
@@ -82,7 +89,8 @@ impl TabStopMap {
         path.0.push(focus_handle.tab_index);
         let order = TabStopNode {
             node_insertion_index: self.insertion_history.len() - 1,
-            tab_stop: focus_handle.tab_stop,
+            // Negative tab indices keep the element focusable but out of the tab order.
+            tab_stop: focus_handle.tab_stop && focus_handle.tab_index >= 0,
             path,
         };
         self.by_id.insert(focus_handle.id, order.clone());
@@ -93,11 +101,24 @@ impl TabStopMap {
         self.insertion_history
             .push(TabStopOperation::Group(tab_index));
         self.current_path.0.push(tab_index);
+        self.group_starts.push(None);
+    }
+
+    /// Begins a group that traversal stays within while focus is inside of it, wrapping at its
+    /// edges.
+    pub fn begin_scoped_group(&mut self, tab_index: isize) {
+        self.group_starts.push(Some(self.insertion_history.len()));
+        self.insertion_history
+            .push(TabStopOperation::ScopedGroup(tab_index));
+        self.current_path.0.push(tab_index);
     }
 
     pub fn end_group(&mut self) {
         self.insertion_history.push(TabStopOperation::GroupEnd);
         self.current_path.0.pop();
+        if let Some(Some(start)) = self.group_starts.pop() {
+            self.scopes.push(start..self.insertion_history.len());
+        }
     }
 
     pub fn clear(&mut self) {
@@ -123,6 +144,11 @@ impl TabStopMap {
         let Some(node) = self.tab_node_for_focus_id(focused_id) else {
             return self.next(None);
         };
+        if let Some(scope) = self.scope_for_node(node) {
+            return self
+                .next_in_scope(node, scope)
+                .and_then(|order| self.focus_handle_for_order(order));
+        }
         let item = self.next_inner(node);
 
         if let Some(item) = item {
@@ -160,6 +186,11 @@ impl TabStopMap {
         let Some(node) = self.tab_node_for_focus_id(focused_id) else {
             return self.prev(None);
         };
+        if let Some(scope) = self.scope_for_node(node) {
+            return self
+                .prev_in_scope(node, scope)
+                .and_then(|order| self.focus_handle_for_order(order));
+        }
         let item = self.prev_inner(node);
 
         if let Some(item) = item {
@@ -182,11 +213,51 @@ impl TabStopMap {
         cursor.item()
     }
 
+    /// The innermost scoped group containing the node. Scoped groups are either nested or
+    /// disjoint, so this is the shortest one containing it.
+    fn scope_for_node(&self, node: &TabStopNode) -> Option<&Range<usize>> {
+        self.scopes
+            .iter()
+            .filter(|scope| scope.contains(&node.node_insertion_index))
+            .min_by_key(|scope| scope.len())
+    }
+
+    fn next_in_scope(&self, node: &TabStopNode, scope: &Range<usize>) -> Option<&TabStopNode> {
+        let is_in_scope =
+            |item: &TabStopNode| item.tab_stop && scope.contains(&item.node_insertion_index);
+        let mut cursor = self.order.cursor::<TabStopNode>(());
+        cursor.seek(&node, Bias::Left);
+        cursor.next();
+        while let Some(item) = cursor.item() {
+            if is_in_scope(item) {
+                return Some(item);
+            }
+            cursor.next();
+        }
+        self.order.iter().find(|item| is_in_scope(item))
+    }
+
+    fn prev_in_scope(&self, node: &TabStopNode, scope: &Range<usize>) -> Option<&TabStopNode> {
+        let is_in_scope =
+            |item: &TabStopNode| item.tab_stop && scope.contains(&item.node_insertion_index);
+        let mut cursor = self.order.cursor::<TabStopNode>(());
+        cursor.seek(&node, Bias::Left);
+        cursor.prev();
+        while let Some(item) = cursor.item() {
+            if is_in_scope(item) {
+                return Some(item);
+            }
+            cursor.prev();
+        }
+        self.order.iter().filter(|item| is_in_scope(item)).last()
+    }
+
     pub fn replay(&mut self, nodes: &[TabStopOperation]) {
         for node in nodes {
             match node {
                 TabStopOperation::Insert(focus_handle) => self.insert(focus_handle),
                 TabStopOperation::Group(tab_index) => self.begin_group(*tab_index),
+                TabStopOperation::ScopedGroup(tab_index) => self.begin_scoped_group(*tab_index),
                 TabStopOperation::GroupEnd => self.end_group(),
             }
         }
@@ -547,6 +618,57 @@ mod tests {
             .assert()
     }
 
+    #[test]
+    fn test_negative_tab_index_is_skipped() {
+        let focus_map = Arc::new(FocusMap::default());
+        let mut tab_map = TabStopMap::default();
+        let first = FocusHandle::new(&focus_map).tab_stop(true).tab_index(0);
+        let skipped = FocusHandle::new(&focus_map).tab_stop(true).tab_index(-1);
+        let second = FocusHandle::new(&focus_map).tab_stop(true).tab_index(1);
+        tab_map.insert(&first);
+        tab_map.insert(&skipped);
+        tab_map.insert(&second);
+
+        assert_eq!(tab_map.next(None).map(|handle| handle.id), Some(first.id));
+        assert_eq!(
+            tab_map.next(Some(&first.id)).map(|handle| handle.id),
+            Some(second.id)
+        );
+        assert_eq!(
+            tab_map.next(Some(&skipped.id)).map(|handle| handle.id),
+            Some(first.id),
+            "traversal from an element out of the tab order should continue in the tab order"
+        );
+    }
+
+    #[test]
+    fn test_scoped_tab_group() {
+        let focus_map = Arc::new(FocusMap::default());
+        let mut tab_map = TabStopMap::default();
+        let outside = FocusHandle::new(&focus_map).tab_stop(true).tab_index(0);
+        let first = FocusHandle::new(&focus_map).tab_stop(true).tab_index(0);
+        let second = FocusHandle::new(&focus_map).tab_stop(true).tab_index(1);
+        let after = FocusHandle::new(&focus_map).tab_stop(true).tab_index(2);
+        tab_map.insert(&outside);
+        tab_map.begin_scoped_group(1);
+        tab_map.insert(&second);
+        tab_map.insert(&first);
+        tab_map.end_group();
+        tab_map.insert(&after);
+
+        let next = |id| tab_map.next(Some(id)).map(|handle| handle.id);
+        let prev = |id| tab_map.prev(Some(id)).map(|handle| handle.id);
+        assert_eq!(next(&outside.id), Some(first.id));
+        assert_eq!(next(&first.id), Some(second.id));
+        assert_eq!(
+            next(&second.id),
+            Some(first.id),
+            "traversal should wrap within the scoped group"
+        );
+        assert_eq!(prev(&first.id), Some(second.id));
+        assert_eq!(next(&after.id), Some(outside.id));
+    }
+
     #[test]
     fn test_sibling_groups() {
         TabStopMapTest::new()
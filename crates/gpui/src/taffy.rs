@@ -4,11 +4,11 @@ use crate::{
 };
 use collections::{FxHashMap, FxHashSet};
 use stacksafe::{StackSafe, stacksafe};
-use std::{fmt::Debug, ops::Range};
+use std::{fmt::Debug, mem, ops::Range};
 use taffy::{
     TaffyTree, TraversePartialTree as _,
     geometry::{Point as TaffyPoint, Rect as TaffyRect, Size as TaffySize},
-    style::AvailableSpace as TaffyAvailableSpace,
+    style::{AvailableSpace as TaffyAvailableSpace, Dimension},
     tree::NodeId,
 };
 
@@ -31,6 +31,74 @@ pub struct TaffyLayoutEngine {
     absolute_layout_bounds: FxHashMap<LayoutId, Bounds<Pixels>>,
     computed_layouts: FxHashSet<LayoutId>,
     layout_bounds_scratch_space: Vec<LayoutId>,
+    content_sized_nodes: Vec<ContentSizedNode>,
+}
+
+/// A node sized with [`Length::MinContent`], [`Length::MaxContent`] or [`Length::FitContent`],
+/// which taffy doesn't support. They're resolved to definite sizes by laying out the node on its
+/// own under min-content and max-content constraints before laying out its tree.
+struct ContentSizedNode {
+    id: LayoutId,
+    size: Size<Length>,
+    min_size: Size<Length>,
+    max_size: Size<Length>,
+    flex_basis: Length,
+    rem_size: Pixels,
+    scale_factor: f32,
+}
+
+impl ContentSizedNode {
+    fn new(id: LayoutId, style: &Style, rem_size: Pixels, scale_factor: f32) -> Option<Self> {
+        let node = Self {
+            id,
+            size: style.size,
+            min_size: style.min_size,
+            max_size: style.max_size,
+            flex_basis: style.flex_basis,
+            rem_size,
+            scale_factor,
+        };
+        node.needs(is_content_sized).then_some(node)
+    }
+
+    /// Returns whether any of the node's sizes satisfy the predicate.
+    fn needs(&self, f: impl Fn(Length) -> bool) -> bool {
+        [
+            self.size.width,
+            self.size.height,
+            self.min_size.width,
+            self.min_size.height,
+            self.max_size.width,
+            self.max_size.height,
+            self.flex_basis,
+        ]
+        .into_iter()
+        .any(f)
+    }
+
+    /// Resolves a content-based length from the node's min-content and max-content sizes along
+    /// the same axis.
+    fn resolve(&self, length: Length, min_content: f32, max_content: f32) -> Option<Dimension> {
+        match length {
+            Length::MinContent => Some(Dimension::length(min_content)),
+            Length::MaxContent => Some(Dimension::length(max_content)),
+            Length::FitContent(DefiniteLength::Absolute(limit)) => {
+                let limit = limit.to_taffy(self.rem_size, self.scale_factor);
+                Some(Dimension::length(max_content.min(limit).max(min_content)))
+            }
+            // Fractions of the parent's size are only known when laying out the tree, so the
+            // limit is applied as a maximum size instead.
+            Length::FitContent(DefiniteLength::Fraction(_)) => Some(Dimension::length(max_content)),
+            Length::Definite(_) | Length::Auto => None,
+        }
+    }
+}
+
+fn is_content_sized(length: Length) -> bool {
+    matches!(
+        length,
+        Length::MinContent | Length::MaxContent | Length::FitContent(_)
+    )
 }
 
 const EXPECT_MESSAGE: &str = "we should avoid taffy layout errors by construction if possible";
@@ -44,6 +112,7 @@ impl TaffyLayoutEngine {
             absolute_layout_bounds: FxHashMap::default(),
             computed_layouts: FxHashSet::default(),
             layout_bounds_scratch_space: Vec::new(),
+            content_sized_nodes: Vec::new(),
         }
     }
 
@@ -51,6 +120,7 @@ impl TaffyLayoutEngine {
         self.taffy.clear();
         self.absolute_layout_bounds.clear();
         self.computed_layouts.clear();
+        self.content_sized_nodes.clear();
     }
 
     pub fn request_layout(
@@ -62,7 +132,7 @@ impl TaffyLayoutEngine {
     ) -> LayoutId {
         let taffy_style = style.to_taffy(rem_size, scale_factor);
 
-        if children.is_empty() {
+        let id = if children.is_empty() {
             self.taffy
                 .new_leaf(taffy_style)
                 .expect(EXPECT_MESSAGE)
@@ -73,7 +143,10 @@ impl TaffyLayoutEngine {
                 .new_with_children(taffy_style, LayoutId::to_taffy_slice(children))
                 .expect(EXPECT_MESSAGE)
                 .into()
-        }
+        };
+        self.content_sized_nodes
+            .extend(ContentSizedNode::new(id, &style, rem_size, scale_factor));
+        id
     }
 
     pub fn request_measured_layout(
@@ -91,7 +164,8 @@ impl TaffyLayoutEngine {
     ) -> LayoutId {
         let taffy_style = style.to_taffy(rem_size, scale_factor);
 
-        self.taffy
+        let id = self
+            .taffy
             .new_leaf_with_context(
                 taffy_style,
                 NodeContext {
@@ -99,7 +173,10 @@ impl TaffyLayoutEngine {
                 },
             )
             .expect(EXPECT_MESSAGE)
-            .into()
+            .into();
+        self.content_sized_nodes
+            .extend(ContentSizedNode::new(id, &style, rem_size, scale_factor));
+        id
     }
 
     // Used to understand performance
@@ -197,6 +274,121 @@ impl TaffyLayoutEngine {
             transform(available_space.height),
         );
 
+        // Nodes are requested before their parents, so content-sized descendants are resolved
+        // before the nodes that contain them are measured.
+        for node in mem::take(&mut self.content_sized_nodes) {
+            self.resolve_content_sizes(node, window, cx);
+        }
+
+        self.compute_scaled_layout(id, available_space, window, cx);
+    }
+
+    /// Replaces the content-based sizes of a node with the sizes of its content.
+    fn resolve_content_sizes(&mut self, node: ContentSizedNode, window: &mut Window, cx: &mut App) {
+        let min_content =
+            if node.needs(|length| matches!(length, Length::MinContent | Length::FitContent(_))) {
+                self.measure_content(node.id, AvailableSpace::MinContent, window, cx)
+            } else {
+                TaffySize::ZERO
+            };
+        let max_content =
+            if node.needs(|length| matches!(length, Length::MaxContent | Length::FitContent(_))) {
+                self.measure_content(node.id, AvailableSpace::MaxContent, window, cx)
+            } else {
+                TaffySize::ZERO
+            };
+
+        let mut style = self
+            .taffy
+            .style(node.id.into())
+            .expect(EXPECT_MESSAGE)
+            .clone();
+        let resolve_axis = |target: &mut Dimension, length: Length, min: f32, max: f32| {
+            if let Some(dimension) = node.resolve(length, min, max) {
+                *target = dimension;
+            }
+        };
+        let resolve_size = |target: &mut TaffySize<Dimension>, lengths: &Size<Length>| {
+            let (min, max) = (min_content, max_content);
+            resolve_axis(&mut target.width, lengths.width, min.width, max.width);
+            resolve_axis(&mut target.height, lengths.height, min.height, max.height);
+        };
+        resolve_size(&mut style.size, &node.size);
+        resolve_size(&mut style.min_size, &node.min_size);
+        resolve_size(&mut style.max_size, &node.max_size);
+
+        // A fit-content size relative to the parent is the max-content size, at most the limit,
+        // but never smaller than the min-content size.
+        if let Length::FitContent(DefiniteLength::Fraction(fraction)) = node.size.width {
+            if node.max_size.width == Length::Auto {
+                style.max_size.width = Dimension::percent(fraction);
+            }
+            if node.min_size.width == Length::Auto {
+                style.min_size.width = Dimension::length(min_content.width);
+            }
+        }
+        if let Length::FitContent(DefiniteLength::Fraction(fraction)) = node.size.height {
+            if node.max_size.height == Length::Auto {
+                style.max_size.height = Dimension::percent(fraction);
+            }
+            if node.min_size.height == Length::Auto {
+                style.min_size.height = Dimension::length(min_content.height);
+            }
+        }
+
+        // The flex basis is along the main axis of the parent's flex layout.
+        let is_row = self
+            .taffy
+            .parent(node.id.into())
+            .and_then(|parent| self.taffy.style(parent).ok())
+            .is_none_or(|parent_style| {
+                matches!(
+                    parent_style.flex_direction,
+                    taffy::FlexDirection::Row | taffy::FlexDirection::RowReverse
+                )
+            });
+        if is_row {
+            resolve_axis(
+                &mut style.flex_basis,
+                node.flex_basis,
+                min_content.width,
+                max_content.width,
+            );
+        } else {
+            resolve_axis(
+                &mut style.flex_basis,
+                node.flex_basis,
+                min_content.height,
+                max_content.height,
+            );
+        }
+
+        self.taffy
+            .set_style(node.id.into(), style)
+            .expect(EXPECT_MESSAGE);
+    }
+
+    /// Lays out a node on its own under the given constraint, returning its size in scaled pixels.
+    fn measure_content(
+        &mut self,
+        id: LayoutId,
+        constraint: AvailableSpace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> TaffySize<f32> {
+        self.compute_scaled_layout(id, size(constraint, constraint), window, cx);
+        self.taffy.layout(id.into()).expect(EXPECT_MESSAGE).size
+    }
+
+    #[stacksafe]
+    fn compute_scaled_layout(
+        &mut self,
+        id: LayoutId,
+        available_space: Size<AvailableSpace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let scale_factor = window.scale_factor();
         self.taffy
             .compute_layout_with_measure(
                 id.into(),
@@ -295,7 +487,9 @@ trait ToTaffy<Output> {
 
 impl ToTaffy<taffy::style::Style> for Style {
     fn to_taffy(&self, rem_size: Pixels, scale_factor: f32) -> taffy::style::Style {
-        use taffy::style_helpers::{fr, length, minmax, repeat};
+        use taffy::style_helpers::{
+            auto, fit_content, fr, length, max_content, min_content, minmax, percent, repeat,
+        };
 
         fn to_grid_line(
             placement: &Range<crate::GridPlacement>,
@@ -314,6 +508,33 @@ impl ToTaffy<taffy::style::Style> for Style {
                 .unwrap_or_default()
         }
 
+        let to_grid_template = |tracks: &Option<Vec<crate::GridTrack>>, count: &Option<u16>| {
+            let Some(tracks) = tracks else {
+                return to_grid_repeat(count);
+            };
+            tracks
+                .iter()
+                .map(|track| {
+                    let track: taffy::TrackSizingFunction = match track {
+                        crate::GridTrack::Definite(DefiniteLength::Absolute(absolute)) => {
+                            length(absolute.to_taffy(rem_size, scale_factor))
+                        }
+                        crate::GridTrack::Definite(DefiniteLength::Fraction(fraction)) => {
+                            percent(*fraction)
+                        }
+                        crate::GridTrack::Fraction(fraction) => fr(*fraction),
+                        crate::GridTrack::Auto => auto(),
+                        crate::GridTrack::MinContent => min_content(),
+                        crate::GridTrack::MaxContent => max_content(),
+                        crate::GridTrack::FitContent(limit) => {
+                            fit_content(limit.to_taffy(rem_size, scale_factor))
+                        }
+                    };
+                    taffy::GridTemplateComponent::Single(track)
+                })
+                .collect()
+        };
+
         taffy::style::Style {
             display: self.display.into(),
             overflow: self.overflow.into(),
@@ -337,8 +558,8 @@ impl ToTaffy<taffy::style::Style> for Style {
             flex_basis: self.flex_basis.to_taffy(rem_size, scale_factor),
             flex_grow: self.flex_grow,
             flex_shrink: self.flex_shrink,
-            grid_template_rows: to_grid_repeat(&self.grid_rows),
-            grid_template_columns: to_grid_repeat(&self.grid_cols),
+            grid_template_rows: to_grid_template(&self.grid_template_rows, &self.grid_rows),
+            grid_template_columns: to_grid_template(&self.grid_template_columns, &self.grid_cols),
             grid_row: self
                 .grid_location
                 .as_ref()
@@ -377,7 +598,10 @@ impl ToTaffy<taffy::style::LengthPercentageAuto> for Length {
     ) -> taffy::prelude::LengthPercentageAuto {
         match self {
             Length::Definite(length) => length.to_taffy(rem_size, scale_factor),
-            Length::Auto => taffy::prelude::LengthPercentageAuto::auto(),
+            // Content-based lengths only apply to sizes, so they're auto in offsets and margins.
+            Length::Auto | Length::MinContent | Length::MaxContent | Length::FitContent(_) => {
+                taffy::prelude::LengthPercentageAuto::auto()
+            }
         }
     }
 }
@@ -386,7 +610,10 @@ impl ToTaffy<taffy::style::Dimension> for Length {
     fn to_taffy(&self, rem_size: Pixels, scale_factor: f32) -> taffy::prelude::Dimension {
         match self {
             Length::Definite(length) => length.to_taffy(rem_size, scale_factor),
-            Length::Auto => taffy::prelude::Dimension::auto(),
+            // Content-based sizes are resolved by `TaffyLayoutEngine::compute_layout`.
+            Length::Auto | Length::MinContent | Length::MaxContent | Length::FitContent(_) => {
+                taffy::prelude::Dimension::auto()
+            }
         }
     }
 }
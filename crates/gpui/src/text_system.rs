@@ -1,11 +1,15 @@
+mod bidi;
 mod font_fallbacks;
 mod font_features;
+mod font_variations;
 mod line;
 mod line_layout;
 mod line_wrapper;
 
+pub use bidi::*;
 pub use font_fallbacks::*;
 pub use font_features::*;
+pub use font_variations::*;
 pub use line::*;
 pub use line_layout::*;
 pub use line_wrapper::*;
@@ -14,7 +18,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     Bounds, DevicePixels, Hsla, Pixels, PlatformTextSystem, Point, Result, SharedString, Size,
-    StrikethroughStyle, UnderlineStyle, px,
+    StrikethroughStyle, TextDirection, UnderlineStyle, px,
 };
 use anyhow::{Context as _, anyhow};
 use collections::FxHashMap;
@@ -31,6 +35,7 @@ use std::{
     ops::{Deref, DerefMut, Range},
     sync::Arc,
 };
+use unicode_segmentation::UnicodeSegmentation;
 
 /// An opaque identifier for a specific font.
 #[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
@@ -130,6 +135,82 @@ impl TextSystem {
         }
     }
 
+    /// Push font runs for the given range of text laid out in the given font, laying out any
+    /// grapheme clusters the font has no glyphs for in the first of its
+    /// [fallbacks](Font::fallbacks) that has them all, before the platform's own fallback is
+    /// consulted. Each cluster is laid out in a single font so that sequences like emoji joined
+    /// by zero width joiners aren't split across fonts. Fallbacks that can't be loaded are
+    /// skipped.
+    fn push_font_runs(
+        &self,
+        text: &str,
+        range: Range<usize>,
+        font: &Font,
+        font_id: FontId,
+        merge_with_last: bool,
+        font_runs: &mut Vec<FontRun>,
+    ) {
+        let push_font_run = |font_runs: &mut Vec<FontRun>, font_id, len, merge| {
+            if let Some(font_run) = font_runs.last_mut()
+                && font_run.font_id == font_id
+                && merge
+            {
+                font_run.len += len;
+            } else {
+                font_runs.push(FontRun { len, font_id });
+            }
+        };
+
+        let fallbacks = font
+            .fallbacks
+            .as_ref()
+            .map_or(&[][..], |fallbacks| fallbacks.fallback_list());
+        let Some(text) = text.get(range.clone()).filter(|_| !fallbacks.is_empty()) else {
+            push_font_run(font_runs, font_id, range.len(), merge_with_last);
+            return;
+        };
+
+        let fallback_font_ids = fallbacks
+            .iter()
+            .filter_map(|family| {
+                self.font_id(&Font {
+                    family: family.clone().into(),
+                    fallbacks: None,
+                    ..font.clone()
+                })
+                .ok()
+            })
+            .collect::<SmallVec<[FontId; 2]>>();
+        // Coverage is only cached for the run, as text tends to repeat its characters.
+        let mut glyph_coverage = FxHashMap::<(FontId, char), bool>::default();
+        let mut covers = |font_id: FontId, cluster: &str| {
+            cluster
+                .chars()
+                .filter(|c| !is_default_ignorable(*c))
+                .all(|c| {
+                    *glyph_coverage.entry((font_id, c)).or_insert_with(|| {
+                        self.platform_text_system
+                            .glyph_for_char(font_id, c)
+                            .is_some()
+                    })
+                })
+        };
+        let mut merge = merge_with_last;
+        for cluster in text.graphemes(true) {
+            let cluster_font_id = if covers(font_id, cluster) {
+                font_id
+            } else {
+                fallback_font_ids
+                    .iter()
+                    .copied()
+                    .find(|fallback_font_id| covers(*fallback_font_id, cluster))
+                    .unwrap_or(font_id)
+            };
+            push_font_run(font_runs, cluster_font_id, cluster.len(), merge);
+            merge = true;
+        }
+    }
+
     /// Get the Font for the Font Id.
     pub fn get_font_for_id(&self, id: FontId) -> Option<Font> {
         let lock = self.font_ids_by_font.read();
@@ -250,6 +331,19 @@ impl TextSystem {
         self.read_metrics(font_id, |metrics| metrics.x_height(font_size))
     }
 
+    /// Get the suggested distance from the baseline up to the top of a strikethrough for the
+    /// given font and size.
+    pub fn strikethrough_position(&self, font_id: FontId, font_size: Pixels) -> Pixels {
+        self.read_metrics(font_id, |metrics| metrics.strikethrough_position(font_size))
+    }
+
+    /// Get the suggested thickness of a strikethrough for the given font and size.
+    pub fn strikethrough_thickness(&self, font_id: FontId, font_size: Pixels) -> Pixels {
+        self.read_metrics(font_id, |metrics| {
+            metrics.strikethrough_thickness(font_size)
+        })
+    }
+
     /// Get the recommended distance from the baseline for the given font
     pub fn ascent(&self, font_id: FontId, font_size: Pixels) -> Pixels {
         self.read_metrics(font_id, |metrics| metrics.ascent(font_size))
@@ -328,6 +422,23 @@ impl TextSystem {
     }
 }
 
+/// How some text would be laid out, as measured by [`Window::measure_text`](crate::Window::measure_text).
+/// Only the text itself is measured: inline elements of a styled text aren't accounted for.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextMeasurement {
+    /// The size of the text, as sized by a text element.
+    pub size: Size<Pixels>,
+    /// The width of each visual line of the text, after wrapping.
+    pub line_widths: Vec<Pixels>,
+}
+
+impl TextMeasurement {
+    /// The number of visual lines of the text, after wrapping.
+    pub fn line_count(&self) -> usize {
+        self.line_widths.len()
+    }
+}
+
 /// The GPUI text layout subsystem.
 #[derive(Deref)]
 pub struct WindowTextSystem {
@@ -405,13 +516,17 @@ impl WindowTextSystem {
 
     /// Shape a multi line string of text, at the given font_size, for painting to the screen.
     /// Subsets of the text can be styled independently with the `runs` parameter.
-    /// If `wrap_width` is provided, the line breaks will be adjusted to fit within the given width.
+    /// Each line is laid out in the paragraph `direction`, reordering any right-to-left text it contains.
+    /// If `wrap_width` is provided, the line breaks will be adjusted to fit within the given width,
+    /// at the positions allowed by `line_break`.
     pub fn shape_text(
         &self,
         text: SharedString,
         font_size: Pixels,
         runs: &[TextRun],
+        direction: TextDirection,
         wrap_width: Option<Pixels>,
+        line_break: LineBreakStyle,
         line_clamp: Option<usize>,
     ) -> Result<SmallVec<[WrappedLine; 1]>> {
         let mut runs = runs.iter().filter(|run| run.len > 0).cloned().peekable();
@@ -454,17 +569,14 @@ impl WindowTextSystem {
                 };
 
                 let font_id = self.resolve_font(&run.font);
-                if let Some(font_run) = font_runs.last_mut()
-                    && font_id == font_run.font_id
-                    && !decoration_changed
-                {
-                    font_run.len += run_len_within_line;
-                } else {
-                    font_runs.push(FontRun {
-                        len: run_len_within_line,
-                        font_id,
-                    });
-                }
+                self.push_font_runs(
+                    &line_text,
+                    run_start - line_start..run_start - line_start + run_len_within_line,
+                    &run.font,
+                    font_id,
+                    !decoration_changed,
+                    &mut font_runs,
+                );
 
                 // Preserve the remainder of the run for the next line
                 run.len -= run_len_within_line;
@@ -478,7 +590,9 @@ impl WindowTextSystem {
                 &line_text,
                 font_size,
                 &font_runs,
+                direction,
                 wrap_width,
+                line_break,
                 max_wrap_lines.map(|max| max.saturating_sub(wrapped_lines)),
             );
             wrapped_lines += layout.wrap_boundaries.len();
@@ -554,6 +668,7 @@ impl WindowTextSystem {
         let mut font_runs = self.font_runs_pool.lock().pop().unwrap_or_default();
         font_runs.clear();
 
+        let mut run_start = 0;
         for run in runs.iter() {
             let decoration_changed = if let Some(last_run) = last_run
                 && last_run.color == run.color
@@ -571,22 +686,29 @@ impl WindowTextSystem {
             if let Some(font_run) = font_runs.last_mut()
                 && Some(font_run.font_id) == last_font
                 && !decoration_changed
+                && run.font.fallbacks.is_none()
             {
                 font_run.len += run.len;
             } else {
                 let font_id = self.resolve_font(&run.font);
                 last_font = Some(font_id);
-                font_runs.push(FontRun {
-                    len: run.len,
+                self.push_font_runs(
+                    text,
+                    run_start..run_start + run.len,
+                    &run.font,
                     font_id,
-                });
+                    false,
+                    &mut font_runs,
+                );
             }
+            run_start += run.len;
         }
 
         let layout = self.line_layout_cache.layout_line(
             &SharedString::new(text),
             font_size,
             &font_runs,
+            TextDirection::Auto,
             force_width,
         );
 
@@ -803,6 +925,9 @@ pub struct Font {
     /// The font features to use.
     pub features: FontFeatures,
 
+    /// The values to use for the axes of a variable font.
+    pub variations: FontVariations,
+
     /// The fallbacks fonts to use.
     pub fallbacks: Option<FontFallbacks>,
 
@@ -824,6 +949,7 @@ pub fn font(family: impl Into<SharedString>) -> Font {
     Font {
         family: family.into(),
         features: FontFeatures::default(),
+        variations: FontVariations::default(),
         weight: FontWeight::default(),
         style: FontStyle::default(),
         fallbacks: None,
@@ -867,6 +993,12 @@ pub struct FontMetrics {
     /// The suggested thickness of the underline.
     pub(crate) underline_thickness: f32,
 
+    /// The suggested distance from the baseline up to the top of a strikethrough.
+    pub(crate) strikethrough_position: f32,
+
+    /// The suggested thickness of a strikethrough.
+    pub(crate) strikethrough_thickness: f32,
+
     /// The height of a capital letter measured from the baseline of the font.
     pub(crate) cap_height: f32,
 
@@ -904,6 +1036,16 @@ impl FontMetrics {
         Pixels((self.underline_thickness / self.units_per_em as f32) * font_size.0)
     }
 
+    /// Returns the suggested distance from the baseline up to the top of a strikethrough in pixels.
+    pub fn strikethrough_position(&self, font_size: Pixels) -> Pixels {
+        Pixels((self.strikethrough_position / self.units_per_em as f32) * font_size.0)
+    }
+
+    /// Returns the suggested thickness of a strikethrough in pixels.
+    pub fn strikethrough_thickness(&self, font_size: Pixels) -> Pixels {
+        Pixels((self.strikethrough_thickness / self.units_per_em as f32) * font_size.0)
+    }
+
     /// Returns the height of a capital letter measured from the baseline of the font in pixels.
     pub fn cap_height(&self, font_size: Pixels) -> Pixels {
         Pixels((self.cap_height / self.units_per_em as f32) * font_size.0)
@@ -920,6 +1062,16 @@ impl FontMetrics {
     }
 }
 
+/// Whether a character is invisible and formats the characters around it, like a zero width
+/// joiner or variation selector, so that fonts needn't have a glyph for it.
+fn is_default_ignorable(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '\u{200B}'..='\u{200F}' | '\u{FE00}'..='\u{FE0F}' | '\u{E0000}'..='\u{E0FFF}'
+        )
+}
+
 #[allow(unused)]
 pub(crate) fn font_name_with_fallbacks<'a>(name: &'a str, system: &'a str) -> &'a str {
     // Note: the "Zed Plex" fonts were deprecated as we are not allowed to use "Plex"
@@ -932,3 +1084,104 @@ pub(crate) fn font_name_with_fallbacks<'a>(name: &'a str, system: &'a str) -> &'
         _ => name,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoopTextSystem;
+
+    /// A text system with a primary font that only covers ASCII and zero width joiners, and an
+    /// emoji font that covers everything.
+    struct FallbackTextSystem(NoopTextSystem);
+
+    const PRIMARY_FONT_ID: FontId = FontId(0);
+    const EMOJI_FONT_ID: FontId = FontId(1);
+
+    impl PlatformTextSystem for FallbackTextSystem {
+        fn add_fonts(&self, fonts: Vec<Cow<'static, [u8]>>) -> Result<()> {
+            self.0.add_fonts(fonts)
+        }
+
+        fn all_font_names(&self) -> Vec<String> {
+            self.0.all_font_names()
+        }
+
+        fn font_id(&self, descriptor: &Font) -> Result<FontId> {
+            match descriptor.family.as_ref() {
+                "Primary" => Ok(PRIMARY_FONT_ID),
+                "Emoji" => Ok(EMOJI_FONT_ID),
+                family => Err(anyhow!("no font named {family}")),
+            }
+        }
+
+        fn font_metrics(&self, font_id: FontId) -> FontMetrics {
+            self.0.font_metrics(font_id)
+        }
+
+        fn typographic_bounds(&self, font_id: FontId, glyph_id: GlyphId) -> Result<Bounds<f32>> {
+            self.0.typographic_bounds(font_id, glyph_id)
+        }
+
+        fn advance(&self, font_id: FontId, glyph_id: GlyphId) -> Result<Size<f32>> {
+            self.0.advance(font_id, glyph_id)
+        }
+
+        fn glyph_for_char(&self, font_id: FontId, ch: char) -> Option<GlyphId> {
+            (font_id == EMOJI_FONT_ID || ch.is_ascii() || ch == '\u{200D}')
+                .then(|| GlyphId(ch as u32))
+        }
+
+        fn glyph_raster_bounds(&self, params: &RenderGlyphParams) -> Result<Bounds<DevicePixels>> {
+            self.0.glyph_raster_bounds(params)
+        }
+
+        fn rasterize_glyph(
+            &self,
+            params: &RenderGlyphParams,
+            raster_bounds: Bounds<DevicePixels>,
+        ) -> Result<(Size<DevicePixels>, Vec<u8>)> {
+            self.0.rasterize_glyph(params, raster_bounds)
+        }
+
+        fn layout_line(&self, text: &str, font_size: Pixels, runs: &[FontRun]) -> LineLayout {
+            self.0.layout_line(text, font_size, runs)
+        }
+    }
+
+    #[test]
+    fn test_fallback_font_runs_keep_grapheme_clusters_together() {
+        let text_system = TextSystem::new(Arc::new(FallbackTextSystem(NoopTextSystem)));
+        let font = Font {
+            fallbacks: Some(FontFallbacks::from_fonts(vec![
+                "Missing".into(),
+                "Emoji".into(),
+            ])),
+            ..font("Primary")
+        };
+
+        // The primary font has a glyph for the zero width joiner, but not for the emoji it joins,
+        // so the whole sequence is laid out in the fallback.
+        let technologist = "\u{1F469}\u{200D}\u{1F4BB}";
+        let text = format!("a{technologist}\u{FE0F}b");
+        let mut font_runs = Vec::new();
+        text_system.push_font_runs(
+            &text,
+            0..text.len(),
+            &font,
+            PRIMARY_FONT_ID,
+            false,
+            &mut font_runs,
+        );
+        assert_eq!(
+            font_runs
+                .iter()
+                .map(|run| (run.font_id, run.len))
+                .collect::<Vec<_>>(),
+            [
+                (PRIMARY_FONT_ID, 1),
+                (EMOJI_FONT_ID, technologist.len() + '\u{FE0F}'.len_utf8()),
+                (PRIMARY_FONT_ID, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use crate::{
+    FontRun, LineLayout, Pixels, PlatformTextSystem, ShapedGlyph, ShapedRun, TextDirection,
+    WrapBoundary, point, px,
+};
+use smallvec::SmallVec;
+use std::ops::Range;
+use unicode_bidi::{BidiClass, BidiInfo, Level, bidi_class};
+
+/// A range of a line whose characters all have the same embedding level, as resolved by the
+/// Unicode Bidirectional Algorithm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BidiRun {
+    /// The byte range of this run within its line.
+    pub range: Range<usize>,
+
+    /// The embedding level of this run. Runs at odd levels are laid out right-to-left.
+    pub level: u8,
+}
+
+impl BidiRun {
+    /// Whether the characters of this run are laid out right-to-left.
+    pub fn is_rtl(&self) -> bool {
+        self.level % 2 == 1
+    }
+}
+
+/// A cluster of glyphs within a visual line, at both its logical and visual position.
+#[derive(Clone, Debug)]
+pub(crate) struct VisualCluster {
+    /// The index of the run containing the cluster's glyphs.
+    pub run_ix: usize,
+    /// The range of the cluster's glyphs within their run.
+    pub glyph_range: Range<usize>,
+    /// The byte index of the cluster's first character.
+    pub index: usize,
+    /// The byte index just after the cluster's last character.
+    pub end_index: usize,
+    /// The position of the cluster in the unwrapped line, in logical order.
+    pub logical_x: Pixels,
+    /// The position of the cluster from the start of its visual line, in visual order.
+    pub visual_x: Pixels,
+    /// The width of the cluster.
+    pub width: Pixels,
+    /// The embedding level of the cluster's characters.
+    pub level: u8,
+    /// Whether the cluster is laid out right-to-left.
+    pub is_rtl: bool,
+}
+
+/// A wrapped line of a [`LineLayout`], with its clusters in visual order.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct VisualLine {
+    /// The position of the start of this line in the unwrapped line.
+    pub logical_start_x: Pixels,
+    /// The width of this line.
+    pub width: Pixels,
+    /// The clusters of this line, from left to right.
+    pub clusters: Vec<VisualCluster>,
+}
+
+impl VisualLine {
+    /// The caret position, from the start of this line, of the character boundary at the given index.
+    pub fn x_for_index(&self, index: usize) -> Pixels {
+        if let Some(cluster) = self
+            .clusters
+            .iter()
+            .find(|cluster| cluster.index <= index && index < cluster.end_index)
+        {
+            return if cluster.is_rtl {
+                cluster.visual_x + cluster.width
+            } else {
+                cluster.visual_x
+            };
+        }
+
+        // The end of the line comes after its logically last cluster.
+        match self.clusters.iter().max_by_key(|cluster| cluster.index) {
+            Some(cluster) if cluster.is_rtl => cluster.visual_x,
+            Some(cluster) => cluster.visual_x + cluster.width,
+            None => px(0.),
+        }
+    }
+
+    /// The character boundaries at the left and right edges of the given cluster.
+    fn boundaries(cluster: &VisualCluster) -> (usize, usize) {
+        if cluster.is_rtl {
+            (cluster.end_index, cluster.index)
+        } else {
+            (cluster.index, cluster.end_index)
+        }
+    }
+
+    /// The index of the character at the given position from the start of this line, or the
+    /// closest character boundary if the position is outside the line.
+    pub fn index_for_x(&self, x: Pixels, closest: bool) -> Result<usize, usize> {
+        let (Some(first), Some(last)) = (self.clusters.first(), self.clusters.last()) else {
+            return Err(0);
+        };
+        if x < px(0.) {
+            return Err(Self::boundaries(first).0);
+        }
+        if x >= self.width {
+            return Err(Self::boundaries(last).1);
+        }
+
+        let cluster = self
+            .clusters
+            .iter()
+            .find(|cluster| x < cluster.visual_x + cluster.width)
+            .unwrap_or(last);
+        if closest {
+            let (left, right) = Self::boundaries(cluster);
+            if x < cluster.visual_x + cluster.width / 2. {
+                Ok(left)
+            } else {
+                Ok(right)
+            }
+        } else {
+            Ok(cluster.index)
+        }
+    }
+
+    /// The horizontal extents, from the start of this line, of the characters in the given range.
+    /// A logically contiguous range can be split into several visual spans, which are returned
+    /// from left to right.
+    pub fn spans_for_range(&self, range: Range<usize>) -> SmallVec<[Range<Pixels>; 1]> {
+        let mut spans = SmallVec::<[Range<Pixels>; 1]>::new();
+        let mut previous_in_range = false;
+        for cluster in &self.clusters {
+            let in_range = range.start <= cluster.index && cluster.index < range.end;
+            if in_range {
+                let end_x = cluster.visual_x + cluster.width;
+                match spans.last_mut() {
+                    Some(span) if previous_in_range => span.end = end_x,
+                    _ => spans.push(cluster.visual_x..end_x),
+                }
+            }
+            previous_in_range = in_range;
+        }
+        spans
+    }
+}
+
+/// Resolve whether a line's paragraph direction is right-to-left, and the level runs within
+/// it. No runs are returned when every character in the line is laid out left-to-right.
+pub(crate) fn resolve_bidi_runs(text: &str, direction: TextDirection) -> (bool, Vec<BidiRun>) {
+    let may_contain_rtl = text.chars().any(|character| {
+        matches!(
+            bidi_class(character),
+            BidiClass::R | BidiClass::AL | BidiClass::RLE | BidiClass::RLO | BidiClass::RLI
+        )
+    });
+    if direction != TextDirection::Rtl && !may_contain_rtl {
+        return (false, Vec::new());
+    }
+
+    let paragraph_level = match direction {
+        TextDirection::Auto => None,
+        TextDirection::Ltr => Some(Level::ltr()),
+        TextDirection::Rtl => Some(Level::rtl()),
+    };
+    let bidi_info = BidiInfo::new(text, paragraph_level);
+    let is_rtl = bidi_info
+        .paragraphs
+        .first()
+        .is_some_and(|paragraph| paragraph.level.is_rtl());
+    if bidi_info.levels.iter().all(|level| level.is_ltr()) {
+        return (is_rtl, Vec::new());
+    }
+
+    let mut runs = Vec::<BidiRun>::new();
+    for (index, level) in bidi_info.levels.iter().enumerate() {
+        let level = level.number();
+        match runs.last_mut() {
+            Some(run) if run.level == level => run.range.end = index + 1,
+            _ => runs.push(BidiRun {
+                range: index..index + 1,
+                level,
+            }),
+        }
+    }
+    (is_rtl, runs)
+}
+
+/// Lay out a line containing right-to-left text by shaping each of its level runs on its own and
+/// placing the results one after another in logical order, so that the line can be wrapped like any
+/// other. The glyphs of each cluster keep the order they were shaped in. The visual order of the
+/// clusters is resolved for each wrapped line when it is painted or hit tested.
+pub(crate) fn layout_bidi_line(
+    platform_text_system: &dyn PlatformTextSystem,
+    text: &str,
+    font_size: Pixels,
+    font_runs: &[FontRun],
+    rtl: bool,
+    bidi_runs: Vec<BidiRun>,
+) -> LineLayout {
+    let mut layout = LineLayout {
+        font_size,
+        len: text.len(),
+        rtl,
+        ..Default::default()
+    };
+
+    let mut x = px(0.);
+    for bidi_run in &bidi_runs {
+        let font_runs = font_runs_in_range(font_runs, &bidi_run.range);
+        let run_layout =
+            platform_text_system.layout_line(&text[bidi_run.range.clone()], font_size, &font_runs);
+        layout.ascent = layout.ascent.max(run_layout.ascent);
+        layout.descent = layout.descent.max(run_layout.descent);
+
+        let glyphs = run_layout
+            .runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter().map(move |glyph| (run.font_id, glyph)))
+            .collect::<Vec<_>>();
+        let mut clusters = Vec::<Range<usize>>::new();
+        for (glyph_ix, (_, glyph)) in glyphs.iter().enumerate() {
+            match clusters.last_mut() {
+                Some(cluster) if glyphs[cluster.start].1.index == glyph.index => {
+                    cluster.end = glyph_ix + 1
+                }
+                _ => clusters.push(glyph_ix..glyph_ix + 1),
+            }
+        }
+        let cluster_x = |cluster_ix: usize| {
+            clusters
+                .get(cluster_ix)
+                .map_or(run_layout.width, |cluster: &Range<usize>| {
+                    glyphs[cluster.start].1.position.x
+                })
+        };
+        let mut cluster_ixs = (0..clusters.len()).collect::<Vec<_>>();
+        cluster_ixs.sort_by_key(|cluster_ix| glyphs[clusters[*cluster_ix].start].1.index);
+
+        for cluster_ix in cluster_ixs {
+            let cluster_start_x = cluster_x(cluster_ix);
+            let cluster_width = cluster_x(cluster_ix + 1) - cluster_start_x;
+            for (font_id, glyph) in &glyphs[clusters[cluster_ix].clone()] {
+                let glyph = ShapedGlyph {
+                    position: point(x + glyph.position.x - cluster_start_x, glyph.position.y),
+                    index: bidi_run.range.start + glyph.index,
+                    ..(*glyph).clone()
+                };
+                match layout.runs.last_mut() {
+                    Some(run) if run.font_id == *font_id => run.glyphs.push(glyph),
+                    _ => layout.runs.push(ShapedRun {
+                        font_id: *font_id,
+                        glyphs: vec![glyph],
+                    }),
+                }
+            }
+            x += cluster_width;
+        }
+    }
+
+    layout.width = x;
+    layout.bidi_runs = bidi_runs;
+    layout
+}
+
+fn font_runs_in_range(font_runs: &[FontRun], range: &Range<usize>) -> SmallVec<[FontRun; 4]> {
+    let mut runs_in_range = SmallVec::new();
+    let mut run_start = 0;
+    for run in font_runs {
+        let run_end = run_start + run.len;
+        let start = run_start.max(range.start);
+        let end = run_end.min(range.end);
+        if start < end {
+            runs_in_range.push(FontRun {
+                len: end - start,
+                font_id: run.font_id,
+            });
+        }
+        run_start = run_end;
+    }
+    runs_in_range
+}
+
+impl LineLayout {
+    /// Whether any of this line's characters are laid out right-to-left, in which case its visual
+    /// order differs from its logical order.
+    pub fn is_bidi(&self) -> bool {
+        !self.bidi_runs.is_empty()
+    }
+
+    /// Split this line at the given wrap boundaries and order the clusters of each resulting line
+    /// visually, reversing runs of higher embedding levels as in rule L2 of the Unicode
+    /// Bidirectional Algorithm.
+    pub(crate) fn visual_lines(&self, wrap_boundaries: &[WrapBoundary]) -> Vec<VisualLine> {
+        let mut lines = Vec::new();
+        let mut line = VisualLine::default();
+        let mut wrap_boundaries = wrap_boundaries.iter().peekable();
+        for (run_ix, run) in self.runs.iter().enumerate() {
+            for (glyph_ix, glyph) in run.glyphs.iter().enumerate() {
+                let starts_line =
+                    wrap_boundaries.peek() == Some(&&WrapBoundary { run_ix, glyph_ix });
+                if let Some(cluster) = line.clusters.last_mut() {
+                    if !starts_line && cluster.run_ix == run_ix && cluster.index == glyph.index {
+                        cluster.glyph_range.end = glyph_ix + 1;
+                        continue;
+                    }
+                    cluster.end_index = glyph.index.max(cluster.index);
+                    cluster.width = glyph.position.x - cluster.logical_x;
+                }
+                if starts_line {
+                    wrap_boundaries.next();
+                    line.width = glyph.position.x - line.logical_start_x;
+                    lines.push(std::mem::replace(
+                        &mut line,
+                        VisualLine {
+                            logical_start_x: glyph.position.x,
+                            ..Default::default()
+                        },
+                    ));
+                }
+
+                let level = self
+                    .bidi_runs
+                    .iter()
+                    .find(|bidi_run| bidi_run.range.contains(&glyph.index))
+                    .map_or(0, |bidi_run| bidi_run.level);
+                line.clusters.push(VisualCluster {
+                    run_ix,
+                    glyph_range: glyph_ix..glyph_ix + 1,
+                    index: glyph.index,
+                    end_index: self.len,
+                    logical_x: glyph.position.x,
+                    visual_x: px(0.),
+                    width: px(0.),
+                    level,
+                    is_rtl: level % 2 == 1,
+                });
+            }
+        }
+        if let Some(cluster) = line.clusters.last_mut() {
+            cluster.width = self.width - cluster.logical_x;
+        }
+        line.width = self.width - line.logical_start_x;
+        lines.push(line);
+
+        for line in &mut lines {
+            let mut order = (0..line.clusters.len()).collect::<Vec<_>>();
+            let levels = line
+                .clusters
+                .iter()
+                .map(|cluster| cluster.level)
+                .collect::<Vec<_>>();
+            let highest_level = levels.iter().copied().max().unwrap_or(0);
+            let lowest_odd_level = levels.iter().copied().min().unwrap_or(0) | 1;
+            for level in (lowest_odd_level..=highest_level).rev() {
+                let mut start = 0;
+                while start < order.len() {
+                    if levels[order[start]] < level {
+                        start += 1;
+                        continue;
+                    }
+                    let mut end = start;
+                    while end < order.len() && levels[order[end]] >= level {
+                        end += 1;
+                    }
+                    order[start..end].reverse();
+                    start = end;
+                }
+            }
+
+            let mut visual_x = px(0.);
+            let mut clusters = order
+                .into_iter()
+                .map(|cluster_ix| line.clusters[cluster_ix].clone())
+                .collect::<Vec<_>>();
+            for cluster in &mut clusters {
+                cluster.visual_x = visual_x;
+                visual_x += cluster.width;
+            }
+            line.clusters = clusters;
+        }
+
+        lines
+    }
+
+    /// The position of each glyph in this line once its wrapped lines are ordered visually. Like
+    /// [`ShapedGlyph::position`], positions are relative to the start of the unwrapped line.
+    pub(crate) fn visual_glyph_positions(
+        &self,
+        wrap_boundaries: &[WrapBoundary],
+    ) -> Vec<Vec<Pixels>> {
+        let mut positions = self
+            .runs
+            .iter()
+            .map(|run| run.glyphs.iter().map(|glyph| glyph.position.x).collect())
+            .collect::<Vec<Vec<_>>>();
+        for line in self.visual_lines(wrap_boundaries) {
+            for cluster in &line.clusters {
+                let offset = line.logical_start_x + cluster.visual_x - cluster.logical_x;
+                for glyph_ix in cluster.glyph_range.clone() {
+                    positions[cluster.run_ix][glyph_ix] += offset;
+                }
+            }
+        }
+        positions
+    }
+}
@@ -0,0 +1,106 @@
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// The values to use for the axes of a variable font, such as `wght` or `wdth`.
+#[derive(Default, Clone)]
+pub struct FontVariations(pub Arc<Vec<(String, f32)>>);
+
+impl FontVariations {
+    /// Creates font variations from a list of OpenType axis tags and their values.
+    pub fn new(axes: &[(&str, f32)]) -> Self {
+        Self(Arc::new(
+            axes.iter()
+                .map(|(tag, value)| ((*tag).to_string(), *value))
+                .collect(),
+        ))
+    }
+
+    /// Get the axis tag and value list of the font variations
+    pub fn axis_value_list(&self) -> &[(String, f32)] {
+        self.0.as_slice()
+    }
+
+    /// Returns whether no axis values are set, in which case the font's defaults are used.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the value set for the given axis tag. When an axis is set more than once, the last
+    /// value wins.
+    pub fn value_for(&self, tag: &str) -> Option<f32> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(axis_tag, _)| axis_tag == tag)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl PartialEq for FontVariations {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(other.0.iter())
+                .all(|((tag, value), (other_tag, other_value))| {
+                    tag == other_tag && value.to_bits() == other_value.to_bits()
+                })
+    }
+}
+
+impl Eq for FontVariations {}
+
+impl Hash for FontVariations {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.len().hash(state);
+        for (tag, value) in self.0.iter() {
+            tag.hash(state);
+            value.to_bits().hash(state);
+        }
+    }
+}
+
+impl std::fmt::Debug for FontVariations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("FontVariations");
+        for (tag, value) in self.axis_value_list() {
+            debug.field(tag, value);
+        }
+
+        debug.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collections::HashMap;
+
+    #[test]
+    fn test_font_variations_keying() {
+        let bold = FontVariations::new(&[("wght", 700.), ("wdth", 90.)]);
+        let mut font_ids = HashMap::default();
+        font_ids.insert(bold.clone(), 0);
+        font_ids.insert(FontVariations::new(&[("wght", 400.), ("wdth", 90.)]), 1);
+        font_ids.insert(FontVariations::new(&[("wdth", 90.), ("wght", 700.)]), 2);
+        font_ids.insert(FontVariations::default(), 3);
+
+        assert_eq!(font_ids.len(), 4);
+        assert_eq!(
+            font_ids.get(&FontVariations::new(&[("wght", 700.), ("wdth", 90.)])),
+            Some(&0)
+        );
+        assert_eq!(font_ids.get(&FontVariations::new(&[])), Some(&3));
+    }
+
+    #[test]
+    fn test_font_variations_value_for() {
+        let variations = FontVariations::new(&[("wght", 400.), ("slnt", -10.), ("wght", 650.)]);
+        assert_eq!(variations.value_for("wght"), Some(650.));
+        assert_eq!(variations.value_for("slnt"), Some(-10.));
+        assert_eq!(variations.value_for("wdth"), None);
+        assert_eq!(FontVariations::default().value_for("wght"), None);
+        assert!(FontVariations::default().is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
-    App, Bounds, Half, Hsla, LineLayout, Pixels, Point, Result, SharedString, StrikethroughStyle,
-    TextAlign, UnderlineStyle, Window, WrapBoundary, WrappedLineLayout, black, fill, point, px,
-    size,
+    App, Bounds, FontId, Half, Hsla, Hyphen, LineLayout, Pixels, Point, Result, SharedString,
+    StrikethroughStyle, TextAlign, TextSystem, UnderlineStyle, Window, WrapBoundary,
+    WrappedLineLayout, black, fill, point, px, size,
 };
 use derive_more::{Deref, DerefMut};
 use smallvec::SmallVec;
@@ -55,6 +55,8 @@ impl ShapedLine {
             descent: layout.descent,
             runs: layout.runs.clone(),
             len,
+            rtl: layout.rtl,
+            bidi_runs: layout.bidi_runs.clone(),
         });
         self
     }
@@ -75,6 +77,7 @@ impl ShapedLine {
             None,
             &self.decoration_runs,
             &[],
+            None,
             window,
             cx,
         )?;
@@ -98,6 +101,7 @@ impl ShapedLine {
             None,
             &self.decoration_runs,
             &[],
+            None,
             window,
             cx,
         )?;
@@ -147,6 +151,7 @@ impl WrappedLine {
             align_width,
             &self.decoration_runs,
             &self.wrap_boundaries,
+            self.layout.hyphen.as_ref(),
             window,
             cx,
         )?;
@@ -177,12 +182,43 @@ impl WrappedLine {
             align_width,
             &self.decoration_runs,
             &self.wrap_boundaries,
+            self.layout.hyphen.as_ref(),
             window,
             cx,
         )?;
 
         Ok(())
     }
+
+    /// The horizontal offset from the line's origin of each of its wrapped rows, where
+    /// [`paint`](Self::paint) places them for the given alignment within the given width.
+    pub(crate) fn aligned_row_offsets(
+        &self,
+        align: TextAlign,
+        align_width: Pixels,
+    ) -> SmallVec<[Pixels; 1]> {
+        let layout = &self.layout.unwrapped_layout;
+        let hyphen = self.layout.hyphen.as_ref();
+        let mut row_start_x = px(0.);
+        let mut offsets = SmallVec::new();
+        for row_end in self.wrap_boundaries.iter().map(Some).chain([None]) {
+            let row_end_x = row_end.map_or(layout.width, |boundary| {
+                layout.runs[boundary.run_ix].glyphs[boundary.glyph_ix]
+                    .position
+                    .x
+            });
+            let row_width = row_end_x - row_start_x + Hyphen::width_at(hyphen, row_end);
+            offsets.push(aligned_line_x(
+                Point::default(),
+                align_width,
+                row_width,
+                &align,
+                layout.rtl,
+            ));
+            row_start_x = row_end_x;
+        }
+        offsets
+    }
 }
 
 fn paint_line(
@@ -193,9 +229,25 @@ fn paint_line(
     align_width: Option<Pixels>,
     decoration_runs: &[DecorationRun],
     wrap_boundaries: &[WrapBoundary],
+    hyphen: Option<&Hyphen>,
     window: &mut Window,
     cx: &mut App,
 ) -> Result<()> {
+    if layout.is_bidi() {
+        return paint_bidi_line(
+            origin,
+            layout,
+            line_height,
+            align,
+            align_width,
+            decoration_runs,
+            wrap_boundaries,
+            hyphen,
+            window,
+            cx,
+        );
+    }
+
     let line_bounds = Bounds::new(
         origin,
         size(
@@ -221,6 +273,7 @@ fn paint_line(
                 &align,
                 layout,
                 wraps.peek(),
+                Hyphen::width_at(hyphen, wraps.peek().copied()),
             ),
             origin.y,
         );
@@ -237,7 +290,15 @@ fn paint_line(
                 }
 
                 if wraps.peek() == Some(&&WrapBoundary { run_ix, glyph_ix }) {
-                    wraps.next();
+                    if let Some(hyphen) = Hyphen::at(hyphen, wraps.next()) {
+                        window.paint_glyph(
+                            glyph_origin + baseline_offset,
+                            hyphen.font_id,
+                            hyphen.glyph_id,
+                            layout.font_size,
+                            color,
+                        )?;
+                    }
                     if let Some((underline_origin, underline_style)) = current_underline.as_mut() {
                         if glyph_origin.x == underline_origin.x {
                             underline_origin.x -= max_glyph_size.width.half();
@@ -272,6 +333,7 @@ fn paint_line(
                         &align,
                         layout,
                         wraps.peek(),
+                        Hyphen::width_at(hyphen, wraps.peek().copied()),
                     );
                     glyph_origin.y += line_height;
                 }
@@ -297,17 +359,13 @@ fn paint_line(
                         {
                             finished_underline = current_underline.take();
                         }
-                        if let Some(run_underline) = style_run.underline.as_ref() {
+                        if let Some(underline) = underline_style(style_run) {
                             current_underline.get_or_insert((
                                 point(
                                     glyph_origin.x,
                                     glyph_origin.y + baseline_offset.y + (layout.descent * 0.618),
                                 ),
-                                UnderlineStyle {
-                                    color: Some(run_underline.color.unwrap_or(style_run.color)),
-                                    thickness: run_underline.thickness,
-                                    wavy: run_underline.wavy,
-                                },
+                                underline,
                             ));
                         }
                         if let Some((_, strikethrough_style)) = &mut current_strikethrough
@@ -315,17 +373,17 @@ fn paint_line(
                         {
                             finished_strikethrough = current_strikethrough.take();
                         }
-                        if let Some(run_strikethrough) = style_run.strikethrough.as_ref() {
+                        if let Some(strikethrough) =
+                            strikethrough_style(style_run, run.font_id, layout, &text_system)
+                        {
                             current_strikethrough.get_or_insert((
                                 point(
                                     glyph_origin.x,
-                                    glyph_origin.y
-                                        + (((layout.ascent * 0.5) + baseline_offset.y) * 0.5),
+                                    glyph_origin.y + baseline_offset.y
+                                        - text_system
+                                            .strikethrough_position(run.font_id, layout.font_size),
                                 ),
-                                StrikethroughStyle {
-                                    color: Some(run_strikethrough.color.unwrap_or(style_run.color)),
-                                    thickness: run_strikethrough.thickness,
-                                },
+                                strikethrough,
                             ));
                         }
 
@@ -430,9 +488,25 @@ fn paint_line_background(
     align_width: Option<Pixels>,
     decoration_runs: &[DecorationRun],
     wrap_boundaries: &[WrapBoundary],
+    hyphen: Option<&Hyphen>,
     window: &mut Window,
     cx: &mut App,
 ) -> Result<()> {
+    if layout.is_bidi() {
+        return paint_bidi_line_background(
+            origin,
+            layout,
+            line_height,
+            align,
+            align_width,
+            decoration_runs,
+            wrap_boundaries,
+            hyphen,
+            window,
+            cx,
+        );
+    }
+
     let line_bounds = Bounds::new(
         origin,
         size(
@@ -454,6 +528,7 @@ fn paint_line_background(
                 &align,
                 layout,
                 wraps.peek(),
+                Hyphen::width_at(hyphen, wraps.peek().copied()),
             ),
             origin.y,
         );
@@ -490,6 +565,7 @@ fn paint_line_background(
                         &align,
                         layout,
                         wraps.peek(),
+                        Hyphen::width_at(hyphen, wraps.peek().copied()),
                     );
                     glyph_origin.y += line_height;
                 }
@@ -574,6 +650,7 @@ fn aligned_origin_x(
     align: &TextAlign,
     layout: &LineLayout,
     wrap_boundary: Option<&&WrapBoundary>,
+    hyphen_width: Pixels,
 ) -> Pixels {
     let end_of_line = if let Some(WrapBoundary { run_ix, glyph_ix }) = wrap_boundary {
         layout.runs[*run_ix].glyphs[*glyph_ix].position.x
@@ -581,11 +658,290 @@ fn aligned_origin_x(
         layout.width
     };
 
-    let line_width = end_of_line - last_glyph_x;
+    let line_width = end_of_line - last_glyph_x + hyphen_width;
+    aligned_line_x(origin, align_width, line_width, align, layout.rtl)
+}
 
-    match align {
-        TextAlign::Left => origin.x,
-        TextAlign::Center => (origin.x * 2.0 + align_width - line_width) / 2.0,
-        TextAlign::Right => origin.x + align_width - line_width,
+fn aligned_line_x(
+    origin: Point<Pixels>,
+    align_width: Pixels,
+    line_width: Pixels,
+    align: &TextAlign,
+    rtl: bool,
+) -> Pixels {
+    match (align, rtl) {
+        (TextAlign::Left, _) | (TextAlign::Start, false) | (TextAlign::End, true) => origin.x,
+        (TextAlign::Center, _) => (origin.x * 2.0 + align_width - line_width) / 2.0,
+        (TextAlign::Right, _) | (TextAlign::Start, true) | (TextAlign::End, false) => {
+            origin.x + align_width - line_width
+        }
     }
 }
+
+/// The decoration run containing the character at the given index.
+fn decoration_run_at(decoration_runs: &[DecorationRun], index: usize) -> Option<&DecorationRun> {
+    let mut run_end = 0;
+    decoration_runs.iter().find(|run| {
+        run_end += run.len as usize;
+        index < run_end
+    })
+}
+
+/// The underline to paint under a decoration run, if it's underlined.
+fn underline_style(style_run: &DecorationRun) -> Option<UnderlineStyle> {
+    let run_underline = style_run.underline.as_ref()?;
+    Some(UnderlineStyle {
+        color: Some(run_underline.color.unwrap_or(style_run.color)),
+        thickness: run_underline.thickness,
+        wavy: run_underline.wavy,
+        style: run_underline.style,
+    })
+}
+
+/// The strikethrough to paint through a decoration run in the given font, if it's struck through.
+/// Strikethroughs without a thickness take the font's suggested one.
+fn strikethrough_style(
+    style_run: &DecorationRun,
+    font_id: FontId,
+    layout: &LineLayout,
+    text_system: &TextSystem,
+) -> Option<StrikethroughStyle> {
+    let run_strikethrough = style_run.strikethrough.as_ref()?;
+    let thickness = if run_strikethrough.thickness > px(0.) {
+        run_strikethrough.thickness
+    } else {
+        text_system.strikethrough_thickness(font_id, layout.font_size)
+    };
+    Some(StrikethroughStyle {
+        color: Some(run_strikethrough.color.unwrap_or(style_run.color)),
+        thickness,
+    })
+}
+
+/// A decoration painted across visually contiguous clusters of a line that share its style.
+struct DecorationSpan<T> {
+    origin: Point<Pixels>,
+    end_x: Pixels,
+    style: T,
+}
+
+impl<T: PartialEq> DecorationSpan<T> {
+    /// Extend the current span over a cluster starting at the given origin if the cluster has the
+    /// same style and follows on from it, or otherwise start a new span for the cluster. Returns the
+    /// span that was finished, if any.
+    fn advance(
+        current: &mut Option<Self>,
+        style: Option<T>,
+        origin: Point<Pixels>,
+        width: Pixels,
+    ) -> Option<Self> {
+        if let (Some(span), Some(style)) = (current.as_mut(), style.as_ref())
+            && span.style == *style
+            && span.end_x == origin.x
+        {
+            span.end_x += width;
+            return None;
+        }
+        std::mem::replace(
+            current,
+            style.map(|style| DecorationSpan {
+                origin,
+                end_x: origin.x + width,
+                style,
+            }),
+        )
+    }
+
+    fn width(&self) -> Pixels {
+        self.end_x - self.origin.x
+    }
+}
+
+/// Paint a line containing right-to-left text, one visual line at a time. Decorations are painted
+/// for each visually contiguous span of clusters that share them.
+fn paint_bidi_line(
+    origin: Point<Pixels>,
+    layout: &LineLayout,
+    line_height: Pixels,
+    align: TextAlign,
+    align_width: Option<Pixels>,
+    decoration_runs: &[DecorationRun],
+    wrap_boundaries: &[WrapBoundary],
+    hyphen: Option<&Hyphen>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Result<()> {
+    let line_bounds = Bounds::new(
+        origin,
+        size(
+            layout.width,
+            line_height * (wrap_boundaries.len() as f32 + 1.),
+        ),
+    );
+    window.paint_layer(line_bounds, |window| {
+        let padding_top = (line_height - layout.ascent - layout.descent) / 2.;
+        let baseline_offset = point(px(0.), padding_top + layout.ascent);
+        let text_system = cx.text_system().clone();
+        for (line_ix, line) in layout.visual_lines(wrap_boundaries).iter().enumerate() {
+            let line_hyphen = Hyphen::at(hyphen, wrap_boundaries.get(line_ix));
+            let line_origin = point(
+                aligned_line_x(
+                    origin,
+                    align_width.unwrap_or(layout.width),
+                    line.width + line_hyphen.map_or(px(0.), |hyphen| hyphen.width),
+                    &align,
+                    layout.rtl,
+                ),
+                origin.y + line_height * line_ix as f32,
+            );
+            let mut current_underline = None;
+            let mut current_strikethrough = None;
+            for cluster in &line.clusters {
+                let run = &layout.runs[cluster.run_ix];
+                let cluster_x = line_origin.x + cluster.visual_x;
+                let style_run = decoration_run_at(decoration_runs, cluster.index);
+                let color = style_run.map_or(black(), |style_run| style_run.color);
+
+                if let Some(span) = DecorationSpan::advance(
+                    &mut current_underline,
+                    style_run.and_then(underline_style),
+                    point(
+                        cluster_x,
+                        line_origin.y + baseline_offset.y + (layout.descent * 0.618),
+                    ),
+                    cluster.width,
+                ) {
+                    window.paint_underline(span.origin, span.width(), &span.style);
+                }
+                if let Some(span) = DecorationSpan::advance(
+                    &mut current_strikethrough,
+                    style_run.and_then(|style_run| {
+                        strikethrough_style(style_run, run.font_id, layout, &text_system)
+                    }),
+                    point(
+                        cluster_x,
+                        line_origin.y + baseline_offset.y
+                            - text_system.strikethrough_position(run.font_id, layout.font_size),
+                    ),
+                    cluster.width,
+                ) {
+                    window.paint_strikethrough(span.origin, span.width(), &span.style);
+                }
+
+                let max_glyph_size = text_system.bounding_box(run.font_id, layout.font_size).size;
+                for glyph in &run.glyphs[cluster.glyph_range.clone()] {
+                    let glyph_origin = point(
+                        cluster_x + glyph.position.x - cluster.logical_x,
+                        line_origin.y,
+                    );
+                    let max_glyph_bounds = Bounds {
+                        origin: glyph_origin,
+                        size: max_glyph_size,
+                    };
+                    if !max_glyph_bounds.intersects(&window.content_mask().bounds) {
+                        continue;
+                    }
+                    if glyph.is_emoji {
+                        window.paint_emoji(
+                            glyph_origin + baseline_offset,
+                            run.font_id,
+                            glyph.id,
+                            layout.font_size,
+                        )?;
+                    } else {
+                        window.paint_glyph(
+                            glyph_origin + baseline_offset,
+                            run.font_id,
+                            glyph.id,
+                            layout.font_size,
+                            color,
+                        )?;
+                    }
+                }
+            }
+
+            if let Some(hyphen) = line_hyphen {
+                let color = line
+                    .clusters
+                    .last()
+                    .and_then(|cluster| decoration_run_at(decoration_runs, cluster.index))
+                    .map_or(black(), |style_run| style_run.color);
+                window.paint_glyph(
+                    line_origin + point(line.width, px(0.)) + baseline_offset,
+                    hyphen.font_id,
+                    hyphen.glyph_id,
+                    layout.font_size,
+                    color,
+                )?;
+            }
+            if let Some(span) = current_underline {
+                window.paint_underline(span.origin, span.width(), &span.style);
+            }
+            if let Some(span) = current_strikethrough {
+                window.paint_strikethrough(span.origin, span.width(), &span.style);
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// Paint the backgrounds of a line containing right-to-left text, one visual line at a time.
+fn paint_bidi_line_background(
+    origin: Point<Pixels>,
+    layout: &LineLayout,
+    line_height: Pixels,
+    align: TextAlign,
+    align_width: Option<Pixels>,
+    decoration_runs: &[DecorationRun],
+    wrap_boundaries: &[WrapBoundary],
+    hyphen: Option<&Hyphen>,
+    window: &mut Window,
+    _cx: &mut App,
+) -> Result<()> {
+    let line_bounds = Bounds::new(
+        origin,
+        size(
+            layout.width,
+            line_height * (wrap_boundaries.len() as f32 + 1.),
+        ),
+    );
+    window.paint_layer(line_bounds, |window| {
+        for (line_ix, line) in layout.visual_lines(wrap_boundaries).iter().enumerate() {
+            let line_hyphen = Hyphen::at(hyphen, wrap_boundaries.get(line_ix));
+            let line_origin = point(
+                aligned_line_x(
+                    origin,
+                    align_width.unwrap_or(layout.width),
+                    line.width + line_hyphen.map_or(px(0.), |hyphen| hyphen.width),
+                    &align,
+                    layout.rtl,
+                ),
+                origin.y + line_height * line_ix as f32,
+            );
+            let mut current_background = None;
+            for cluster in &line.clusters {
+                if let Some(span) = DecorationSpan::advance(
+                    &mut current_background,
+                    decoration_run_at(decoration_runs, cluster.index)
+                        .and_then(|style_run| style_run.background_color),
+                    point(line_origin.x + cluster.visual_x, line_origin.y),
+                    cluster.width,
+                ) {
+                    window.paint_quad(fill(
+                        Bounds::new(span.origin, size(span.width(), line_height)),
+                        span.style,
+                    ));
+                }
+            }
+            if let Some(span) = current_background {
+                window.paint_quad(fill(
+                    Bounds::new(span.origin, size(span.width(), line_height)),
+                    span.style,
+                ));
+            }
+        }
+
+        Ok(())
+    })
+}
@@ -1,4 +1,7 @@
-use crate::{FontId, GlyphId, Pixels, PlatformTextSystem, Point, SharedString, Size, point, px};
+use crate::{
+    BidiRun, FontId, GlyphId, LineBreakStyle, Pixels, PlatformTextSystem, Point, SharedString,
+    Size, TextDirection, WordBreak, layout_bidi_line, point, px, resolve_bidi_runs,
+};
 use collections::FxHashMap;
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
 use smallvec::SmallVec;
@@ -26,6 +29,10 @@ pub struct LineLayout {
     pub runs: Vec<ShapedRun>,
     /// The length of the line in utf-8 bytes
     pub len: usize,
+    /// Whether the paragraph direction of the line is right-to-left
+    pub rtl: bool,
+    /// The level runs of the line, if any of its characters are laid out right-to-left
+    pub bidi_runs: Vec<BidiRun>,
 }
 
 /// A run of text that has been shaped .
@@ -125,16 +132,26 @@ impl LineLayout {
         None
     }
 
-    fn compute_wrap_boundaries(
+    /// Compute where this line wraps to fit the given width. When hyphenation is enabled,
+    /// `hyphen_width` is reserved at the end of every line whose last word continues on the next
+    /// one.
+    pub(crate) fn compute_wrap_boundaries(
         &self,
         text: &str,
         wrap_width: Pixels,
         max_lines: Option<usize>,
+        line_break: LineBreakStyle,
+        hyphen_width: Pixels,
     ) -> SmallVec<[WrapBoundary; 1]> {
+        let hyphen_width = if line_break.hyphenate {
+            hyphen_width
+        } else {
+            px(0.)
+        };
         let mut boundaries = SmallVec::new();
         let mut first_non_whitespace_ix = None;
-        let mut last_candidate_ix = None;
-        let mut last_candidate_x = px(0.);
+        let mut last_candidate: Option<(WrapBoundary, Pixels)> = None;
+        let mut last_fitting_boundary: Option<(WrapBoundary, Pixels)> = None;
         let mut last_boundary = WrapBoundary {
             run_ix: 0,
             glyph_ix: 0,
@@ -162,17 +179,29 @@ impl LineLayout {
                 continue;
             }
 
+            // The width needed at the end of the line if it were broken before this glyph.
+            let break_width = x - last_boundary_x
+                + if LineWrapper::breaks_word(prev_ch, ch) {
+                    hyphen_width
+                } else {
+                    px(0.)
+                };
+
             // Here is very similar to `LineWrapper::wrap_line` to determine text wrapping,
             // but there are some differences, so we have to duplicate the code here.
             if LineWrapper::is_word_char(ch) {
                 if prev_ch == ' ' && ch != ' ' && first_non_whitespace_ix.is_some() {
-                    last_candidate_ix = Some(boundary);
-                    last_candidate_x = x;
+                    last_candidate = Some((boundary, x));
+                } else if line_break.word_break == WordBreak::BreakAll
+                    && ch != ' '
+                    && first_non_whitespace_ix.is_some()
+                    && break_width <= wrap_width
+                {
+                    last_candidate = Some((boundary, x));
                 }
             } else {
                 if ch != ' ' && first_non_whitespace_ix.is_some() {
-                    last_candidate_ix = Some(boundary);
-                    last_candidate_x = x;
+                    last_candidate = Some((boundary, x));
                 }
             }
 
@@ -180,31 +209,53 @@ impl LineLayout {
                 first_non_whitespace_ix = Some(boundary);
             }
 
+            if boundary > last_boundary && break_width <= wrap_width {
+                last_fitting_boundary = Some((boundary, x));
+            }
+
             let next_x = glyphs.peek().map_or(self.width, |(_, _, x)| *x);
             let width = next_x - last_boundary_x;
 
             if width > wrap_width && boundary > last_boundary {
-                // When used line_clamp, we should limit the number of lines.
-                if let Some(max_lines) = max_lines
-                    && boundaries.len() >= max_lines - 1
-                {
-                    break;
-                }
+                // Words that don't fit on a line of their own are broken at the last glyph that
+                // fits, or are left to overflow the line until the next candidate.
+                let wrap = last_candidate.take().or_else(|| {
+                    line_break
+                        .breaks_long_words()
+                        .then(|| last_fitting_boundary.unwrap_or((boundary, x)))
+                });
+                if let Some((wrap_boundary, wrap_x)) = wrap {
+                    // When used line_clamp, we should limit the number of lines.
+                    if let Some(max_lines) = max_lines
+                        && boundaries.len() >= max_lines - 1
+                    {
+                        break;
+                    }
 
-                if let Some(last_candidate_ix) = last_candidate_ix.take() {
-                    last_boundary = last_candidate_ix;
-                    last_boundary_x = last_candidate_x;
-                } else {
-                    last_boundary = boundary;
-                    last_boundary_x = x;
+                    last_boundary = wrap_boundary;
+                    last_boundary_x = wrap_x;
+                    last_fitting_boundary = None;
+                    boundaries.push(last_boundary);
                 }
-                boundaries.push(last_boundary);
             }
             prev_ch = ch;
         }
 
         boundaries
     }
+
+    /// Whether a hyphen is inserted at the given wrap boundary of this line when hyphenation is
+    /// enabled, because the boundary splits a word.
+    pub(crate) fn breaks_word_at(&self, text: &str, boundary: WrapBoundary) -> bool {
+        let index = self.runs[boundary.run_ix].glyphs[boundary.glyph_ix].index;
+        match (
+            text[..index].chars().next_back(),
+            text[index..].chars().next(),
+        ) {
+            (Some(prev), Some(next)) => LineWrapper::breaks_word(prev, next),
+            _ => false,
+        }
+    }
 }
 
 /// A line of text that has been wrapped to fit a given width
@@ -218,6 +269,38 @@ pub struct WrappedLineLayout {
 
     /// The width of the line, if it was wrapped
     pub wrap_width: Option<Pixels>,
+
+    /// The hyphen painted at the wrap boundaries that split a word, if hyphenation is enabled
+    pub hyphen: Option<Hyphen>,
+}
+
+/// A hyphen inserted where a wrapped line splits a word
+#[derive(Clone, Debug)]
+pub struct Hyphen {
+    /// The font of the hyphen, which is the first font of the line
+    pub font_id: FontId,
+    /// The hyphen's glyph in its font
+    pub glyph_id: GlyphId,
+    /// The width of the hyphen
+    pub width: Pixels,
+    /// The wrap boundaries that split a word, at the end of whose lines the hyphen is painted
+    pub boundaries: SmallVec<[WrapBoundary; 1]>,
+}
+
+impl Hyphen {
+    /// The hyphen painted at the end of the line wrapped at the given boundary, if any.
+    pub(crate) fn at<'a>(
+        hyphen: Option<&'a Hyphen>,
+        boundary: Option<&WrapBoundary>,
+    ) -> Option<&'a Hyphen> {
+        hyphen
+            .filter(|hyphen| boundary.is_some_and(|boundary| hyphen.boundaries.contains(boundary)))
+    }
+
+    /// The width of the hyphen at the end of the line wrapped at the given boundary, if any.
+    pub(crate) fn width_at(hyphen: Option<&Hyphen>, boundary: Option<&WrapBoundary>) -> Pixels {
+        Self::at(hyphen, boundary).map_or(px(0.), |hyphen| hyphen.width)
+    }
 }
 
 /// A boundary at which a line was wrapped
@@ -309,6 +392,14 @@ impl WrappedLineLayout {
     ) -> Result<usize, usize> {
         let wrapped_line_ix = (position.y / line_height) as usize;
 
+        if self.unwrapped_layout.is_bidi() {
+            let visual_lines = self.unwrapped_layout.visual_lines(&self.wrap_boundaries);
+            let Some(visual_line) = visual_lines.get(wrapped_line_ix) else {
+                return Err(0);
+            };
+            return visual_line.index_for_x(position.x, closest);
+        }
+
         let wrapped_line_start_index;
         let wrapped_line_start_x;
         if wrapped_line_ix > 0 {
@@ -371,6 +462,10 @@ impl WrappedLineLayout {
             })
             .chain([self.len()])
             .enumerate();
+        let visual_lines = self
+            .unwrapped_layout
+            .is_bidi()
+            .then(|| self.unwrapped_layout.visual_lines(&self.wrap_boundaries));
         for (ix, line_end_ix) in line_end_indices {
             let line_y = ix as f32 * line_height;
             if index < line_start_ix {
@@ -378,6 +473,9 @@ impl WrappedLineLayout {
             } else if index > line_end_ix {
                 line_start_ix = line_end_ix;
                 continue;
+            } else if let Some(visual_line) = visual_lines.as_ref().and_then(|lines| lines.get(ix))
+            {
+                return Some(point(visual_line.x_for_index(index), line_y));
             } else {
                 let line_start_x = self.unwrapped_layout.x_for_index(line_start_ix);
                 let x = self.unwrapped_layout.x_for_index(index) - line_start_x;
@@ -470,7 +568,9 @@ impl LineLayoutCache {
         text: Text,
         font_size: Pixels,
         runs: &[FontRun],
+        direction: TextDirection,
         wrap_width: Option<Pixels>,
+        line_break: LineBreakStyle,
         max_lines: Option<usize>,
     ) -> Arc<WrappedLineLayout>
     where
@@ -481,7 +581,9 @@ impl LineLayoutCache {
             text: text.as_ref(),
             font_size,
             runs,
+            direction,
             wrap_width,
+            line_break,
             force_width: None,
         } as &dyn AsCacheKeyRef;
 
@@ -501,22 +603,43 @@ impl LineLayoutCache {
         } else {
             drop(current_frame);
             let text = SharedString::from(text);
-            let unwrapped_layout = self.layout_line::<&SharedString>(&text, font_size, runs, None);
+            let unwrapped_layout =
+                self.layout_line::<&SharedString>(&text, font_size, runs, direction, None);
+            let mut hyphen = runs
+                .first()
+                .filter(|_| line_break.hyphenate && wrap_width.is_some())
+                .and_then(|run| self.layout_hyphen(run.font_id, font_size));
             let wrap_boundaries = if let Some(wrap_width) = wrap_width {
-                unwrapped_layout.compute_wrap_boundaries(text.as_ref(), wrap_width, max_lines)
+                unwrapped_layout.compute_wrap_boundaries(
+                    text.as_ref(),
+                    wrap_width,
+                    max_lines,
+                    line_break,
+                    hyphen.as_ref().map_or(px(0.), |hyphen| hyphen.width),
+                )
             } else {
                 SmallVec::new()
             };
+            if let Some(hyphen) = hyphen.as_mut() {
+                hyphen.boundaries = wrap_boundaries
+                    .iter()
+                    .copied()
+                    .filter(|boundary| unwrapped_layout.breaks_word_at(&text, *boundary))
+                    .collect();
+            }
             let layout = Arc::new(WrappedLineLayout {
                 unwrapped_layout,
                 wrap_boundaries,
                 wrap_width,
+                hyphen,
             });
             let key = Arc::new(CacheKey {
                 text,
                 font_size,
                 runs: SmallVec::from(runs),
+                direction,
                 wrap_width,
+                line_break,
                 force_width: None,
             });
 
@@ -530,11 +653,31 @@ impl LineLayoutCache {
         }
     }
 
+    fn layout_hyphen(&self, font_id: FontId, font_size: Pixels) -> Option<Hyphen> {
+        let layout = self.platform_text_system.layout_line(
+            "-",
+            font_size,
+            &[FontRun {
+                len: "-".len(),
+                font_id,
+            }],
+        );
+        let run = layout.runs.first()?;
+        let glyph = run.glyphs.first()?;
+        Some(Hyphen {
+            font_id: run.font_id,
+            glyph_id: glyph.id,
+            width: layout.width,
+            boundaries: SmallVec::new(),
+        })
+    }
+
     pub fn layout_line<Text>(
         &self,
         text: Text,
         font_size: Pixels,
         runs: &[FontRun],
+        direction: TextDirection,
         force_width: Option<Pixels>,
     ) -> Arc<LineLayout>
     where
@@ -545,7 +688,9 @@ impl LineLayoutCache {
             text: text.as_ref(),
             font_size,
             runs,
+            direction,
             wrap_width: None,
+            line_break: LineBreakStyle::default(),
             force_width,
         } as &dyn AsCacheKeyRef;
 
@@ -561,9 +706,23 @@ impl LineLayoutCache {
             layout
         } else {
             let text = SharedString::from(text);
-            let mut layout = self
-                .platform_text_system
-                .layout_line(&text, font_size, runs);
+            let (rtl, bidi_runs) = resolve_bidi_runs(&text, direction);
+            let mut layout = if bidi_runs.is_empty() {
+                let mut layout = self
+                    .platform_text_system
+                    .layout_line(&text, font_size, runs);
+                layout.rtl = rtl;
+                layout
+            } else {
+                layout_bidi_line(
+                    &*self.platform_text_system,
+                    &text,
+                    font_size,
+                    runs,
+                    rtl,
+                    bidi_runs,
+                )
+            };
 
             if let Some(force_width) = force_width {
                 let mut glyph_pos = 0;
@@ -581,7 +740,9 @@ impl LineLayoutCache {
                 text,
                 font_size,
                 runs: SmallVec::from(runs),
+                direction,
                 wrap_width: None,
+                line_break: LineBreakStyle::default(),
                 force_width,
             });
             let layout = Arc::new(layout);
@@ -608,7 +769,9 @@ struct CacheKey {
     text: SharedString,
     font_size: Pixels,
     runs: SmallVec<[FontRun; 1]>,
+    direction: TextDirection,
     wrap_width: Option<Pixels>,
+    line_break: LineBreakStyle,
     force_width: Option<Pixels>,
 }
 
@@ -617,7 +780,9 @@ struct CacheKeyRef<'a> {
     text: &'a str,
     font_size: Pixels,
     runs: &'a [FontRun],
+    direction: TextDirection,
     wrap_width: Option<Pixels>,
+    line_break: LineBreakStyle,
     force_width: Option<Pixels>,
 }
 
@@ -641,7 +806,9 @@ impl AsCacheKeyRef for CacheKey {
             text: &self.text,
             font_size: self.font_size,
             runs: self.runs.as_slice(),
+            direction: self.direction,
             wrap_width: self.wrap_width,
+            line_break: self.line_break,
             force_width: self.force_width,
         }
     }
@@ -1,6 +1,27 @@
-use crate::{FontId, FontRun, Pixels, PlatformTextSystem, SharedString, TextRun, px};
+use crate::{
+    FontId, FontRun, OverflowWrap, Pixels, PlatformTextSystem, SharedString, TextRun,
+    TruncationStyle, WordBreak, px,
+};
 use collections::HashMap;
-use std::{borrow::Cow, iter, sync::Arc};
+use std::{borrow::Cow, iter, ops::Range, sync::Arc};
+
+/// The rules for where a line of text may be broken when it's wrapped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LineBreakStyle {
+    /// Where lines may be broken within words.
+    pub word_break: WordBreak,
+    /// What to do with words too long to fit on a line of their own.
+    pub overflow_wrap: OverflowWrap,
+    /// Whether to insert a hyphen where a word is broken across lines.
+    pub hyphenate: bool,
+}
+
+impl LineBreakStyle {
+    /// Whether a word that doesn't fit on a line of its own may be broken.
+    pub fn breaks_long_words(&self) -> bool {
+        self.word_break != WordBreak::Normal || self.overflow_wrap == OverflowWrap::BreakWord
+    }
+}
 
 /// The GPUI line wrapper, used to wrap lines of text to a given width.
 pub struct LineWrapper {
@@ -129,7 +150,31 @@ impl LineWrapper {
     }
 
     /// Truncate a line of text to the given width with this wrapper's font and font size.
+    ///
+    /// The truncation style decides which part of the line is replaced by the suffix.
     pub fn truncate_line<'a>(
+        &mut self,
+        line: SharedString,
+        truncate_width: Pixels,
+        truncation_suffix: &str,
+        truncation_style: TruncationStyle,
+        runs: &'a [TextRun],
+    ) -> (SharedString, Cow<'a, [TextRun]>) {
+        match truncation_style {
+            TruncationStyle::End => {
+                self.truncate_line_end(line, truncate_width, truncation_suffix, runs)
+            }
+            TruncationStyle::Start | TruncationStyle::Middle => self.truncate_line_from_ends(
+                line,
+                truncate_width,
+                truncation_suffix,
+                truncation_style,
+                runs,
+            ),
+        }
+    }
+
+    fn truncate_line_end<'a>(
         &mut self,
         line: SharedString,
         truncate_width: Pixels,
@@ -164,6 +209,117 @@ impl LineWrapper {
         (line, Cow::Borrowed(runs))
     }
 
+    /// Truncate text that's clamped to a number of lines at the end of its last visible row,
+    /// replacing everything after it with the suffix. Characters are dropped from the end of
+    /// the row until it fits in `row_width` along with the suffix, and whitespace before the
+    /// suffix is removed.
+    pub fn truncate_clamped_text(
+        &mut self,
+        text: &str,
+        last_row: Range<usize>,
+        row_width: Option<Pixels>,
+        truncation_suffix: &str,
+        runs: &[TextRun],
+    ) -> (SharedString, Vec<TextRun>) {
+        let suffix_width = truncation_suffix
+            .chars()
+            .map(|c| self.width_for_char(c))
+            .fold(px(0.), |a, x| a + x);
+        let available_width = row_width.map(|row_width| row_width - suffix_width);
+
+        let mut width = px(0.);
+        let mut end_ix = last_row.start;
+        for (ix, c) in text[last_row.clone()].char_indices() {
+            width += self.width_for_char(c);
+            if available_width.is_some_and(|available_width| width > available_width) {
+                break;
+            }
+            end_ix = last_row.start + ix + c.len_utf8();
+        }
+
+        let result = SharedString::from(format!(
+            "{}{}",
+            text[..end_ix].trim_end(),
+            truncation_suffix
+        ));
+        let mut runs = runs.to_vec();
+        update_runs_after_truncation(&result, truncation_suffix, &mut runs);
+        (result, runs)
+    }
+
+    /// Keeps characters from the end of the line (and, for middle truncation, alternately from
+    /// its start) until the width left over after the suffix has been used up.
+    fn truncate_line_from_ends<'a>(
+        &mut self,
+        line: SharedString,
+        truncate_width: Pixels,
+        truncation_suffix: &str,
+        truncation_style: TruncationStyle,
+        runs: &'a [TextRun],
+    ) -> (SharedString, Cow<'a, [TextRun]>) {
+        let line_width = line
+            .chars()
+            .map(|c| self.width_for_char(c))
+            .fold(px(0.), |a, x| a + x);
+        if line_width.floor() <= truncate_width {
+            return (line, Cow::Borrowed(runs));
+        }
+
+        let suffix_width = truncation_suffix
+            .chars()
+            .map(|c| self.width_for_char(c))
+            .fold(px(0.), |a, x| a + x);
+        let available_width = truncate_width - suffix_width;
+
+        let mut used_width = px(0.);
+        let mut chars = line.char_indices();
+        // The line keeps `..prefix_end` and `suffix_start..`.
+        let mut prefix_end = 0;
+        let mut suffix_start = line.len();
+        let mut prefix_full = truncation_style == TruncationStyle::Start;
+        let mut suffix_full = false;
+        while !(prefix_full && suffix_full) {
+            if !suffix_full {
+                match chars
+                    .next_back()
+                    .map(|(ix, c)| (ix, self.width_for_char(c)))
+                {
+                    Some((ix, char_width)) if used_width + char_width <= available_width => {
+                        used_width += char_width;
+                        suffix_start = ix;
+                    }
+                    _ => suffix_full = true,
+                }
+            }
+            if !prefix_full {
+                match chars
+                    .next()
+                    .map(|(ix, c)| (ix + c.len_utf8(), self.width_for_char(c)))
+                {
+                    Some((end_ix, char_width)) if used_width + char_width <= available_width => {
+                        used_width += char_width;
+                        prefix_end = end_ix;
+                    }
+                    _ => prefix_full = true,
+                }
+            }
+        }
+
+        let result = SharedString::from(format!(
+            "{}{}{}",
+            &line[..prefix_end],
+            truncation_suffix,
+            &line[suffix_start..]
+        ));
+        let runs = runs_after_truncation_from_ends(
+            runs,
+            prefix_end,
+            suffix_start,
+            truncation_suffix.len(),
+        );
+        (result, Cow::Owned(runs))
+    }
+
     /// Any character in this list should be treated as a word character,
     /// meaning it can be part of a word that should not be wrapped.
     pub(crate) fn is_word_char(c: char) -> bool {
@@ -190,6 +346,12 @@ impl LineWrapper {
         matches!(c, '⋯')
     }
 
+    /// Whether breaking a line between the two given characters splits a word, in which case a
+    /// hyphen is inserted at the break when hyphenation is enabled.
+    pub(crate) fn breaks_word(prev: char, next: char) -> bool {
+        prev != '-' && Self::is_word_char(prev) && Self::is_word_char(next)
+    }
+
     #[inline(always)]
     fn width_for_char(&mut self, c: char) -> Pixels {
         if (c as u32) < 128 {
@@ -238,6 +400,30 @@ fn update_runs_after_truncation(result: &str, ellipsis: &str, runs: &mut Vec<Tex
     }
 }
 
+/// Builds the runs for a line that kept `..prefix_end` and `suffix_start..` of its original text,
+/// with the truncation suffix in between taking the style of the first removed character.
+fn runs_after_truncation_from_ends(
+    runs: &[TextRun],
+    prefix_end: usize,
+    suffix_start: usize,
+    suffix_len: usize,
+) -> Vec<TextRun> {
+    let mut result = Vec::with_capacity(runs.len() + 1);
+    let mut run_start = 0;
+    for run in runs {
+        let run_end = run_start + run.len;
+        let kept_len = run_end.min(prefix_end).saturating_sub(run_start)
+            + run_end.saturating_sub(suffix_start.max(run_start));
+        let contains_suffix = (run_start..run_end).contains(&prefix_end);
+        let len = kept_len + if contains_suffix { suffix_len } else { 0 };
+        if len > 0 {
+            result.push(TextRun { len, ..run.clone() });
+        }
+        run_start = run_end;
+    }
+    result
+}
+
 /// A fragment of a line that can be wrapped.
 pub enum LineFragment<'a> {
     /// A text fragment consisting of characters.
@@ -315,9 +501,12 @@ impl Boundary {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Font, FontFeatures, FontStyle, FontWeight, TestAppContext, TestDispatcher, font};
+    use crate::{
+        Font, FontFeatures, FontStyle, FontVariations, FontWeight, TestAppContext, TestDispatcher,
+        font,
+    };
     #[cfg(target_os = "macos")]
-    use crate::{TextRun, WindowTextSystem, WrapBoundary};
+    use crate::{TextDirection, TextRun, WindowTextSystem, WrapBoundary};
     use rand::prelude::*;
 
     fn build_wrapper() -> LineWrapper {
@@ -335,6 +524,7 @@ mod tests {
                 font: Font {
                     family: "Dummy".into(),
                     features: FontFeatures::default(),
+                    variations: FontVariations::default(),
                     fallbacks: None,
                     weight: FontWeight::default(),
                     style: FontStyle::Normal,
@@ -494,8 +684,13 @@ mod tests {
         ) {
             let dummy_run_lens = vec![text.len()];
             let dummy_runs = generate_test_runs(&dummy_run_lens);
-            let (result, dummy_runs) =
-                wrapper.truncate_line(text.into(), px(220.), ellipsis, &dummy_runs);
+            let (result, dummy_runs) = wrapper.truncate_line(
+                text.into(),
+                px(220.),
+                ellipsis,
+                TruncationStyle::End,
+                &dummy_runs,
+            );
             assert_eq!(result, expected);
             assert_eq!(dummy_runs.first().unwrap().len, result.len());
         }
@@ -533,8 +728,13 @@ mod tests {
             line_width: Pixels,
         ) {
             let dummy_runs = generate_test_runs(run_lens);
-            let (result, dummy_runs) =
-                wrapper.truncate_line(text.into(), line_width, "…", &dummy_runs);
+            let (result, dummy_runs) = wrapper.truncate_line(
+                text.into(),
+                line_width,
+                "…",
+                TruncationStyle::End,
+                &dummy_runs,
+            );
             assert_eq!(result, expected);
             for (run, result_len) in dummy_runs.iter().zip(result_run_len) {
                 assert_eq!(run.len, *result_len);
@@ -579,6 +779,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_truncate_line_from_ends() {
+        let mut wrapper = build_wrapper();
+
+        fn perform_test(
+            wrapper: &mut LineWrapper,
+            truncation_style: TruncationStyle,
+            line_width: Pixels,
+            expected: &str,
+            result_run_lens: &[usize],
+        ) {
+            let dummy_runs = generate_test_runs(&[4, 9, 6]);
+            let (result, dummy_runs) = wrapper.truncate_line(
+                "src/elements/svg.rs".into(),
+                line_width,
+                "…",
+                truncation_style,
+                &dummy_runs,
+            );
+            assert_eq!(result, expected);
+            assert_eq!(
+                dummy_runs.iter().map(|run| run.len).collect::<Vec<_>>(),
+                result_run_lens
+            );
+        }
+
+        // Text that fits is left untouched.
+        perform_test(
+            &mut wrapper,
+            TruncationStyle::Middle,
+            px(190.),
+            "src/elements/svg.rs",
+            &[4, 9, 6],
+        );
+        // Middle: characters are kept alternately from the end and the start, and the
+        // ellipsis joins the run of the first removed character.
+        perform_test(
+            &mut wrapper,
+            TruncationStyle::Middle,
+            px(110.),
+            "src/e…vg.rs",
+            &[4, 4, 5],
+        );
+        // Start: only the end of the text is kept.
+        perform_test(
+            &mut wrapper,
+            TruncationStyle::Start,
+            px(110.),
+            "…nts/svg.rs",
+            &[3, 4, 6],
+        );
+    }
+
+    #[test]
+    fn test_truncate_clamped_text() {
+        let mut wrapper = build_wrapper();
+        let dummy_runs = generate_test_runs(&[4, 7, 4]);
+
+        // The whitespace at the end of the last row is dropped before the ellipsis.
+        let (result, runs) = wrapper.truncate_clamped_text(
+            "aaa bbb ccc\nddd",
+            4..8,
+            Some(px(50.)),
+            "…",
+            &dummy_runs,
+        );
+        assert_eq!(result, "aaa bbb…");
+        assert_eq!(runs.iter().map(|run| run.len).collect::<Vec<_>>(), [4, 6]);
+
+        // Characters are dropped from the end of the last row to make room for the ellipsis.
+        let (result, _) = wrapper.truncate_clamped_text(
+            "aaa bbb ccc\nddd",
+            4..8,
+            Some(px(30.)),
+            "…",
+            &dummy_runs,
+        );
+        assert_eq!(result, "aaa bb…");
+    }
+
     #[test]
     fn test_update_run_after_truncation() {
         fn perform_test(result: &str, run_lens: &[usize], result_run_lens: &[usize]) {
@@ -706,7 +986,9 @@ mod tests {
                         bold.with_len(1),
                         normal.with_len(7),
                     ],
+                    TextDirection::Auto,
                     Some(px(72.)),
+                    LineBreakStyle::default(),
                     None,
                 )
                 .unwrap();
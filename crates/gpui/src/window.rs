@@ -2,29 +2,33 @@
 use crate::Inspector;
 use crate::{
     Action, AnyDrag, AnyElement, AnyImageCache, AnyTooltip, AnyView, App, AppContext, Arena, Asset,
-    AsyncWindowContext, AvailableSpace, Background, BorderStyle, Bounds, BoxShadow, Capslock,
-    Context, Corners, CursorStyle, Decorations, DevicePixels, DispatchActionListener,
-    DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter,
-    FileDropEvent, FontId, Global, GlobalElementId, GlyphId, GpuSpecs, Hsla, InputHandler, IsZero,
-    KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke, KeystrokeEvent, LayoutId,
-    LineLayoutIndex, Modifiers, ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent,
-    MouseMoveEvent, MouseUpEvent, Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
-    PlatformInputHandler, PlatformWindow, Point, PolychromeSprite, PromptButton, PromptLevel, Quad,
-    Render, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams, Replay, ResizeEdge,
-    SMOOTH_SVG_SCALE_FACTOR, SUBPIXEL_VARIANTS_X, SUBPIXEL_VARIANTS_Y, ScaledPixels, Scene, Shadow,
-    SharedString, Size, StrikethroughStyle, Style, SubscriberSet, Subscription, SystemWindowTab,
-    SystemWindowTabController, TabStopMap, TaffyLayoutEngine, Task, TextStyle, TextStyleRefinement,
-    TransformationMatrix, Underline, UnderlineStyle, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
-    point, prelude::*, px, rems, size, transparent_black,
+    AsyncWindowContext, AvailableSpace, Backdrop, Background, BackgroundTag, BlendMode,
+    BorderStyle, Bounds, BoxShadow, Capslock, ClipRegion, ClipRegionKind, Context, Corners,
+    CursorStyle, Decorations, Deferred, DevicePixels, DispatchActionListener, DispatchNodeId,
+    DispatchTree, DisplayId, DragImage, Edges, Effect, Entity, EntityId, EventEmitter,
+    FileDropEvent, FontId, Global, GlobalElementId, GlyphId, GpuSpecs, GradientSprite,
+    GroupHitboxes, Hsla, InputHandler, IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent,
+    Keystroke, KeystrokeEvent, LayoutId, LineLayoutIndex, LineStyle, Modifiers,
+    ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseButtonFlags, MouseEvent,
+    MouseMoveEvent, MouseUpEvent, Path, PathBuilder, Pixels, PlatformAtlas, PlatformDisplay,
+    PlatformInput, PlatformInputHandler, PlatformWindow, Point, PolychromeSprite, PromptButton,
+    PromptLevel, Quad, Render, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams,
+    Replay, ResizeEdge, SMOOTH_SVG_SCALE_FACTOR, SUBPIXEL_VARIANTS_X, SUBPIXEL_VARIANTS_Y,
+    ScaledPixels, Scene, Shadow, SharedString, Size, StrikethroughStyle, StrokeStyle, Style,
+    SubscriberSet, Subscription, SvgParseOptions, SvgRenderOptions, SystemWindowTab,
+    SystemWindowTabController, TabStopMap, TaffyLayoutEngine, Task, TextMeasurement, TextOverflow,
+    TextRun, TextStyle, TextStyleRefinement, TransformationMatrix, Underline, UnderlineStyle,
+    WhiteSpace, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControls,
+    WindowDecorations, WindowOptions, WindowParams, WindowTextSystem, hash, point, prelude::*, px,
+    remove_svg_assets, rems, size, svg_transformation_scale, transparent_black, visual_rows,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::{FxHashMap, FxHashSet};
 #[cfg(target_os = "macos")]
 use core_video::pixel_buffer::CVPixelBuffer;
 use derive_more::{Deref, DerefMut};
-use futures::FutureExt;
 use futures::channel::oneshot;
+use futures::{FutureExt, StreamExt, future};
 use itertools::FoldWhile::{Continue, Done};
 use itertools::Itertools;
 use parking_lot::RwLock;
@@ -42,6 +46,7 @@ use std::{
     marker::PhantomData,
     mem,
     ops::{DerefMut, Range},
+    path::PathBuf,
     rc::Rc,
     sync::{
         Arc, Weak,
@@ -60,6 +65,10 @@ pub use prompts::*;
 
 pub(crate) const DEFAULT_WINDOW_SIZE: Size<Pixels> = size(px(1536.), px(864.));
 
+/// The number of SVG rasterizations a window keeps in its sprite atlas before it evicts the ones
+/// that weren't used to draw the last frame.
+pub(crate) const MAX_RENDERED_SVGS: usize = 256;
+
 /// A 6:5 aspect ratio minimum window size to be used for functional,
 /// additional-to-main-Zed windows, like the settings and rules library windows.
 pub const DEFAULT_ADDITIONAL_WINDOW_SIZE: Size<Pixels> = Size {
@@ -132,6 +141,16 @@ impl WindowInvalidator {
         }
     }
 
+    /// Marks the view dirty without a notification, for state that changes outside of an
+    /// update, so that the window redraws it on its next frame.
+    pub fn invalidate_view_silently(&self, entity: EntityId) {
+        let mut inner = self.inner.borrow_mut();
+        inner.dirty_views.insert(entity);
+        if inner.draw_phase == DrawPhase::None {
+            inner.dirty = true;
+        }
+    }
+
     pub fn is_dirty(&self) -> bool {
         self.inner.borrow().dirty
     }
@@ -156,6 +175,10 @@ impl WindowInvalidator {
         self.inner.borrow().draw_phase == DrawPhase::None
     }
 
+    pub fn phase(&self) -> DrawPhase {
+        self.inner.borrow().draw_phase
+    }
+
     #[track_caller]
     pub fn debug_assert_paint(&self) {
         debug_assert!(
@@ -479,6 +502,8 @@ pub(crate) struct CursorStyleRequest {
 pub(crate) struct HitTest {
     pub(crate) ids: SmallVec<[HitboxId; 8]>,
     pub(crate) hover_hitbox_count: usize,
+    /// The frontmost hit hitbox and those of its ancestors that were also hit.
+    pub(crate) topmost_ids: SmallVec<[HitboxId; 8]>,
 }
 
 /// A type of window control area that corresponds to the platform window.
@@ -514,6 +539,14 @@ impl HitboxId {
         false
     }
 
+    /// Checks if the hitbox with this ID is currently hovered and isn't covered by a hitbox in front
+    /// of it, other than those of its descendants.
+    ///
+    /// See [`Hitbox::is_hovered_unoccluded`] for details.
+    pub fn is_hovered_unoccluded(self, window: &Window) -> bool {
+        self.is_hovered(window) && window.mouse_hit_test.topmost_ids.contains(&self)
+    }
+
     /// Checks if the hitbox with this ID contains the mouse and should handle scroll events.
     /// Typically this should only be used when handling `ScrollWheelEvent`, and otherwise
     /// `is_hovered` should be used. See the documentation of `Hitbox::is_hovered` for details about
@@ -540,6 +573,167 @@ pub struct Hitbox {
     pub content_mask: ContentMask<Pixels>,
     /// Flags that specify hitbox behavior.
     pub behavior: HitboxBehavior,
+    /// The shape of the hitbox within its bounds.
+    pub shape: HitboxShape,
+    /// Restricts the hitbox to the opaque parts of a mask, see [`Window::set_hitbox_mask`].
+    pub(crate) mask: Option<Arc<HitboxMask>>,
+    /// The hitbox of the nearest ancestor that inserted one, see [`Window::with_hitbox_parent`].
+    pub(crate) parent: Option<HitboxId>,
+    /// The clip path the hitbox was inserted within, see [`Window::with_clip_path`].
+    pub(crate) clip_path: Option<Arc<HitboxClipPath>>,
+}
+
+/// The shape of a hitbox within its bounds, so that the mouse only hits the element where it's
+/// drawn. See [`InteractiveElement::hitbox_shape`](crate::InteractiveElement::hitbox_shape).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum HitboxShape {
+    /// The whole bounds.
+    #[default]
+    Rect,
+    /// The bounds with corners rounded by the given radii.
+    RoundedRect(Corners<Pixels>),
+    /// The ellipse inscribed in the bounds.
+    Ellipse,
+    /// A polygon whose vertices are relative to the origin of the bounds. Whether a point is
+    /// inside the polygon is decided by the even-odd rule.
+    Polygon(Vec<Point<Pixels>>),
+}
+
+impl HitboxShape {
+    /// Whether the given position, which lies within the given bounds, lies within the shape.
+    pub(crate) fn contains(&self, bounds: &Bounds<Pixels>, position: Point<Pixels>) -> bool {
+        match self {
+            Self::Rect => true,
+            Self::RoundedRect(corner_radii) => {
+                let corner_radii = corner_radii.clone().clamp_radii_for_quad_size(bounds.size);
+                let center = bounds.center();
+                let left = position.x < center.x;
+                let top = position.y < center.y;
+                let (radius, corner) = match (left, top) {
+                    (true, true) => (corner_radii.top_left, bounds.origin),
+                    (false, true) => (corner_radii.top_right, bounds.top_right()),
+                    (false, false) => (corner_radii.bottom_right, bounds.bottom_right()),
+                    (true, false) => (corner_radii.bottom_left, bounds.bottom_left()),
+                };
+                // Only the points between the corner and the center of its circle can miss.
+                let circle_center = point(
+                    if left {
+                        corner.x + radius
+                    } else {
+                        corner.x - radius
+                    },
+                    if top {
+                        corner.y + radius
+                    } else {
+                        corner.y - radius
+                    },
+                );
+                let offset = position - circle_center;
+                let outside_x = if left {
+                    offset.x < px(0.)
+                } else {
+                    offset.x > px(0.)
+                };
+                let outside_y = if top {
+                    offset.y < px(0.)
+                } else {
+                    offset.y > px(0.)
+                };
+                !(outside_x && outside_y)
+                    || offset.x.0 * offset.x.0 + offset.y.0 * offset.y.0 <= radius.0 * radius.0
+            }
+            Self::Ellipse => {
+                let half_width = bounds.size.width.0 / 2.;
+                let half_height = bounds.size.height.0 / 2.;
+                if half_width <= 0. || half_height <= 0. {
+                    return false;
+                }
+                let offset = position - bounds.center();
+                let x = offset.x.0 / half_width;
+                let y = offset.y.0 / half_height;
+                x * x + y * y <= 1.
+            }
+            Self::Polygon(vertices) => {
+                let position = position - bounds.origin;
+                let mut inside = false;
+                for (index, vertex) in vertices.iter().enumerate() {
+                    let previous = vertices[(index + vertices.len() - 1) % vertices.len()];
+                    if (vertex.y > position.y) != (previous.y > position.y) {
+                        let crossing_x = vertex.x.0
+                            + (position.y.0 - vertex.y.0) / (previous.y.0 - vertex.y.0)
+                                * (previous.x.0 - vertex.x.0);
+                        if position.x.0 < crossing_x {
+                            inside = !inside;
+                        }
+                    }
+                }
+                inside
+            }
+        }
+    }
+}
+
+/// An alpha mask restricting a hitbox to the points where the mask is at least as opaque as a
+/// threshold, such as an SVG's shape.
+#[derive(Clone)]
+pub(crate) struct HitboxMask {
+    /// The bounds the mask is stretched across, before the transformation.
+    pub bounds: Bounds<Pixels>,
+    /// Maps window positions back into the untransformed bounds.
+    pub inverse_transformation: TransformationMatrix,
+    /// The size of the mask in pixels.
+    pub size: Size<DevicePixels>,
+    /// The alpha of each pixel of the mask, row by row.
+    pub alpha: Arc<[u8]>,
+    /// The lowest alpha that hits the hitbox.
+    pub threshold: u8,
+}
+
+impl HitboxMask {
+    fn contains(&self, position: Point<Pixels>) -> bool {
+        let position = self.inverse_transformation.apply(position);
+        if !self.bounds.contains(&position) {
+            return false;
+        }
+        let width = self.size.width.0.max(0) as usize;
+        let height = self.size.height.0.max(0) as usize;
+        let offset = position - self.bounds.origin;
+        let column = (offset.x / self.bounds.size.width * width as f32) as usize;
+        let row = (offset.y / self.bounds.size.height * height as f32) as usize;
+        self.alpha
+            .get(row.min(height.saturating_sub(1)) * width + column.min(width.saturating_sub(1)))
+            .is_some_and(|alpha| *alpha >= self.threshold)
+    }
+}
+
+/// A shape that hitboxes inserted within [`Window::with_clip_path`] are restricted to, along
+/// with the clip paths enclosing it.
+#[derive(Clone, Debug)]
+pub(crate) struct HitboxClipPath {
+    pub bounds: Bounds<Pixels>,
+    pub shape: HitboxShape,
+    pub parent: Option<Arc<HitboxClipPath>>,
+}
+
+impl HitboxClipPath {
+    fn contains(&self, position: Point<Pixels>) -> bool {
+        self.bounds.contains(&position)
+            && self.shape.contains(&self.bounds, position)
+            && self
+                .parent
+                .as_ref()
+                .is_none_or(|parent| parent.contains(position))
+    }
+}
+
+impl std::fmt::Debug for HitboxMask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HitboxMask")
+            .field("bounds", &self.bounds)
+            .field("size", &self.size)
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Hitbox {
@@ -561,6 +755,16 @@ impl Hitbox {
         self.id.is_hovered(window)
     }
 
+    /// Checks if the hitbox is currently hovered and is the frontmost hovered hitbox, or an
+    /// ancestor of it. This is what hover styles and hover listeners use, so that when siblings
+    /// overlap only the one in front is highlighted.
+    ///
+    /// Hitboxes inserted by deferred draws have no ancestors, so an overlay occludes the element
+    /// it's anchored to.
+    pub fn is_hovered_unoccluded(&self, window: &Window) -> bool {
+        self.id.is_hovered_unoccluded(window)
+    }
+
     /// Checks if the hitbox contains the mouse and should handle scroll events. Typically this
     /// should only be used when handling `ScrollWheelEvent`, and otherwise `is_hovered` should be
     /// used. See the documentation of `Hitbox::is_hovered` for details about this distinction.
@@ -676,6 +880,10 @@ pub(crate) struct Frame {
     pub(crate) window_active: bool,
     pub(crate) element_states: FxHashMap<(GlobalElementId, TypeId), ElementStateBox>,
     accessed_element_states: Vec<(GlobalElementId, TypeId)>,
+    /// The assets used to draw the frame, which aren't evicted from the asset cache.
+    pub(crate) used_assets: Vec<(TypeId, u64)>,
+    /// The SVG rasterizations used to draw the frame, which aren't evicted from the sprite atlas.
+    pub(crate) used_svgs: Vec<RenderSvgParams>,
     pub(crate) mouse_listeners: Vec<Option<AnyMouseListener>>,
     pub(crate) dispatch_tree: DispatchTree,
     pub(crate) scene: Scene,
@@ -685,6 +893,7 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) group_hitboxes: GroupHitboxes,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
     #[cfg(any(feature = "inspector", debug_assertions))]
@@ -701,6 +910,7 @@ pub(crate) struct PrepaintStateIndex {
     deferred_draws_index: usize,
     dispatch_tree_index: usize,
     accessed_element_states_index: usize,
+    used_assets_index: usize,
     line_layout_index: LineLayoutIndex,
 }
 
@@ -711,6 +921,8 @@ pub(crate) struct PaintIndex {
     input_handlers_index: usize,
     cursor_styles_index: usize,
     accessed_element_states_index: usize,
+    used_assets_index: usize,
+    used_svgs_index: usize,
     tab_handle_index: usize,
     line_layout_index: LineLayoutIndex,
 }
@@ -722,6 +934,8 @@ impl Frame {
             window_active: false,
             element_states: FxHashMap::default(),
             accessed_element_states: Vec::new(),
+            used_assets: Vec::new(),
+            used_svgs: Vec::new(),
             mouse_listeners: Vec::new(),
             dispatch_tree,
             scene: Scene::default(),
@@ -731,6 +945,7 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            group_hitboxes: GroupHitboxes::default(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
@@ -747,12 +962,15 @@ impl Frame {
     pub(crate) fn clear(&mut self) {
         self.element_states.clear();
         self.accessed_element_states.clear();
+        self.used_assets.clear();
+        self.used_svgs.clear();
         self.mouse_listeners.clear();
         self.dispatch_tree.clear();
         self.scene.clear();
         self.input_handlers.clear();
         self.tooltip_requests.clear();
         self.cursor_styles.clear();
+        self.group_hitboxes.clear();
         self.hitboxes.clear();
         self.window_control_hitboxes.clear();
         self.deferred_draws.clear();
@@ -782,9 +1000,32 @@ impl Frame {
     pub(crate) fn hit_test(&self, position: Point<Pixels>) -> HitTest {
         let mut set_hover_hitbox_count = false;
         let mut hit_test = HitTest::default();
+        // Ancestors are always inserted before their descendants, so they're found later in this
+        // reversed iteration.
+        let mut next_ancestor = None;
         for hitbox in self.hitboxes.iter().rev() {
+            let is_next_ancestor = next_ancestor == Some(hitbox.id);
+            if is_next_ancestor {
+                next_ancestor = hitbox.parent;
+            }
             let bounds = hitbox.bounds.intersect(&hitbox.content_mask.bounds);
-            if bounds.contains(&position) {
+            if bounds.contains(&position)
+                && hitbox.shape.contains(&hitbox.bounds, position)
+                && hitbox
+                    .mask
+                    .as_ref()
+                    .is_none_or(|mask| mask.contains(position))
+                && hitbox
+                    .clip_path
+                    .as_ref()
+                    .is_none_or(|clip_path| clip_path.contains(position))
+            {
+                if hit_test.ids.is_empty() {
+                    next_ancestor = hitbox.parent;
+                    hit_test.topmost_ids.push(hitbox.id);
+                } else if is_next_ancestor {
+                    hit_test.topmost_ids.push(hitbox.id);
+                }
                 hit_test.ids.push(hitbox.id);
                 if !set_hover_hitbox_count
                     && hitbox.behavior == HitboxBehavior::BlockMouseExceptScroll
@@ -851,12 +1092,23 @@ pub struct Window {
     pub(crate) rendered_entity_stack: Vec<EntityId>,
     pub(crate) element_offset_stack: Vec<Point<Pixels>>,
     pub(crate) element_opacity: f32,
+    pub(crate) element_blend_mode: BlendMode,
+    /// The bounds of the snap points prepainted so far in the innermost scroll container, if
+    /// it snaps.
+    pub(crate) snap_points: Option<Vec<Bounds<Pixels>>>,
+    /// The part of the delta of the scroll wheel event being dispatched that the scroll
+    /// containers it's reached so far haven't consumed, or `None` before it reaches the first.
+    pub(crate) unconsumed_scroll_delta: Option<Point<Pixels>>,
     pub(crate) content_mask_stack: Vec<ContentMask<Pixels>>,
     pub(crate) requested_autoscroll: Option<Bounds<Pixels>>,
     pub(crate) image_cache_stack: Vec<AnyImageCache>,
+    /// The sprite atlas entries rasterized for each SVG path, so they can be evicted together.
+    pub(crate) rendered_svgs: FxHashMap<SharedString, FxHashSet<RenderSvgParams>>,
     pub(crate) rendered_frame: Frame,
     pub(crate) next_frame: Frame,
     next_hitbox_id: HitboxId,
+    hitbox_parent_stack: Vec<HitboxId>,
+    clip_path_stack: Vec<Arc<HitboxClipPath>>,
     pub(crate) next_tooltip_id: TooltipId,
     pub(crate) tooltip_bounds: Option<TooltipBounds>,
     next_frame_callbacks: Rc<RefCell<Vec<FrameCallback>>>,
@@ -865,6 +1117,7 @@ pub struct Window {
     pub(crate) focus_lost_listeners: SubscriberSet<(), AnyObserver>,
     default_prevented: bool,
     mouse_position: Point<Pixels>,
+    pressed_mouse_buttons: MouseButtonFlags,
     mouse_hit_test: HitTest,
     modifiers: Modifiers,
     capslock: Capslock,
@@ -1297,11 +1550,16 @@ impl Window {
             element_offset_stack: Vec::new(),
             content_mask_stack: Vec::new(),
             element_opacity: 1.0,
+            element_blend_mode: BlendMode::Normal,
+            snap_points: None,
+            unconsumed_scroll_delta: None,
             requested_autoscroll: None,
             rendered_frame: Frame::new(DispatchTree::new(cx.keymap.clone(), cx.actions.clone())),
             next_frame: Frame::new(DispatchTree::new(cx.keymap.clone(), cx.actions.clone())),
             next_frame_callbacks,
             next_hitbox_id: HitboxId(0),
+            hitbox_parent_stack: Vec::new(),
+            clip_path_stack: Vec::new(),
             next_tooltip_id: TooltipId::default(),
             tooltip_bounds: None,
             dirty_views: FxHashSet::default(),
@@ -1309,6 +1567,7 @@ impl Window {
             focus_lost_listeners: SubscriberSet::new(),
             default_prevented: true,
             mouse_position,
+            pressed_mouse_buttons: MouseButtonFlags::default(),
             mouse_hit_test: HitTest::default(),
             modifiers,
             capslock,
@@ -1331,6 +1590,7 @@ impl Window {
             prompt: None,
             client_inset: None,
             image_cache_stack: Vec::new(),
+            rendered_svgs: FxHashMap::default(),
             #[cfg(any(feature = "inspector", debug_assertions))]
             inspector: None,
         })
@@ -1828,6 +2088,32 @@ impl Window {
         self.platform_window.start_window_move()
     }
 
+    /// Starts a platform drag session that carries the given files out of the window, e.g. into
+    /// Finder or Explorer. Returns false if the platform doesn't support dragging files out.
+    ///
+    /// The given part of the last rendered frame, typically the dragged element's bounds, follows
+    /// the pointer as the drag image. This should be called while the left mouse button is held
+    /// down, and mouse events may not be received while the drag is in progress.
+    pub fn start_drag_out(&self, paths: &[PathBuf], image_bounds: Bounds<Pixels>) -> bool {
+        let scale_factor = self.scale_factor();
+        // Renderers can only read back the visible part of the frame.
+        let image_bounds = image_bounds
+            .intersect(&Bounds::new(Point::default(), self.viewport_size))
+            .to_device_pixels(scale_factor);
+        let image = self
+            .platform_window
+            .render_to_image(&self.rendered_frame.scene, image_bounds)
+            .map(|image| DragImage {
+                image,
+                cursor_offset: self
+                    .mouse_position
+                    .scale(scale_factor)
+                    .map(|x| DevicePixels(x.0.round() as i32))
+                    - image_bounds.origin,
+            });
+        self.platform_window.start_drag_out(paths, image)
+    }
+
     /// When using client side decorations, set this to the width of the invisible decorations (Wayland and X11)
     pub fn set_client_inset(&mut self, inset: Pixels) {
         self.client_inset = Some(inset);
@@ -1947,6 +2233,99 @@ impl Window {
         self.text_style().line_height_in_pixels(self.rem_size())
     }
 
+    /// Measure the given text as a text element with the given style would lay it out in
+    /// `available_width`, without painting it. The text wraps to the available width unless the
+    /// style disables wrapping, and is truncated by the style's `text_overflow` and `line_clamp`
+    /// as a text element would be. Text is shaped with the same text system and layout cache as
+    /// painting, so the measurement matches what's rendered, and this can be called at any point
+    /// in the frame, e.g. while laying out another element.
+    pub fn measure_text(
+        &self,
+        text: &str,
+        style: &TextStyle,
+        available_width: Option<Pixels>,
+    ) -> TextMeasurement {
+        let font_size = style.font_size.to_pixels(self.rem_size());
+        let line_height = style
+            .line_height
+            .to_pixels(font_size.into(), self.rem_size());
+        let wrap_width = available_width.filter(|_| style.white_space == WhiteSpace::Normal);
+        let max_lines = style.line_clamp.filter(|max_lines| *max_lines > 0);
+        let (truncate_width, truncation_suffix) = match (style.text_overflow.clone(), max_lines) {
+            (Some(TextOverflow::Truncate(s)), None) => (available_width, s),
+            (Some(TextOverflow::Truncate(s)), Some(_)) => (None, s),
+            (None, Some(_)) => (None, SharedString::new_static("…")),
+            (None, None) => (None, "".into()),
+        };
+
+        let text = SharedString::from(text.to_string());
+        let runs = [style.to_run(text.len())];
+        let mut line_wrapper = self.text_system.line_wrapper(style.font(), font_size);
+        let (shaped_text, runs) = if let Some(truncate_width) = truncate_width {
+            line_wrapper.truncate_line(
+                text.clone(),
+                truncate_width,
+                &truncation_suffix,
+                style.truncation_style,
+                &runs,
+            )
+        } else {
+            (text, Cow::Borrowed(&runs[..]))
+        };
+        let shape_text = |text: SharedString, runs: &[TextRun], line_clamp: Option<usize>| {
+            self.text_system
+                .shape_text(
+                    text,
+                    font_size,
+                    runs,
+                    style.direction,
+                    wrap_width,
+                    style.line_break_style(),
+                    line_clamp,
+                )
+                .log_err()
+        };
+        let mut lines = shape_text(shaped_text.clone(), &runs, None);
+        if let Some(max_lines) = max_lines
+            && let Some(shaped_lines) = lines.as_ref()
+            && visual_rows(shaped_lines).nth(max_lines).is_some()
+            && let Some(last_row) = visual_rows(shaped_lines).nth(max_lines - 1)
+        {
+            let (clamped_text, clamped_runs) = line_wrapper.truncate_clamped_text(
+                &shaped_text,
+                last_row,
+                wrap_width.or(available_width),
+                &truncation_suffix,
+                &runs,
+            );
+            lines = shape_text(clamped_text, &clamped_runs, Some(max_lines));
+        }
+        let Some(lines) = lines else {
+            return TextMeasurement::default();
+        };
+
+        let mut measurement = TextMeasurement::default();
+        for line in &lines {
+            let line_size = line.size(line_height);
+            measurement.size.height += line_size.height;
+            measurement.size.width = measurement.size.width.max(line_size.width).ceil();
+
+            let mut row_start_x = px(0.);
+            for boundary in &line.wrap_boundaries {
+                let row_end_x = line.unwrapped_layout.runs[boundary.run_ix].glyphs
+                    [boundary.glyph_ix]
+                    .position
+                    .x;
+                measurement.line_widths.push(row_end_x - row_start_x);
+                row_start_x = row_end_x;
+            }
+            measurement
+                .line_widths
+                .push(line.unwrapped_layout.width - row_start_x);
+        }
+        measurement
+    }
+
     /// Call to prevent the default action of an event. Currently only used to prevent
     /// parent elements from becoming focused on mouse down.
     pub fn prevent_default(&mut self) {
@@ -1967,11 +2346,22 @@ impl Window {
             .is_action_available(action, node_id)
     }
 
-    /// The position of the mouse relative to the window.
+    /// The position of the mouse relative to the window's content area, in logical [`Pixels`]
+    /// rather than [`ScaledPixels`]. This is kept up to date as input events arrive, so it
+    /// can be read during layout, paint, and from timers as well as in event handlers. When the
+    /// mouse is outside of the window, this is the last position reported by the platform.
     pub fn mouse_position(&self) -> Point<Pixels> {
         self.mouse_position
     }
 
+    /// The mouse buttons currently held down, as last reported by the platform. Like
+    /// [`Self::mouse_position`], this is valid outside of event handlers. Buttons pressed or
+    /// released while the mouse is outside of the window are only observed once the platform
+    /// reports the mouse again, e.g. on the next mouse move into the window.
+    pub fn pressed_mouse_buttons(&self) -> MouseButtonFlags {
+        self.pressed_mouse_buttons
+    }
+
     /// The current state of the keyboard's modifiers
     pub fn modifiers(&self) -> Modifiers {
         self.modifiers
@@ -2056,6 +2446,8 @@ impl Window {
 
         debug_assert!(self.rendered_entity_stack.is_empty());
         self.record_entities_accessed(cx);
+        cx.finish_asset_frame(&self.rendered_frame.used_assets);
+        self.evict_unused_svgs();
         self.reset_cursor_style(cx);
         self.refreshing = false;
         self.invalidator.set_phase(DrawPhase::None);
@@ -2064,6 +2456,36 @@ impl Window {
         ArenaClearNeeded
     }
 
+    /// Evicts the SVG rasterizations that weren't used to draw the last frame from the sprite
+    /// atlas, once there are more than [`MAX_RENDERED_SVGS`] of them.
+    fn evict_unused_svgs(&mut self) {
+        let rendered_count = self
+            .rendered_svgs
+            .values()
+            .map(FxHashSet::len)
+            .sum::<usize>();
+        if rendered_count <= MAX_RENDERED_SVGS {
+            return;
+        }
+
+        let used_svgs = self
+            .rendered_frame
+            .used_svgs
+            .iter()
+            .collect::<FxHashSet<_>>();
+        let sprite_atlas = &self.sprite_atlas;
+        self.rendered_svgs.retain(|_, rendered_svgs| {
+            rendered_svgs.retain(|params| {
+                let used = used_svgs.contains(params);
+                if !used {
+                    sprite_atlas.remove(&params.clone().into());
+                }
+                used
+            });
+            !rendered_svgs.is_empty()
+        });
+    }
+
     fn record_entities_accessed(&mut self, cx: &mut App) {
         let mut entities_ref = cx.entities.accessed_entities.borrow_mut();
         let mut entities = mem::take(entities_ref.deref_mut());
@@ -2148,6 +2570,24 @@ impl Window {
 
         self.mouse_hit_test = self.next_frame.hit_test(self.mouse_position);
 
+        // The drag preview and tooltip are painted between the deferred draws with a lower or
+        // equal priority and those with a higher one. The prompt is always painted on top.
+        let overlay_priority = if prompt_element.is_some() {
+            None
+        } else if active_drag_element.is_some() {
+            Some(Deferred::DRAG_PREVIEW_PRIORITY)
+        } else if tooltip_element.is_some() {
+            Some(Deferred::TOOLTIP_PRIORITY)
+        } else {
+            None
+        };
+        let overlay_split = overlay_priority.map_or(sorted_deferred_draws.len(), |priority| {
+            sorted_deferred_draws
+                .partition_point(|ix| self.next_frame.deferred_draws[*ix].priority <= priority)
+        });
+        let (deferred_draws_below, deferred_draws_above) =
+            sorted_deferred_draws.split_at(overlay_split);
+
         // Now actually paint the elements.
         self.invalidator.set_phase(DrawPhase::Paint);
         root_element.paint(self, cx);
@@ -2155,7 +2595,7 @@ impl Window {
         #[cfg(any(feature = "inspector", debug_assertions))]
         self.paint_inspector(inspector_element, cx);
 
-        self.paint_deferred_draws(&sorted_deferred_draws, cx);
+        self.paint_deferred_draws(deferred_draws_below, cx);
 
         if let Some(mut prompt_element) = prompt_element {
             prompt_element.paint(self, cx);
@@ -2165,6 +2605,8 @@ impl Window {
             tooltip_element.paint(self, cx);
         }
 
+        self.paint_deferred_draws(deferred_draws_above, cx);
+
         #[cfg(any(feature = "inspector", debug_assertions))]
         self.paint_inspector_hitbox(cx);
     }
@@ -2309,15 +2751,30 @@ impl Window {
             deferred_draws_index: self.next_frame.deferred_draws.len(),
             dispatch_tree_index: self.next_frame.dispatch_tree.len(),
             accessed_element_states_index: self.next_frame.accessed_element_states.len(),
+            used_assets_index: self.next_frame.used_assets.len(),
             line_layout_index: self.text_system.layout_index(),
         }
     }
 
     pub(crate) fn reuse_prepaint(&mut self, range: Range<PrepaintStateIndex>) {
+        // The hitboxes whose parent isn't reused are reparented to the current parent, which may
+        // have been inserted with a new ID this frame.
+        let parent = self.hitbox_parent_stack.last().copied();
+        let mut reused_ids = FxHashSet::default();
         self.next_frame.hitboxes.extend(
             self.rendered_frame.hitboxes[range.start.hitboxes_index..range.end.hitboxes_index]
                 .iter()
-                .cloned(),
+                .map(|hitbox| {
+                    let mut hitbox = hitbox.clone();
+                    if !hitbox
+                        .parent
+                        .is_some_and(|hitbox_parent| reused_ids.contains(&hitbox_parent))
+                    {
+                        hitbox.parent = parent;
+                    }
+                    reused_ids.insert(hitbox.id);
+                    hitbox
+                }),
         );
         self.next_frame.tooltip_requests.extend(
             self.rendered_frame.tooltip_requests
@@ -2331,6 +2788,10 @@ impl Window {
                 .iter()
                 .map(|(id, type_id)| (id.clone(), *type_id)),
         );
+        self.next_frame.used_assets.extend_from_slice(
+            &self.rendered_frame.used_assets
+                [range.start.used_assets_index..range.end.used_assets_index],
+        );
         self.text_system
             .reuse_layouts(range.start.line_layout_index..range.end.line_layout_index);
 
@@ -2369,6 +2830,8 @@ impl Window {
             input_handlers_index: self.next_frame.input_handlers.len(),
             cursor_styles_index: self.next_frame.cursor_styles.len(),
             accessed_element_states_index: self.next_frame.accessed_element_states.len(),
+            used_assets_index: self.next_frame.used_assets.len(),
+            used_svgs_index: self.next_frame.used_svgs.len(),
             tab_handle_index: self.next_frame.tab_stops.paint_index(),
             line_layout_index: self.text_system.layout_index(),
         }
@@ -2399,6 +2862,13 @@ impl Window {
                 .iter()
                 .map(|(id, type_id)| (id.clone(), *type_id)),
         );
+        self.next_frame.used_assets.extend_from_slice(
+            &self.rendered_frame.used_assets
+                [range.start.used_assets_index..range.end.used_assets_index],
+        );
+        self.next_frame.used_svgs.extend_from_slice(
+            &self.rendered_frame.used_svgs[range.start.used_svgs_index..range.end.used_svgs_index],
+        );
         self.next_frame.tab_stops.replay(
             &self.rendered_frame.tab_stops.insertion_history
                 [range.start.tab_handle_index..range.end.tab_handle_index],
@@ -2467,7 +2937,8 @@ impl Window {
     }
 
     /// Invoke the given function with the given content mask after intersecting it
-    /// with the current mask. This method should only be called during element drawing.
+    /// with the current mask. This method should only be called during element drawing,
+    /// which includes the prepaint and paint closures of a [`canvas`](crate::canvas).
     // This function is called in a highly recursive manner in editor
     // prepainting, make sure its inlined to reduce the stack burden
     #[inline]
@@ -2488,6 +2959,85 @@ impl Window {
         }
     }
 
+    /// Invoke the given function with everything painted within it clipped to the given bounds
+    /// with the given corners rounded, after intersecting it with the current mask and clip path.
+    /// Text, sprites and paths are clipped to the rounded corners as well as quads. Like
+    /// [`Self::with_content_mask`], this can be called from the paint closure of a
+    /// [`canvas`](crate::canvas) to keep custom drawing within a part of it.
+    pub fn with_rounded_content_mask<R>(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        corner_radii: Corners<Pixels>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.with_content_mask(Some(ContentMask { bounds }), |window| {
+            if corner_radii == Corners::default() {
+                f(window)
+            } else {
+                window.with_clip_path(bounds, HitboxShape::RoundedRect(corner_radii), f)
+            }
+        })
+    }
+
+    /// Invoke the given function with everything painted and every hitbox inserted within it
+    /// clipped to the given shape within the given bounds, in addition to the content mask.
+    /// Unlike a content mask, the clip is applied per pixel, with antialiased edges. Surfaces
+    /// aren't clipped. This method should only be called during element drawing.
+    pub fn with_clip_path<R>(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        shape: HitboxShape,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.invalidator.debug_assert_paint_or_prepaint();
+
+        if self.invalidator.phase() == DrawPhase::Paint {
+            let scale_factor = self.scale_factor();
+            let (kind, corner_radii, vertices) = match &shape {
+                HitboxShape::Rect => (ClipRegionKind::RoundedRect, Corners::default(), Vec::new()),
+                HitboxShape::RoundedRect(corner_radii) => (
+                    ClipRegionKind::RoundedRect,
+                    corner_radii
+                        .clone()
+                        .clamp_radii_for_quad_size(bounds.size)
+                        .scale(scale_factor),
+                    Vec::new(),
+                ),
+                HitboxShape::Ellipse => (ClipRegionKind::Ellipse, Corners::default(), Vec::new()),
+                HitboxShape::Polygon(vertices) => (
+                    ClipRegionKind::Polygon,
+                    Corners::default(),
+                    vertices
+                        .iter()
+                        .map(|vertex| (bounds.origin + *vertex).scale(scale_factor))
+                        .collect(),
+                ),
+            };
+            self.next_frame.scene.push_clip_region(
+                ClipRegion {
+                    bounds: bounds.scale(scale_factor),
+                    corner_radii,
+                    kind,
+                    ..Default::default()
+                },
+                &vertices,
+            );
+        }
+        self.clip_path_stack.push(Arc::new(HitboxClipPath {
+            bounds,
+            shape,
+            parent: self.clip_path_stack.last().cloned(),
+        }));
+
+        let result = f(self);
+
+        self.clip_path_stack.pop();
+        if self.invalidator.phase() == DrawPhase::Paint {
+            self.next_frame.scene.pop_clip_region();
+        }
+        result
+    }
+
     /// Updates the global element offset relative to the current offset. This is used to implement
     /// scrolling. This method should only be called during the prepaint phase of element drawing.
     pub fn with_element_offset<R>(
@@ -2538,6 +3088,49 @@ impl Window {
         result
     }
 
+    /// Paints the quads and sprites painted by the given function with the given blend mode,
+    /// unless it's `None`.
+    pub(crate) fn with_element_blend_mode<R>(
+        &mut self,
+        blend_mode: Option<BlendMode>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.invalidator.debug_assert_paint_or_prepaint();
+
+        let Some(blend_mode) = blend_mode else {
+            return f(self);
+        };
+
+        let previous_blend_mode = mem::replace(&mut self.element_blend_mode, blend_mode);
+        let result = f(self);
+        self.element_blend_mode = previous_blend_mode;
+        result
+    }
+
+    /// Prepaints the contents of a scroll container with the given function, returning the
+    /// bounds of the snap points in it if `snaps` is true. Otherwise the contents' snap points
+    /// are ignored, rather than being collected by a container further out.
+    pub(crate) fn with_snap_points<R>(
+        &mut self,
+        snaps: bool,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> (R, Vec<Bounds<Pixels>>) {
+        self.invalidator.debug_assert_prepaint();
+
+        let previous_snap_points = mem::replace(&mut self.snap_points, snaps.then(Vec::new));
+        let result = f(self);
+        let snap_points = mem::replace(&mut self.snap_points, previous_snap_points);
+        (result, snap_points.unwrap_or_default())
+    }
+
+    /// Registers the bounds of an element marked as a snap point with the scroll container
+    /// it's in.
+    pub(crate) fn insert_snap_point(&mut self, bounds: Bounds<Pixels>) {
+        if let Some(snap_points) = self.snap_points.as_mut() {
+            snap_points.push(bounds);
+        }
+    }
+
     /// Perform prepaint on child elements in a "retryable" manner, so that any side effects
     /// of prepaints can be discarded before prepainting again. This is used to support autoscroll
     /// where we need to prepaint children to detect the autoscroll bounds, then adjust the
@@ -2561,6 +3154,9 @@ impl Window {
             self.next_frame
                 .accessed_element_states
                 .truncate(index.accessed_element_states_index);
+            self.next_frame
+                .used_assets
+                .truncate(index.used_assets_index);
             self.text_system.truncate_layouts(index.line_layout_index);
         }
         result
@@ -2589,14 +3185,20 @@ impl Window {
     /// Note that the multiple calls to this method will only result in one `Asset::load` call at a
     /// time.
     pub fn use_asset<A: Asset>(&mut self, source: &A::Source, cx: &mut App) -> Option<A::Output> {
+        self.next_frame
+            .used_assets
+            .push((TypeId::of::<A>(), hash(source)));
         let (task, is_first) = cx.fetch_asset::<A>(source);
         task.clone().now_or_never().or_else(|| {
             if is_first {
                 let entity_id = self.current_view();
+                let removal = cx.asset_removal::<A>(source);
                 self.spawn(cx, {
                     let task = task.clone();
                     async move |cx| {
-                        task.await;
+                        // Let go of the load if the asset is removed, so that it's canceled. The
+                        // view is notified either way, to load the asset again if it's still used.
+                        future::select(task, removal).await;
 
                         cx.on_next_frame(move |_, cx| {
                             cx.notify(entity_id);
@@ -2610,12 +3212,64 @@ impl Window {
         })
     }
 
+    /// Asynchronously load an asset like [`Self::use_asset`], also returning the fraction of it
+    /// that's loaded, as last reported by [`Asset::load_with_progress`], or None if the load
+    /// hasn't reported any. Your view will be re-drawn at most once per frame when the load
+    /// reports progress, and once the asset has finished loading.
+    pub fn use_asset_with_progress<A: Asset>(
+        &mut self,
+        source: &A::Source,
+        cx: &mut App,
+    ) -> (Option<A::Output>, Option<f32>) {
+        self.next_frame
+            .used_assets
+            .push((TypeId::of::<A>(), hash(source)));
+        let (task, _) = cx.fetch_asset::<A>(source);
+        let progress = cx.asset_progress::<A>(source);
+        if let Some(output) = task.clone().now_or_never() {
+            return (Some(output), progress);
+        }
+
+        if let Some(mut progress_updates) = cx.take_asset_progress_updates::<A>(source) {
+            let entity_id = self.current_view();
+            let removal = cx.asset_removal::<A>(source);
+            self.spawn(cx, async move |cx| {
+                let mut loaded = future::select(task, removal);
+                loop {
+                    match future::select(loaded, progress_updates.next()).await {
+                        future::Either::Right((Some(()), still_loading)) => {
+                            loaded = still_loading;
+                            cx.on_next_frame(move |_, cx| {
+                                cx.notify(entity_id);
+                            });
+                        }
+                        future::Either::Right((None, still_loading)) => {
+                            still_loading.await;
+                            break;
+                        }
+                        future::Either::Left(_) => break,
+                    }
+                }
+
+                cx.on_next_frame(move |_, cx| {
+                    cx.notify(entity_id);
+                });
+            })
+            .detach();
+        }
+
+        (None, progress)
+    }
+
     /// Asynchronously load an asset, if the asset hasn't finished loading or doesn't exist this will return None.
     /// Your view will not be re-drawn once the asset has finished loading.
     ///
     /// Note that the multiple calls to this method will only result in one `Asset::load` call at a
     /// time.
     pub fn get_asset<A: Asset>(&mut self, source: &A::Source, cx: &mut App) -> Option<A::Output> {
+        self.next_frame
+            .used_assets
+            .push((TypeId::of::<A>(), hash(source)));
         let (task, _) = cx.fetch_asset::<A>(source);
         task.now_or_never()
     }
@@ -2826,8 +3480,28 @@ impl Window {
     /// Executes the given closure within the context of a tab group.
     #[inline]
     pub fn with_tab_group<R>(&mut self, index: Option<isize>, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.with_optionally_scoped_tab_group(index, false, f)
+    }
+
+    /// Executes the given closure within the context of a tab group that traps tab traversal while
+    /// focus is within it, wrapping at its edges.
+    #[inline]
+    pub fn with_tab_scope<R>(&mut self, index: Option<isize>, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.with_optionally_scoped_tab_group(index, true, f)
+    }
+
+    pub(crate) fn with_optionally_scoped_tab_group<R>(
+        &mut self,
+        index: Option<isize>,
+        scoped: bool,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
         if let Some(index) = index {
-            self.next_frame.tab_stops.begin_group(index);
+            if scoped {
+                self.next_frame.tab_stops.begin_scoped_group(index);
+            } else {
+                self.next_frame.tab_stops.begin_group(index);
+            }
             let result = f(self);
             self.next_frame.tab_stops.end_group();
             result
@@ -2838,7 +3512,8 @@ impl Window {
 
     /// Defers the drawing of the given element, scheduling it to be painted on top of the currently-drawn tree
     /// at a later time. The `priority` parameter determines the drawing order relative to other deferred elements,
-    /// with higher values being drawn on top.
+    /// with higher values being drawn on top. See [`Deferred::DRAG_PREVIEW_PRIORITY`] and
+    /// [`Deferred::TOOLTIP_PRIORITY`] for where drag previews and tooltips are drawn.
     ///
     /// This method should only be called as part of the prepaint phase of element drawing.
     pub fn defer_draw(
@@ -2889,6 +3564,8 @@ impl Window {
     }
 
     /// Paint one or more drop shadows into the scene for the next frame at the current z-index.
+    /// Inset shadows are painted inside of the given bounds, so they should be painted after
+    /// the background of the element.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
     pub fn paint_shadows(
@@ -2903,18 +3580,70 @@ impl Window {
         let content_mask = self.content_mask();
         let opacity = self.element_opacity();
         for shadow in shadows {
-            let shadow_bounds = (bounds + shadow.offset).dilate(shadow.spread_radius);
+            let (shadow_bounds, shadow_corner_radii) = if shadow.inset {
+                // The shadow is cast by the edges of a hole, which the spread shrinks.
+                let hole_bounds = (bounds + shadow.offset).dilate(-shadow.spread_radius);
+                let hole_corner_radii =
+                    corner_radii.map(|radius| (*radius - shadow.spread_radius).max(Pixels::ZERO));
+                (
+                    Bounds::centered_at(
+                        hole_bounds.center(),
+                        hole_bounds.size.max(&Size::default()),
+                    ),
+                    hole_corner_radii,
+                )
+            } else {
+                (
+                    (bounds + shadow.offset).dilate(shadow.spread_radius),
+                    corner_radii.clone(),
+                )
+            };
             self.next_frame.scene.insert_primitive(Shadow {
                 order: 0,
                 blur_radius: shadow.blur_radius.scale(scale_factor),
                 bounds: shadow_bounds.scale(scale_factor),
                 content_mask: content_mask.scale(scale_factor),
-                corner_radii: corner_radii.scale(scale_factor),
+                corner_radii: shadow_corner_radii.scale(scale_factor),
                 color: shadow.color.opacity(opacity),
+                clip_bounds: bounds.scale(scale_factor),
+                clip_corner_radii: corner_radii.scale(scale_factor),
+                inset: shadow.inset as u32,
+                clip_region: 0,
             });
         }
     }
 
+    /// Paint a blur of everything painted so far beneath the given bounds into the scene for
+    /// the next frame, clipped to the given corner radii. Paint the element's own background
+    /// afterwards to tint the blurred content.
+    ///
+    /// Renderers that can't read back the frame paint `fallback_color` instead.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_backdrop_blur(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        corner_radii: Corners<Pixels>,
+        blur_radius: Pixels,
+        fallback_color: Hsla,
+    ) {
+        self.invalidator.debug_assert_paint();
+
+        let scale_factor = self.scale_factor();
+        let content_mask = self.content_mask();
+        let opacity = self.element_opacity();
+        self.next_frame.scene.insert_primitive(Backdrop {
+            order: 0,
+            blur_radius: blur_radius.scale(scale_factor),
+            bounds: bounds.scale(scale_factor),
+            corner_radii: corner_radii.scale(scale_factor),
+            content_mask: content_mask.scale(scale_factor),
+            fallback_color: fallback_color.opacity(opacity),
+            clip_region: 0,
+            pad: 0,
+        });
+    }
+
     /// Paint one or more quads into the scene for the next frame at the current stacking context.
     /// Quads are colored rectangular regions with an optional background, border, and corner radius.
     /// see [`fill`], [`outline`], and [`quad`] to construct this type.
@@ -2935,10 +3664,12 @@ impl Window {
             bounds: quad.bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
             background: quad.background.opacity(opacity),
-            border_color: quad.border_color.opacity(opacity),
+            border_colors: quad.border_colors.map(|color| color.opacity(opacity)),
             corner_radii: quad.corner_radii.scale(scale_factor),
             border_widths: quad.border_widths.scale(scale_factor),
             border_style: quad.border_style,
+            clip_region: 0,
+            blend_mode: self.element_blend_mode,
         });
     }
 
@@ -2959,6 +3690,34 @@ impl Window {
             .insert_primitive(path.scale(scale_factor));
     }
 
+    /// Build the given `PathBuilder` with its own style and paint it into the scene for the next
+    /// frame at the current z-index. Unlike building the path yourself and calling
+    /// [`Window::paint_path`], curves are flattened within the style's tolerance in device pixels.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn fill_path(&mut self, path: PathBuilder, color: impl Into<Background>) -> Result<()> {
+        self.invalidator.debug_assert_paint();
+
+        let path = path.with_scale_factor(self.scale_factor()).build()?;
+        self.paint_path(path, color);
+        Ok(())
+    }
+
+    /// Stroke the outline built by the given `PathBuilder` into the scene for the next frame at
+    /// the current z-index, with the width, caps, joins and dashes of the given style. The
+    /// builder's own style and dash array are ignored.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn stroke_path(&mut self, path: PathBuilder, style: &StrokeStyle) -> Result<()> {
+        self.invalidator.debug_assert_paint();
+
+        let stroke = path
+            .with_scale_factor(self.scale_factor())
+            .build_stroke(style)?;
+        self.paint_path(stroke, style.color);
+        Ok(())
+    }
+
     /// Paint an underline into the scene for the next frame at the current z-index.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
@@ -2971,26 +3730,36 @@ impl Window {
         self.invalidator.debug_assert_paint();
 
         let scale_factor = self.scale_factor();
-        let height = if style.wavy {
-            style.thickness * 3.
+        let (bounds, thickness) = if style.line_style() == LineStyle::Wavy {
+            let bounds = Bounds {
+                origin,
+                size: size(width, style.thickness * 3.),
+            };
+            (
+                bounds.scale(scale_factor),
+                style.thickness.scale(scale_factor),
+            )
         } else {
-            style.thickness
-        };
-        let bounds = Bounds {
-            origin,
-            size: size(width, height),
+            let bounds = snap_line_to_device_pixels(
+                Bounds {
+                    origin,
+                    size: size(width, style.thickness),
+                }
+                .scale(scale_factor),
+            );
+            (bounds, bounds.size.height)
         };
         let content_mask = self.content_mask();
         let element_opacity = self.element_opacity();
 
         self.next_frame.scene.insert_primitive(Underline {
             order: 0,
-            pad: 0,
-            bounds: bounds.scale(scale_factor),
+            clip_region: 0,
+            bounds,
             content_mask: content_mask.scale(scale_factor),
             color: style.color.unwrap_or_default().opacity(element_opacity),
-            thickness: style.thickness.scale(scale_factor),
-            wavy: if style.wavy { 1 } else { 0 },
+            thickness,
+            style: style.line_style() as u32,
         });
     }
 
@@ -3006,22 +3775,24 @@ impl Window {
         self.invalidator.debug_assert_paint();
 
         let scale_factor = self.scale_factor();
-        let height = style.thickness;
-        let bounds = Bounds {
-            origin,
-            size: size(width, height),
-        };
+        let bounds = snap_line_to_device_pixels(
+            Bounds {
+                origin,
+                size: size(width, style.thickness),
+            }
+            .scale(scale_factor),
+        );
         let content_mask = self.content_mask();
         let opacity = self.element_opacity();
 
         self.next_frame.scene.insert_primitive(Underline {
             order: 0,
-            pad: 0,
-            bounds: bounds.scale(scale_factor),
+            clip_region: 0,
+            bounds,
             content_mask: content_mask.scale(scale_factor),
-            thickness: style.thickness.scale(scale_factor),
+            thickness: bounds.size.height,
             color: style.color.unwrap_or_default().opacity(opacity),
-            wavy: 0,
+            style: LineStyle::Solid as u32,
         });
     }
 
@@ -3076,12 +3847,14 @@ impl Window {
             let content_mask = self.content_mask().scale(scale_factor);
             self.next_frame.scene.insert_primitive(MonochromeSprite {
                 order: 0,
-                pad: 0,
+                clip_region: 0,
                 bounds,
                 content_mask,
                 color: color.opacity(element_opacity),
                 tile,
                 transformation: TransformationMatrix::unit(),
+                blend_mode: self.element_blend_mode,
+                pad: 0,
             });
         }
         Ok(())
@@ -3135,13 +3908,16 @@ impl Window {
 
             self.next_frame.scene.insert_primitive(PolychromeSprite {
                 order: 0,
-                pad: 0,
+                clip_region: 0,
                 grayscale: false,
                 bounds,
                 corner_radii: Default::default(),
                 content_mask,
                 tile,
+                transformation: TransformationMatrix::unit(),
                 opacity,
+                blend_mode: self.element_blend_mode,
+                pad: 0,
             });
         }
         Ok(())
@@ -3154,23 +3930,51 @@ impl Window {
         &mut self,
         bounds: Bounds<Pixels>,
         path: SharedString,
-        mut data: Option<&[u8]>,
+        data: Option<&[u8]>,
         transformation: TransformationMatrix,
         color: Hsla,
         cx: &App,
+    ) -> Result<()> {
+        self.paint_svg_with_options(
+            bounds,
+            path,
+            data,
+            transformation,
+            color.into(),
+            SvgRenderOptions::default(),
+            cx,
+        )
+    }
+
+    pub(crate) fn paint_svg_with_options(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        path: SharedString,
+        mut data: Option<&[u8]>,
+        transformation: TransformationMatrix,
+        color: Background,
+        options: SvgRenderOptions,
+        cx: &App,
     ) -> Result<()> {
         self.invalidator.debug_assert_paint();
 
         let element_opacity = self.element_opacity();
         let scale_factor = self.scale_factor();
 
+        // Rasterize at the scale the transformation will display the SVG at, so it stays sharp.
+        let transformation_scale = svg_transformation_scale(&transformation);
+        let raster_scale = SMOOTH_SVG_SCALE_FACTOR * transformation_scale;
         let bounds = bounds.scale(scale_factor);
         let params = RenderSvgParams {
             path,
-            size: bounds.size.map(|pixels| {
-                DevicePixels::from((pixels.0 * SMOOTH_SVG_SCALE_FACTOR).ceil() as i32)
-            }),
+            size: bounds
+                .size
+                .map(|pixels| DevicePixels::from((pixels.0 * raster_scale).ceil() as i32)),
+            transformation_scale_percent: (transformation_scale * 100.).round() as u32,
+            preserve_colors: false,
+            options,
         };
+        self.next_frame.used_svgs.push(params.clone());
 
         let Some(tile) =
             self.sprite_atlas
@@ -3179,6 +3983,151 @@ impl Window {
                     else {
                         return Ok(None);
                     };
+                    self.rendered_svgs
+                        .entry(params.path.clone())
+                        .or_default()
+                        .insert(params.clone());
+                    Ok(Some((size, Cow::Owned(bytes))))
+                })?
+        else {
+            return Ok(());
+        };
+        let content_mask = self.content_mask().scale(scale_factor);
+        let svg_bounds = Bounds {
+            origin: bounds.center()
+                - Point::new(
+                    ScaledPixels(tile.bounds.size.width.0 as f32 / raster_scale / 2.),
+                    ScaledPixels(tile.bounds.size.height.0 as f32 / raster_scale / 2.),
+                ),
+            size: tile
+                .bounds
+                .size
+                .map(|value| ScaledPixels(value.0 as f32 / raster_scale)),
+        };
+
+        let bounds = svg_bounds
+            .map_origin(|origin| origin.round())
+            .map_size(|size| size.ceil());
+        let color = color.opacity(element_opacity);
+        if color.tag == BackgroundTag::Solid {
+            self.next_frame.scene.insert_primitive(MonochromeSprite {
+                order: 0,
+                clip_region: 0,
+                bounds,
+                content_mask,
+                color: color.solid,
+                tile,
+                transformation,
+                blend_mode: self.element_blend_mode,
+                pad: 0,
+            });
+        } else {
+            self.next_frame.scene.insert_primitive(GradientSprite {
+                order: 0,
+                clip_region: 0,
+                bounds,
+                content_mask,
+                color,
+                tile,
+                transformation,
+                blend_mode: self.element_blend_mode,
+                pad: 0,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Paint the shadow of an SVG's shape, blurring its alpha mask rather than its bounds.
+    pub(crate) fn paint_svg_shadow(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        path: SharedString,
+        data: Option<&[u8]>,
+        transformation: TransformationMatrix,
+        shadow: &BoxShadow,
+        options: SvgRenderOptions,
+        cx: &App,
+    ) -> Result<()> {
+        let raster_scale = self.scale_factor()
+            * SMOOTH_SVG_SCALE_FACTOR
+            * svg_transformation_scale(&transformation);
+        let options = SvgRenderOptions {
+            blur_radius: Some((shadow.blur_radius.0 * raster_scale).round() as u32),
+            ..options
+        };
+        self.paint_svg_with_options(
+            bounds + shadow.offset,
+            path,
+            data,
+            transformation,
+            shadow.color.into(),
+            options,
+            cx,
+        )
+    }
+
+    /// Paint an SVG into the scene for the next frame at the current stacking context,
+    /// using the fills and strokes embedded in the SVG instead of tinting it with a single color.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_colored_svg(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        path: SharedString,
+        data: Option<&[u8]>,
+        transformation: TransformationMatrix,
+        cx: &App,
+    ) -> Result<()> {
+        self.paint_colored_svg_with_options(
+            bounds,
+            path,
+            data,
+            transformation,
+            SvgRenderOptions::default(),
+            cx,
+        )
+    }
+
+    pub(crate) fn paint_colored_svg_with_options(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        path: SharedString,
+        data: Option<&[u8]>,
+        transformation: TransformationMatrix,
+        options: SvgRenderOptions,
+        cx: &App,
+    ) -> Result<()> {
+        self.invalidator.debug_assert_paint();
+
+        let element_opacity = self.element_opacity();
+        let scale_factor = self.scale_factor();
+
+        // Rasterize at the scale the transformation will display the SVG at, so it stays sharp.
+        let transformation_scale = svg_transformation_scale(&transformation);
+        let raster_scale = SMOOTH_SVG_SCALE_FACTOR * transformation_scale;
+        let bounds = bounds.scale(scale_factor);
+        let params = RenderSvgParams {
+            path,
+            size: bounds
+                .size
+                .map(|pixels| DevicePixels::from((pixels.0 * raster_scale).ceil() as i32)),
+            transformation_scale_percent: (transformation_scale * 100.).round() as u32,
+            preserve_colors: true,
+            options,
+        };
+        self.next_frame.used_svgs.push(params.clone());
+
+        let Some(tile) =
+            self.sprite_atlas
+                .get_or_insert_with(&params.clone().into(), &mut || {
+                    let Some((size, bytes)) = cx.svg_renderer.render_colors(&params, data)? else {
+                        return Ok(None);
+                    };
+                    self.rendered_svgs
+                        .entry(params.path.clone())
+                        .or_default()
+                        .insert(params.clone());
                     Ok(Some((size, Cow::Owned(bytes))))
                 })?
         else {
@@ -3188,25 +4137,29 @@ impl Window {
         let svg_bounds = Bounds {
             origin: bounds.center()
                 - Point::new(
-                    ScaledPixels(tile.bounds.size.width.0 as f32 / SMOOTH_SVG_SCALE_FACTOR / 2.),
-                    ScaledPixels(tile.bounds.size.height.0 as f32 / SMOOTH_SVG_SCALE_FACTOR / 2.),
+                    ScaledPixels(tile.bounds.size.width.0 as f32 / raster_scale / 2.),
+                    ScaledPixels(tile.bounds.size.height.0 as f32 / raster_scale / 2.),
                 ),
             size: tile
                 .bounds
                 .size
-                .map(|value| ScaledPixels(value.0 as f32 / SMOOTH_SVG_SCALE_FACTOR)),
+                .map(|value| ScaledPixels(value.0 as f32 / raster_scale)),
         };
 
-        self.next_frame.scene.insert_primitive(MonochromeSprite {
+        self.next_frame.scene.insert_primitive(PolychromeSprite {
             order: 0,
-            pad: 0,
+            clip_region: 0,
+            grayscale: false,
             bounds: svg_bounds
                 .map_origin(|origin| origin.round())
                 .map_size(|size| size.ceil()),
             content_mask,
-            color: color.opacity(element_opacity),
+            corner_radii: Default::default(),
             tile,
             transformation,
+            opacity: element_opacity,
+            blend_mode: self.element_blend_mode,
+            pad: 0,
         });
 
         Ok(())
@@ -3223,6 +4176,24 @@ impl Window {
         data: Arc<RenderImage>,
         frame_index: usize,
         grayscale: bool,
+    ) -> Result<()> {
+        self.paint_image_region(bounds, corner_radii, data, frame_index, None, grayscale)
+    }
+
+    /// Paint a region of an image into the scene for the next frame at the current z-index,
+    /// stretching it to fill the given bounds. The region is given in the image's pixels, and the
+    /// whole image is painted if it's `None`.
+    /// This method will panic if the frame_index is not valid
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_image_region(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        corner_radii: Corners<Pixels>,
+        data: Arc<RenderImage>,
+        frame_index: usize,
+        region: Option<Bounds<DevicePixels>>,
+        grayscale: bool,
     ) -> Result<()> {
         self.invalidator.debug_assert_paint();
 
@@ -3233,7 +4204,7 @@ impl Window {
             frame_index,
         };
 
-        let tile = self
+        let mut tile = self
             .sprite_atlas
             .get_or_insert_with(&params.into(), &mut || {
                 Ok(Some((
@@ -3245,13 +4216,17 @@ impl Window {
                 )))
             })?
             .expect("Callback above only returns Some");
+        if let Some(region) = region {
+            let region = region.intersect(&Bounds::new(Point::default(), data.size(frame_index)));
+            tile.bounds = Bounds::new(tile.bounds.origin + region.origin, region.size);
+        }
         let content_mask = self.content_mask().scale(scale_factor);
         let corner_radii = corner_radii.scale(scale_factor);
         let opacity = self.element_opacity();
 
         self.next_frame.scene.insert_primitive(PolychromeSprite {
             order: 0,
-            pad: 0,
+            clip_region: 0,
             grayscale,
             bounds: bounds
                 .map_origin(|origin| origin.floor())
@@ -3259,7 +4234,10 @@ impl Window {
             content_mask,
             corner_radii,
             tile,
+            transformation: TransformationMatrix::unit(),
             opacity,
+            blend_mode: self.element_blend_mode,
+            pad: 0,
         });
         Ok(())
     }
@@ -3298,6 +4276,87 @@ impl Window {
         Ok(())
     }
 
+    /// Rasterizes the SVGs at the given paths and sizes on the background executor and stores them
+    /// in this window's sprite atlas, so that painting them doesn't stall the first frame they
+    /// appear in. SVGs that are already rasterized at a size are skipped.
+    ///
+    /// The rasterizations are shared with [`crate::Svg`] elements of the same size that are tinted
+    /// with a single color, and that have no transformation or other render options.
+    pub fn preload_svgs(
+        &mut self,
+        svgs: impl IntoIterator<Item = (SharedString, Size<Pixels>)>,
+        cx: &mut App,
+    ) -> Task<()> {
+        let scale_factor = self.scale_factor();
+        let params = svgs
+            .into_iter()
+            .map(|(path, size)| RenderSvgParams {
+                path,
+                size: size.scale(scale_factor).map(|pixels| {
+                    DevicePixels::from((pixels.0 * SMOOTH_SVG_SCALE_FACTOR).ceil() as i32)
+                }),
+                transformation_scale_percent: 100,
+                preserve_colors: false,
+                options: SvgRenderOptions::default(),
+            })
+            .filter(|params| {
+                !params.size.is_zero()
+                    && !self
+                        .rendered_svgs
+                        .get(&params.path)
+                        .is_some_and(|rendered_svgs| rendered_svgs.contains(params))
+            })
+            .collect::<Vec<_>>();
+        let svg_renderer = cx.svg_renderer();
+        let rendered = cx.background_executor().spawn(async move {
+            params
+                .into_iter()
+                .filter_map(|params| {
+                    let rendered = svg_renderer.render_alpha_mask(&params, None).log_err()??;
+                    Some((params, rendered))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        self.spawn(cx, async move |cx| {
+            let rendered = rendered.await;
+            cx.update(|window, _| {
+                for (params, (size, bytes)) in rendered {
+                    let mut bytes = Some(bytes);
+                    window
+                        .sprite_atlas
+                        .get_or_insert_with(&params.clone().into(), &mut || {
+                            Ok(bytes.take().map(|bytes| (size, Cow::Owned(bytes))))
+                        })
+                        .log_err();
+                    window
+                        .rendered_svgs
+                        .entry(params.path.clone())
+                        .or_default()
+                        .insert(params);
+                }
+            })
+            .ok();
+        })
+    }
+
+    /// Discards everything cached for the SVG at the given path, so that it is reloaded and
+    /// rasterized again the next time it's painted. This covers both the bytes loaded for
+    /// [`crate::Svg::external_path`] and the rasterizations stored in this window's sprite atlas.
+    pub fn invalidate_svg(&mut self, path: &SharedString, cx: &mut App) {
+        let rendered_svgs = self.rendered_svgs.remove(path).unwrap_or_default();
+        let parse_options = rendered_svgs
+            .iter()
+            .map(|params| params.options.parse_options.clone())
+            .chain([SvgParseOptions::default()])
+            .collect::<FxHashSet<_>>();
+        remove_svg_assets(path, parse_options, cx);
+        for params in rendered_svgs {
+            self.sprite_atlas.remove(&params.into());
+        }
+        self.refresh();
+    }
+
     /// Add a node to the layout tree for the current frame. Takes the `Style` of the element for which
     /// layout is being requested, along with the layout ids of any children. This method is called during
     /// calls to the [`Element::request_layout`] trait method and enables any element to participate in layout.
@@ -3390,6 +4449,19 @@ impl Window {
     ///
     /// This method should only be called as part of the prepaint phase of element drawing.
     pub fn insert_hitbox(&mut self, bounds: Bounds<Pixels>, behavior: HitboxBehavior) -> Hitbox {
+        self.insert_shaped_hitbox(bounds, behavior, HitboxShape::Rect)
+    }
+
+    /// Like [`Self::insert_hitbox`], but the hitbox only covers the given shape within its
+    /// bounds, so that mouse events outside the shape reach the hitboxes behind it.
+    ///
+    /// This method should only be called as part of the prepaint phase of element drawing.
+    pub fn insert_shaped_hitbox(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        behavior: HitboxBehavior,
+        shape: HitboxShape,
+    ) -> Hitbox {
         self.invalidator.debug_assert_prepaint();
 
         let content_mask = self.content_mask();
@@ -3400,11 +4472,57 @@ impl Window {
             bounds,
             content_mask,
             behavior,
+            shape,
+            mask: None,
+            parent: self.hitbox_parent_stack.last().copied(),
+            clip_path: self.clip_path_stack.last().cloned(),
         };
         self.next_frame.hitboxes.push(hitbox.clone());
         hitbox
     }
 
+    /// Invoke the given function with the given hitbox as the parent of the hitboxes inserted
+    /// within it. A hitbox in front of another only occludes its hover if it isn't one of its
+    /// descendants, see [`Hitbox::is_hovered_unoccluded`]. Passing `None` leaves the parent
+    /// unchanged.
+    ///
+    /// This method should only be called as part of the prepaint phase of element drawing.
+    pub fn with_hitbox_parent<R>(
+        &mut self,
+        parent: Option<HitboxId>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.invalidator.debug_assert_prepaint();
+
+        let Some(parent) = parent else {
+            return f(self);
+        };
+        self.hitbox_parent_stack.push(parent);
+        let result = f(self);
+        self.hitbox_parent_stack.pop();
+        result
+    }
+
+    /// Restricts a hitbox inserted this frame to the points where the given mask is opaque, so
+    /// that mouse events over its transparent parts reach the hitboxes behind it.
+    ///
+    /// This method should only be called as part of the prepaint phase of element drawing.
+    pub(crate) fn set_hitbox_mask(&mut self, hitbox: &mut Hitbox, mask: HitboxMask) {
+        self.invalidator.debug_assert_prepaint();
+
+        let mask = Arc::new(mask);
+        if let Some(inserted) = self
+            .next_frame
+            .hitboxes
+            .iter_mut()
+            .rev()
+            .find(|inserted| inserted.id == hitbox.id)
+        {
+            inserted.mask = Some(mask.clone());
+        }
+        hitbox.mask = Some(mask);
+    }
+
     /// Set a hitbox which will act as a control area of the platform window.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
@@ -3689,20 +4807,24 @@ impl Window {
             PlatformInput::MouseMove(mouse_move) => {
                 self.mouse_position = mouse_move.position;
                 self.modifiers = mouse_move.modifiers;
+                self.sync_pressed_mouse_buttons(mouse_move.pressed_button);
                 PlatformInput::MouseMove(mouse_move)
             }
             PlatformInput::MouseDown(mouse_down) => {
                 self.mouse_position = mouse_down.position;
                 self.modifiers = mouse_down.modifiers;
+                self.pressed_mouse_buttons.insert(mouse_down.button);
                 PlatformInput::MouseDown(mouse_down)
             }
             PlatformInput::MouseUp(mouse_up) => {
                 self.mouse_position = mouse_up.position;
                 self.modifiers = mouse_up.modifiers;
+                self.pressed_mouse_buttons.remove(mouse_up.button);
                 PlatformInput::MouseUp(mouse_up)
             }
             PlatformInput::MouseExited(mouse_exited) => {
                 self.modifiers = mouse_exited.modifiers;
+                self.sync_pressed_mouse_buttons(mouse_exited.pressed_button);
                 PlatformInput::MouseExited(mouse_exited)
             }
             PlatformInput::ModifiersChanged(modifiers_changed) => {
@@ -3713,8 +4835,19 @@ impl Window {
             PlatformInput::ScrollWheel(scroll_wheel) => {
                 self.mouse_position = scroll_wheel.position;
                 self.modifiers = scroll_wheel.modifiers;
+                self.unconsumed_scroll_delta = None;
                 PlatformInput::ScrollWheel(scroll_wheel)
             }
+            PlatformInput::Pinch(pinch) => {
+                self.mouse_position = pinch.position;
+                self.modifiers = pinch.modifiers;
+                PlatformInput::Pinch(pinch)
+            }
+            PlatformInput::RotateGesture(rotate) => {
+                self.mouse_position = rotate.position;
+                self.modifiers = rotate.modifiers;
+                PlatformInput::RotateGesture(rotate)
+            }
             // Translate dragging and dropping of external files from the operating system
             // to internal drag and drop events.
             PlatformInput::FileDrop(file_drop) => match file_drop {
@@ -3772,6 +4905,15 @@ impl Window {
         }
     }
 
+    /// Reconcile the tracked mouse buttons with the button reported by a mouse move or exit
+    /// event, which covers presses and releases that happened outside of the window.
+    fn sync_pressed_mouse_buttons(&mut self, pressed_button: Option<MouseButton>) {
+        match pressed_button {
+            Some(button) => self.pressed_mouse_buttons.insert(button),
+            None => self.pressed_mouse_buttons = MouseButtonFlags::default(),
+        }
+    }
+
     fn dispatch_mouse_event(&mut self, event: &dyn Any, cx: &mut App) {
         let hit_test = self.rendered_frame.hit_test(self.mouse_position());
         if hit_test != self.mouse_hit_test {
@@ -5160,8 +6302,8 @@ pub struct PaintQuad {
     pub background: Background,
     /// The widths of the quad's borders.
     pub border_widths: Edges<Pixels>,
-    /// The color of the quad's borders.
-    pub border_color: Hsla,
+    /// The colors of the quad's borders.
+    pub border_colors: Edges<Hsla>,
     /// The style of the quad's borders.
     pub border_style: BorderStyle,
 }
@@ -5183,10 +6325,19 @@ impl PaintQuad {
         }
     }
 
-    /// Sets the border color of the quad.
+    /// Sets the color of all of the quad's borders.
     pub fn border_color(self, border_color: impl Into<Hsla>) -> Self {
         PaintQuad {
-            border_color: border_color.into(),
+            border_colors: Edges::all(border_color.into()),
+            ..self
+        }
+    }
+
+    /// Sets the colors of each of the quad's borders. Where two borders of different colors
+    /// meet at a corner, they are split along the diagonal of the corner.
+    pub fn border_colors(self, border_colors: Edges<Hsla>) -> Self {
+        PaintQuad {
+            border_colors,
             ..self
         }
     }
@@ -5200,6 +6351,17 @@ impl PaintQuad {
     }
 }
 
+/// Rounds a straight line's top edge and thickness to whole device pixels, so that it isn't
+/// smeared across two rows of pixels at fractional scale factors. Lines thinner than a device
+/// pixel are drawn one device pixel thick.
+fn snap_line_to_device_pixels(mut bounds: Bounds<ScaledPixels>) -> Bounds<ScaledPixels> {
+    bounds.origin.y = bounds.origin.y.round();
+    if bounds.size.height.0 > 0. {
+        bounds.size.height = ScaledPixels(bounds.size.height.0.round().max(1.));
+    }
+    bounds
+}
+
 /// Creates a quad with the given parameters.
 pub fn quad(
     bounds: Bounds<Pixels>,
@@ -5214,7 +6376,7 @@ pub fn quad(
         corner_radii: corner_radii.into(),
         background: background.into(),
         border_widths: border_widths.into(),
-        border_color: border_color.into(),
+        border_colors: Edges::all(border_color.into()),
         border_style,
     }
 }
@@ -5226,7 +6388,7 @@ pub fn fill(bounds: impl Into<Bounds<Pixels>>, background: impl Into<Background>
         corner_radii: (0.).into(),
         background: background.into(),
         border_widths: (0.).into(),
-        border_color: transparent_black(),
+        border_colors: Edges::all(transparent_black()),
         border_style: BorderStyle::default(),
     }
 }
@@ -5242,7 +6404,7 @@ pub fn outline(
         corner_radii: (0.).into(),
         background: transparent_black().into(),
         border_widths: (1.).into(),
-        border_color: border_color.into(),
+        border_colors: Edges::all(border_color.into()),
         border_style,
     }
 }
@@ -371,13 +371,58 @@ pub fn border_style_methods(input: TokenStream) -> TokenStream {
     }
 
     let output = quote! {
-        /// Sets the border color of the element.
+        /// Sets the border color of the element, replacing any previously set per-side colors.
         #visibility fn border_color<C>(mut self, border_color: C) -> Self
         where
             C: Into<gpui::Hsla>,
             Self: Sized,
         {
-            self.style().border_color = Some(border_color.into());
+            let style = self.style();
+            style.border_color = Some(border_color.into());
+            style.border_colors.top = Some(None);
+            style.border_colors.right = Some(None);
+            style.border_colors.bottom = Some(None);
+            style.border_colors.left = Some(None);
+            self
+        }
+
+        /// Sets the color of the top border of the element, taking precedence over `border_color`.
+        #visibility fn border_t_color<C>(mut self, border_color: C) -> Self
+        where
+            C: Into<gpui::Hsla>,
+            Self: Sized,
+        {
+            self.style().border_colors.top = Some(Some(border_color.into()));
+            self
+        }
+
+        /// Sets the color of the right border of the element, taking precedence over `border_color`.
+        #visibility fn border_r_color<C>(mut self, border_color: C) -> Self
+        where
+            C: Into<gpui::Hsla>,
+            Self: Sized,
+        {
+            self.style().border_colors.right = Some(Some(border_color.into()));
+            self
+        }
+
+        /// Sets the color of the bottom border of the element, taking precedence over `border_color`.
+        #visibility fn border_b_color<C>(mut self, border_color: C) -> Self
+        where
+            C: Into<gpui::Hsla>,
+            Self: Sized,
+        {
+            self.style().border_colors.bottom = Some(Some(border_color.into()));
+            self
+        }
+
+        /// Sets the color of the left border of the element, taking precedence over `border_color`.
+        #visibility fn border_l_color<C>(mut self, border_color: C) -> Self
+        where
+            C: Into<gpui::Hsla>,
+            Self: Sized,
+        {
+            self.style().border_colors.left = Some(Some(border_color.into()));
             self
         }
 
@@ -416,6 +461,7 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                 offset: point(px(0.), px(1.)),
                 blur_radius: px(0.),
                 spread_radius: px(0.),
+                inset: false,
             }]);
             self
         }
@@ -431,6 +477,7 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                 offset: point(px(0.), px(1.)),
                 blur_radius: px(2.),
                 spread_radius: px(0.),
+                inset: false,
             }]);
             self
         }
@@ -447,12 +494,14 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                     offset: point(px(0.), px(1.)),
                     blur_radius: px(3.),
                     spread_radius: px(0.),
+                    inset: false,
                 },
                 BoxShadow {
                     color: hsla(0., 0., 0., 0.1),
                     offset: point(px(0.), px(1.)),
                     blur_radius: px(2.),
                     spread_radius: px(-1.),
+                    inset: false,
                 }
             ]);
             self
@@ -470,12 +519,14 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                     offset: point(px(0.), px(4.)),
                     blur_radius: px(6.),
                     spread_radius: px(-1.),
+                    inset: false,
                 },
                 BoxShadow {
                     color: hsla(0., 0., 0., 0.1),
                     offset: point(px(0.), px(2.)),
                     blur_radius: px(4.),
                     spread_radius: px(-2.),
+                    inset: false,
                 }
             ]);
             self
@@ -493,12 +544,14 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                     offset: point(px(0.), px(10.)),
                     blur_radius: px(15.),
                     spread_radius: px(-3.),
+                    inset: false,
                 },
                 BoxShadow {
                     color: hsla(0., 0., 0., 0.1),
                     offset: point(px(0.), px(4.)),
                     blur_radius: px(6.),
                     spread_radius: px(-4.),
+                    inset: false,
                 }
             ]);
             self
@@ -516,12 +569,14 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                     offset: point(px(0.), px(20.)),
                     blur_radius: px(25.),
                     spread_radius: px(-5.),
+                    inset: false,
                 },
                 BoxShadow {
                     color: hsla(0., 0., 0., 0.1),
                     offset: point(px(0.), px(8.)),
                     blur_radius: px(10.),
                     spread_radius: px(-6.),
+                    inset: false,
                 }
             ]);
             self
@@ -538,9 +593,61 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                 offset: point(px(0.), px(25.)),
                 blur_radius: px(50.),
                 spread_radius: px(-12.),
+                inset: false,
             }]);
             self
         }
+
+        /// Sets the inset box shadow of the element, keeping its outset box shadows.
+        /// [Docs](https://tailwindcss.com/docs/box-shadow#adding-an-inset-shadow)
+        #visibility fn inset_shadow_2xs(mut self) -> Self {
+            use gpui::{BoxShadow, hsla, point, px};
+
+            let box_shadow = self.style().box_shadow.get_or_insert_default();
+            box_shadow.retain(|shadow| !shadow.inset);
+            box_shadow.push(BoxShadow {
+                color: hsla(0., 0., 0., 0.05),
+                offset: point(px(0.), px(1.)),
+                blur_radius: px(0.),
+                spread_radius: px(0.),
+                inset: true,
+            });
+            self
+        }
+
+        /// Sets the inset box shadow of the element, keeping its outset box shadows.
+        /// [Docs](https://tailwindcss.com/docs/box-shadow#adding-an-inset-shadow)
+        #visibility fn inset_shadow_xs(mut self) -> Self {
+            use gpui::{BoxShadow, hsla, point, px};
+
+            let box_shadow = self.style().box_shadow.get_or_insert_default();
+            box_shadow.retain(|shadow| !shadow.inset);
+            box_shadow.push(BoxShadow {
+                color: hsla(0., 0., 0., 0.05),
+                offset: point(px(0.), px(1.)),
+                blur_radius: px(1.),
+                spread_radius: px(0.),
+                inset: true,
+            });
+            self
+        }
+
+        /// Sets the inset box shadow of the element, keeping its outset box shadows.
+        /// [Docs](https://tailwindcss.com/docs/box-shadow#adding-an-inset-shadow)
+        #visibility fn inset_shadow_sm(mut self) -> Self {
+            use gpui::{BoxShadow, hsla, point, px};
+
+            let box_shadow = self.style().box_shadow.get_or_insert_default();
+            box_shadow.retain(|shadow| !shadow.inset);
+            box_shadow.push(BoxShadow {
+                color: hsla(0., 0., 0., 0.05),
+                offset: point(px(0.), px(2.)),
+                blur_radius: px(4.),
+                spread_radius: px(0.),
+                inset: true,
+            });
+            self
+        }
     };
 
     output.into()
@@ -951,19 +1058,19 @@ fn box_prefixes() -> Vec<BoxStylePrefix> {
             prefix: "gap",
             auto_allowed: false,
             fields: vec![quote! { gap.width }, quote! { gap.height }],
-            doc_string_prefix: "Sets the gap between rows and columns in flex layouts. [Docs](https://tailwindcss.com/docs/gap)",
+            doc_string_prefix: "Sets the gap between rows and columns in flex and grid layouts. [Docs](https://tailwindcss.com/docs/gap)",
         },
         BoxStylePrefix {
             prefix: "gap_x",
             auto_allowed: false,
             fields: vec![quote! { gap.width }],
-            doc_string_prefix: "Sets the gap between columns in flex layouts. [Docs](https://tailwindcss.com/docs/gap#changing-row-and-column-gaps-independently)",
+            doc_string_prefix: "Sets the gap between columns in flex and grid layouts. [Docs](https://tailwindcss.com/docs/gap#changing-row-and-column-gaps-independently)",
         },
         BoxStylePrefix {
             prefix: "gap_y",
             auto_allowed: false,
             fields: vec![quote! { gap.height }],
-            doc_string_prefix: "Sets the gap between rows in flex layouts. [Docs](https://tailwindcss.com/docs/gap#changing-row-and-column-gaps-independently)",
+            doc_string_prefix: "Sets the gap between rows in flex and grid layouts. [Docs](https://tailwindcss.com/docs/gap#changing-row-and-column-gaps-independently)",
         },
     ]
 }
@@ -1,5 +1,8 @@
 use anyhow::{Context as _, anyhow};
-use gpui::{App, DivInspectorState, Inspector, InspectorElementId, IntoElement, Window};
+use gpui::{
+    App, DivInspectorState, Inspector, InspectorElementId, IntoElement, SvgColorSource,
+    SvgInspectorState, Window,
+};
 use std::{cell::OnceCell, path::Path, sync::Arc};
 use title_bar::platform_title_bar::PlatformTitleBar;
 use ui::{Label, Tooltip, prelude::*};
@@ -46,6 +49,24 @@ pub fn init(app_state: Arc<AppState>, cx: &mut App) {
         })
     });
 
+    cx.register_inspector_element(|_id, state: &SvgInspectorState, _window, cx| {
+        let source = match state.color_source {
+            SvgColorSource::Fill => "fill",
+            SvgColorSource::Color => "color",
+            SvgColorSource::TextColor => "text color",
+            SvgColorSource::Preserved => "preserved colors",
+            SvgColorSource::None => "none, not painted",
+        };
+        let color = state
+            .color
+            .map(|color| format!(" ({color:?})"))
+            .unwrap_or_default();
+        div()
+            .text_ui(cx)
+            .child(format!("SVG color: {source}{color}"))
+            .into_any_element()
+    });
+
     cx.set_inspector_renderer(Box::new(render_inspector));
 }
 
@@ -100,6 +100,7 @@ impl Render for MarkdownExample {
                     thickness: px(1.),
                     color: Some(Color::Accent.color(cx)),
                     wavy: false,
+                    ..Default::default()
                 }),
                 ..Default::default()
             },
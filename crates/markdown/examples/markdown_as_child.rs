@@ -84,6 +84,7 @@ impl Render for HelloWorld {
                     thickness: px(1.),
                     color: Some(Color::Accent.color(cx)),
                     wavy: false,
+                    ..Default::default()
                 }),
                 ..Default::default()
             },
@@ -65,6 +65,7 @@ impl AlacMouseButton {
         let is_positive = match e.delta {
             gpui::ScrollDelta::Pixels(pixels) => pixels.y > px(0.),
             gpui::ScrollDelta::Lines(lines) => lines.y > 0.,
+            gpui::ScrollDelta::Page(pages) => pages.y > 0.,
         };
 
         if is_positive {
@@ -575,6 +575,7 @@ impl TerminalElement {
             color: Some(fg),
             thickness: Pixels::from(1.0),
             wavy: flags.contains(Flags::UNDERCURL),
+            ..Default::default()
         });
 
         let strikethrough = flags
@@ -936,6 +937,7 @@ impl Element for TerminalElement {
                         thickness: px(1.0),
                         color: Some(theme.colors().link_text_hover),
                         wavy: false,
+                        ..Default::default()
                     }),
                     strikethrough: None,
                     fade_out: None,
@@ -1312,6 +1314,7 @@ impl Element for TerminalElement {
                                     color: Some(ime_style.color),
                                     thickness: px(1.0),
                                     wavy: false,
+                                    ..Default::default()
                                 });
 
                                 let shaped_line = window.text_system().shape_line(
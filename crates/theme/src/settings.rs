@@ -698,6 +698,7 @@ impl settings::Settings for ThemeSettings {
             ui_font: Font {
                 family: content.ui_font_family.as_ref().unwrap().0.clone().into(),
                 features: content.ui_font_features.clone().unwrap(),
+                variations: Default::default(),
                 fallbacks: font_fallbacks_from_settings(content.ui_font_fallbacks.clone()),
                 weight: clamp_font_weight(content.ui_font_weight.unwrap().0),
                 style: Default::default(),
@@ -711,6 +712,7 @@ impl settings::Settings for ThemeSettings {
                     .clone()
                     .into(),
                 features: content.buffer_font_features.clone().unwrap(),
+                variations: Default::default(),
                 fallbacks: font_fallbacks_from_settings(content.buffer_font_fallbacks.clone()),
                 weight: clamp_font_weight(content.buffer_font_weight.unwrap().0),
                 style: FontStyle::default(),
@@ -1,7 +1,7 @@
 use documented::Documented;
 use gpui::{
     AnyElement, AnyView, ClickEvent, CursorStyle, DefiniteLength, FocusHandle, Hsla, MouseButton,
-    MouseClickEvent, MouseDownEvent, MouseUpEvent, Rems, StyleRefinement, relative,
+    MouseClickEvent, MouseDownEvent, MouseUpEvent, PointerKind, Rems, StyleRefinement, relative,
     transparent_black,
 };
 use smallvec::SmallVec;
@@ -719,6 +719,7 @@ impl RenderOnce for ButtonLike {
                                     modifiers: event.modifiers,
                                     click_count: 1,
                                     first_mouse: false,
+                                    pointer_kind: PointerKind::Mouse,
                                 },
                                 up: MouseUpEvent {
                                     button: MouseButton::Right,
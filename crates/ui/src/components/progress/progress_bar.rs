@@ -76,6 +76,7 @@ impl RenderOnce for ProgressBar {
                 offset: point(px(0.), px(1.)),
                 blur_radius: px(0.),
                 spread_radius: px(0.),
+                inset: false,
             }])
             .child(
                 div()